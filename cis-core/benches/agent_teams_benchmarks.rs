@@ -133,6 +133,7 @@ fn bench_agent_pool_acquire_release(c: &mut Criterion) {
                         health_check_interval: Duration::from_secs(30),
                         auto_cleanup: false,
                         idle_timeout: Duration::from_secs(600),
+                        ..Default::default()
                     });
 
                     pool.register_runtime(Arc::new(BenchRuntime::new(RuntimeType::Claude)))
@@ -207,6 +208,7 @@ fn bench_dag_execution(c: &mut Criterion) {
                         health_check_interval: Duration::from_secs(30),
                         auto_cleanup: false,
                         idle_timeout: Duration::from_secs(600),
+                        ..Default::default()
                     });
                     pool.register_runtime(Arc::new(BenchRuntime::new(RuntimeType::Claude)))
                         .await
@@ -258,6 +260,7 @@ fn bench_parallel_tasks(c: &mut Criterion) {
                         health_check_interval: Duration::from_secs(30),
                         auto_cleanup: false,
                         idle_timeout: Duration::from_secs(600),
+                        ..Default::default()
                     });
                     pool.register_runtime(Arc::new(BenchRuntime::new(RuntimeType::Claude)))
                         .await
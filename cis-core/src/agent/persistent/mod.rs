@@ -23,7 +23,10 @@ pub mod pool;
 
 pub use claude::{ClaudeAgentStats, ClaudePersistentAgent, ClaudeRuntime};
 pub use opencode::{OpenCodePersistentAgent, OpenCodeRuntime};
-pub use pool::{AgentAcquireConfig, AgentHandle, AgentPool, PoolConfig};
+pub use pool::{
+    AgentAcquireConfig, AgentHandle, AgentJobHandle, AgentPool, AgentPoolStats,
+    LoadBalancingStrategy, PoolConfig,
+};
 
 /// Persistent Agent 统一接口
 ///
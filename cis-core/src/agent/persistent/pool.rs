@@ -7,13 +7,15 @@
 //! - Agent 复用和池化
 //! - 自动健康检查和清理
 //! - 优雅关闭管理
+//! - 任务负载均衡（`submit`）和容量排队
 
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::Duration;
 
 use chrono::Utc;
-use tokio::sync::RwLock;
+use rand::Rng;
+use tokio::sync::{oneshot, watch, RwLock};
 use tokio::task::JoinHandle;
 use tracing::{debug, info, warn};
 
@@ -22,6 +24,23 @@ use crate::agent::persistent::{
     TaskRequest, TaskResult,
 };
 use crate::error::{CisError, Result};
+use crate::event_bus::EventBusRef;
+use crate::events::{EventWrapper, SystemEvent};
+
+type AgentMap = Arc<RwLock<HashMap<String, Arc<dyn PersistentAgent>>>>;
+type AgentInfoMap = Arc<RwLock<HashMap<String, AgentInfo>>>;
+
+/// 任务分配的负载均衡策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadBalancingStrategy {
+    /// 按 Agent 注册顺序轮询
+    #[default]
+    RoundRobin,
+    /// 选择活跃任务最少的 Agent
+    LeastLoaded,
+    /// 在有空位的 Agent 中随机选择
+    Random,
+}
 
 /// Pool 配置
 #[derive(Debug, Clone)]
@@ -36,6 +55,8 @@ pub struct PoolConfig {
     pub auto_cleanup: bool,
     /// 空闲超时时间
     pub idle_timeout: Duration,
+    /// `submit` 使用的负载均衡策略
+    pub strategy: LoadBalancingStrategy,
 }
 
 impl Default for PoolConfig {
@@ -46,6 +67,7 @@ impl Default for PoolConfig {
             health_check_interval: Duration::from_secs(30),
             auto_cleanup: true,
             idle_timeout: Duration::from_secs(600),
+            strategy: LoadBalancingStrategy::default(),
         }
     }
 }
@@ -100,7 +122,7 @@ impl AgentAcquireConfig {
 #[derive(Clone)]
 pub struct AgentHandle {
     agent_id: String,
-    agents: Arc<RwLock<HashMap<String, Box<dyn PersistentAgent>>>>,
+    agents: Arc<RwLock<HashMap<String, Arc<dyn PersistentAgent>>>>,
     agent_info: Arc<RwLock<HashMap<String, AgentInfo>>>,
 }
 
@@ -108,7 +130,7 @@ impl AgentHandle {
     /// 创建新的 Agent 句柄
     fn new(
         agent_id: String,
-        agents: Arc<RwLock<HashMap<String, Box<dyn PersistentAgent>>>>,
+        agents: Arc<RwLock<HashMap<String, Arc<dyn PersistentAgent>>>>,
         agent_info: Arc<RwLock<HashMap<String, AgentInfo>>>,
     ) -> Self {
         Self {
@@ -123,12 +145,19 @@ impl AgentHandle {
         &self.agent_id
     }
 
+    /// 从池中取出 Agent，不在执行期间持有池锁
+    async fn agent(&self) -> Result<Arc<dyn PersistentAgent>> {
+        self.agents
+            .read()
+            .await
+            .get(&self.agent_id)
+            .cloned()
+            .ok_or_else(|| CisError::not_found(format!("Agent {} not found in pool", self.agent_id)))
+    }
+
     /// 执行任务
     pub async fn execute(&self, task: TaskRequest) -> Result<TaskResult> {
-        let agents = self.agents.read().await;
-        let agent = agents
-            .get(&self.agent_id)
-            .ok_or_else(|| CisError::not_found(format!("Agent {} not found in pool", self.agent_id)))?;
+        let agent = self.agent().await?;
 
         // 更新 Agent 信息为 Busy 状态
         {
@@ -159,21 +188,13 @@ impl AgentHandle {
 
     /// 获取 Agent 状态
     pub async fn status(&self) -> Result<AgentStatus> {
-        let agents = self.agents.read().await;
-        let agent = agents
-            .get(&self.agent_id)
-            .ok_or_else(|| CisError::not_found(format!("Agent {} not found in pool", self.agent_id)))?;
-
+        let agent = self.agent().await?;
         Ok(agent.status().await)
     }
 
     /// Attach 到 Agent（进入交互式模式）
     pub async fn attach(&self) -> Result<()> {
-        let agents = self.agents.read().await;
-        let agent = agents
-            .get(&self.agent_id)
-            .ok_or_else(|| CisError::not_found(format!("Agent {} not found in pool", self.agent_id)))?;
-
+        let agent = self.agent().await?;
         agent.attach().await
     }
 }
@@ -186,6 +207,244 @@ impl std::fmt::Debug for AgentHandle {
     }
 }
 
+/// Pool 任务统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AgentPoolStats {
+    /// 可接收任务的 Agent 数
+    pub total_agents: usize,
+    /// 正在执行的任务数
+    pub active_jobs: usize,
+    /// 等待空位的任务数
+    pub queued_jobs: usize,
+    /// 已完成的任务数（含失败）
+    pub completed_jobs: u64,
+}
+
+/// `submit` 返回的任务句柄
+#[derive(Debug)]
+pub struct AgentJobHandle {
+    task_id: String,
+    rx: oneshot::Receiver<Result<TaskResult>>,
+}
+
+impl AgentJobHandle {
+    /// 获取任务 ID
+    pub fn task_id(&self) -> &str {
+        &self.task_id
+    }
+
+    /// 等待任务完成
+    pub async fn wait(self) -> Result<TaskResult> {
+        self.rx.await.map_err(|_| {
+            CisError::execution(format!("Job {} dropped before completion", self.task_id))
+        })?
+    }
+}
+
+/// 等待分配的任务
+struct QueuedJob {
+    task: TaskRequest,
+    tx: oneshot::Sender<Result<TaskResult>>,
+}
+
+/// 单个 Agent 的容量
+struct AgentSlot {
+    capacity: usize,
+    active: usize,
+}
+
+impl AgentSlot {
+    fn has_room(&self) -> bool {
+        self.active < self.capacity
+    }
+}
+
+#[derive(Default)]
+struct SchedulerState {
+    slots: HashMap<String, AgentSlot>,
+    /// 注册顺序（轮询用）
+    order: Vec<String>,
+    next: usize,
+    queue: VecDeque<QueuedJob>,
+    active_jobs: usize,
+    completed_jobs: u64,
+}
+
+/// 任务调度器
+///
+/// 状态只在同步临界区内修改，任务完成后在执行任务的 tokio task 中
+/// 直接调度队列中的下一个任务。
+struct JobScheduler {
+    strategy: LoadBalancingStrategy,
+    agents: AgentMap,
+    agent_info: AgentInfoMap,
+    state: Mutex<SchedulerState>,
+    /// 未完成任务数（活跃 + 排队），供 `drain` 等待
+    outstanding: watch::Sender<usize>,
+}
+
+impl JobScheduler {
+    fn new(strategy: LoadBalancingStrategy, agents: AgentMap, agent_info: AgentInfoMap) -> Self {
+        Self {
+            strategy,
+            agents,
+            agent_info,
+            state: Mutex::new(SchedulerState::default()),
+            outstanding: watch::channel(0).0,
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, SchedulerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn register(self: &Arc<Self>, agent_id: &str, capacity: usize) {
+        {
+            let mut state = self.state();
+            if state.slots.contains_key(agent_id) {
+                return;
+            }
+            state.slots.insert(
+                agent_id.to_string(),
+                AgentSlot {
+                    capacity: capacity.max(1),
+                    active: 0,
+                },
+            );
+            state.order.push(agent_id.to_string());
+        }
+        // 新 Agent 可以接收排队中的任务
+        self.dispatch();
+    }
+
+    fn unregister(&self, agent_id: &str) {
+        let mut state = self.state();
+        state.slots.remove(agent_id);
+        state.order.retain(|id| id != agent_id);
+    }
+
+    fn stats(&self) -> AgentPoolStats {
+        let state = self.state();
+        AgentPoolStats {
+            total_agents: state.slots.len(),
+            active_jobs: state.active_jobs,
+            queued_jobs: state.queue.len(),
+            completed_jobs: state.completed_jobs,
+        }
+    }
+
+    /// 入队并尝试分配，池耗尽时返回系统事件
+    fn submit(self: &Arc<Self>, task: TaskRequest) -> Result<(AgentJobHandle, Option<SystemEvent>)> {
+        let (tx, rx) = oneshot::channel();
+        let task_id = task.task_id.clone();
+
+        let exhausted = {
+            let mut state = self.state();
+            if state.slots.is_empty() {
+                return Err(CisError::execution(format!(
+                    "No agents in pool to run task {}",
+                    task_id
+                )));
+            }
+
+            state.queue.push_back(QueuedJob { task, tx });
+            // 只在队列由空变为非空时报告一次
+            (state.queue.len() == 1 && !state.slots.values().any(AgentSlot::has_room)).then(|| {
+                warn!(
+                    "Agent pool exhausted: {} agents at capacity, queueing task {}",
+                    state.slots.len(),
+                    task_id
+                );
+                SystemEvent::warning(
+                    "agent_pool",
+                    format!("Agent pool exhausted, task {} queued", task_id),
+                    "agent_pool",
+                )
+                .with_details(serde_json::json!({
+                    "task_id": task_id,
+                    "total_agents": state.slots.len(),
+                    "active_jobs": state.active_jobs,
+                }))
+            })
+        };
+
+        self.dispatch();
+
+        Ok((AgentJobHandle { task_id, rx }, exhausted))
+    }
+
+    /// 把队列中的任务分配给有空位的 Agent
+    fn dispatch(self: &Arc<Self>) {
+        let mut state = self.state();
+        while !state.queue.is_empty() {
+            let Some(agent_id) = self.pick(&mut state) else {
+                break;
+            };
+            let job = state.queue.pop_front().expect("queue is not empty");
+            if let Some(slot) = state.slots.get_mut(&agent_id) {
+                slot.active += 1;
+            }
+            state.active_jobs += 1;
+
+            debug!("Dispatching task {} to agent {}", job.task.task_id, agent_id);
+            let scheduler = Arc::clone(self);
+            tokio::spawn(async move {
+                let handle = AgentHandle::new(
+                    agent_id.clone(),
+                    scheduler.agents.clone(),
+                    scheduler.agent_info.clone(),
+                );
+                let result = handle.execute(job.task).await;
+                let _ = job.tx.send(result);
+                scheduler.finish(&agent_id);
+            });
+        }
+        self.outstanding
+            .send_replace(state.active_jobs + state.queue.len());
+    }
+
+    fn finish(self: &Arc<Self>, agent_id: &str) {
+        {
+            let mut state = self.state();
+            if let Some(slot) = state.slots.get_mut(agent_id) {
+                slot.active = slot.active.saturating_sub(1);
+            }
+            state.active_jobs = state.active_jobs.saturating_sub(1);
+            state.completed_jobs += 1;
+        }
+        self.dispatch();
+    }
+
+    /// 按策略选择有空位的 Agent
+    fn pick(&self, state: &mut SchedulerState) -> Option<String> {
+        let available: Vec<usize> = (0..state.order.len())
+            .filter(|&i| state.slots[&state.order[i]].has_room())
+            .collect();
+        if available.is_empty() {
+            return None;
+        }
+
+        let index = match self.strategy {
+            LoadBalancingStrategy::RoundRobin => {
+                let len = state.order.len();
+                let index = (0..len)
+                    .map(|offset| (state.next + offset) % len)
+                    .find(|i| available.contains(i))?;
+                state.next = (index + 1) % len;
+                index
+            }
+            LoadBalancingStrategy::LeastLoaded => *available
+                .iter()
+                .min_by_key(|&&i| state.slots[&state.order[i]].active)?,
+            LoadBalancingStrategy::Random => {
+                available[rand::thread_rng().gen_range(0..available.len())]
+            }
+        };
+
+        Some(state.order[index].clone())
+    }
+}
+
 /// Agent Pool
 ///
 /// 管理多个 PersistentAgent 实例的池，支持：
@@ -193,11 +452,12 @@ impl std::fmt::Debug for AgentHandle {
 /// - Agent 复用和生命周期管理
 /// - 自动健康检查
 /// - 空闲清理
+/// - 按负载均衡策略分配任务
 pub struct AgentPool {
-    /// Runtime 注册表（使用 Arc 以便共享）
-    runtimes: RwLock<HashMap<RuntimeType, Arc<dyn AgentRuntime>>>,
+    /// Runtime 注册表（clone 之间共享）
+    runtimes: Arc<RwLock<HashMap<RuntimeType, Arc<dyn AgentRuntime>>>>,
     /// 持久化 Agent 实例表
-    agents: Arc<RwLock<HashMap<String, Box<dyn PersistentAgent>>>>,
+    agents: Arc<RwLock<HashMap<String, Arc<dyn PersistentAgent>>>>,
     /// Agent 元信息表
     agent_info: Arc<RwLock<HashMap<String, AgentInfo>>>,
     /// 配置
@@ -206,6 +466,10 @@ pub struct AgentPool {
     health_check_handle: RwLock<Option<JoinHandle<()>>>,
     /// 关闭信号发送端
     shutdown_tx: RwLock<Option<tokio::sync::mpsc::Sender<()>>>,
+    /// 任务调度器
+    scheduler: Arc<JobScheduler>,
+    /// 池耗尽等系统事件的发布目标
+    event_bus: Option<EventBusRef>,
 }
 
 impl std::fmt::Debug for AgentPool {
@@ -221,12 +485,14 @@ impl std::fmt::Debug for AgentPool {
 impl Clone for AgentPool {
     fn clone(&self) -> Self {
         Self {
-            runtimes: RwLock::new(HashMap::new()), // 新实例需要重新注册 runtime
+            runtimes: Arc::clone(&self.runtimes),
             agents: Arc::clone(&self.agents),
             agent_info: Arc::clone(&self.agent_info),
             config: self.config.clone(),
             health_check_handle: RwLock::new(None),
             shutdown_tx: RwLock::new(None),
+            scheduler: Arc::clone(&self.scheduler),
+            event_bus: self.event_bus.clone(),
         }
     }
 }
@@ -234,16 +500,32 @@ impl Clone for AgentPool {
 impl AgentPool {
     /// 创建新的 Pool
     pub fn new(config: PoolConfig) -> Self {
+        let agents: AgentMap = Arc::new(RwLock::new(HashMap::new()));
+        let agent_info: AgentInfoMap = Arc::new(RwLock::new(HashMap::new()));
+        let scheduler = Arc::new(JobScheduler::new(
+            config.strategy,
+            agents.clone(),
+            agent_info.clone(),
+        ));
+
         Self {
-            runtimes: RwLock::new(HashMap::new()),
-            agents: Arc::new(RwLock::new(HashMap::new())),
-            agent_info: Arc::new(RwLock::new(HashMap::new())),
+            runtimes: Arc::new(RwLock::new(HashMap::new())),
+            agents,
+            agent_info,
             config,
             health_check_handle: RwLock::new(None),
             shutdown_tx: RwLock::new(None),
+            scheduler,
+            event_bus: None,
         }
     }
 
+    /// 设置系统事件发布的 EventBus
+    pub fn with_event_bus(mut self, event_bus: EventBusRef) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
     /// 注册 Runtime
     pub async fn register_runtime(&self, runtime: Arc<dyn AgentRuntime>) -> Result<()> {
         let runtime_type = runtime.runtime_type();
@@ -316,6 +598,7 @@ impl AgentPool {
             )
         });
 
+        let capacity = agent_config.max_concurrent_tasks;
         let agent = runtime.create_agent(agent_config.clone()).await?;
         let agent_id = agent.agent_id().to_string();
 
@@ -333,7 +616,7 @@ impl AgentPool {
         // 存储 Agent
         {
             let mut agents = self.agents.write().await;
-            agents.insert(agent_id.clone(), agent);
+            agents.insert(agent_id.clone(), Arc::from(agent));
         }
 
         // 存储 Agent 信息
@@ -342,6 +625,8 @@ impl AgentPool {
             info.insert(agent_id.clone(), agent_info);
         }
 
+        self.scheduler.register(&agent_id, capacity);
+
         Ok(AgentHandle::new(
             agent_id,
             self.agents.clone(),
@@ -360,6 +645,7 @@ impl AgentPool {
 
         if !keep {
             // 从池中移除并关闭 Agent
+            self.scheduler.unregister(&agent_id);
             let mut agents = self.agents.write().await;
             let mut info = self.agent_info.write().await;
 
@@ -409,6 +695,7 @@ impl AgentPool {
     /// 强制终止 Agent
     pub async fn kill(&self, agent_id: &str) -> Result<()> {
         info!("Killing agent: {}", agent_id);
+        self.scheduler.unregister(agent_id);
 
         let mut agents = self.agents.write().await;
         let mut info = self.agent_info.write().await;
@@ -487,6 +774,7 @@ impl AgentPool {
         let mut errors = Vec::new();
 
         for agent_id in agent_ids {
+            self.scheduler.unregister(&agent_id);
             if let Some(agent) = agents.remove(&agent_id) {
                 info!("Shutting down agent: {}", agent_id);
                 if let Err(e) = agent.shutdown().await {
@@ -509,6 +797,34 @@ impl AgentPool {
         }
     }
 
+    /// 提交任务，按负载均衡策略分配给池中已有的 Agent
+    ///
+    /// 所有 Agent 都达到 `max_concurrent_tasks` 时任务进入队列，
+    /// 在有 Agent 空出位置后按提交顺序执行。
+    pub async fn submit(&self, task: TaskRequest) -> Result<AgentJobHandle> {
+        let (handle, exhausted) = self.scheduler.submit(task)?;
+
+        if let (Some(event), Some(bus)) = (exhausted, &self.event_bus) {
+            if let Err(e) = bus.publish(EventWrapper::System(event)).await {
+                warn!("Failed to publish pool exhaustion event: {}", e);
+            }
+        }
+
+        Ok(handle)
+    }
+
+    /// 等待所有排队和执行中的任务完成
+    pub async fn drain(&self) {
+        let mut outstanding = self.scheduler.outstanding.subscribe();
+        // Sender 由调度器持有，等待期间不会关闭
+        let _ = outstanding.wait_for(|count| *count == 0).await;
+    }
+
+    /// 获取任务统计
+    pub fn stats(&self) -> AgentPoolStats {
+        self.scheduler.stats()
+    }
+
     /// 获取当前 Agent 数量
     pub async fn agent_count(&self) -> usize {
         self.agents.read().await.len()
@@ -522,7 +838,7 @@ impl AgentPool {
 
 /// 健康检查单次执行
 async fn health_check_tick(
-    agents: &Arc<RwLock<HashMap<String, Box<dyn PersistentAgent>>>>,
+    agents: &Arc<RwLock<HashMap<String, Arc<dyn PersistentAgent>>>>,
     agent_info: &Arc<RwLock<HashMap<String, AgentInfo>>>,
     auto_cleanup: bool,
     idle_timeout: Duration,
@@ -701,7 +1017,29 @@ mod tests {
             health_check_interval: Duration::from_millis(100),
             auto_cleanup: false,
             idle_timeout: Duration::from_secs(1),
+            strategy: LoadBalancingStrategy::RoundRobin,
+        }
+    }
+
+    async fn pool_with_agents(config: PoolConfig, count: usize, capacity: usize) -> AgentPool {
+        let pool = AgentPool::new(config);
+        pool.register_runtime(Arc::new(MockRuntime::new(RuntimeType::Claude)))
+            .await
+            .unwrap();
+        for i in 0..count {
+            let agent_config = AgentConfig::new(format!("worker-{}", i), PathBuf::from("/tmp"))
+                .with_max_concurrent(capacity);
+            pool.acquire(AgentAcquireConfig::new(RuntimeType::Claude).with_agent_config(agent_config))
+                .await
+                .unwrap();
         }
+        pool
+    }
+
+    async fn tasks_per_agent(pool: &AgentPool) -> Vec<u64> {
+        let mut list = pool.list().await;
+        list.sort_by(|a, b| a.id.cmp(&b.id));
+        list.iter().map(|info| info.total_tasks).collect()
     }
 
     #[tokio::test]
@@ -946,7 +1284,7 @@ mod tests {
         // Test health check cleanup logic directly
         use chrono::Duration as ChronoDuration;
 
-        let agents: Arc<RwLock<HashMap<String, Box<dyn PersistentAgent>>>> = 
+        let agents: Arc<RwLock<HashMap<String, Arc<dyn PersistentAgent>>>> = 
             Arc::new(RwLock::new(HashMap::new()));
         let agent_info: Arc<RwLock<HashMap<String, AgentInfo>>> = 
             Arc::new(RwLock::new(HashMap::new()));
@@ -957,7 +1295,7 @@ mod tests {
         
         {
             let mut agents_guard = agents.write().await;
-            agents_guard.insert("old-agent".to_string(), Box::new(mock_agent));
+            agents_guard.insert("old-agent".to_string(), Arc::new(mock_agent));
         }
 
         {
@@ -1045,4 +1383,80 @@ mod tests {
 
         pool.release(handle, false).await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_submit_round_robin() {
+        let pool = pool_with_agents(create_test_config(), 2, 4).await;
+
+        let mut jobs = Vec::new();
+        for i in 0..4 {
+            jobs.push(pool.submit(TaskRequest::new(format!("t{}", i), "work")).await.unwrap());
+        }
+        for job in jobs {
+            assert!(job.wait().await.unwrap().success);
+        }
+
+        assert_eq!(tasks_per_agent(&pool).await, vec![2, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_submit_least_loaded() {
+        let config = PoolConfig {
+            strategy: LoadBalancingStrategy::LeastLoaded,
+            ..create_test_config()
+        };
+        let pool = pool_with_agents(config, 3, 2).await;
+
+        for i in 0..3 {
+            pool.submit(TaskRequest::new(format!("t{}", i), "work")).await.unwrap();
+        }
+        // 每个 Agent 各分到一个任务
+        assert_eq!(pool.stats().active_jobs, 3);
+        pool.drain().await;
+
+        assert_eq!(tasks_per_agent(&pool).await, vec![1, 1, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_submit_queues_when_exhausted() {
+        use crate::event_bus::EventBus;
+
+        let bus = Arc::new(crate::event_bus::MemoryEventBus::new());
+        let pool = pool_with_agents(create_test_config(), 1, 1)
+            .await
+            .with_event_bus(bus.clone());
+
+        for i in 0..3 {
+            pool.submit(TaskRequest::new(format!("t{}", i), "work")).await.unwrap();
+        }
+
+        let stats = pool.stats();
+        assert_eq!(stats.total_agents, 1);
+        assert_eq!(stats.active_jobs, 1);
+        assert_eq!(stats.queued_jobs, 2);
+
+        pool.drain().await;
+        assert_eq!(
+            pool.stats(),
+            AgentPoolStats {
+                total_agents: 1,
+                active_jobs: 0,
+                queued_jobs: 0,
+                completed_jobs: 3,
+            }
+        );
+
+        // 队列由空变为非空时只报告一次
+        let events = bus.get_history("system.event", 10).await.unwrap();
+        assert_eq!(events.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_without_agents() {
+        let pool = AgentPool::new(create_test_config());
+        assert!(pool.submit(TaskRequest::new("t", "work")).await.is_err());
+
+        // 空池 drain 立即返回
+        pool.drain().await;
+    }
 }
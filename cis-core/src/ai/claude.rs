@@ -1,11 +1,15 @@
 //! Claude CLI AI Provider 实现
 
-use super::{AiProvider, AiError, ConversationContext, Message, Result};
+use super::{estimate_tokens, AiProvider, AiError, ConversationContext, Message, Result};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
+use tokio::sync::{Mutex, OnceCell};
 
 /// Claude CLI 配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,12 +38,187 @@ impl Default for ClaudeConfig {
     }
 }
 
+/// 服务端会话状态（`--conversation-id`）
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ServerConversation {
+    /// Claude 会话 UUID
+    conversation_id: String,
+    /// 已发送的历史消息数
+    sent: usize,
+}
+
+impl ServerConversation {
+    fn new() -> Self {
+        Self {
+            conversation_id: uuid::Uuid::new_v4().to_string(),
+            sent: 0,
+        }
+    }
+
+    /// 本次需要发送的消息
+    ///
+    /// 服务端已持有前 `sent` 条历史（以及上次的回复），只需发送之后的用户消息。
+    /// 历史变短说明调用方重置了对话，返回 None。
+    fn pending<'a>(&self, messages: &'a [Message]) -> Option<Vec<&'a Message>> {
+        if messages.len() < self.sent {
+            return None;
+        }
+        Some(
+            messages[self.sent..]
+                .iter()
+                .filter(|m| matches!(m.role, super::Role::User))
+                .collect(),
+        )
+    }
+}
+
 pub struct ClaudeCliProvider {
     config: ClaudeConfig,
+    /// `claude count-tokens` 不可用时跳过后续调用
+    count_tokens_unsupported: AtomicBool,
+    /// CIS 会话 ID，设置后尝试使用服务端会话
+    session_id: Option<String>,
+    /// CLI 是否支持 `--conversation-id`（首次使用时检测）
+    conversation_support: OnceCell<bool>,
+    /// CIS 会话 ID -> 服务端会话
+    conversations: Mutex<HashMap<String, ServerConversation>>,
 }
 
 impl ClaudeCliProvider {
-    pub fn new(config: ClaudeConfig) -> Self { Self { config } }
+    pub fn new(config: ClaudeConfig) -> Self {
+        Self {
+            config,
+            count_tokens_unsupported: AtomicBool::new(false),
+            session_id: None,
+            conversation_support: OnceCell::new(),
+            conversations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 绑定 CIS 会话，`chat_with_context` 将复用服务端会话上下文
+    pub fn with_session_id(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    /// 检测 CLI 是否支持 `--conversation-id`
+    async fn supports_conversation_id(&self) -> bool {
+        *self
+            .conversation_support
+            .get_or_init(|| async {
+                match Command::new("claude").arg("--help").output().await {
+                    Ok(output) => {
+                        String::from_utf8_lossy(&output.stdout).contains("--conversation-id")
+                    }
+                    Err(_) => false,
+                }
+            })
+            .await
+    }
+
+    /// 使用服务端会话对话，只发送新的用户消息
+    async fn chat_in_conversation(
+        &self,
+        session_id: &str,
+        system: &str,
+        messages: &[Message],
+    ) -> Result<String> {
+        let mut conversations = self.conversations.lock().await;
+        let existing = conversations.get(session_id).cloned();
+        let (mut conversation, pending) = match existing
+            .as_ref()
+            .and_then(|c| c.pending(messages).map(|p| (c.clone(), p)))
+        {
+            Some(found) => found,
+            None => {
+                let conversation = ServerConversation::new();
+                let pending = conversation.pending(messages).unwrap_or_default();
+                (conversation, pending)
+            }
+        };
+        let is_new = conversation.sent == 0;
+
+        let mut cmd = Command::new("claude");
+        cmd.arg("--model").arg(&self.config.model)
+           .arg("--conversation-id").arg(&conversation.conversation_id);
+
+        // 系统提示与已有回复只在新会话中发送
+        if is_new {
+            cmd.arg("--system").arg(system);
+            for msg in messages {
+                match msg.role {
+                    super::Role::User => { cmd.arg("--user").arg(&msg.content); }
+                    super::Role::Assistant => { cmd.arg("--assistant").arg(&msg.content); }
+                    _ => {}
+                }
+            }
+        } else {
+            for msg in pending {
+                cmd.arg("--user").arg(&msg.content);
+            }
+        }
+
+        let response = self.run(cmd).await?;
+        conversation.sent = messages.len();
+        conversations.insert(session_id.to_string(), conversation);
+        Ok(response)
+    }
+
+    /// 每次发送完整历史
+    async fn chat_with_full_history(&self, system: &str, messages: &[Message]) -> Result<String> {
+        let mut cmd = Command::new("claude");
+        cmd.arg("--model").arg(&self.config.model)
+           .arg("--system").arg(system);
+        
+        for msg in messages {
+            match msg.role {
+                super::Role::User => { cmd.arg("--user").arg(&msg.content); }
+                super::Role::Assistant => { cmd.arg("--assistant").arg(&msg.content); }
+                _ => {}
+            }
+        }
+
+        self.run(cmd).await
+    }
+
+    async fn run(&self, mut cmd: Command) -> Result<String> {
+        cmd.stdin(Stdio::null())
+           .stdout(Stdio::piped())
+           .stderr(Stdio::piped());
+
+        let output: std::process::Output = cmd.output().await.map_err(AiError::Io)?;
+        
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(AiError::CliError(stderr.to_string()));
+        }
+        
+        Ok(String::from_utf8(output.stdout)?)
+    }
+
+    /// 调用 `claude count-tokens`（文本经 stdin 传入），返回 None 表示不支持
+    async fn cli_count_tokens(&self, text: &str) -> Option<usize> {
+        let mut child = Command::new("claude")
+            .arg("count-tokens")
+            .arg("--model").arg(&self.config.model)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .ok()?;
+
+        // 写完后关闭 stdin
+        let mut stdin = child.stdin.take()?;
+        stdin.write_all(text.as_bytes()).await.ok()?;
+        drop(stdin);
+        let output = child.wait_with_output().await.ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        String::from_utf8(output.stdout).ok()?.trim().parse().ok()
+    }
 }
 
 impl Default for ClaudeCliProvider {
@@ -83,30 +262,12 @@ impl AiProvider for ClaudeCliProvider {
     }
     
     async fn chat_with_context(&self, system: &str, messages: &[Message]) -> Result<String> {
-        let mut cmd = Command::new("claude");
-        cmd.arg("--model").arg(&self.config.model)
-           .arg("--system").arg(system);
-        
-        for msg in messages {
-            match msg.role {
-                super::Role::User => { cmd.arg("--user").arg(&msg.content); }
-                super::Role::Assistant => { cmd.arg("--assistant").arg(&msg.content); }
-                _ => {}
+        match &self.session_id {
+            Some(session_id) if self.supports_conversation_id().await => {
+                self.chat_in_conversation(session_id, system, messages).await
             }
+            _ => self.chat_with_full_history(system, messages).await,
         }
-        
-        cmd.stdin(Stdio::null())
-           .stdout(Stdio::piped())
-           .stderr(Stdio::piped());
-
-        let output: std::process::Output = cmd.output().await.map_err(AiError::Io)?;
-        
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(AiError::CliError(stderr.to_string()));
-        }
-        
-        Ok(String::from_utf8(output.stdout)?)
     }
     
     async fn generate_json(&self, prompt: &str, schema: &str) -> Result<serde_json::Value> {
@@ -136,6 +297,29 @@ impl AiProvider for ClaudeCliProvider {
             .map_err(|e| AiError::InvalidResponse(format!("JSON parse error: {}", e)))
     }
 
+    /// Schema 作为 JSON 指令注入系统提示（不使用服务端会话，避免污染对话历史）
+    async fn structured_chat(&self, prompt: &str, schema: &serde_json::Value) -> Result<serde_json::Value> {
+        let system = super::structured::structured_instruction(schema);
+        let response = self
+            .chat_with_full_history(&system, &[Message::user(prompt)])
+            .await?;
+        super::structured::parse_structured_output(&response, schema)
+    }
+
+    /// 优先使用 `claude count-tokens`，不可用时回退到启发式估算
+    async fn count_tokens(&self, text: &str) -> Result<usize> {
+        if !self.count_tokens_unsupported.load(Ordering::Relaxed) {
+            match self.cli_count_tokens(text).await {
+                Some(count) => return Ok(count),
+                None => {
+                    tracing::debug!("claude count-tokens unavailable, using heuristic");
+                    self.count_tokens_unsupported.store(true, Ordering::Relaxed);
+                }
+            }
+        }
+        Ok(estimate_tokens(text))
+    }
+
     /// 带 RAG 上下文的对话 (CVI-011)
     async fn chat_with_rag(
         &self,
@@ -160,3 +344,31 @@ impl AiProvider for ClaudeCliProvider {
         self.chat(&enhanced_prompt).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_conversation_pending() {
+        let history = vec![Message::user("hi"), Message::assistant("hello")];
+        let mut conversation = ServerConversation::new();
+
+        // 新会话发送全部用户消息
+        assert_eq!(conversation.pending(&history).unwrap().len(), 1);
+
+        conversation.sent = 1;
+        let next = vec![
+            Message::user("hi"),
+            Message::assistant("hello"),
+            Message::user("how are you?"),
+        ];
+        let pending = conversation.pending(&next).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].content, "how are you?");
+
+        // 历史被重置
+        conversation.sent = 5;
+        assert!(conversation.pending(&next).is_none());
+    }
+}
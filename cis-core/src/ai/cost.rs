@@ -0,0 +1,425 @@
+//! AI 调用成本统计与预算控制
+//!
+//! 每个会话的用量记录在记忆 `ai::cost::<session_id>` 中。
+//! [`CostTrackingProvider`] 包装任意 Provider：调用前检查预算周期内的累计成本，
+//! 调用后按 [`TokenPricing`] 计算本次成本并写回会话记录。
+
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use super::{AiError, AiProvider, ConversationContext, Message, Result};
+use crate::error::CisError;
+use crate::memory::MemoryService;
+use crate::types::{MemoryCategory, MemoryDomain};
+
+/// 成本记录的记忆键前缀
+pub const COST_KEY_PREFIX: &str = "ai::cost::";
+
+/// 带成本的 Token 用量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub total_cost_usd: f64,
+}
+
+impl CostUsage {
+    pub fn total_tokens(&self) -> u32 {
+        self.prompt_tokens + self.completion_tokens
+    }
+}
+
+impl std::ops::AddAssign for CostUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.total_cost_usd += other.total_cost_usd;
+    }
+}
+
+/// Token 单价（USD / 百万 token）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenPricing {
+    #[serde(default)]
+    pub prompt_per_mtok: f64,
+    #[serde(default)]
+    pub completion_per_mtok: f64,
+}
+
+impl TokenPricing {
+    /// 计算一次调用的用量与成本
+    pub fn usage(&self, prompt_tokens: u32, completion_tokens: u32) -> CostUsage {
+        CostUsage {
+            prompt_tokens,
+            completion_tokens,
+            total_cost_usd: (prompt_tokens as f64 * self.prompt_per_mtok
+                + completion_tokens as f64 * self.completion_per_mtok)
+                / 1_000_000.0,
+        }
+    }
+}
+
+/// 预算周期
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetPeriod {
+    #[default]
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl BudgetPeriod {
+    /// 当前周期起点（UTC，周以周一开始）
+    pub fn period_start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let today = now.date_naive();
+        let start = match self {
+            BudgetPeriod::Daily => today,
+            BudgetPeriod::Weekly => today - Duration::days(today.weekday().num_days_from_monday() as i64),
+            BudgetPeriod::Monthly => today.with_day(1).unwrap_or(today),
+        };
+        midnight(start)
+    }
+
+    /// 下一次重置时间
+    pub fn next_reset(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let start = self.period_start(now);
+        match self {
+            BudgetPeriod::Daily => start + Duration::days(1),
+            BudgetPeriod::Weekly => start + Duration::days(7),
+            BudgetPeriod::Monthly => {
+                let (year, month) = if start.month() == 12 {
+                    (start.year() + 1, 1)
+                } else {
+                    (start.year(), start.month() + 1)
+                };
+                NaiveDate::from_ymd_opt(year, month, 1).map(midnight).unwrap_or(start)
+            }
+        }
+    }
+}
+
+fn midnight(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0)
+        .map(|dt| dt.and_utc())
+        .unwrap_or_else(Utc::now)
+}
+
+/// 检查预算：周期内累计成本达到预算时返回 `BudgetExceeded`
+pub fn check_budget(
+    spent_usd: f64,
+    budget_usd: Option<f64>,
+    period: BudgetPeriod,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    match budget_usd {
+        Some(budget) if spent_usd >= budget => Err(AiError::BudgetExceeded {
+            remaining: (budget - spent_usd).max(0.0),
+            reset_at: period.next_reset(now),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// 单次调用记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostRecord {
+    pub provider: String,
+    pub usage: CostUsage,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// 会话成本统计
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionCostTracker {
+    pub session_id: String,
+    pub records: Vec<CostRecord>,
+}
+
+impl SessionCostTracker {
+    pub fn new(session_id: impl Into<String>) -> Self {
+        Self {
+            session_id: session_id.into(),
+            records: Vec::new(),
+        }
+    }
+
+    /// 记忆键
+    pub fn memory_key(session_id: &str) -> String {
+        format!("{}{}", COST_KEY_PREFIX, session_id)
+    }
+
+    /// 记录一次调用
+    pub fn record(&mut self, provider: impl Into<String>, usage: CostUsage) {
+        self.records.push(CostRecord {
+            provider: provider.into(),
+            usage,
+            timestamp: Utc::now(),
+        });
+    }
+
+    /// 累计用量
+    pub fn total(&self) -> CostUsage {
+        self.records.iter().fold(CostUsage::default(), |mut acc, r| {
+            acc += r.usage;
+            acc
+        })
+    }
+
+    /// 按 Provider 汇总
+    pub fn by_provider(&self) -> BTreeMap<String, CostUsage> {
+        let mut totals: BTreeMap<String, CostUsage> = BTreeMap::new();
+        for record in &self.records {
+            *totals.entry(record.provider.clone()).or_default() += record.usage;
+        }
+        totals
+    }
+
+    /// 指定时间之后的成本
+    pub fn cost_since(&self, since: DateTime<Utc>) -> f64 {
+        self.records
+            .iter()
+            .filter(|r| r.timestamp >= since)
+            .map(|r| r.usage.total_cost_usd)
+            .sum()
+    }
+
+    /// 从记忆加载，不存在时返回空记录
+    pub async fn load(memory: &MemoryService, session_id: &str) -> crate::error::Result<Self> {
+        match memory.get(&Self::memory_key(session_id)).await? {
+            Some(item) => serde_json::from_slice(&item.value).map_err(|e| {
+                CisError::internal_error(format!("Invalid cost record for session {}: {}", session_id, e))
+            }),
+            None => Ok(Self::new(session_id)),
+        }
+    }
+
+    /// 加载所有会话的记录
+    pub async fn load_all(memory: &MemoryService) -> crate::error::Result<Vec<Self>> {
+        let mut trackers = Vec::new();
+        for key in memory.list_keys(Some(MemoryDomain::Private)).await? {
+            let Some(session_id) = key.strip_prefix(COST_KEY_PREFIX) else {
+                continue;
+            };
+            trackers.push(Self::load(memory, session_id).await?);
+        }
+        Ok(trackers)
+    }
+
+    /// 写回记忆
+    pub async fn save(&self, memory: &MemoryService) -> crate::error::Result<()> {
+        let key = Self::memory_key(&self.session_id);
+        let value = serde_json::to_vec(self)
+            .map_err(|e| CisError::memory_set_failed(key.as_str(), e.to_string()))?;
+        memory
+            .set(
+                &key,
+                &value,
+                MemoryDomain::Private,
+                MemoryCategory::Execution,
+            )
+            .await
+    }
+}
+
+/// 带成本统计与预算控制的 Provider
+pub struct CostTrackingProvider {
+    inner: Box<dyn AiProvider>,
+    memory: Arc<MemoryService>,
+    session_id: String,
+    pricing: TokenPricing,
+    budget_usd: Option<f64>,
+    budget_period: BudgetPeriod,
+}
+
+impl CostTrackingProvider {
+    pub fn new(inner: Box<dyn AiProvider>, memory: Arc<MemoryService>, session_id: impl Into<String>) -> Self {
+        Self {
+            inner,
+            memory,
+            session_id: session_id.into(),
+            pricing: TokenPricing::default(),
+            budget_usd: None,
+            budget_period: BudgetPeriod::default(),
+        }
+    }
+
+    pub fn with_pricing(mut self, pricing: TokenPricing) -> Self {
+        self.pricing = pricing;
+        self
+    }
+
+    pub fn with_budget(mut self, budget_usd: Option<f64>, period: BudgetPeriod) -> Self {
+        self.budget_usd = budget_usd;
+        self.budget_period = period;
+        self
+    }
+
+    /// 调用前检查所有会话在本周期内的累计成本
+    async fn ensure_budget(&self) -> Result<()> {
+        if self.budget_usd.is_none() {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        let since = self.budget_period.period_start(now);
+        let spent = match SessionCostTracker::load_all(&self.memory).await {
+            Ok(trackers) => trackers.iter().map(|t| t.cost_since(since)).sum(),
+            Err(e) => {
+                tracing::warn!("Failed to load AI cost records, skipping budget check: {}", e);
+                return Ok(());
+            }
+        };
+
+        check_budget(spent, self.budget_usd, self.budget_period, now)
+    }
+
+    /// 统计本次调用并写回会话记录（失败只记录日志）
+    async fn track(&self, prompt: &str, completion: &str) {
+        let counts = (
+            self.inner.count_tokens(prompt).await,
+            self.inner.count_tokens(completion).await,
+        );
+        let usage = match counts {
+            (Ok(p), Ok(c)) => self.pricing.usage(p as u32, c as u32),
+            _ => return,
+        };
+
+        let result = async {
+            let mut tracker = SessionCostTracker::load(&self.memory, &self.session_id).await?;
+            tracker.record(self.inner.name(), usage);
+            tracker.save(&self.memory).await
+        }
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!("Failed to record AI cost for session {}: {}", self.session_id, e);
+        }
+    }
+}
+
+#[async_trait]
+impl AiProvider for CostTrackingProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn available(&self) -> bool {
+        self.inner.available().await
+    }
+
+    async fn chat(&self, prompt: &str) -> Result<String> {
+        self.ensure_budget().await?;
+        let response = self.inner.chat(prompt).await?;
+        self.track(prompt, &response).await;
+        Ok(response)
+    }
+
+    async fn chat_with_context(&self, system: &str, messages: &[Message]) -> Result<String> {
+        self.ensure_budget().await?;
+        let response = self.inner.chat_with_context(system, messages).await?;
+
+        let mut prompt = system.to_string();
+        for msg in messages {
+            prompt.push('\n');
+            prompt.push_str(&msg.content);
+        }
+        self.track(&prompt, &response).await;
+        Ok(response)
+    }
+
+    async fn chat_with_rag(&self, prompt: &str, ctx: Option<&ConversationContext>) -> Result<String> {
+        self.ensure_budget().await?;
+        let response = self.inner.chat_with_rag(prompt, ctx).await?;
+        self.track(prompt, &response).await;
+        Ok(response)
+    }
+
+    async fn generate_json(&self, prompt: &str, schema: &str) -> Result<serde_json::Value> {
+        self.ensure_budget().await?;
+        let value = self.inner.generate_json(prompt, schema).await?;
+        self.track(&format!("{}\n{}", prompt, schema), &value.to_string()).await;
+        Ok(value)
+    }
+
+    async fn structured_chat(&self, prompt: &str, schema: &serde_json::Value) -> Result<serde_json::Value> {
+        self.ensure_budget().await?;
+        let value = self.inner.structured_chat(prompt, schema).await?;
+        self.track(&format!("{}\n{}", prompt, schema), &value.to_string()).await;
+        Ok(value)
+    }
+
+    async fn count_tokens(&self, text: &str) -> Result<usize> {
+        self.inner.count_tokens(text).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_pricing_usage() {
+        let pricing = TokenPricing {
+            prompt_per_mtok: 3.0,
+            completion_per_mtok: 15.0,
+        };
+        let usage = pricing.usage(1_000_000, 200_000);
+        assert_eq!(usage.total_tokens(), 1_200_000);
+        assert!((usage.total_cost_usd - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_budget_period_boundaries() {
+        // 2025-01-15 为周三
+        let now = at("2025-01-15T13:45:00Z");
+        assert_eq!(BudgetPeriod::Daily.next_reset(now), at("2025-01-16T00:00:00Z"));
+        assert_eq!(BudgetPeriod::Weekly.period_start(now), at("2025-01-13T00:00:00Z"));
+        assert_eq!(BudgetPeriod::Weekly.next_reset(now), at("2025-01-20T00:00:00Z"));
+        assert_eq!(BudgetPeriod::Monthly.period_start(now), at("2025-01-01T00:00:00Z"));
+        assert_eq!(
+            BudgetPeriod::Monthly.next_reset(at("2025-12-31T23:00:00Z")),
+            at("2026-01-01T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_check_budget() {
+        let now = at("2025-01-15T13:45:00Z");
+        assert!(check_budget(100.0, None, BudgetPeriod::Daily, now).is_ok());
+        assert!(check_budget(4.5, Some(5.0), BudgetPeriod::Daily, now).is_ok());
+
+        match check_budget(5.5, Some(5.0), BudgetPeriod::Daily, now) {
+            Err(AiError::BudgetExceeded { remaining, reset_at }) => {
+                assert_eq!(remaining, 0.0);
+                assert_eq!(reset_at, at("2025-01-16T00:00:00Z"));
+            }
+            other => panic!("expected BudgetExceeded, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_session_tracker_totals() {
+        let mut tracker = SessionCostTracker::new("s1");
+        tracker.record("claude-cli", CostUsage { prompt_tokens: 10, completion_tokens: 5, total_cost_usd: 0.5 });
+        tracker.record("kimi", CostUsage { prompt_tokens: 1, completion_tokens: 1, total_cost_usd: 0.1 });
+        tracker.record("claude-cli", CostUsage { prompt_tokens: 2, completion_tokens: 3, total_cost_usd: 0.2 });
+
+        assert_eq!(SessionCostTracker::memory_key("s1"), "ai::cost::s1");
+        assert_eq!(tracker.total().total_tokens(), 22);
+
+        let by_provider = tracker.by_provider();
+        assert_eq!(by_provider["claude-cli"].prompt_tokens, 12);
+        assert!((by_provider["kimi"].total_cost_usd - 0.1).abs() < 1e-9);
+
+        assert!((tracker.cost_since(Utc::now() - Duration::hours(1)) - 0.8).abs() < 1e-9);
+        assert_eq!(tracker.cost_since(Utc::now() + Duration::hours(1)), 0.0);
+    }
+}
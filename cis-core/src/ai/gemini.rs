@@ -0,0 +1,299 @@
+//! Google Gemini AI Provider 实现
+//!
+//! 通过 Gemini REST API (`generateContent`) 调用，不依赖本地 CLI。
+//! API Key 未在配置中提供时读取 `GEMINI_API_KEY` 环境变量。
+
+use super::{AiError, AiProvider, ConversationContext, Message, Result, Role};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Gemini API 根地址
+pub const GEMINI_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+/// Gemini 安全设置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SafetySetting {
+    /// 危害类别，如 `HARM_CATEGORY_HARASSMENT`
+    pub category: String,
+    /// 拦截阈值，如 `BLOCK_MEDIUM_AND_ABOVE`
+    pub threshold: String,
+}
+
+/// Gemini 配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeminiConfig {
+    #[serde(default = "default_api_key")]
+    pub api_key: String,
+    #[serde(default = "default_model")]
+    pub model: String,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub safety_settings: Vec<SafetySetting>,
+}
+
+fn default_api_key() -> String {
+    std::env::var("GEMINI_API_KEY").unwrap_or_default()
+}
+fn default_model() -> String { "gemini-pro".to_string() }
+
+impl Default for GeminiConfig {
+    fn default() -> Self {
+        Self {
+            api_key: default_api_key(),
+            model: default_model(),
+            temperature: None,
+            safety_settings: Vec::new(),
+        }
+    }
+}
+
+pub struct GeminiProvider {
+    config: GeminiConfig,
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl GeminiProvider {
+    pub fn new(config: GeminiConfig) -> Self {
+        Self {
+            config,
+            base_url: GEMINI_API_BASE.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// 发送一个最小请求，验证 API Key 是否有效
+    pub async fn verify_api_key(&self) -> Result<()> {
+        if self.config.api_key.is_empty() {
+            return Err(AiError::NotAvailable("GEMINI_API_KEY not set".to_string()));
+        }
+        self.chat("ping").await.map(|_| ())
+    }
+
+    fn endpoint(&self) -> String {
+        format!("{}/models/{}:generateContent", self.base_url, self.config.model)
+    }
+
+    /// 构建 `generateContent` 请求体
+    ///
+    /// Gemini 只有 `user` / `model` 两种角色，系统提示作为第一条 user 消息发送。
+    fn request_body(&self, system: &str, messages: &[Message]) -> serde_json::Value {
+        let mut contents = Vec::with_capacity(messages.len() + 1);
+        if !system.is_empty() {
+            contents.push(serde_json::json!({ "role": "user", "parts": [{ "text": system }] }));
+        }
+        for message in messages {
+            let role = match message.role {
+                Role::Assistant => "model",
+                Role::User | Role::System => "user",
+            };
+            contents.push(serde_json::json!({ "role": role, "parts": [{ "text": message.content }] }));
+        }
+
+        let mut body = serde_json::json!({ "contents": contents });
+        if let Some(temperature) = self.config.temperature {
+            body["generationConfig"] = serde_json::json!({ "temperature": temperature });
+        }
+        if !self.config.safety_settings.is_empty() {
+            body["safetySettings"] = serde_json::json!(self.config.safety_settings);
+        }
+        body
+    }
+
+    async fn generate(&self, system: &str, messages: &[Message]) -> Result<String> {
+        self.send(self.request_body(system, messages)).await
+    }
+
+    async fn send(&self, body: serde_json::Value) -> Result<String> {
+        if self.config.api_key.is_empty() {
+            return Err(AiError::NotAvailable("GEMINI_API_KEY not set".to_string()));
+        }
+
+        let response = self
+            .client
+            .post(self.endpoint())
+            .header("x-goog-api-key", &self.config.api_key)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| AiError::Http(format!("Gemini API request failed: {}", e)))?;
+
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| AiError::Http(format!("Failed to read Gemini response: {}", e)))?;
+        if !status.is_success() {
+            return Err(AiError::Http(format!("Gemini API error ({}): {}", status, text)));
+        }
+
+        let value: serde_json::Value = serde_json::from_str(&text)
+            .map_err(|e| AiError::InvalidResponse(format!("JSON parse error: {}", e)))?;
+        extract_text(&value)
+    }
+}
+
+impl Default for GeminiProvider {
+    fn default() -> Self { Self::new(GeminiConfig::default()) }
+}
+
+/// 取出 `candidates[0].content.parts[0].text`
+fn extract_text(response: &serde_json::Value) -> Result<String> {
+    if let Some(text) = response
+        .pointer("/candidates/0/content/parts/0/text")
+        .and_then(|t| t.as_str())
+    {
+        return Ok(text.to_string());
+    }
+
+    // 被安全设置拦截时没有 content，只有 finishReason / promptFeedback
+    let reason = response
+        .pointer("/candidates/0/finishReason")
+        .or_else(|| response.pointer("/promptFeedback/blockReason"))
+        .and_then(|r| r.as_str())
+        .unwrap_or("no candidates");
+    Err(AiError::InvalidResponse(format!("Gemini returned no text: {}", reason)))
+}
+
+#[async_trait]
+impl AiProvider for GeminiProvider {
+    fn name(&self) -> &str { "gemini" }
+
+    async fn available(&self) -> bool {
+        !self.config.api_key.is_empty()
+    }
+
+    async fn chat(&self, prompt: &str) -> Result<String> {
+        self.generate("", &[Message::user(prompt)]).await
+    }
+
+    async fn chat_with_context(&self, system: &str, messages: &[Message]) -> Result<String> {
+        self.generate(system, messages).await
+    }
+
+    async fn generate_json(&self, prompt: &str, schema: &str) -> Result<serde_json::Value> {
+        let full_prompt = format!(
+            "{}\n\nPlease respond with valid JSON matching this schema:\n{}\n\nRespond ONLY with the JSON object.",
+            prompt, schema
+        );
+
+        let response = self.chat(&full_prompt).await?;
+
+        let trimmed = response.trim();
+        let json_str = if trimmed.starts_with('{') && trimmed.ends_with('}') {
+            trimmed
+        } else if let Some(start) = trimmed.find("```") {
+            let after = &trimmed[start + 3..];
+            if let Some(end) = after.find("```") {
+                let content = after[..end].trim();
+                content.strip_prefix("json").map(str::trim).unwrap_or(content)
+            } else {
+                return Err(AiError::InvalidResponse("Invalid JSON block".to_string()));
+            }
+        } else {
+            return Err(AiError::InvalidResponse("No JSON found".to_string()));
+        };
+
+        serde_json::from_str(json_str)
+            .map_err(|e| AiError::InvalidResponse(format!("JSON parse error: {}", e)))
+    }
+
+    /// 使用 Gemini 原生 JSON 模式（`responseMimeType: application/json`），并在提示中附带 Schema
+    async fn structured_chat(&self, prompt: &str, schema: &serde_json::Value) -> Result<serde_json::Value> {
+        let system = super::structured::structured_instruction(schema);
+        let mut body = self.request_body(&system, &[Message::user(prompt)]);
+        body["generationConfig"]["responseMimeType"] = serde_json::json!("application/json");
+
+        let response = self.send(body).await?;
+        super::structured::parse_structured_output(&response, schema)
+    }
+
+    /// 带 RAG 上下文的对话 (CVI-011)
+    async fn chat_with_rag(
+        &self,
+        prompt: &str,
+        ctx: Option<&ConversationContext>,
+    ) -> Result<String> {
+        let enhanced_prompt = if let Some(context) = ctx {
+            match context.prepare_ai_prompt(prompt).await {
+                Ok(enhanced) => enhanced,
+                Err(e) => {
+                    tracing::warn!("Failed to prepare AI prompt: {}, using original", e);
+                    prompt.to_string()
+                }
+            }
+        } else {
+            prompt.to_string()
+        };
+
+        self.chat(&enhanced_prompt).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider() -> GeminiProvider {
+        GeminiProvider::new(GeminiConfig {
+            api_key: "test-key".to_string(),
+            model: "gemini-pro".to_string(),
+            temperature: Some(0.2),
+            safety_settings: vec![SafetySetting {
+                category: "HARM_CATEGORY_HARASSMENT".to_string(),
+                threshold: "BLOCK_ONLY_HIGH".to_string(),
+            }],
+        })
+    }
+
+    #[test]
+    fn test_request_body() {
+        let provider = provider();
+        assert_eq!(
+            provider.endpoint(),
+            "https://generativelanguage.googleapis.com/v1beta/models/gemini-pro:generateContent"
+        );
+
+        let body = provider.request_body(
+            "Be brief.",
+            &[Message::user("Hi"), Message::assistant("Hello"), Message::user("Bye")],
+        );
+        let roles: Vec<&str> = body["contents"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["role"].as_str().unwrap())
+            .collect();
+        assert_eq!(roles, vec!["user", "user", "model", "user"]);
+        assert_eq!(body["contents"][0]["parts"][0]["text"], "Be brief.");
+        assert_eq!(body["generationConfig"]["temperature"].as_f64().unwrap() as f32, 0.2);
+        assert_eq!(body["safetySettings"][0]["threshold"], "BLOCK_ONLY_HIGH");
+
+        let plain = GeminiProvider::new(GeminiConfig::default()).request_body("", &[Message::user("Hi")]);
+        assert!(plain.get("generationConfig").is_none());
+        assert!(plain.get("safetySettings").is_none());
+    }
+
+    #[test]
+    fn test_extract_text() {
+        let response = serde_json::json!({
+            "candidates": [{ "content": { "role": "model", "parts": [{ "text": "Hello!" }] } }]
+        });
+        assert_eq!(extract_text(&response).unwrap(), "Hello!");
+
+        let blocked = serde_json::json!({ "candidates": [{ "finishReason": "SAFETY" }] });
+        let err = extract_text(&blocked).unwrap_err();
+        assert!(err.to_string().contains("SAFETY"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_api_key() {
+        let provider = GeminiProvider::new(GeminiConfig {
+            api_key: String::new(),
+            ..GeminiConfig::default()
+        });
+        assert!(!provider.available().await);
+        assert!(matches!(provider.verify_api_key().await, Err(AiError::NotAvailable(_))));
+    }
+}
@@ -1,12 +1,12 @@
 //! # AI Provider 模块
 //!
-//! 提供统一的 AI 调用接口，支持 Claude CLI（默认）和 Kimi Code
+//! 提供统一的 AI 调用接口，支持 Claude CLI（默认）、Kimi Code 和 Google Gemini
 //! 同时提供 RAG (Retrieval Augmented Generation) 增强功能
 //!
 //! ## 功能特性
 //!
 //! - 统一 AI Provider 接口
-//! - 支持 Claude CLI 和 Kimi Code
+//! - 支持 Claude CLI、Kimi Code 和 Google Gemini
 //! - RAG 增强生成
 //! - 向量存储集成
 //!
@@ -38,8 +38,11 @@ use std::sync::Arc;
 use thiserror::Error;
 
 mod claude;
+pub mod cost;
+mod gemini;
 mod kimi;
 mod opencode;
+pub mod structured;
 
 pub mod embedding;
 #[cfg(feature = "vector")]
@@ -49,6 +52,7 @@ pub mod embedding_init;
 pub mod embedding_service;
 
 pub use claude::{ClaudeCliProvider, ClaudeConfig};
+pub use cost::{BudgetPeriod, CostTrackingProvider, CostUsage, SessionCostTracker, TokenPricing};
 pub use embedding::{
     create_embedding_service, create_embedding_service_sync, create_embedding_service_with_fallback,
     cosine_similarity, filter_by_similarity,
@@ -68,6 +72,7 @@ pub use embedding_init::{
 };
 #[cfg(feature = "vector")]
 pub use embedding_service::EmbeddingService;
+pub use gemini::{GeminiConfig, GeminiProvider, SafetySetting, GEMINI_API_BASE};
 pub use kimi::{KimiCodeProvider, KimiConfig};
 pub use opencode::{OpenCodeProvider, OpenCodeConfig, OpenCodeSession};
 
@@ -88,6 +93,18 @@ pub enum AiError {
     
     #[error("UTF-8 error: {0}")]
     Utf8(#[from] std::string::FromUtf8Error),
+
+    #[error("HTTP error: {0}")]
+    Http(String),
+
+    #[error("Malformed structured output: {0}")]
+    MalformedStructuredOutput(String),
+
+    #[error("AI budget exceeded (remaining ${remaining:.2}, resets at {reset_at})")]
+    BudgetExceeded {
+        remaining: f64,
+        reset_at: chrono::DateTime<chrono::Utc>,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, AiError>;
@@ -139,6 +156,7 @@ impl Message {
 ///
 /// - `ClaudeCliProvider`: Claude CLI 实现
 /// - `KimiCodeProvider`: Kimi Code 实现
+/// - `GeminiProvider`: Google Gemini REST API 实现
 /// - `RagProvider<P>`: RAG 增强包装器
 ///
 /// ## 示例
@@ -228,6 +246,34 @@ pub trait AiProvider: Send + Sync {
         prompt: &str,
         schema: &str,
     ) -> Result<serde_json::Value>;
+
+    /// 按 JSON Schema 约束的结构化对话
+    ///
+    /// 默认实现将 Schema 指令附加到 prompt 后调用 [`chat`](Self::chat)；
+    /// 支持系统提示或原生 JSON 模式的 Provider 可覆盖此方法。
+    /// 回复不是 JSON 或不符合 Schema 时返回 [`AiError::MalformedStructuredOutput`]。
+    async fn structured_chat(
+        &self,
+        prompt: &str,
+        schema: &serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let full_prompt = format!("{}\n\n{}", prompt, structured::structured_instruction(schema));
+        let response = self.chat(&full_prompt).await?;
+        structured::parse_structured_output(&response, schema)
+    }
+
+    /// 计算文本的 token 数
+    ///
+    /// 用于在发送长对话历史前检查上下文窗口。
+    /// 默认使用 [`estimate_tokens`] 启发式估算，Provider 可提供精确实现。
+    async fn count_tokens(&self, text: &str) -> Result<usize> {
+        Ok(estimate_tokens(text))
+    }
+}
+
+/// 启发式 token 估算（约 4 字节 / token）
+pub fn estimate_tokens(text: &str) -> usize {
+    text.len() / 4
 }
 
 /// AI Provider 工厂
@@ -239,6 +285,20 @@ impl AiProviderFactory {
         Box::new(ClaudeCliProvider::default())
     }
     
+    /// 根据配置创建带成本统计与预算控制的 Provider
+    pub fn tracked(
+        config: AiProviderConfig,
+        memory: Arc<crate::memory::MemoryService>,
+        session_id: impl Into<String>,
+    ) -> Box<dyn AiProvider> {
+        let (pricing, budget_usd, budget_period) = (config.pricing, config.budget_usd, config.budget_period);
+        Box::new(
+            CostTrackingProvider::new(Self::from_config(config), memory, session_id)
+                .with_pricing(pricing)
+                .with_budget(budget_usd, budget_period),
+        )
+    }
+
     /// 根据配置创建 Provider
     pub fn from_config(config: AiProviderConfig) -> Box<dyn AiProvider> {
         match config.provider_type {
@@ -251,6 +311,9 @@ impl AiProviderFactory {
             ProviderType::OpenCode => {
                 Box::new(OpenCodeProvider::new(config.opencode.unwrap_or_default()))
             }
+            ProviderType::Gemini => {
+                Box::new(GeminiProvider::new(config.gemini.unwrap_or_default()))
+            }
         }
     }
 }
@@ -264,6 +327,7 @@ pub enum ProviderType {
     Claude,
     Kimi,
     OpenCode,
+    Gemini,
 }
 
 
@@ -276,6 +340,18 @@ pub struct AiProviderConfig {
     pub claude: Option<ClaudeConfig>,
     pub kimi: Option<KimiConfig>,
     pub opencode: Option<OpenCodeConfig>,
+    #[serde(default)]
+    pub gemini: Option<GeminiConfig>,
+
+    /// 预算上限（USD），None 表示不限制
+    #[serde(default)]
+    pub budget_usd: Option<f64>,
+    /// 预算周期
+    #[serde(default)]
+    pub budget_period: BudgetPeriod,
+    /// Token 单价，用于计算成本
+    #[serde(default)]
+    pub pricing: TokenPricing,
 }
 
 impl Default for AiProviderConfig {
@@ -285,6 +361,10 @@ impl Default for AiProviderConfig {
             claude: Some(ClaudeConfig::default()),
             kimi: None,
             opencode: None,
+            gemini: None,
+            budget_usd: None,
+            budget_period: BudgetPeriod::default(),
+            pricing: TokenPricing::default(),
         }
     }
 }
@@ -532,6 +612,14 @@ impl<P: AiProvider> AiProvider for RagProvider<P> {
     ) -> Result<serde_json::Value> {
         self.inner.generate_json(prompt, schema).await
     }
+
+    async fn structured_chat(
+        &self,
+        prompt: &str,
+        schema: &serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        self.inner.structured_chat(prompt, schema).await
+    }
     
     fn name(&self) -> &str {
         self.inner.name()
@@ -609,6 +697,12 @@ mod tests {
         assert!(req.system.is_none());
     }
     
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcdefgh"), 2);
+    }
+
     #[test]
     fn test_rag_provider_builder() {
         // Just test the builder structure without actual storage
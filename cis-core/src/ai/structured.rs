@@ -0,0 +1,152 @@
+//! 结构化输出
+//!
+//! 为 [`AiProvider::structured_chat`](super::AiProvider::structured_chat) 提供公共逻辑：
+//! 生成 JSON Schema 约束指令、从模型回复中提取 JSON，并按 Schema 校验。
+//!
+//! 校验只覆盖常用关键字（`type`、`properties`、`required`、`items`、`enum`），
+//! 足以拒绝明显不符合约定的回复。
+
+use super::{AiError, Result};
+
+/// 生成要求模型按 Schema 输出 JSON 的指令
+pub fn structured_instruction(schema: &serde_json::Value) -> String {
+    format!(
+        "You must respond with a single JSON value that conforms to this JSON Schema:\n{}\n\n\
+         Respond ONLY with the JSON, without markdown formatting or explanations.",
+        serde_json::to_string_pretty(schema).unwrap_or_else(|_| schema.to_string())
+    )
+}
+
+/// 从回复中解析 JSON 并按 Schema 校验
+///
+/// 回复可以是纯 JSON，也可以包在 ```` ```json ```` 代码块中。
+/// 解析或校验失败时返回 [`AiError::MalformedStructuredOutput`]，携带原始回复。
+pub fn parse_structured_output(raw: &str, schema: &serde_json::Value) -> Result<serde_json::Value> {
+    let malformed = || AiError::MalformedStructuredOutput(raw.to_string());
+
+    let value = extract_json(raw).ok_or_else(malformed)?;
+    if let Err(reason) = validate_schema(&value, schema, "$") {
+        tracing::debug!("Structured output does not match schema: {}", reason);
+        return Err(malformed());
+    }
+    Ok(value)
+}
+
+fn extract_json(raw: &str) -> Option<serde_json::Value> {
+    let trimmed = raw.trim();
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Some(value);
+    }
+
+    // ```json ... ``` 代码块
+    let start = trimmed.find("```")?;
+    let after = &trimmed[start + 3..];
+    let end = after.find("```")?;
+    let block = after[..end].trim();
+    let block = block.strip_prefix("json").unwrap_or(block);
+    serde_json::from_str(block.trim()).ok()
+}
+
+/// 按 Schema 校验 JSON 值，返回第一个不符合项
+pub fn validate_schema(
+    value: &serde_json::Value,
+    schema: &serde_json::Value,
+    path: &str,
+) -> std::result::Result<(), String> {
+    use serde_json::Value;
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            return Err(format!("{}: value not in enum", path));
+        }
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let matches = |ty: &str| match ty {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "boolean" => value.is_boolean(),
+            "null" => value.is_null(),
+            _ => true,
+        };
+        let ok = match expected {
+            Value::String(ty) => matches(ty),
+            Value::Array(types) => types.iter().filter_map(Value::as_str).any(matches),
+            _ => true,
+        };
+        if !ok {
+            return Err(format!("{}: expected type {}", path, expected));
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(key) {
+                    return Err(format!("{}: missing required property '{}'", path, key));
+                }
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (key, property_schema) in properties {
+                if let Some(property) = object.get(key) {
+                    validate_schema(property, property_schema, &format!("{}.{}", path, key))?;
+                }
+            }
+        }
+    }
+
+    if let (Some(items), Some(item_schema)) = (value.as_array(), schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            validate_schema(item, item_schema, &format!("{}[{}]", path, i))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "required": ["name", "tags"],
+            "properties": {
+                "name": { "type": "string" },
+                "priority": { "type": "string", "enum": ["low", "high"] },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse_structured_output() {
+        let value = parse_structured_output(r#"{"name": "a", "tags": ["x"]}"#, &schema()).unwrap();
+        assert_eq!(value["name"], "a");
+
+        let fenced = "Sure:\n```json\n{\"name\": \"b\", \"tags\": [], \"priority\": \"high\"}\n```";
+        assert_eq!(parse_structured_output(fenced, &schema()).unwrap()["priority"], "high");
+    }
+
+    #[test]
+    fn test_malformed_structured_output() {
+        for raw in [
+            "not json",
+            r#"{"name": "a"}"#,
+            r#"{"name": 1, "tags": []}"#,
+            r#"{"name": "a", "tags": [1]}"#,
+            r#"{"name": "a", "tags": [], "priority": "urgent"}"#,
+        ] {
+            match parse_structured_output(raw, &schema()) {
+                Err(AiError::MalformedStructuredOutput(text)) => assert_eq!(text, raw),
+                other => panic!("expected malformed output for {}, got {:?}", raw, other),
+            }
+        }
+    }
+}
@@ -370,6 +370,29 @@ impl ConfigLoader {
             }
         }
 
+        // Merge memory watermark config
+        if let Some(watermark) = file.memory_watermark {
+            if let Some(mb) = watermark.warn_threshold_mb {
+                base.memory_watermark.warn_threshold_mb = mb;
+            }
+            if let Some(mb) = watermark.critical_threshold_mb {
+                base.memory_watermark.critical_threshold_mb = mb;
+            }
+            if let Some(secs) = watermark.check_interval_secs {
+                base.memory_watermark.check_interval_secs = secs;
+            }
+        }
+
+        // Merge skill dispatch config
+        if let Some(dispatch) = file.skill_dispatch {
+            if let Some(threshold) = dispatch.similarity_threshold {
+                base.skill_dispatch.similarity_threshold = threshold;
+            }
+            if let Some(top_k) = dispatch.top_k {
+                base.skill_dispatch.top_k = top_k;
+            }
+        }
+
         base
     }
 
@@ -457,6 +480,28 @@ impl ConfigLoader {
             config.security.require_https = parse_bool(&val, "SECURITY_REQUIRE_HTTPS")?;
         }
 
+        // Memory watermark environment variables
+        if let Ok(val) = env::var(format!("{}_MEMORY_WATERMARK_WARN_MB", prefix)) {
+            config.memory_watermark.warn_threshold_mb = parse_u64(&val, "MEMORY_WATERMARK_WARN_MB")?;
+        }
+        if let Ok(val) = env::var(format!("{}_MEMORY_WATERMARK_CRITICAL_MB", prefix)) {
+            config.memory_watermark.critical_threshold_mb =
+                parse_u64(&val, "MEMORY_WATERMARK_CRITICAL_MB")?;
+        }
+        if let Ok(val) = env::var(format!("{}_MEMORY_WATERMARK_INTERVAL_SECS", prefix)) {
+            config.memory_watermark.check_interval_secs =
+                parse_u64(&val, "MEMORY_WATERMARK_INTERVAL_SECS")?;
+        }
+
+        // Skill dispatch environment variables
+        if let Ok(val) = env::var(format!("{}_SKILL_DISPATCH_THRESHOLD", prefix)) {
+            config.skill_dispatch.similarity_threshold =
+                parse_f32(&val, "SKILL_DISPATCH_THRESHOLD")?;
+        }
+        if let Ok(val) = env::var(format!("{}_SKILL_DISPATCH_TOP_K", prefix)) {
+            config.skill_dispatch.top_k = parse_usize(&val, "SKILL_DISPATCH_TOP_K")?;
+        }
+
         // WASM environment variables
         if let Ok(val) = env::var(format!("{}_WASM_MAX_MEMORY", prefix)) {
             config.wasm.max_memory = parse_usize(&val, "WASM_MAX_MEMORY")?;
@@ -633,6 +678,15 @@ port_mapping_enabled = true
 [p2p.quic]
 enabled = true
 max_streams = 100
+
+[memory_watermark]
+warn_threshold_mb = 512
+critical_threshold_mb = 1024
+check_interval_secs = 300
+
+[skill_dispatch]
+similarity_threshold = 0.75
+top_k = 5
 "#;
 
         Ok(template.to_string())
@@ -679,6 +733,16 @@ fn parse_usize(s: &str, name: &str) -> Result<usize> {
     })
 }
 
+/// Parse an f32 from string
+fn parse_f32(s: &str, name: &str) -> Result<f32> {
+    s.parse::<f32>().map_err(|e| {
+        CisError::configuration(format!(
+            "Invalid {} '{}': must be a valid number. Error: {}",
+            name, s, e
+        ))
+    })
+}
+
 /// Parse a boolean from string
 fn parse_bool(s: &str, name: &str) -> Result<bool> {
     match s.to_lowercase().as_str() {
@@ -705,6 +769,23 @@ struct FileConfig {
     pub wasm: Option<FileWasmConfig>,
     #[serde(default)]
     pub p2p: Option<FileP2PConfig>,
+    #[serde(default)]
+    pub memory_watermark: Option<FileMemoryWatermarkConfig>,
+    #[serde(default)]
+    pub skill_dispatch: Option<FileSkillDispatchConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct FileSkillDispatchConfig {
+    pub similarity_threshold: Option<f32>,
+    pub top_k: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct FileMemoryWatermarkConfig {
+    pub warn_threshold_mb: Option<u64>,
+    pub critical_threshold_mb: Option<u64>,
+    pub check_interval_secs: Option<u64>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -101,6 +101,83 @@ impl MemoryConflictConfig {
     }
 }
 
+/// 记忆数据库水位告警配置
+///
+/// 数据库大小按 `PRAGMA page_count * page_size` 计算。
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MemoryWatermarkConfig {
+    /// 超过该大小（MB）发出 Warning 事件
+    pub warn_threshold_mb: u64,
+
+    /// 超过该大小（MB）发出 Critical 事件
+    pub critical_threshold_mb: u64,
+
+    /// 检查间隔（秒）
+    pub check_interval_secs: u64,
+}
+
+impl Default for MemoryWatermarkConfig {
+    fn default() -> Self {
+        Self {
+            warn_threshold_mb: 512,
+            critical_threshold_mb: 1024,
+            check_interval_secs: 300,
+        }
+    }
+}
+
+impl ValidateConfig for MemoryWatermarkConfig {
+    fn validate(&self) -> Result<()> {
+        if self.warn_threshold_mb == 0 {
+            return Err(validation_error("memory_watermark.warn_threshold_mb cannot be zero"));
+        }
+        if self.critical_threshold_mb < self.warn_threshold_mb {
+            return Err(validation_error(format!(
+                "memory_watermark.critical_threshold_mb ({}) must be >= warn_threshold_mb ({})",
+                self.critical_threshold_mb, self.warn_threshold_mb
+            )));
+        }
+        validate_positive_duration(
+            std::time::Duration::from_secs(self.check_interval_secs),
+            "memory_watermark.check_interval_secs",
+        )
+    }
+}
+
+/// `cis skill do` 语义匹配配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SkillDispatchConfig {
+    /// 最佳候选的余弦相似度达到该值时直接执行，否则列出候选并请求确认
+    pub similarity_threshold: f32,
+
+    /// 返回的候选数量上限
+    pub top_k: usize,
+}
+
+impl Default for SkillDispatchConfig {
+    fn default() -> Self {
+        Self {
+            similarity_threshold: 0.75,
+            top_k: 5,
+        }
+    }
+}
+
+impl ValidateConfig for SkillDispatchConfig {
+    fn validate(&self) -> Result<()> {
+        if !(0.0..=1.0).contains(&self.similarity_threshold) {
+            return Err(validation_error(format!(
+                "skill_dispatch.similarity_threshold must be within [0.0, 1.0] (got {})",
+                self.similarity_threshold
+            )));
+        }
+        if self.top_k == 0 {
+            return Err(validation_error("skill_dispatch.top_k cannot be zero"));
+        }
+        Ok(())
+    }
+}
+
 use crate::error::{CisError, Result};
 
 /// Main configuration structure
@@ -129,6 +206,14 @@ pub struct Config {
     /// Memory conflict configuration (P1.7.0 任务组 0.5)
     #[serde(default)]
     pub memory_conflict: MemoryConflictConfig,
+
+    /// Memory database watermark alerts
+    #[serde(default)]
+    pub memory_watermark: MemoryWatermarkConfig,
+
+    /// Semantic skill dispatch (`cis skill do`)
+    #[serde(default)]
+    pub skill_dispatch: SkillDispatchConfig,
 }
 
 impl Default for Config {
@@ -140,6 +225,8 @@ impl Default for Config {
             wasm: WasmConfig::default(),
             p2p: P2PConfig::default(),
             memory_conflict: MemoryConflictConfig::default(),  // 默认强制检测
+            memory_watermark: MemoryWatermarkConfig::default(),
+            skill_dispatch: SkillDispatchConfig::default(),
         }
     }
 }
@@ -162,6 +249,8 @@ impl Config {
         self.security.validate()?;
         self.wasm.validate()?;
         self.p2p.validate()?;
+        self.memory_watermark.validate()?;
+        self.skill_dispatch.validate()?;
 
         // 验证 memory_conflict 配置（P1.7.0 任务组 0.5）
         let _validated_conflict = self.memory_conflict.validate()?;
@@ -150,6 +150,8 @@ impl ServiceContainer {
             external_address: None,
             transport_config: crate::p2p::transport_secure::SecureTransportConfig::default(),
             node_keys: None,
+            max_bytes_per_second: 0,
+            reputation: Default::default(),
         };
         
         let network: NetworkServiceRef = Arc::new(
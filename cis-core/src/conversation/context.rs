@@ -8,6 +8,8 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::ai::embedding::{cosine_similarity, EmbeddingService};
+use crate::ai::{AiProvider, Message};
 use crate::error::{CisError, Result};
 use crate::storage::conversation_db::{Conversation, ConversationDb};
 use crate::vector::VectorStorage;
@@ -643,6 +645,106 @@ impl ConversationContext {
     ) -> Result<String> {
         self.add_assistant_message_with_index(content, None).await
     }
+
+    /// 多 Provider 共识对话
+    ///
+    /// 将同一请求并发发送给所有 Provider，对回复做嵌入后两两比较：
+    /// 与某条回复余弦相似度超过 [`CONSENSUS_SIMILARITY_THRESHOLD`] 的回复（含自身）
+    /// 不少于 `quorum` 条时返回 [`ConsensusResult::Agree`]，否则返回全部回复。
+    /// 调用失败的 Provider 不参与投票。嵌入使用上下文的向量存储。
+    pub async fn consensus_chat(
+        &self,
+        providers: &[Arc<dyn AiProvider>],
+        messages: &[Message],
+        quorum: usize,
+    ) -> Result<ConsensusResult> {
+        let storage = self.vector_storage.as_ref().ok_or_else(|| {
+            CisError::configuration("Consensus chat requires vector storage for embeddings")
+        })?;
+        self.consensus_chat_with_embedding(providers, messages, quorum, storage.embedding_service().as_ref())
+            .await
+    }
+
+    /// 使用指定嵌入服务的共识对话
+    pub async fn consensus_chat_with_embedding(
+        &self,
+        providers: &[Arc<dyn AiProvider>],
+        messages: &[Message],
+        quorum: usize,
+        embedding: &dyn EmbeddingService,
+    ) -> Result<ConsensusResult> {
+        if quorum == 0 || quorum > providers.len() {
+            return Err(CisError::invalid_input(format!(
+                "Quorum must be between 1 and {} (got {})",
+                providers.len(),
+                quorum
+            )));
+        }
+
+        let system = self.project_context_prompt().unwrap_or_default();
+        let replies = futures::future::join_all(
+            providers.iter().map(|p| p.chat_with_context(&system, messages)),
+        )
+        .await;
+
+        let responses: Vec<ProviderResponse> = providers
+            .iter()
+            .zip(replies)
+            .filter_map(|(provider, reply)| match reply {
+                Ok(content) => Some(ProviderResponse {
+                    provider: provider.name().to_string(),
+                    content,
+                }),
+                Err(e) => {
+                    tracing::warn!("Provider {} failed during consensus chat: {}", provider.name(), e);
+                    None
+                }
+            })
+            .collect();
+        if responses.is_empty() {
+            return Err(CisError::other("All providers failed during consensus chat"));
+        }
+
+        let texts: Vec<&str> = responses.iter().map(|r| r.content.as_str()).collect();
+        let vectors = embedding.batch_embed(&texts).await?;
+
+        // 每条回复的支持数（与之相似的回复数，含自身），取支持最多的一条
+        let best = (0..vectors.len())
+            .map(|i| {
+                let support = vectors
+                    .iter()
+                    .filter(|v| cosine_similarity(&vectors[i], v) > CONSENSUS_SIMILARITY_THRESHOLD)
+                    .count();
+                (i, support)
+            })
+            .max_by_key(|&(i, support)| (support, std::cmp::Reverse(i)));
+
+        match best {
+            Some((i, support)) if support >= quorum => {
+                Ok(ConsensusResult::Agree(responses.into_iter().nth(i).expect("index in range")))
+            }
+            _ => Ok(ConsensusResult::Disagree(responses)),
+        }
+    }
+}
+
+/// 共识判定的余弦相似度阈值
+pub const CONSENSUS_SIMILARITY_THRESHOLD: f32 = 0.9;
+
+/// 单个 Provider 的回复
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderResponse {
+    pub provider: String,
+    pub content: String,
+}
+
+/// 多 Provider 共识结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConsensusResult {
+    /// 达到法定数的回复语义一致，取其中支持最多的一条
+    Agree(ProviderResponse),
+    /// 未达成共识，返回所有成功的回复
+    Disagree(Vec<ProviderResponse>),
 }
 
 /// 可恢复会话信息
@@ -977,4 +1079,72 @@ mod tests {
             "这是..."
         );
     }
+
+    /// 按文本预设向量的嵌入服务
+    struct KeyedEmbedding(std::collections::HashMap<String, Vec<f32>>);
+
+    #[async_trait::async_trait]
+    impl EmbeddingService for KeyedEmbedding {
+        async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+            Ok(self.0.get(text).cloned().unwrap_or_else(|| vec![0.0, 0.0, 1.0]))
+        }
+
+        async fn batch_embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+            let mut vectors = Vec::new();
+            for text in texts {
+                vectors.push(self.embed(text).await?);
+            }
+            Ok(vectors)
+        }
+    }
+
+    fn provider(reply: &str) -> Arc<dyn AiProvider> {
+        use crate::{MockAiProvider, MockResponse};
+        Arc::new(MockAiProvider::with_responses(vec![MockResponse::Return(reply.to_string())]))
+    }
+
+    #[tokio::test]
+    async fn test_consensus_chat() {
+        let ctx = ConversationContext::new("conv-008".to_string(), "session-001".to_string());
+        let embedding = KeyedEmbedding(
+            [
+                ("部署在周五".to_string(), vec![1.0, 0.0, 0.0]),
+                ("周五部署".to_string(), vec![0.99, 0.05, 0.0]),
+                ("下周一部署".to_string(), vec![0.0, 1.0, 0.0]),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let messages = vec![Message::user("什么时候部署？")];
+        let providers = vec![
+            provider("部署在周五"),
+            provider("下周一部署"),
+            provider("周五部署"),
+        ];
+
+        match ctx
+            .consensus_chat_with_embedding(&providers, &messages, 2, &embedding)
+            .await
+            .unwrap()
+        {
+            ConsensusResult::Agree(response) => assert_eq!(response.content, "部署在周五"),
+            other => panic!("expected agreement, got {:?}", other),
+        }
+
+        match ctx
+            .consensus_chat_with_embedding(&providers, &messages, 3, &embedding)
+            .await
+            .unwrap()
+        {
+            ConsensusResult::Disagree(responses) => assert_eq!(responses.len(), 3),
+            other => panic!("expected disagreement, got {:?}", other),
+        }
+
+        assert!(ctx
+            .consensus_chat_with_embedding(&providers, &messages, 4, &embedding)
+            .await
+            .is_err());
+        // 没有向量存储时无法计算嵌入
+        assert!(ctx.consensus_chat(&providers, &messages, 2).await.is_err());
+    }
 }
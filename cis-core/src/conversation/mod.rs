@@ -25,4 +25,5 @@ pub use crate::storage::conversation_db::{Conversation, ConversationDb, Conversa
 pub use context::{
     ConversationContext, ContextMessage, MessageRole, 
     RecoverableSession, SessionRecovery,
+    ConsensusResult, ProviderResponse, CONSENSUS_SIMILARITY_THRESHOLD,
 };
@@ -94,6 +94,10 @@ pub enum CisError {
     #[error("Skill not found: {0}")]
     SkillNotFound(String),
 
+    /// Skill dependency cannot be satisfied (dependency name, version requirement)
+    #[error("Unresolved skill dependency: {0} {1}")]
+    UnresolvedDependency(String, String),
+
     /// AI/LLM errors
     #[error("AI error: {0}")]
     Ai(String),
@@ -114,6 +118,10 @@ pub enum CisError {
     #[error("Encryption error: {0}")]
     Encryption(String),
 
+    /// Circuit breaker is open for an external service endpoint
+    #[error("Circuit open: {0}")]
+    CircuitOpen(String),
+
     /// Resource exhausted errors (P0-6: memory limits, etc.)
     #[error("Resource exhausted: {0}")]
     ResourceExhausted(String),
@@ -219,6 +227,16 @@ impl CisError {
         Self::SkillNotFound(msg.into())
     }
 
+    /// Create a new unresolved skill dependency error
+    pub fn unresolved_dependency(name: impl Into<String>, version_req: impl Into<String>) -> Self {
+        Self::UnresolvedDependency(name.into(), version_req.into())
+    }
+
+    /// Create a new circuit open error
+    pub fn circuit_open(endpoint: impl Into<String>) -> Self {
+        Self::CircuitOpen(endpoint.into())
+    }
+
     /// Create a new AI error
     pub fn ai(msg: impl Into<String>) -> Self {
         Self::Ai(msg.into())
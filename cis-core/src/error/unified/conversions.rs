@@ -171,6 +171,16 @@ impl From<LegacyCisError> for CisError {
                 format!("Cloud Anchor error: {}", message),
             ),
             LegacyCisError::SkillNotFound(_) => Self::skill_not_found(message),
+            LegacyCisError::CircuitOpen(_) => Self::new(
+                ErrorCategory::Network,
+                "000",
+                message,
+            ),
+            LegacyCisError::UnresolvedDependency(..) => Self::new(
+                ErrorCategory::Skill,
+                "000",
+                message,
+            ),
             LegacyCisError::Ai(_) => Self::new(
                 ErrorCategory::Ai,
                 "000",
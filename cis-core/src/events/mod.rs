@@ -18,6 +18,7 @@
 //! | `SkillCompletedEvent` | Skill 执行完成 | Skill 执行结果 |
 //! | `AgentOnlineEvent` | Agent 上线 | 节点 Agent 上线通知 |
 //! | `FederationTaskEvent` | 联邦任务 | 跨节点任务分发 |
+//! | `SystemEvent` | 系统事件 | 子系统状态通知 |
 
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
@@ -40,6 +41,8 @@ pub enum EventWrapper {
     AgentOnline(AgentOnlineEvent),
     /// 联邦任务事件
     FederationTask(FederationTaskEvent),
+    /// 系统事件
+    System(SystemEvent),
 }
 
 impl EventWrapper {
@@ -51,6 +54,7 @@ impl EventWrapper {
             EventWrapper::SkillCompleted(_) => "skill.completed",
             EventWrapper::AgentOnline(_) => "agent.online",
             EventWrapper::FederationTask(_) => "federation.task",
+            EventWrapper::System(_) => "system.event",
         }
     }
 
@@ -62,6 +66,7 @@ impl EventWrapper {
             EventWrapper::SkillCompleted(e) => &e.event_id,
             EventWrapper::AgentOnline(e) => &e.event_id,
             EventWrapper::FederationTask(e) => &e.event_id,
+            EventWrapper::System(e) => &e.event_id,
         }
     }
 
@@ -73,6 +78,7 @@ impl EventWrapper {
             EventWrapper::SkillCompleted(e) => e.timestamp,
             EventWrapper::AgentOnline(e) => e.timestamp,
             EventWrapper::FederationTask(e) => e.timestamp,
+            EventWrapper::System(e) => e.timestamp,
         }
     }
 }
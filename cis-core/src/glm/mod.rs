@@ -15,10 +15,14 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+pub mod stream;
+
+pub use stream::CisEvent;
+
 /// GLM API 配置
 #[derive(Debug, Clone)]
 pub struct GlmApiConfig {
@@ -31,6 +35,8 @@ pub struct GlmApiConfig {
     pub default_room_id: String,
     /// 任务超时时间（秒）
     pub task_timeout_secs: u64,
+    /// 事件流 WebSocket 共享 API Key（未设置时无法启动事件流）
+    pub api_key: Option<String>,
 }
 
 impl Default for GlmApiConfig {
@@ -43,6 +49,7 @@ impl Default for GlmApiConfig {
             ],
             default_room_id: "!default:matrix.org".to_string(),
             task_timeout_secs: 300,
+            api_key: None,
         }
     }
 }
@@ -65,6 +72,8 @@ pub struct GlmApiState {
     skill_manager: Option<Arc<crate::skill::SkillManager>>,
     /// Matrix HTTP Client（用于发送消息到 Matrix Room）
     matrix_client: Option<MatrixHttpClient>,
+    /// 事件广播（推送到事件流客户端）
+    events: broadcast::Sender<CisEvent>,
 }
 
 /// Matrix HTTP Client 配置
@@ -406,6 +415,39 @@ pub struct DagStatusResponse {
     pub raw_data: Value,
 }
 
+/// DAG 运行控制动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DagRunControl {
+    Pause,
+    Resume,
+}
+
+impl DagRunControl {
+    /// 从路径参数解析
+    pub fn parse(action: &str) -> Option<Self> {
+        match action {
+            "pause" => Some(Self::Pause),
+            "resume" => Some(Self::Resume),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pause => "pause",
+            Self::Resume => "resume",
+        }
+    }
+
+    /// 对应的 dag-executor Skill 事件名
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            Self::Pause => "dag:pause",
+            Self::Resume => "dag:resume",
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AnalyzeDagRequest {
     pub dag_id: String,
@@ -422,6 +464,7 @@ impl GlmApiServer {
             default_room_id: config.default_room_id.clone(),
             skill_manager: None,
             matrix_client: None,
+            events: broadcast::channel(stream::EVENT_CHANNEL_CAPACITY).0,
         });
 
         Self { config, state }
@@ -438,6 +481,7 @@ impl GlmApiServer {
             default_room_id: config.default_room_id.clone(),
             skill_manager: Some(skill_manager),
             matrix_client: None,
+            events: broadcast::channel(stream::EVENT_CHANNEL_CAPACITY).0,
         });
 
         Self { config, state }
@@ -462,6 +506,7 @@ impl GlmApiServer {
             default_room_id: config.default_room_id.clone(),
             skill_manager: None,
             matrix_client,
+            events: broadcast::channel(stream::EVENT_CHANNEL_CAPACITY).0,
         });
 
         Self { config, state }
@@ -487,6 +532,7 @@ impl GlmApiServer {
             default_room_id: config.default_room_id.clone(),
             skill_manager: Some(skill_manager),
             matrix_client,
+            events: broadcast::channel(stream::EVENT_CHANNEL_CAPACITY).0,
         });
 
         Self { config, state }
@@ -511,6 +557,16 @@ impl GlmApiServer {
 }
 
 impl GlmApiState {
+    /// 发布事件到事件流（没有订阅者时丢弃）
+    pub fn publish_event(&self, event: CisEvent) {
+        let _ = self.events.send(event);
+    }
+
+    /// 订阅事件流
+    pub fn subscribe_events(&self) -> broadcast::Receiver<CisEvent> {
+        self.events.subscribe()
+    }
+
     /// 执行 DAG 转发逻辑（Task 4.1 & 4.2）
     /// 
     /// 逻辑：
@@ -585,6 +641,10 @@ impl GlmApiState {
         };
 
         self.tasks.write().await.insert(task_id, task.clone());
+        self.publish_event(CisEvent::new(
+            "task.status",
+            json!({ "task_id": task.task_id, "status": task.status }),
+        ));
 
         // 发送任务到 Room（这里简化处理，实际应通过 Matrix 发送）
         info!("Task {} created for room {:?}", task.task_id, task.room_id);
@@ -619,6 +679,10 @@ impl GlmApiState {
             task.result = result;
             task.error = error;
             task.updated_at = chrono::Local::now().to_rfc3339();
+            self.publish_event(CisEvent::new(
+                "task.status",
+                json!({ "task_id": task_id, "status": task.status, "error": task.error }),
+            ));
         }
         Ok(())
     }
@@ -646,6 +710,10 @@ impl GlmApiState {
             .write()
             .await
             .insert(req.dag_id.clone(), pending.clone());
+        self.publish_event(CisEvent::new(
+            "dag.status",
+            json!({ "dag_id": pending.dag_id, "status": "pending_confirmation" }),
+        ));
 
         Ok(pending)
     }
@@ -667,6 +735,10 @@ impl GlmApiState {
             // 调用 dag-executor skill 执行 DAG
             let run_id = self.execute_dag_with_skill(dag).await?;
             info!("DAG {} execution started with run_id: {}", dag_id, run_id);
+            self.publish_event(CisEvent::new(
+                "dag.status",
+                json!({ "dag_id": dag_id, "run_id": run_id, "status": "running" }),
+            ));
             
             return Ok(Some((dag.clone(), run_id)));
         }
@@ -687,6 +759,10 @@ impl GlmApiState {
             command: t.command.clone(),
             depends_on: t.depends_on.clone(),
             env: std::collections::HashMap::new(),
+            per_task_retry: None,
+            timeout_secs: None,
+            level: None,
+            fail_fast: false,
         }).collect();
         
         let spec = DagSpec::new(dag.dag_id.clone(), tasks);
@@ -751,6 +827,33 @@ impl GlmApiState {
         Ok(())
     }
 
+    /// 暂停/恢复 DAG 运行
+    ///
+    /// 转发 `dag:pause` / `dag:resume` 事件到 dag-executor，由其通知 Worker
+    pub async fn control_dag_run(&self, run_id: &str, control: DagRunControl) -> anyhow::Result<()> {
+        use crate::skill::Event;
+
+        let Some(ref skill_manager) = self.skill_manager else {
+            error!("SkillManager not available, cannot {} DAG run", control.as_str());
+            return Err(anyhow::anyhow!("SkillManager not available"));
+        };
+
+        let event = Event::Custom {
+            name: control.event_name().to_string(),
+            data: json!({ "run_id": run_id }),
+        };
+
+        skill_manager.send_event("dag-executor", event).await
+            .map_err(|e| anyhow::anyhow!("Failed to send event to dag-executor: {}", e))?;
+
+        info!("DAG run {} {} requested", run_id, control.as_str());
+        self.publish_event(CisEvent::new(
+            "dag.status",
+            json!({ "run_id": run_id, "status": control.as_str() }),
+        ));
+        Ok(())
+    }
+
     /// 获取 DAG 状态
     pub async fn get_dag_status(&self, dag_id: &str) -> Option<DagStatusResponse> {
         // 简化处理，实际应从 scheduler 获取
@@ -818,6 +921,7 @@ fn create_router(state: Arc<GlmApiState>, config: GlmApiConfig) -> Router {
         .route("/api/v1/dag/:dag_id/confirm", post(confirm_dag))
         .route("/api/v1/dag/:dag_id/status", get(query_dag_status))
         .route("/api/v1/dag/:dag_id/analyze", post(analyze_dag))
+        .route("/api/v1/dag/run/:run_id/:action", post(control_dag_run))
         .route("/api/v1/pending", get(list_pending))
         .layer(axum::middleware::from_fn_with_state(
             config.clone(),
@@ -978,6 +1082,26 @@ async fn query_dag_status(
     }
 }
 
+/// 暂停/恢复 DAG 运行
+async fn control_dag_run(
+    State(state): State<Arc<GlmApiState>>,
+    Path((run_id, action)): Path<(String, String)>,
+) -> Result<Json<Value>, StatusCode> {
+    let control = DagRunControl::parse(&action).ok_or(StatusCode::NOT_FOUND)?;
+
+    match state.control_dag_run(&run_id, control).await {
+        Ok(()) => Ok(Json(json!({
+            "success": true,
+            "run_id": run_id,
+            "action": control.as_str(),
+        }))),
+        Err(e) => {
+            error!("Failed to {} DAG run {}: {}", control.as_str(), run_id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 /// 分析 DAG 卡点
 async fn analyze_dag(
     Path(dag_id): Path<String>,
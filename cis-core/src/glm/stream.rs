@@ -0,0 +1,216 @@
+//! GLM 事件流 - WebSocket 推送
+//!
+//! 客户端通过 WebSocket 连接后，服务端以换行分隔的 JSON（每帧一行）推送 [`CisEvent`]：
+//!
+//! - 握手时校验共享 API Key（`Authorization: Bearer <key>` 或 `?api_key=<key>`）
+//! - 客户端发送 `{"subscribe": ["dag.status", "memory.change"]}` 只接收指定类型，
+//!   空列表恢复为接收全部事件
+
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::{http, Message};
+use tracing::{debug, info, warn};
+
+use super::GlmApiServer;
+use crate::events::EventWrapper;
+
+/// 事件广播通道容量
+pub(crate) const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// 推送给事件流客户端的事件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CisEvent {
+    /// 事件类型（如 `dag.status`、`task.status`）
+    pub event_type: String,
+    /// 事件数据
+    pub data: Value,
+    /// 时间戳（RFC 3339）
+    pub timestamp: String,
+}
+
+impl CisEvent {
+    /// 创建事件
+    pub fn new(event_type: impl Into<String>, data: Value) -> Self {
+        Self {
+            event_type: event_type.into(),
+            data,
+            timestamp: chrono::Local::now().to_rfc3339(),
+        }
+    }
+
+    /// 序列化为一行 JSON（以换行结尾）
+    pub fn to_ndjson(&self) -> String {
+        let mut line = serde_json::to_string(self).unwrap_or_default();
+        line.push('\n');
+        line
+    }
+}
+
+impl From<&EventWrapper> for CisEvent {
+    fn from(event: &EventWrapper) -> Self {
+        let data = serde_json::to_value(event)
+            .ok()
+            .and_then(|mut v| v.get_mut("payload").map(Value::take))
+            .unwrap_or(Value::Null);
+        Self {
+            event_type: event.event_type().to_string(),
+            data,
+            timestamp: event.timestamp().to_rfc3339(),
+        }
+    }
+}
+
+/// 客户端订阅请求
+#[derive(Debug, Deserialize)]
+struct SubscribeRequest {
+    subscribe: Vec<String>,
+}
+
+impl GlmApiServer {
+    /// 启动 WebSocket 事件流服务（会阻塞）
+    pub async fn start_websocket_server(&self, addr: SocketAddr) -> anyhow::Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        self.serve_websocket(listener).await
+    }
+
+    /// 在已绑定的监听器上提供事件流服务
+    pub async fn serve_websocket(&self, listener: TcpListener) -> anyhow::Result<()> {
+        let api_key: Arc<str> = match self.config.api_key.as_deref() {
+            Some(key) if !key.is_empty() => key.into(),
+            _ => anyhow::bail!("GLM event stream requires an API key (GlmApiConfig::api_key)"),
+        };
+
+        info!("GLM event stream listening on {}", listener.local_addr()?);
+
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let api_key = Arc::clone(&api_key);
+            let events = self.state.subscribe_events();
+            tokio::spawn(async move {
+                if let Err(e) = handle_connection(stream, api_key, events).await {
+                    debug!("GLM event stream connection {} closed: {}", peer, e);
+                }
+            });
+        }
+    }
+}
+
+/// 校验握手请求中的 API Key
+fn is_authorized(req: &Request, api_key: &str) -> bool {
+    let bearer = req
+        .headers()
+        .get(http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    if bearer == Some(api_key) {
+        return true;
+    }
+
+    req.uri()
+        .query()
+        .map(|q| q.split('&').any(|pair| pair.strip_prefix("api_key=") == Some(api_key)))
+        .unwrap_or(false)
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    api_key: Arc<str>,
+    mut events: broadcast::Receiver<CisEvent>,
+) -> anyhow::Result<()> {
+    let callback = |req: &Request, resp: Response| -> Result<Response, ErrorResponse> {
+        if is_authorized(req, &api_key) {
+            Ok(resp)
+        } else {
+            warn!("GLM event stream: rejected unauthenticated connection");
+            let mut error = ErrorResponse::new(Some("invalid API key".to_string()));
+            *error.status_mut() = http::StatusCode::UNAUTHORIZED;
+            Err(error)
+        }
+    };
+    let ws = tokio_tungstenite::accept_hdr_async(stream, callback).await?;
+    let (mut sink, mut source) = ws.split();
+
+    // None 表示接收全部事件
+    let mut subscription: Option<HashSet<String>> = None;
+
+    loop {
+        tokio::select! {
+            msg = source.next() => match msg {
+                Some(Ok(Message::Text(text))) => {
+                    match serde_json::from_str::<SubscribeRequest>(&text) {
+                        Ok(req) if req.subscribe.is_empty() => subscription = None,
+                        Ok(req) => subscription = Some(req.subscribe.into_iter().collect()),
+                        Err(e) => {
+                            let error = json!({ "error": format!("invalid subscribe request: {}", e) });
+                            sink.send(Message::Text(format!("{}\n", error))).await?;
+                        }
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.into()),
+            },
+            event = events.recv() => match event {
+                Ok(event) => {
+                    let wanted = subscription
+                        .as_ref()
+                        .map_or(true, |types| types.contains(&event.event_type));
+                    if wanted {
+                        sink.send(Message::Text(event.to_ndjson())).await?;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("GLM event stream client lagged, {} events dropped", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_authorized() {
+        let req = |uri: &str, auth: Option<&str>| {
+            let mut builder = Request::builder().uri(uri);
+            if let Some(auth) = auth {
+                builder = builder.header("Authorization", auth);
+            }
+            builder.body(()).unwrap()
+        };
+
+        assert!(is_authorized(&req("/", Some("Bearer secret")), "secret"));
+        assert!(is_authorized(&req("/?x=1&api_key=secret", None), "secret"));
+        assert!(!is_authorized(&req("/", Some("Bearer wrong")), "secret"));
+        assert!(!is_authorized(&req("/?api_key=secrets", None), "secret"));
+        assert!(!is_authorized(&req("/", None), "secret"));
+    }
+
+    #[test]
+    fn test_event_from_wrapper() {
+        let wrapper = EventWrapper::System(crate::events::SystemEvent::new(
+            crate::events::SystemEventLevel::Info,
+            "scheduler",
+            "scheduler started",
+            "test",
+        ));
+        let event = CisEvent::from(&wrapper);
+
+        assert_eq!(event.event_type, "system.event");
+        assert!(event.data.is_object());
+        assert!(event.to_ndjson().ends_with('\n'));
+    }
+}
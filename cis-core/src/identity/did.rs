@@ -8,6 +8,7 @@ use std::path::Path;
 use std::fs;
 use crate::error::{CisError, Result};
 use crate::matrix::error::{MatrixError, MatrixResult};
+use super::document::{did_method, DIDDocument, DIDDocumentStore, IdentityError};
 
 /// 设置密钥文件权限（Unix + Windows）
 ///
@@ -16,7 +17,7 @@ use crate::matrix::error::{MatrixError, MatrixResult};
 /// - **Unix**: 设置权限为 0o600 (仅所有者可读写)
 /// - **Windows**: 使用 icacls 禁用继承并限制访问
 /// - **验证**: 权限设置后进行验证，确保生效
-fn set_key_permissions(key_path: &Path) -> Result<()> {
+pub(crate) fn set_key_permissions(key_path: &Path) -> Result<()> {
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
@@ -336,6 +337,62 @@ impl DIDManager {
         hex::encode(signature.to_bytes())
     }
     
+    /// 生成本节点的 DID 文档
+    pub fn document(&self) -> DIDDocument {
+        DIDDocument::new(self.did.clone(), &self.verifying_key())
+    }
+
+    /// 对 DID 文档自签名
+    pub fn sign_document(&self, document: &DIDDocument) -> Vec<u8> {
+        self.sign(&document.signing_bytes()).to_bytes().to_vec()
+    }
+
+    /// 验证远程 DID 文档的自签名
+    ///
+    /// 文档必须属于 `did`，文档公钥须与 DID 中的公钥前缀一致，
+    /// 且签名由该公钥产生。格式错误或不匹配返回 `Ok(false)`。
+    pub fn verify_did_document(did: &str, document: &DIDDocument, signature: &[u8]) -> Result<bool> {
+        if did_method(did)? != "cis" {
+            return Err(IdentityError::UnsupportedDIDMethod(did.to_string()).into());
+        }
+        let Some((_, pub_key_short)) = Self::parse_did(did) else {
+            return Err(IdentityError::InvalidDID(did.to_string()).into());
+        };
+
+        if document.id != did {
+            return Ok(false);
+        }
+
+        let verifying_key = match document.verifying_key() {
+            Ok(key) => key,
+            Err(_) => return Ok(false),
+        };
+        if hex::encode(&verifying_key.to_bytes()[..8]) != pub_key_short {
+            return Ok(false);
+        }
+
+        let Ok(signature) = Signature::from_slice(signature) else {
+            return Ok(false);
+        };
+
+        Ok(Self::verify(&verifying_key, &document.signing_bytes(), &signature))
+    }
+
+    /// 解析 DID 文档
+    ///
+    /// `did:cis:*` 从本地存储读取，其他 DID 方法暂不支持。
+    pub fn resolve(did: &str) -> Result<DIDDocument> {
+        Self::resolve_from(&DIDDocumentStore::open_default(), did)
+    }
+
+    /// 从指定存储解析 DID 文档
+    pub fn resolve_from(store: &DIDDocumentStore, did: &str) -> Result<DIDDocument> {
+        match did_method(did)? {
+            "cis" => Ok(store.get(did)?),
+            _ => Err(IdentityError::UnsupportedDIDMethod(did.to_string()).into()),
+        }
+    }
+
     /// 验证签名（静态方法）
     pub fn verify(verifying_key: &VerifyingKey, data: &[u8], signature: &Signature) -> bool {
         verifying_key.verify(data, signature).is_ok()
@@ -458,6 +515,45 @@ mod tests {
         assert_eq!(manager1.did(), manager2.did());
     }
 
+    #[test]
+    fn test_verify_did_document() {
+        let manager = DIDManager::generate("remote-node").unwrap();
+        let document = manager.document();
+        let signature = manager.sign_document(&document);
+
+        assert!(DIDManager::verify_did_document(manager.did(), &document, &signature).unwrap());
+
+        // 篡改文档
+        let mut tampered = document.clone();
+        tampered.created_at += 1;
+        assert!(!DIDManager::verify_did_document(manager.did(), &tampered, &signature).unwrap());
+
+        // 用其他密钥冒充
+        let other = DIDManager::generate("remote-node").unwrap();
+        let mut forged = other.document();
+        forged.id = manager.did().to_string();
+        let forged_signature = other.sign_document(&forged);
+        assert!(!DIDManager::verify_did_document(manager.did(), &forged, &forged_signature).unwrap());
+
+        // 不支持的 DID 方法
+        assert!(DIDManager::verify_did_document("did:web:example.com", &document, &signature).is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = DIDDocumentStore::new(temp_dir.path());
+        let manager = DIDManager::generate("resolve-node").unwrap();
+
+        assert!(DIDManager::resolve_from(&store, manager.did()).is_err());
+
+        store.put(&manager.document()).unwrap();
+        let document = DIDManager::resolve_from(&store, manager.did()).unwrap();
+        assert_eq!(document.verifying_key().unwrap(), manager.verifying_key());
+
+        assert!(DIDManager::resolve("did:web:example.com").is_err());
+    }
+
     #[test]
     fn test_hex_signature() {
         let manager = DIDManager::generate("test-node").unwrap();
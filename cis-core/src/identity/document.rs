@@ -0,0 +1,169 @@
+//! DID 文档
+//!
+//! DID 本身只携带公钥前 8 字节（`did:cis:{node_id}:{pub_key_short}`），
+//! 完整公钥通过 DID 文档交换。文档由 DID 对应的私钥自签名，
+//! 验证时检查文档公钥与 DID 中的前缀一致，再用该公钥验证签名。
+//!
+//! `did:cis:*` 的解析器为本地存储：验证通过的远程文档写入
+//! `{data_dir}/identity/documents/`。
+
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::CisError;
+use crate::storage::paths::Paths;
+
+/// 身份错误
+#[derive(Debug, thiserror::Error)]
+pub enum IdentityError {
+    #[error("Unsupported DID method: {0}")]
+    UnsupportedDIDMethod(String),
+
+    #[error("Invalid DID: {0}")]
+    InvalidDID(String),
+
+    #[error("Invalid DID document: {0}")]
+    InvalidDocument(String),
+
+    #[error("DID document not found: {0}")]
+    DocumentNotFound(String),
+
+    #[error("DID document storage error: {0}")]
+    Storage(String),
+}
+
+impl From<IdentityError> for CisError {
+    fn from(e: IdentityError) -> Self {
+        CisError::identity(e.to_string())
+    }
+}
+
+/// DID 文档
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DIDDocument {
+    /// DID
+    pub id: String,
+    /// 完整 Ed25519 公钥（十六进制）
+    pub public_key: String,
+    /// 创建时间（Unix 秒）
+    pub created_at: i64,
+}
+
+impl DIDDocument {
+    pub fn new(id: impl Into<String>, public_key: &VerifyingKey) -> Self {
+        Self {
+            id: id.into(),
+            public_key: hex::encode(public_key.to_bytes()),
+            created_at: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    /// 签名所覆盖的字节
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        // 字段顺序固定，序列化结果确定
+        serde_json::to_vec(self).unwrap_or_default()
+    }
+
+    /// 解析文档中的公钥
+    pub fn verifying_key(&self) -> Result<VerifyingKey, IdentityError> {
+        let bytes: [u8; 32] = hex::decode(&self.public_key)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| IdentityError::InvalidDocument("invalid public key".to_string()))?;
+
+        VerifyingKey::from_bytes(&bytes)
+            .map_err(|e| IdentityError::InvalidDocument(format!("invalid public key: {}", e)))
+    }
+}
+
+/// DID 方法名（`did:{method}:...`）
+pub fn did_method(did: &str) -> Result<&str, IdentityError> {
+    let mut parts = did.splitn(3, ':');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some("did"), Some(method), Some(_)) if !method.is_empty() => Ok(method),
+        _ => Err(IdentityError::InvalidDID(did.to_string())),
+    }
+}
+
+/// 本地 DID 文档存储（`did:cis` 解析器）
+#[derive(Debug, Clone)]
+pub struct DIDDocumentStore {
+    dir: PathBuf,
+}
+
+impl DIDDocumentStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// 默认存储目录
+    pub fn open_default() -> Self {
+        Self::new(Paths::data_dir().join("identity").join("documents"))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path_for(&self, did: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", did.replace(':', "_")))
+    }
+
+    /// 读取文档
+    pub fn get(&self, did: &str) -> Result<DIDDocument, IdentityError> {
+        let path = self.path_for(did);
+        let content = fs::read_to_string(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => IdentityError::DocumentNotFound(did.to_string()),
+            _ => IdentityError::Storage(format!("{}: {}", path.display(), e)),
+        })?;
+
+        serde_json::from_str(&content)
+            .map_err(|e| IdentityError::InvalidDocument(format!("{}: {}", path.display(), e)))
+    }
+
+    /// 写入文档（调用方负责先验证）
+    pub fn put(&self, document: &DIDDocument) -> Result<(), IdentityError> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| IdentityError::Storage(format!("{}: {}", self.dir.display(), e)))?;
+
+        let content = serde_json::to_string_pretty(document)
+            .map_err(|e| IdentityError::InvalidDocument(e.to_string()))?;
+        let path = self.path_for(&document.id);
+        fs::write(&path, content)
+            .map_err(|e| IdentityError::Storage(format!("{}: {}", path.display(), e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::DIDManager;
+
+    #[test]
+    fn test_did_method() {
+        assert_eq!(did_method("did:cis:node:abc").unwrap(), "cis");
+        assert_eq!(did_method("did:web:example.com").unwrap(), "web");
+        assert!(did_method("invalid").is_err());
+        assert!(did_method("did::x").is_err());
+    }
+
+    #[test]
+    fn test_store_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = DIDDocumentStore::new(temp_dir.path());
+        let manager = DIDManager::generate("store-node").unwrap();
+        let document = manager.document();
+
+        assert!(matches!(
+            store.get(manager.did()),
+            Err(IdentityError::DocumentNotFound(_))
+        ));
+
+        store.put(&document).unwrap();
+        let loaded = store.get(manager.did()).unwrap();
+        assert_eq!(loaded, document);
+        assert_eq!(loaded.verifying_key().unwrap(), manager.verifying_key());
+    }
+}
@@ -0,0 +1,596 @@
+//! 硬件绑定节点密钥
+//!
+//! 将 `node.key` 封存到本机硬件，封存后明文密钥文件被删除，只能在原硬件环境中解封：
+//!
+//! - **TPM 2.0**（Linux / Windows，`tpm` feature）：以 PCR 0-3、7 的当前状态作为策略封存，
+//!   固件、引导链或机器变化后 TPM 拒绝解封
+//! - **Secure Enclave**（macOS，`secure-enclave` feature）：用不可导出的 Enclave P-256 密钥
+//!   ECIES 加密节点密钥
+//! - 两者都不可用时发出警告并保留软件密钥
+//!
+//! 绑定证明写入数据目录下的 [`HARDWARE_ATTESTATION_FILE`]。存在绑定证明时，节点密钥只能通过
+//! [`NodeClaimService::load_node_key`] 解封读取，配置文件中的 `[node] key` 会被清除。
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{CisError, Result};
+use crate::storage::paths::Paths;
+
+use super::did::set_key_permissions;
+
+/// 绑定证明文件名（位于数据目录下）
+pub const HARDWARE_ATTESTATION_FILE: &str = "node_hardware_attestation";
+
+/// 节点密钥长度
+const NODE_KEY_LEN: usize = 32;
+
+/// 密钥绑定方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HardwareBackend {
+    Tpm2,
+    SecureEnclave,
+    /// 未绑定硬件
+    Software,
+}
+
+impl std::fmt::Display for HardwareBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HardwareBackend::Tpm2 => write!(f, "TPM 2.0"),
+            HardwareBackend::SecureEnclave => write!(f, "Secure Enclave"),
+            HardwareBackend::Software => write!(f, "software"),
+        }
+    }
+}
+
+/// 绑定证明
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HardwareAttestation {
+    pub backend: HardwareBackend,
+    pub bound_at: DateTime<Utc>,
+    /// 节点密钥 SHA-256（十六进制），解封后用于校验
+    pub key_fingerprint: String,
+    /// 封存后的密钥（十六进制，格式由 backend 决定），软件密钥为 None
+    #[serde(default)]
+    pub sealed_key: Option<String>,
+    /// 绑定参数（TPM 的 PCR 选择、Enclave 密钥标签等）
+    #[serde(default)]
+    pub details: std::collections::BTreeMap<String, String>,
+}
+
+/// 已绑定（或软件）的节点密钥
+#[derive(Debug, Clone)]
+pub struct HardwareBoundKey {
+    attestation: HardwareAttestation,
+    key_path: PathBuf,
+}
+
+impl HardwareBoundKey {
+    pub fn backend(&self) -> HardwareBackend {
+        self.attestation.backend
+    }
+
+    pub fn attestation(&self) -> &HardwareAttestation {
+        &self.attestation
+    }
+
+    pub fn is_hardware_bound(&self) -> bool {
+        self.attestation.backend != HardwareBackend::Software
+    }
+
+    /// 解封节点密钥
+    ///
+    /// 硬件绑定的密钥只能在原硬件环境中解封；解封结果与绑定时的指纹不一致时同样拒绝。
+    pub fn unseal(&self) -> Result<Vec<u8>> {
+        let key = match self.attestation.backend {
+            HardwareBackend::Software => std::fs::read(&self.key_path)
+                .map_err(|e| CisError::identity(format!("Failed to read node key: {}", e)))?,
+            HardwareBackend::Tpm2 => tpm::unseal(&self.sealed_blob()?)?,
+            HardwareBackend::SecureEnclave => enclave::unseal(&self.sealed_blob()?, &self.attestation.details)?,
+        };
+
+        if fingerprint(&key) != self.attestation.key_fingerprint {
+            return Err(CisError::identity(
+                "Node key does not match the hardware attestation fingerprint",
+            ));
+        }
+        Ok(key)
+    }
+
+    fn sealed_blob(&self) -> Result<Vec<u8>> {
+        let sealed = self
+            .attestation
+            .sealed_key
+            .as_deref()
+            .ok_or_else(|| CisError::identity("Hardware attestation has no sealed key"))?;
+        hex::decode(sealed).map_err(|e| CisError::identity(format!("Invalid sealed key: {}", e)))
+    }
+}
+
+/// 节点认领服务：把节点密钥绑定到本机硬件
+pub struct NodeClaimService {
+    key_path: PathBuf,
+    attestation_path: PathBuf,
+    /// 可能残留明文 `[node] key` 的配置文件
+    config_path: Option<PathBuf>,
+}
+
+impl NodeClaimService {
+    pub fn new(key_path: impl Into<PathBuf>, attestation_path: impl Into<PathBuf>) -> Self {
+        Self {
+            key_path: key_path.into(),
+            attestation_path: attestation_path.into(),
+            config_path: None,
+        }
+    }
+
+    /// 认领和加载密钥时清除该配置文件中的 `[node] key`
+    pub fn with_config_file(mut self, config_path: impl Into<PathBuf>) -> Self {
+        self.config_path = Some(config_path.into());
+        self
+    }
+
+    /// 使用默认数据目录中的 `node.key` 和默认配置文件
+    pub fn open_default() -> Self {
+        Self::new(Paths::node_key_file(), Paths::data_dir().join(HARDWARE_ATTESTATION_FILE))
+            .with_config_file(Paths::config_file())
+    }
+
+    pub fn attestation_path(&self) -> &Path {
+        &self.attestation_path
+    }
+
+    /// 读取已有的绑定
+    pub fn load(&self) -> Result<Option<HardwareBoundKey>> {
+        if !self.attestation_path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&self.attestation_path)?;
+        let attestation: HardwareAttestation = serde_json::from_str(&content)
+            .map_err(|e| CisError::identity(format!("Invalid hardware attestation: {}", e)))?;
+        Ok(Some(HardwareBoundKey {
+            attestation,
+            key_path: self.key_path.clone(),
+        }))
+    }
+
+    /// 节点密钥是否存在（明文密钥文件或绑定证明）
+    ///
+    /// 硬件绑定后明文密钥文件已删除，此时以绑定证明为准。
+    pub fn has_node_key(&self) -> bool {
+        self.attestation_path.exists() || self.key_path.exists()
+    }
+
+    /// 读取节点密钥
+    ///
+    /// 存在绑定证明时通过 [`HardwareBoundKey::unseal`] 解封，否则读取明文密钥文件。
+    /// 同时清除配置文件中残留的 `[node] key`。
+    pub fn load_node_key(&self) -> Result<Vec<u8>> {
+        let key = match self.load()? {
+            Some(bound) => bound.unseal()?,
+            None => self.read_node_key()?,
+        };
+        self.scrub_config_key()?;
+        Ok(key)
+    }
+
+    /// 认领节点：依次尝试 TPM 2.0、Secure Enclave，都不可用时保留软件密钥
+    ///
+    /// 已绑定硬件时直接返回现有绑定。
+    pub fn claim(&self) -> Result<HardwareBoundKey> {
+        if let Some(existing) = self.load()? {
+            if existing.is_hardware_bound() {
+                return Ok(existing);
+            }
+        }
+
+        match self.bind_to_tpm() {
+            Ok(key) => return Ok(key),
+            Err(e) => tracing::debug!("TPM binding unavailable: {}", e),
+        }
+        match self.bind_to_secure_enclave() {
+            Ok(key) => return Ok(key),
+            Err(e) => tracing::debug!("Secure Enclave binding unavailable: {}", e),
+        }
+
+        tracing::warn!("No TPM 2.0 or Secure Enclave available, node key is not hardware-bound");
+        let key = self.read_node_key()?;
+        self.finish(HardwareBackend::Software, None, Default::default(), &key)
+    }
+
+    /// 用 TPM 2.0 按当前 PCR 状态封存节点密钥
+    pub fn bind_to_tpm(&self) -> Result<HardwareBoundKey> {
+        let key = self.read_node_key()?;
+        let (sealed, details) = tpm::seal(&key)?;
+        self.finish(HardwareBackend::Tpm2, Some(sealed), details, &key)
+    }
+
+    /// 用 Secure Enclave 密钥加密节点密钥
+    pub fn bind_to_secure_enclave(&self) -> Result<HardwareBoundKey> {
+        let key = self.read_node_key()?;
+        let (sealed, details) = enclave::seal(&key)?;
+        self.finish(HardwareBackend::SecureEnclave, Some(sealed), details, &key)
+    }
+
+    fn read_node_key(&self) -> Result<Vec<u8>> {
+        let key = std::fs::read(&self.key_path).map_err(|e| {
+            CisError::identity(format!("Failed to read node key {:?}: {}", self.key_path, e))
+        })?;
+        if key.len() != NODE_KEY_LEN {
+            return Err(CisError::identity(format!(
+                "Invalid node key length: {} (expected {})",
+                key.len(),
+                NODE_KEY_LEN
+            )));
+        }
+        Ok(key)
+    }
+
+    /// 写入绑定证明；硬件绑定成功后删除明文密钥
+    fn finish(
+        &self,
+        backend: HardwareBackend,
+        sealed: Option<Vec<u8>>,
+        details: std::collections::BTreeMap<String, String>,
+        key: &[u8],
+    ) -> Result<HardwareBoundKey> {
+        let attestation = HardwareAttestation {
+            backend,
+            bound_at: Utc::now(),
+            key_fingerprint: fingerprint(key),
+            sealed_key: sealed.map(hex::encode),
+            details,
+        };
+
+        if let Some(parent) = self.attestation_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(&attestation)
+            .map_err(|e| CisError::identity(format!("Failed to serialize attestation: {}", e)))?;
+        std::fs::write(&self.attestation_path, content)?;
+        set_key_permissions(&self.attestation_path)?;
+
+        let bound = HardwareBoundKey {
+            attestation,
+            key_path: self.key_path.clone(),
+        };
+
+        if bound.is_hardware_bound() {
+            // 确认能解封后再删除明文
+            bound.unseal()?;
+            std::fs::write(&self.key_path, vec![0u8; key.len()])?;
+            std::fs::remove_file(&self.key_path)?;
+        }
+        self.scrub_config_key()?;
+        Ok(bound)
+    }
+
+    /// 删除配置文件 `[node]` 段中的 `key`，保留其余内容和注释
+    fn scrub_config_key(&self) -> Result<()> {
+        let Some(config_path) = self.config_path.as_ref().filter(|p| p.exists()) else {
+            return Ok(());
+        };
+        let content = std::fs::read_to_string(config_path)?;
+
+        let mut in_node = false;
+        let mut scrubbed = false;
+        let mut kept = Vec::new();
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('[') {
+                in_node = trimmed.trim_start_matches('[').trim_end_matches(']').trim() == "node";
+            } else if in_node && is_key_assignment(trimmed) {
+                scrubbed = true;
+                continue;
+            }
+            kept.push(line);
+        }
+
+        if scrubbed {
+            let mut content = kept.join("\n");
+            content.push('\n');
+            std::fs::write(config_path, content)?;
+            tracing::info!("Removed plaintext node key from {}", config_path.display());
+        }
+        Ok(())
+    }
+}
+
+/// `key = ...` 或 `"key" = ...`
+fn is_key_assignment(line: &str) -> bool {
+    line.split_once('=')
+        .map(|(name, _)| name.trim().trim_matches('"') == "key")
+        .unwrap_or(false)
+}
+
+fn fingerprint(key: &[u8]) -> String {
+    hex::encode(Sha256::digest(key))
+}
+
+type SealResult = Result<(Vec<u8>, std::collections::BTreeMap<String, String>)>;
+
+#[cfg(all(feature = "tpm", any(target_os = "linux", target_os = "windows")))]
+mod tpm {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Serialize};
+    use tss_esapi::constants::SessionType;
+    use tss_esapi::handles::KeyHandle;
+    use tss_esapi::interface_types::algorithm::{HashingAlgorithm, PublicAlgorithm};
+    use tss_esapi::interface_types::key_bits::RsaKeyBits;
+    use tss_esapi::interface_types::resource_handles::Hierarchy;
+    use tss_esapi::interface_types::session_handles::PolicySession;
+    use tss_esapi::attributes::ObjectAttributesBuilder;
+    use tss_esapi::structures::{
+        Digest, KeyedHashScheme, PcrSelectionListBuilder, PcrSlot, Private, Public, PublicBuilder,
+        PublicKeyedHashParameters, RsaExponent, SensitiveData, SymmetricDefinition,
+        SymmetricDefinitionObject,
+    };
+    use tss_esapi::tcti_ldr::TctiNameConf;
+    use tss_esapi::traits::{Marshall, UnMarshall};
+    use tss_esapi::utils::create_restricted_decryption_rsa_public;
+    use tss_esapi::Context;
+
+    use super::SealResult;
+    use crate::error::{CisError, Result};
+
+    /// 封存策略使用的 PCR：固件、固件配置、Option ROM、安全启动状态
+    const SEALING_PCRS: [PcrSlot; 5] = [PcrSlot::Slot0, PcrSlot::Slot1, PcrSlot::Slot2, PcrSlot::Slot3, PcrSlot::Slot7];
+
+    #[derive(Serialize, Deserialize)]
+    struct SealedBlob {
+        private: Vec<u8>,
+        public: Vec<u8>,
+    }
+
+    fn tpm_err(e: impl std::fmt::Display) -> CisError {
+        CisError::identity(format!("TPM error: {}", e))
+    }
+
+    fn open_context() -> Result<Context> {
+        let tcti = TctiNameConf::from_environment_variable().unwrap_or_else(|_| TctiNameConf::Device(Default::default()));
+        Context::new(tcti).map_err(tpm_err)
+    }
+
+    /// 所有者层级下的存储主密钥，同一 TPM 上每次派生结果相同
+    fn create_primary(context: &mut Context) -> Result<KeyHandle> {
+        let public = create_restricted_decryption_rsa_public(
+            SymmetricDefinitionObject::AES_128_CFB,
+            RsaKeyBits::Rsa2048,
+            RsaExponent::default(),
+        )
+        .map_err(tpm_err)?;
+        context
+            .execute_with_nullauth_session(|ctx| ctx.create_primary(Hierarchy::Owner, public, None, None, None, None))
+            .map(|result| result.key_handle)
+            .map_err(tpm_err)
+    }
+
+    /// 以当前 PCR 值建立策略会话（pcrDigest 为空时 TPM 使用当前值）
+    fn pcr_policy(context: &mut Context, session_type: SessionType) -> Result<PolicySession> {
+        let session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                session_type,
+                SymmetricDefinition::AES_128_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .map_err(tpm_err)?
+            .ok_or_else(|| tpm_err("no session returned"))?;
+        let policy = PolicySession::try_from(session).map_err(tpm_err)?;
+        let selection = PcrSelectionListBuilder::new()
+            .with_selection(HashingAlgorithm::Sha256, &SEALING_PCRS)
+            .build()
+            .map_err(tpm_err)?;
+        context.policy_pcr(policy, Digest::default(), selection).map_err(tpm_err)?;
+        Ok(policy)
+    }
+
+    pub(super) fn seal(key: &[u8]) -> SealResult {
+        let mut context = open_context()?;
+        let primary = create_primary(&mut context)?;
+
+        let trial = pcr_policy(&mut context, SessionType::Trial)?;
+        let policy_digest = context.policy_get_digest(trial).map_err(tpm_err)?;
+
+        let attributes = ObjectAttributesBuilder::new()
+            .with_fixed_tpm(true)
+            .with_fixed_parent(true)
+            .with_no_da(true)
+            .build()
+            .map_err(tpm_err)?;
+        let public = PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::KeyedHash)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_auth_policy(policy_digest)
+            .with_object_attributes(attributes)
+            .with_keyed_hash_parameters(PublicKeyedHashParameters::new(KeyedHashScheme::Null))
+            .with_keyed_hash_unique_identifier(Digest::default())
+            .build()
+            .map_err(tpm_err)?;
+        let sensitive = SensitiveData::try_from(key.to_vec()).map_err(tpm_err)?;
+
+        let created = context
+            .execute_with_nullauth_session(|ctx| ctx.create(primary, public, None, Some(sensitive), None, None))
+            .map_err(tpm_err)?;
+        let blob = SealedBlob {
+            private: created.out_private.value().to_vec(),
+            public: created.out_public.marshall().map_err(tpm_err)?,
+        };
+
+        let details = BTreeMap::from([("pcrs".to_string(), "sha256:0,1,2,3,7".to_string())]);
+        let sealed = serde_json::to_vec(&blob).map_err(tpm_err)?;
+        Ok((sealed, details))
+    }
+
+    pub(super) fn unseal(sealed: &[u8]) -> Result<Vec<u8>> {
+        let blob: SealedBlob = serde_json::from_slice(sealed).map_err(tpm_err)?;
+        let mut context = open_context()?;
+        let primary = create_primary(&mut context)?;
+
+        let private = Private::try_from(blob.private).map_err(tpm_err)?;
+        let public = Public::unmarshall(&blob.public).map_err(tpm_err)?;
+        let handle = context
+            .execute_with_nullauth_session(|ctx| ctx.load(primary, private, public))
+            .map_err(tpm_err)?;
+
+        let policy = pcr_policy(&mut context, SessionType::Policy)?;
+        let data = context
+            .execute_with_session(Some(policy.into()), |ctx| ctx.unseal(handle.into()))
+            .map_err(|e| {
+                CisError::identity(format!(
+                    "TPM refused to unseal the node key (different machine or boot state changed): {}",
+                    e
+                ))
+            })?;
+        Ok(data.value().to_vec())
+    }
+}
+
+#[cfg(not(all(feature = "tpm", any(target_os = "linux", target_os = "windows"))))]
+mod tpm {
+    use super::SealResult;
+    use crate::error::{CisError, Result};
+
+    pub(super) fn seal(_key: &[u8]) -> SealResult {
+        Err(CisError::identity("TPM 2.0 support is not available in this build"))
+    }
+
+    pub(super) fn unseal(_sealed: &[u8]) -> Result<Vec<u8>> {
+        Err(CisError::identity(
+            "Node key is sealed to a TPM, but TPM 2.0 support is not available in this build",
+        ))
+    }
+}
+
+#[cfg(all(feature = "secure-enclave", target_os = "macos"))]
+mod enclave {
+    use std::collections::BTreeMap;
+
+    use security_framework::item::{ItemClass, ItemSearchOptions, Reference, SearchResult};
+    use security_framework::key::{Algorithm, GenerateKeyOptions, KeyType, SecKey, Token};
+
+    use super::SealResult;
+    use crate::error::{CisError, Result};
+
+    const ALGORITHM: Algorithm = Algorithm::ECIESEncryptionCofactorVariableIVX963SHA256AESGCM;
+
+    fn enclave_err(e: impl std::fmt::Display) -> CisError {
+        CisError::identity(format!("Secure Enclave error: {}", e))
+    }
+
+    pub(super) fn seal(key: &[u8]) -> SealResult {
+        let label = format!("cis.node-key.{}", uuid::Uuid::new_v4());
+        let mut options = GenerateKeyOptions::default();
+        options
+            .set_key_type(KeyType::ec())
+            .set_size_in_bits(256)
+            .set_token(Token::SecureEnclave)
+            .set_label(&label);
+        let private = SecKey::new(&options).map_err(enclave_err)?;
+        let public = private.public_key().ok_or_else(|| enclave_err("no public key"))?;
+
+        let sealed = public.encrypt_data(ALGORITHM, key).map_err(enclave_err)?;
+        Ok((sealed, BTreeMap::from([("key_label".to_string(), label)])))
+    }
+
+    pub(super) fn unseal(sealed: &[u8], details: &BTreeMap<String, String>) -> Result<Vec<u8>> {
+        let label = details
+            .get("key_label")
+            .ok_or_else(|| enclave_err("attestation has no key label"))?;
+        let results = ItemSearchOptions::new()
+            .class(ItemClass::key())
+            .label(label)
+            .load_refs(true)
+            .search()
+            .map_err(|e| enclave_err(format!("Enclave key {} not found on this machine: {}", label, e)))?;
+        let private = results
+            .into_iter()
+            .find_map(|r| match r {
+                SearchResult::Ref(Reference::Key(key)) => Some(key),
+                _ => None,
+            })
+            .ok_or_else(|| enclave_err(format!("Enclave key {} not found on this machine", label)))?;
+
+        private.decrypt_data(ALGORITHM, sealed).map_err(enclave_err)
+    }
+}
+
+#[cfg(not(all(feature = "secure-enclave", target_os = "macos")))]
+mod enclave {
+    use std::collections::BTreeMap;
+
+    use super::SealResult;
+    use crate::error::{CisError, Result};
+
+    pub(super) fn seal(_key: &[u8]) -> SealResult {
+        Err(CisError::identity("Secure Enclave support is not available in this build"))
+    }
+
+    pub(super) fn unseal(_sealed: &[u8], _details: &BTreeMap<String, String>) -> Result<Vec<u8>> {
+        Err(CisError::identity(
+            "Node key is sealed to a Secure Enclave, but Secure Enclave support is not available in this build",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_software_fallback() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let key_path = temp_dir.path().join("node.key");
+        std::fs::write(&key_path, [7u8; NODE_KEY_LEN]).unwrap();
+
+        let service = NodeClaimService::new(&key_path, temp_dir.path().join(HARDWARE_ATTESTATION_FILE));
+        assert!(service.load().unwrap().is_none());
+
+        // 测试环境没有 TPM / Secure Enclave，退回软件密钥
+        let bound = service.claim().unwrap();
+        assert_eq!(bound.backend(), HardwareBackend::Software);
+        assert_eq!(bound.unseal().unwrap(), vec![7u8; NODE_KEY_LEN]);
+        assert!(key_path.exists());
+
+        let loaded = service.load().unwrap().unwrap();
+        assert_eq!(loaded.attestation().key_fingerprint, bound.attestation().key_fingerprint);
+
+        // 密钥被替换后拒绝
+        std::fs::write(&key_path, [8u8; NODE_KEY_LEN]).unwrap();
+        assert!(loaded.unseal().is_err());
+        assert!(service.load_node_key().is_err());
+    }
+
+    #[test]
+    fn test_claim_then_restart_loads_same_key() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let key_path = temp_dir.path().join("node.key");
+        let attestation_path = temp_dir.path().join(HARDWARE_ATTESTATION_FILE);
+        let config_path = temp_dir.path().join("config.toml");
+        std::fs::write(&key_path, [7u8; NODE_KEY_LEN]).unwrap();
+        std::fs::write(
+            &config_path,
+            "# CIS config\n[node]\nname = \"n1\"\nkey = \"0707\"\n\n[p2p]\nkey = \"kept\"\n",
+        )
+        .unwrap();
+
+        let service = NodeClaimService::new(&key_path, &attestation_path).with_config_file(&config_path);
+        service.claim().unwrap();
+
+        let config = std::fs::read_to_string(&config_path).unwrap();
+        assert_eq!(config, "# CIS config\n[node]\nname = \"n1\"\n\n[p2p]\nkey = \"kept\"\n");
+
+        // 重启后重新打开服务，经绑定证明读取到同一密钥
+        let restarted = NodeClaimService::new(&key_path, &attestation_path).with_config_file(&config_path);
+        assert!(restarted.has_node_key());
+        assert_eq!(restarted.load_node_key().unwrap(), vec![7u8; NODE_KEY_LEN]);
+    }
+}
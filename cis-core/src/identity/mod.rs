@@ -9,9 +9,15 @@
 //! - Message signing and verification
 //! - Deterministic key derivation from seed
 //! - Secure key storage
+//! - Self-signed DID documents for cross-node authentication
+//! - Node key binding to TPM 2.0 / Secure Enclave (`cis node claim`)
 
 pub mod did;
+pub mod document;
+pub mod hardware;
 pub mod ssh_key;
 
 pub use did::DIDManager;
+pub use document::{DIDDocument, DIDDocumentStore, IdentityError};
+pub use hardware::{HardwareAttestation, HardwareBackend, HardwareBoundKey, NodeClaimService};
 pub use ssh_key::SshKeyEncryption;
@@ -12,7 +12,7 @@ use std::io::{self, Write};
 
 
 use crate::error::{CisError, Result};
-use crate::project::Project;
+use crate::project::{Project, ProjectSkillManifest};
 use crate::storage::paths::Paths;
 
 /// 初始化向导
@@ -247,9 +247,33 @@ impl InitWizard {
             path: which::which("aider").ok().map(|p| p.to_string_lossy().to_string()),
         });
 
+        // Check Gemini（REST API，通过测试请求验证 API Key）
+        if std::env::var("GEMINI_API_KEY").is_ok_and(|key| !key.is_empty()) {
+            let gemini = self.check_gemini().await;
+            agents.push(AgentCheck {
+                name: "Google Gemini".to_string(),
+                available: gemini.is_ok(),
+                version: gemini.as_ref().ok().cloned(),
+                path: None,
+            });
+        }
+
         agents
     }
 
+    /// 使用 `GEMINI_API_KEY` 发送测试请求，返回使用的模型名
+    async fn check_gemini(&self) -> Result<String> {
+        use crate::ai::{GeminiConfig, GeminiProvider};
+
+        let config = GeminiConfig::default();
+        let model = config.model.clone();
+        GeminiProvider::new(config)
+            .verify_api_key()
+            .await
+            .map_err(|e| CisError::other(format!("Gemini API key check failed: {}", e)))?;
+        Ok(model)
+    }
+
     async fn check_agent(&self, name: &str, args: &[&str]) -> Result<String> {
         let output = tokio::process::Command::new(name)
             .args(args)
@@ -341,13 +365,15 @@ impl InitWizard {
             println!("    2) Claude CLI");
             println!("    3) Kimi Code");
             println!("    4) Aider");
+            println!("    5) Google Gemini (需要 GEMINI_API_KEY)");
 
-            let choice = self.prompt_input("请输入选项 (1-4, 默认1): ")?;
+            let choice = self.prompt_input("请输入选项 (1-5, 默认1): ")?;
 
             match choice.trim() {
                 "2" => "claude".to_string(),
                 "3" => "kimi".to_string(),
                 "4" => "aider".to_string(),
+                "5" => "gemini".to_string(),
                 _ => "opencode".to_string(),
             }
         } else {
@@ -373,7 +399,7 @@ name = "{}"
 key = "{}"
 
 [ai]
-# 默认 AI Provider: opencode | claude | kimi | aider
+# 默认 AI Provider: opencode | claude | kimi | aider | gemini
 default_provider = "{}"
 
 [ai.opencode]
@@ -399,6 +425,13 @@ temperature = 0.7
 model = "kimi-k2"
 max_tokens = 8192
 
+[ai.gemini]
+# Google Gemini 配置（API Key 未填写时读取 GEMINI_API_KEY 环境变量）
+# api_key = ""
+model = "gemini-pro"
+# temperature = 0.7
+# safety_settings = [{{ category = "HARM_CATEGORY_HARASSMENT", threshold = "BLOCK_ONLY_HIGH" }}]
+
 [vector]
 # 向量引擎配置（用于语义搜索和记忆）
 # 嵌入维度: 768 (Nomic Embed), 1536 (OpenAI), 384 (MiniLM)
@@ -458,6 +491,11 @@ bootstrap_nodes = []
             }
         }
 
+        // 没有本地 CLI 时，可以使用 Gemini REST API
+        if std::env::var("GEMINI_API_KEY").is_ok_and(|key| !key.is_empty()) {
+            return Some("gemini".to_string());
+        }
+
         None
     }
 
@@ -690,6 +728,11 @@ bootstrap_nodes = []
         println!("  创建: {}", cis_dir.join("project.toml").display());
         println!("  创建: {}", gitignore.display());
 
+        // 创建项目级 Skill 清单
+        if ProjectSkillManifest::write_starter(&project_dir)? {
+            println!("  创建: {}", ProjectSkillManifest::manifest_path(&project_dir).display());
+        }
+
         Ok(())
     }
 
@@ -812,6 +855,23 @@ bootstrap_nodes = []
             .and_then(|p| p.as_str())
             .unwrap_or("opencode");
 
+        // Gemini 通过 REST API 调用，验证 API Key 而不是查找 CLI
+        if provider == "gemini" {
+            let mut gemini = crate::ai::GeminiConfig::default();
+            if let Some(section) = config.get("ai").and_then(|ai| ai.get("gemini")) {
+                if let Some(key) = section.get("api_key").and_then(|k| k.as_str()) {
+                    gemini.api_key = key.to_string();
+                }
+                if let Some(model) = section.get("model").and_then(|m| m.as_str()) {
+                    gemini.model = model.to_string();
+                }
+            }
+            return crate::ai::GeminiProvider::new(gemini)
+                .verify_api_key()
+                .await
+                .map_err(|e| CisError::other(format!("Gemini API key check failed: {}", e)));
+        }
+
         // 检查 provider 是否可用
         if which::which(provider).is_err() {
             return Err(CisError::other(format!(
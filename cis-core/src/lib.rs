@@ -137,7 +137,7 @@ pub mod engine;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test;
 #[cfg(any(test, feature = "test-utils"))]
-pub use test::mocks::{MockNetworkService, MockStorageService, MockEventBus, MockAiProvider, MockEmbeddingService, MockSkillExecutor};
+pub use test::mocks::{MockNetworkService, MockStorageService, MockEventBus, MockAiProvider, MockResponse, MockEmbeddingService, MockSkillExecutor};
 
 pub use error::{CisError, Result};
 pub use identity::DIDManager;
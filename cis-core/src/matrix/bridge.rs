@@ -79,6 +79,18 @@ impl crate::ai::AiProvider for BoxedAiProvider {
     ) -> crate::ai::Result<serde_json::Value> {
         self.0.generate_json(prompt, schema).await
     }
+
+    async fn structured_chat(
+        &self,
+        prompt: &str,
+        schema: &serde_json::Value,
+    ) -> crate::ai::Result<serde_json::Value> {
+        self.0.structured_chat(prompt, schema).await
+    }
+
+    async fn count_tokens(&self, text: &str) -> crate::ai::Result<usize> {
+        self.0.count_tokens(text).await
+    }
 }
 
 use super::error::{MatrixError, MatrixResult};
@@ -71,6 +71,10 @@ pub enum MatrixError {
     /// Federation error
     #[error("Federation error: {0}")]
     Federation(String),
+
+    /// Schema migration failed and was rolled back
+    #[error("Migration to schema v{0} failed: {1}")]
+    MigrationFailed(u32, String),
 }
 
 impl MatrixError {
@@ -96,6 +100,7 @@ impl MatrixError {
             MatrixError::UserInUse(_) => "M_USER_IN_USE",
             MatrixError::InvalidUsername(_) => "M_INVALID_USERNAME",
             MatrixError::Federation(_) => "M_FEDERATION_ERROR",
+            MatrixError::MigrationFailed(..) => "M_DATABASE_ERROR",
         }
     }
 
@@ -116,6 +121,7 @@ impl MatrixError {
             MatrixError::UserInUse(_) => StatusCode::CONFLICT,
             MatrixError::InvalidUsername(_) => StatusCode::BAD_REQUEST,
             MatrixError::Federation(_) => StatusCode::BAD_GATEWAY,
+            MatrixError::MigrationFailed(..) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 }
@@ -119,7 +119,7 @@ pub use error::{MatrixError, MatrixResult};
 // Re-export nucleus types (core)
 pub use nucleus::{
     HandlerId, MatrixEvent, MatrixNucleus, MatrixRoom, RoomId, RoomManager,
-    RoomOptions as NucleusRoomOptions, RoomState, EventId, UserId,
+    RoomOptions as NucleusRoomOptions, RoomState, RoomStateCache, EventId, UserId,
 };
 
 // Re-export server types
@@ -5,15 +5,17 @@
 //! - 事件联邦广播
 //! - 节点间同步
 //! - 断线重连
+//! - 客户端 `/sync` 事件接收
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use ed25519_dalek::VerifyingKey;
 use ruma::events::AnyMessageLikeEventContent;
-use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, watch, RwLock};
+use tokio::task::JoinHandle;
 use tokio::time::interval;
 use tracing::{debug, info, warn};
 
@@ -30,7 +32,9 @@ use crate::matrix::federation_impl::{
 };
 use crate::matrix::federation::PeerDiscovery;
 use crate::matrix::store::MatrixStore;
-use crate::matrix::sync::{SyncPriority, SyncQueue, SyncQueueConfig, SyncTask};
+use crate::matrix::sync::client::{run_sync_loop, SyncClient};
+use crate::matrix::sync::{SyncClientConfig, SyncPriority, SyncQueue, SyncQueueConfig, SyncTask};
+use crate::matrix::websocket::protocol::SyncFilter;
 use crate::matrix::websocket::tunnel::TunnelManager;
 
 /// Matrix 事件
@@ -410,6 +414,76 @@ impl Default for RoomManager {
     }
 }
 
+/// 房间成员缓存默认有效期
+pub const ROOM_STATE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// 房间成员缓存
+///
+/// 避免每次成员检查都访问 homeserver。条目超过 TTL 后视为未命中，
+/// 由 [`MatrixNucleus::check_membership`] 通过 `/state` 重新拉取。
+/// 使用同步锁，保证 [`MatrixNucleus::is_member`] 可以在非 async 上下文中调用。
+#[derive(Debug)]
+pub struct RoomStateCache {
+    ttl: Duration,
+    members: std::sync::RwLock<HashMap<RoomId, HashSet<UserId>>>,
+    last_updated: std::sync::RwLock<HashMap<RoomId, Instant>>,
+}
+
+impl RoomStateCache {
+    /// 创建缓存
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            members: std::sync::RwLock::new(HashMap::new()),
+            last_updated: std::sync::RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 缓存有效期
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// 房间条目存在且未过期
+    pub fn is_fresh(&self, room_id: &RoomId) -> bool {
+        let last_updated = self.last_updated.read().unwrap_or_else(|e| e.into_inner());
+        last_updated
+            .get(room_id)
+            .is_some_and(|at| at.elapsed() < self.ttl)
+    }
+
+    /// 查询成员；未命中或已过期时返回 `None`
+    pub fn get(&self, room_id: &RoomId, user_id: &UserId) -> Option<bool> {
+        if !self.is_fresh(room_id) {
+            return None;
+        }
+        let members = self.members.read().unwrap_or_else(|e| e.into_inner());
+        members.get(room_id).map(|m| m.contains(user_id))
+    }
+
+    /// 用完整成员列表替换房间条目
+    pub fn set_members(&self, room_id: &RoomId, room_members: HashSet<UserId>) {
+        let mut members = self.members.write().unwrap_or_else(|e| e.into_inner());
+        let mut last_updated = self.last_updated.write().unwrap_or_else(|e| e.into_inner());
+        members.insert(room_id.clone(), room_members);
+        last_updated.insert(room_id.clone(), Instant::now());
+    }
+
+    /// 移除房间条目，下次查询时重新拉取
+    pub fn invalidate(&self, room_id: &RoomId) {
+        let mut members = self.members.write().unwrap_or_else(|e| e.into_inner());
+        let mut last_updated = self.last_updated.write().unwrap_or_else(|e| e.into_inner());
+        members.remove(room_id);
+        last_updated.remove(room_id);
+    }
+}
+
+impl Default for RoomStateCache {
+    fn default() -> Self {
+        Self::new(ROOM_STATE_CACHE_TTL)
+    }
+}
+
 /// MatrixNucleus - 统一 Matrix 核心
 pub struct MatrixNucleus {
     /// 存储
@@ -436,6 +510,12 @@ pub struct MatrixNucleus {
     pub node_did: String,
     /// 房间集合（用于联邦同步）
     rooms: Arc<RwLock<HashMap<String, MatrixRoom>>>,
+    /// 客户端同步配置（可选）
+    sync_client: Option<SyncClientConfig>,
+    /// 客户端同步是否暂停
+    sync_paused: watch::Sender<bool>,
+    /// 房间成员缓存
+    room_state_cache: Arc<RoomStateCache>,
 }
 
 impl std::fmt::Debug for MatrixNucleus {
@@ -558,6 +638,9 @@ impl MatrixNucleus {
             sync_queue,
             node_did: did.did().to_string(),
             rooms: Arc::new(RwLock::new(HashMap::new())),
+            sync_client: None,
+            sync_paused: watch::Sender::new(false),
+            room_state_cache: Arc::new(RoomStateCache::default()),
         };
 
         // Start event processing task
@@ -774,6 +857,8 @@ impl MatrixNucleus {
 
         // Register room
         self.room_manager.register_room(&room_id, &opts).await;
+        self.room_state_cache
+            .set_members(&room_id, HashSet::from([UserId::new(creator.clone())]));
 
         // Store in federation rooms
         {
@@ -841,6 +926,7 @@ impl MatrixNucleus {
         // Local join
         self.store.join_room(room_id.as_str(), user_id.as_str())?;
         self.room_manager.add_member(room_id, user_id.as_str().to_string()).await;
+        self.invalidate_room(room_id);
 
         info!("User {} joined room {}", user_id, room_id);
         Ok(())
@@ -1284,6 +1370,53 @@ impl MatrixNucleus {
         self
     }
 
+    /// 设置房间成员缓存有效期（默认 60 秒）
+    pub fn with_room_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.room_state_cache = Arc::new(RoomStateCache::new(ttl));
+        self
+    }
+
+    /// 设置客户端同步配置
+    pub fn with_sync_client(mut self, config: SyncClientConfig) -> Self {
+        self.sync_client = Some(config);
+        self
+    }
+
+    /// 启动客户端同步循环
+    ///
+    /// 长轮询 homeserver 的 `/sync`，将通过 `filter` 的房间事件发送到房间订阅者
+    /// 和事件总线（由此分发到已注册的处理器）。
+    pub fn start_sync(&self, filter: SyncFilter) -> Result<JoinHandle<()>> {
+        let config = self
+            .sync_client
+            .clone()
+            .ok_or_else(|| CisError::configuration("Matrix sync client not configured"))?;
+
+        Ok(tokio::spawn(run_sync_loop(
+            SyncClient::new(config),
+            filter,
+            self.event_bus.clone(),
+            self.room_manager.clone(),
+            self.room_state_cache.clone(),
+            self.sync_paused.subscribe(),
+        )))
+    }
+
+    /// 暂停客户端同步（进行中的请求完成后生效）
+    pub fn pause_sync(&self) {
+        self.sync_paused.send_replace(true);
+    }
+
+    /// 恢复客户端同步
+    pub fn resume_sync(&self) {
+        self.sync_paused.send_replace(false);
+    }
+
+    /// 客户端同步是否暂停
+    pub fn is_sync_paused(&self) -> bool {
+        *self.sync_paused.borrow()
+    }
+
     /// 获取事件广播器
     pub fn broadcaster(&self) -> Option<&EventBroadcaster> {
         self.broadcaster.as_deref()
@@ -1314,6 +1447,45 @@ impl MatrixNucleus {
         self.store.is_user_in_room(room_id.as_str(), user_id.as_str())
     }
 
+    /// 从成员缓存中检查用户是否在房间中
+    ///
+    /// 纯内存查询，缓存未命中或过期时返回 `false`；需要准确结果时使用
+    /// [`check_membership`](Self::check_membership)。
+    pub fn is_member(&self, room_id: &RoomId, user_id: &UserId) -> bool {
+        self.room_state_cache.get(room_id, user_id).unwrap_or(false)
+    }
+
+    /// 检查用户是否在房间中，缓存未命中或过期时先刷新
+    pub async fn check_membership(&self, room_id: &RoomId, user_id: &UserId) -> MatrixResult<bool> {
+        if let Some(is_member) = self.room_state_cache.get(room_id, user_id) {
+            return Ok(is_member);
+        }
+        self.refresh_room_state(room_id).await?;
+        Ok(self.is_member(room_id, user_id))
+    }
+
+    /// 重新拉取房间成员并写入缓存
+    ///
+    /// 配置了客户端同步时通过 homeserver 的 `/state` 获取，否则读取本地存储。
+    pub async fn refresh_room_state(&self, room_id: &RoomId) -> MatrixResult<()> {
+        let members = match &self.sync_client {
+            Some(config) => SyncClient::new(config.clone()).room_members(room_id).await?,
+            None => self
+                .store
+                .get_room_members(room_id.as_str())?
+                .into_iter()
+                .map(UserId::new)
+                .collect(),
+        };
+        self.room_state_cache.set_members(room_id, members);
+        Ok(())
+    }
+
+    /// 使房间成员缓存失效（成员加入/离开后调用）
+    pub fn invalidate_room(&self, room_id: &RoomId) {
+        self.room_state_cache.invalidate(room_id);
+    }
+
     /// 获取用户加入的房间
     pub fn get_joined_rooms(&self, user_id: &UserId) -> MatrixResult<Vec<String>> {
         self.store.get_joined_rooms(user_id.as_str())
@@ -1392,6 +1564,28 @@ mod tests {
         assert!(state.is_some());
     }
 
+    #[test]
+    fn test_room_state_cache() {
+        let cache = RoomStateCache::new(Duration::from_millis(50));
+        let room_id = RoomId::new("!workers:example.com");
+        let worker = UserId::new("@worker-1:example.com");
+
+        // 未命中
+        assert_eq!(cache.get(&room_id, &worker), None);
+
+        cache.set_members(&room_id, HashSet::from([worker.clone()]));
+        assert_eq!(cache.get(&room_id, &worker), Some(true));
+        assert_eq!(cache.get(&room_id, &UserId::new("@other:example.com")), Some(false));
+
+        cache.invalidate(&room_id);
+        assert_eq!(cache.get(&room_id, &worker), None);
+
+        // 过期
+        cache.set_members(&room_id, HashSet::from([worker.clone()]));
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(cache.get(&room_id, &worker), None);
+    }
+
     #[tokio::test]
     async fn test_room_subscriber() {
         let manager = RoomManager::new();
@@ -1415,6 +1609,79 @@ mod tests {
         assert_eq!(received.unwrap().event_type, "m.room.message");
     }
 
+    #[tokio::test]
+    async fn test_start_sync_refreshes_token_and_resumes() {
+        use axum::extract::Query;
+        use axum::http::{HeaderMap, StatusCode};
+        use axum::routing::{get, post};
+        use axum::{Json, Router};
+
+        async fn sync(
+            headers: HeaderMap,
+            Query(query): Query<HashMap<String, String>>,
+        ) -> (StatusCode, Json<serde_json::Value>) {
+            let auth = headers.get("authorization").and_then(|v| v.to_str().ok());
+            if auth != Some("Bearer fresh") {
+                let body = serde_json::json!({"errcode": "M_UNKNOWN_TOKEN", "error": "expired"});
+                return (StatusCode::UNAUTHORIZED, Json(body));
+            }
+            if query.contains_key("since") {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                return (StatusCode::OK, Json(serde_json::json!({"next_batch": "s2"})));
+            }
+            let body = serde_json::json!({
+                "next_batch": "s1",
+                "rooms": {"join": {"!dag:example.com": {"timeline": {"events": [
+                    {"event_id": "$1", "sender": "@bob:example.com", "type": "m.room.message", "content": {"body": "hi"}},
+                    {"event_id": "$2", "sender": "@bob:example.com", "type": "m.typing", "content": {}}
+                ]}}}}
+            });
+            (StatusCode::OK, Json(body))
+        }
+
+        let app = Router::new()
+            .route("/_matrix/client/v3/sync", get(sync))
+            .route(
+                "/_matrix/client/v3/refresh",
+                post(|| async { Json(serde_json::json!({"access_token": "fresh"})) }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let config = SyncClientConfig::new(format!("http://{}", addr), "stale")
+            .with_refresh_token("refresh")
+            .with_timeout_ms(0);
+        let nucleus = MatrixNucleus::new_simple(
+            Arc::new(MatrixStore::open_in_memory().unwrap()),
+            Arc::new(DIDManager::generate("test-node").unwrap()),
+        )
+        .with_sync_client(config);
+        let mut events = nucleus.subscribe_events();
+
+        nucleus.pause_sync();
+        let handle = nucleus
+            .start_sync(SyncFilter::new().with_event_types(vec!["m.room.message".to_string()]))
+            .unwrap();
+        assert!(tokio::time::timeout(Duration::from_millis(100), events.recv())
+            .await
+            .is_err());
+
+        nucleus.resume_sync();
+        let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.event_id_str(), "$1");
+        assert_eq!(event.room_id_str(), "!dag:example.com");
+
+        // m.typing 被过滤
+        assert!(tokio::time::timeout(Duration::from_millis(100), events.recv())
+            .await
+            .is_err());
+        handle.abort();
+    }
+
     #[test]
     fn test_matrix_event_to_federation() {
         let event = MatrixEvent::new(
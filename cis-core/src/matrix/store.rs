@@ -786,6 +786,24 @@ impl MatrixStore {
         }
     }
 
+    /// Get all joined members of a room
+    pub fn get_room_members(&self, room_id: &str) -> MatrixResult<Vec<String>> {
+        let db = self.db.lock()
+            .map_err(|_| MatrixError::Internal("Failed to lock database".to_string()))?;
+
+        let mut stmt = db.prepare(
+            "SELECT user_id FROM matrix_room_members
+             WHERE room_id = ?1 AND membership = 'join'"
+        ).map_err(|e| MatrixError::Store(format!("Failed to prepare query: {}", e)))?;
+
+        let members: Result<Vec<String>, rusqlite::Error> = stmt
+            .query_map([room_id], |row| row.get(0))
+            .map_err(|e| MatrixError::Store(format!("Failed to query members: {}", e)))?
+            .collect();
+
+        members.map_err(|e| MatrixError::Store(format!("Failed to collect members: {}", e)))
+    }
+
     /// Check if a room exists
     pub fn room_exists(&self, room_id: &str) -> MatrixResult<bool> {
         let db = self.db.lock()
@@ -15,12 +15,45 @@
 //! - `matrix_devices`: 设备注册
 //! - `matrix_tokens`: 访问令牌
 //! - `matrix_profiles`: 用户详细资料（扩展）
+//! - `did_rotation_history`: DID 轮换记录（v2）
+//!
+//! ## 迁移
+//!
+//! `init_schema` 建立 v1 表结构，之后按版本号依次执行 [`MIGRATIONS`]，
+//! 已执行的版本记录在 `schema_version` 表中。
 
 use rusqlite::{Connection, OptionalExtension};
 use std::sync::{Arc, Mutex};
 
 use super::error::{MatrixError, MatrixResult};
 
+/// 基础表结构（`init_schema`）对应的版本
+const BASE_SCHEMA_VERSION: u32 = 1;
+
+/// Schema 迁移：`(目标版本, SQL)`，按版本升序排列
+///
+/// SQL 必须可重复执行（使用 `IF NOT EXISTS`）。
+pub const MIGRATIONS: &[(u32, &str)] = &[
+    (
+        2,
+        "CREATE TABLE IF NOT EXISTS did_rotation_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id TEXT NOT NULL,
+            old_did TEXT NOT NULL,
+            new_did TEXT NOT NULL,
+            reason TEXT,
+            rotated_at INTEGER DEFAULT (unixepoch()),
+            FOREIGN KEY (user_id) REFERENCES matrix_users(user_id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_did_rotation_user ON did_rotation_history(user_id);",
+    ),
+];
+
+/// 当前 Schema 版本
+pub fn latest_schema_version() -> u32 {
+    MIGRATIONS.last().map_or(BASE_SCHEMA_VERSION, |(version, _)| *version)
+}
+
 /// 用户记录
 #[derive(Debug, Clone)]
 pub struct UserRecord {
@@ -82,6 +115,7 @@ impl MatrixSocialStore {
         };
 
         store.init_schema()?;
+        store.run_migrations()?;
         Ok(store)
     }
 
@@ -95,6 +129,7 @@ impl MatrixSocialStore {
         };
 
         store.init_schema()?;
+        store.run_migrations()?;
         Ok(store)
     }
 
@@ -189,9 +224,77 @@ impl MatrixSocialStore {
             [],
         ).map_err(|e| MatrixError::Store(format!("Failed to create index: {}", e)))?;
 
+        // Schema 版本表
+        db.execute(
+            "CREATE TABLE IF NOT EXISTS schema_version (
+                version INTEGER PRIMARY KEY,
+                applied_at INTEGER DEFAULT (unixepoch())
+            )",
+            [],
+        ).map_err(|e| MatrixError::Store(format!("Failed to create schema version table: {}", e)))?;
+
+        db.execute(
+            "INSERT OR IGNORE INTO schema_version (version) VALUES (?1)",
+            [BASE_SCHEMA_VERSION],
+        ).map_err(|e| MatrixError::Store(format!("Failed to record schema version: {}", e)))?;
+
         Ok(())
     }
 
+    // ==================== Schema Migrations ====================
+
+    /// 当前数据库的 Schema 版本
+    pub fn schema_version(&self) -> MatrixResult<u32> {
+        let db = self.db.lock()
+            .map_err(|_| MatrixError::Internal("Failed to lock database".to_string()))?;
+
+        Self::current_version(&db)
+    }
+
+    /// 执行未应用的迁移，返回已执行迁移的描述（如 `v1 -> v2`）
+    ///
+    /// 每个迁移在独立事务中执行，失败时回滚并返回
+    /// [`MatrixError::MigrationFailed`]，之前成功的迁移保留。
+    pub fn run_migrations(&self) -> MatrixResult<Vec<String>> {
+        self.apply_migrations(MIGRATIONS)
+    }
+
+    fn apply_migrations(&self, migrations: &[(u32, &str)]) -> MatrixResult<Vec<String>> {
+        let mut db = self.db.lock()
+            .map_err(|_| MatrixError::Internal("Failed to lock database".to_string()))?;
+
+        let mut current = Self::current_version(&db)?;
+        let mut applied = Vec::new();
+
+        for &(version, sql) in migrations {
+            if version <= current {
+                continue;
+            }
+
+            let fail = |e: rusqlite::Error| MatrixError::MigrationFailed(version, e.to_string());
+            let tx = db.transaction().map_err(fail)?;
+            tx.execute_batch(sql).map_err(fail)?;
+            tx.execute("INSERT OR IGNORE INTO schema_version (version) VALUES (?1)", [version])
+                .map_err(fail)?;
+            tx.commit().map_err(fail)?;
+
+            applied.push(format!("v{} -> v{}", current, version));
+            current = version;
+        }
+
+        Ok(applied)
+    }
+
+    fn current_version(db: &Connection) -> MatrixResult<u32> {
+        let version: Option<u32> = db.query_row(
+            "SELECT MAX(version) FROM schema_version",
+            [],
+            |row| row.get(0),
+        ).map_err(|e| MatrixError::Store(format!("Failed to read schema version: {}", e)))?;
+
+        Ok(version.unwrap_or(BASE_SCHEMA_VERSION))
+    }
+
     // ==================== User Management ====================
 
     /// 创建新用户
@@ -869,6 +972,47 @@ mod tests {
         assert!(store.validate_token(&token).unwrap().is_none());
     }
 
+    #[test]
+    fn test_migrations_applied_on_open() {
+        let store = MatrixSocialStore::open_in_memory().unwrap();
+        assert_eq!(store.schema_version().unwrap(), latest_schema_version());
+        assert!(store.run_migrations().unwrap().is_empty());
+
+        // 模拟 v1 数据库
+        {
+            let db = store.conn();
+            db.execute_batch(
+                "DROP TABLE did_rotation_history; DELETE FROM schema_version WHERE version > 1;",
+            ).unwrap();
+        }
+        assert_eq!(store.schema_version().unwrap(), 1);
+        assert_eq!(store.run_migrations().unwrap(), vec!["v1 -> v2".to_string()]);
+        assert_eq!(store.schema_version().unwrap(), 2);
+
+        store.conn().execute(
+            "INSERT INTO did_rotation_history (user_id, old_did, new_did) VALUES ('@a:x', 'did:cis:a:1', 'did:cis:a:2')",
+            [],
+        ).unwrap();
+    }
+
+    #[test]
+    fn test_failed_migration_rolls_back() {
+        let store = MatrixSocialStore::open_in_memory().unwrap();
+        let latest = latest_schema_version();
+        let broken = [(latest + 1, "CREATE TABLE partial (x INTEGER); NOT VALID SQL")];
+
+        let result = store.apply_migrations(&broken);
+        assert!(matches!(result, Err(MatrixError::MigrationFailed(v, _)) if v == latest + 1));
+        assert_eq!(store.schema_version().unwrap(), latest);
+
+        let partial: i64 = store.conn().query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE name = 'partial'",
+            [],
+            |row| row.get(0),
+        ).unwrap();
+        assert_eq!(partial, 0);
+    }
+
     #[test]
     fn test_complete_registration() {
         let store = MatrixSocialStore::open_in_memory().unwrap();
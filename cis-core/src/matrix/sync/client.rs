@@ -0,0 +1,376 @@
+//! Matrix 客户端同步循环
+//!
+//! 以 `since` 令牌长轮询 homeserver 的 `GET /_matrix/client/v3/sync`，
+//! 把已加入房间时间线中的事件转换为 [`MatrixEvent`] 后分发：
+//!
+//! - 遇到 `M_UNKNOWN_TOKEN` 时用 refresh token 换取新的 access token 后重试
+//! - 网络或服务端错误按指数退避重试
+//! - 通过 `watch` 通道暂停/恢复
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use tokio::sync::{broadcast, watch};
+use tracing::{debug, info, warn};
+
+use crate::matrix::error::{MatrixError, MatrixResult};
+use crate::matrix::nucleus::{EventId, MatrixEvent, RoomId, RoomManager, RoomStateCache, UserId};
+use crate::matrix::websocket::protocol::SyncFilter;
+
+/// 同步客户端配置
+#[derive(Debug, Clone)]
+pub struct SyncClientConfig {
+    /// homeserver 地址（如 `https://matrix.example.com`）
+    pub homeserver: String,
+    /// 访问令牌
+    pub access_token: String,
+    /// 刷新令牌（可选）
+    pub refresh_token: Option<String>,
+    /// 长轮询超时（毫秒）
+    pub timeout_ms: u64,
+    /// 初始退避时间
+    pub initial_backoff: Duration,
+    /// 最大退避时间
+    pub max_backoff: Duration,
+}
+
+impl SyncClientConfig {
+    /// 创建配置
+    pub fn new(homeserver: impl Into<String>, access_token: impl Into<String>) -> Self {
+        Self {
+            homeserver: homeserver.into().trim_end_matches('/').to_string(),
+            access_token: access_token.into(),
+            refresh_token: None,
+            timeout_ms: 30_000,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+
+    /// 设置刷新令牌
+    pub fn with_refresh_token(mut self, refresh_token: impl Into<String>) -> Self {
+        self.refresh_token = Some(refresh_token.into());
+        self
+    }
+
+    /// 设置长轮询超时
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// 设置退避范围
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+}
+
+/// `/sync` 响应中用到的部分
+#[derive(Debug, Deserialize)]
+struct SyncBatch {
+    next_batch: String,
+    #[serde(default)]
+    rooms: SyncRooms,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SyncRooms {
+    #[serde(default)]
+    join: HashMap<String, JoinedRoom>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct JoinedRoom {
+    #[serde(default)]
+    timeline: Timeline,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Timeline {
+    #[serde(default)]
+    events: Vec<RoomEvent>,
+}
+
+/// 房间时间线事件
+#[derive(Debug, Deserialize)]
+struct RoomEvent {
+    event_id: String,
+    sender: String,
+    #[serde(rename = "type")]
+    event_type: String,
+    #[serde(default)]
+    content: serde_json::Value,
+    origin_server_ts: Option<i64>,
+}
+
+impl RoomEvent {
+    fn into_matrix_event(self, room_id: &str) -> MatrixEvent {
+        let mut event = MatrixEvent::new(
+            RoomId::new(room_id),
+            EventId::new(self.event_id),
+            UserId::new(self.sender),
+            self.event_type,
+            self.content,
+        );
+        if let Some(ts) = self.origin_server_ts {
+            event.timestamp = ts;
+        }
+        event
+    }
+}
+
+/// `/rooms/{roomId}/state` 返回的状态事件
+#[derive(Debug, Deserialize)]
+struct StateEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    #[serde(default)]
+    state_key: String,
+    #[serde(default)]
+    content: serde_json::Value,
+}
+
+/// 从房间状态中取出 `membership = join` 的成员
+fn joined_members(state: Vec<StateEvent>) -> HashSet<UserId> {
+    state
+        .into_iter()
+        .filter(|e| e.event_type == "m.room.member" && e.content["membership"] == "join")
+        .map(|e| UserId::new(e.state_key))
+        .collect()
+}
+
+/// `/refresh` 响应
+#[derive(Debug, Deserialize)]
+struct RefreshResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+}
+
+/// 同步客户端（由同步任务独占）
+pub(crate) struct SyncClient {
+    http: Client,
+    config: SyncClientConfig,
+    since: Option<String>,
+}
+
+impl SyncClient {
+    pub(crate) fn new(config: SyncClientConfig) -> Self {
+        // 长轮询期间服务端最多挂起 timeout_ms，额外留出网络余量
+        let http = Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms) + Duration::from_secs(30))
+            .build()
+            .unwrap_or_default();
+        Self {
+            http,
+            config,
+            since: None,
+        }
+    }
+
+    /// 执行一次 `/sync`，成功后推进 `since`
+    async fn sync_once(&mut self) -> MatrixResult<Vec<MatrixEvent>> {
+        let url = format!("{}/_matrix/client/v3/sync", self.config.homeserver);
+        let mut query = vec![("timeout", self.config.timeout_ms.to_string())];
+        if let Some(since) = &self.since {
+            query.push(("since", since.clone()));
+        }
+
+        let response = self
+            .http
+            .get(&url)
+            .bearer_auth(&self.config.access_token)
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| MatrixError::ServerError(format!("sync request failed: {}", e)))?;
+
+        let batch: SyncBatch = Self::read_json(response).await?;
+        self.since = Some(batch.next_batch);
+
+        Ok(batch
+            .rooms
+            .join
+            .into_iter()
+            .flat_map(|(room_id, room)| {
+                room.timeline
+                    .events
+                    .into_iter()
+                    .map(move |e| e.into_matrix_event(&room_id))
+            })
+            .collect())
+    }
+
+    /// 通过 `GET /rooms/{roomId}/state` 获取房间当前已加入的成员
+    pub(crate) async fn room_members(&self, room_id: &RoomId) -> MatrixResult<HashSet<UserId>> {
+        let mut url = reqwest::Url::parse(&self.config.homeserver)
+            .map_err(|e| MatrixError::InvalidParameter(format!("invalid homeserver url: {}", e)))?;
+        url.path_segments_mut()
+            .map_err(|_| MatrixError::InvalidParameter("invalid homeserver url".to_string()))?
+            .extend(["_matrix", "client", "v3", "rooms", room_id.as_str(), "state"]);
+
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(&self.config.access_token)
+            .send()
+            .await
+            .map_err(|e| MatrixError::ServerError(format!("state request failed: {}", e)))?;
+
+        let state: Vec<StateEvent> = Self::read_json(response).await?;
+        Ok(joined_members(state))
+    }
+
+    /// 用 refresh token 换取新的 access token
+    async fn refresh_token(&mut self) -> MatrixResult<()> {
+        let Some(refresh_token) = self.config.refresh_token.clone() else {
+            return Err(MatrixError::Unauthorized(
+                "access token rejected and no refresh token configured".to_string(),
+            ));
+        };
+
+        let url = format!("{}/_matrix/client/v3/refresh", self.config.homeserver);
+        let response = self
+            .http
+            .post(&url)
+            .json(&serde_json::json!({ "refresh_token": refresh_token }))
+            .send()
+            .await
+            .map_err(|e| MatrixError::ServerError(format!("refresh request failed: {}", e)))?;
+
+        let refreshed: RefreshResponse = Self::read_json(response).await?;
+        self.config.access_token = refreshed.access_token;
+        if refreshed.refresh_token.is_some() {
+            self.config.refresh_token = refreshed.refresh_token;
+        }
+        Ok(())
+    }
+
+    /// 解析响应；401 + `M_UNKNOWN_TOKEN` 映射为 [`MatrixError::Unauthorized`]
+    async fn read_json<T: serde::de::DeserializeOwned>(
+        response: reqwest::Response,
+    ) -> MatrixResult<T> {
+        let status = response.status();
+        if status.is_success() {
+            return response
+                .json()
+                .await
+                .map_err(|e| MatrixError::InvalidJson(e.to_string()));
+        }
+
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let errcode = body["errcode"].as_str().unwrap_or_default();
+        if status == StatusCode::UNAUTHORIZED && errcode == "M_UNKNOWN_TOKEN" {
+            return Err(MatrixError::Unauthorized(errcode.to_string()));
+        }
+        Err(MatrixError::ServerError(format!("{} {}", status, body)))
+    }
+}
+
+/// 同步循环：直到事件总线关闭或暂停通道被丢弃时退出
+pub(crate) async fn run_sync_loop(
+    mut client: SyncClient,
+    filter: SyncFilter,
+    event_bus: broadcast::Sender<MatrixEvent>,
+    room_manager: RoomManager,
+    room_state_cache: Arc<RoomStateCache>,
+    mut paused: watch::Receiver<bool>,
+) {
+    let mut backoff = client.config.initial_backoff;
+    info!("Matrix sync started against {}", client.config.homeserver);
+
+    loop {
+        // 暂停时等待恢复
+        while *paused.borrow_and_update() {
+            if paused.changed().await.is_err() {
+                return;
+            }
+        }
+
+        let error = match client.sync_once().await {
+            Ok(events) => {
+                backoff = client.config.initial_backoff;
+                for event in events {
+                    // 成员变化后缓存的成员列表失效
+                    if event.event_type == "m.room.member" {
+                        room_state_cache.invalidate(&event.room_id);
+                    }
+                    if !filter.matches_event_type(&event.event_type)
+                        || !filter.matches_sender(event.sender_str())
+                    {
+                        continue;
+                    }
+                    room_manager.broadcast_to_room(&event.room_id, &event).await;
+                    if event_bus.send(event).is_err() {
+                        debug!("No event bus receivers, stopping Matrix sync");
+                        return;
+                    }
+                }
+                continue;
+            }
+            Err(MatrixError::Unauthorized(_)) => match client.refresh_token().await {
+                Ok(()) => {
+                    info!("Matrix access token refreshed, resyncing");
+                    continue;
+                }
+                Err(e) => e,
+            },
+            Err(e) => e,
+        };
+
+        warn!("Matrix sync failed: {}, retrying in {:?}", error, backoff);
+        tokio::select! {
+            _ = tokio::time::sleep(backoff) => {}
+            // 暂停/恢复时立即重新检查
+            changed = paused.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+            }
+        }
+        backoff = (backoff * 2).min(client.config.max_backoff);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sync_batch() {
+        let batch: SyncBatch = serde_json::from_value(serde_json::json!({
+            "next_batch": "s2",
+            "rooms": {
+                "join": {
+                    "!a:example.com": {
+                        "timeline": {
+                            "events": [{
+                                "event_id": "$1",
+                                "sender": "@alice:example.com",
+                                "type": "m.room.message",
+                                "content": {"body": "hi"},
+                                "origin_server_ts": 42
+                            }]
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(batch.next_batch, "s2");
+        let room = batch.rooms.join.into_iter().next().unwrap();
+        let event = room.1.timeline.events.into_iter().next().unwrap().into_matrix_event(&room.0);
+        assert_eq!(event.room_id_str(), "!a:example.com");
+        assert_eq!(event.event_type, "m.room.message");
+        assert_eq!(event.timestamp, 42);
+
+        // 空响应只含 next_batch
+        let empty: SyncBatch = serde_json::from_str(r#"{"next_batch":"s3"}"#).unwrap();
+        assert!(empty.rooms.join.is_empty());
+    }
+}
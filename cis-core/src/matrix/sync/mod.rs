@@ -7,10 +7,14 @@
 //! - **SyncConsumer**: 断线同步队列消费者，定期消费 pending_sync 表
 //! - **SyncQueue**: 优化的同步队列，支持优先级和批处理
 //! - **SyncTask**: 同步任务定义
+//! - **SyncClientConfig**: 客户端 `/sync` 长轮询配置
 
+pub mod client;
 pub mod consumer;
 pub mod queue;
 
+pub use client::SyncClientConfig;
+
 pub use consumer::{QueueStatus, SyncConfig, SyncConsumer, SyncResult};
 pub use queue::{
     BatchOperation, SyncMetrics, SyncPriority, SyncQueue, SyncQueueConfig, SyncStatus, SyncTask,
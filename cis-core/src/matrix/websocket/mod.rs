@@ -128,7 +128,8 @@ pub use noise::{
 };
 pub use protocol::{
     build_ws_url, AckMessage, AuthMessage, ErrorCode, ErrorMessage, EventMessage,
-    HandshakeMessage, PingMessage, PongMessage, SyncRequest, SyncResponse, WsMessage,
+    HandshakeMessage, MemorySyncFrame, PingMessage, PongMessage, SyncRequest, SyncResponse,
+    WsMessage,
     DEFAULT_WS_PORT, PROTOCOL_VERSION, WS_PATH,
 };
 pub use server::{
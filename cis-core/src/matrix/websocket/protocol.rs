@@ -9,6 +9,7 @@
 //! - `Event`: Matrix event forwarding
 //! - `Heartbeat`: Keep-alive ping/pong
 //! - `Error`: Error responses
+//! - `MemorySync*`: Public memory sync (see [`crate::memory::sync`])
 
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -52,6 +53,31 @@ pub enum WsMessage {
     /// Sync response with events
     #[serde(rename = "sync_response")]
     SyncResponse(SyncResponse),
+
+    /// Public memory sync request
+    #[serde(rename = "memory_sync_request")]
+    MemorySyncRequest(MemorySyncFrame),
+
+    /// Public memory sync response
+    #[serde(rename = "memory_sync_response")]
+    MemorySyncResponse(MemorySyncFrame),
+}
+
+/// Memory sync payload
+///
+/// Carries a JSON-encoded `MemorySyncRequest` / `MemorySyncResponse` so the
+/// federation protocol does not depend on the memory types.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemorySyncFrame {
+    /// JSON body
+    pub body: String,
+}
+
+impl MemorySyncFrame {
+    /// Create a new memory sync frame
+    pub fn new(body: impl Into<String>) -> Self {
+        Self { body: body.into() }
+    }
 }
 
 /// Handshake message for Noise protocol
@@ -8,6 +8,7 @@
 //! - Noise protocol handshake (placeholder)
 //! - DID authentication
 //! - Event forwarding
+//! - Public memory sync for authenticated peers
 
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -20,10 +21,12 @@ use tracing::{debug, error, info, warn};
 
 use crate::matrix::store::MatrixStore;
 use crate::identity::DIDManager;
+use crate::memory::sync::{MemorySyncRequest, MemorySyncResponse};
+use crate::memory::MemorySyncService;
 
 use super::protocol::{
-    AckMessage, AuthMessage, ErrorCode, ErrorMessage, HandshakeMessage, WsMessage,
-    PROTOCOL_VERSION, WS_PATH,
+    AckMessage, AuthMessage, ErrorCode, ErrorMessage, HandshakeMessage, MemorySyncFrame,
+    WsMessage, PROTOCOL_VERSION, WS_PATH,
 };
 use super::tunnel::{TunnelManager, TunnelState};
 
@@ -39,6 +42,8 @@ pub struct WebSocketServer {
     node_did: String,
     /// DID manager for authentication
     did_manager: Arc<DIDManager>,
+    /// Serves memory sync requests from authenticated peers
+    memory_sync: Option<Arc<MemorySyncService>>,
     /// Shutdown signal sender
     shutdown_tx: Option<mpsc::Sender<()>>,
 }
@@ -123,10 +128,17 @@ impl WebSocketServer {
             store,
             node_did: node_did.into(),
             did_manager,
+            memory_sync: None,
             shutdown_tx: None,
         }
     }
 
+    /// Serve public memory sync requests from authenticated peers
+    pub fn with_memory_sync(mut self, memory_sync: Arc<MemorySyncService>) -> Self {
+        self.memory_sync = Some(memory_sync);
+        self
+    }
+
     /// Run the WebSocket server
     pub async fn run(&mut self) -> Result<(), WsServerError> {
         let addr = SocketAddr::from((
@@ -184,6 +196,7 @@ impl WebSocketServer {
         let config = self.config.clone();
         let node_did = self.node_did.clone();
         let did_manager = self.did_manager.clone();
+        let memory_sync = self.memory_sync.clone();
 
         tokio::spawn(async move {
             debug!("New WebSocket connection from {}", peer_addr);
@@ -206,6 +219,7 @@ impl WebSocketServer {
                 config,
                 node_did,
                 did_manager,
+                memory_sync,
             );
 
             if let Err(e) = handler.run().await {
@@ -249,6 +263,8 @@ struct ConnectionHandler {
     node_did: String,
     /// DID manager for authentication
     did_manager: Arc<DIDManager>,
+    /// Memory sync service (if enabled)
+    memory_sync: Option<Arc<MemorySyncService>>,
     /// Remote node ID (set after auth)
     remote_node_id: Option<String>,
     /// Authenticated flag
@@ -269,6 +285,7 @@ impl ConnectionHandler {
         config: WsServerConfig,
         node_did: String,
         did_manager: Arc<DIDManager>,
+        memory_sync: Option<Arc<MemorySyncService>>,
     ) -> Self {
         let (msg_tx, msg_rx) = mpsc::unbounded_channel();
         Self {
@@ -279,6 +296,7 @@ impl ConnectionHandler {
             config,
             node_did,
             did_manager,
+            memory_sync,
             remote_node_id: None,
             authenticated: false,
             msg_tx,
@@ -525,6 +543,36 @@ impl ConnectionHandler {
                 }
                 Ok(())
             }
+
+            WsMessage::MemorySyncRequest(frame) => {
+                // 记忆同步会写入本地公域记忆，始终要求对端完成 DID 认证
+                if !self.authenticated {
+                    return self
+                        .send_error(ErrorCode::Unauthorized, "Not authenticated")
+                        .await;
+                }
+
+                let Some(memory_sync) = self.memory_sync.clone() else {
+                    return self
+                        .send_error(ErrorCode::InternalError, "Memory sync is not enabled")
+                        .await;
+                };
+
+                let response = match serde_json::from_str::<MemorySyncRequest>(&frame.body) {
+                    Ok(request) => memory_sync.handle_request(request).await,
+                    Err(e) => MemorySyncResponse::Error(format!("Invalid sync request: {}", e)),
+                };
+                let body = serde_json::to_string(&response)
+                    .map_err(|e| WsServerError::SerializationError(e.to_string()))?;
+                self.msg_tx
+                    .send(WsMessage::MemorySyncResponse(MemorySyncFrame::new(body)))
+                    .map_err(|_| WsServerError::SendError)
+            }
+
+            WsMessage::MemorySyncResponse(_) => {
+                debug!("Ignoring unsolicited memory sync response from {}", self.peer_addr);
+                Ok(())
+            }
         }
     }
 
@@ -814,6 +862,7 @@ pub struct WebSocketServerBuilder {
     store_path: Option<String>,
     node_did: Option<String>,
     did_manager: Option<Arc<DIDManager>>,
+    memory_sync: Option<Arc<MemorySyncService>>,
 }
 
 impl WebSocketServerBuilder {
@@ -846,6 +895,12 @@ impl WebSocketServerBuilder {
         self
     }
 
+    /// Set memory sync service
+    pub fn memory_sync(mut self, memory_sync: Arc<MemorySyncService>) -> Self {
+        self.memory_sync = Some(memory_sync);
+        self
+    }
+
     /// Build the server
     pub fn build(self) -> Result<WebSocketServer, WsServerError> {
         let config = self.config.unwrap_or_default();
@@ -866,7 +921,12 @@ impl WebSocketServerBuilder {
         let did_manager = self.did_manager
             .ok_or_else(|| WsServerError::ConfigError("DID manager is required".to_string()))?;
 
-        Ok(WebSocketServer::new(config, tunnel_manager, Arc::new(store), node_did, did_manager))
+        let mut server =
+            WebSocketServer::new(config, tunnel_manager, Arc::new(store), node_did, did_manager);
+        if let Some(memory_sync) = self.memory_sync {
+            server = server.with_memory_sync(memory_sync);
+        }
+        Ok(server)
     }
 }
 
@@ -977,6 +1037,7 @@ mod tests {
             config,
             node_did: "did:cis:test".to_string(),
             did_manager,
+            memory_sync: None,
             remote_node_id: Some("remote-node".to_string()),
             authenticated: true,
             msg_tx,
@@ -1035,6 +1096,7 @@ mod tests {
             config,
             node_did: "did:cis:test".to_string(),
             did_manager,
+            memory_sync: None,
             remote_node_id: Some("remote-node".to_string()),
             authenticated: true,
             msg_tx,
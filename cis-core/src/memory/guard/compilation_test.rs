@@ -24,6 +24,7 @@ mod compilation_tests {
             category: MemoryCategory::Context,
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            tags: Vec::new(),
         };
 
         memories.insert("test/key".to_string(), entry);
@@ -68,6 +69,7 @@ mod compilation_tests {
                 category: MemoryCategory::Context,
                 created_at: Utc::now().timestamp(),
                 updated_at: Utc::now().timestamp(),
+                tags: Vec::new(),
             };
 
             memories.insert(format!("key{}", i), entry);
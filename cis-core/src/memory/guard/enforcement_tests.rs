@@ -76,6 +76,7 @@ mod enforcement_tests {
             category: MemoryCategory::Context,
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            tags: Vec::new(),
         };
         memories.insert("test/key".to_string(), entry);
 
@@ -206,6 +207,7 @@ mod enforcement_tests {
                 category: MemoryCategory::Context,
                 created_at: Utc::now().timestamp(),
                 updated_at: Utc::now().timestamp(),
+                tags: Vec::new(),
             };
             memories.insert(format!("key{}", i), entry);
         }
@@ -370,6 +372,7 @@ mod enforcement_tests {
             category: MemoryCategory::Context,
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            tags: Vec::new(),
         };
         memories.insert("test/key".to_string(), entry);
 
@@ -438,6 +441,7 @@ mod test_helpers {
             category: MemoryCategory::Context,
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            tags: Vec::new(),
         }
     }
 }
@@ -200,6 +200,7 @@ mod tests {
             category: MemoryCategory::Context,
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            tags: Vec::new(),
         };
 
         memories.insert("test/key".to_string(), entry);
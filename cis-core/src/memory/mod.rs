@@ -38,14 +38,21 @@ pub mod crypto;
 pub mod weekly_archived;
 pub mod guard;  // Conflict detection guard module (Phase 0: P1.7.0)
 pub mod scope;   // Memory scope (v1.1.7: stable hash binding)
+pub mod watermark;
+pub mod sync;
 
 // Re-export all public types
 pub use self::encryption::MemoryEncryption;
 pub use self::encryption_v2::{EncryptionKeyV2, MemoryEncryptionV2};
-pub use self::service::{MemoryItem, MemoryService, MemorySearchResult, SearchOptions, SyncMarker};
+pub use self::service::{
+    is_active_namespace, GcReport, MemoryItem, MemoryService, MemorySearchResult, NamespaceInfo, SearchOptions,
+    SyncMarker, PROJECT_NAMESPACE_PREFIX,
+};
 pub use self::weekly_archived::{WeeklyArchivedMemory, MemoryItem as WeeklyMemoryItem, WeeklyMemoryStats};
 pub use self::guard::{ConflictChecked, SafeMemoryContext};  // Conflict detection types
 pub use self::scope::MemoryScope;  // Memory scope
+pub use self::watermark::{MemoryWatermarkMonitor, WatermarkLevel, WatermarkStatus};
+pub use self::sync::{MemorySync, MemorySyncService, NodeEndpoint, SyncReport};
 
 /// 扩展的记忆条目（包含更多元数据）
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -133,6 +140,7 @@ mod tests {
             category: MemoryCategory::Context,
             created_at: 1234567890,
             updated_at: 1234567890,
+            tags: Vec::new(),
         };
 
         let ext: MemoryEntryExt = entry.into();
@@ -156,6 +164,7 @@ mod tests {
             version: 2,
             encrypted: false,
             owner: "node1".to_string(),
+            tags: Vec::new(),
         };
 
         let ext: MemoryEntryExt = item.into();
@@ -155,13 +155,7 @@ impl SearchOperations {
     /// - `Result<Vec<String>>`: 记忆键列表
     pub async fn list_keys(&self, domain: Option<MemoryDomain>) -> Result<Vec<String>> {
         let db = self.state.memory_db.lock().await;
-
-        let prefix = match &self.state.namespace {
-            Some(ns) => format!("{}/", ns),
-            None => String::new(),
-        };
-
-        db.list_keys(&prefix, domain)
+        db.list_keys(&self.namespace_prefix(), domain)
     }
 
     /// 使用过滤器列出记忆
@@ -218,6 +212,51 @@ impl SearchOperations {
         Ok(items)
     }
 
+    /// 列出带有指定标签的记忆
+    ///
+    /// # 参数
+    /// - `tag`: 标签
+    ///
+    /// # 返回
+    /// - `Result<Vec<MemoryItem>>`: 当前命名空间内带该标签的记忆（按键排序）
+    pub async fn list_by_tag(&self, tag: &str) -> Result<Vec<MemoryItem>> {
+        let db = self.state.memory_db.lock().await;
+        let entries = db.list_by_tag(tag, &self.namespace_prefix())?;
+
+        let mut items = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let mut item = MemoryItem::from(entry);
+            item.owner = self.state.node_id.clone();
+
+            // 解密私域记忆
+            if item.encrypted {
+                if let Some(ref enc) = self.state.encryption {
+                    item.value = enc.decrypt(&item.value)?;
+                }
+            }
+
+            items.push(item);
+        }
+
+        Ok(items)
+    }
+
+    /// 列出所有标签及其记忆数量
+    ///
+    /// # 返回
+    /// - `Result<Vec<(String, usize)>>`: 当前命名空间内的标签，按数量降序
+    pub async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+        let db = self.state.memory_db.lock().await;
+        db.list_tags(&self.namespace_prefix())
+    }
+
+    fn namespace_prefix(&self) -> String {
+        match &self.state.namespace {
+            Some(ns) => format!("{}/", ns),
+            None => String::new(),
+        }
+    }
+
     /// 统计记忆数量
     ///
     /// # 参数
@@ -356,4 +395,24 @@ mod tests {
         // 初始应该为空
         assert_eq!(items.len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_list_by_tag() {
+        let (state, _temp) = setup_test_state();
+        let set_ops = crate::memory::ops::SetOperations::new(Arc::clone(&state));
+        let ops = SearchOperations::new(state);
+
+        set_ops.set("a", b"alpha", MemoryDomain::Private, MemoryCategory::Context).await.unwrap();
+        set_ops.set("b", b"beta", MemoryDomain::Public, MemoryCategory::Result).await.unwrap();
+        set_ops.set_tags("a", &["work".to_string(), "important".to_string()]).await.unwrap();
+        set_ops.set_tags("b", &["work".to_string()]).await.unwrap();
+
+        let items = ops.list_by_tag("work").await.unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].value, b"alpha");
+        assert_eq!(items[0].tags, vec!["important", "work"]);
+
+        let tags = ops.list_tags().await.unwrap();
+        assert_eq!(tags, vec![("work".to_string(), 2), ("important".to_string(), 1)]);
+    }
 }
@@ -203,6 +203,26 @@ impl SetOperations {
         Ok(deleted)
     }
 
+    /// 设置记忆标签（替换已有标签）
+    ///
+    /// # 参数
+    /// - `key`: 记忆键
+    /// - `tags`: 标签列表
+    ///
+    /// # 返回
+    /// - `Result<()>`: 记忆不存在时返回错误
+    pub async fn set_tags(&self, key: &str, tags: &[String]) -> Result<()> {
+        let full_key = self.state.full_key(key);
+        let db = self.state.memory_db.lock().await;
+        db.set_tags(&full_key, tags)?;
+
+        if let Some(cache) = &self.state.cache {
+            cache.invalidate(key).await;
+        }
+
+        Ok(())
+    }
+
     /// 后台更新向量索引
     fn spawn_index_update(&self, key: &str, value: &[u8], category: &MemoryCategory) {
         // 尝试获取当前运行时
@@ -6,6 +6,7 @@ use std::sync::Arc;
 
 use crate::error::Result;
 use crate::memory::ops::MemoryServiceState;
+use crate::storage::memory_db::MemoryEntry;
 use crate::types::{MemoryCategory, MemoryDomain};
 use chrono::{DateTime, Utc};
 
@@ -118,6 +119,43 @@ impl SyncOperations {
         Ok(())
     }
 
+    /// 合并对端公域记忆
+    ///
+    /// 保留条目原有的时间戳（用于 LWW 冲突解决），写入后即视为已同步。
+    ///
+    /// # 参数
+    /// - `items`: 对端传来的记忆列表（非公域条目会被忽略）
+    ///
+    /// # 返回
+    /// - `Result<usize>`: 实际写入的条目数
+    pub async fn merge_public(&self, items: Vec<MemoryItem>) -> Result<usize> {
+        let mut merged = 0;
+        for item in items {
+            if item.domain != MemoryDomain::Public {
+                continue;
+            }
+
+            let entry = MemoryEntry {
+                key: item.key,
+                value: item.value,
+                domain: MemoryDomain::Public,
+                category: item.category,
+                created_at: item.created_at.timestamp(),
+                updated_at: item.updated_at.timestamp(),
+                tags: item.tags,
+            };
+
+            {
+                let db = self.state.memory_db.lock().await;
+                db.upsert_public_entry(&entry)?;
+            }
+
+            self.spawn_index_update(&entry.key, &entry.value, &entry.category);
+            merged += 1;
+        }
+        Ok(merged)
+    }
+
     /// 同步完成回调
     ///
     /// 当记忆成功同步到对等节点后调用。
@@ -293,6 +331,7 @@ mod tests {
             version: 1,
             encrypted: false,
             owner: "other-node".to_string(),
+            tags: Vec::new(),
         }];
 
         ops.import_public(items).await.unwrap();
@@ -39,6 +39,7 @@
 //! # }
 //! ```
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use chrono::{DateTime, Utc};
@@ -81,6 +82,9 @@ pub struct MemoryItem {
     pub version: u64,
     pub encrypted: bool,
     pub owner: String, // 节点ID
+    /// 用户定义的标签
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl From<crate::storage::memory_db::MemoryEntry> for MemoryItem {
@@ -95,6 +99,7 @@ impl From<crate::storage::memory_db::MemoryEntry> for MemoryItem {
             version: 1,
             encrypted: matches!(entry.domain, MemoryDomain::Private),
             owner: String::new(),
+            tags: entry.tags,
         }
     }
 }
@@ -153,6 +158,50 @@ pub struct SyncMarker {
     pub sync_peers: Vec<String>,
 }
 
+/// 项目记忆命名空间前缀（见 `ProjectConfig.memory.namespace`）
+pub const PROJECT_NAMESPACE_PREFIX: &str = "project/";
+
+/// 项目命名空间占用信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamespaceInfo {
+    /// 命名空间，如 `project/my-app`
+    pub namespace: String,
+    /// 键数量
+    pub key_count: u32,
+    /// 值总字节数
+    pub total_bytes: u64,
+    /// 最近访问时间（记忆库只记录写入时间，取最近一次更新）
+    pub last_accessed: DateTime<Utc>,
+}
+
+/// 孤儿命名空间回收报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GcReport {
+    /// 已删除的命名空间
+    pub deleted_namespaces: Vec<String>,
+    /// 已删除的键数量
+    pub deleted_keys: usize,
+    /// 释放的字节数
+    pub freed_bytes: u64,
+}
+
+/// 取键所属的项目命名空间：`project/<name>/...` → `project/<name>`
+fn project_namespace(key: &str) -> Option<&str> {
+    let rest = key.strip_prefix(PROJECT_NAMESPACE_PREFIX)?;
+    let name = rest.split('/').next().filter(|n| !n.is_empty())?;
+    Some(&key[..PROJECT_NAMESPACE_PREFIX.len() + name.len()])
+}
+
+/// 命名空间是否属于活跃项目
+///
+/// 活跃项目可写作完整命名空间（`project/foo`）或项目名（`foo`）。
+pub fn is_active_namespace(namespace: &str, active_projects: &[String]) -> bool {
+    active_projects.iter().any(|p| {
+        p.trim_end_matches('/') == namespace
+            || namespace.strip_prefix(PROJECT_NAMESPACE_PREFIX) == Some(p.as_str())
+    })
+}
+
 /// 记忆服务 - 私域/公域记忆分离管理
 ///
 /// 重构后的版本，使用 ops 模块分离职责。
@@ -399,6 +448,21 @@ impl MemoryService {
         self.search_ops.list_keys(domain).await
     }
 
+    /// 设置记忆标签（替换已有标签）
+    pub async fn set_tags(&self, key: &str, tags: &[String]) -> Result<()> {
+        self.set_ops.set_tags(key, tags).await
+    }
+
+    /// 列出带有指定标签的记忆
+    pub async fn list_by_tag(&self, tag: &str) -> Result<Vec<MemoryItem>> {
+        self.search_ops.list_by_tag(tag).await
+    }
+
+    /// 列出所有标签及其记忆数量
+    pub async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+        self.search_ops.list_tags().await
+    }
+
     // ==================== 私域记忆操作 ====================
 
     /// 存储私域记忆（内部方法）
@@ -467,11 +531,86 @@ impl MemoryService {
         self.sync_ops.import_public(items).await
     }
 
+    /// 合并对端公域记忆（保留原时间戳）
+    pub async fn merge_public(&self, items: Vec<MemoryItem>) -> Result<usize> {
+        self.sync_ops.merge_public(items).await
+    }
+
     /// 同步完成回调
     pub async fn on_sync_complete(&self, key: &str, peer_id: &str) -> Result<()> {
         self.sync_ops.on_sync_complete(key, peer_id).await
     }
 
+    // ==================== 命名空间回收 ====================
+
+    /// 列出所有项目命名空间及其占用
+    ///
+    /// 直接扫描记忆库，不受本服务 `namespace` 限制。
+    pub async fn list_project_namespaces(&self) -> Result<Vec<NamespaceInfo>> {
+        let entries = {
+            let db = self.state.memory_db.lock().await;
+            db.list_key_sizes(PROJECT_NAMESPACE_PREFIX)?
+        };
+
+        let mut namespaces: BTreeMap<String, (u32, u64, i64)> = BTreeMap::new();
+        for (key, size, updated_at) in &entries {
+            let Some(namespace) = project_namespace(key) else {
+                continue;
+            };
+            let stats = namespaces.entry(namespace.to_string()).or_default();
+            stats.0 += 1;
+            stats.1 += size;
+            stats.2 = stats.2.max(*updated_at);
+        }
+
+        Ok(namespaces
+            .into_iter()
+            .map(|(namespace, (key_count, total_bytes, updated_at))| NamespaceInfo {
+                namespace,
+                key_count,
+                total_bytes,
+                last_accessed: DateTime::from_timestamp(updated_at, 0).unwrap_or_else(Utc::now),
+            })
+            .collect())
+    }
+
+    /// 删除不属于任何活跃项目的项目命名空间
+    ///
+    /// `active_projects` 中的项可以是完整命名空间（`project/foo`）或项目名（`foo`）。
+    /// 同时移除对应的向量索引并清空缓存。
+    pub async fn garbage_collect_orphans(&self, active_projects: &[String]) -> Result<GcReport> {
+        let mut report = GcReport::default();
+        let mut deleted_namespaces = BTreeSet::new();
+
+        {
+            let db = self.state.memory_db.lock().await;
+            for (key, size, _) in db.list_key_sizes(PROJECT_NAMESPACE_PREFIX)? {
+                let Some(namespace) = project_namespace(&key) else {
+                    continue;
+                };
+                if is_active_namespace(namespace, active_projects) {
+                    continue;
+                }
+
+                if db.delete(&key)? {
+                    let _ = self.state.vector_storage.delete_memory_index(&key);
+                    deleted_namespaces.insert(namespace.to_string());
+                    report.deleted_keys += 1;
+                    report.freed_bytes += size;
+                }
+            }
+        }
+
+        if report.deleted_keys > 0 {
+            if let Some(cache) = &self.state.cache {
+                cache.clear().await;
+            }
+        }
+
+        report.deleted_namespaces = deleted_namespaces.into_iter().collect();
+        Ok(report)
+    }
+
     // ==================== 工具方法 ====================
 
     /// 获取节点ID
@@ -740,4 +879,42 @@ mod tests {
 
         service.close().await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_garbage_collect_orphans() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let memory_db = MemoryDb::open(&temp_dir.path().join("memory.db")).unwrap();
+        let vector_storage =
+            VectorStorage::open_with_service(&temp_dir.path().join("vector.db"), Arc::new(MockEmbeddingService))
+                .unwrap();
+        let service = MemoryService::new(
+            Arc::new(tokio::sync::Mutex::new(memory_db)),
+            Arc::new(vector_storage),
+            "test-node",
+        )
+        .unwrap();
+
+        {
+            let db = service.state.memory_db.lock().await;
+            db.set_private("project/alive/notes", b"keep", MemoryCategory::Context).unwrap();
+            db.set_private("project/gone/notes", b"12345", MemoryCategory::Context).unwrap();
+            db.set_public("project/gone/arch", b"678", MemoryCategory::Result).unwrap();
+            db.set_private("user/theme", b"dark", MemoryCategory::Context).unwrap();
+        }
+
+        let namespaces = service.list_project_namespaces().await.unwrap();
+        let names: Vec<_> = namespaces.iter().map(|n| n.namespace.as_str()).collect();
+        assert_eq!(names, vec!["project/alive", "project/gone"]);
+        assert_eq!((namespaces[1].key_count, namespaces[1].total_bytes), (2, 8));
+
+        // 活跃项目可用项目名表示
+        let report = service.garbage_collect_orphans(&["alive".to_string()]).await.unwrap();
+        assert_eq!(report.deleted_namespaces, vec!["project/gone".to_string()]);
+        assert_eq!((report.deleted_keys, report.freed_bytes), (2, 8));
+
+        let namespaces = service.list_project_namespaces().await.unwrap();
+        assert_eq!(namespaces.len(), 1);
+        assert_eq!(namespaces[0].namespace, "project/alive");
+        assert!(service.state.memory_db.lock().await.get("user/theme").unwrap().is_some());
+    }
 }
@@ -0,0 +1,768 @@
+//! # 双向公域记忆同步
+//!
+//! 与单个对等节点进行双向（bilateral）公域记忆同步。
+//!
+//! ## 协议
+//!
+//! 1. 交换公域记忆的 Merkle 根，根相同则直接结束
+//! 2. 交换每个键的摘要（更新时间 + 内容哈希），计算出分歧键
+//! 3. 拉取对端缺失/更新的条目，推送本地缺失/更新的条目
+//!
+//! 冲突按 `updated_at` 做 LWW（Last-Write-Wins）；时间相同时取内容哈希较大者，
+//! 保证双方得出相同结论。每个传输完成的键都会更新 [`SyncMarker`]。
+//!
+//! 传输层由 [`MemorySync`] 抽象：本地 [`MemorySyncService`] 自身实现该 trait，
+//! 远端节点通过 [`WsMemorySyncPeer`] 接入。请求走联邦 WebSocket 协议
+//! （[`WsMessage::MemorySyncRequest`]），连接建立后先以本节点 DID 完成认证；
+//! 服务端由 [`WebSocketServer::with_memory_sync`](crate::matrix::websocket::WebSocketServer::with_memory_sync)
+//! 注册，只处理已认证对端的请求，交给 [`MemorySyncService::handle_request`] 处理。
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::error::{CisError, Result};
+use crate::identity::DIDManager;
+use crate::matrix::websocket::protocol::{
+    AckStatus, AuthMessage, HandshakeMessage, MemorySyncFrame, WsMessage, PROTOCOL_VERSION,
+};
+use crate::memory::{MemoryItem, MemoryService, SyncMarker};
+use crate::types::MemoryDomain;
+
+/// 对等节点地址
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeEndpoint {
+    /// 节点 ID
+    pub node_id: String,
+    /// WebSocket 地址（如 `ws://10.0.0.2:6767`）
+    pub endpoint: String,
+}
+
+impl NodeEndpoint {
+    /// 创建节点地址
+    pub fn new(node_id: impl Into<String>, endpoint: impl Into<String>) -> Self {
+        Self {
+            node_id: node_id.into(),
+            endpoint: endpoint.into(),
+        }
+    }
+}
+
+/// 单个键的摘要
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyDigest {
+    /// 记忆键
+    pub key: String,
+    /// 最后更新时间（Unix 秒）
+    pub updated_at: i64,
+    /// 内容哈希（SHA-256 hex）
+    pub hash: String,
+}
+
+impl KeyDigest {
+    /// 从记忆条目计算摘要
+    pub fn of(item: &MemoryItem) -> Self {
+        Self {
+            key: item.key.clone(),
+            updated_at: item.updated_at.timestamp(),
+            hash: hex::encode(Sha256::digest(&item.value)),
+        }
+    }
+
+    /// Merkle 叶子哈希（键、时间和内容共同决定）
+    fn leaf(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.key.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(self.updated_at.to_le_bytes());
+        hasher.update(self.hash.as_bytes());
+        hasher.finalize().into()
+    }
+}
+
+/// 计算摘要集合的 Merkle 根
+///
+/// 叶子按键排序，奇数节点直接上提；空集合返回空输入的哈希。
+pub fn merkle_root(digests: &[KeyDigest]) -> String {
+    let mut sorted: Vec<&KeyDigest> = digests.iter().collect();
+    sorted.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let mut level: Vec<[u8; 32]> = sorted.iter().map(|d| d.leaf()).collect();
+    if level.is_empty() {
+        return hex::encode(Sha256::digest(b""));
+    }
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut hasher = Sha256::new();
+                    hasher.update(left);
+                    hasher.update(right);
+                    hasher.finalize().into()
+                }
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+
+    hex::encode(level[0])
+}
+
+/// 记忆同步端点
+///
+/// 同步双方都通过该 trait 暴露自己的公域记忆，
+/// 因此本地服务和远端连接可以互换使用。
+#[async_trait]
+pub trait MemorySync: Send + Sync {
+    /// 节点 ID
+    fn node_id(&self) -> &str;
+
+    /// 公域记忆的 Merkle 根
+    async fn merkle_root(&self) -> Result<String>;
+
+    /// 所有公域键的摘要
+    async fn key_digests(&self) -> Result<Vec<KeyDigest>>;
+
+    /// 按键读取完整条目
+    async fn fetch_entries(&self, keys: &[String]) -> Result<Vec<MemoryItem>>;
+
+    /// 写入对端推送的条目，返回写入数量
+    async fn push_entries(&self, items: Vec<MemoryItem>) -> Result<usize>;
+}
+
+/// 同步报告
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    /// 对等节点 ID
+    pub peer: String,
+    /// 本地新增的键（对端独有）
+    pub added: usize,
+    /// 本地被对端较新版本覆盖的键
+    pub updated: usize,
+    /// 推送给对端的键
+    pub pushed: usize,
+    /// 双方都修改过、经 LWW 决出胜者的键
+    pub conflicts_resolved: usize,
+    /// Merkle 根一致，无需传输
+    pub already_in_sync: bool,
+    /// 本次传输涉及键的同步标记
+    pub markers: Vec<SyncMarker>,
+}
+
+impl SyncReport {
+    /// 传输的键总数
+    pub fn transferred(&self) -> usize {
+        self.added + self.updated + self.pushed
+    }
+}
+
+/// 同步方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Resolution {
+    /// 对端独有，拉取
+    Add,
+    /// 对端较新，拉取覆盖
+    Update,
+    /// 本地较新或本地独有，推送
+    Push,
+}
+
+/// 比较双方摘要，得出每个分歧键的处理方式
+///
+/// 返回 `(键 -> 处理方式, 冲突数)`。
+fn diff(
+    local: &BTreeMap<String, KeyDigest>,
+    remote: &BTreeMap<String, KeyDigest>,
+) -> (BTreeMap<String, Resolution>, usize) {
+    let mut plan = BTreeMap::new();
+    let mut conflicts = 0;
+
+    let keys: BTreeSet<&String> = local.keys().chain(remote.keys()).collect();
+    for key in keys {
+        let resolution = match (local.get(key), remote.get(key)) {
+            (None, Some(_)) => Resolution::Add,
+            (Some(_), None) => Resolution::Push,
+            (Some(l), Some(r)) if l.hash == r.hash => continue,
+            (Some(l), Some(r)) => {
+                conflicts += 1;
+                // LWW：时间相同时以哈希决胜，保证双方结论一致
+                if (r.updated_at, &r.hash) > (l.updated_at, &l.hash) {
+                    Resolution::Update
+                } else {
+                    Resolution::Push
+                }
+            }
+            (None, None) => continue,
+        };
+        plan.insert(key.clone(), resolution);
+    }
+
+    (plan, conflicts)
+}
+
+/// 记忆同步服务
+///
+/// 包装本地 [`MemoryService`]，负责与对等节点进行双向同步。
+pub struct MemorySyncService {
+    memory: Arc<MemoryService>,
+    /// 连接远端时用于认证的本节点身份
+    identity: Option<Arc<DIDManager>>,
+}
+
+impl std::fmt::Debug for MemorySyncService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemorySyncService")
+            .field("node_id", &self.memory.node_id())
+            .finish_non_exhaustive()
+    }
+}
+
+impl MemorySyncService {
+    /// 创建同步服务
+    pub fn new(memory: Arc<MemoryService>) -> Self {
+        Self { memory, identity: None }
+    }
+
+    /// 设置连接远端时使用的节点身份
+    pub fn with_identity(mut self, identity: Arc<DIDManager>) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// 与远端节点同步（通过 WebSocket 连接）
+    pub async fn sync_with_peer(&self, peer: &NodeEndpoint) -> Result<SyncReport> {
+        let identity = self
+            .identity
+            .as_deref()
+            .ok_or_else(|| CisError::identity("Memory sync with a remote peer requires a node identity"))?;
+        let remote = WsMemorySyncPeer::connect(peer, identity).await?;
+        self.sync_with(&remote).await
+    }
+
+    /// 与任意 [`MemorySync`] 端点同步
+    pub async fn sync_with(&self, peer: &dyn MemorySync) -> Result<SyncReport> {
+        let mut report = SyncReport {
+            peer: peer.node_id().to_string(),
+            ..Default::default()
+        };
+
+        // 1. Merkle 根
+        let local_digests = self.key_digests().await?;
+        if merkle_root(&local_digests) == peer.merkle_root().await? {
+            report.already_in_sync = true;
+            return Ok(report);
+        }
+
+        // 2. 分歧键
+        let local: BTreeMap<String, KeyDigest> = local_digests
+            .into_iter()
+            .map(|d| (d.key.clone(), d))
+            .collect();
+        let remote: BTreeMap<String, KeyDigest> = peer
+            .key_digests()
+            .await?
+            .into_iter()
+            .map(|d| (d.key.clone(), d))
+            .collect();
+
+        let (plan, conflicts) = diff(&local, &remote);
+        report.conflicts_resolved = conflicts;
+
+        let mut pull = Vec::new();
+        let mut push = Vec::new();
+        for (key, resolution) in &plan {
+            match resolution {
+                Resolution::Add => {
+                    report.added += 1;
+                    pull.push(key.clone());
+                }
+                Resolution::Update => {
+                    report.updated += 1;
+                    pull.push(key.clone());
+                }
+                Resolution::Push => push.push(key.clone()),
+            }
+        }
+
+        // 3. 传输
+        if !pull.is_empty() {
+            let items = peer.fetch_entries(&pull).await?;
+            self.memory.merge_public(items).await?;
+        }
+        if !push.is_empty() {
+            let items = self.fetch_entries(&push).await?;
+            report.pushed = peer.push_entries(items).await?;
+        }
+
+        let synced_at = Utc::now();
+        for key in pull.iter().chain(push.iter()) {
+            self.memory.on_sync_complete(key, peer.node_id()).await?;
+            report.markers.push(SyncMarker {
+                key: key.clone(),
+                domain: MemoryDomain::Public,
+                last_sync_at: Some(synced_at),
+                sync_peers: vec![peer.node_id().to_string()],
+            });
+        }
+
+        tracing::info!(
+            "Memory sync with {}: {} added, {} updated, {} pushed, {} conflicts",
+            report.peer,
+            report.added,
+            report.updated,
+            report.pushed,
+            report.conflicts_resolved
+        );
+
+        Ok(report)
+    }
+
+    /// 处理远端发来的同步请求（服务端入口）
+    pub async fn handle_request(&self, request: MemorySyncRequest) -> MemorySyncResponse {
+        let result = match request {
+            MemorySyncRequest::MerkleRoot => self.merkle_root().await.map(MemorySyncResponse::MerkleRoot),
+            MemorySyncRequest::Digests => self.key_digests().await.map(MemorySyncResponse::Digests),
+            MemorySyncRequest::Fetch { keys } => {
+                self.fetch_entries(&keys).await.map(MemorySyncResponse::Entries)
+            }
+            MemorySyncRequest::Push { entries } => {
+                self.push_entries(entries).await.map(MemorySyncResponse::Pushed)
+            }
+        };
+
+        result.unwrap_or_else(|e| MemorySyncResponse::Error(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl MemorySync for MemorySyncService {
+    fn node_id(&self) -> &str {
+        self.memory.node_id()
+    }
+
+    async fn merkle_root(&self) -> Result<String> {
+        Ok(merkle_root(&self.key_digests().await?))
+    }
+
+    async fn key_digests(&self) -> Result<Vec<KeyDigest>> {
+        let items = self.memory.export_public(0).await?;
+        Ok(items.iter().map(KeyDigest::of).collect())
+    }
+
+    async fn fetch_entries(&self, keys: &[String]) -> Result<Vec<MemoryItem>> {
+        let wanted: BTreeSet<&str> = keys.iter().map(String::as_str).collect();
+        let items = self.memory.export_public(0).await?;
+        Ok(items
+            .into_iter()
+            .filter(|item| wanted.contains(item.key.as_str()))
+            .collect())
+    }
+
+    async fn push_entries(&self, items: Vec<MemoryItem>) -> Result<usize> {
+        self.memory.merge_public(items).await
+    }
+}
+
+/// 同步请求（JSON 编码后放入 [`MemorySyncFrame`]）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MemorySyncRequest {
+    MerkleRoot,
+    Digests,
+    Fetch { keys: Vec<String> },
+    Push { entries: Vec<MemoryItem> },
+}
+
+/// 同步响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum MemorySyncResponse {
+    MerkleRoot(String),
+    Digests(Vec<KeyDigest>),
+    Entries(Vec<MemoryItem>),
+    Pushed(usize),
+    Error(String),
+}
+
+/// 通过 WebSocket 访问的远端同步端点
+pub struct WsMemorySyncPeer {
+    node_id: String,
+    stream: Mutex<SyncStream>,
+}
+
+impl WsMemorySyncPeer {
+    /// 连接远端节点并以 `identity` 完成 DID 认证
+    pub async fn connect(peer: &NodeEndpoint, identity: &DIDManager) -> Result<Self> {
+        let (stream, _) = tokio_tungstenite::connect_async(peer.endpoint.as_str())
+            .await
+            .map_err(|e| {
+                CisError::network(format!("Failed to connect to {}: {}", peer.endpoint, e))
+            })?;
+
+        let this = Self {
+            node_id: peer.node_id.clone(),
+            stream: Mutex::new(stream),
+        };
+        this.authenticate(identity).await?;
+        Ok(this)
+    }
+
+    /// 握手并发送签名的认证消息，等待服务端确认
+    async fn authenticate(&self, identity: &DIDManager) -> Result<()> {
+        let handshake = HandshakeMessage::new(PROTOCOL_VERSION, vec![], identity.did());
+        let public_key = hex::decode(identity.public_key_hex())
+            .map_err(|e| CisError::identity(format!("Invalid node public key: {}", e)))?;
+        let mut auth = AuthMessage::new(identity.did(), Vec::new(), public_key);
+        let challenge = format!("{}:{}", auth.did, auth.timestamp);
+        auth.challenge_response = identity.sign(challenge.as_bytes()).to_bytes().to_vec();
+
+        let mut stream = self.stream.lock().await;
+        for message in [WsMessage::Handshake(handshake), WsMessage::Auth(auth)] {
+            send(&mut stream, &message).await?;
+        }
+
+        loop {
+            match receive(&mut stream, &self.node_id).await? {
+                WsMessage::Ack(ack) if ack.message_id == "auth" => {
+                    return match ack.status {
+                        AckStatus::Success => Ok(()),
+                        _ => Err(CisError::network(format!(
+                            "Peer {} rejected authentication: {}",
+                            self.node_id,
+                            ack.error.unwrap_or_default()
+                        ))),
+                    };
+                }
+                WsMessage::Error(e) => {
+                    return Err(CisError::network(format!(
+                        "Peer {} rejected authentication: {}",
+                        self.node_id, e.message
+                    )));
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    async fn request(&self, request: &MemorySyncRequest) -> Result<MemorySyncResponse> {
+        let body = serde_json::to_string(request)
+            .map_err(|e| CisError::serialization(format!("Failed to encode sync request: {}", e)))?;
+
+        let mut stream = self.stream.lock().await;
+        send(&mut stream, &WsMessage::MemorySyncRequest(MemorySyncFrame::new(body))).await?;
+
+        loop {
+            match receive(&mut stream, &self.node_id).await? {
+                WsMessage::MemorySyncResponse(frame) => {
+                    let response: MemorySyncResponse = serde_json::from_str(&frame.body).map_err(|e| {
+                        CisError::serialization(format!("Invalid sync response: {}", e))
+                    })?;
+                    if let MemorySyncResponse::Error(e) = response {
+                        return Err(CisError::network(format!(
+                            "Peer {} rejected sync request: {}",
+                            self.node_id, e
+                        )));
+                    }
+                    return Ok(response);
+                }
+                WsMessage::Error(e) => {
+                    return Err(CisError::network(format!(
+                        "Peer {} rejected sync request: {}",
+                        self.node_id, e.message
+                    )));
+                }
+                _ => continue,
+            }
+        }
+    }
+}
+
+type SyncStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn send(stream: &mut SyncStream, message: &WsMessage) -> Result<()> {
+    let payload = serde_json::to_string(message)
+        .map_err(|e| CisError::serialization(format!("Failed to encode sync message: {}", e)))?;
+    stream
+        .send(Message::Text(payload))
+        .await
+        .map_err(|e| CisError::network(format!("Failed to send sync message: {}", e)))
+}
+
+/// 读取下一条协议消息，跳过非文本帧
+async fn receive(stream: &mut SyncStream, peer: &str) -> Result<WsMessage> {
+    while let Some(message) = stream.next().await {
+        let message =
+            message.map_err(|e| CisError::network(format!("Sync connection error: {}", e)))?;
+        match message {
+            Message::Text(text) => {
+                return serde_json::from_str(&text).map_err(|e| {
+                    CisError::serialization(format!("Invalid sync message: {}", e))
+                });
+            }
+            Message::Close(_) => break,
+            _ => continue,
+        }
+    }
+
+    Err(CisError::network(format!("Peer {} closed the sync connection", peer)))
+}
+
+fn unexpected(response: MemorySyncResponse) -> CisError {
+    CisError::network(format!("Unexpected sync response: {:?}", response))
+}
+
+#[async_trait]
+impl MemorySync for WsMemorySyncPeer {
+    fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    async fn merkle_root(&self) -> Result<String> {
+        match self.request(&MemorySyncRequest::MerkleRoot).await? {
+            MemorySyncResponse::MerkleRoot(root) => Ok(root),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    async fn key_digests(&self) -> Result<Vec<KeyDigest>> {
+        match self.request(&MemorySyncRequest::Digests).await? {
+            MemorySyncResponse::Digests(digests) => Ok(digests),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    async fn fetch_entries(&self, keys: &[String]) -> Result<Vec<MemoryItem>> {
+        let request = MemorySyncRequest::Fetch { keys: keys.to_vec() };
+        match self.request(&request).await? {
+            MemorySyncResponse::Entries(entries) => Ok(entries),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    async fn push_entries(&self, items: Vec<MemoryItem>) -> Result<usize> {
+        let request = MemorySyncRequest::Push { entries: items };
+        match self.request(&request).await? {
+            MemorySyncResponse::Pushed(count) => Ok(count),
+            other => Err(unexpected(other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::embedding::{EmbeddingService, DEFAULT_EMBEDDING_DIM};
+    use crate::storage::memory_db::MemoryDb;
+    use crate::types::MemoryCategory;
+    use crate::vector::VectorStorage;
+    use chrono::DateTime;
+    use tempfile::TempDir;
+
+    struct MockEmbeddingService;
+
+    #[async_trait]
+    impl EmbeddingService for MockEmbeddingService {
+        async fn embed(&self, _text: &str) -> Result<Vec<f32>> {
+            let mut vec = vec![0.0f32; DEFAULT_EMBEDDING_DIM];
+            vec[0] = 1.0;
+            Ok(vec)
+        }
+
+        async fn batch_embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+            let mut results = Vec::with_capacity(texts.len());
+            for text in texts {
+                results.push(self.embed(text).await?);
+            }
+            Ok(results)
+        }
+    }
+
+    fn setup_node(node_id: &str) -> (MemorySyncService, TempDir) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let memory_db = MemoryDb::open(&temp_dir.path().join("memory.db")).unwrap();
+        let vector_storage = VectorStorage::open_with_service(
+            &temp_dir.path().join("vector.db"),
+            Arc::new(MockEmbeddingService),
+        )
+        .unwrap();
+
+        let memory = MemoryService::new(
+            Arc::new(tokio::sync::Mutex::new(memory_db)),
+            Arc::new(vector_storage),
+            node_id,
+        )
+        .unwrap();
+
+        (MemorySyncService::new(Arc::new(memory)), temp_dir)
+    }
+
+    fn item(key: &str, value: &str, updated_at: i64) -> MemoryItem {
+        let ts = DateTime::from_timestamp(updated_at, 0).unwrap();
+        MemoryItem {
+            key: key.to_string(),
+            value: value.as_bytes().to_vec(),
+            domain: MemoryDomain::Public,
+            category: MemoryCategory::Context,
+            created_at: ts,
+            updated_at: ts,
+            version: 1,
+            encrypted: false,
+            owner: String::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    async fn value_of(node: &MemorySyncService, key: &str) -> Option<Vec<u8>> {
+        node.fetch_entries(&[key.to_string()])
+            .await
+            .unwrap()
+            .pop()
+            .map(|item| item.value)
+    }
+
+    #[test]
+    fn test_merkle_root_is_order_independent() {
+        let a = KeyDigest::of(&item("a", "1", 10));
+        let b = KeyDigest::of(&item("b", "2", 20));
+        let c = KeyDigest::of(&item("c", "3", 30));
+
+        let root = merkle_root(&[a.clone(), b.clone(), c.clone()]);
+        assert_eq!(root, merkle_root(&[c.clone(), a.clone(), b.clone()]));
+        assert_ne!(root, merkle_root(&[a, b]));
+        assert_ne!(root, merkle_root(&[]));
+    }
+
+    #[tokio::test]
+    async fn test_bilateral_sync_with_lww() {
+        let (node_a, _dir_a) = setup_node("node-a");
+        let (node_b, _dir_b) = setup_node("node-b");
+
+        node_a
+            .push_entries(vec![
+                item("shared/same", "same", 100),
+                item("shared/newer-on-a", "a-wins", 300),
+                item("shared/newer-on-b", "a-loses", 100),
+                item("only/a", "from-a", 100),
+            ])
+            .await
+            .unwrap();
+        node_b
+            .push_entries(vec![
+                item("shared/same", "same", 100),
+                item("shared/newer-on-a", "b-loses", 200),
+                item("shared/newer-on-b", "b-wins", 400),
+                item("only/b", "from-b", 100),
+            ])
+            .await
+            .unwrap();
+
+        let report = node_a.sync_with(&node_b).await.unwrap();
+        assert_eq!(report.peer, "node-b");
+        assert_eq!(report.added, 1);
+        assert_eq!(report.updated, 1);
+        assert_eq!(report.pushed, 2);
+        assert_eq!(report.conflicts_resolved, 2);
+        assert_eq!(report.markers.len(), 4);
+
+        for node in [&node_a, &node_b] {
+            assert_eq!(value_of(node, "shared/newer-on-a").await.unwrap(), b"a-wins");
+            assert_eq!(value_of(node, "shared/newer-on-b").await.unwrap(), b"b-wins");
+            assert_eq!(value_of(node, "only/a").await.unwrap(), b"from-a");
+            assert_eq!(value_of(node, "only/b").await.unwrap(), b"from-b");
+        }
+
+        // 再次同步时 Merkle 根一致，不再传输
+        let again = node_a.sync_with(&node_b).await.unwrap();
+        assert!(again.already_in_sync);
+        assert_eq!(again.transferred(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_sync_over_websocket_server() {
+        use crate::matrix::websocket::protocol::ErrorCode;
+        use crate::matrix::websocket::{WebSocketServerBuilder, WsServerConfig};
+
+        let (node_a, _dir_a) = setup_node("node-a");
+        let (node_b, _dir_b) = setup_node("node-b");
+        node_a.push_entries(vec![item("only/a", "from-a", 100)]).await.unwrap();
+        node_b.push_entries(vec![item("only/b", "from-b", 100)]).await.unwrap();
+        let node_b = Arc::new(node_b);
+
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server_identity = Arc::new(DIDManager::generate("node-b").unwrap());
+        let mut server = WebSocketServerBuilder::new()
+            .config(
+                WsServerConfig::new("node-b")
+                    .with_port(port)
+                    .with_bind_address("127.0.0.1"),
+            )
+            .node_did(server_identity.did())
+            .did_manager(server_identity)
+            .memory_sync(node_b.clone())
+            .build()
+            .unwrap();
+        let server_task = tokio::spawn(async move { server.run().await });
+
+        let endpoint = NodeEndpoint::new("node-b", format!("ws://127.0.0.1:{}", port));
+
+        // 没有节点身份无法连接远端
+        assert!(node_a.sync_with_peer(&endpoint).await.is_err());
+
+        let node_a = node_a.with_identity(Arc::new(DIDManager::generate("node-a").unwrap()));
+        let report = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                match node_a.sync_with_peer(&endpoint).await {
+                    Ok(report) => break report,
+                    Err(_) => tokio::time::sleep(std::time::Duration::from_millis(20)).await,
+                }
+            }
+        })
+        .await
+        .expect("sync over WebSocket did not complete");
+
+        assert_eq!(report.peer, "node-b");
+        assert_eq!(report.added, 1);
+        assert_eq!(report.pushed, 1);
+        for node in [&node_a, node_b.as_ref()] {
+            assert_eq!(value_of(node, "only/a").await.unwrap(), b"from-a");
+            assert_eq!(value_of(node, "only/b").await.unwrap(), b"from-b");
+        }
+
+        // 未认证的连接被拒绝
+        let (mut stream, _) = tokio_tungstenite::connect_async(endpoint.endpoint.as_str())
+            .await
+            .unwrap();
+        let body = serde_json::to_string(&MemorySyncRequest::Digests).unwrap();
+        send(&mut stream, &WsMessage::MemorySyncRequest(MemorySyncFrame::new(body)))
+            .await
+            .unwrap();
+        loop {
+            match receive(&mut stream, "node-b").await.unwrap() {
+                WsMessage::Error(e) => {
+                    assert_eq!(e.code, ErrorCode::Unauthorized);
+                    break;
+                }
+                WsMessage::MemorySyncResponse(_) => panic!("unauthenticated peer was served"),
+                _ => continue,
+            }
+        }
+
+        server_task.abort();
+    }
+}
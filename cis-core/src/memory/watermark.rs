@@ -0,0 +1,214 @@
+//! # 记忆数据库水位监控
+//!
+//! 后台任务定期计算数据库大小（`PRAGMA page_count * page_size`），
+//! 超过阈值时通过 [`EventBus`] 发布系统事件：
+//!
+//! - 超过 `warn_threshold_mb`：`SystemEventLevel::Warning`
+//! - 超过 `critical_threshold_mb`：`SystemEventLevel::Critical`
+//!
+//! 只在水位升高时发布一次，回落到阈值以下后重新计数，避免每个周期重复告警。
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rusqlite::{Connection, OpenFlags};
+use serde_json::json;
+use tokio::task::JoinHandle;
+
+use crate::config::MemoryWatermarkConfig;
+use crate::error::{CisError, Result};
+use crate::event_bus::EventBus;
+use crate::events::{EventWrapper, SystemEvent, SystemEventLevel};
+
+const MB: u64 = 1024 * 1024;
+
+/// 事件类别
+pub const WATERMARK_CATEGORY: &str = "memory.watermark";
+
+/// 水位级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WatermarkLevel {
+    Normal,
+    Warning,
+    Critical,
+}
+
+impl WatermarkLevel {
+    /// 级别名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WatermarkLevel::Normal => "normal",
+            WatermarkLevel::Warning => "warning",
+            WatermarkLevel::Critical => "critical",
+        }
+    }
+}
+
+/// 单次检查结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatermarkStatus {
+    /// 数据库大小（字节）
+    pub size_bytes: u64,
+    /// 当前水位
+    pub level: WatermarkLevel,
+}
+
+/// 数据库大小（字节），按 `page_count * page_size` 计算
+pub fn database_size(path: &Path) -> Result<u64> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| CisError::storage(format!("Failed to open {}: {}", path.display(), e)))?;
+    let size: i64 = conn
+        .query_row(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| CisError::storage(format!("Failed to read database size: {}", e)))?;
+    Ok(size.max(0) as u64)
+}
+
+/// 按配置判定水位
+pub fn watermark_level(size_bytes: u64, config: &MemoryWatermarkConfig) -> WatermarkLevel {
+    if size_bytes > config.critical_threshold_mb * MB {
+        WatermarkLevel::Critical
+    } else if size_bytes > config.warn_threshold_mb * MB {
+        WatermarkLevel::Warning
+    } else {
+        WatermarkLevel::Normal
+    }
+}
+
+/// 记忆数据库水位监控
+pub struct MemoryWatermarkMonitor {
+    db_path: PathBuf,
+    config: MemoryWatermarkConfig,
+    event_bus: Arc<dyn EventBus>,
+    /// 上次发布告警时的水位
+    last_level: tokio::sync::Mutex<WatermarkLevel>,
+}
+
+impl MemoryWatermarkMonitor {
+    /// 创建监控
+    pub fn new(
+        db_path: impl Into<PathBuf>,
+        config: MemoryWatermarkConfig,
+        event_bus: Arc<dyn EventBus>,
+    ) -> Self {
+        Self {
+            db_path: db_path.into(),
+            config,
+            event_bus,
+            last_level: tokio::sync::Mutex::new(WatermarkLevel::Normal),
+        }
+    }
+
+    /// 检查一次，水位升高时发布事件
+    pub async fn check(&self) -> Result<WatermarkStatus> {
+        let path = self.db_path.clone();
+        let size_bytes = tokio::task::spawn_blocking(move || database_size(&path))
+            .await
+            .map_err(|e| CisError::internal(format!("Watermark check panicked: {}", e)))??;
+        let level = watermark_level(size_bytes, &self.config);
+
+        let mut last_level = self.last_level.lock().await;
+        if level > *last_level {
+            self.event_bus
+                .publish(EventWrapper::System(self.event(size_bytes, level)))
+                .await?;
+        }
+        *last_level = level;
+
+        Ok(WatermarkStatus { size_bytes, level })
+    }
+
+    fn event(&self, size_bytes: u64, level: WatermarkLevel) -> SystemEvent {
+        let (severity, threshold_mb) = match level {
+            WatermarkLevel::Critical => (SystemEventLevel::Critical, self.config.critical_threshold_mb),
+            _ => (SystemEventLevel::Warning, self.config.warn_threshold_mb),
+        };
+        let message = format!(
+            "Memory database is {:.1} MB, above the {} threshold of {} MB",
+            size_bytes as f64 / MB as f64,
+            level.as_str(),
+            threshold_mb
+        );
+
+        SystemEvent::new(severity, WATERMARK_CATEGORY, message, "memory-watermark").with_details(json!({
+            "db_path": self.db_path.display().to_string(),
+            "size_bytes": size_bytes,
+            "warn_threshold_mb": self.config.warn_threshold_mb,
+            "critical_threshold_mb": self.config.critical_threshold_mb,
+        }))
+    }
+
+    /// 启动后台检查任务（数据库尚不存在时跳过本轮）
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        let interval = Duration::from_secs(self.config.check_interval_secs.max(1));
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if !self.db_path.exists() {
+                    continue;
+                }
+                if let Err(e) = self.check().await {
+                    tracing::warn!("Memory watermark check failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_bus::MemoryEventBus;
+
+    #[tokio::test]
+    async fn test_watermark_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory.db");
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch("CREATE TABLE t (v BLOB)").unwrap();
+
+        let bus = Arc::new(MemoryEventBus::new());
+        let config = MemoryWatermarkConfig {
+            warn_threshold_mb: 1,
+            critical_threshold_mb: 2,
+            check_interval_secs: 1,
+        };
+        let monitor = MemoryWatermarkMonitor::new(&path, config, bus.clone());
+
+        let status = monitor.check().await.unwrap();
+        assert_eq!(status.level, WatermarkLevel::Normal);
+        assert!(status.size_bytes > 0);
+
+        // 写入约 1.5 MB 触发 Warning，重复检查不再发布
+        conn.execute("INSERT INTO t (v) VALUES (zeroblob(1572864))", []).unwrap();
+        assert_eq!(monitor.check().await.unwrap().level, WatermarkLevel::Warning);
+        monitor.check().await.unwrap();
+
+        conn.execute("INSERT INTO t (v) VALUES (zeroblob(1048576))", []).unwrap();
+        assert_eq!(monitor.check().await.unwrap().level, WatermarkLevel::Critical);
+
+        let levels: Vec<_> = bus
+            .get_all_history()
+            .await
+            .into_iter()
+            .filter_map(|(_, event)| match event {
+                EventWrapper::System(e) if e.category == WATERMARK_CATEGORY => Some(e.level),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(levels, vec![SystemEventLevel::Warning, SystemEventLevel::Critical]);
+    }
+
+    #[test]
+    fn test_watermark_level() {
+        let config = MemoryWatermarkConfig::default();
+        assert_eq!(watermark_level(0, &config), WatermarkLevel::Normal);
+        assert_eq!(watermark_level(513 * MB, &config), WatermarkLevel::Warning);
+        assert_eq!(watermark_level(1025 * MB, &config), WatermarkLevel::Critical);
+    }
+}
@@ -3,6 +3,7 @@
 //! Manages whitelist/blacklist for network admission control.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{info, warn};
@@ -40,6 +41,56 @@ impl std::fmt::Display for NetworkMode {
     }
 }
 
+/// Policy for message senders that match no explicit rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[derive(Default)]
+pub enum DefaultPolicy {
+    /// Allow unmatched senders (keeps existing conversations working)
+    #[default]
+    Allow,
+    /// Deny unmatched senders
+    Deny,
+}
+
+impl std::fmt::Display for DefaultPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DefaultPolicy::Allow => write!(f, "allow"),
+            DefaultPolicy::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+/// Metadata key holding per-conversation rules (`conversation.metadata.acl`)
+pub const CONVERSATION_ACL_METADATA_KEY: &str = "acl";
+
+/// Per-conversation sender rules
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationAcl {
+    /// DIDs allowed to send in this conversation
+    #[serde(default)]
+    pub allow: Vec<String>,
+
+    /// DIDs denied from sending in this conversation
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl ConversationAcl {
+    /// Read rules from conversation metadata
+    ///
+    /// Returns `Ok(None)` when the metadata carries no `acl` key.
+    pub fn from_metadata(metadata: &serde_json::Value) -> crate::Result<Option<Self>> {
+        match metadata.get(CONVERSATION_ACL_METADATA_KEY) {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(value) => serde_json::from_value(value.clone())
+                .map(Some)
+                .map_err(|e| crate::error::CisError::invalid_input("metadata.acl", e.to_string())),
+        }
+    }
+}
+
 /// ACL entry for whitelist or blacklist
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AclEntry {
@@ -131,6 +182,14 @@ pub struct NetworkAcl {
     /// Signature of this ACL (to prevent tampering)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signature: Option<String>,
+    
+    /// Policy for message senders matching no explicit rule
+    #[serde(default)]
+    pub default_policy: DefaultPolicy,
+    
+    /// Per-conversation sender rules, keyed by conversation ID
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub conversations: HashMap<String, ConversationAcl>,
 }
 
 fn default_version() -> u64 {
@@ -154,6 +213,8 @@ impl Default for NetworkAcl {
             version: 1,
             updated_at: now(),
             signature: None,
+            default_policy: DefaultPolicy::default(),
+            conversations: HashMap::new(),
         }
     }
 }
@@ -169,6 +230,8 @@ impl NetworkAcl {
             version: 1,
             updated_at: now(),
             signature: None,
+            default_policy: DefaultPolicy::default(),
+            conversations: HashMap::new(),
         }
     }
     
@@ -250,6 +313,69 @@ impl NetworkAcl {
         }
     }
     
+    /// Check whether `sender_did` may send messages in `conversation_id`
+    ///
+    /// Uses the rules stored for the conversation, see
+    /// [`check_message_send_with`](Self::check_message_send_with).
+    pub fn check_message_send(&self, sender_did: &str, conversation_id: &str) -> crate::Result<()> {
+        self.check_message_send_with(sender_did, conversation_id, self.conversations.get(conversation_id))
+    }
+    
+    /// Check whether `sender_did` may send messages in `conversation_id` under `rules`
+    ///
+    /// Order: global blacklist, conversation deny, conversation allow,
+    /// global whitelist, then `default_policy`.
+    pub fn check_message_send_with(
+        &self,
+        sender_did: &str,
+        conversation_id: &str,
+        rules: Option<&ConversationAcl>,
+    ) -> crate::Result<()> {
+        let denied = |reason: String| {
+            warn!("Message from {} to conversation {} denied: {}", sender_did, conversation_id, reason);
+            Err(crate::error::CisError::acl_denied(sender_did, reason))
+        };
+        
+        if self.is_blacklisted(sender_did) {
+            return denied(format!("{} is in blacklist", sender_did));
+        }
+        
+        if let Some(rules) = rules {
+            if rules.deny.iter().any(|d| d == sender_did) {
+                return denied(format!("{} is denied in conversation {}", sender_did, conversation_id));
+            }
+            if rules.allow.iter().any(|d| d == sender_did) {
+                return Ok(());
+            }
+        }
+        
+        if self.is_whitelisted(sender_did) {
+            return Ok(());
+        }
+        
+        match self.default_policy {
+            DefaultPolicy::Allow => Ok(()),
+            DefaultPolicy::Deny => denied(format!(
+                "{} is not allowed to send in conversation {}",
+                sender_did, conversation_id
+            )),
+        }
+    }
+    
+    /// Set rules for a conversation
+    pub fn set_conversation_rules(&mut self, conversation_id: impl Into<String>, rules: ConversationAcl) -> &mut Self {
+        self.conversations.insert(conversation_id.into(), rules);
+        self.bump_version();
+        self
+    }
+    
+    /// Set default policy for message senders
+    pub fn set_default_policy(&mut self, policy: DefaultPolicy) -> &mut Self {
+        self.default_policy = policy;
+        self.bump_version();
+        self
+    }
+    
     /// Check if DID is in whitelist
     pub fn is_whitelisted(&self, did: &str) -> bool {
         self.whitelist.iter().any(|e| e.did == did && !e.is_expired())
@@ -502,6 +628,40 @@ mod tests {
         assert_eq!(acl.version, v1 + 2);
     }
 
+    #[test]
+    fn test_check_message_send() {
+        let mut acl = NetworkAcl::new("did:cis:local:abc123");
+        acl.deny("did:cis:enemy:bad999", "did:cis:local:abc123");
+        acl.set_conversation_rules("conv-1", ConversationAcl {
+            allow: vec!["did:cis:friend:def456".into()],
+            deny: vec!["did:cis:muted:aaa111".into()],
+        });
+        
+        // Default policy allow
+        assert!(acl.check_message_send("did:cis:anyone:xyz789", "conv-1").is_ok());
+        assert!(acl.check_message_send("did:cis:muted:aaa111", "conv-1").is_err());
+        assert!(acl.check_message_send("did:cis:muted:aaa111", "conv-2").is_ok());
+        assert!(acl.check_message_send("did:cis:enemy:bad999", "conv-1").is_err());
+        
+        acl.set_default_policy(DefaultPolicy::Deny);
+        assert!(acl.check_message_send("did:cis:anyone:xyz789", "conv-1").is_err());
+        assert!(acl.check_message_send("did:cis:friend:def456", "conv-1").is_ok());
+        assert!(acl.check_message_send("did:cis:friend:def456", "conv-2").is_err());
+    }
+
+    #[test]
+    fn test_conversation_acl_from_metadata() {
+        assert_eq!(ConversationAcl::from_metadata(&serde_json::Value::Null).unwrap(), None);
+        
+        let metadata = serde_json::json!({ "acl": { "allow": ["did:cis:a:01"] } });
+        let rules = ConversationAcl::from_metadata(&metadata).unwrap().unwrap();
+        assert_eq!(rules.allow, vec!["did:cis:a:01".to_string()]);
+        assert!(rules.deny.is_empty());
+        
+        let invalid = serde_json::json!({ "acl": { "allow": "did:cis:a:01" } });
+        assert!(ConversationAcl::from_metadata(&invalid).is_err());
+    }
+
     #[test]
     fn test_acl_entry_expiration() {
         let mut entry = AclEntry::new("did:test", "did:local");
@@ -8,6 +8,9 @@ pub mod acl {
 }
 
 // Re-export 主要类型
-pub use acl::{AclEntry, AclResult, AclSummary, NetworkAcl, NetworkMode};
+pub use acl::{
+    AclEntry, AclResult, AclSummary, ConversationAcl, DefaultPolicy, NetworkAcl, NetworkMode,
+    CONVERSATION_ACL_METADATA_KEY,
+};
 pub use acl::{AclSigner, AclVerifier};
 pub use acl::{AclValidator, AclValidationResult};
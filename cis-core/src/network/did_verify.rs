@@ -20,11 +20,12 @@
 //!     │ ◄───────────────────────  │
 //!     │  {                        │
 //!     │    responder_did,         │
-//!     │    challenge_signature    │
+//!     │    challenge_signature,   │
+//!     │    did_document           │
 //!     │  }                        │
 //!     │                           │
 //!     │  3. Verify                │
-//!     │     - Parse responder_did │
+//!     │     - Verify DID document │
 //!     │     - Extract public key  │
 //!     │     - Verify signature    │
 //!     │     - Check whitelist     │
@@ -36,6 +37,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, info, warn};
 
 use crate::identity::did::DIDManager;
+use crate::identity::{DIDDocument, DIDDocumentStore};
 use crate::network::{AclResult, NetworkAcl, NetworkError};
 
 /// Default challenge timeout in seconds
@@ -114,6 +116,14 @@ pub struct DidResponse {
     /// Signature of the challenge (hex encoded)
     /// Signature = sign(challenge_bytes, responder_private_key)
     pub challenge_signature: String,
+
+    /// Responder's self-signed DID document (carries the full public key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub did_document: Option<DIDDocument>,
+
+    /// Self-signature of the DID document (hex encoded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document_signature: Option<String>,
 }
 
 impl DidResponse {
@@ -127,10 +137,14 @@ impl DidResponse {
             .map_err(|e| NetworkError::VerificationFailed(format!("Failed to serialize challenge: {}", e)))?;
         
         let signature = did_manager.sign_to_hex(&challenge_bytes);
+        let document = did_manager.document();
+        let document_signature = hex::encode(did_manager.sign_document(&document));
         
         Ok(Self {
             responder_did: responder_did.into(),
             challenge_signature: signature,
+            did_document: Some(document),
+            document_signature: Some(document_signature),
         })
     }
     
@@ -139,8 +153,8 @@ impl DidResponse {
         // 1. Verify challenge is not expired
         challenge.verify()?;
         
-        // 2. Parse responder DID to get public key
-        let verifying_key = parse_did_to_public_key(&self.responder_did)?;
+        // 2. Resolve responder public key (attached DID document or local storage)
+        let verifying_key = self.responder_public_key()?;
         
         // 3. Serialize challenge
         let challenge_bytes = serde_json::to_vec(challenge)
@@ -170,6 +184,37 @@ impl DidResponse {
     }
 }
 
+impl DidResponse {
+    /// Public key of the responder
+    ///
+    /// An attached DID document is verified against the DID and cached in
+    /// local storage; otherwise the document is resolved locally.
+    fn responder_public_key(&self) -> Result<VerifyingKey, NetworkError> {
+        let (document, signature) = match (&self.did_document, &self.document_signature) {
+            (Some(document), Some(signature)) => (document, signature),
+            _ => return parse_did_to_public_key(&self.responder_did),
+        };
+
+        let signature = hex::decode(signature)
+            .map_err(|e| NetworkError::VerificationFailed(format!("Invalid document signature hex: {}", e)))?;
+
+        let valid = DIDManager::verify_did_document(&self.responder_did, document, &signature)
+            .map_err(|e| NetworkError::VerificationFailed(e.to_string()))?;
+        if !valid {
+            warn!("DID document verification failed for {}", self.responder_did);
+            return Err(NetworkError::VerificationFailed("Invalid DID document".into()));
+        }
+
+        if let Err(e) = DIDDocumentStore::open_default().put(document) {
+            warn!("Failed to store DID document for {}: {}", self.responder_did, e);
+        }
+
+        document
+            .verifying_key()
+            .map_err(|e| NetworkError::VerificationFailed(e.to_string()))
+    }
+}
+
 /// Verification result
 #[derive(Debug, Clone)]
 pub enum VerificationResult {
@@ -256,63 +301,15 @@ pub struct VerifiedPeer {
     pub verified_at: i64,
 }
 
-/// Parse DID to extract public key
+/// Resolve DID to public key via its DID document
 fn parse_did_to_public_key(did: &str) -> Result<VerifyingKey, NetworkError> {
-    // DID format: did:cis:{node_id}:{pub_key_short}
-    // pub_key_short is first 8 bytes (16 hex chars) of public key
-    // Full public key needs to be obtained from DID document or storage
-    
-    let parts: Vec<&str> = did.split(':').collect();
-    if parts.len() != 4 || parts[0] != "did" || parts[1] != "cis" {
-        return Err(NetworkError::VerificationFailed(
-            format!("Invalid DID format: {}", did)
-        ));
-    }
-    
-    let pub_key_short = parts[3];
-    if pub_key_short.len() != 16 {
-        return Err(NetworkError::VerificationFailed(
-            format!("Invalid public key short in DID: {}", pub_key_short)
-        ));
-    }
-    
-    // Try to get full public key from storage/network
-    // For now, we assume the full key is stored or can be retrieved
-    // In a real implementation, this would query a DID resolver
-    
-    // Placeholder: attempt to decode full key from storage
-    match resolve_did_to_full_key(did) {
-        Some(key_bytes) => {
-            VerifyingKey::from_bytes(&key_bytes)
-                .map_err(|e| NetworkError::VerificationFailed(
-                    format!("Invalid public key: {}", e)
-                ))
-        }
-        None => Err(NetworkError::VerificationFailed(
-            format!("Cannot resolve DID: {}", did)
-        ))
-    }
-}
+    let document = DIDManager::resolve(did).map_err(|e| {
+        NetworkError::VerificationFailed(format!("Cannot resolve DID {}: {}", did, e))
+    })?;
 
-/// Resolve DID to full public key bytes
-/// 
-/// In production, this would:
-/// Resolve DID to full public key
-/// 
-/// 从 DID 格式 `did:cis:{node_id}:{public_key_hex}` 提取完整公钥
-fn resolve_did_to_full_key(did: &str) -> Option<[u8; 32]> {
-    use crate::identity::did::DIDManager;
-    
-    // 解析 DID
-    let (_, public_key_hex) = DIDManager::parse_did(did)?;
-    
-    // 从十六进制解码公钥
-    let bytes = hex::decode(&public_key_hex).ok()?;
-    if bytes.len() != 32 {
-        return None;
-    }
-    
-    bytes.try_into().ok()
+    document
+        .verifying_key()
+        .map_err(|e| NetworkError::VerificationFailed(format!("Invalid public key: {}", e)))
 }
 
 /// Generate cryptographically secure nonce
@@ -408,4 +405,30 @@ mod tests {
         let invalid = "did:other:node:key";
         assert!(parse_did_to_public_key(invalid).is_err());
     }
+
+    #[test]
+    fn test_response_with_did_document() {
+        let responder = DIDManager::generate("responder").unwrap();
+        let challenge = DidChallenge::new("did:cis:challenger:abc123");
+        let response = DidResponse::new(responder.did(), &challenge, &responder).unwrap();
+
+        let key = response.responder_public_key().unwrap();
+        assert_eq!(key, responder.verifying_key());
+    }
+
+    #[test]
+    fn test_response_with_forged_document_rejected() {
+        let responder = DIDManager::generate("responder").unwrap();
+        let impostor = DIDManager::generate("responder").unwrap();
+        let challenge = DidChallenge::new("did:cis:challenger:abc123");
+
+        // Impostor claims the responder's DID with its own document
+        let mut response = DidResponse::new(responder.did(), &challenge, &impostor).unwrap();
+        let mut document = impostor.document();
+        document.id = responder.did().to_string();
+        response.document_signature = Some(hex::encode(impostor.sign_document(&document)));
+        response.did_document = Some(document);
+
+        assert!(response.verify(&challenge).is_err());
+    }
 }
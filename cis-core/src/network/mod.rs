@@ -44,6 +44,7 @@ pub mod websocket;
 pub mod websocket_auth;
 pub mod websocket_integration;
 pub mod clock_tolerance;
+pub mod trust_store;
 
 // 🔒 ACL模块 (访问控制列表)
 pub mod acl_module;
@@ -55,7 +56,10 @@ mod acl_tests;
 
 
 // 🔒 从acl_module重新导出ACL类型
-pub use acl_module::{AclEntry, AclResult, NetworkAcl, NetworkMode};
+pub use acl_module::{
+    AclEntry, AclResult, ConversationAcl, DefaultPolicy, NetworkAcl, NetworkMode,
+    CONVERSATION_ACL_METADATA_KEY,
+};
 
 // 🔒 从acl_service重新导出AclService
 pub use acl_service::{AclService, AclPermission, AclAction, NetworkAclService};
@@ -127,6 +131,7 @@ pub use websocket::{
 pub use audit::{AuditLogger, AuditEntry, AuditEventType, Severity};
 pub use pairing::{PairingManager, PairingService, PairingSession, PairingState, PairingResult, PairingNodeInfo};
 pub use simple_discovery::{SimpleDiscovery, DiscoveredNode};
+pub use trust_store::{TrustEntry, TrustLevel, TrustStore};
 
 use crate::error::{CisError, Result};
 use std::path::PathBuf;
@@ -136,6 +141,11 @@ pub fn default_acl_path() -> PathBuf {
     crate::storage::paths::Paths::config_dir().join("network_acl.toml")
 }
 
+/// Default DID trust store path
+pub fn default_trust_store_path() -> PathBuf {
+    crate::storage::paths::Paths::data_dir().join("network_trust.db")
+}
+
 /// Initialize network module
 pub async fn init() -> Result<()> {
     let acl_path = default_acl_path();
@@ -0,0 +1,296 @@
+//! # DID Trust Store
+//!
+//! 按 DID 记录信任级别（`block` / `read` / `write`）的 SQLite 存储，
+//! 供 `cis network trust` / `cis network import` 使用。
+//!
+//! 与 `network_acl.toml` 中的白名单/黑名单相互独立：白名单决定能否建立连接，
+//! 信任级别决定连接建立后允许的数据访问。
+//!
+//! ## 使用示例
+//!
+//! ```rust,no_run
+//! use cis_core::network::trust_store::{TrustLevel, TrustStore};
+//!
+//! # fn example() -> cis_core::error::Result<()> {
+//! let store = TrustStore::open("network_trust.db")?;
+//! store.set_trust("did:cis:peer:abc123", TrustLevel::Write, None)?;
+//! assert_eq!(store.trust_level("did:cis:peer:abc123")?, Some(TrustLevel::Write));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::Path;
+use std::str::FromStr;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CisError, Result};
+
+/// 除 `did:cis` 外接受的外部 DID 方法
+pub const ACCEPTED_EXTERNAL_DID_METHODS: &[&str] = &["key", "web", "peer"];
+
+/// DID 信任级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrustLevel {
+    /// 拒绝所有访问
+    Block,
+    /// 只读
+    Read,
+    /// 读写
+    Write,
+}
+
+impl TrustLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrustLevel::Block => "block",
+            TrustLevel::Read => "read",
+            TrustLevel::Write => "write",
+        }
+    }
+}
+
+impl std::fmt::Display for TrustLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TrustLevel {
+    type Err = CisError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "block" => Ok(TrustLevel::Block),
+            "read" => Ok(TrustLevel::Read),
+            "write" => Ok(TrustLevel::Write),
+            other => Err(CisError::invalid_input(format!(
+                "Unknown trust level '{}' (expected block, read or write)",
+                other
+            ))),
+        }
+    }
+}
+
+/// 信任条目
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustEntry {
+    pub did: String,
+    pub level: TrustLevel,
+    /// 备注
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// 最后更新时间（Unix 秒）；导入文件中可省略
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+/// 校验 DID 格式
+///
+/// 接受 `did:cis:<id>` 以及 [`ACCEPTED_EXTERNAL_DID_METHODS`] 中的外部方法。
+/// 方法特定标识符只能包含字母、数字和 `.` `-` `_` `%` `:`，且不能以 `:` 结尾。
+pub fn validate_did(did: &str) -> Result<()> {
+    let invalid = |reason: &str| CisError::invalid_input(format!("Invalid DID '{}': {}", did, reason));
+
+    let mut parts = did.splitn(3, ':');
+    let (Some("did"), Some(method), Some(id)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid("expected did:<method>:<id>"));
+    };
+    if method != "cis" && !ACCEPTED_EXTERNAL_DID_METHODS.contains(&method) {
+        return Err(invalid(&format!(
+            "unsupported method '{}' (accepted: cis, {})",
+            method,
+            ACCEPTED_EXTERNAL_DID_METHODS.join(", ")
+        )));
+    }
+    let valid_id = !id.is_empty()
+        && !id.ends_with(':')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '%' | ':'));
+    if !valid_id {
+        return Err(invalid("malformed method-specific identifier"));
+    }
+    Ok(())
+}
+
+/// SQLite 信任存储
+pub struct TrustStore {
+    conn: Connection,
+}
+
+impl TrustStore {
+    /// 打开（必要时创建）数据库
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        if let Some(parent) = path.as_ref().parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Self::init(Connection::open(path)?)
+    }
+
+    /// 内存数据库（测试用）
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS network_trust (
+                did TEXT NOT NULL,
+                level TEXT NOT NULL,
+                note TEXT,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE UNIQUE INDEX IF NOT EXISTS idx_network_trust_did ON network_trust(did);
+            CREATE INDEX IF NOT EXISTS idx_network_trust_level ON network_trust(level);",
+        )?;
+        Ok(Self { conn })
+    }
+
+    /// 新增或更新 DID 的信任级别
+    pub fn set_trust(&self, did: &str, level: TrustLevel, note: Option<&str>) -> Result<()> {
+        validate_did(did)?;
+        Self::upsert(&self.conn, did, level, note)
+    }
+
+    fn upsert(conn: &Connection, did: &str, level: TrustLevel, note: Option<&str>) -> Result<()> {
+        conn.execute(
+            "INSERT INTO network_trust (did, level, note, updated_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(did) DO UPDATE SET
+                level = excluded.level,
+                note = COALESCE(excluded.note, network_trust.note),
+                updated_at = excluded.updated_at",
+            params![did, level.as_str(), note, chrono::Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    /// 查询 DID 的信任级别
+    pub fn trust_level(&self, did: &str) -> Result<Option<TrustLevel>> {
+        let level: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT level FROM network_trust WHERE did = ?1",
+                params![did],
+                |row| row.get(0),
+            )
+            .optional()?;
+        level.map(|l| l.parse()).transpose()
+    }
+
+    /// 全部条目，按 DID 排序
+    pub fn list(&self) -> Result<Vec<TrustEntry>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT did, level, note, updated_at FROM network_trust ORDER BY did")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let (did, level, note, updated_at) = row?;
+            entries.push(TrustEntry {
+                did,
+                level: level.parse()?,
+                note,
+                updated_at,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// 删除条目，返回是否存在
+    pub fn remove(&self, did: &str) -> Result<bool> {
+        let removed = self
+            .conn
+            .execute("DELETE FROM network_trust WHERE did = ?1", params![did])?;
+        Ok(removed > 0)
+    }
+
+    /// 批量导入
+    ///
+    /// 先校验所有 DID，任一无效则整体失败；写入在同一事务中完成。
+    /// 返回导入条数。
+    pub fn import(&mut self, entries: &[TrustEntry]) -> Result<usize> {
+        for (i, entry) in entries.iter().enumerate() {
+            validate_did(&entry.did)
+                .map_err(|e| CisError::invalid_input(format!("Entry {}: {}", i + 1, e)))?;
+        }
+
+        let tx = self.conn.transaction()?;
+        for entry in entries {
+            Self::upsert(&tx, &entry.did, entry.level, entry.note.as_deref())?;
+        }
+        tx.commit()?;
+        Ok(entries.len())
+    }
+
+    /// 从 JSON 文件批量导入（`[{"did": ..., "level": ..., "note": ...}]`）
+    pub fn import_file<P: AsRef<Path>>(&mut self, path: P) -> Result<usize> {
+        let content = std::fs::read_to_string(path)?;
+        let entries: Vec<TrustEntry> = serde_json::from_str(&content)
+            .map_err(|e| CisError::invalid_input(format!("Invalid ACL file: {}", e)))?;
+        self.import(&entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_did() {
+        for did in ["did:cis:peer:abc123", "did:cis:node1", "did:key:z6MkhaXg", "did:web:example.com"] {
+            assert!(validate_did(did).is_ok(), "{}", did);
+        }
+        for did in ["cis:peer:abc", "did:cis:", "did:ethr:0xabc", "did:cis:a b", "did:cis:peer:", "did"] {
+            assert!(validate_did(did).is_err(), "{}", did);
+        }
+    }
+
+    #[test]
+    fn test_set_and_list_trust() {
+        let store = TrustStore::open_in_memory().unwrap();
+        store.set_trust("did:cis:peer:b", TrustLevel::Read, Some("laptop")).unwrap();
+        store.set_trust("did:cis:peer:a", TrustLevel::Block, None).unwrap();
+        // 更新级别时保留原备注
+        store.set_trust("did:cis:peer:b", TrustLevel::Write, None).unwrap();
+        assert!(store.set_trust("not-a-did", TrustLevel::Read, None).is_err());
+
+        let entries = store.list().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].did, "did:cis:peer:a");
+        assert_eq!(entries[1].level, TrustLevel::Write);
+        assert_eq!(entries[1].note.as_deref(), Some("laptop"));
+
+        assert!(store.remove("did:cis:peer:a").unwrap());
+        assert_eq!(store.trust_level("did:cis:peer:a").unwrap(), None);
+    }
+
+    #[test]
+    fn test_import() {
+        let mut store = TrustStore::open_in_memory().unwrap();
+        let entries: Vec<TrustEntry> = serde_json::from_str(
+            r#"[{"did": "did:cis:peer:a", "level": "write"}, {"did": "did:key:z6Mk", "level": "block", "note": "lost"}]"#,
+        )
+        .unwrap();
+        assert_eq!(store.import(&entries).unwrap(), 2);
+        assert_eq!(store.trust_level("did:key:z6Mk").unwrap(), Some(TrustLevel::Block));
+
+        // 任一 DID 无效时不写入任何条目
+        let bad = vec![
+            TrustEntry { did: "did:cis:peer:c".into(), level: TrustLevel::Read, note: None, updated_at: 0 },
+            TrustEntry { did: "bogus".into(), level: TrustLevel::Read, note: None, updated_at: 0 },
+        ];
+        assert!(store.import(&bad).is_err());
+        assert_eq!(store.trust_level("did:cis:peer:c").unwrap(), None);
+    }
+}
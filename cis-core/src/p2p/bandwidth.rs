@@ -0,0 +1,211 @@
+//! P2P 带宽限制
+//!
+//! 令牌桶限速器，作用于节点的全部出站流量：
+//!
+//! - 每次发送前按字节数取令牌，不足时等待补充
+//! - 桶容量为一秒的额度，允许短暂突发
+//! - 统计最近一分钟的收发字节数与累计限流等待时间
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 统计窗口（秒）
+const STATS_WINDOW_SECS: u64 = 60;
+
+/// 带宽统计
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BandwidthStats {
+    /// 最近一分钟发送字节数
+    pub bytes_sent_last_minute: u64,
+    /// 最近一分钟接收字节数
+    pub bytes_received_last_minute: u64,
+    /// 累计限流等待时间（毫秒）
+    pub throttled_ms: u64,
+    /// 当前限速（字节/秒，0 表示不限速）
+    pub max_bytes_per_second: u64,
+}
+
+/// 令牌桶状态
+#[derive(Debug)]
+struct Bucket {
+    /// 可用令牌，可为负（欠额由后续等待偿还）
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// 每秒的收发计数
+#[derive(Debug)]
+struct Sample {
+    second: u64,
+    sent: u64,
+    received: u64,
+}
+
+/// 带宽限制器
+#[derive(Debug)]
+pub struct BandwidthLimiter {
+    /// 限速（字节/秒，0 表示不限速）
+    max_bytes_per_second: AtomicU64,
+    bucket: Mutex<Bucket>,
+    samples: Mutex<VecDeque<Sample>>,
+    throttled_ms: AtomicU64,
+    created_at: Instant,
+}
+
+impl BandwidthLimiter {
+    /// 创建限制器，`max_bytes_per_second` 为 0 时不限速
+    pub fn new(max_bytes_per_second: u64) -> Self {
+        let now = Instant::now();
+        Self {
+            max_bytes_per_second: AtomicU64::new(max_bytes_per_second),
+            bucket: Mutex::new(Bucket {
+                tokens: max_bytes_per_second as f64,
+                last_refill: now,
+            }),
+            samples: Mutex::new(VecDeque::new()),
+            throttled_ms: AtomicU64::new(0),
+            created_at: now,
+        }
+    }
+
+    /// 当前限速（字节/秒）
+    pub fn limit(&self) -> u64 {
+        self.max_bytes_per_second.load(Ordering::Relaxed)
+    }
+
+    /// 调整限速，令牌桶重置为满
+    pub fn set_limit(&self, max_bytes_per_second: u64) {
+        self.max_bytes_per_second
+            .store(max_bytes_per_second, Ordering::Relaxed);
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.tokens = max_bytes_per_second as f64;
+        bucket.last_refill = Instant::now();
+    }
+
+    /// 发送 `bytes` 字节前调用，令牌不足时等待
+    pub async fn acquire(&self, bytes: usize) {
+        let wait = self.reserve(bytes as u64);
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+            self.throttled_ms
+                .fetch_add(wait.as_millis() as u64, Ordering::Relaxed);
+        }
+        self.record(bytes as u64, 0);
+    }
+
+    /// 记录接收的字节数（接收方向不限速）
+    pub fn record_received(&self, bytes: usize) {
+        self.record(0, bytes as u64);
+    }
+
+    /// 当前统计
+    pub fn stats(&self) -> BandwidthStats {
+        let now = self.current_second();
+        let samples = self.samples.lock().unwrap();
+        let (sent, received) = samples
+            .iter()
+            .filter(|s| s.second + STATS_WINDOW_SECS > now)
+            .fold((0, 0), |(sent, received), s| {
+                (sent + s.sent, received + s.received)
+            });
+
+        BandwidthStats {
+            bytes_sent_last_minute: sent,
+            bytes_received_last_minute: received,
+            throttled_ms: self.throttled_ms.load(Ordering::Relaxed),
+            max_bytes_per_second: self.limit(),
+        }
+    }
+
+    /// 扣除令牌并返回需要等待的时间
+    fn reserve(&self, bytes: u64) -> Duration {
+        let rate = self.limit();
+        if rate == 0 {
+            return Duration::ZERO;
+        }
+
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        let refill = now.duration_since(bucket.last_refill).as_secs_f64() * rate as f64;
+        bucket.tokens = (bucket.tokens + refill).min(rate as f64) - bytes as f64;
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / rate as f64)
+        }
+    }
+
+    fn record(&self, sent: u64, received: u64) {
+        let now = self.current_second();
+        let mut samples = self.samples.lock().unwrap();
+        match samples.back_mut() {
+            Some(last) if last.second == now => {
+                last.sent += sent;
+                last.received += received;
+            }
+            _ => samples.push_back(Sample {
+                second: now,
+                sent,
+                received,
+            }),
+        }
+        while samples
+            .front()
+            .is_some_and(|s| s.second + STATS_WINDOW_SECS <= now)
+        {
+            samples.pop_front();
+        }
+    }
+
+    fn current_second(&self) -> u64 {
+        self.created_at.elapsed().as_secs()
+    }
+}
+
+impl Default for BandwidthLimiter {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unlimited_does_not_wait() {
+        let limiter = BandwidthLimiter::default();
+        let start = Instant::now();
+        limiter.acquire(10_000_000).await;
+        limiter.record_received(42);
+
+        assert!(start.elapsed() < Duration::from_millis(50));
+        let stats = limiter.stats();
+        assert_eq!(stats.bytes_sent_last_minute, 10_000_000);
+        assert_eq!(stats.bytes_received_last_minute, 42);
+        assert_eq!(stats.throttled_ms, 0);
+    }
+
+    #[tokio::test]
+    async fn test_throttles_over_limit() {
+        let limiter = BandwidthLimiter::new(1000);
+        let start = Instant::now();
+
+        // 第一秒的额度可以直接用完
+        limiter.acquire(1000).await;
+        assert!(start.elapsed() < Duration::from_millis(100));
+
+        // 超出部分需要等待约 500ms
+        limiter.acquire(500).await;
+        assert!(start.elapsed() >= Duration::from_millis(400));
+
+        let stats = limiter.stats();
+        assert_eq!(stats.bytes_sent_last_minute, 1500);
+        assert!(stats.throttled_ms >= 400);
+        assert_eq!(stats.max_bytes_per_second, 1000);
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! 提供节点发现、连接管理和数据同步功能。
 
+pub mod bandwidth;
 pub mod connection_manager;
 pub mod crdt;
 pub mod discovery;
@@ -16,6 +17,7 @@ pub mod nat;
 pub mod mdns_service;
 pub mod network;
 pub mod offline_queue;  // P1-9: 离线队列
+pub mod reputation;
 
 #[cfg(test)]
 mod connection_manager_tests;
@@ -23,8 +25,10 @@ mod connection_manager_tests;
 #[cfg(test)]
 mod transport_secure_tests;
 
+pub use bandwidth::{BandwidthLimiter, BandwidthStats};
 pub use connection_manager::{ConnectionManager, ConnectionHandle, ConnectionState};
 pub use peer::Message;
+pub use reputation::{PeerReputation, ReputationConfig, ReputationEvent, ReputationScorer};
 pub use offline_queue::{OfflineQueue, OfflineQueueConfig, QueuedMessage, QueueStats};  // P1-9
 
 pub mod crypto {
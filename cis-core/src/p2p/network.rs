@@ -19,10 +19,13 @@ use std::sync::Arc;
 
 use crate::error::{CisError, Result};
 use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
 use tokio::sync::{OnceCell, RwLock};
 use tracing::{debug, error, info, warn};
 
 use crate::p2p::{
+    bandwidth::{BandwidthLimiter, BandwidthStats},
+    reputation::{PeerStatus, ReputationConfig, ReputationEvent, ReputationScorer},
     crypto::keys::NodeKeyPair,
     kademlia::{KademliaDht, KademliaConfig, NodeId as KademliaNodeId, NodeInfo, 
                transport::{DhtTransport, P2PNetworkTransport}},
@@ -75,6 +78,10 @@ pub struct P2PConfig {
     pub transport_config: SecureTransportConfig,
     /// 节点密钥对（用于加密和身份验证）
     pub node_keys: Option<Arc<NodeKeyPair>>,
+    /// 出站带宽上限（字节/秒，0 表示不限速）
+    pub max_bytes_per_second: u64,
+    /// 节点信誉评分配置
+    pub reputation: ReputationConfig,
 }
 
 impl Default for P2PConfig {
@@ -92,6 +99,8 @@ impl Default for P2PConfig {
             external_address: None,
             transport_config: SecureTransportConfig::default(),
             node_keys: None,
+            max_bytes_per_second: 0,
+            reputation: ReputationConfig::default(),
         }
     }
 }
@@ -134,6 +143,8 @@ pub struct PeerInfo {
     pub last_seen: std::time::SystemTime,
     /// 最后同步时间
     pub last_sync_at: Option<DateTime<Utc>>,
+    /// 往返时延（毫秒，仅已连接节点）
+    pub rtt_ms: Option<u64>,
 }
 
 /// P2P 网络管理器
@@ -152,6 +163,8 @@ pub struct P2PNetwork {
     node_keys: Arc<NodeKeyPair>,
     /// Kademlia DHT（如果启用）
     dht: Option<Arc<KademliaDht<P2PNetworkTransport>>>,
+    /// 节点信誉评分
+    reputation: Arc<ReputationScorer>,
 }
 
 impl P2PNetwork {
@@ -181,6 +194,9 @@ impl P2PNetwork {
             )
             .await?,
         );
+        transport
+            .bandwidth_limiter()
+            .set_limit(config.max_bytes_per_second);
         
         let mdns = if config.enable_mdns {
             match MdnsService::new(
@@ -229,11 +245,14 @@ impl P2PNetwork {
                 external_address: config.external_address,
                 transport_config: config.transport_config,
                 node_keys: Some(Arc::clone(&node_keys)),
+                max_bytes_per_second: config.max_bytes_per_second,
+                reputation: config.reputation.clone(),
             },
             mdns,
             transport,
             discovered_peers: Arc::new(RwLock::new(HashMap::new())),
             started_at: std::time::Instant::now(),
+            reputation: Arc::new(ReputationScorer::new(config.reputation.clone())),
             node_keys,
             dht,
         })
@@ -390,6 +409,9 @@ impl P2PNetwork {
         )
         .await
         .map_err(|e| CisError::p2p(format!("Failed to bind secure transport: {}", e)))?;
+        transport
+            .bandwidth_limiter()
+            .set_limit(config.max_bytes_per_second);
 
         // 创建 mDNS 服务（如果启用）
         let mdns = if config.enable_mdns {
@@ -432,6 +454,7 @@ impl P2PNetwork {
             transport: transport_arc,
             discovered_peers: Arc::new(RwLock::new(HashMap::new())),
             started_at: std::time::Instant::now(),
+            reputation: Arc::new(ReputationScorer::new(config.reputation.clone())),
             node_keys,
             dht,
         });
@@ -546,12 +569,81 @@ impl P2PNetwork {
         // 从地址推断 node_id
         let node_id = format!("peer-{}", socket_addr.port());
 
-        self.transport.connect(&node_id, socket_addr).await?;
+        if let Err(e) = self.transport.connect(&node_id, socket_addr).await {
+            self.report_connect_failure(&node_id, &e).await;
+            return Err(e);
+        }
 
         info!("Connected to {} at {}", node_id, addr);
         Ok(())
     }
 
+    /// 握手阶段的身份验证失败计入对端信誉
+    async fn report_connect_failure(&self, node_id: &str, err: &CisError) {
+        if is_auth_failure(err) {
+            self.report_peer(node_id, ReputationEvent::AuthFailure).await;
+        }
+    }
+
+    /// 解码来自指定节点的消息
+    ///
+    /// 已封禁节点的消息直接拒绝；解码失败记为无效消息，成功记为有效消息。
+    pub async fn decode_from<T: DeserializeOwned>(&self, node_id: &str, data: &[u8]) -> Result<T> {
+        if self.reputation.is_banned(node_id) {
+            return Err(CisError::p2p(format!("Peer {} is banned", node_id)));
+        }
+        match serde_json::from_slice(data) {
+            Ok(message) => {
+                self.report_peer(node_id, ReputationEvent::ValidMessage).await;
+                Ok(message)
+            }
+            Err(e) => {
+                self.report_peer(node_id, ReputationEvent::InvalidMessage).await;
+                Err(CisError::p2p(format!("Invalid message from {}: {}", node_id, e)))
+            }
+        }
+    }
+
+    /// 从指定节点接收并解码一条消息
+    pub async fn receive_from<T: DeserializeOwned>(&self, node_id: &str) -> Result<T> {
+        let data = self.transport.receive(node_id).await?;
+        self.decode_from(node_id, &data).await
+    }
+
+    /// 校验消息声明的来源与实际发送方一致，不一致记为协议违规
+    pub async fn verify_sender(&self, from: &str, claimed: &str) -> Result<()> {
+        if from != claimed {
+            self.report_peer(from, ReputationEvent::ProtocolViolation).await;
+            return Err(CisError::p2p(format!(
+                "Peer {} sent a message claiming to be {}",
+                from, claimed
+            )));
+        }
+        Ok(())
+    }
+
+    /// 记录节点行为并更新信誉，进入封禁状态时断开连接
+    pub async fn report_peer(&self, node_id: &str, event: ReputationEvent) -> PeerStatus {
+        let was_banned = self.reputation.is_banned(node_id);
+        let status = self.reputation.record(node_id, event);
+        if status == PeerStatus::Banned && !was_banned {
+            warn!(
+                "Peer {} banned (reputation {})",
+                node_id,
+                self.reputation.score(node_id)
+            );
+            if let Err(e) = self.transport.disconnect(node_id).await {
+                debug!("Failed to disconnect banned peer {}: {}", node_id, e);
+            }
+        }
+        status
+    }
+
+    /// 节点信誉评分器
+    pub fn reputation(&self) -> &Arc<ReputationScorer> {
+        &self.reputation
+    }
+
     /// 断开与节点的连接
     pub async fn disconnect(&self, node_id: &str) -> Result<()> {
         self.transport.disconnect(node_id).await?;
@@ -575,6 +667,7 @@ impl P2PNetwork {
                 connected: connected_ids.contains(&node.node_id),
                 last_seen: std::time::SystemTime::now(),
                 last_sync_at: None,
+                rtt_ms: None,
             })
             .collect()
     }
@@ -582,6 +675,7 @@ impl P2PNetwork {
     /// 获取已连接的节点列表
     pub async fn connected_peers(&self) -> Vec<PeerInfo> {
         let connections = self.transport.list_connections().await;
+        let rtts = self.transport.connection_rtts().await;
         let discovered = self.discovered_peers.read().await;
 
         connections
@@ -597,6 +691,7 @@ impl P2PNetwork {
                     connected: true,
                     last_seen: std::time::SystemTime::now(),
                     last_sync_at: None,
+                    rtt_ms: rtts.get(&conn.node_id).copied(),
                 }
             })
             .collect()
@@ -604,6 +699,9 @@ impl P2PNetwork {
 
     /// 发送消息到指定节点
     pub async fn send_to(&self, node_id: &str, data: &[u8]) -> Result<()> {
+        if self.reputation.is_banned(node_id) {
+            return Err(CisError::p2p(format!("Peer {} is banned", node_id)));
+        }
         self.transport.send(node_id, data).await
     }
 
@@ -639,14 +737,48 @@ impl P2PNetwork {
                 connected: true,
                 last_seen: std::time::SystemTime::now(),
                 last_sync_at: None,
+                rtt_ms: None,
             }
         })
     }
 
+    /// 获取全部已知节点（已连接节点带往返时延）
+    pub async fn get_peers(&self) -> Vec<PeerInfo> {
+        let mut peers: HashMap<String, PeerInfo> = self
+            .discovered_peers()
+            .await
+            .into_iter()
+            .map(|p| (p.node_id.clone(), p))
+            .collect();
+        for peer in self.connected_peers().await {
+            let entry = peers.entry(peer.node_id.clone()).or_insert_with(|| peer.clone());
+            entry.connected = true;
+            entry.rtt_ms = peer.rtt_ms;
+        }
+
+        let mut peers: Vec<PeerInfo> = peers.into_values().collect();
+        peers.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+        peers
+    }
+
+    /// 获取连接边 `(本节点 ID, 对端节点 ID)`
+    ///
+    /// 只包含本节点的直连，远端节点之间的连接不可见。
+    pub async fn get_connections(&self) -> Vec<(String, String)> {
+        self.transport
+            .list_connections()
+            .await
+            .into_iter()
+            .map(|conn| (self.config.node_id.clone(), conn.node_id))
+            .collect()
+    }
+
     /// 订阅主题（简化实现）
+    ///
+    /// 回调参数为 `(发送方节点 ID, 消息数据)`。
     pub async fn subscribe<F>(&self, _topic: &str, _callback: F) -> Result<()>
     where
-        F: Fn(Vec<u8>) + Send + Sync + 'static,
+        F: Fn(String, Vec<u8>) + Send + Sync + 'static,
     {
         // 主题订阅尚未完全实现
         Err(CisError::p2p("Topic subscription not fully implemented".to_string()))
@@ -710,6 +842,16 @@ impl P2PNetwork {
         self.transport.local_addr().to_string()
     }
 
+    /// 全局带宽限制器（作用于所有连接的出站流量）
+    pub fn bandwidth_limiter(&self) -> &Arc<BandwidthLimiter> {
+        self.transport.bandwidth_limiter()
+    }
+
+    /// 最近一分钟的带宽统计
+    pub fn bandwidth_stats(&self) -> BandwidthStats {
+        self.transport.bandwidth_limiter().stats()
+    }
+
     /// 获取节点 ID
     pub fn node_id(&self) -> &str {
         &self.config.node_id
@@ -938,10 +1080,64 @@ impl P2PNetwork {
     }
 }
 
+/// 握手失败是否源于对端身份验证（签名或公钥无效）
+fn is_auth_failure(err: &CisError) -> bool {
+    let msg = err.to_string();
+    msg.contains("Auth signature verification failed") || msg.contains("Invalid remote public key")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    async fn test_network() -> P2PNetwork {
+        let config = P2PConfig {
+            enable_mdns: false,
+            ..P2PConfig::default()
+        };
+        P2PNetwork::new(
+            "local".to_string(),
+            "did:cis:local".to_string(),
+            "127.0.0.1:0",
+            config,
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_misbehaving_peer_is_banned() {
+        let network = test_network().await;
+        let peer = "peer-9000";
+
+        // 解码路径：无效消息
+        assert!(network.decode_from::<serde_json::Value>(peer, b"not json").await.is_err());
+        assert_eq!(network.reputation().score(peer), 95);
+
+        // 同步路径：伪造来源
+        assert!(network.verify_sender(peer, "someone-else").await.is_err());
+        assert_eq!(network.reputation().score(peer), 75);
+
+        // 认证路径：握手签名校验失败
+        let err = CisError::crypto("Auth signature verification failed: bad signature");
+        network.report_connect_failure(peer, &err).await;
+        assert_eq!(network.reputation().status(peer), PeerStatus::Suspected);
+
+        // 非认证类连接错误不影响信誉
+        network
+            .report_connect_failure(peer, &CisError::p2p("Connection timeout"))
+            .await;
+        assert_eq!(network.reputation().score(peer), 25);
+
+        while !network.reputation().is_banned(peer) {
+            let _ = network.decode_from::<serde_json::Value>(peer, b"{").await;
+        }
+
+        assert!(network.send_to(peer, b"hello").await.is_err());
+        assert!(network.decode_from::<serde_json::Value>(peer, b"{}").await.is_err());
+        assert!(network.decode_from::<serde_json::Value>("peer-9001", b"{}").await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_p2p_config_default() {
         let config = P2PConfig::default();
@@ -0,0 +1,277 @@
+//! P2P 节点信誉评分
+//!
+//! 每个节点初始 100 分：
+//!
+//! - 无效消息格式、协议违规、认证失败按配置扣分
+//! - 成功同步、有效消息每次加 1 分，最高 100 分
+//! - 低于 30 分标记为 [`PeerStatus::Suspected`]
+//! - 低于 10 分封禁一段时间，到期后以 10 分（可疑状态）解封
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 满分
+pub const MAX_REPUTATION: i32 = 100;
+
+/// 信誉评分配置
+#[derive(Debug, Clone)]
+pub struct ReputationConfig {
+    /// 无效消息格式扣分
+    pub invalid_message_penalty: i32,
+    /// 协议违规扣分
+    pub protocol_violation_penalty: i32,
+    /// 认证失败扣分
+    pub auth_failure_penalty: i32,
+    /// 良好行为加分
+    pub good_behavior_reward: i32,
+    /// 低于该分数标记为可疑
+    pub suspect_threshold: i32,
+    /// 低于该分数封禁
+    pub ban_threshold: i32,
+    /// 封禁时长
+    pub ban_duration: Duration,
+}
+
+impl Default for ReputationConfig {
+    fn default() -> Self {
+        Self {
+            invalid_message_penalty: 5,
+            protocol_violation_penalty: 20,
+            auth_failure_penalty: 50,
+            good_behavior_reward: 1,
+            suspect_threshold: 30,
+            ban_threshold: 10,
+            ban_duration: Duration::from_secs(3600),
+        }
+    }
+}
+
+/// 影响信誉的节点行为
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReputationEvent {
+    /// 无效消息格式
+    InvalidMessage,
+    /// 协议违规
+    ProtocolViolation,
+    /// 认证失败
+    AuthFailure,
+    /// 同步成功
+    SyncSucceeded,
+    /// 有效消息
+    ValidMessage,
+}
+
+/// 节点信誉状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerStatus {
+    /// 正常
+    Normal,
+    /// 可疑（降级）
+    Suspected,
+    /// 已封禁
+    Banned,
+}
+
+impl PeerStatus {
+    /// 状态名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PeerStatus::Normal => "normal",
+            PeerStatus::Suspected => "suspected",
+            PeerStatus::Banned => "banned",
+        }
+    }
+}
+
+/// 节点信誉快照
+#[derive(Debug, Clone)]
+pub struct PeerReputation {
+    /// 节点 ID
+    pub node_id: String,
+    /// 当前分数
+    pub score: i32,
+    /// 当前状态
+    pub status: PeerStatus,
+    /// 剩余封禁时间
+    pub ban_remaining: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    score: i32,
+    banned_until: Option<Instant>,
+}
+
+/// 信誉评分器
+#[derive(Debug, Default)]
+pub struct ReputationScorer {
+    config: ReputationConfig,
+    peers: Mutex<HashMap<String, Entry>>,
+}
+
+impl ReputationScorer {
+    /// 创建评分器
+    pub fn new(config: ReputationConfig) -> Self {
+        Self {
+            config,
+            peers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 记录节点行为，返回更新后的状态
+    pub fn record(&self, node_id: &str, event: ReputationEvent) -> PeerStatus {
+        let now = Instant::now();
+        let mut peers = self.peers.lock().unwrap();
+        let entry = peers.entry(node_id.to_string()).or_insert(Entry {
+            score: MAX_REPUTATION,
+            banned_until: None,
+        });
+        self.expire_ban(entry, now);
+
+        // 封禁期间不再计分
+        if entry.banned_until.is_some() {
+            return PeerStatus::Banned;
+        }
+
+        let delta = match event {
+            ReputationEvent::InvalidMessage => -self.config.invalid_message_penalty,
+            ReputationEvent::ProtocolViolation => -self.config.protocol_violation_penalty,
+            ReputationEvent::AuthFailure => -self.config.auth_failure_penalty,
+            ReputationEvent::SyncSucceeded | ReputationEvent::ValidMessage => {
+                self.config.good_behavior_reward
+            }
+        };
+        entry.score = (entry.score + delta).clamp(0, MAX_REPUTATION);
+
+        if entry.score < self.config.ban_threshold {
+            entry.banned_until = Some(now + self.config.ban_duration);
+        }
+        self.status_of(entry)
+    }
+
+    /// 节点当前分数（未记录过的节点为满分）
+    pub fn score(&self, node_id: &str) -> i32 {
+        self.snapshot_peer(node_id).score
+    }
+
+    /// 节点当前状态
+    pub fn status(&self, node_id: &str) -> PeerStatus {
+        self.snapshot_peer(node_id).status
+    }
+
+    /// 节点是否处于封禁期
+    pub fn is_banned(&self, node_id: &str) -> bool {
+        self.status(node_id) == PeerStatus::Banned
+    }
+
+    /// 所有已记录节点的信誉，按分数升序
+    pub fn snapshot(&self) -> Vec<PeerReputation> {
+        let now = Instant::now();
+        let mut peers = self.peers.lock().unwrap();
+        let mut result: Vec<_> = peers
+            .iter_mut()
+            .map(|(node_id, entry)| {
+                self.expire_ban(entry, now);
+                self.reputation_of(node_id, entry, now)
+            })
+            .collect();
+        result.sort_by(|a, b| a.score.cmp(&b.score).then_with(|| a.node_id.cmp(&b.node_id)));
+        result
+    }
+
+    /// 单个节点的信誉（未记录过的节点为满分）
+    pub fn snapshot_peer(&self, node_id: &str) -> PeerReputation {
+        let now = Instant::now();
+        let mut peers = self.peers.lock().unwrap();
+        match peers.get_mut(node_id) {
+            Some(entry) => {
+                self.expire_ban(entry, now);
+                self.reputation_of(node_id, entry, now)
+            }
+            None => PeerReputation {
+                node_id: node_id.to_string(),
+                score: MAX_REPUTATION,
+                status: PeerStatus::Normal,
+                ban_remaining: None,
+            },
+        }
+    }
+
+    /// 封禁到期后以封禁阈值的分数解封
+    fn expire_ban(&self, entry: &mut Entry, now: Instant) {
+        if entry.banned_until.is_some_and(|until| until <= now) {
+            entry.banned_until = None;
+            entry.score = entry.score.max(self.config.ban_threshold);
+        }
+    }
+
+    fn status_of(&self, entry: &Entry) -> PeerStatus {
+        if entry.banned_until.is_some() {
+            PeerStatus::Banned
+        } else if entry.score < self.config.suspect_threshold {
+            PeerStatus::Suspected
+        } else {
+            PeerStatus::Normal
+        }
+    }
+
+    fn reputation_of(&self, node_id: &str, entry: &Entry, now: Instant) -> PeerReputation {
+        PeerReputation {
+            node_id: node_id.to_string(),
+            score: entry.score,
+            status: self.status_of(entry),
+            ban_remaining: entry.banned_until.map(|until| until.saturating_duration_since(now)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_penalties_and_thresholds() {
+        let scorer = ReputationScorer::default();
+        assert_eq!(scorer.score("a"), 100);
+        assert_eq!(scorer.status("a"), PeerStatus::Normal);
+
+        // 100 - 50 - 20 = 30，尚未低于可疑阈值
+        scorer.record("a", ReputationEvent::AuthFailure);
+        assert_eq!(scorer.record("a", ReputationEvent::ProtocolViolation), PeerStatus::Normal);
+        assert_eq!(scorer.record("a", ReputationEvent::InvalidMessage), PeerStatus::Suspected);
+        assert_eq!(scorer.score("a"), 25);
+
+        // 良好行为加分，最高 100
+        scorer.record("b", ReputationEvent::InvalidMessage);
+        for _ in 0..10 {
+            scorer.record("b", ReputationEvent::ValidMessage);
+        }
+        assert_eq!(scorer.score("b"), 100);
+
+        // 低于 10 分封禁，封禁期间不再计分
+        assert_eq!(scorer.record("a", ReputationEvent::ProtocolViolation), PeerStatus::Banned);
+        assert!(scorer.is_banned("a"));
+        scorer.record("a", ReputationEvent::SyncSucceeded);
+        assert_eq!(scorer.score("a"), 5);
+
+        let snapshot = scorer.snapshot();
+        assert_eq!(snapshot[0].node_id, "a");
+        assert!(snapshot[0].ban_remaining.is_some());
+    }
+
+    #[test]
+    fn test_ban_expires() {
+        let scorer = ReputationScorer::new(ReputationConfig {
+            ban_duration: Duration::ZERO,
+            ..Default::default()
+        });
+
+        scorer.record("a", ReputationEvent::AuthFailure);
+        assert_eq!(scorer.record("a", ReputationEvent::AuthFailure), PeerStatus::Banned);
+
+        // 解封后处于可疑状态
+        assert_eq!(scorer.status("a"), PeerStatus::Suspected);
+        assert_eq!(scorer.score("a"), 10);
+    }
+}
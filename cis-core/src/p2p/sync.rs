@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use crate::memory::MemoryService;
 use crate::vector::VectorStorage;
 use crate::p2p::P2PNetwork;
+use crate::p2p::reputation::ReputationEvent;
 use crate::p2p::crdt::VectorClock;
 use crate::error::{CisError, Result};
 use crate::types::{MemoryDomain, MemoryCategory};
@@ -88,10 +89,10 @@ impl MemorySyncManager {
         let sync_manager = Arc::new(self.clone_as_handle());
         
         // 订阅同步主题
-        self.p2p.subscribe("memory_sync", move |data| {
+        self.p2p.subscribe("memory_sync", move |from, data| {
             let sync_mgr = Arc::clone(&sync_manager);
             tokio::spawn(async move {
-                if let Err(e) = sync_mgr.handle_sync_message(data).await {
+                if let Err(e) = sync_mgr.handle_sync_message(&from, data).await {
                     tracing::error!("Sync message handling failed: {}", e);
                 }
             });
@@ -105,15 +106,20 @@ impl MemorySyncManager {
     }
 
     /// 处理同步消息
-    async fn handle_sync_message(&self, data: Vec<u8>) -> Result<()> {
-        let message: SyncMessage = serde_json::from_slice(&data)?;
+    ///
+    /// `from` 为传输层确认的发送方，解码失败和来源伪造都会计入其信誉。
+    async fn handle_sync_message(&self, from: &str, data: Vec<u8>) -> Result<()> {
+        let message: SyncMessage = self.p2p.decode_from(from, &data).await?;
 
         match message {
             SyncMessage::Request(req) => {
+                self.p2p.verify_sender(from, &req.node_id).await?;
                 self.handle_sync_request(req).await?;
             }
             SyncMessage::Response(resp) => {
+                self.p2p.verify_sender(from, &resp.node_id).await?;
                 self.handle_sync_response(resp).await?;
+                self.p2p.report_peer(from, ReputationEvent::SyncSucceeded).await;
             }
             SyncMessage::Broadcast(entry) => {
                 self.handle_broadcast(entry).await?;
@@ -411,15 +417,20 @@ struct MemorySyncHandle {
 
 impl MemorySyncHandle {
     /// 处理同步消息
-    async fn handle_sync_message(&self, data: Vec<u8>) -> Result<()> {
-        let message: SyncMessage = serde_json::from_slice(&data)?;
+    ///
+    /// `from` 为传输层确认的发送方，解码失败和来源伪造都会计入其信誉。
+    async fn handle_sync_message(&self, from: &str, data: Vec<u8>) -> Result<()> {
+        let message: SyncMessage = self.p2p.decode_from(from, &data).await?;
 
         match message {
             SyncMessage::Request(req) => {
+                self.p2p.verify_sender(from, &req.node_id).await?;
                 self.handle_sync_request(req).await?;
             }
             SyncMessage::Response(resp) => {
+                self.p2p.verify_sender(from, &resp.node_id).await?;
                 self.handle_sync_response(resp).await?;
+                self.p2p.report_peer(from, ReputationEvent::SyncSucceeded).await;
             }
             SyncMessage::Broadcast(entry) => {
                 self.handle_broadcast(entry).await?;
@@ -19,6 +19,7 @@ use tokio::time::timeout;
 use tracing::{debug, error, info, trace, warn};
 
 use crate::error::{CisError, Result};
+use crate::p2p::bandwidth::BandwidthLimiter;
 use crate::p2p::crypto::keys::NodeKeyPair;
 use crate::p2p::crypto::noise::{NoiseHandshake, NoiseTransport};
 
@@ -106,6 +107,8 @@ pub struct SecureP2PTransport {
     config: SecureTransportConfig,
     /// 关闭信号
     shutdown_tx: Option<tokio::sync::mpsc::Sender<()>>,
+    /// 出站带宽限制（所有连接共享）
+    bandwidth: Arc<BandwidthLimiter>,
 }
 
 /// 连接句柄
@@ -216,6 +219,7 @@ impl SecureP2PTransport {
             connections: Arc::new(RwLock::new(HashMap::new())),
             config,
             shutdown_tx: Some(shutdown_tx),
+            bandwidth: Arc::new(BandwidthLimiter::default()),
         })
     }
 
@@ -866,6 +870,9 @@ impl SecureP2PTransport {
 
     /// 发送数据到指定节点（加密）
     pub async fn send(&self, node_id: &str, data: &[u8]) -> Result<()> {
+        // 限流等待期间不持有连接表锁
+        self.bandwidth.acquire(data.len()).await;
+
         let mut connections = self.connections.write().await;
         let handle = connections
             .get_mut(node_id)
@@ -887,6 +894,7 @@ impl SecureP2PTransport {
 
         let data = handle.connection.receive().await?;
         handle.info.bytes_received += data.len() as u64;
+        self.bandwidth.record_received(data.len());
         handle.last_active = std::time::Instant::now();
 
         Ok(data)
@@ -908,6 +916,22 @@ impl SecureP2PTransport {
         connections.values().map(|h| h.info.clone()).collect()
     }
 
+    /// 出站带宽限制器
+    pub fn bandwidth_limiter(&self) -> &Arc<BandwidthLimiter> {
+        &self.bandwidth
+    }
+
+    /// 各连接当前的往返时延（毫秒），按节点 ID 索引
+    pub async fn connection_rtts(&self) -> HashMap<String, u64> {
+        let connections = self.connections.read().await;
+        connections
+            .iter()
+            .map(|(node_id, h)| {
+                (node_id.clone(), h.connection.quinn_conn.rtt().as_millis() as u64)
+            })
+            .collect()
+    }
+
     /// 获取活跃连接数
     pub async fn active_connection_count(&self) -> usize {
         self.connections.read().await.len()
@@ -1,6 +1,6 @@
 //! # Project 模块
 //!
-//! 管理项目级配置和本地 Skill
+//! 管理项目级配置和本地 Skill（`.cis/skills.toml` 声明项目级 Skills）
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -8,9 +8,13 @@ use std::path::{Path, PathBuf};
 
 use crate::error::{CisError, Result};
 
+pub mod registry;
 pub mod session;
+pub mod skills_manifest;
 
+pub use registry::{ProjectRegistry, RegisteredProject, PROJECT_REGISTRY_FILE};
 pub use session::ProjectSession;
+pub use skills_manifest::{ProjectSkillDecl, ProjectSkillManifest};
 
 /// 项目配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -233,6 +237,11 @@ impl Project {
         let local_skills_dir = cis_dir.join("skills");
         std::fs::create_dir_all(&local_skills_dir)?;
 
+        // 记录到注册表，供孤儿记忆回收判断（失败不影响初始化）
+        if let Err(e) = ProjectRegistry::open_default().register(dir, &config.memory.namespace) {
+            tracing::warn!("Failed to register project {:?}: {}", dir, e);
+        }
+
         Ok(Self {
             config,
             local_skills_dir,
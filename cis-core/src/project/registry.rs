@@ -0,0 +1,108 @@
+//! # 项目注册表
+//!
+//! 记录本机初始化过的项目根目录及其记忆命名空间（数据目录下的 `projects.json`）。
+//! 项目目录被删除后，对应命名空间的记忆不会自动清理，
+//! `cis memory gc --orphans` 只回收已注册但项目已不存在的命名空间，
+//! 没有注册记录的命名空间（如注册表出现之前创建的项目）会被保留。
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::error::{CisError, Result};
+use crate::storage::paths::Paths;
+
+/// 注册表文件名（位于数据目录下）
+pub const PROJECT_REGISTRY_FILE: &str = "projects.json";
+
+/// 注册表条目
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisteredProject {
+    /// 项目根目录
+    pub root: PathBuf,
+    /// 记忆命名空间，如 `project/my-app`
+    pub namespace: String,
+}
+
+impl RegisteredProject {
+    /// 项目配置文件是否仍存在
+    pub fn exists(&self) -> bool {
+        self.root.join(".cis").join("project.toml").exists()
+    }
+}
+
+/// 项目注册表
+pub struct ProjectRegistry {
+    path: PathBuf,
+}
+
+impl ProjectRegistry {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// 使用默认数据目录
+    pub fn open_default() -> Self {
+        Self::new(Paths::data_dir().join(PROJECT_REGISTRY_FILE))
+    }
+
+    /// 所有已注册项目，文件不存在时为空
+    pub fn list(&self) -> Result<Vec<RegisteredProject>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = std::fs::read_to_string(&self.path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| CisError::config_parse_error(&self.path.display().to_string(), e.to_string()))
+    }
+
+    /// 注册项目，同一根目录重复注册时更新命名空间
+    pub fn register(&self, root: &Path, namespace: &str) -> Result<()> {
+        let mut projects = self.list()?;
+        projects.retain(|p| p.root != root);
+        projects.push(RegisteredProject {
+            root: root.to_path_buf(),
+            namespace: namespace.to_string(),
+        });
+        self.save(&projects)
+    }
+
+    /// 仍存在于磁盘上的项目
+    pub fn active(&self) -> Result<Vec<RegisteredProject>> {
+        Ok(self.list()?.into_iter().filter(|p| p.exists()).collect())
+    }
+
+    fn save(&self, projects: &[RegisteredProject]) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(projects)
+            .map_err(|e| CisError::configuration(format!("Failed to serialize project registry: {}", e)))?;
+        std::fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_projects() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let registry = ProjectRegistry::new(temp_dir.path().join(PROJECT_REGISTRY_FILE));
+
+        let alive = temp_dir.path().join("alive");
+        std::fs::create_dir_all(alive.join(".cis")).unwrap();
+        std::fs::write(alive.join(".cis").join("project.toml"), "").unwrap();
+
+        registry.register(&alive, "project/alive").unwrap();
+        registry.register(&temp_dir.path().join("gone"), "project/gone").unwrap();
+        // 重复注册只保留一条
+        registry.register(&alive, "project/alive").unwrap();
+
+        assert_eq!(registry.list().unwrap().len(), 2);
+        let active = registry.active().unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].namespace, "project/alive");
+    }
+}
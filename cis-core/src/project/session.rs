@@ -94,6 +94,7 @@ impl ProjectSession {
                             subscriptions: Vec::new(),
                             config_schema: None,
                             room_config: None,
+                            dependencies: Vec::new(),
                         };
 
                         // 注册 skill 元数据
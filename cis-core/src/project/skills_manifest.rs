@@ -0,0 +1,190 @@
+//! # 项目 Skill 清单
+//!
+//! 读取项目根目录下的 `.cis/skills.toml`，声明项目级 Skills：
+//!
+//! ```toml
+//! [[skill]]
+//! name = "lint-helper"
+//! path = "skills/lint_helper.wasm"
+//! version = "0.1.0"
+//! auto_activate = true
+//! ```
+//!
+//! `path` 相对于项目根目录，指向 WASM 文件或动态库。
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::error::{CisError, Result};
+use crate::skill::types::SkillType;
+
+/// 清单文件名（位于 `.cis/` 下）
+pub const SKILLS_MANIFEST_FILE: &str = "skills.toml";
+
+/// `cis init --project` 生成的初始清单
+pub const STARTER_SKILLS_MANIFEST: &str = r#"# 项目级 Skills
+#
+# 每个 [[skill]] 声明一个随项目加载的 Skill，path 相对于项目根目录。
+#
+# [[skill]]
+# name = "my-skill"
+# path = ".cis/skills/my_skill.wasm"
+# version = "0.1.0"
+# auto_activate = false
+"#;
+
+/// 清单中的 Skill 条目
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectSkillDecl {
+    /// Skill 名称
+    pub name: String,
+    /// WASM 文件或动态库路径（相对于项目根目录）
+    pub path: String,
+    /// 版本号
+    #[serde(default = "default_version")]
+    pub version: String,
+    /// 加载后是否自动激活
+    #[serde(default)]
+    pub auto_activate: bool,
+}
+
+fn default_version() -> String {
+    "0.1.0".to_string()
+}
+
+impl ProjectSkillDecl {
+    /// 根据文件扩展名推断 Skill 类型
+    pub fn skill_type(&self) -> Result<SkillType> {
+        match Path::new(&self.path).extension().and_then(|e| e.to_str()) {
+            Some("wasm") => Ok(SkillType::Wasm),
+            Some("so") | Some("dylib") | Some("dll") => Ok(SkillType::Native),
+            _ => Err(CisError::skill(format!(
+                "Skill '{}': unsupported artifact '{}' (expected .wasm or shared library)",
+                self.name, self.path
+            ))),
+        }
+    }
+}
+
+/// 项目 Skill 清单
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectSkillManifest {
+    /// 声明的 Skills
+    #[serde(rename = "skill", default)]
+    pub skills: Vec<ProjectSkillDecl>,
+}
+
+impl ProjectSkillManifest {
+    /// 清单文件路径
+    pub fn manifest_path(project_root: &Path) -> PathBuf {
+        project_root.join(".cis").join(SKILLS_MANIFEST_FILE)
+    }
+
+    /// 解析清单内容
+    pub fn parse(content: &str) -> Result<Self> {
+        let manifest: Self = toml::from_str(content)
+            .map_err(|e| CisError::config_parse_error(SKILLS_MANIFEST_FILE, e.to_string()))?;
+
+        let mut seen = std::collections::HashSet::new();
+        for skill in &manifest.skills {
+            if !seen.insert(skill.name.as_str()) {
+                return Err(CisError::config_validation_error(
+                    "skill.name",
+                    format!("duplicate skill '{}'", skill.name),
+                ));
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// 从项目根目录加载清单，不存在时返回空清单
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let path = Self::manifest_path(project_root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        Self::parse(&content)
+    }
+
+    /// 写入初始清单（已存在时不覆盖），返回是否新建
+    pub fn write_starter(project_root: &Path) -> Result<bool> {
+        let path = Self::manifest_path(project_root);
+        if path.exists() {
+            return Ok(false);
+        }
+
+        std::fs::create_dir_all(project_root.join(".cis"))?;
+        std::fs::write(&path, STARTER_SKILLS_MANIFEST)?;
+        Ok(true)
+    }
+
+    /// Skill 文件的绝对路径
+    pub fn resolve_path(project_root: &Path, skill: &ProjectSkillDecl) -> PathBuf {
+        project_root.join(&skill.path)
+    }
+
+    /// 是否声明了指定 Skill
+    pub fn contains(&self, name: &str) -> bool {
+        self.skills.iter().any(|s| s.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = ProjectSkillManifest::parse(
+            r#"
+[[skill]]
+name = "lint-helper"
+path = "skills/lint_helper.wasm"
+version = "1.2.0"
+auto_activate = true
+
+[[skill]]
+name = "native-tool"
+path = "target/release/libnative_tool.so"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.skills.len(), 2);
+        assert!(manifest.skills[0].auto_activate);
+        assert_eq!(manifest.skills[0].skill_type().unwrap(), SkillType::Wasm);
+        assert_eq!(manifest.skills[1].version, "0.1.0");
+        assert_eq!(manifest.skills[1].skill_type().unwrap(), SkillType::Native);
+        assert!(manifest.contains("native-tool"));
+    }
+
+    #[test]
+    fn test_parse_rejects_duplicates() {
+        let content = r#"
+[[skill]]
+name = "a"
+path = "a.wasm"
+
+[[skill]]
+name = "a"
+path = "b.wasm"
+"#;
+        assert!(ProjectSkillManifest::parse(content).is_err());
+    }
+
+    #[test]
+    fn test_starter_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert!(ProjectSkillManifest::load(dir.path()).unwrap().skills.is_empty());
+        assert!(ProjectSkillManifest::write_starter(dir.path()).unwrap());
+        assert!(!ProjectSkillManifest::write_starter(dir.path()).unwrap());
+
+        // 初始清单全部为注释，解析为空
+        let manifest = ProjectSkillManifest::load(dir.path()).unwrap();
+        assert!(manifest.skills.is_empty());
+    }
+}
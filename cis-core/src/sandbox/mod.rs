@@ -9,12 +9,17 @@
 //! - Symlink attack prevention
 //! - Recursive symlink depth limiting
 //! - Safe path construction
+//! - Shell command metacharacter validation ([`SandboxValidator`])
 //!
 //! ## Architecture
 //!
 //! This module is 100% inherited from AgentFlow's proven implementation,
 //! adapted only for CIS crate naming.
 
+mod validator;
+
+pub use validator::{SandboxValidator, ShellRules, SHELL_OPERATORS};
+
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
@@ -36,6 +41,9 @@ pub enum SandboxError {
 
     #[error("Path validation failed: {0}")]
     ValidationFailed(String),
+
+    #[error("Command injection detected: {0}")]
+    CommandInjection(String),
 }
 
 /// Sandbox configuration
@@ -0,0 +1,260 @@
+//! # Sandbox Validator
+//!
+//! Stateless checks applied before running commands from untrusted task specs:
+//!
+//! - [`SandboxValidator::validate_path`] resolves symlinks and requires the
+//!   canonical path to stay under one of the allowed roots
+//! - [`SandboxValidator::validate_shell_command`] rejects the shell operators
+//!   listed in [`ShellRules`] outside of quoted arguments; plain variable
+//!   expansion (`$NAME`, `"${NAME}"`) is always allowed
+
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use super::SandboxError;
+
+/// Shell operators the validator recognizes, longest first
+pub const SHELL_OPERATORS: &[&str] = &["&&", "||", "$(", ";", "&", "|", ">", "<", "`", "(", ")", "\n"];
+
+/// Operators that still expand inside double quotes
+const DOUBLE_QUOTE_EXPANSIONS: &[&str] = &["$(", "`"];
+
+/// Shell operators rejected by [`SandboxValidator::validate_shell_command_with`]
+///
+/// Defaults to every entry of [`SHELL_OPERATORS`]; use [`ShellRules::allow`]
+/// to permit e.g. pipes or `&&` chains for trusted task sources.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellRules {
+    forbidden: Vec<&'static str>,
+}
+
+impl Default for ShellRules {
+    fn default() -> Self {
+        Self {
+            forbidden: SHELL_OPERATORS.to_vec(),
+        }
+    }
+}
+
+impl ShellRules {
+    /// Permit `operator`; returns `None` if it is not a known operator
+    pub fn allow(mut self, operator: &str) -> Option<Self> {
+        if !SHELL_OPERATORS.contains(&operator) {
+            return None;
+        }
+        self.forbidden.retain(|op| *op != operator);
+        Some(self)
+    }
+
+    /// Whether `operator` is rejected
+    pub fn forbids(&self, operator: &str) -> bool {
+        self.forbidden.contains(&operator)
+    }
+}
+
+/// Stateless sandbox validator
+pub struct SandboxValidator;
+
+impl SandboxValidator {
+    /// Validate that `path` resolves to a location under one of `allowed_roots`
+    ///
+    /// Symlinks are resolved for the existing part of the path; the remaining
+    /// (not yet created) components are appended as-is.
+    pub fn validate_path(path: &Path, allowed_roots: &[PathBuf]) -> Result<(), SandboxError> {
+        let resolved = Self::resolve(path)?;
+
+        let allowed = allowed_roots
+            .iter()
+            .filter_map(|root| std::fs::canonicalize(root).ok())
+            .any(|root| resolved.starts_with(&root));
+
+        if !allowed {
+            warn!(
+                "Path {} resolves to {} outside allowed roots",
+                path.display(),
+                resolved.display()
+            );
+            return Err(SandboxError::PathTraversalDetected(path.display().to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Validate `cmd` against the default [`ShellRules`]
+    pub fn validate_shell_command(cmd: &str) -> Result<(), SandboxError> {
+        Self::validate_shell_command_with(cmd, &ShellRules::default())
+    }
+
+    /// Validate that `cmd` uses no operator forbidden by `rules` outside of quotes
+    ///
+    /// Single quotes suppress everything. Double quotes still run command
+    /// substitution (`$(` and backticks), so those are checked there as well.
+    /// Variable expansion (`$NAME`, `${NAME}`) is never rejected.
+    pub fn validate_shell_command_with(cmd: &str, rules: &ShellRules) -> Result<(), SandboxError> {
+        let mut rest = cmd;
+        let mut single = false;
+        let mut double = false;
+
+        while let Some(c) = rest.chars().next() {
+            let operator = if single {
+                None
+            } else {
+                SHELL_OPERATORS
+                    .iter()
+                    .copied()
+                    .find(|op| rest.starts_with(op))
+                    .filter(|op| !double || DOUBLE_QUOTE_EXPANSIONS.contains(op))
+            };
+            if let Some(op) = operator {
+                if rules.forbids(op) {
+                    return Err(Self::injection(cmd, op));
+                }
+                rest = &rest[op.len()..];
+                continue;
+            }
+
+            match c {
+                '\'' if !double => single = !single,
+                '"' if !single => double = !double,
+                // Escaped character is literal (outside single quotes)
+                '\\' if !single => {
+                    rest = &rest[1..];
+                    if let Some(escaped) = rest.chars().next() {
+                        rest = &rest[escaped.len_utf8()..];
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+
+        if single || double {
+            return Err(SandboxError::CommandInjection(format!(
+                "unterminated quote in command: {}",
+                cmd
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn injection(cmd: &str, op: &str) -> SandboxError {
+        warn!("Shell operator {:?} rejected in command: {}", op, cmd);
+        SandboxError::CommandInjection(format!("operator {:?} in command: {}", op, cmd))
+    }
+
+    /// Canonicalize the longest existing prefix and append the rest
+    fn resolve(path: &Path) -> Result<PathBuf, SandboxError> {
+        let mut existing = path.to_path_buf();
+        let mut rest = Vec::new();
+
+        loop {
+            match std::fs::canonicalize(&existing) {
+                Ok(canonical) => {
+                    return Ok(rest.into_iter().rev().fold(canonical, |p, c| p.join(c)));
+                }
+                Err(_) => {
+                    // `file_name` is `None` for a trailing `..`, which is never
+                    // appended unresolved
+                    let Some(name) = existing.file_name().map(|n| n.to_os_string()) else {
+                        return Err(SandboxError::PathResolutionFailed(path.display().to_string()));
+                    };
+                    rest.push(name);
+                    if !existing.pop() {
+                        return Err(SandboxError::PathResolutionFailed(path.display().to_string()));
+                    }
+                    if existing.as_os_str().is_empty() {
+                        existing = PathBuf::from(".");
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_path_under_root() {
+        let root = tempfile::tempdir().unwrap();
+        let roots = vec![root.path().to_path_buf()];
+
+        std::fs::create_dir(root.path().join("sub")).unwrap();
+        assert!(SandboxValidator::validate_path(&root.path().join("sub"), &roots).is_ok());
+        // Not yet created files are allowed
+        assert!(SandboxValidator::validate_path(&root.path().join("sub/new.txt"), &roots).is_ok());
+
+        let escaped = root.path().join("sub/../../etc");
+        assert!(matches!(
+            SandboxValidator::validate_path(&escaped, &roots),
+            Err(SandboxError::PathTraversalDetected(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_path_resolves_symlinks() {
+        let root = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let link = root.path().join("link");
+        std::os::unix::fs::symlink(outside.path(), &link).unwrap();
+
+        let roots = vec![root.path().to_path_buf()];
+        assert!(matches!(
+            SandboxValidator::validate_path(&link.join("file.txt"), &roots),
+            Err(SandboxError::PathTraversalDetected(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_shell_command() {
+        assert!(SandboxValidator::validate_shell_command("cargo build --release").is_ok());
+        assert!(SandboxValidator::validate_shell_command("echo 'a; b | c > d'").is_ok());
+        assert!(SandboxValidator::validate_shell_command("grep \"x && y\" file").is_ok());
+        assert!(SandboxValidator::validate_shell_command("echo a\\;b").is_ok());
+        // Plain variable expansion
+        assert!(SandboxValidator::validate_shell_command("echo $HOME").is_ok());
+        assert!(SandboxValidator::validate_shell_command("echo \"$TASK_STEP1_OUTPUT\"").is_ok());
+        assert!(SandboxValidator::validate_shell_command("cd \"${GITHUB_WORKSPACE}/src\"").is_ok());
+
+        for cmd in [
+            "ls; rm -rf /",
+            "cat a && cat b",
+            "cat file | sh",
+            "echo $(id)",
+            "echo `id`",
+            "echo \"`id`\"",
+            "echo x > /etc/passwd",
+            "sh < script",
+            "(ls)",
+            "echo \"$(id)\"",
+            "echo 'unterminated",
+        ] {
+            assert!(
+                matches!(
+                    SandboxValidator::validate_shell_command(cmd),
+                    Err(SandboxError::CommandInjection(_))
+                ),
+                "{} should be rejected",
+                cmd
+            );
+        }
+    }
+
+    #[test]
+    fn test_configurable_shell_rules() {
+        let rules = ShellRules::default().allow("&&").unwrap().allow("|").unwrap();
+        assert!(SandboxValidator::validate_shell_command_with("cargo build && cargo test", &rules).is_ok());
+        assert!(SandboxValidator::validate_shell_command_with("git log | head -n 5", &rules).is_ok());
+
+        // Allowing `&&` does not allow a lone `&`, nor `||`
+        assert!(SandboxValidator::validate_shell_command_with("sleep 10 &", &rules).is_err());
+        assert!(SandboxValidator::validate_shell_command_with("a || b", &rules).is_err());
+        assert!(SandboxValidator::validate_shell_command_with("echo $(id)", &rules).is_err());
+
+        assert!(ShellRules::default().allow("rm").is_none());
+    }
+}
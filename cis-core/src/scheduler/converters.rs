@@ -134,6 +134,10 @@ pub struct UnifiedTask {
     /// Output mapping
     #[serde(default)]
     pub outputs: Option<Map<String, String>>,
+
+    /// Cancel running sibling tasks when this task fails
+    #[serde(default)]
+    pub fail_fast: bool,
 }
 
 fn default_skill_method() -> String {
@@ -253,6 +257,7 @@ impl UnifiedTask {
             condition: None,
             idempotent: false,
             outputs: None,
+            fail_fast: node.fail_fast,
         }
     }
 }
@@ -294,6 +299,7 @@ impl TryFrom<UnifiedDag> for TaskDag {
                 }
 
                 node.rollback = task.rollback;
+                node.fail_fast = task.fail_fast;
             }
         }
 
@@ -376,6 +382,7 @@ impl UnifiedTask {
             condition: None,
             idempotent: false,
             outputs: None,
+            fail_fast: false,
         }
     }
 }
@@ -674,6 +681,7 @@ mod tests {
                     condition: None,
                     idempotent: false,
                     outputs: None,
+                    fail_fast: false,
                 },
                 UnifiedTask {
                     id: "task-2".to_string(),
@@ -691,6 +699,7 @@ mod tests {
                     condition: None,
                     idempotent: false,
                     outputs: None,
+                    fail_fast: false,
                 },
             ],
             execution_policy: ExecutionPolicy::AllSuccess,
@@ -821,6 +830,7 @@ impl Default for UnifiedTask {
             condition: None,
             idempotent: false,
             outputs: None,
+            fail_fast: false,
         }
     }
 }
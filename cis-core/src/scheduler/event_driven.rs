@@ -10,17 +10,25 @@
 //! - Error notifications (via broadcast)
 //! - Periodic health checks
 //!
+//! DAG runs can also be submitted remotely: [`EventDrivenScheduler::start`]
+//! listens on the configured Matrix room for `io.cis.dag.execute` events and
+//! turns each one into a new run. Only senders listed in
+//! [`EventDrivenConfig::allowed_senders`] may submit runs. Event IDs are
+//! remembered so that events replayed after a reconnect are not executed twice.
+//!
 //! ## Benefits
 //!
 //! - **Lower latency**: <1ms response vs 50ms average with polling
 //! - **Reduced CPU**: No continuous wake-ups
 //! - **Better scalability**: Efficient for many concurrent DAG runs
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
 use crate::agent::persistent::{
@@ -28,14 +36,25 @@ use crate::agent::persistent::{
 };
 use crate::agent::cluster::context::ContextStore;
 use crate::error::{CisError, Result};
+use crate::matrix::events::{parse_dag_event, DagExecuteContent, DagExecuteEvent};
+use crate::matrix::nucleus::{MatrixEvent, MatrixNucleus};
+use crate::scheduler::fairness::{
+    FairShareScheduler, DEFAULT_FAIRNESS_RESET_INTERVAL_SECS, DEFAULT_MAX_SHARE,
+};
 use crate::scheduler::notify::{
     CompletionNotifier, ErrorNotifier, ErrorSeverity, NotificationBundle, ReadyNotify,
     TaskCompletion, TaskError,
 };
 use crate::scheduler::{
-    DagNode, DagNodeStatus, DagRunStatus, DagScheduler, RuntimeType, TaskDag,
+    DagNode, DagNodeStatus, DagRunStatus, DagScheduler, DagScope, DagSpec, RuntimeType, TaskDag,
 };
 
+/// Matrix event type carrying a DAG execution request
+pub const DAG_EXECUTE_EVENT_TYPE: &str = "io.cis.dag.execute";
+
+/// Number of processed room event IDs remembered for deduplication
+const MAX_PROCESSED_EVENTS: usize = 10_000;
+
 /// Configuration for event-driven scheduler
 #[derive(Debug, Clone)]
 pub struct EventDrivenConfig {
@@ -51,6 +70,14 @@ pub struct EventDrivenConfig {
     pub default_runtime: PersistentRuntimeType,
     /// Whether to auto-cleanup agents after DAG completion
     pub auto_cleanup_agents: bool,
+    /// Matrix room to receive DAG execute events from
+    pub room_id: Option<String>,
+    /// Matrix user IDs allowed to submit DAG runs (empty rejects all)
+    pub allowed_senders: HashSet<String>,
+    /// Maximum share of task slots a single DAG scope may use (0.0 - 1.0]
+    pub max_share: f64,
+    /// Fair-share usage window length in seconds
+    pub fairness_reset_interval_secs: u64,
 }
 
 impl Default for EventDrivenConfig {
@@ -62,6 +89,10 @@ impl Default for EventDrivenConfig {
             enable_context_injection: true,
             default_runtime: PersistentRuntimeType::Claude,
             auto_cleanup_agents: true,
+            room_id: None,
+            allowed_senders: HashSet::new(),
+            max_share: DEFAULT_MAX_SHARE,
+            fairness_reset_interval_secs: DEFAULT_FAIRNESS_RESET_INTERVAL_SECS,
         }
     }
 }
@@ -100,6 +131,46 @@ impl EventDrivenConfig {
         self.auto_cleanup_agents = cleanup;
         self
     }
+
+    pub fn with_room(mut self, room_id: impl Into<String>) -> Self {
+        self.room_id = Some(room_id.into());
+        self
+    }
+
+    pub fn with_allowed_sender(mut self, sender: impl Into<String>) -> Self {
+        self.allowed_senders.insert(sender.into());
+        self
+    }
+
+    pub fn with_fair_share(mut self, max_share: f64, reset_interval_secs: u64) -> Self {
+        self.max_share = max_share;
+        self.fairness_reset_interval_secs = reset_interval_secs;
+        self
+    }
+}
+
+/// Bounded set of room event IDs that have already been turned into runs
+#[derive(Debug, Default)]
+struct ProcessedEvents {
+    ids: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl ProcessedEvents {
+    /// Record an event ID, returning `false` if it was already seen
+    fn insert(&mut self, event_id: &str) -> bool {
+        if !self.ids.insert(event_id.to_string()) {
+            return false;
+        }
+
+        self.order.push_back(event_id.to_string());
+        if self.order.len() > MAX_PROCESSED_EVENTS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+        true
+    }
 }
 
 /// Event-driven scheduler
@@ -120,6 +191,12 @@ pub struct EventDrivenScheduler {
     active_agents: Arc<RwLock<HashMap<String, HashMap<String, AgentHandle>>>>,
     /// Currently running task count
     running_task_count: Arc<RwLock<usize>>,
+    /// Room event IDs already turned into runs
+    processed_events: Arc<RwLock<ProcessedEvents>>,
+    /// Whether a `run()` loop is currently driving execution
+    driving: Arc<AtomicBool>,
+    /// Fair-share slot allocation across DAG scopes
+    fairness: Arc<RwLock<FairShareScheduler>>,
 }
 
 impl std::fmt::Debug for EventDrivenScheduler {
@@ -138,6 +215,7 @@ impl EventDrivenScheduler {
         config: EventDrivenConfig,
     ) -> Result<Self> {
         let context_store = ContextStore::default_store()?;
+        let fairness = FairShareScheduler::new(config.max_share, config.fairness_reset_interval_secs);
 
         Ok(Self {
             scheduler: Arc::new(RwLock::new(scheduler)),
@@ -147,9 +225,17 @@ impl EventDrivenScheduler {
             config,
             active_agents: Arc::new(RwLock::new(HashMap::new())),
             running_task_count: Arc::new(RwLock::new(0)),
+            processed_events: Arc::new(RwLock::new(ProcessedEvents::default())),
+            driving: Arc::new(AtomicBool::new(false)),
+            fairness: Arc::new(RwLock::new(fairness)),
         })
     }
 
+    /// Fair-share scheduler used to split task slots between DAG scopes
+    pub fn fairness_scheduler(&self) -> Arc<RwLock<FairShareScheduler>> {
+        self.fairness.clone()
+    }
+
     /// Create with defaults
     pub fn with_defaults(agent_pool: AgentPool) -> Result<Self> {
         Self::new(DagScheduler::new(), agent_pool, EventDrivenConfig::default())
@@ -167,6 +253,134 @@ impl EventDrivenScheduler {
         Ok(run_id)
     }
 
+    /// Start receiving DAG execute events from the configured Matrix room
+    ///
+    /// Each new `io.cis.dag.execute` event becomes a DAG run, and a `run()`
+    /// loop is spawned whenever none is active. The returned task ends when
+    /// the nucleus event bus closes, or immediately if no room is configured.
+    pub fn start(&self, nucleus: Arc<MatrixNucleus>) -> JoinHandle<()> {
+        let this = self.clone_ref();
+        // Subscribe before spawning so events sent right after start() are seen
+        let mut events = nucleus.subscribe_events();
+
+        tokio::spawn(async move {
+            let Some(room_id) = this.config.room_id.clone() else {
+                warn!("No Matrix room configured, DAG event intake disabled");
+                return;
+            };
+            info!("Listening for DAG execute events in room {}", room_id);
+
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        if event.room_id.as_str() != room_id {
+                            continue;
+                        }
+                        match this.handle_room_event(&event).await {
+                            Ok(Some(_)) => this.ensure_driver(),
+                            Ok(None) => {}
+                            Err(e) => warn!("Rejected DAG event {}: {}", event.event_id, e),
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("Matrix event bus lagged, missed {} events", n);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        info!("Matrix event bus closed, stopping DAG event intake");
+                        break;
+                    }
+                }
+            }
+        })
+    }
+
+    /// Turn a room event into a DAG run
+    ///
+    /// Returns `Ok(None)` for events that are not DAG execute requests and for
+    /// events whose ID was already processed (e.g. replayed after reconnect).
+    /// Requests from senders outside the allow-list are rejected.
+    pub async fn handle_room_event(&self, event: &MatrixEvent) -> Result<Option<String>> {
+        let Some(dag_event) = parse_execute_event(event) else {
+            return Ok(None);
+        };
+
+        if !self.config.allowed_senders.contains(event.sender.as_str()) {
+            return Err(CisError::scheduler(format!(
+                "Sender {} is not allowed to submit DAG runs",
+                event.sender.as_str()
+            )));
+        }
+
+        if !self.processed_events.write().await.insert(event.event_id.as_str()) {
+            debug!("Skipping already processed DAG event {}", event.event_id);
+            return Ok(None);
+        }
+
+        let content = dag_event.content;
+        let dag = DagSpec::new(content.dag_id.clone(), content.tasks.clone())
+            .to_task_dag()
+            .and_then(|dag| dag.validate().map(|_| dag))
+            .map_err(|e| CisError::scheduler(format!("Invalid DAG {}: {}", content.dag_id, e)))?;
+        let task_commands = content
+            .tasks
+            .iter()
+            .map(|task| (task.id.clone(), task.command.clone()))
+            .collect();
+
+        let run_id = {
+            let mut scheduler = self.scheduler.write().await;
+            let run_id = scheduler.create_run_with_source(dag, None, None, task_commands);
+            if let Some(run) = scheduler.get_run_mut(&run_id) {
+                run.scope = content.scope;
+                run.target_node = content.target_node;
+                run.priority = content.priority;
+            }
+            run_id
+        };
+
+        self.notifications.ready.notify_ready();
+
+        info!(
+            "Created DAG run {} for {} from event {} ({})",
+            run_id, content.dag_id, event.event_id, event.sender
+        );
+        Ok(Some(run_id))
+    }
+
+    /// Spawn a `run()` loop unless one is already active
+    fn ensure_driver(&self) {
+        if self.driving.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let this = self.clone_ref();
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = this.run().await {
+                    error!("Event-driven scheduler loop failed: {}", e);
+                }
+                this.driving.store(false, Ordering::SeqCst);
+
+                // A run created while the loop was exiting would otherwise
+                // wait for the next event
+                if !this.has_pending_runs().await || this.driving.swap(true, Ordering::SeqCst) {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Whether any run is still running
+    async fn has_pending_runs(&self) -> bool {
+        let scheduler = self.scheduler.read().await;
+        let run_ids: Vec<_> = scheduler.run_ids().cloned().collect();
+        run_ids.iter().any(|id| {
+            scheduler
+                .get_run(id)
+                .is_some_and(|run| run.status == DagRunStatus::Running)
+        })
+    }
+
     /// Execute the event-driven scheduler main loop
     ///
     /// This method runs until all DAG runs are completed or an error occurs.
@@ -306,11 +520,12 @@ impl EventDrivenScheduler {
             return Ok(());
         }
 
-        // Schedule tasks up to available slots
-        let tasks_to_schedule: Vec<_> = ready_tasks
-            .into_iter()
-            .take(available_slots)
-            .collect();
+        // Schedule tasks up to available slots, without letting one scope take them all
+        let tasks_to_schedule = self.fairness.write().await.select(
+            ready_tasks,
+            available_slots,
+            self.config.max_concurrent_tasks,
+        );
 
         for (run_id, task_id) in tasks_to_schedule {
             if let Err(e) = self.schedule_task(run_id, task_id).await {
@@ -321,8 +536,8 @@ impl EventDrivenScheduler {
         Ok(())
     }
 
-    /// Get all ready tasks across all DAG runs
-    async fn get_all_ready_tasks(&self) -> Result<Vec<(String, String)>> {
+    /// Get all ready tasks across all DAG runs, tagged with the run's scope
+    async fn get_all_ready_tasks(&self) -> Result<Vec<(DagScope, (String, String))>> {
         let mut ready_tasks = Vec::new();
 
         let scheduler = self.scheduler.read().await;
@@ -333,7 +548,7 @@ impl EventDrivenScheduler {
                 if run.status == DagRunStatus::Running || run.status == DagRunStatus::Pending {
                     let task_ids = run.dag.get_ready_tasks();
                     for task_id in task_ids {
-                        ready_tasks.push((run_id.clone(), task_id));
+                        ready_tasks.push((run.scope.clone(), (run_id.clone(), task_id)));
                     }
                 }
             }
@@ -712,10 +927,40 @@ impl EventDrivenScheduler {
             config: self.config.clone(),
             active_agents: self.active_agents.clone(),
             running_task_count: self.running_task_count.clone(),
+            processed_events: self.processed_events.clone(),
+            driving: self.driving.clone(),
+            fairness: self.fairness.clone(),
         }
     }
 }
 
+/// Extract a DAG execute request from a room event
+///
+/// Accepts the custom `io.cis.dag.execute` event type as well as plain
+/// `m.room.message` events whose body is a serialized [`DagExecuteEvent`].
+fn parse_execute_event(event: &MatrixEvent) -> Option<DagExecuteEvent> {
+    match event.event_type.as_str() {
+        DAG_EXECUTE_EVENT_TYPE => {
+            match serde_json::from_value::<DagExecuteContent>(event.content.clone()) {
+                Ok(content) => Some(DagExecuteEvent {
+                    event_type: DAG_EXECUTE_EVENT_TYPE.to_string(),
+                    content,
+                }),
+                Err(e) => {
+                    warn!("Malformed DAG execute event {}: {}", event.event_id, e);
+                    None
+                }
+            }
+        }
+        "m.room.message" => event
+            .content
+            .get("body")
+            .and_then(|body| body.as_str())
+            .and_then(parse_dag_event),
+        _ => None,
+    }
+}
+
 /// Single task execution result
 #[derive(Debug, Clone)]
 struct SingleTaskResult {
@@ -768,6 +1013,7 @@ mod tests {
         assert_eq!(config.max_concurrent_tasks, 4);
         assert_eq!(config.task_timeout, Duration::from_secs(300));
         assert!(config.enable_context_injection);
+        assert_eq!(config.max_share, 0.5);
     }
 
     #[test]
@@ -775,9 +1021,12 @@ mod tests {
         let config = EventDrivenConfig::new()
             .with_max_concurrent(8)
             .with_task_timeout(Duration::from_secs(600))
-            .with_context_injection(false);
+            .with_context_injection(false)
+            .with_fair_share(0.25, 120);
 
         assert_eq!(config.max_concurrent_tasks, 8);
+        assert_eq!(config.max_share, 0.25);
+        assert_eq!(config.fairness_reset_interval_secs, 120);
         assert_eq!(config.task_timeout, Duration::from_secs(600));
         assert!(!config.enable_context_injection);
     }
@@ -803,4 +1052,163 @@ mod tests {
         assert!(!failure.success);
         assert_eq!(failure.exit_code, 1);
     }
+
+    fn test_scheduler(dir: &std::path::Path, config: EventDrivenConfig) -> EventDrivenScheduler {
+        EventDrivenScheduler {
+            scheduler: Arc::new(RwLock::new(DagScheduler::new())),
+            agent_pool: AgentPool::new(Default::default()),
+            context_store: ContextStore::new(dir.join("context.db")).unwrap(),
+            notifications: NotificationBundle::new(),
+            config,
+            active_agents: Arc::new(RwLock::new(HashMap::new())),
+            running_task_count: Arc::new(RwLock::new(0)),
+            processed_events: Arc::new(RwLock::new(ProcessedEvents::default())),
+            driving: Arc::new(AtomicBool::new(false)),
+            fairness: Arc::new(RwLock::new(FairShareScheduler::new(
+                DEFAULT_MAX_SHARE,
+                DEFAULT_FAIRNESS_RESET_INTERVAL_SECS,
+            ))),
+        }
+    }
+
+    fn dag_content(dag_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "dag_id": dag_id,
+            "tasks": [
+                {"id": "build", "type": "shell", "command": "cargo build"},
+                {"id": "test", "type": "shell", "command": "cargo test", "depends_on": ["build"]}
+            ],
+            "scope": {"type": "global"},
+            "target_node": "node-a",
+            "timestamp": "2026-01-01T00:00:00Z"
+        })
+    }
+
+    #[test]
+    fn test_processed_events_dedup() {
+        let mut processed = ProcessedEvents::default();
+        assert!(processed.insert("$1"));
+        assert!(!processed.insert("$1"));
+
+        for i in 0..MAX_PROCESSED_EVENTS {
+            processed.insert(&format!("$fill-{}", i));
+        }
+        // Oldest entry was evicted
+        assert!(processed.insert("$1"));
+        assert_eq!(processed.ids.len(), MAX_PROCESSED_EVENTS);
+    }
+
+    #[tokio::test]
+    async fn test_start_creates_runs_from_room_events() {
+        use crate::identity::DIDManager;
+        use crate::matrix::nucleus::{EventId, RoomId, UserId};
+        use crate::matrix::store::MatrixStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = EventDrivenConfig::new()
+            .with_room("!dags:cis")
+            .with_allowed_sender("@peer:cis");
+        let scheduler = test_scheduler(dir.path(), config);
+        let nucleus = Arc::new(MatrixNucleus::new_simple(
+            Arc::new(MatrixStore::open_in_memory().unwrap()),
+            Arc::new(DIDManager::generate("test-node").unwrap()),
+        ));
+        let handle = scheduler.start(nucleus.clone());
+
+        let room = RoomId::new("!dags:cis");
+        let sender = UserId::new("@peer:cis");
+        let inject = |room: &RoomId, event_id: &str, event_type: &str, content: serde_json::Value| {
+            let event = MatrixEvent::new(
+                room.clone(),
+                EventId::new(event_id),
+                sender.clone(),
+                event_type,
+                content,
+            );
+            nucleus.event_bus().send(event).unwrap();
+        };
+
+        inject(&room, "$evt-1", DAG_EXECUTE_EVENT_TYPE, dag_content("dag-1"));
+        // Replayed after reconnect
+        inject(&room, "$evt-1", DAG_EXECUTE_EVENT_TYPE, dag_content("dag-1"));
+        // Other room and unrelated event types are ignored
+        inject(&RoomId::new("!other:cis"), "$evt-2", DAG_EXECUTE_EVENT_TYPE, dag_content("dag-2"));
+        inject(&room, "$evt-3", "m.room.message", serde_json::json!({"body": "hello"}));
+        // Serialized event in a text message body
+        let body = serde_json::json!({"type": DAG_EXECUTE_EVENT_TYPE, "content": dag_content("dag-4")});
+        inject(&room, "$evt-4", "m.room.message", serde_json::json!({"body": body.to_string()}));
+
+        let runs = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if scheduler.scheduler.read().await.run_count() >= 2 {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await;
+        assert!(runs.is_ok(), "runs were not created");
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let dag_scheduler = scheduler.scheduler.read().await;
+        assert_eq!(dag_scheduler.run_count(), 2);
+        for run_id in dag_scheduler.run_ids() {
+            let run = dag_scheduler.get_run(run_id).unwrap();
+            assert_eq!(run.dag.node_count(), 2);
+            assert_eq!(run.dag.get_node("test").unwrap().dependencies, vec!["build"]);
+            assert_eq!(run.task_commands.get("build").map(String::as_str), Some("cargo build"));
+            assert_eq!(run.target_node.as_deref(), Some("node-a"));
+        }
+        drop(dag_scheduler);
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_handle_room_event_rejects_invalid_dag() {
+        use crate::matrix::nucleus::{EventId, RoomId, UserId};
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = EventDrivenConfig::default().with_allowed_sender("@peer:cis");
+        let scheduler = test_scheduler(dir.path(), config);
+
+        let mut content = dag_content("dag-cycle");
+        content["tasks"][0]["depends_on"] = serde_json::json!(["test"]);
+        let event = MatrixEvent::new(
+            RoomId::new("!dags:cis"),
+            EventId::new("$cycle"),
+            UserId::new("@peer:cis"),
+            DAG_EXECUTE_EVENT_TYPE,
+            content,
+        );
+
+        assert!(scheduler.handle_room_event(&event).await.is_err());
+        assert_eq!(scheduler.scheduler.read().await.run_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_handle_room_event_rejects_unknown_sender() {
+        use crate::matrix::nucleus::{EventId, RoomId, UserId};
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = EventDrivenConfig::default().with_allowed_sender("@peer:cis");
+        let scheduler = test_scheduler(dir.path(), config);
+
+        let event = |event_id: &str, sender: &str| {
+            MatrixEvent::new(
+                RoomId::new("!dags:cis"),
+                EventId::new(event_id),
+                UserId::new(sender),
+                DAG_EXECUTE_EVENT_TYPE,
+                dag_content("dag-1"),
+            )
+        };
+
+        assert!(scheduler.handle_room_event(&event("$1", "@stranger:cis")).await.is_err());
+        assert_eq!(scheduler.scheduler.read().await.run_count(), 0);
+
+        // A rejected event does not block a later one with the same ID from an allowed sender
+        assert!(scheduler.handle_room_event(&event("$1", "@peer:cis")).await.unwrap().is_some());
+        assert!(scheduler.has_pending_runs().await);
+    }
 }
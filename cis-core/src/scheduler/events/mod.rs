@@ -11,6 +11,7 @@ use tokio::sync::RwLock;
 use crate::error::Result;
 use crate::types::TaskStatus;
 use super::execution::ExecutionResult;
+use super::TodoListDiff;
 
 /// 调度器事件类型
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -27,6 +28,8 @@ pub enum SchedulerEventType {
     DagCompleted,
     /// DAG 执行失败
     DagFailed,
+    /// TODO list 外部变更
+    TodoListChanged,
 }
 
 /// 调度器事件
@@ -61,6 +64,11 @@ pub enum SchedulerEvent {
         dag_id: String,
         error: String,
     },
+    /// TODO list 外部变更事件
+    TodoListChanged {
+        run_id: String,
+        diff: TodoListDiff,
+    },
 }
 
 impl SchedulerEvent {
@@ -73,6 +81,7 @@ impl SchedulerEvent {
             Self::DagStarted { .. } => SchedulerEventType::DagStarted,
             Self::DagCompleted { .. } => SchedulerEventType::DagCompleted,
             Self::DagFailed { .. } => SchedulerEventType::DagFailed,
+            Self::TodoListChanged { .. } => SchedulerEventType::TodoListChanged,
         }
     }
 
@@ -85,6 +94,7 @@ impl SchedulerEvent {
             Self::DagStarted { dag_id } => format!("dag-started:{}", dag_id),
             Self::DagCompleted { dag_id, .. } => format!("dag-completed:{}", dag_id),
             Self::DagFailed { dag_id, .. } => format!("dag-failed:{}", dag_id),
+            Self::TodoListChanged { run_id, .. } => format!("todo-list-changed:{}", run_id),
         }
     }
 }
@@ -248,6 +258,16 @@ impl EventListener for LoggingEventListener {
                     "DAG failed"
                 );
             }
+            SchedulerEvent::TodoListChanged { run_id, diff } => {
+                tracing::info!(
+                    listener = %self.name,
+                    run_id = %run_id,
+                    added = diff.added.len(),
+                    removed = diff.removed.len(),
+                    modified = diff.modified.len(),
+                    "TODO list changed"
+                );
+            }
         }
         Ok(())
     }
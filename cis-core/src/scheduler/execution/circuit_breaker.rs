@@ -0,0 +1,251 @@
+//! # 熔断器
+//!
+//! DAG 任务调用的外部服务持续失败时，继续执行只会不断失败和重试。
+//! 熔断器按服务端点统计连续失败次数：
+//!
+//! - `Closed`: 正常放行，连续失败达到阈值后进入 `Open`
+//! - `Open`: 直接拒绝，经过 `reset_timeout` 后进入 `HalfOpen`
+//! - `HalfOpen`: 只放行一个探测请求，成功则 `Closed`，失败则重新 `Open`
+//!
+//! 服务端点从任务输入的 `env` 中提取（见 [`service_endpoint`]）。
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::types::Task;
+
+/// 默认连续失败阈值
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// 默认熔断恢复时间
+pub const DEFAULT_RESET_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 熔断器状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// 正常放行
+    Closed,
+    /// 熔断中，拒绝所有请求
+    Open,
+    /// 探测中，只放行一个请求
+    HalfOpen,
+}
+
+/// 单个服务端点的熔断器
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    /// 连续失败多少次后熔断
+    pub failure_threshold: u32,
+    /// 熔断后多久进入半开状态
+    pub reset_timeout: Duration,
+    /// 当前状态
+    pub state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probe_in_flight: bool,
+}
+
+impl CircuitBreaker {
+    /// 创建熔断器（初始为 `Closed`，阈值 0 按 1 处理）
+    pub fn new(failure_threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            reset_timeout,
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            opened_at: None,
+            probe_in_flight: false,
+        }
+    }
+
+    /// 请求是否可以放行
+    ///
+    /// `Open` 状态超过 `reset_timeout` 时转为 `HalfOpen` 并放行一个探测请求。
+    pub fn try_acquire(&mut self) -> bool {
+        match self.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => {
+                let elapsed = self.opened_at.map_or(Duration::MAX, |t| t.elapsed());
+                if elapsed >= self.reset_timeout {
+                    self.state = CircuitState::HalfOpen;
+                    self.probe_in_flight = true;
+                    true
+                } else {
+                    false
+                }
+            }
+            CircuitState::HalfOpen => {
+                if self.probe_in_flight {
+                    false
+                } else {
+                    self.probe_in_flight = true;
+                    true
+                }
+            }
+        }
+    }
+
+    /// 记录成功：关闭熔断器
+    pub fn record_success(&mut self) {
+        self.state = CircuitState::Closed;
+        self.consecutive_failures = 0;
+        self.opened_at = None;
+        self.probe_in_flight = false;
+    }
+
+    /// 记录失败：达到阈值或探测失败时打开熔断器
+    pub fn record_failure(&mut self) {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        if self.state == CircuitState::HalfOpen || self.consecutive_failures >= self.failure_threshold {
+            self.state = CircuitState::Open;
+            self.opened_at = Some(Instant::now());
+        }
+        self.probe_in_flight = false;
+    }
+
+    /// 当前连续失败次数
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(DEFAULT_FAILURE_THRESHOLD, DEFAULT_RESET_TIMEOUT)
+    }
+}
+
+/// 按服务端点管理的熔断器集合
+#[derive(Debug)]
+pub struct CircuitBreakerRegistry {
+    failure_threshold: u32,
+    reset_timeout: Duration,
+    breakers: Mutex<HashMap<String, CircuitBreaker>>,
+}
+
+impl CircuitBreakerRegistry {
+    /// 创建熔断器集合，新端点使用给定的阈值和恢复时间
+    pub fn new(failure_threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            failure_threshold,
+            reset_timeout,
+            breakers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 检查端点是否可以放行
+    pub fn try_acquire(&self, endpoint: &str) -> bool {
+        self.with_breaker(endpoint, CircuitBreaker::try_acquire)
+    }
+
+    /// 记录端点调用成功
+    pub fn record_success(&self, endpoint: &str) {
+        self.with_breaker(endpoint, CircuitBreaker::record_success);
+    }
+
+    /// 记录端点调用失败
+    pub fn record_failure(&self, endpoint: &str) {
+        self.with_breaker(endpoint, CircuitBreaker::record_failure);
+    }
+
+    /// 获取端点当前状态（未调用过的端点为 `Closed`）
+    pub fn state(&self, endpoint: &str) -> CircuitState {
+        self.breakers
+            .lock()
+            .ok()
+            .and_then(|breakers| breakers.get(endpoint).map(|b| b.state))
+            .unwrap_or(CircuitState::Closed)
+    }
+
+    fn with_breaker<T>(&self, endpoint: &str, f: impl FnOnce(&mut CircuitBreaker) -> T) -> T {
+        let mut breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
+        let breaker = breakers
+            .entry(endpoint.to_string())
+            .or_insert_with(|| CircuitBreaker::new(self.failure_threshold, self.reset_timeout));
+        f(breaker)
+    }
+}
+
+impl Default for CircuitBreakerRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_FAILURE_THRESHOLD, DEFAULT_RESET_TIMEOUT)
+    }
+}
+
+/// 从任务 `skill_params.env` 中提取外部服务端点
+///
+/// 取名称以 `_URL` / `_ENDPOINT` 结尾（或等于 `URL` / `ENDPOINT`）的变量中按名称排序的第一个，
+/// URL 只保留 `scheme://host[:port]` 部分，使同一服务的不同路径共享熔断器。
+pub fn service_endpoint(task: &Task) -> Option<String> {
+    let env = task.skill_params.as_ref()?.get("env")?.as_object()?;
+
+    let mut keys: Vec<&String> = env
+        .keys()
+        .filter(|key| {
+            let key = key.to_ascii_uppercase();
+            key == "URL" || key == "ENDPOINT" || key.ends_with("_URL") || key.ends_with("_ENDPOINT")
+        })
+        .collect();
+    keys.sort();
+
+    keys.into_iter()
+        .filter_map(|key| env[key].as_str())
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .map(|value| match value.split_once("://") {
+            Some((scheme, rest)) => {
+                let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+                format!("{}://{}", scheme.to_ascii_lowercase(), authority.to_ascii_lowercase())
+            }
+            None => value.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_transitions() {
+        let mut breaker = CircuitBreaker::new(2, Duration::from_millis(20));
+        assert!(breaker.try_acquire());
+        breaker.record_failure();
+        assert_eq!(breaker.state, CircuitState::Closed);
+        breaker.record_failure();
+        assert_eq!(breaker.state, CircuitState::Open);
+        assert!(!breaker.try_acquire());
+
+        std::thread::sleep(Duration::from_millis(25));
+        assert!(breaker.try_acquire());
+        assert_eq!(breaker.state, CircuitState::HalfOpen);
+        // 探测期间只放行一个请求
+        assert!(!breaker.try_acquire());
+
+        // 探测失败重新熔断
+        breaker.record_failure();
+        assert_eq!(breaker.state, CircuitState::Open);
+        assert!(!breaker.try_acquire());
+
+        std::thread::sleep(Duration::from_millis(25));
+        assert!(breaker.try_acquire());
+        breaker.record_success();
+        assert_eq!(breaker.state, CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 0);
+    }
+
+    #[test]
+    fn test_service_endpoint() {
+        let mut task = Task::new("t".to_string(), "t".to_string(), "g".to_string());
+        assert_eq!(service_endpoint(&task), None);
+
+        task.skill_params = Some(serde_json::json!({
+            "env": {
+                "TOKEN": "secret",
+                "PAYMENT_API_URL": "https://Pay.example.com:8443/v1/charge?x=1",
+                "SEARCH_ENDPOINT": "search.internal:9200"
+            }
+        }));
+        assert_eq!(service_endpoint(&task).as_deref(), Some("https://pay.example.com:8443"));
+    }
+}
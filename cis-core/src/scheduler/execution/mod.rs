@@ -4,14 +4,16 @@
 
 pub mod sync;
 pub mod parallel;
+pub mod circuit_breaker;
 
 use async_trait::async_trait;
 
 use crate::error::Result;
 use crate::types::{Task, TaskStatus};
 
-pub use sync::SyncExecutor;
+pub use sync::{CheckpointEntry, DagCheckpoint, SyncExecutor, TaskHandler};
 pub use parallel::ParallelExecutor;
+pub use circuit_breaker::{service_endpoint, CircuitBreaker, CircuitBreakerRegistry, CircuitState};
 
 /// 任务执行结果
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
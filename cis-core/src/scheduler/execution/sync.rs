@@ -6,23 +6,78 @@
 //! - 测试环境
 //! - 单机任务
 //! - 需要严格顺序执行的任务
+//!
+//! ## 检查点
+//!
+//! 配置检查点存储后，[`SyncExecutor::execute_run`] 每完成一个任务就把
+//! `DagRun` 状态和执行时间线写入 SQLite（按 `run_id` 一行）。进程中断后
+//! 通过 [`SyncExecutor::resume`] 加载最近的检查点，已完成的任务不会重复执行。
+//!
+//! ## 熔断
+//!
+//! 任务 `env` 中声明了外部服务端点时，执行前先检查该端点的熔断器，
+//! 熔断中的任务直接以 `CisError::CircuitOpen` 失败，不再调用服务（见 [`super::circuit_breaker`]）。
 
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
 
+use super::circuit_breaker::{service_endpoint, CircuitBreakerRegistry};
 use super::{Executor, ExecutionResult, ExecutorStats};
 use crate::error::{CisError, Result};
+use crate::scheduler::{DagPersistence, DagRun};
 use crate::types::Task;
 
+/// 自定义任务处理函数
+///
+/// 处理函数在阻塞线程池中调用，可以直接执行同步 I/O（如运行子进程）。
+pub type TaskHandler = Arc<dyn Fn(&Task) -> Result<serde_json::Value> + Send + Sync>;
+
+/// 执行时间线条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointEntry {
+    /// 任务执行结果（含输出）
+    pub result: ExecutionResult,
+    /// 完成时间
+    pub finished_at: DateTime<Utc>,
+}
+
+/// DAG 运行检查点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DagCheckpoint {
+    /// 运行状态（含节点状态）
+    pub run: DagRun,
+    /// 按完成顺序排列的执行时间线
+    pub timeline: Vec<CheckpointEntry>,
+}
+
+impl DagCheckpoint {
+    /// 获取任务输出
+    pub fn output(&self, task_id: &str) -> Option<&serde_json::Value> {
+        self.timeline
+            .iter()
+            .rev()
+            .find(|entry| entry.result.task_id == task_id)
+            .map(|entry| &entry.result.output)
+    }
+}
+
 /// 同步执行器
 ///
 /// 单线程顺序执行任务，记录统计信息。
 pub struct SyncExecutor {
     /// 执行统计
     stats: Arc<Mutex<ExecutorStats>>,
+    /// 自定义任务处理函数
+    handler: Option<TaskHandler>,
+    /// 检查点存储
+    checkpoints: Option<Arc<std::sync::Mutex<DagPersistence>>>,
+    /// 按服务端点的熔断器
+    circuit_breakers: Arc<CircuitBreakerRegistry>,
 }
 
 impl SyncExecutor {
@@ -33,9 +88,141 @@ impl SyncExecutor {
                 name: "sync".to_string(),
                 ..Default::default()
             })),
+            handler: None,
+            checkpoints: None,
+            circuit_breakers: Arc::new(CircuitBreakerRegistry::default()),
         }
     }
 
+    /// 设置任务处理函数
+    pub fn with_handler(mut self, handler: TaskHandler) -> Self {
+        self.handler = Some(handler);
+        self
+    }
+
+    /// 启用检查点
+    pub fn with_checkpoints(mut self, persistence: DagPersistence) -> Self {
+        self.checkpoints = Some(Arc::new(std::sync::Mutex::new(persistence)));
+        self
+    }
+
+    /// 设置熔断参数（默认连续失败 5 次熔断，30 秒后探测）
+    pub fn with_circuit_breaker(mut self, failure_threshold: u32, reset_timeout: Duration) -> Self {
+        self.circuit_breakers = Arc::new(CircuitBreakerRegistry::new(failure_threshold, reset_timeout));
+        self
+    }
+
+    /// 熔断器集合
+    pub fn circuit_breakers(&self) -> &Arc<CircuitBreakerRegistry> {
+        &self.circuit_breakers
+    }
+
+    /// 按拓扑顺序执行 DAG 运行中所有就绪任务
+    ///
+    /// 已完成的任务直接跳过；每个任务结束后写入检查点。
+    /// 返回本次调用中执行的任务结果。
+    pub async fn execute_run(&self, run: &mut DagRun) -> Result<Vec<ExecutionResult>> {
+        let mut timeline = self
+            .load_checkpoint(&run.run_id)?
+            .map(|checkpoint| checkpoint.timeline)
+            .unwrap_or_default();
+        let mut results = Vec::new();
+
+        loop {
+            let mut ready = run.dag.get_ready_tasks();
+            if ready.is_empty() {
+                break;
+            }
+            ready.sort();
+
+            for task_id in ready {
+                run.dag
+                    .mark_running(task_id.clone())
+                    .map_err(|e| CisError::scheduler(e.to_string()))?;
+
+                let result = self.execute(Self::task_for_node(run, &task_id)).await?;
+                let marked = if result.is_success() {
+                    run.dag.mark_completed(task_id.clone())
+                } else {
+                    run.dag.mark_failed(task_id.clone())
+                };
+                marked.map_err(|e| CisError::scheduler(e.to_string()))?;
+                run.update_status();
+
+                timeline.push(CheckpointEntry {
+                    result: result.clone(),
+                    finished_at: Utc::now(),
+                });
+                self.save_checkpoint(run, &timeline)?;
+                results.push(result);
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// 从最近的检查点恢复 DAG 运行
+    ///
+    /// 返回的 `DagRun` 中已完成任务保持完成状态，可直接传给
+    /// [`SyncExecutor::execute_run`] 继续执行。
+    pub fn resume(&self, run_id: &str) -> Result<DagRun> {
+        let checkpoint = self
+            .load_checkpoint(run_id)?
+            .ok_or_else(|| CisError::not_found(format!("No checkpoint for run {}", run_id)))?;
+
+        tracing::info!(
+            run_id = %run_id,
+            completed = checkpoint.timeline.len(),
+            "Resuming DAG run from checkpoint"
+        );
+        Ok(checkpoint.run)
+    }
+
+    /// 加载运行检查点（未启用检查点时返回 `None`）
+    pub fn load_checkpoint(&self, run_id: &str) -> Result<Option<DagCheckpoint>> {
+        let Some(checkpoints) = &self.checkpoints else {
+            return Ok(None);
+        };
+
+        let json = checkpoints
+            .lock()
+            .map_err(|_| CisError::internal_error("checkpoint store lock poisoned"))?
+            .load_checkpoint(run_id)?;
+
+        json.map(|json| {
+            serde_json::from_str(&json)
+                .map_err(|e| CisError::scheduler(format!("Invalid checkpoint for run {}: {}", run_id, e)))
+        })
+        .transpose()
+    }
+
+    /// 写入运行检查点
+    fn save_checkpoint(&self, run: &DagRun, timeline: &[CheckpointEntry]) -> Result<()> {
+        let Some(checkpoints) = &self.checkpoints else {
+            return Ok(());
+        };
+
+        let checkpoint = DagCheckpoint {
+            run: run.clone(),
+            timeline: timeline.to_vec(),
+        };
+        let json = serde_json::to_string(&checkpoint)
+            .map_err(|e| CisError::scheduler(format!("Failed to serialize checkpoint: {}", e)))?;
+
+        checkpoints
+            .lock()
+            .map_err(|_| CisError::internal_error("checkpoint store lock poisoned"))?
+            .save_checkpoint(&run.run_id, &json)
+    }
+
+    /// 由 DAG 节点构造待执行任务，命令放在 `description` 中
+    fn task_for_node(run: &DagRun, task_id: &str) -> Task {
+        let mut task = Task::new(task_id.to_string(), task_id.to_string(), run.run_id.clone());
+        task.description = run.task_commands.get(task_id).cloned();
+        task.dependencies = run.dag.get_task_dependencies(task_id);
+        task
+    }
+
     /// 更新统计信息
     async fn update_stats(&self, duration_secs: f64, success: bool) {
         let mut stats = self.stats.lock().await;
@@ -123,6 +310,36 @@ impl SyncExecutor {
     ///
     /// TODO: 集成 skill/agent 执行逻辑
     async fn execute_task_impl(&self, task: &Task) -> Result<serde_json::Value> {
+        let Some(endpoint) = service_endpoint(task) else {
+            return self.run_task(task).await;
+        };
+
+        if !self.circuit_breakers.try_acquire(&endpoint) {
+            tracing::warn!(task_id = %task.id, endpoint = %endpoint, "Circuit open, skipping task");
+            return Err(CisError::circuit_open(endpoint));
+        }
+
+        let result = self.run_task(task).await;
+        if result.is_ok() {
+            self.circuit_breakers.record_success(&endpoint);
+        } else {
+            self.circuit_breakers.record_failure(&endpoint);
+        }
+        result
+    }
+
+    /// 调用处理函数或 skill 执行任务
+    async fn run_task(&self, task: &Task) -> Result<serde_json::Value> {
+        if let Some(handler) = &self.handler {
+            let handler = handler.clone();
+            let task = task.clone();
+            return match tokio::task::spawn_blocking(move || handler(&task)).await {
+                Ok(result) => result,
+                Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+                Err(e) => Err(CisError::execution(format!("Task handler cancelled: {}", e))),
+            };
+        }
+
         // 如果任务指定了 skill，调用 skill 执行
         if let Some(skill_id) = &task.skill {
             tracing::debug!(skill_id = %skill_id, "Executing skill");
@@ -197,6 +414,125 @@ mod tests {
         assert!(results.iter().all(|r| r.is_success()));
     }
 
+    #[tokio::test]
+    async fn test_resume_skips_completed_tasks() {
+        use crate::scheduler::{DagNodeStatus, DagRunStatus, TaskDag};
+
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("checkpoints.db");
+        let db_path = db_path.to_str().unwrap();
+
+        let mut dag = TaskDag::new();
+        dag.add_node("step1".to_string(), vec![]).unwrap();
+        dag.add_node("step2".to_string(), vec!["step1".to_string()]).unwrap();
+        dag.add_node("step3".to_string(), vec!["step2".to_string()]).unwrap();
+        dag.initialize();
+        let run = DagRun::new(dag);
+        let run_id = run.run_id.clone();
+
+        let executed = Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let handler = |crash_on: Option<&'static str>| -> TaskHandler {
+            let executed = executed.clone();
+            Arc::new(move |task: &Task| {
+                if crash_on == Some(task.id.as_str()) {
+                    panic!("simulated crash before {}", task.id);
+                }
+                executed.lock().unwrap().push(task.id.clone());
+                Ok(serde_json::json!({ "task": task.id }))
+            })
+        };
+
+        // 第一次执行在 step1 完成后崩溃
+        let executor = SyncExecutor::new()
+            .with_handler(handler(Some("step2")))
+            .with_checkpoints(DagPersistence::new(db_path).unwrap());
+        let crashed = tokio::spawn(async move {
+            let mut run = run;
+            executor.execute_run(&mut run).await
+        })
+        .await;
+        assert!(crashed.unwrap_err().is_panic());
+        assert_eq!(*executed.lock().unwrap(), vec!["step1"]);
+
+        // 新执行器从检查点恢复
+        let executor = SyncExecutor::new()
+            .with_handler(handler(None))
+            .with_checkpoints(DagPersistence::new(db_path).unwrap());
+        let mut run = executor.resume(&run_id).unwrap();
+        assert_eq!(run.dag.get_node_status("step1"), Some(DagNodeStatus::Completed));
+
+        let results = executor.execute_run(&mut run).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(*executed.lock().unwrap(), vec!["step1", "step2", "step3"]);
+        assert_eq!(run.status, DagRunStatus::Completed);
+
+        let checkpoint = executor.load_checkpoint(&run_id).unwrap().unwrap();
+        assert_eq!(checkpoint.timeline.len(), 3);
+        assert_eq!(checkpoint.output("step1"), Some(&serde_json::json!({ "task": "step1" })));
+    }
+
+    #[test]
+    fn test_resume_without_checkpoint() {
+        let executor = SyncExecutor::new();
+        assert!(executor.resume("missing").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_handler_runs_off_runtime_thread() {
+        let runtime_thread = std::thread::current().id();
+        let executor = SyncExecutor::new().with_handler(Arc::new(move |_task: &Task| {
+            assert_ne!(std::thread::current().id(), runtime_thread);
+            Ok(serde_json::json!({}))
+        }));
+
+        let result = executor.execute(create_test_task("blocking")).await.unwrap();
+        assert!(!result.is_failure());
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_short_circuits_failing_endpoint() {
+        use super::super::CircuitState;
+
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = calls.clone();
+        let executor = SyncExecutor::new()
+            .with_handler(Arc::new(move |task: &Task| {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                if task.id.starts_with("fail") {
+                    Err(CisError::network("connection refused"))
+                } else {
+                    Ok(serde_json::json!({}))
+                }
+            }))
+            .with_circuit_breaker(2, Duration::from_millis(30));
+        let endpoint = "https://api.example.com";
+
+        let with_env = |id: &str| {
+            let mut task = create_test_task(id);
+            task.skill_params =
+                Some(serde_json::json!({ "env": { "API_URL": "https://api.example.com/v1" } }));
+            task
+        };
+
+        for id in ["fail-1", "fail-2"] {
+            assert!(executor.execute(with_env(id)).await.unwrap().is_failure());
+        }
+        assert_eq!(executor.circuit_breakers().state(endpoint), CircuitState::Open);
+
+        // 熔断期间不调用处理函数
+        let result = executor.execute(with_env("ok-1")).await.unwrap();
+        assert!(result.error.unwrap().contains("Circuit open"));
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // 未声明端点的任务不受影响
+        assert!(executor.execute(create_test_task("plain")).await.unwrap().is_success());
+
+        // 恢复时间后探测成功，熔断器关闭
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        assert!(executor.execute(with_env("ok-2")).await.unwrap().is_success());
+        assert_eq!(executor.circuit_breakers().state(endpoint), CircuitState::Closed);
+    }
+
     #[tokio::test]
     async fn test_executor_stats() {
         let executor = SyncExecutor::new();
@@ -0,0 +1,190 @@
+//! # Fair-Share Scheduling
+//!
+//! Prevents a single scope (e.g. one project submitting many DAGs) from
+//! monopolizing all worker slots.
+//!
+//! Ready tasks are grouped by the [`DagScope`] of their run. Each scope may
+//! use at most `max_share` of the worker slots within the current fairness
+//! window; once a scope reaches its share, its tasks are only dispatched after
+//! every under-served scope has been given a slot. Slots are never left idle
+//! just to enforce fairness.
+//!
+//! Usage counts are reset every `reset_interval` so that a scope that was busy
+//! earlier is not penalized forever.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::scheduler::DagScope;
+
+/// Default maximum share of worker slots per scope
+pub const DEFAULT_MAX_SHARE: f64 = 0.5;
+
+/// Default fairness window length in seconds
+pub const DEFAULT_FAIRNESS_RESET_INTERVAL_SECS: u64 = 60;
+
+/// Fair-share slot allocator across DAG scopes
+#[derive(Debug, Clone)]
+pub struct FairShareScheduler {
+    /// Maximum fraction of worker slots a single scope may use (0.0 - 1.0]
+    max_share: f64,
+    /// Fairness window length
+    reset_interval: Duration,
+    /// Slots granted per scope in the current window (scope key -> count)
+    scope_usage: HashMap<String, usize>,
+    /// Start of the current window
+    window_start: Instant,
+}
+
+impl FairShareScheduler {
+    /// Create a fair-share scheduler
+    ///
+    /// `max_share` is clamped to `(0.0, 1.0]`; non-finite or non-positive
+    /// values fall back to [`DEFAULT_MAX_SHARE`].
+    pub fn new(max_share: f64, reset_interval_secs: u64) -> Self {
+        let max_share = if max_share.is_finite() && max_share > 0.0 {
+            max_share.min(1.0)
+        } else {
+            DEFAULT_MAX_SHARE
+        };
+
+        Self {
+            max_share,
+            reset_interval: Duration::from_secs(reset_interval_secs),
+            scope_usage: HashMap::new(),
+            window_start: Instant::now(),
+        }
+    }
+
+    /// Maximum share of worker slots per scope
+    pub fn max_share(&self) -> f64 {
+        self.max_share
+    }
+
+    /// Slots granted per scope in the current window
+    pub fn scope_usage(&self) -> &HashMap<String, usize> {
+        &self.scope_usage
+    }
+
+    /// Number of slots a single scope may use out of `total_slots` (at least 1)
+    pub fn scope_limit(&self, total_slots: usize) -> usize {
+        ((total_slots as f64 * self.max_share).floor() as usize).max(1)
+    }
+
+    /// Reset usage counts if the fairness window has elapsed
+    fn maybe_reset_window(&mut self) {
+        if self.window_start.elapsed() >= self.reset_interval {
+            self.scope_usage.clear();
+            self.window_start = Instant::now();
+        }
+    }
+
+    /// Pick up to `available_slots` candidates in fair-share order
+    ///
+    /// `candidates` are `(scope, item)` pairs in their natural priority order;
+    /// order is preserved within a scope. Each pick goes to the scope with the
+    /// lowest usage that is still under its limit, falling back to over-limit
+    /// scopes only when no under-served scope has work left. Usage is recorded
+    /// for every returned item.
+    pub fn select<T>(
+        &mut self,
+        candidates: Vec<(DagScope, T)>,
+        available_slots: usize,
+        total_slots: usize,
+    ) -> Vec<T> {
+        self.maybe_reset_window();
+
+        let limit = self.scope_limit(total_slots);
+
+        // Group by scope, keeping first-seen scope order for tie-breaking
+        let mut queues: Vec<(String, VecDeque<T>)> = Vec::new();
+        for (scope, item) in candidates {
+            let key = scope.share_key();
+            match queues.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, queue)) => queue.push_back(item),
+                None => queues.push((key, VecDeque::from([item]))),
+            }
+        }
+
+        let mut selected = Vec::with_capacity(available_slots);
+        while selected.len() < available_slots {
+            let usage = |key: &str| self.scope_usage.get(key).copied().unwrap_or(0);
+            let next = queues
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, queue))| !queue.is_empty())
+                // Under-served scopes first, then the least-used scope
+                .min_by_key(|(i, (key, _))| (usage(key) >= limit, usage(key), *i))
+                .map(|(i, _)| i);
+
+            let Some(index) = next else {
+                break;
+            };
+            let (key, queue) = &mut queues[index];
+            if let Some(item) = queue.pop_front() {
+                *self.scope_usage.entry(key.clone()).or_insert(0) += 1;
+                selected.push(item);
+            }
+        }
+
+        selected
+    }
+}
+
+impl Default for FairShareScheduler {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_SHARE, DEFAULT_FAIRNESS_RESET_INTERVAL_SECS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(id: &str) -> DagScope {
+        DagScope::Project {
+            project_id: id.to_string(),
+            force_new: false,
+        }
+    }
+
+    #[test]
+    fn test_busy_scope_limited_to_share() {
+        let mut fairness = FairShareScheduler::new(0.5, 60);
+        let mut candidates: Vec<_> = (0..6).map(|i| (project("a"), format!("a{}", i))).collect();
+        candidates.push((project("b"), "b0".to_string()));
+        candidates.push((DagScope::Global, "g0".to_string()));
+
+        // 4 slots, limit 2 per scope: project a cannot take more than half
+        let picked = fairness.select(candidates, 4, 4);
+        assert_eq!(picked, vec!["a0", "b0", "g0", "a1"]);
+        assert_eq!(fairness.scope_usage()["project:a"], 2);
+
+        // In the same window, a is already at its share and goes last
+        let candidates = vec![
+            (project("a"), "a2".to_string()),
+            (project("b"), "b1".to_string()),
+        ];
+        assert_eq!(fairness.select(candidates, 1, 4), vec!["b1"]);
+    }
+
+    #[test]
+    fn test_over_share_scope_uses_idle_slots() {
+        let mut fairness = FairShareScheduler::new(0.5, 60);
+        let candidates: Vec<_> = (0..4).map(|i| (project("a"), i)).collect();
+        assert_eq!(fairness.select(candidates, 4, 4), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_window_reset() {
+        let mut fairness = FairShareScheduler::new(0.5, 0);
+        fairness.select(vec![(project("a"), 0), (project("a"), 1)], 2, 2);
+        // Zero-length window: usage from the previous call is discarded
+        fairness.select(vec![(project("b"), 2)], 1, 2);
+        assert_eq!(fairness.scope_usage().get("project:a"), None);
+        assert_eq!(fairness.scope_usage()["project:b"], 1);
+
+        assert_eq!(FairShareScheduler::new(f64::NAN, 60).max_share(), DEFAULT_MAX_SHARE);
+        assert_eq!(FairShareScheduler::new(0.25, 60).scope_limit(2), 1);
+    }
+}
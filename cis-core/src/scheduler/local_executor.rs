@@ -7,18 +7,110 @@
 //! - Type: 每 dag_type 独立 worker
 //!
 //! Worker 通信：通过 Matrix Room 发送 Task
+//!
+//! 同时存活的 Worker 数受并发上限约束，初始值读取自 `executor.toml`，
+//! 可通过 [`LocalExecutor::set_concurrency`] 在运行时调整：扩容立即放行等待中的
+//! Worker 创建，缩容时终止多余的空闲 Worker，忙碌的 Worker 在任务完成后再回收。
+//! 槽位用尽时，新 Worker 会回收其他作用域的空闲 Worker。
+//!
+//! 任务完成通过 Worker Room 中的 `cis.task_result` 事件感知。
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
+use std::time::Duration;
 
+use serde::{Deserialize, Serialize};
 use tokio::process::{Child, Command};
-use tokio::sync::Mutex;
+use tokio::sync::{watch, Mutex};
 use tracing::{debug, error, info, warn};
 
 use crate::error::{CisError, Result};
+use crate::event_bus::EventBusRef;
+use crate::events::{EventWrapper, SystemEvent};
 use crate::matrix::store::MatrixStore;
 use crate::scheduler::{DagScope, DagSpec, DagTaskSpec};
+use crate::storage::paths::Paths;
+
+/// 配置文件名（位于配置目录下）
+pub const EXECUTOR_CONFIG_FILE: &str = "executor.toml";
+
+/// 等待 Worker 槽位时检查 Worker 存活的间隔
+const SLOT_RECHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 每次从 Worker Room 拉取的最大事件数
+const RESULT_BATCH_SIZE: usize = 100;
+
+/// 本地执行器配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalExecutorConfig {
+    /// 同时存活的 Worker 数上限
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    /// `concurrency` 允许设置的最大值
+    #[serde(default = "default_max_concurrency_limit")]
+    pub max_concurrency_limit: usize,
+}
+
+fn default_concurrency() -> usize {
+    4
+}
+
+fn default_max_concurrency_limit() -> usize {
+    32
+}
+
+impl Default for LocalExecutorConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: default_concurrency(),
+            max_concurrency_limit: default_max_concurrency_limit(),
+        }
+    }
+}
+
+impl LocalExecutorConfig {
+    /// 默认配置文件路径
+    pub fn default_path() -> PathBuf {
+        Paths::config_dir().join(EXECUTOR_CONFIG_FILE)
+    }
+
+    /// 从文件加载，不存在时返回默认配置
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        let config: Self = toml::from_str(&content)
+            .map_err(|e| CisError::config_parse_error(&path.display().to_string(), e.to_string()))?;
+        config.validate_concurrency(config.concurrency)?;
+        Ok(config)
+    }
+
+    /// 保存到文件
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| CisError::configuration(format!("Failed to serialize executor config: {}", e)))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// 检查并发数是否在 `1..=max_concurrency_limit` 范围内
+    pub fn validate_concurrency(&self, n: usize) -> Result<()> {
+        if n == 0 || n > self.max_concurrency_limit {
+            return Err(CisError::invalid_input(format!(
+                "Concurrency must be between 1 and {} (got {})",
+                self.max_concurrency_limit, n
+            )));
+        }
+        Ok(())
+    }
+}
 
 /// Worker 进程信息
 #[derive(Debug)]
@@ -35,6 +127,8 @@ pub struct WorkerInfo {
     pub room_id: String,
     /// 活跃任务数
     pub active_tasks: usize,
+    /// 已处理到的任务结果事件时间戳（毫秒）
+    pub results_since: i64,
 }
 
 impl WorkerInfo {
@@ -73,17 +167,37 @@ pub struct LocalExecutor {
     default_room: String,
     /// Matrix Store (用于事件持久化)
     matrix_store: Option<Arc<MatrixStore>>,
+    /// 执行器配置
+    config: LocalExecutorConfig,
+    /// 配置文件路径（显式传入配置时为空）
+    config_path: Option<PathBuf>,
+    /// 当前并发上限，可从任意线程更新
+    concurrency: watch::Sender<usize>,
+    /// 事件总线（发布并发变更事件）
+    event_bus: Option<EventBusRef>,
 }
 
 impl LocalExecutor {
     /// 创建新的本地执行器
+    ///
+    /// 并发配置从 [`LocalExecutorConfig::default_path`] 加载，
+    /// 文件无效时使用默认配置。
     pub fn new(node_id: String, worker_binary: String, default_room: String) -> Self {
+        let config_path = LocalExecutorConfig::default_path();
+        let config = LocalExecutorConfig::load(&config_path).unwrap_or_else(|e| {
+            warn!("Ignoring invalid executor config {}: {}", config_path.display(), e);
+            LocalExecutorConfig::default()
+        });
         Self {
             workers: Arc::new(Mutex::new(HashMap::new())),
             node_id,
             worker_binary,
             default_room,
             matrix_store: None,
+            concurrency: watch::Sender::new(config.concurrency),
+            config,
+            config_path: Some(config_path),
+            event_bus: None,
         }
     }
     
@@ -100,6 +214,167 @@ impl LocalExecutor {
             worker_binary,
             default_room,
             matrix_store: Some(matrix_store),
+            ..Self::new(String::new(), String::new(), String::new())
+        }
+    }
+
+    /// 设置执行器配置（并发上限取 `config.concurrency`）
+    ///
+    /// 显式配置优先，不再跟随配置文件变化。
+    pub fn with_config(mut self, config: LocalExecutorConfig) -> Self {
+        self.concurrency.send_replace(config.concurrency);
+        self.config = config;
+        self.config_path = None;
+        self
+    }
+
+    /// 设置事件总线
+    pub fn with_event_bus(mut self, event_bus: EventBusRef) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// 当前并发上限
+    pub fn concurrency(&self) -> usize {
+        *self.concurrency.borrow()
+    }
+
+    /// 运行时调整并发上限
+    ///
+    /// `n` 必须在 `1..=max_concurrency_limit` 范围内。扩容会立即唤醒等待槽位的
+    /// Worker 创建；缩容会终止多余的空闲 Worker，忙碌的 Worker 在
+    /// [`LocalExecutor::complete_task`] 后回收。可在任意线程调用，
+    /// 不在 tokio 运行时中调用时，回收和事件推迟到下次调度。
+    pub fn set_concurrency(&self, n: usize) -> Result<()> {
+        self.config.validate_concurrency(n)?;
+
+        let previous = self.concurrency.send_replace(n);
+        if previous == n {
+            return Ok(());
+        }
+        info!("Worker concurrency changed: {} -> {}", previous, n);
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return Ok(());
+        };
+
+        let workers = self.workers.clone();
+        let event_bus = self.event_bus.clone();
+        let node_id = self.node_id.clone();
+        runtime.spawn(async move {
+            if n < previous {
+                drain_excess_workers(&mut *workers.lock().await, n).await;
+            }
+
+            if let Some(bus) = event_bus {
+                let event = SystemEvent::info(
+                    "local_executor",
+                    format!("Worker concurrency changed from {} to {}", previous, n),
+                    "local_executor",
+                )
+                .with_details(serde_json::json!({
+                    "node_id": node_id,
+                    "previous": previous,
+                    "concurrency": n,
+                }));
+                if let Err(e) = bus.publish(EventWrapper::System(event)).await {
+                    warn!("Failed to publish concurrency change event: {}", e);
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// 标记 Worker 完成一个任务
+    ///
+    /// 并发上限已缩小时，空闲下来的多余 Worker 会被终止。
+    pub async fn complete_task(&self, worker_id: &str) {
+        let mut workers = self.workers.lock().await;
+        if let Some(worker) = workers.get_mut(worker_id) {
+            worker.active_tasks = worker.active_tasks.saturating_sub(1);
+        }
+
+        drain_excess_workers(&mut workers, self.concurrency()).await;
+    }
+
+    /// 重新读取配置文件，应用 `cis dag set-concurrency` 持久化的并发上限
+    fn reload_config(&self) {
+        let Some(ref path) = self.config_path else {
+            return;
+        };
+
+        match LocalExecutorConfig::load(path) {
+            Ok(config) if config.concurrency != self.concurrency() => {
+                if let Err(e) = self.set_concurrency(config.concurrency) {
+                    warn!("Ignoring concurrency from {}: {}", path.display(), e);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Failed to reload executor config {}: {}", path.display(), e),
+        }
+    }
+
+    /// 从 Worker Room 拉取任务结果事件，对每个结果调用 [`LocalExecutor::complete_task`]
+    async fn reap_completed_tasks(&self) {
+        let Some(ref store) = self.matrix_store else {
+            return;
+        };
+
+        let mut completed = Vec::new();
+        {
+            let mut workers = self.workers.lock().await;
+            for worker in workers.values_mut() {
+                let messages = match store.get_room_messages(
+                    &worker.room_id,
+                    worker.results_since,
+                    RESULT_BATCH_SIZE,
+                ) {
+                    Ok(messages) => messages,
+                    Err(e) => {
+                        warn!("Failed to read results for worker {}: {}", worker.worker_id, e);
+                        continue;
+                    }
+                };
+
+                for message in messages {
+                    worker.results_since = worker.results_since.max(message.origin_server_ts);
+                    if is_task_result(&message.content) {
+                        completed.push(worker.worker_id.clone());
+                    }
+                }
+            }
+        }
+
+        for worker_id in completed {
+            debug!("Worker {} reported a task result", worker_id);
+            self.complete_task(&worker_id).await;
+        }
+    }
+
+    /// 等待存活 Worker 数低于并发上限
+    ///
+    /// 槽位用尽时回收其他作用域的空闲 Worker，为新 Worker 腾出位置。
+    async fn wait_for_slot(&self) {
+        let mut concurrency = self.concurrency.subscribe();
+
+        loop {
+            let limit = *concurrency.borrow_and_update();
+            self.reap_completed_tasks().await;
+            {
+                let mut workers = self.workers.lock().await;
+                drain_excess_workers(&mut workers, limit.saturating_sub(1)).await;
+                if workers.len() < limit {
+                    return;
+                }
+                debug!("All {} worker slots in use, waiting", limit);
+            }
+
+            // 扩容时立即唤醒，否则定期检查是否有 Worker 退出
+            tokio::select! {
+                _ = concurrency.changed() => {}
+                _ = tokio::time::sleep(SLOT_RECHECK_INTERVAL) => {}
+            }
         }
     }
     
@@ -118,6 +393,9 @@ impl LocalExecutor {
             spec.dag_id, worker_id, run_id
         );
         
+        self.reload_config();
+        self.reap_completed_tasks().await;
+
         // 1. 确保 Worker 存在
         let room_id = self.ensure_worker(&worker_id, &spec.scope).await?;
         
@@ -149,6 +427,7 @@ impl LocalExecutor {
         
         // 创建新 Worker
         drop(workers); // 释放锁，避免持有锁期间启动进程
+        self.wait_for_slot().await;
         let room_id = self.spawn_worker(worker_id, scope).await?;
         Ok(room_id)
     }
@@ -194,6 +473,7 @@ impl LocalExecutor {
                     started_at: chrono::Utc::now(),
                     room_id: room_id.clone(),
                     active_tasks: 0,
+                    results_since: chrono::Utc::now().timestamp_millis(),
                 };
                 
                 let mut workers = self.workers.lock().await;
@@ -355,6 +635,40 @@ impl LocalExecutor {
     }
 }
 
+/// 任务结果事件内容是否携带 `cis.task_result`
+fn is_task_result(content: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(content)
+        .map(|value| value.get("cis.task_result").is_some())
+        .unwrap_or(false)
+}
+
+/// 终止超出上限的空闲 Worker（按启动时间从早到晚）
+async fn drain_excess_workers(workers: &mut HashMap<String, WorkerInfo>, limit: usize) {
+    workers.retain(|_, worker| worker.is_alive());
+
+    let excess = workers.len().saturating_sub(limit);
+    if excess == 0 {
+        return;
+    }
+
+    let mut idle: Vec<_> = workers
+        .values()
+        .filter(|worker| worker.active_tasks == 0)
+        .map(|worker| (worker.started_at, worker.worker_id.clone()))
+        .collect();
+    idle.sort();
+
+    for (_, worker_id) in idle.into_iter().take(excess) {
+        if let Some(mut worker) = workers.remove(&worker_id) {
+            if let Err(e) = worker.kill().await {
+                warn!("Failed to stop excess worker {}: {}", worker_id, e);
+            } else {
+                info!("Stopped idle worker {} (worker limit {})", worker_id, limit);
+            }
+        }
+    }
+}
+
 /// Worker 摘要信息
 #[derive(Debug, Clone)]
 pub struct WorkerSummary {
@@ -408,4 +722,167 @@ mod tests {
         assert_eq!(stats.total_workers, 0);
         assert_eq!(stats.node_id, "test-node");
     }
+
+    #[cfg(unix)]
+    fn spawn_worker_info(worker_id: &str, active_tasks: usize) -> WorkerInfo {
+        WorkerInfo {
+            worker_id: worker_id.to_string(),
+            scope: DagScope::Global,
+            process: Command::new("sleep").arg("30").kill_on_drop(true).spawn().unwrap(),
+            started_at: chrono::Utc::now(),
+            room_id: format!("!{}:localhost", worker_id),
+            active_tasks,
+            results_since: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_concurrency_validation() {
+        use crate::event_bus::{EventBus, MemoryEventBus};
+
+        let bus = Arc::new(MemoryEventBus::new());
+        let executor = LocalExecutor::new(
+            "test-node".to_string(),
+            "/usr/local/bin/cis-node".to_string(),
+            "!test-room:localhost".to_string(),
+        )
+        .with_config(LocalExecutorConfig {
+            concurrency: 2,
+            max_concurrency_limit: 8,
+        })
+        .with_event_bus(bus.clone());
+
+        assert_eq!(executor.concurrency(), 2);
+        assert!(executor.set_concurrency(0).is_err());
+        assert!(executor.set_concurrency(9).is_err());
+        assert_eq!(executor.concurrency(), 2);
+
+        executor.set_concurrency(6).unwrap();
+        assert_eq!(executor.concurrency(), 6);
+
+        let mut published = Vec::new();
+        for _ in 0..50 {
+            published = bus.get_history("system.event", 10).await.unwrap();
+            if !published.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(published.len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_decrease_concurrency_drains_idle_workers() {
+        let executor = LocalExecutor::new(
+            "test-node".to_string(),
+            "/usr/local/bin/cis-node".to_string(),
+            "!test-room:localhost".to_string(),
+        );
+        {
+            let mut workers = executor.workers.lock().await;
+            workers.insert("idle".to_string(), spawn_worker_info("idle", 0));
+            workers.insert("busy".to_string(), spawn_worker_info("busy", 1));
+        }
+
+        drain_excess_workers(&mut *executor.workers.lock().await, 1).await;
+        let remaining: Vec<_> = executor.workers.lock().await.keys().cloned().collect();
+        assert_eq!(remaining, vec!["busy".to_string()]);
+
+        // 忙碌的 Worker 完成任务后，超出上限时被回收
+        executor.concurrency.send_replace(0);
+        executor.complete_task("busy").await;
+        assert!(executor.workers.lock().await.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_wait_for_slot_evicts_idle_worker_of_other_scope() {
+        let executor = LocalExecutor::new(
+            "test-node".to_string(),
+            "/usr/local/bin/cis-node".to_string(),
+            "!test-room:localhost".to_string(),
+        )
+        .with_config(LocalExecutorConfig {
+            concurrency: 1,
+            max_concurrency_limit: 8,
+        });
+        executor
+            .workers
+            .lock()
+            .await
+            .insert("busy".to_string(), spawn_worker_info("busy", 1));
+
+        // 忙碌的 Worker 不会被回收
+        let waited = tokio::time::timeout(Duration::from_millis(200), executor.wait_for_slot()).await;
+        assert!(waited.is_err());
+
+        executor.workers.lock().await.get_mut("busy").unwrap().active_tasks = 0;
+        tokio::time::timeout(Duration::from_secs(2), executor.wait_for_slot())
+            .await
+            .unwrap();
+        assert!(executor.workers.lock().await.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_task_results_reclaim_busy_workers() {
+        let store = Arc::new(MatrixStore::open_in_memory().unwrap());
+        let executor = LocalExecutor::with_matrix_store(
+            "test-node".to_string(),
+            "/usr/local/bin/cis-node".to_string(),
+            "!test-room:localhost".to_string(),
+            store.clone(),
+        )
+        .with_config(LocalExecutorConfig {
+            concurrency: 2,
+            max_concurrency_limit: 8,
+        });
+        {
+            let mut workers = executor.workers.lock().await;
+            workers.insert("a".to_string(), spawn_worker_info("a", 1));
+            workers.insert("b".to_string(), spawn_worker_info("b", 1));
+        }
+
+        executor.set_concurrency(1).unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(executor.workers.lock().await.len(), 2);
+
+        let content = serde_json::json!({
+            "msgtype": "m.text",
+            "body": "Task t1 completed: Success",
+            "cis.task_result": { "task_id": "t1", "status": "success" },
+        });
+        store
+            .save_event(
+                "!a:localhost",
+                "$result-1",
+                "@a:localhost",
+                "m.room.message",
+                &content.to_string(),
+                1,
+                None,
+                None,
+            )
+            .unwrap();
+
+        executor.reap_completed_tasks().await;
+        let remaining: Vec<_> = executor.workers.lock().await.keys().cloned().collect();
+        assert_eq!(remaining, vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn test_executor_config_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(EXECUTOR_CONFIG_FILE);
+
+        assert_eq!(LocalExecutorConfig::load(&path).unwrap(), LocalExecutorConfig::default());
+
+        let config = LocalExecutorConfig {
+            concurrency: 6,
+            max_concurrency_limit: 16,
+        };
+        config.save(&path).unwrap();
+        assert_eq!(LocalExecutorConfig::load(&path).unwrap(), config);
+    }
 }
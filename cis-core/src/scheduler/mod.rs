@@ -16,6 +16,7 @@
 //! adapted only for CIS crate naming.
 
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -62,13 +63,20 @@ pub mod persistence;
 pub mod events;
 pub mod error;
 pub mod node_selector;  // P1-10: Heterogeneous task routing
+pub mod fairness;
+pub mod template;
 
 // Re-export new module types
 pub use core::{DagScheduler, SchedulerDagError, SchedulerDagNode, DagStats, SchedulerCore, TaskQueue, TaskQueueItem, TaskQueueError, TaskQueueStats};
 pub use execution::{Executor, ExecutionResult, ExecutorStats, SyncExecutor, ParallelExecutor};
 pub use events::{SchedulerEvent, SchedulerEventType, EventListener, EventRegistry, LoggingEventListener};
 pub use persistence::{Persistence, SqlitePersistence, MemoryPersistence};
-pub use node_selector::{NodeSelector, NodeInfo, NodeResources, NodeSelectorFilter};  // P1-10
+pub use node_selector::{
+    LocalLoad, NodeInfo, NodeResources, NodeSelector, NodeSelectorFilter, ResourceThreshold,
+    SelectionStrategy,
+};  // P1-10
+pub use fairness::FairShareScheduler;
+pub use template::{DagTemplate, TemplateParameter};
 // error module exports Result type
 pub use error::Result as SchedulerResult;
 
@@ -84,7 +92,7 @@ pub mod skill_executor_unified;
 pub mod todo_monitor;
 
 // Re-export old persistence types
-pub use persistence_old::{DagPersistence, TaskExecution, TaskExecutionStatus};
+pub use persistence_old::{DagPersistence, GcReport, OutputStream, TaskExecution, TaskExecutionStatus, TaskLevelChange, TaskOutput};
 
 // DAG definition unified module (added in v1.1.6)
 pub mod converters;
@@ -97,7 +105,7 @@ mod tests {
 }
 
 pub use event_driven::{EventDrivenConfig, EventDrivenScheduler, ExecutionSummary};
-pub use local_executor::{LocalExecutor, WorkerInfo, WorkerSummary, ExecutorStats as LocalExecutorStats};
+pub use local_executor::{LocalExecutor, LocalExecutorConfig, WorkerInfo, WorkerSummary, ExecutorStats as LocalExecutorStats};
 pub use multi_agent_executor::{
     MultiAgentDagExecutor, MultiAgentExecutorConfig, MultiAgentExecutionReport,
     TaskExecutionResult,
@@ -166,6 +174,10 @@ pub struct DagTask {
     #[serde(default = "default_keep_agent")]
     pub keep_agent: bool,
 
+    /// Cancel running sibling tasks when this task fails
+    #[serde(default)]
+    pub fail_fast: bool,
+
     /// Agent configuration (used when creating new Agent)
     #[serde(default)]
     pub agent_config: Option<AgentConfig>,
@@ -223,6 +235,10 @@ pub struct DagNode {
     #[serde(default)]
     pub keep_agent: bool,
 
+    /// Cancel running sibling tasks when this task fails
+    #[serde(default)]
+    pub fail_fast: bool,
+
     /// Agent configuration (used when creating new Agent)
     #[serde(default)]
     pub agent_config: Option<AgentConfig>,
@@ -245,6 +261,7 @@ impl DagNode {
             agent_runtime: None,
             reuse_agent: None,
             keep_agent: false,
+            fail_fast: false,
             agent_config: None,
             node_selector: None,  // P1-10
         }
@@ -274,6 +291,17 @@ impl DagNode {
     }
 }
 
+/// Result of [`TaskDag::critical_path`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CriticalPathResult {
+    /// Tasks on the critical path, in execution order
+    pub path: Vec<String>,
+    /// Length of the critical path (minimum possible DAG duration)
+    pub total_duration: Duration,
+    /// How long each task can be delayed without delaying the DAG
+    pub slack: HashMap<String, Duration>,
+}
+
 /// DAG graph structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskDag {
@@ -605,6 +633,15 @@ impl TaskDag {
         Ok(new_ready)
     }
 
+    /// Mark task as blocking debt (skip downstream until the debt is resolved)
+    pub fn mark_task_blocking(&mut self, task_id: &str) -> std::result::Result<Vec<String>, DagError> {
+        let skipped = self.mark_failed(task_id.to_string())?;
+        if let Some(node) = self.nodes.get_mut(task_id) {
+            node.status = DagNodeStatus::Debt(FailureType::Blocking);
+        }
+        Ok(skipped)
+    }
+
     /// Recursively mark dependent tasks as skipped
     fn mark_dependents_skipped(&mut self, task_id: &str, skipped: &mut Vec<String>) {
         // First mark current task as Skipped (if it's still Pending or Ready status)
@@ -720,6 +757,131 @@ impl TaskDag {
         Ok(levels)
     }
 
+    /// Critical path analysis (CPM)
+    ///
+    /// Runs a forward pass for earliest start/finish times and a backward pass
+    /// for latest start/finish times. Tasks without an entry in `durations`
+    /// are treated as taking zero time.
+    ///
+    /// # Returns
+    /// - `Ok(result)` - Critical path, its total duration and per-task slack
+    /// - `Err(DagError::CycleDetected)` - Circular dependencies exist
+    pub fn critical_path(
+        &self,
+        durations: &HashMap<String, Duration>,
+    ) -> Result<CriticalPathResult, DagError> {
+        self.validate()?;
+
+        let order = self.topological_order();
+        let duration_of = |id: &str| durations.get(id).copied().unwrap_or_default();
+
+        // Dependents derived from dependencies (node.dependents depends on insertion order)
+        let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+        for node in self.nodes.values() {
+            for dep in node.dependencies.iter().filter(|d| self.nodes.contains_key(*d)) {
+                dependents.entry(dep.as_str()).or_default().push(node.task_id.as_str());
+            }
+        }
+
+        // Forward pass: earliest start
+        let mut earliest_start: HashMap<&str, Duration> = HashMap::new();
+        let mut total_duration = Duration::ZERO;
+        for id in &order {
+            let node = &self.nodes[id];
+            let start = node
+                .dependencies
+                .iter()
+                .filter_map(|dep| earliest_start.get(dep.as_str()).map(|es| *es + duration_of(dep)))
+                .max()
+                .unwrap_or_default();
+            earliest_start.insert(id.as_str(), start);
+            total_duration = total_duration.max(start + duration_of(id));
+        }
+
+        // Backward pass: latest start
+        let mut latest_start: HashMap<&str, Duration> = HashMap::new();
+        for id in order.iter().rev() {
+            let finish = dependents
+                .get(id.as_str())
+                .and_then(|next| next.iter().filter_map(|n| latest_start.get(n).copied()).min())
+                .unwrap_or(total_duration);
+            latest_start.insert(id.as_str(), finish.saturating_sub(duration_of(id)));
+        }
+
+        let slack: HashMap<String, Duration> = order
+            .iter()
+            .map(|id| {
+                let id = id.as_str();
+                (id.to_string(), latest_start[id].saturating_sub(earliest_start[id]))
+            })
+            .collect();
+
+        // Walk critical tasks from the start, always following a zero-slack dependent
+        // that begins exactly when the current task finishes
+        let mut path = Vec::new();
+        let mut current = order.iter().map(String::as_str).find(|id| {
+            slack[*id].is_zero()
+                && earliest_start[*id].is_zero()
+                && (!duration_of(id).is_zero() || total_duration.is_zero())
+        });
+        while let Some(id) = current {
+            path.push(id.to_string());
+            let finish = earliest_start[id] + duration_of(id);
+            current = order.iter().map(String::as_str).find(|next| {
+                slack[*next].is_zero()
+                    && earliest_start[*next] == finish
+                    && dependents.get(id).is_some_and(|d| d.contains(next))
+            });
+        }
+
+        Ok(CriticalPathResult {
+            path,
+            total_duration,
+            slack,
+        })
+    }
+
+    /// Topological order over all nodes (ties broken by task ID)
+    ///
+    /// Assumes the graph has been validated; nodes on a cycle are omitted.
+    fn topological_order(&self) -> Vec<String> {
+        let mut in_degree: HashMap<&str, usize> = self
+            .nodes
+            .values()
+            .map(|node| {
+                let degree = node
+                    .dependencies
+                    .iter()
+                    .filter(|dep| self.nodes.contains_key(*dep))
+                    .count();
+                (node.task_id.as_str(), degree)
+            })
+            .collect();
+
+        let mut ready: std::collections::BTreeSet<&str> = in_degree
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(id, _)| *id)
+            .collect();
+
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(id) = ready.pop_first() {
+            order.push(id.to_string());
+            for node in self.nodes.values() {
+                if node.dependencies.iter().any(|dep| dep == id) {
+                    if let Some(degree) = in_degree.get_mut(node.task_id.as_str()) {
+                        *degree -= 1;
+                        if *degree == 0 {
+                            ready.insert(node.task_id.as_str());
+                        }
+                    }
+                }
+            }
+        }
+
+        order
+    }
+
     /// Get node status
     ///
     /// # Arguments
@@ -865,13 +1027,38 @@ impl TaskDag {
     }
 
     /// Resolve a debt
+    ///
+    /// With `resume_downstream`, the task is marked completed and all
+    /// transitively skipped descendants are reset to Pending, then
+    /// re-evaluated for readiness.
+    ///
+    /// # Returns
+    /// List of tasks that became Ready
     pub fn resolve_debt(&mut self, task_id: &str, resume_downstream: bool) -> Result<Vec<String>, DagError> {
         let node = self.nodes.get_mut(task_id).ok_or_else(|| DagError::NodeNotFound(task_id.to_string()))?;
         
         match node.status {
             DagNodeStatus::Debt(_) => {
                 if resume_downstream {
-                    self.mark_completed(task_id.to_string())
+                    let mut reset = Vec::new();
+                    let dependents = node.dependents.clone();
+                    for dependent_id in dependents {
+                        self.reset_skipped_descendants(&dependent_id, &mut reset)?;
+                    }
+                    
+                    let mut new_ready = self.mark_completed(task_id.to_string())?;
+                    for reset_id in reset {
+                        let ready = self.nodes.get(&reset_id).is_some_and(|n| {
+                            n.status == DagNodeStatus::Pending && self.check_dependencies_ready(n)
+                        });
+                        if ready {
+                            if let Some(node) = self.nodes.get_mut(&reset_id) {
+                                node.status = DagNodeStatus::Ready;
+                            }
+                            new_ready.push(reset_id);
+                        }
+                    }
+                    Ok(new_ready)
                 } else {
                     node.status = DagNodeStatus::Failed;
                     Ok(Vec::new())
@@ -882,6 +1069,22 @@ impl TaskDag {
             ))),
         }
     }
+
+    /// Recursively reset skipped descendants to Pending
+    fn reset_skipped_descendants(&mut self, task_id: &str, reset: &mut Vec<String>) -> Result<(), DagError> {
+        let dependents = match self.nodes.get(task_id) {
+            Some(node) if node.status == DagNodeStatus::Skipped => node.dependents.clone(),
+            _ => return Ok(()),
+        };
+        
+        self.reset_node(task_id)?;
+        reset.push(task_id.to_string());
+        
+        for dependent_id in dependents {
+            self.reset_skipped_descendants(&dependent_id, reset)?;
+        }
+        Ok(())
+    }
 }
 
 impl Default for TaskDag {
@@ -925,6 +1128,21 @@ pub enum DagScope {
         #[serde(default)]
         force_new: bool,
     },
+
+    /// Ephemeral scope - always a fresh worker, never reused (one-off scripts)
+    /// The worker is terminated when the run completes, or after
+    /// `cleanup_after_secs` without task activity
+    Ephemeral {
+        #[serde(default = "default_ephemeral_cleanup_secs")]
+        cleanup_after_secs: u64,
+    },
+}
+
+/// Default idle time before an ephemeral worker is cleaned up
+pub const DEFAULT_EPHEMERAL_CLEANUP_SECS: u64 = 300;
+
+fn default_ephemeral_cleanup_secs() -> u64 {
+    DEFAULT_EPHEMERAL_CLEANUP_SECS
 }
 
 impl DagScope {
@@ -935,14 +1153,23 @@ impl DagScope {
             Self::Project { force_new, .. } => *force_new,
             Self::User { force_new, .. } => *force_new,
             Self::Type { force_new, .. } => *force_new,
+            Self::Ephemeral { .. } => true, // Ephemeral never reuses
         }
     }
+
+    /// Check if this is an ephemeral scope
+    pub fn is_ephemeral(&self) -> bool {
+        matches!(self, Self::Ephemeral { .. })
+    }
     
     /// Get a unique worker key for this scope
     /// If force_new is true, includes a unique suffix
     pub fn worker_key(&self) -> String {
         let base = self.worker_id();
-        if self.force_new_worker() {
+        if self.is_ephemeral() {
+            // worker_id is already unique
+            base
+        } else if self.force_new_worker() {
             format!("{}-new-{}", base, uuid::Uuid::new_v4().to_string().split('-').next().unwrap())
         } else {
             base
@@ -956,15 +1183,37 @@ impl DagScope {
             DagScope::Project { project_id, .. } => format!("worker-project-{}", project_id),
             DagScope::User { user_id, .. } => format!("worker-user-{}", user_id),
             DagScope::Type { dag_type, .. } => format!("worker-type-{}", dag_type),
+            DagScope::Ephemeral { .. } => format!(
+                "worker-ephemeral-{}",
+                uuid::Uuid::new_v4().to_string().split('-').next().unwrap()
+            ),
         }
     }
-    
+
+    /// Stable key grouping runs of the same scope (used for fair-share scheduling)
+    ///
+    /// Unlike [`worker_id`](Self::worker_id), all ephemeral runs share one key.
+    pub fn share_key(&self) -> String {
+        match self {
+            DagScope::Global => "global".to_string(),
+            DagScope::Project { project_id, .. } => format!("project:{}", project_id),
+            DagScope::User { user_id, .. } => format!("user:{}", user_id),
+            DagScope::Type { dag_type, .. } => format!("type:{}", dag_type),
+            DagScope::Ephemeral { .. } => "ephemeral".to_string(),
+        }
+    }
+
     /// Infer scope from DAG content
     pub fn infer_from_dag(dag_id: &str, tasks: &[DagTaskSpec]) -> Self {
         // 1. Try to extract from dag_id naming convention
         // Format: proj-{id}-* or user-{id}-* or {type}-*
         let parts: Vec<&str> = dag_id.split('-').collect();
         if parts.len() >= 2 {
+            if parts[0] == "ephemeral" {
+                return DagScope::Ephemeral {
+                    cleanup_after_secs: DEFAULT_EPHEMERAL_CLEANUP_SECS,
+                };
+            }
             if parts[0] == "proj" || parts[0] == "project" {
                 return DagScope::Project { 
                     project_id: parts[1].to_string(),
@@ -1013,6 +1262,11 @@ impl DagScope {
         
         if parts.len() >= 2 {
             match parts[0] {
+                "ephemeral" => {
+                    return Some(DagScope::Ephemeral {
+                        cleanup_after_secs: DEFAULT_EPHEMERAL_CLEANUP_SECS,
+                    });
+                }
                 "proj" | "project" => {
                     return Some(DagScope::Project { 
                         project_id: parts[1].to_string(),
@@ -1045,6 +1299,9 @@ impl DagScope {
             DagScope::Project { project_id, .. } => ("Project".to_string(), Some(project_id.clone())),
             DagScope::User { user_id, .. } => ("User".to_string(), Some(user_id.clone())),
             DagScope::Type { dag_type, .. } => ("Type".to_string(), Some(dag_type.clone())),
+            DagScope::Ephemeral { cleanup_after_secs } => {
+                ("Ephemeral".to_string(), Some(cleanup_after_secs.to_string()))
+            }
         }
     }
     
@@ -1063,11 +1320,52 @@ impl DagScope {
                 dag_type: scope_id.unwrap_or("default").to_string(),
                 force_new: false,
             },
+            "Ephemeral" => DagScope::Ephemeral {
+                cleanup_after_secs: scope_id
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(DEFAULT_EPHEMERAL_CLEANUP_SECS),
+            },
             _ => DagScope::Global,
         }
     }
 }
 
+/// Task retry configuration
+///
+/// Used as the DAG-level default by the executor, and optionally overridden
+/// per task via [`DagTaskSpec::per_task_retry`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryConfig {
+    /// Maximum number of retries
+    pub max_retries: u32,
+    /// Delay between retries (seconds)
+    pub retry_delay_secs: u64,
+    /// Whether to use exponential backoff
+    pub exponential_backoff: bool,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            retry_delay_secs: 5,
+            exponential_backoff: true,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Delay before the given retry attempt (0-based)
+    pub fn delay_for_attempt(&self, attempt: u32) -> u64 {
+        if self.exponential_backoff {
+            self.retry_delay_secs.saturating_mul(2_u64.saturating_pow(attempt))
+        } else {
+            self.retry_delay_secs
+        }
+    }
+}
+
 /// DAG task specification (for external API - GLM/CLI)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DagTaskSpec {
@@ -1079,6 +1377,30 @@ pub struct DagTaskSpec {
     pub depends_on: Vec<String>,
     #[serde(default)]
     pub env: HashMap<String, String>,
+    /// Task-level retry override (falls back to the DAG-level default)
+    ///
+    /// The executor uses it for dispatch retries, and the cis-node worker
+    /// re-runs failed or timed-out tasks up to `max_retries` times. The
+    /// DAG-level default only covers dispatch; execution is not retried
+    /// unless this is set.
+    #[serde(default, alias = "retry", skip_serializing_if = "Option::is_none")]
+    pub per_task_retry: Option<RetryConfig>,
+    /// Task execution timeout in seconds (falls back to the worker default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Decision level (`None` is treated as mechanical)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<TaskLevel>,
+    /// Cancel running sibling tasks when this task fails
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fail_fast: bool,
+}
+
+impl DagTaskSpec {
+    /// Resolve the effective retry config for this task
+    pub fn retry_config<'a>(&'a self, dag_default: &'a RetryConfig) -> &'a RetryConfig {
+        self.per_task_retry.as_ref().unwrap_or(dag_default)
+    }
 }
 
 /// Agent Runtime type
@@ -1181,9 +1503,19 @@ pub struct DagTodoItem {
     /// Tags for categorization
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Parent item ID (for nested checklist items)
+    #[serde(default)]
+    pub parent_id: Option<String>,
 }
 
 impl DagTodoItem {
+    /// Priority for `[!HIGH]` markdown tags
+    pub const PRIORITY_HIGH: i32 = 3;
+    /// Priority for `[!MEDIUM]` markdown tags
+    pub const PRIORITY_MEDIUM: i32 = 2;
+    /// Priority for `[!LOW]` markdown tags
+    pub const PRIORITY_LOW: i32 = 1;
+
     /// Create a new TODO item
     pub fn new(id: String, description: String) -> Self {
         let now = chrono::Utc::now();
@@ -1198,6 +1530,7 @@ impl DagTodoItem {
             completed_at: None,
             notes: String::new(),
             tags: Vec::new(),
+            parent_id: None,
         }
     }
 
@@ -1219,6 +1552,27 @@ impl DagTodoItem {
         self
     }
 
+    /// Set parent item
+    pub fn with_parent(mut self, parent_id: impl Into<String>) -> Self {
+        self.parent_id = Some(parent_id.into());
+        self
+    }
+
+    /// Compare everything except timestamps
+    ///
+    /// Lists re-imported from markdown get fresh timestamps on every load,
+    /// so those must not count as modifications.
+    pub fn same_content(&self, other: &DagTodoItem) -> bool {
+        self.id == other.id
+            && self.description == other.description
+            && self.status == other.status
+            && self.task_id == other.task_id
+            && self.priority == other.priority
+            && self.notes == other.notes
+            && self.tags == other.tags
+            && self.parent_id == other.parent_id
+    }
+
     /// Mark as in progress
     pub fn mark_in_progress(&mut self) {
         self.status = TodoItemStatus::InProgress;
@@ -1257,6 +1611,100 @@ impl DagTodoItem {
     }
 }
 
+/// Parse a markdown task line into `(depth, checked, text)`
+fn parse_markdown_task(line: &str) -> Option<(usize, bool, &str)> {
+    let mut depth = 0;
+    let mut spaces = 0;
+    let mut rest = line;
+    loop {
+        if let Some(r) = rest.strip_prefix('\t') {
+            depth += 1;
+            spaces = 0;
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix(' ') {
+            spaces += 1;
+            if spaces == 4 {
+                depth += 1;
+                spaces = 0;
+            }
+            rest = r;
+        } else {
+            break;
+        }
+    }
+
+    let rest = rest
+        .strip_prefix("- ")
+        .or_else(|| rest.strip_prefix("* "))
+        .or_else(|| rest.strip_prefix("+ "))?;
+    let (checked, text) = if let Some(text) = rest.strip_prefix("[ ]") {
+        (false, text)
+    } else if let Some(text) = rest.strip_prefix("[x]").or_else(|| rest.strip_prefix("[X]")) {
+        (true, text)
+    } else {
+        return None;
+    };
+
+    Some((depth, checked, text.trim()))
+}
+
+/// Strip a `[!HIGH]` / `[!MEDIUM]` / `[!LOW]` tag, returning `(priority, description)`
+fn take_priority_tag(text: &str) -> (i32, String) {
+    for (tag, priority) in [
+        ("[!HIGH]", DagTodoItem::PRIORITY_HIGH),
+        ("[!MEDIUM]", DagTodoItem::PRIORITY_MEDIUM),
+        ("[!LOW]", DagTodoItem::PRIORITY_LOW),
+    ] {
+        if let Some(pos) = text.find(tag) {
+            let description = format!("{}{}", &text[..pos], &text[pos + tag.len()..]);
+            return (priority, description.split_whitespace().collect::<Vec<_>>().join(" "));
+        }
+    }
+    (0, text.to_string())
+}
+
+/// Three-way merge of a single field
+///
+/// Returns `None` (and records a conflict) when both sides changed the field
+/// to different values and neither proposal outranks the other.
+fn merge_field<T: PartialEq + Clone + std::fmt::Debug>(
+    item_id: &str,
+    field: &str,
+    base: &T,
+    ours: &T,
+    theirs: &T,
+    precedence: std::cmp::Ordering,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Option<T> {
+    if ours == theirs || theirs == base {
+        return Some(ours.clone());
+    }
+    if ours == base {
+        return Some(theirs.clone());
+    }
+    match precedence {
+        std::cmp::Ordering::Greater => Some(ours.clone()),
+        std::cmp::Ordering::Less => Some(theirs.clone()),
+        std::cmp::Ordering::Equal => {
+            conflicts.push(MergeConflict {
+                item_id: item_id.to_string(),
+                field: field.to_string(),
+                ours: format!("{:?}", ours),
+                theirs: format!("{:?}", theirs),
+            });
+            None
+        }
+    }
+}
+
+/// Whether two items carry the same user-visible content
+fn same_todo_content(a: &DagTodoItem, b: &DagTodoItem) -> bool {
+    a.description == b.description
+        && a.status == b.status
+        && a.priority == b.priority
+        && a.parent_id == b.parent_id
+}
+
 /// TODO list for DAG execution checkpoint and dynamic adjustment
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct DagTodoList {
@@ -1275,8 +1723,14 @@ pub struct DagTodoList {
     /// Proposal history (accepted/rejected)
     #[serde(default)]
     pub proposal_history: Vec<ProposalResult>,
+    /// Recently accepted proposals (base for three-way merges of stale proposals)
+    #[serde(default)]
+    pub accepted_proposals: Vec<TodoListProposal>,
 }
 
+/// Number of accepted proposals kept for three-way merges
+const MAX_ACCEPTED_PROPOSALS: usize = 32;
+
 impl DagTodoList {
     /// Create a new empty TODO list
     pub fn new() -> Self {
@@ -1286,6 +1740,90 @@ impl DagTodoList {
             agent_notes: String::new(),
             pending_proposals: Vec::new(),
             proposal_history: Vec::new(),
+            accepted_proposals: Vec::new(),
+        }
+    }
+
+    /// Import a GitHub-style markdown task list
+    ///
+    /// - `- [ ] task` becomes Pending, `- [x] task` becomes Completed
+    /// - Items indented by 4 spaces or 1 tab per level become children
+    ///   of the preceding shallower item
+    /// - `[!HIGH]`, `[!MEDIUM]`, `[!LOW]` tags set the priority
+    ///
+    /// Lines that are not task items are ignored. Item IDs are assigned in
+    /// document order (`todo-1`, `todo-2`, ...).
+    pub fn import_from_markdown(md: &str) -> Result<DagTodoList> {
+        let mut list = DagTodoList::new();
+        // Item IDs of the current ancestor chain, indexed by depth
+        let mut ancestors: Vec<String> = Vec::new();
+
+        for (line_no, line) in md.lines().enumerate() {
+            let Some((depth, checked, text)) = parse_markdown_task(line) else {
+                continue;
+            };
+
+            if depth > ancestors.len() {
+                return Err(DagError::InvalidOperation(format!(
+                    "line {}: item indented {} levels without a parent",
+                    line_no + 1,
+                    depth
+                ))
+                .into());
+            }
+            ancestors.truncate(depth);
+
+            let (priority, description) = take_priority_tag(text);
+            let id = format!("todo-{}", list.items.len() + 1);
+            let mut item = DagTodoItem::new(id.clone(), description).with_priority(priority);
+            if let Some(parent) = ancestors.last() {
+                item = item.with_parent(parent.clone());
+            }
+            if checked {
+                item.mark_completed();
+            }
+
+            list.add_item(item);
+            ancestors.push(id);
+        }
+
+        Ok(list)
+    }
+
+    /// Export as a GitHub-style markdown task list
+    ///
+    /// Inverse of [`import_from_markdown`](Self::import_from_markdown): children
+    /// are indented 4 spaces below their parent, only Completed items are checked.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let is_root = |item: &DagTodoItem| {
+            item.parent_id
+                .as_deref()
+                .and_then(|parent| self.get(parent))
+                .is_none()
+        };
+
+        for item in self.items.iter().filter(|i| is_root(i)) {
+            self.write_markdown_item(item, 0, &mut out);
+        }
+        out
+    }
+
+    fn write_markdown_item(&self, item: &DagTodoItem, depth: usize, out: &mut String) {
+        let checkbox = if item.is_completed() { "[x]" } else { "[ ]" };
+        let tag = if item.priority >= DagTodoItem::PRIORITY_HIGH {
+            "[!HIGH] "
+        } else if item.priority >= DagTodoItem::PRIORITY_MEDIUM {
+            "[!MEDIUM] "
+        } else if item.priority >= DagTodoItem::PRIORITY_LOW {
+            "[!LOW] "
+        } else {
+            ""
+        };
+        out.push_str(&format!("{}- {} {}{}\n", "    ".repeat(depth), checkbox, tag, item.description));
+
+        for child in self.items.iter().filter(|i| i.parent_id.as_deref() == Some(item.id.as_str())) {
+            self.write_markdown_item(child, depth + 1, out);
         }
     }
 
@@ -1342,7 +1880,7 @@ impl DagTodoList {
 
             // Worker autonomous decision
             if should_accept(&proposal, self) {
-                let result = self.merge_proposal(&proposal);
+                let result = self.merge_reviewed_proposal(proposal);
                 self.proposal_history.push(result.clone());
                 result
             } else {
@@ -1361,6 +1899,208 @@ impl DagTodoList {
         }
     }
 
+    /// Merge an accepted proposal, three-way merging it against the last
+    /// accepted proposal that touched the same items if it is stale
+    ///
+    /// Unresolvable conflicts keep the proposal pending for human resolution.
+    fn merge_reviewed_proposal(&mut self, proposal: TodoListProposal) -> ProposalResult {
+        if !self.is_stale(&proposal) {
+            return self.merge_proposal(&proposal);
+        }
+
+        let Some(applied) = self
+            .accepted_proposals
+            .iter()
+            .rev()
+            .find(|a| a.id != proposal.id && a.changes.overlaps(&proposal.changes))
+            .cloned()
+        else {
+            return self.merge_proposal(&proposal);
+        };
+
+        let base = self.revert(&applied.changes);
+        match Self::merge_proposals(&base, &applied, &proposal) {
+            MergeResult::Clean(diff) => {
+                // Items removed by the applied proposal but kept by the merge
+                for change in &diff.modified {
+                    if self.get(&change.id).is_none() {
+                        if let Some(item) = base.get(&change.id) {
+                            self.items.push(item.clone());
+                        }
+                    }
+                }
+                self.merge_proposal(&TodoListProposal { changes: diff, ..proposal })
+            }
+            MergeResult::Conflict(conflicts) => {
+                let proposal_id = proposal.id.clone();
+                self.pending_proposals.push(proposal);
+                ProposalResult::Conflict { proposal_id, conflicts }
+            }
+        }
+    }
+
+    /// Whether the proposal was made against a different state than the current one
+    fn is_stale(&self, proposal: &TodoListProposal) -> bool {
+        proposal.changes.modified.iter().any(|change| match self.get(&change.id) {
+            Some(item) => {
+                item.status != change.old_status
+                    || item.priority != change.old_priority
+                    || item.description != change.old_description
+            }
+            None => true,
+        }) || proposal.changes.removed.iter().any(|item| self.get(&item.id).is_none())
+    }
+
+    /// State of this list before `diff` was applied
+    fn revert(&self, diff: &TodoListDiff) -> DagTodoList {
+        let mut base = self.clone();
+        for item in &diff.added {
+            base.remove(&item.id);
+        }
+        for item in &diff.removed {
+            if base.get(&item.id).is_none() {
+                base.items.push(item.clone());
+            }
+        }
+        for change in &diff.modified {
+            if let Some(item) = base.get_mut(&change.id) {
+                item.status = change.old_status;
+                item.priority = change.old_priority;
+                item.description = change.old_description.clone();
+            }
+        }
+        base
+    }
+
+    /// Three-way merge of two proposals made against `base`
+    ///
+    /// Changes to different items or different fields of the same item merge
+    /// cleanly. When both proposals change the same field (or one removes an
+    /// item the other modifies), the proposal whose source has the higher
+    /// [`rank`](ProposalSource::rank) wins; equal ranks are reported as conflicts.
+    pub fn merge_proposals(base: &Self, ours: &TodoListProposal, theirs: &TodoListProposal) -> MergeResult {
+        let precedence = ours.source.rank().cmp(&theirs.source.rank());
+        let mut conflicts = Vec::new();
+        let mut merged = TodoListDiff::default();
+
+        let ours_modified: HashMap<&str, &TodoItemChange> =
+            ours.changes.modified.iter().map(|c| (c.id.as_str(), c)).collect();
+        let theirs_modified: HashMap<&str, &TodoItemChange> =
+            theirs.changes.modified.iter().map(|c| (c.id.as_str(), c)).collect();
+        let ours_removed: HashSet<&str> = ours.changes.removed.iter().map(|i| i.id.as_str()).collect();
+        let theirs_removed: HashSet<&str> = theirs.changes.removed.iter().map(|i| i.id.as_str()).collect();
+
+        // Removals (a removal against a modification is decided by rank)
+        let mut removed_ids = HashSet::new();
+        for item in ours.changes.removed.iter().chain(&theirs.changes.removed) {
+            let id = item.id.as_str();
+            if !removed_ids.insert(id) {
+                continue;
+            }
+            let ours_removes = ours_removed.contains(id);
+            let theirs_removes = theirs_removed.contains(id);
+            let other_modifies = (ours_removes && !theirs_removes && theirs_modified.contains_key(id))
+                || (theirs_removes && !ours_removes && ours_modified.contains_key(id));
+
+            if other_modifies {
+                let remover_wins = match precedence {
+                    std::cmp::Ordering::Greater => ours_removes,
+                    std::cmp::Ordering::Less => theirs_removes,
+                    std::cmp::Ordering::Equal => {
+                        let describe = |removes: bool| if removes { "removed" } else { "modified" };
+                        conflicts.push(MergeConflict {
+                            item_id: id.to_string(),
+                            field: "removed".to_string(),
+                            ours: describe(ours_removes).to_string(),
+                            theirs: describe(theirs_removes).to_string(),
+                        });
+                        continue;
+                    }
+                };
+                if !remover_wins {
+                    removed_ids.remove(id);
+                    continue;
+                }
+            }
+            merged.removed.push(item.clone());
+        }
+
+        // Additions
+        for item in &ours.changes.added {
+            match theirs.changes.added.iter().find(|t| t.id == item.id) {
+                Some(other) if !same_todo_content(item, other) => match precedence {
+                    std::cmp::Ordering::Greater => merged.added.push(item.clone()),
+                    std::cmp::Ordering::Less => merged.added.push(other.clone()),
+                    std::cmp::Ordering::Equal => conflicts.push(MergeConflict {
+                        item_id: item.id.clone(),
+                        field: "added".to_string(),
+                        ours: item.description.clone(),
+                        theirs: other.description.clone(),
+                    }),
+                },
+                _ => merged.added.push(item.clone()),
+            }
+        }
+        for item in &theirs.changes.added {
+            if !ours.changes.added.iter().any(|o| o.id == item.id) {
+                merged.added.push(item.clone());
+            }
+        }
+
+        // Modifications (field by field)
+        let modified_ids = ours.changes.modified.iter()
+            .chain(theirs.changes.modified.iter().filter(|c| !ours_modified.contains_key(c.id.as_str())))
+            .map(|c| c.id.as_str());
+        for id in modified_ids {
+            if removed_ids.contains(id) {
+                continue;
+            }
+            let change = match (ours_modified.get(id), theirs_modified.get(id)) {
+                (Some(o), Some(t)) => {
+                    let base_item = base.get(id);
+                    let old_status = base_item.map_or(o.old_status, |i| i.status);
+                    let old_priority = base_item.map_or(o.old_priority, |i| i.priority);
+                    let old_description = base_item
+                        .map_or_else(|| o.old_description.clone(), |i| i.description.clone());
+
+                    let new_status = merge_field(
+                        id, "status", &old_status, &o.new_status, &t.new_status, precedence, &mut conflicts,
+                    );
+                    let new_priority = merge_field(
+                        id, "priority", &old_priority, &o.new_priority, &t.new_priority, precedence, &mut conflicts,
+                    );
+                    let new_description = merge_field(
+                        id, "description", &old_description, &o.new_description, &t.new_description, precedence, &mut conflicts,
+                    );
+
+                    let (Some(new_status), Some(new_priority), Some(new_description)) =
+                        (new_status, new_priority, new_description)
+                    else {
+                        continue;
+                    };
+                    TodoItemChange {
+                        id: id.to_string(),
+                        old_status,
+                        new_status,
+                        old_priority,
+                        new_priority,
+                        old_description,
+                        new_description,
+                    }
+                }
+                (Some(c), None) | (None, Some(c)) => (*c).clone(),
+                (None, None) => continue,
+            };
+            merged.modified.push(change);
+        }
+
+        if conflicts.is_empty() {
+            MergeResult::Clean(merged)
+        } else {
+            MergeResult::Conflict(conflicts)
+        }
+    }
+
     /// Automatically merge all safe external proposals
     ///
     /// Only merge low-risk changes (e.g., priority adjustments)
@@ -1436,6 +2176,11 @@ impl DagTodoList {
             proposal.id, proposal.proposer, proposal.reason
         ));
 
+        self.accepted_proposals.push(proposal.clone());
+        if self.accepted_proposals.len() > MAX_ACCEPTED_PROPOSALS {
+            self.accepted_proposals.remove(0);
+        }
+
         ProposalResult::Accepted {
             proposal_id: proposal.id.clone(),
             merged_at: chrono::Utc::now(),
@@ -1559,7 +2304,7 @@ impl DagTodoList {
         for item in &other.items {
             match self.get(&item.id) {
                 None => added.push(item.clone()),
-                Some(existing) if existing != item => {
+                Some(existing) if !existing.same_content(item) => {
                     modified.push(TodoItemChange {
                         id: item.id.clone(),
                         old_status: existing.status,
@@ -1628,6 +2373,16 @@ impl ProposalSource {
             _ => true,  // External changes require review
         }
     }
+
+    /// Precedence when two proposals change the same item (higher wins)
+    pub fn rank(&self) -> u8 {
+        match self {
+            Self::UserCLI => 3,
+            Self::WorkerAgent => 2,
+            Self::RoomAgent => 1,
+            Self::AutoSystem => 0,
+        }
+    }
 }
 
 /// TODO List change proposal (safe mode)
@@ -1698,6 +2453,8 @@ pub enum ProposalResult {
     Expired { proposal_id: String },
     /// Pending review (requires Worker confirmation)
     PendingReview { proposal_id: String },
+    /// Conflicts with an accepted proposal, kept pending for human resolution
+    Conflict { proposal_id: String, conflicts: Vec<MergeConflict> },
 }
 
 impl ProposalResult {
@@ -1708,6 +2465,7 @@ impl ProposalResult {
             ProposalResult::Rejected { proposal_id, .. } => proposal_id,
             ProposalResult::Expired { proposal_id } => proposal_id,
             ProposalResult::PendingReview { proposal_id } => proposal_id,
+            ProposalResult::Conflict { proposal_id, .. } => proposal_id,
         }
     }
 
@@ -1717,6 +2475,25 @@ impl ProposalResult {
     }
 }
 
+/// Conflicting change found during a three-way merge
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeConflict {
+    pub item_id: String,
+    /// Conflicting field (`status`, `priority`, `description`, `added` or `removed`)
+    pub field: String,
+    pub ours: String,
+    pub theirs: String,
+}
+
+/// Result of [`DagTodoList::merge_proposals`]
+#[derive(Debug, Clone)]
+pub enum MergeResult {
+    /// Combined changes of both proposals
+    Clean(TodoListDiff),
+    /// Changes that need human resolution
+    Conflict(Vec<MergeConflict>),
+}
+
 /// Difference between two TODO lists
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TodoListDiff {
@@ -1745,6 +2522,20 @@ impl TodoListDiff {
     pub fn has_status_changes(&self) -> bool {
         self.modified.iter().any(|m| m.old_status != m.new_status)
     }
+
+    /// IDs of all items touched by this diff
+    pub fn item_ids(&self) -> HashSet<&str> {
+        self.added.iter().map(|i| i.id.as_str())
+            .chain(self.removed.iter().map(|i| i.id.as_str()))
+            .chain(self.modified.iter().map(|m| m.id.as_str()))
+            .collect()
+    }
+
+    /// Whether both diffs touch at least one common item
+    pub fn overlaps(&self, other: &TodoListDiff) -> bool {
+        let ids = self.item_ids();
+        other.item_ids().iter().any(|id| ids.contains(id))
+    }
 }
 
 /// Observer for TODO list changes
@@ -2004,6 +2795,136 @@ impl DagSpec {
     pub fn worker_id(&self) -> String {
         self.scope.worker_id()
     }
+
+    /// Compare with another version of this DAG (`self` is the old version)
+    pub fn diff(&self, other: &DagSpec) -> DagSpecDiff {
+        let old_tasks: HashMap<&str, &DagTaskSpec> =
+            self.tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+        let new_ids: HashSet<&str> = other.tasks.iter().map(|t| t.id.as_str()).collect();
+
+        let mut added_tasks = Vec::new();
+        let mut modified_tasks = Vec::new();
+        for task in &other.tasks {
+            match old_tasks.get(task.id.as_str()) {
+                None => added_tasks.push(task.clone()),
+                Some(old) => {
+                    let task_diff = TaskDiff::compute(old, task);
+                    if task_diff.has_changes() {
+                        modified_tasks.push(task_diff);
+                    }
+                }
+            }
+        }
+
+        let removed_tasks = self
+            .tasks
+            .iter()
+            .filter(|t| !new_ids.contains(t.id.as_str()))
+            .map(|t| t.id.clone())
+            .collect();
+
+        DagSpecDiff {
+            added_tasks,
+            removed_tasks,
+            modified_tasks,
+            scope_changed: self.scope != other.scope,
+            priority_changed: self.priority != other.priority,
+        }
+    }
+}
+
+/// Change to a single task environment variable
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum EnvChange {
+    Added { value: String },
+    Removed { value: String },
+    Modified { old: String, new: String },
+}
+
+/// Changes to a task present in both DAG versions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskDiff {
+    pub task_id: String,
+    pub command_changed: bool,
+    pub deps_added: Vec<String>,
+    pub deps_removed: Vec<String>,
+    pub env_diff: HashMap<String, EnvChange>,
+}
+
+impl TaskDiff {
+    fn compute(old: &DagTaskSpec, new: &DagTaskSpec) -> Self {
+        let deps_added = new
+            .depends_on
+            .iter()
+            .filter(|d| !old.depends_on.contains(d))
+            .cloned()
+            .collect();
+        let deps_removed = old
+            .depends_on
+            .iter()
+            .filter(|d| !new.depends_on.contains(d))
+            .cloned()
+            .collect();
+
+        let mut env_diff = HashMap::new();
+        for (key, value) in &new.env {
+            match old.env.get(key) {
+                None => {
+                    env_diff.insert(key.clone(), EnvChange::Added { value: value.clone() });
+                }
+                Some(old_value) if old_value != value => {
+                    env_diff.insert(
+                        key.clone(),
+                        EnvChange::Modified { old: old_value.clone(), new: value.clone() },
+                    );
+                }
+                _ => {}
+            }
+        }
+        for (key, value) in &old.env {
+            if !new.env.contains_key(key) {
+                env_diff.insert(key.clone(), EnvChange::Removed { value: value.clone() });
+            }
+        }
+
+        Self {
+            task_id: new.id.clone(),
+            command_changed: old.command != new.command,
+            deps_added,
+            deps_removed,
+            env_diff,
+        }
+    }
+
+    /// Check if the task changed at all
+    pub fn has_changes(&self) -> bool {
+        self.command_changed
+            || !self.deps_added.is_empty()
+            || !self.deps_removed.is_empty()
+            || !self.env_diff.is_empty()
+    }
+}
+
+/// Difference between two versions of a DAG specification
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DagSpecDiff {
+    pub added_tasks: Vec<DagTaskSpec>,
+    pub removed_tasks: Vec<String>,
+    pub modified_tasks: Vec<TaskDiff>,
+    pub scope_changed: bool,
+    pub priority_changed: bool,
+}
+
+impl DagSpecDiff {
+    /// Check if there are any differences
+    pub fn has_changes(&self) -> bool {
+        !self.added_tasks.is_empty()
+            || !self.removed_tasks.is_empty()
+            || !self.modified_tasks.is_empty()
+            || self.scope_changed
+            || self.priority_changed
+    }
 }
 
 /// Scope inference and conflict detection
@@ -2181,6 +3102,15 @@ impl DagPriority {
             Self::Critical => 3,
         }
     }
+
+    /// Next higher priority (Critical stays Critical)
+    pub fn promoted(&self) -> Self {
+        match self {
+            Self::Low => Self::Normal,
+            Self::Normal => Self::High,
+            Self::High | Self::Critical => Self::Critical,
+        }
+    }
 }
 
 /// DAG run status
@@ -2234,6 +3164,9 @@ pub struct DagRun {
     /// Optimistic locking version (Task 5.2)
     #[serde(default = "default_version")]
     pub version: i64,
+    /// Tasks included in a partial run, sorted (None = all tasks)
+    #[serde(default)]
+    pub selected_tasks: Option<Vec<String>>,
 }
 
 impl DagRun {
@@ -2253,6 +3186,7 @@ impl DagRun {
             priority: DagPriority::default(),
             todo_list: DagTodoList::new(),
             version: 1,
+            selected_tasks: None,
         }
     }
 
@@ -2272,6 +3206,7 @@ impl DagRun {
             priority: DagPriority::default(),
             todo_list: DagTodoList::new(),
             version: 1,
+            selected_tasks: None,
         }
     }
 
@@ -2404,9 +3339,88 @@ impl DagRun {
         Ok(())
     }
 
+    /// Preview the tasks that would be dispatched next, without executing them
+    ///
+    /// Useful while the run is paused to inspect what will happen on resume.
+    /// The result is sorted by task ID for stable output.
+    pub fn pending_tasks(&self) -> Vec<String> {
+        let mut tasks = self.dag.get_ready_tasks();
+        tasks.sort();
+        tasks
+    }
+
+    /// Create a new run containing only `task_ids` and their transitive dependencies
+    ///
+    /// The new run starts from scratch: selected tasks are reset to Pending (roots
+    /// become Ready) and every other task is pre-marked as Skipped. Skipped tasks
+    /// outside the selection do not count as failures in [`DagRun::update_status`].
+    pub fn execute_subset(&self, task_ids: &[String]) -> std::result::Result<DagRun, DagError> {
+        if task_ids.is_empty() {
+            return Err(DagError::InvalidOperation("No tasks selected".to_string()));
+        }
+
+        let mut selected: HashSet<String> = HashSet::new();
+        let mut stack: Vec<String> = task_ids.to_vec();
+        while let Some(task_id) = stack.pop() {
+            if selected.contains(&task_id) {
+                continue;
+            }
+            let node = self
+                .dag
+                .get_node(&task_id)
+                .ok_or_else(|| DagError::NodeNotFound(task_id.clone()))?;
+            for dep in &node.dependencies {
+                if self.dag.get_node(dep).is_none() {
+                    return Err(DagError::InvalidDependency(format!("{} -> {}", task_id, dep)));
+                }
+                stack.push(dep.clone());
+            }
+            selected.insert(task_id);
+        }
+
+        let mut dag = self.dag.clone();
+        for node in dag.nodes_mut().values_mut() {
+            node.status = if selected.contains(&node.task_id) {
+                DagNodeStatus::Pending
+            } else {
+                DagNodeStatus::Skipped
+            };
+        }
+        dag.initialize();
+
+        let mut selected_tasks: Vec<String> = selected.into_iter().collect();
+        selected_tasks.sort();
+
+        let mut run = DagRun::new(dag)
+            .with_scope(self.scope.clone())
+            .with_priority(self.priority);
+        run.target_node = self.target_node.clone();
+        run.source_file = self.source_file.clone();
+        run.task_commands = self
+            .task_commands
+            .iter()
+            .filter(|(id, _)| selected_tasks.binary_search(*id).is_ok())
+            .map(|(id, cmd)| (id.clone(), cmd.clone()))
+            .collect();
+        run.selected_tasks = Some(selected_tasks);
+        Ok(run)
+    }
+
+    /// Whether a task is part of this run (always true for full runs)
+    pub fn is_selected(&self, task_id: &str) -> bool {
+        match &self.selected_tasks {
+            Some(tasks) => tasks.binary_search_by(|t| t.as_str().cmp(task_id)).is_ok(),
+            None => true,
+        }
+    }
+
     pub fn update_status(&mut self) {
         let all_finished = self.dag.nodes().values().all(|n| n.is_terminal());
-        let has_failed = self.dag.nodes().values().any(|n| matches!(n.status, DagNodeStatus::Failed | DagNodeStatus::Skipped));
+        let has_failed = self.dag.nodes().values().any(|n| match n.status {
+            DagNodeStatus::Failed => true,
+            DagNodeStatus::Skipped => self.is_selected(&n.task_id),
+            _ => false,
+        });
         let has_blocking_debt = self.dag.nodes().values().any(|n| matches!(n.status, DagNodeStatus::Debt(FailureType::Blocking)));
         let has_unresolved_arbitration = self.dag.nodes().values().any(|n| n.status == DagNodeStatus::Arbitrated);
 
@@ -2600,7 +3614,7 @@ impl DagScheduler {
             FailureType::Blocking => {
                 // Mark as Blocking debt, skip downstream
                 run.status = DagRunStatus::Paused;
-                run.dag.mark_failed(task_id.to_string())?
+                run.dag.mark_task_blocking(task_id)?
             }
         };
 
@@ -2652,9 +3666,8 @@ impl DagScheduler {
     pub fn resolve_run_debt(&mut self, run_id: &str, task_id: &str, resume_downstream: bool) -> std::result::Result<Vec<String>, DagError> {
         let run = self.runs.get_mut(run_id).ok_or_else(|| DagError::NodeNotFound(run_id.to_string()))?;
         let new_ready = run.dag.resolve_debt(task_id, resume_downstream)?;
-        if resume_downstream {
-            run.resolve_debt(task_id)?;
-        }
+        // The debt is settled either way; without resume the task stays failed
+        run.resolve_debt(task_id)?;
         run.update_status();
         run.updated_at = chrono::Utc::now();
         let run_clone = run.clone();
@@ -2859,6 +3872,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolve_debt_restarts_skipped_downstream() {
+        // task1 -> task2 -> task3
+        let mut dag = TaskDag::new();
+        dag.add_node("task1".to_string(), vec![]).unwrap();
+        dag.add_node("task2".to_string(), vec!["task1".to_string()]).unwrap();
+        dag.add_node("task3".to_string(), vec!["task2".to_string()]).unwrap();
+        dag.initialize();
+
+        dag.mark_running("task1".to_string()).unwrap();
+        dag.mark_completed("task1".to_string()).unwrap();
+        dag.mark_running("task2".to_string()).unwrap();
+        let skipped = dag.mark_task_blocking("task2").unwrap();
+        assert_eq!(skipped, vec!["task3".to_string()]);
+        assert_eq!(dag.get_node_status("task3"), Some(DagNodeStatus::Skipped));
+
+        let new_ready = dag.resolve_debt("task2", true).unwrap();
+        assert_eq!(new_ready, vec!["task3".to_string()]);
+        assert_eq!(dag.get_node_status("task2"), Some(DagNodeStatus::Completed));
+        assert_eq!(dag.get_node_status("task3"), Some(DagNodeStatus::Ready));
+    }
+
+    #[test]
+    fn test_resolve_debt_resets_transitive_descendants() {
+        // task1 -> task2 -> task3 -> task4
+        let mut dag = TaskDag::new();
+        dag.add_node("task1".to_string(), vec![]).unwrap();
+        dag.add_node("task2".to_string(), vec!["task1".to_string()]).unwrap();
+        dag.add_node("task3".to_string(), vec!["task2".to_string()]).unwrap();
+        dag.add_node("task4".to_string(), vec!["task3".to_string()]).unwrap();
+        dag.initialize();
+
+        dag.mark_running("task1".to_string()).unwrap();
+        dag.mark_task_blocking("task1").unwrap();
+
+        let new_ready = dag.resolve_debt("task1", true).unwrap();
+        assert_eq!(new_ready, vec!["task2".to_string()]);
+        assert_eq!(dag.get_node_status("task3"), Some(DagNodeStatus::Pending));
+        assert_eq!(dag.get_node_status("task4"), Some(DagNodeStatus::Pending));
+    }
+
     #[test]
     fn test_get_execution_order() {
         let mut dag = TaskDag::new();
@@ -2998,6 +4052,10 @@ mod tests {
                 command: "echo test".to_string(),
                 depends_on: vec![],
                 env: [("PROJECT_ID".to_string(), "env-project".to_string())].into_iter().collect(),
+                per_task_retry: None,
+                timeout_secs: None,
+                level: None,
+                fail_fast: false,
             }
         ];
         
@@ -3015,6 +4073,10 @@ mod tests {
                 command: "echo test".to_string(),
                 depends_on: vec![],
                 env: [("USER_ID".to_string(), "john".to_string())].into_iter().collect(),
+                per_task_retry: None,
+                timeout_secs: None,
+                level: None,
+                fail_fast: false,
             }
         ];
         
@@ -3068,6 +4130,10 @@ mod tests {
                 command: "echo test".to_string(),
                 depends_on: vec![],
                 env: [("PROJECT_ID".to_string(), "env-proj".to_string())].into_iter().collect(),
+                per_task_retry: None,
+                timeout_secs: None,
+                level: None,
+                fail_fast: false,
             }
         ];
         
@@ -3191,6 +4257,55 @@ mod tests {
         assert_eq!(pending.len(), 2);
     }
 
+    #[test]
+    fn test_todo_list_import_from_markdown() {
+        let md = [
+            "# Plan",
+            "- [ ] [!HIGH] Design schema",
+            "    - [x] Draft tables",
+            "\t- [ ] Review with team [!LOW]",
+            "        - [ ] Book meeting",
+            "- [X] Set up CI",
+            "Some notes",
+        ]
+        .join("\n");
+
+        let list = DagTodoList::import_from_markdown(&md).unwrap();
+        assert_eq!(list.items.len(), 5);
+
+        let design = list.get("todo-1").unwrap();
+        assert_eq!(design.description, "Design schema");
+        assert_eq!(design.priority, DagTodoItem::PRIORITY_HIGH);
+        assert_eq!(design.status, TodoItemStatus::Pending);
+        assert_eq!(design.parent_id, None);
+
+        let draft = list.get("todo-2").unwrap();
+        assert!(draft.is_completed());
+        assert_eq!(draft.parent_id.as_deref(), Some("todo-1"));
+
+        let review = list.get("todo-3").unwrap();
+        assert_eq!(review.description, "Review with team");
+        assert_eq!(review.priority, DagTodoItem::PRIORITY_LOW);
+        assert_eq!(review.parent_id.as_deref(), Some("todo-1"));
+
+        assert_eq!(list.get("todo-4").unwrap().parent_id.as_deref(), Some("todo-3"));
+        assert_eq!(list.get("todo-5").unwrap().status, TodoItemStatus::Completed);
+        assert_eq!(list.get("todo-5").unwrap().parent_id, None);
+    }
+
+    #[test]
+    fn test_todo_list_import_rejects_orphan_indent() {
+        assert!(DagTodoList::import_from_markdown("        - [ ] too deep\n").is_err());
+    }
+
+    #[test]
+    fn test_todo_list_markdown_round_trip() {
+        let md = "- [ ] [!HIGH] Design schema\n    - [x] Draft tables\n        - [ ] [!MEDIUM] Index\n- [ ] Ship\n";
+
+        let list = DagTodoList::import_from_markdown(md).unwrap();
+        assert_eq!(list.to_markdown(), md);
+    }
+
     #[test]
     fn test_todo_list_priority() {
         let mut list = DagTodoList::new();
@@ -3246,6 +4361,33 @@ mod tests {
         assert!(scope.worker_key().starts_with("worker-project-test-new-"));
     }
 
+    #[test]
+    fn test_dag_scope_ephemeral() {
+        let scope = DagScope::infer_from_dag("ephemeral-cleanup-script", &[]);
+        assert_eq!(
+            scope,
+            DagScope::Ephemeral { cleanup_after_secs: DEFAULT_EPHEMERAL_CLEANUP_SECS }
+        );
+        assert!(scope.force_new_worker());
+
+        // Every call yields a fresh worker
+        let a = scope.worker_id();
+        let b = scope.worker_id();
+        assert!(a.starts_with("worker-ephemeral-"));
+        assert_ne!(a, b);
+        assert!(scope.worker_key().starts_with("worker-ephemeral-"));
+
+        let ephemeral = DagScope::Ephemeral { cleanup_after_secs: 60 };
+        let (scope_type, scope_id) = ephemeral.to_db_fields();
+        assert_eq!(DagScope::from_db_fields(&scope_type, scope_id.as_deref()), ephemeral);
+
+        let parsed: DagScope = serde_json::from_str(r#"{"type":"ephemeral"}"#).unwrap();
+        assert_eq!(
+            parsed,
+            DagScope::Ephemeral { cleanup_after_secs: DEFAULT_EPHEMERAL_CLEANUP_SECS }
+        );
+    }
+
     #[test]
     fn test_dag_scope_reuse_default() {
         let scope = DagScope::Project {
@@ -3356,6 +4498,117 @@ mod tests {
         assert!(list.pending_review().is_empty());
     }
 
+    fn change_proposal(
+        source: ProposalSource,
+        id: &str,
+        status: TodoItemStatus,
+        priority: i32,
+    ) -> TodoListProposal {
+        let mut diff = TodoListDiff::default();
+        diff.modified.push(TodoItemChange {
+            id: id.to_string(),
+            old_status: TodoItemStatus::Pending,
+            new_status: status,
+            old_priority: 0,
+            new_priority: priority,
+            old_description: "Task 1".to_string(),
+            new_description: "Task 1".to_string(),
+        });
+        TodoListProposal::new(source, "agent", diff, "update")
+    }
+
+    #[test]
+    fn test_merge_proposals_different_fields() {
+        let mut base = DagTodoList::new();
+        base.add("task-1", "Task 1");
+
+        let ours = change_proposal(ProposalSource::RoomAgent, "task-1", TodoItemStatus::Pending, 5);
+        let theirs = change_proposal(ProposalSource::RoomAgent, "task-1", TodoItemStatus::InProgress, 0);
+
+        let MergeResult::Clean(diff) = DagTodoList::merge_proposals(&base, &ours, &theirs) else {
+            panic!("expected clean merge");
+        };
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].new_priority, 5);
+        assert_eq!(diff.modified[0].new_status, TodoItemStatus::InProgress);
+    }
+
+    #[test]
+    fn test_merge_proposals_conflict_and_rank() {
+        let mut base = DagTodoList::new();
+        base.add("task-1", "Task 1");
+
+        let ours = change_proposal(ProposalSource::RoomAgent, "task-1", TodoItemStatus::Pending, 5);
+        let theirs = change_proposal(ProposalSource::AutoSystem, "task-1", TodoItemStatus::Pending, 7);
+        let MergeResult::Clean(diff) = DagTodoList::merge_proposals(&base, &ours, &theirs) else {
+            panic!("higher ranked source should win");
+        };
+        assert_eq!(diff.modified[0].new_priority, 5);
+
+        let theirs = change_proposal(ProposalSource::RoomAgent, "task-1", TodoItemStatus::Pending, 7);
+        let MergeResult::Conflict(conflicts) = DagTodoList::merge_proposals(&base, &ours, &theirs) else {
+            panic!("expected conflict");
+        };
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].field, "priority");
+    }
+
+    #[test]
+    fn test_merge_proposals_remove_vs_modify() {
+        let mut base = DagTodoList::new();
+        base.add("task-1", "Task 1");
+
+        let mut diff = TodoListDiff::default();
+        diff.removed.push(base.get("task-1").unwrap().clone());
+        let remove = TodoListProposal::new(ProposalSource::UserCLI, "user", diff, "drop");
+        let modify = change_proposal(ProposalSource::RoomAgent, "task-1", TodoItemStatus::Pending, 5);
+
+        let MergeResult::Clean(diff) = DagTodoList::merge_proposals(&base, &remove, &modify) else {
+            panic!("expected clean merge");
+        };
+        assert_eq!(diff.removed.len(), 1);
+        assert!(diff.modified.is_empty());
+    }
+
+    #[test]
+    fn test_review_and_merge_stale_proposal() {
+        let mut list = DagTodoList::new();
+        list.add("task-1", "Task 1");
+
+        let first = change_proposal(ProposalSource::RoomAgent, "task-1", TodoItemStatus::Pending, 5);
+        let second = change_proposal(ProposalSource::RoomAgent, "task-1", TodoItemStatus::InProgress, 0);
+        let (first_id, second_id) = (first.id.clone(), second.id.clone());
+        list.submit_proposal(first);
+        list.submit_proposal(second);
+
+        assert!(list.review_and_merge(&first_id, |_, _| true).is_accepted());
+        assert!(list.review_and_merge(&second_id, |_, _| true).is_accepted());
+
+        // The second proposal does not undo the first one's priority change
+        let item = list.get("task-1").unwrap();
+        assert_eq!(item.priority, 5);
+        assert_eq!(item.status, TodoItemStatus::InProgress);
+    }
+
+    #[test]
+    fn test_review_and_merge_reports_conflict() {
+        let mut list = DagTodoList::new();
+        list.add("task-1", "Task 1");
+
+        let first = change_proposal(ProposalSource::RoomAgent, "task-1", TodoItemStatus::Pending, 5);
+        let second = change_proposal(ProposalSource::RoomAgent, "task-1", TodoItemStatus::Pending, 7);
+        let (first_id, second_id) = (first.id.clone(), second.id.clone());
+        list.submit_proposal(first);
+        list.submit_proposal(second);
+
+        list.review_and_merge(&first_id, |_, _| true);
+        let result = list.review_and_merge(&second_id, |_, _| true);
+
+        assert!(matches!(result, ProposalResult::Conflict { ref conflicts, .. } if conflicts.len() == 1));
+        assert_eq!(list.get("task-1").unwrap().priority, 5);
+        assert!(list.pending_review().iter().any(|p| p.id == second_id));
+    }
+
     #[test]
     fn test_todo_list_review_and_reject() {
         let mut list = DagTodoList::new();
@@ -3450,6 +4703,208 @@ mod tests {
             matches!(r, ProposalResult::Expired { proposal_id: id } if id == &proposal_id)
         ));
     }
+
+    #[test]
+    fn test_dag_task_spec_overrides_from_yaml() {
+        let yaml = r#"
+dag_id: deploy-app
+tasks:
+  - id: build
+    type: shell
+    command: make build
+    timeout_secs: 600
+    retry:
+      max_retries: 1
+      retry_delay_secs: 10
+  - id: ship
+    type: shell
+    command: make ship
+    depends_on: [build]
+"#;
+        let spec: DagSpec = serde_yaml::from_str(yaml).unwrap();
+        let dag_default = RetryConfig::default();
+
+        let build = &spec.tasks[0];
+        assert_eq!(build.timeout_secs, Some(600));
+        let retry = build.retry_config(&dag_default);
+        assert_eq!(retry.max_retries, 1);
+        assert_eq!(retry.retry_delay_secs, 10);
+        // Unspecified fields keep their defaults
+        assert!(retry.exponential_backoff);
+
+        let ship = &spec.tasks[1];
+        assert_eq!(ship.timeout_secs, None);
+        assert_eq!(ship.retry_config(&dag_default), &dag_default);
+    }
+
+    #[test]
+    fn test_dag_spec_diff() {
+        let task = |id: &str, command: &str, deps: &[&str], env: &[(&str, &str)]| DagTaskSpec {
+            id: id.to_string(),
+            task_type: "shell".to_string(),
+            command: command.to_string(),
+            depends_on: deps.iter().map(|d| d.to_string()).collect(),
+            env: env.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            per_task_retry: None,
+            timeout_secs: None,
+            level: None,
+            fail_fast: false,
+        };
+
+        let v1 = DagSpec::new(
+            "deploy".to_string(),
+            vec![
+                task("build", "make build", &[], &[("PROFILE", "debug"), ("CI", "1")]),
+                task("lint", "make lint", &[], &[]),
+                task("ship", "make ship", &["build"], &[]),
+            ],
+        );
+        let mut v2 = DagSpec::new(
+            "deploy".to_string(),
+            vec![
+                task("build", "make build", &[], &[("PROFILE", "release"), ("LTO", "1")]),
+                task("test", "make test", &["build"], &[]),
+                task("ship", "make ship --prod", &["test"], &[]),
+            ],
+        );
+        v2.version = 2;
+        v2.priority = crate::types::TaskPriority::High;
+
+        let diff = v1.diff(&v2);
+        assert!(diff.has_changes());
+        assert_eq!(diff.added_tasks.len(), 1);
+        assert_eq!(diff.added_tasks[0].id, "test");
+        assert_eq!(diff.removed_tasks, vec!["lint".to_string()]);
+        assert!(diff.priority_changed);
+        assert!(!diff.scope_changed);
+
+        let build = diff.modified_tasks.iter().find(|t| t.task_id == "build").unwrap();
+        assert!(!build.command_changed);
+        assert_eq!(
+            build.env_diff["PROFILE"],
+            EnvChange::Modified { old: "debug".to_string(), new: "release".to_string() }
+        );
+        assert_eq!(build.env_diff["LTO"], EnvChange::Added { value: "1".to_string() });
+        assert_eq!(build.env_diff["CI"], EnvChange::Removed { value: "1".to_string() });
+
+        let ship = diff.modified_tasks.iter().find(|t| t.task_id == "ship").unwrap();
+        assert!(ship.command_changed);
+        assert_eq!(ship.deps_added, vec!["test".to_string()]);
+        assert_eq!(ship.deps_removed, vec!["build".to_string()]);
+
+        assert!(!v1.diff(&v1).has_changes());
+    }
+
+    #[test]
+    fn test_retry_config_delay() {
+        let config = RetryConfig::default();
+        assert_eq!(config.delay_for_attempt(0), 5);
+        assert_eq!(config.delay_for_attempt(2), 20);
+
+        let linear = RetryConfig { exponential_backoff: false, ..RetryConfig::default() };
+        assert_eq!(linear.delay_for_attempt(2), 5);
+    }
+
+    #[test]
+    fn test_dag_run_pending_tasks_while_paused() {
+        let mut dag = TaskDag::new();
+        dag.add_node("b".to_string(), vec![]).unwrap();
+        dag.add_node("a".to_string(), vec![]).unwrap();
+        dag.add_node("c".to_string(), vec!["a".to_string()]).unwrap();
+        dag.initialize();
+
+        let mut run = DagRun::new(dag);
+        run.status = DagRunStatus::Paused;
+
+        assert_eq!(run.pending_tasks(), vec!["a".to_string(), "b".to_string()]);
+        // Previewing must not change node state
+        assert_eq!(run.dag.get_node_status("a"), Some(DagNodeStatus::Ready));
+
+        run.dag.mark_running("a".to_string()).unwrap();
+        run.dag.mark_completed("a".to_string()).unwrap();
+        assert_eq!(run.pending_tasks(), vec!["b".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn test_dag_run_execute_subset() {
+        // fetch -> build -> test, fetch -> docs
+        let mut dag = TaskDag::new();
+        dag.add_node("fetch".to_string(), vec![]).unwrap();
+        dag.add_node("build".to_string(), vec!["fetch".to_string()]).unwrap();
+        dag.add_node("test".to_string(), vec!["build".to_string()]).unwrap();
+        dag.add_node("docs".to_string(), vec!["fetch".to_string()]).unwrap();
+        dag.initialize();
+        let mut run = DagRun::new(dag);
+        run.task_commands = ["fetch", "build", "test", "docs"]
+            .iter()
+            .map(|id| (id.to_string(), format!("make {}", id)))
+            .collect();
+
+        let mut subset = run.execute_subset(&["build".to_string()]).unwrap();
+        assert_ne!(subset.run_id, run.run_id);
+        assert_eq!(subset.selected_tasks, Some(vec!["build".to_string(), "fetch".to_string()]));
+        assert_eq!(subset.task_commands.len(), 2);
+        assert_eq!(subset.dag.get_node_status("fetch"), Some(DagNodeStatus::Ready));
+        assert_eq!(subset.dag.get_node_status("build"), Some(DagNodeStatus::Pending));
+        assert_eq!(subset.dag.get_node_status("test"), Some(DagNodeStatus::Skipped));
+        assert_eq!(subset.dag.get_node_status("docs"), Some(DagNodeStatus::Skipped));
+
+        // Pre-skipped tasks outside the selection are not failures
+        for task in ["fetch", "build"] {
+            subset.dag.mark_running(task.to_string()).unwrap();
+            subset.dag.mark_completed(task.to_string()).unwrap();
+        }
+        subset.update_status();
+        assert_eq!(subset.status, DagRunStatus::Completed);
+
+        assert_eq!(
+            run.execute_subset(&["missing".to_string()]).unwrap_err(),
+            DagError::NodeNotFound("missing".to_string())
+        );
+        assert!(run.execute_subset(&[]).is_err());
+    }
+
+    #[test]
+    fn test_critical_path() {
+        //   fetch(3) -> build(5) -> test(4) -> ship(1)
+        //   fetch(3) -> docs(2) ---------------^
+        let mut dag = TaskDag::new();
+        dag.add_node("ship".to_string(), vec!["test".to_string(), "docs".to_string()]).unwrap();
+        dag.add_node("test".to_string(), vec!["build".to_string()]).unwrap();
+        dag.add_node("build".to_string(), vec!["fetch".to_string()]).unwrap();
+        dag.add_node("docs".to_string(), vec!["fetch".to_string()]).unwrap();
+        dag.add_node("fetch".to_string(), vec![]).unwrap();
+
+        let secs = |s| Duration::from_secs(s);
+        let durations: HashMap<String, Duration> = [
+            ("fetch", 3),
+            ("build", 5),
+            ("test", 4),
+            ("docs", 2),
+            ("ship", 1),
+        ]
+        .into_iter()
+        .map(|(id, s)| (id.to_string(), secs(s)))
+        .collect();
+
+        let result = dag.critical_path(&durations).unwrap();
+        assert_eq!(result.path, vec!["fetch", "build", "test", "ship"]);
+        assert_eq!(result.total_duration, secs(13));
+        assert_eq!(result.slack["docs"], secs(7));
+        assert!(result.path.iter().all(|id| result.slack[id].is_zero()));
+
+        // Unknown durations count as zero
+        let result = dag.critical_path(&HashMap::new()).unwrap();
+        assert_eq!(result.total_duration, Duration::ZERO);
+
+        let mut cyclic = TaskDag::new();
+        cyclic.add_node("a".to_string(), vec!["b".to_string()]).unwrap();
+        cyclic.add_node("b".to_string(), vec!["a".to_string()]).unwrap();
+        assert!(matches!(
+            cyclic.critical_path(&durations),
+            Err(DagError::CycleDetected(_))
+        ));
+    }
 }
 
 /// From conversion implementations
@@ -3466,6 +4921,8 @@ impl From<DagTask> for DagNode {
             agent_runtime: task.agent_runtime,
             reuse_agent: task.reuse_agent,
             keep_agent: task.keep_agent,
+            fail_fast: task.fail_fast,
+            node_selector: task.node_selector,
             agent_config: task.agent_config,
         }
     }
@@ -6,7 +6,7 @@
 //! - 多 Runtime 支持（Claude, OpenCode, Kimi, Aider）
 //! - Agent 复用和池化管理
 //! - 上游上下文自动注入
-//! - 并发任务执行
+//! - 并发任务执行（就绪任务并行提交，`fail_fast` 节点失败时取消其他任务）
 //! - 超时和错误处理
 
 use std::collections::HashMap;
@@ -14,6 +14,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use tokio::sync::RwLock;
+use tokio::task::{JoinError, JoinSet};
 use tracing::{debug, info, warn};
 
 use crate::agent::persistent::{
@@ -179,6 +180,17 @@ impl TaskExecutionResult {
     }
 }
 
+/// 并行执行中的任务
+///
+/// 每个任务在独立的 tokio task 中执行，结束前已在调度器写锁内写回 DAG 状态，
+/// 返回 `(task_id, success)`。
+#[derive(Default)]
+struct RunningTasks {
+    set: JoinSet<(String, bool)>,
+    /// tokio task ID -> DAG task ID（任务 panic 时定位节点）
+    task_ids: HashMap<tokio::task::Id, String>,
+}
+
 /// 多 Agent 执行报告
 #[derive(Debug, Clone)]
 pub struct MultiAgentExecutionReport {
@@ -264,281 +276,254 @@ impl MultiAgentDagExecutor {
         }
     }
 
-    /// 事件驱动执行（新实现）
+    /// 事件驱动执行
+    ///
+    /// 就绪任务并行提交到 Agent 池，每完成一个任务立即调度新就绪的任务。
     async fn execute_event_driven(
         &self,
         run_id: &str,
         start_time: std::time::Instant,
     ) -> Result<MultiAgentExecutionReport> {
-        use crate::scheduler::notify::{ReadyNotify, TaskCompletion};
-        use tokio::select;
-
-        let ready_notify = Arc::new(ReadyNotify::new());
-        let (completion_tx, mut completion_rx) = tokio::sync::broadcast::channel(100);
-
-        // 初始通知
-        ready_notify.notify_ready();
+        let mut running = RunningTasks::default();
 
         loop {
-            // 检查运行状态
-            let should_stop = {
-                let scheduler = self.scheduler.read().await;
-                let run = scheduler.get_run(run_id);
-                if let Some(run) = run {
-                    run.status == crate::scheduler::DagRunStatus::Failed
-                        || run.status == crate::scheduler::DagRunStatus::Paused
-                } else {
-                    return Err(CisError::scheduler("Run not found"));
-                }
-            };
-
-            if should_stop {
-                warn!("Run {} stopped due to failure or pause", run_id);
-                break;
-            }
-
-            // 获取就绪任务
-            let ready_tasks = {
-                let scheduler = self.scheduler.read().await;
-                let run = scheduler.get_run(run_id);
-                if let Some(run) = run {
-                    run.dag.get_ready_tasks()
-                } else {
-                    return Err(CisError::scheduler("Run not found"));
-                }
-            };
+            self.dispatch_ready(run_id, &mut running).await?;
 
-            // 检查是否完成
-            if ready_tasks.is_empty() {
-                let is_completed = {
-                    let scheduler = self.scheduler.read().await;
-                    let run = scheduler.get_run(run_id);
-                    if let Some(run) = run {
-                        run.dag.nodes().values().all(|n| n.is_terminal())
-                    } else {
-                        return Err(CisError::scheduler("Run not found"));
-                    }
-                };
-
-                if is_completed {
-                    info!("Run {} completed", run_id);
-                    break;
-                }
-            } else {
-                // 有就绪任务，限制并发数
-                let active_count = self.count_active_agents(run_id).await;
-                let available_slots = self
-                    .config
-                    .max_concurrent_tasks
-                    .saturating_sub(active_count);
-
-                if available_slots > 0 {
-                    let tasks_to_start: Vec<String> = ready_tasks
-                        .into_iter()
-                        .take(available_slots)
-                        .collect();
-
-                    info!(
-                        "Starting {} tasks for run {} (active: {}, slots: {})",
-                        tasks_to_start.len(),
-                        run_id,
-                        active_count,
-                        available_slots
-                    );
-
-                    // 启动任务
-                    for task_id in tasks_to_start {
-                        // 标记为运行中
-                        {
-                            let mut scheduler = self.scheduler.write().await;
-                            if let Some(run) = scheduler.get_run_mut(run_id) {
-                                let _ = run.dag.mark_running(task_id.clone());
-                            }
-                        }
-
-                        // 异步执行任务
-                        let this = self.clone_ref();
-                        let run_id = run_id.to_string();
-                        let task_id = task_id.clone();
-                        let ready_notify = ready_notify.clone();
-                        let completion_tx = completion_tx.clone();
-
-                        tokio::spawn(async move {
-                            let result = this.execute_task(&run_id, &task_id).await;
-
-                            match result {
-                                Ok(task_result) => {
-                                    // 更新结果
-                                    let _ = this.update_task_result(&run_id, &task_id, task_result).await;
-
-                                    // 发送完成通知
-                                    let _ = completion_tx.send(TaskCompletion {
-                                        run_id: run_id.clone(),
-                                        task_id: task_id.clone(),
-                                        success: true,
-                                        output: String::new(),
-                                        exit_code: 0,
-                                        duration_ms: 0,
-                                    });
-
-                                    // 通知有任务可能就绪
-                                    ready_notify.notify_ready();
-                                }
-                                Err(e) => {
-                                    warn!("Task {} execution failed: {}", task_id, e);
-                                    let _ = this.mark_task_failed(&run_id, &task_id, e.to_string()).await;
-
-                                    // 即使失败也通知（可能触发跳过等逻辑）
-                                    ready_notify.notify_ready();
-                                }
-                            }
-                        });
-                    }
-                }
-            }
-
-            // 等待事件
-            select! {
-                _ = ready_notify.wait_for_ready() => {
-                    // 有任务就绪，继续循环
-                }
-                _ = tokio::time::sleep(Duration::from_secs(5)) => {
-                    // 超时，继续循环检查
-                }
+            // 没有运行中的任务说明已无可调度的任务（完成、失败或暂停）
+            match running.set.join_next_with_id().await {
+                Some(joined) => self.handle_joined(run_id, joined, &mut running).await?,
+                None => break,
             }
         }
 
-        // 清理 Agent
-        if let Err(e) = self.cleanup_run(run_id).await {
-            warn!("Failed to cleanup run {}: {}", run_id, e);
-        }
-
-        // 生成报告
-        self.build_report(run_id, start_time.elapsed()).await
+        self.finish_run(run_id, start_time).await
     }
 
-    /// 轮询执行（原始实现）
+    /// 轮询执行
     async fn execute_polling(
         &self,
         run_id: &str,
         start_time: std::time::Instant,
     ) -> Result<MultiAgentExecutionReport> {
-        // 主执行循环（原始轮询逻辑）
+        let mut running = RunningTasks::default();
+
         loop {
-            // 检查运行状态
-            let should_stop = {
-                let scheduler = self.scheduler.read().await;
-                if let Some(run) = scheduler.get_run(run_id) {
-                    run.status == crate::scheduler::DagRunStatus::Failed
-                        || run.status == crate::scheduler::DagRunStatus::Paused
-                } else {
-                    return Err(CisError::scheduler("Run not found"));
-                }
-            };
+            self.dispatch_ready(run_id, &mut running).await?;
 
-            if should_stop {
-                warn!("Run {} stopped due to failure or pause", run_id);
+            if running.set.is_empty() {
                 break;
             }
 
-            // 获取就绪任务
-            let ready_tasks = {
-                let scheduler = self.scheduler.read().await;
-                let run = scheduler
-                    .get_run(run_id)
-                    .ok_or_else(|| CisError::scheduler("Run not found"))?;
-                run.dag.get_ready_tasks()
-            };
+            // 等待一段时间再收集已完成的任务
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            while let Some(joined) = running.set.try_join_next_with_id() {
+                self.handle_joined(run_id, joined, &mut running).await?;
+            }
+        }
 
-            if ready_tasks.is_empty() {
-                // 检查是否完成
-                let is_completed = {
-                    let scheduler = self.scheduler.read().await;
-                    let run = scheduler
-                        .get_run(run_id)
-                        .ok_or_else(|| CisError::scheduler("Run not found"))?;
-                    run.dag.nodes().values().all(|n| n.is_terminal())
-                };
+        self.finish_run(run_id, start_time).await
+    }
+
+    /// 原子地领取就绪任务并标记为运行中
+    ///
+    /// 运行失败或暂停后不再领取新任务，已在运行的任务继续完成。
+    async fn claim_ready_tasks(&self, run_id: &str, limit: usize) -> Result<Vec<String>> {
+        let mut scheduler = self.scheduler.write().await;
+        let run = scheduler
+            .get_run_mut(run_id)
+            .ok_or_else(|| CisError::scheduler("Run not found"))?;
 
-                if is_completed {
-                    info!("Run {} completed", run_id);
-                    break;
+        if matches!(
+            run.status,
+            crate::scheduler::DagRunStatus::Failed | crate::scheduler::DagRunStatus::Paused
+        ) {
+            return Ok(Vec::new());
+        }
+
+        let mut ready_tasks = run.dag.get_ready_tasks();
+        ready_tasks.sort();
+        ready_tasks.truncate(limit);
+
+        let mut claimed = Vec::with_capacity(ready_tasks.len());
+        for task_id in ready_tasks {
+            match run.dag.mark_running(task_id.clone()) {
+                Ok(()) => claimed.push(task_id),
+                Err(e) => warn!("Failed to mark task {} running: {}", task_id, e),
+            }
+        }
+
+        Ok(claimed)
+    }
+
+    /// 把就绪任务并行提交到 Agent 池
+    async fn dispatch_ready(&self, run_id: &str, running: &mut RunningTasks) -> Result<()> {
+        let available_slots = self
+            .config
+            .max_concurrent_tasks
+            .saturating_sub(running.set.len());
+        if available_slots == 0 {
+            debug!(
+                "Max concurrent reached ({}/{}), waiting...",
+                running.set.len(),
+                self.config.max_concurrent_tasks
+            );
+            return Ok(());
+        }
+
+        let tasks_to_start = self.claim_ready_tasks(run_id, available_slots).await?;
+        if tasks_to_start.is_empty() {
+            return Ok(());
+        }
+
+        info!(
+            "Starting {} tasks for run {} (running: {}, slots: {})",
+            tasks_to_start.len(),
+            run_id,
+            running.set.len(),
+            available_slots
+        );
+
+        for task_id in tasks_to_start {
+            let this = self.clone_ref();
+            let run_id = run_id.to_string();
+            let handle = running.set.spawn({
+                let task_id = task_id.clone();
+                async move {
+                    let success = this.run_task(&run_id, &task_id).await;
+                    (task_id, success)
                 }
+            });
+            running.task_ids.insert(handle.id(), task_id);
+        }
+
+        Ok(())
+    }
 
-                // 等待一段时间再检查
-                tokio::time::sleep(Duration::from_millis(100)).await;
-                continue;
+    /// 执行任务并写回结果，返回是否成功
+    async fn run_task(&self, run_id: &str, task_id: &str) -> bool {
+        match self.execute_task(run_id, task_id).await {
+            Ok(result) => {
+                let success = result.success;
+                if let Err(e) = self.update_task_result(run_id, task_id, result).await {
+                    warn!("Failed to update task result for {}: {}", task_id, e);
+                }
+                success
             }
+            Err(e) => {
+                warn!("Task {} execution failed: {}", task_id, e);
+                if let Err(e) = self.mark_task_failed(run_id, task_id, e.to_string()).await {
+                    warn!("Failed to mark task {} as failed: {}", task_id, e);
+                }
+                false
+            }
+        }
+    }
 
-            // 限制并发数
-            let active_count = self.count_active_agents(run_id).await;
-            let available_slots = self
-                .config
-                .max_concurrent_tasks
-                .saturating_sub(active_count);
-
-            if available_slots == 0 {
-                debug!(
-                    "Max concurrent reached ({}/{}), waiting...",
-                    active_count, self.config.max_concurrent_tasks
-                );
-                tokio::time::sleep(Duration::from_millis(100)).await;
-                continue;
+    /// 处理一个结束的任务
+    ///
+    /// 失败任务的节点设置了 `fail_fast` 时取消其他运行中的任务，
+    /// 否则其他任务继续执行到完成。
+    async fn handle_joined(
+        &self,
+        run_id: &str,
+        joined: std::result::Result<(tokio::task::Id, (String, bool)), JoinError>,
+        running: &mut RunningTasks,
+    ) -> Result<()> {
+        let (task_id, success) = match joined {
+            Ok((id, (task_id, success))) => {
+                running.task_ids.remove(&id);
+                (task_id, success)
+            }
+            Err(e) => {
+                let Some(task_id) = running.task_ids.remove(&e.id()) else {
+                    return Ok(());
+                };
+                if e.is_cancelled() {
+                    return Ok(());
+                }
+                warn!("Task {} panicked: {}", task_id, e);
+                self.mark_task_failed(run_id, &task_id, format!("Task panicked: {}", e))
+                    .await?;
+                (task_id, false)
             }
+        };
 
-            let tasks_to_start: Vec<String> = ready_tasks
-                .into_iter()
-                .take(available_slots)
-                .collect();
+        if success || running.set.is_empty() {
+            return Ok(());
+        }
+
+        let fail_fast = {
+            let scheduler = self.scheduler.read().await;
+            scheduler
+                .get_run(run_id)
+                .and_then(|run| run.dag.get_node(&task_id))
+                .is_some_and(|node| node.fail_fast)
+        };
 
-            info!(
-                "Starting {} tasks for run {} (active: {}, slots: {})",
-                tasks_to_start.len(),
-                run_id,
-                active_count,
-                available_slots
+        if fail_fast {
+            warn!(
+                "Task {} failed with fail_fast, cancelling {} running tasks",
+                task_id,
+                running.set.len()
             );
+            self.cancel_running(run_id, &task_id, running).await?;
+        }
 
-            // 顺序执行就绪任务（避免 Send 问题）
-            for task_id in tasks_to_start {
-                // 标记任务为运行中
-                {
-                    let mut scheduler = self.scheduler.write().await;
-                    let run = scheduler
-                        .get_run_mut(run_id)
-                        .ok_or_else(|| CisError::scheduler("Run not found"))?;
-                    if let Err(e) = run.dag.mark_running(task_id.clone()) {
-                        warn!("Failed to mark task {} running: {}", task_id, e);
-                        continue;
-                    }
-                }
+        Ok(())
+    }
 
-                // 执行任务
-                match self.execute_task(run_id, &task_id).await {
-                    Ok(result) => {
-                        // 更新任务结果
-                        if let Err(e) = self.update_task_result(run_id, &task_id, result).await {
-                            warn!("Failed to update task result for {}: {}", task_id, e);
-                        }
-                    }
-                    Err(e) => {
-                        // 任务执行出错
-                        warn!("Task {} execution failed: {}", task_id, e);
-                        if let Err(e) = self.mark_task_failed(run_id, &task_id, e.to_string()).await
-                        {
-                            warn!("Failed to mark task {} as failed: {}", task_id, e);
-                        }
-                    }
+    /// 取消所有运行中的任务并标记为失败
+    async fn cancel_running(
+        &self,
+        run_id: &str,
+        failed_task_id: &str,
+        running: &mut RunningTasks,
+    ) -> Result<()> {
+        running.set.abort_all();
+        // 已在取消前完成的任务已经写回了结果
+        while running.set.join_next().await.is_some() {}
+        running.task_ids.clear();
+
+        let cancelled: Vec<String> = {
+            let mut scheduler = self.scheduler.write().await;
+            let run = scheduler
+                .get_run_mut(run_id)
+                .ok_or_else(|| CisError::scheduler("Run not found"))?;
+
+            let cancelled: Vec<String> = run
+                .dag
+                .nodes()
+                .values()
+                .filter(|node| node.status == DagNodeStatus::Running)
+                .map(|node| node.task_id.clone())
+                .collect();
+            for task_id in &cancelled {
+                if let Err(e) = run.dag.mark_failed(task_id.clone()) {
+                    warn!("Failed to mark task {} as failed: {}", task_id, e);
                 }
             }
+            run.update_status();
+            cancelled
+        };
+
+        let reason = format!("Cancelled after fail_fast task {} failed", failed_task_id);
+        for task_id in cancelled {
+            self.context_store.save(run_id, &task_id, &reason, Some(1)).await?;
         }
 
-        // 清理 Agent
+        Ok(())
+    }
+
+    /// 清理 Agent 并生成报告
+    async fn finish_run(
+        &self,
+        run_id: &str,
+        start_time: std::time::Instant,
+    ) -> Result<MultiAgentExecutionReport> {
         if let Err(e) = self.cleanup_run(run_id).await {
             warn!("Failed to cleanup run {}: {}", run_id, e);
         }
 
-        // 生成报告
         let report = self.build_report(run_id, start_time.elapsed()).await?;
 
         info!(
@@ -905,15 +890,6 @@ impl MultiAgentDagExecutor {
         })
     }
 
-    /// 计算当前运行的 Agent 数量
-    async fn count_active_agents(&self, run_id: &str) -> usize {
-        let run_agents = self.run_agents.read().await;
-        run_agents
-            .get(run_id)
-            .map(|agents| agents.len())
-            .unwrap_or(0)
-    }
-
     /// 获取运行状态
     pub async fn get_run_status(
         &self,
@@ -997,6 +973,137 @@ fn format_output(output: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+
+    use crate::agent::persistent::{
+        AgentConfig, AgentInfo, AgentRuntime, AgentStatus, PersistentAgent, PoolConfig,
+    };
+
+    /// 记录同时执行的任务数
+    #[derive(Default)]
+    struct Probe {
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    /// 按任务 ID 决定行为的 Mock Agent：`fail*` 很快失败，`slow*` 长时间运行
+    struct MockAgent {
+        agent_id: String,
+        probe: Arc<Probe>,
+        slow: Duration,
+    }
+
+    #[async_trait]
+    impl PersistentAgent for MockAgent {
+        fn agent_id(&self) -> &str {
+            &self.agent_id
+        }
+
+        fn runtime_type(&self) -> AgentRuntimeType {
+            AgentRuntimeType::Claude
+        }
+
+        async fn execute(&self, task: TaskRequest) -> Result<TaskResult> {
+            let running = self.probe.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.probe.max_in_flight.fetch_max(running, Ordering::SeqCst);
+
+            let (delay, success) = if task.task_id.starts_with("fail") {
+                (Duration::from_millis(20), false)
+            } else if task.task_id.starts_with("slow") {
+                (self.slow, true)
+            } else {
+                (Duration::from_millis(100), true)
+            };
+            tokio::time::sleep(delay).await;
+
+            self.probe.in_flight.fetch_sub(1, Ordering::SeqCst);
+            if success {
+                Ok(TaskResult::success(task.task_id, "done"))
+            } else {
+                Ok(TaskResult::error(task.task_id, "boom"))
+            }
+        }
+
+        async fn status(&self) -> AgentStatus {
+            AgentStatus::Idle
+        }
+
+        async fn attach(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn detach(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct MockRuntime {
+        probe: Arc<Probe>,
+        slow: Duration,
+    }
+
+    #[async_trait]
+    impl AgentRuntime for MockRuntime {
+        fn runtime_type(&self) -> AgentRuntimeType {
+            AgentRuntimeType::Claude
+        }
+
+        async fn create_agent(&self, config: AgentConfig) -> Result<Box<dyn PersistentAgent>> {
+            Ok(Box::new(MockAgent {
+                agent_id: config.name,
+                probe: self.probe.clone(),
+                slow: self.slow,
+            }))
+        }
+
+        async fn list_agents(&self) -> Vec<AgentInfo> {
+            Vec::new()
+        }
+    }
+
+    async fn test_executor(
+        dir: &tempfile::TempDir,
+        probe: Arc<Probe>,
+        slow: Duration,
+    ) -> MultiAgentDagExecutor {
+        let agent_pool = AgentPool::new(PoolConfig::default());
+        agent_pool
+            .register_runtime(Arc::new(MockRuntime { probe, slow }))
+            .await
+            .unwrap();
+
+        MultiAgentDagExecutor {
+            scheduler: Arc::new(RwLock::new(DagScheduler::new())),
+            agent_pool,
+            context_store: ContextStore::new(dir.path().join("context.db")).unwrap(),
+            config: MultiAgentExecutorConfig::new().with_context_injection(false),
+            run_agents: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    async fn node_status(
+        executor: &MultiAgentDagExecutor,
+        run_id: &str,
+        task_id: &str,
+    ) -> DagNodeStatus {
+        let scheduler = executor.scheduler.read().await;
+        scheduler.get_run(run_id).unwrap().dag.get_node(task_id).unwrap().status
+    }
+
+    /// 构建两个独立任务：很快失败的 `fail` 和长时间运行的 `slow`
+    fn failing_dag(fail_fast: bool) -> TaskDag {
+        let mut dag = TaskDag::new();
+        dag.add_node("fail".to_string(), vec![]).unwrap();
+        dag.add_node("slow".to_string(), vec![]).unwrap();
+        dag.get_node_mut("fail").unwrap().fail_fast = fail_fast;
+        dag
+    }
 
     #[test]
     fn test_multi_agent_executor_config_default() {
@@ -1085,4 +1192,82 @@ mod tests {
             RuntimeType::OpenCode
         );
     }
+
+    #[tokio::test]
+    async fn test_running_tasks_locate_panicked_task() {
+        fn boom() -> (String, bool) {
+            panic!("boom")
+        }
+
+        let mut running = RunningTasks::default();
+        let handle = running.set.spawn(async { boom() });
+        running.task_ids.insert(handle.id(), "task-1".to_string());
+
+        let err = running.set.join_next_with_id().await.unwrap().unwrap_err();
+        assert!(err.is_panic());
+        assert_eq!(running.task_ids.get(&err.id()).map(String::as_str), Some("task-1"));
+    }
+
+    #[test]
+    fn test_dag_node_fail_fast_default() {
+        let node = DagNode::new("task-1".to_string(), vec![]);
+        assert!(!node.fail_fast);
+
+        let mut json = serde_json::to_value(&node).unwrap();
+        json.as_object_mut().unwrap().remove("fail_fast");
+        let node: DagNode = serde_json::from_value(json).unwrap();
+        assert!(!node.fail_fast);
+    }
+
+    #[tokio::test]
+    async fn test_ready_tasks_run_concurrently() {
+        let dir = tempfile::tempdir().unwrap();
+        let probe = Arc::new(Probe::default());
+        let executor = test_executor(&dir, probe.clone(), Duration::from_millis(100)).await;
+
+        let mut dag = TaskDag::new();
+        for task_id in ["a", "b", "c"] {
+            dag.add_node(task_id.to_string(), vec![]).unwrap();
+        }
+        dag.add_node("d".to_string(), vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .unwrap();
+        let run_id = executor.create_run(dag).await.unwrap();
+
+        let report = executor.execute(&run_id).await.unwrap();
+
+        assert_eq!(report.completed, 4);
+        assert_eq!(report.failed, 0);
+        assert_eq!(probe.max_in_flight.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_failure_cancels_running_tasks() {
+        let dir = tempfile::tempdir().unwrap();
+        let probe = Arc::new(Probe::default());
+        let executor = test_executor(&dir, probe.clone(), Duration::from_secs(30)).await;
+        let run_id = executor.create_run(failing_dag(true)).await.unwrap();
+
+        let start = std::time::Instant::now();
+        let report = executor.execute(&run_id).await.unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert_eq!(report.failed, 2);
+        assert_eq!(node_status(&executor, &run_id, "slow").await, DagNodeStatus::Failed);
+        assert_eq!(probe.max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_normal_failure_lets_running_tasks_finish() {
+        let dir = tempfile::tempdir().unwrap();
+        let probe = Arc::new(Probe::default());
+        let executor = test_executor(&dir, probe.clone(), Duration::from_millis(300)).await;
+        let run_id = executor.create_run(failing_dag(false)).await.unwrap();
+
+        let report = executor.execute(&run_id).await.unwrap();
+
+        assert_eq!(report.completed, 1);
+        assert_eq!(report.failed, 1);
+        assert_eq!(node_status(&executor, &run_id, "fail").await, DagNodeStatus::Failed);
+        assert_eq!(node_status(&executor, &run_id, "slow").await, DagNodeStatus::Completed);
+    }
 }
@@ -122,6 +122,8 @@ impl UnifiedTask {
             agent_runtime,
             reuse_agent,
             keep_agent,
+            fail_fast: self.fail_fast,
+            node_selector: None,
             agent_config: None, // 将在 execute_task 时使用
         }
     }
@@ -174,6 +176,7 @@ mod tests {
                     condition: None,
                     idempotent: false,
                     outputs: None,
+                    fail_fast: false,
                 },
                 UnifiedTask {
                     id: "task-2".to_string(),
@@ -198,6 +201,7 @@ mod tests {
                     condition: None,
                     idempotent: false,
                     outputs: None,
+                    fail_fast: false,
                 },
             ],
             execution_policy: crate::scheduler::converters::ExecutionPolicy::AllSuccess,
@@ -229,6 +233,7 @@ mod tests {
             condition: None,
             idempotent: false,
             outputs: None,
+            fail_fast: false,
         };
 
         let node = task.to_dag_node();
@@ -3,11 +3,25 @@
 //! Provides node selection capability for heterogeneous task routing.
 //! Allows DAG tasks to specify which nodes should execute them based on
 //! architecture, features, or custom constraints.
+//!
+//! With [`SelectionStrategy::PreferLocal`], tasks stay on the local node
+//! unless it is overloaded according to the filter's [`ResourceThreshold`].
 
 use crate::error::{CisError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Node selection strategy
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionStrategy {
+    /// Route to any matching node
+    #[default]
+    Any,
+    /// Route to the local node unless it is overloaded
+    PreferLocal,
+}
+
 /// Node selector for heterogeneous task routing
 ///
 /// Specifies requirements for the node that should execute a DAG task.
@@ -28,6 +42,10 @@ pub struct NodeSelector {
 
     /// Custom labels as key-value pairs
     pub labels: Option<HashMap<String, String>>,
+
+    /// Selection strategy
+    #[serde(default)]
+    pub strategy: SelectionStrategy,
 }
 
 /// Resource requirements for task execution
@@ -58,9 +76,21 @@ impl NodeSelector {
             os: None,
             min_resources: None,
             labels: None,
+            strategy: SelectionStrategy::Any,
         }
     }
 
+    /// Prefer the local node when it is not overloaded
+    pub fn prefer_local(mut self) -> Self {
+        self.strategy = SelectionStrategy::PreferLocal;
+        self
+    }
+
+    /// Whether this selector prefers the local node
+    pub fn is_prefer_local(&self) -> bool {
+        self.strategy == SelectionStrategy::PreferLocal
+    }
+
     /// Set target architecture
     pub fn with_arch(mut self, arch: impl Into<String>) -> Self {
         self.arch = Some(arch.into());
@@ -205,6 +235,21 @@ impl NodeSelector {
             }
         }
 
+        // Parse strategy
+        if let Some(strategy) = table.get("strategy") {
+            match strategy.as_str() {
+                Some("prefer_local") => selector = selector.prefer_local(),
+                Some("any") => {}
+                Some(other) => {
+                    return Err(CisError::invalid_input(
+                        "strategy",
+                        format!("unknown node selection strategy: {}", other),
+                    ))
+                }
+                None => {}
+            }
+        }
+
         Ok(selector)
     }
 }
@@ -256,16 +301,133 @@ pub struct NodeResources {
     pub gpu_memory_mb: Option<u64>,
 }
 
+/// Resource threshold above which the local node is considered overloaded
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ResourceThreshold {
+    /// Maximum CPU load (percentage of all cores, 1-minute load average)
+    pub cpu_max_percent: f32,
+
+    /// Minimum available memory in MB
+    pub memory_min_mb: u64,
+}
+
+impl Default for ResourceThreshold {
+    fn default() -> Self {
+        Self {
+            cpu_max_percent: 80.0,
+            memory_min_mb: 512,
+        }
+    }
+}
+
+impl ResourceThreshold {
+    /// Check whether the given load exceeds the threshold
+    pub fn is_overloaded(&self, load: &LocalLoad) -> bool {
+        load.cpu_percent > self.cpu_max_percent || load.available_memory_mb < self.memory_min_mb
+    }
+}
+
+/// Current load of the local node
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalLoad {
+    /// CPU load as percentage of all cores
+    pub cpu_percent: f32,
+
+    /// Available memory in MB
+    pub available_memory_mb: u64,
+}
+
+impl LocalLoad {
+    /// Read the current load of this machine
+    ///
+    /// Uses `/proc/loadavg` and `/proc/meminfo` on Linux and `sysinfo` on
+    /// other platforms. `sysinfo` has no load average on Windows, so only
+    /// available memory counts there. Returns `None` when the Linux files
+    /// cannot be parsed, in which case the local node is treated as not
+    /// overloaded.
+    pub fn read() -> Option<Self> {
+        let cpus = std::thread::available_parallelism()
+            .map(|n| n.get() as u32)
+            .unwrap_or(1);
+
+        #[cfg(target_os = "linux")]
+        {
+            let loadavg = std::fs::read_to_string("/proc/loadavg").ok()?;
+            let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+            Some(Self {
+                cpu_percent: parse_loadavg(&loadavg, cpus)?,
+                available_memory_mb: parse_meminfo(&meminfo)?,
+            })
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let mut system = sysinfo::System::new();
+            system.refresh_memory();
+            Some(Self {
+                cpu_percent: load_percent(sysinfo::System::load_average().one as f32, cpus),
+                available_memory_mb: system.available_memory() / 1024 / 1024,
+            })
+        }
+    }
+}
+
+/// Convert a 1-minute load average into a CPU percentage across `cpus` cores
+fn load_percent(load: f32, cpus: u32) -> f32 {
+    load / cpus.max(1) as f32 * 100.0
+}
+
+/// Parse `/proc/loadavg` into a CPU percentage across `cpus` cores
+fn parse_loadavg(content: &str, cpus: u32) -> Option<f32> {
+    let load: f32 = content.split_whitespace().next()?.parse().ok()?;
+    Some(load_percent(load, cpus))
+}
+
+/// Parse available memory (MB) from `/proc/meminfo`
+///
+/// Prefers `MemAvailable`, falling back to `MemFree` on old kernels.
+fn parse_meminfo(content: &str) -> Option<u64> {
+    let field = |name: &str| {
+        content.lines().find_map(|line| {
+            let rest = line.strip_prefix(name)?.strip_prefix(':')?;
+            rest.split_whitespace().next()?.parse::<u64>().ok()
+        })
+    };
+    field("MemAvailable").or_else(|| field("MemFree")).map(|kb| kb / 1024)
+}
+
 /// Node selector filter for batch operations
 #[derive(Debug, Clone)]
 pub struct NodeSelectorFilter {
     selectors: Vec<NodeSelector>,
+
+    /// ID of the local node (used by `PreferLocal`)
+    pub local_node_id: Option<String>,
+
+    /// Threshold above which the local node is skipped
+    pub resource_threshold: Option<ResourceThreshold>,
 }
 
 impl NodeSelectorFilter {
     /// Create a new filter from multiple selectors
     pub fn new(selectors: Vec<NodeSelector>) -> Self {
-        Self { selectors }
+        Self {
+            selectors,
+            local_node_id: None,
+            resource_threshold: None,
+        }
+    }
+
+    /// Set the local node ID
+    pub fn with_local_node(mut self, node_id: impl Into<String>) -> Self {
+        self.local_node_id = Some(node_id.into());
+        self
+    }
+
+    /// Set the local overload threshold
+    pub fn with_resource_threshold(mut self, threshold: ResourceThreshold) -> Self {
+        self.resource_threshold = Some(threshold);
+        self
     }
 
     /// Filter nodes that match any of the selectors
@@ -284,8 +446,44 @@ impl NodeSelectorFilter {
 
     /// Find the best matching node based on selector criteria
     pub fn find_best_match(&self, nodes: &[NodeInfo]) -> Option<NodeInfo> {
+        let load = if self.prefers_local() { LocalLoad::read() } else { None };
+        self.find_best_match_with_load(nodes, load.as_ref())
+    }
+
+    /// Find the best matching node given the local node's current load
+    ///
+    /// Under `PreferLocal` the local node wins unless `load` exceeds the
+    /// resource threshold; it is still used as a last resort when no remote
+    /// node matches.
+    pub fn find_best_match_with_load(
+        &self,
+        nodes: &[NodeInfo],
+        load: Option<&LocalLoad>,
+    ) -> Option<NodeInfo> {
         let matching = self.filter_nodes(nodes);
-        matching.into_iter().next()
+
+        let local_id = match (&self.local_node_id, self.prefers_local()) {
+            (Some(id), true) => id,
+            _ => return matching.into_iter().next(),
+        };
+
+        let overloaded = match (&self.resource_threshold, load) {
+            (Some(threshold), Some(load)) => threshold.is_overloaded(load),
+            _ => false,
+        };
+
+        let (local, remote): (Vec<_>, Vec<_>) =
+            matching.into_iter().partition(|n| &n.node_id == local_id);
+
+        if overloaded {
+            remote.into_iter().next().or_else(|| local.into_iter().next())
+        } else {
+            local.into_iter().next().or_else(|| remote.into_iter().next())
+        }
+    }
+
+    fn prefers_local(&self) -> bool {
+        self.selectors.iter().any(|s| s.is_prefer_local())
     }
 }
 
@@ -418,4 +616,83 @@ mod tests {
         assert!(filtered.iter().any(|n| n.node_id == "mac-1"));
         assert!(filtered.iter().any(|n| n.node_id == "linux-1"));
     }
+
+    fn plain_node(id: &str) -> NodeInfo {
+        NodeInfo {
+            node_id: id.to_string(),
+            arch: None,
+            os: None,
+            features: None,
+            resources: None,
+            labels: None,
+        }
+    }
+
+    #[test]
+    fn test_prefer_local() {
+        let filter = NodeSelectorFilter::new(vec![NodeSelector::new().prefer_local()])
+            .with_local_node("local")
+            .with_resource_threshold(ResourceThreshold {
+                cpu_max_percent: 80.0,
+                memory_min_mb: 1024,
+            });
+        let nodes = vec![plain_node("remote-1"), plain_node("local")];
+
+        let idle = LocalLoad { cpu_percent: 20.0, available_memory_mb: 4096 };
+        let best = filter.find_best_match_with_load(&nodes, Some(&idle)).unwrap();
+        assert_eq!(best.node_id, "local");
+
+        // 无法获取负载时仍优先本地
+        let best = filter.find_best_match_with_load(&nodes, None).unwrap();
+        assert_eq!(best.node_id, "local");
+
+        let busy_cpu = LocalLoad { cpu_percent: 95.0, available_memory_mb: 4096 };
+        let best = filter.find_best_match_with_load(&nodes, Some(&busy_cpu)).unwrap();
+        assert_eq!(best.node_id, "remote-1");
+
+        let low_mem = LocalLoad { cpu_percent: 10.0, available_memory_mb: 256 };
+        let best = filter.find_best_match_with_load(&nodes, Some(&low_mem)).unwrap();
+        assert_eq!(best.node_id, "remote-1");
+
+        // 没有远程节点时回退到本地
+        let only_local = vec![plain_node("local")];
+        let best = filter.find_best_match_with_load(&only_local, Some(&busy_cpu)).unwrap();
+        assert_eq!(best.node_id, "local");
+    }
+
+    #[test]
+    fn test_any_strategy_ignores_local() {
+        let filter = NodeSelectorFilter::new(vec![NodeSelector::new()]).with_local_node("local");
+        let nodes = vec![plain_node("remote-1"), plain_node("local")];
+        let best = filter.find_best_match_with_load(&nodes, None).unwrap();
+        assert_eq!(best.node_id, "remote-1");
+    }
+
+    #[test]
+    fn test_parse_proc_files() {
+        assert_eq!(parse_loadavg("2.00 1.50 1.00 3/512 12345\n", 4), Some(50.0));
+        assert_eq!(parse_loadavg("", 4), None);
+
+        let meminfo = "MemTotal:       16384000 kB\nMemFree:         1024000 kB\nMemAvailable:    8192000 kB\n";
+        assert_eq!(parse_meminfo(meminfo), Some(8000));
+        assert_eq!(parse_meminfo("MemFree:         2048000 kB\n"), Some(2000));
+        assert_eq!(parse_meminfo("garbage"), None);
+    }
+
+    #[test]
+    fn test_read_local_load() {
+        let load = LocalLoad::read().expect("local load should be readable");
+        assert!(load.cpu_percent >= 0.0);
+        assert!(load.available_memory_mb > 0);
+    }
+
+    #[test]
+    fn test_strategy_from_toml() {
+        let table: toml::Value = toml::from_str("strategy = \"prefer_local\"").unwrap();
+        let selector = NodeSelector::from_toml_table(&table).unwrap();
+        assert!(selector.is_prefer_local());
+
+        let table: toml::Value = toml::from_str("strategy = \"nearest\"").unwrap();
+        assert!(NodeSelector::from_toml_table(&table).is_err());
+    }
 }
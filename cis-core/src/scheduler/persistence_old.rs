@@ -5,8 +5,8 @@
 use rusqlite::{Connection, OptionalExtension};
 
 use crate::error::Result;
-use crate::scheduler::{DagRun, DagRunStatus, DagSpec};
-use crate::types::{Task, TaskStatus};
+use crate::scheduler::{DagRun, DagRunStatus, DagSpec, DagTemplate};
+use crate::types::{Task, TaskLevel, TaskStatus};
 
 /// DAG 持久化存储
 pub struct DagPersistence {
@@ -35,6 +35,18 @@ impl DagPersistence {
             [],
         )?;
 
+        // 创建 dag_spec_versions 表 - 保留每个版本的规格，用于版本对比
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS dag_spec_versions (
+                dag_id TEXT NOT NULL,
+                version INTEGER NOT NULL,
+                spec_json TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (dag_id, version)
+            )",
+            [],
+        )?;
+
         // 创建 dag_runs 表 - 存储 DAG 运行实例
         conn.execute(
             "CREATE TABLE IF NOT EXISTS dag_runs (
@@ -84,6 +96,54 @@ impl DagPersistence {
             [],
         )?;
 
+        // 创建 task_outputs 表 - 存储任务 stdout/stderr（按块追加）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS task_outputs (
+                run_id TEXT NOT NULL,
+                task_id TEXT NOT NULL,
+                stdout TEXT NOT NULL DEFAULT '',
+                stderr TEXT NOT NULL DEFAULT '',
+                exit_code INTEGER,
+                duration_ms INTEGER NOT NULL DEFAULT 0,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (run_id, task_id)
+            )",
+            [],
+        )?;
+
+        // 创建 dag_checkpoints 表 - 每个运行一行，保存最近一次检查点
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS dag_checkpoints (
+                run_id TEXT PRIMARY KEY,
+                checkpoint_json TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // 创建 dag_templates 表 - 存储参数化 DAG 模板
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS dag_templates (
+                name TEXT PRIMARY KEY,
+                template_json TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // 创建 task_level_history 表 - 记录任务决策级别变更
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS task_level_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id TEXT NOT NULL,
+                old_level TEXT NOT NULL,
+                new_level TEXT NOT NULL,
+                changed_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
         // 创建索引
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_task_executions_run_id ON task_executions(run_id)",
@@ -123,9 +183,52 @@ impl DagPersistence {
             ],
         )?;
 
+        // 同一版本重复保存时以最后一次为准
+        self.db.execute(
+            "INSERT OR REPLACE INTO dag_spec_versions (dag_id, version, spec_json, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![spec.dag_id, spec.version, spec_json, chrono::Utc::now().to_rfc3339()],
+        )?;
+
         Ok(())
     }
 
+    /// 加载 DAG 规格的指定版本
+    ///
+    /// 版本历史表之前保存的规格只能取到当前版本。
+    pub fn load_spec_version(&self, dag_id: &str, version: i64) -> Result<Option<DagSpec>> {
+        let spec_json: Option<String> = self
+            .db
+            .query_row(
+                "SELECT spec_json FROM dag_spec_versions WHERE dag_id = ?1 AND version = ?2
+                 UNION ALL
+                 SELECT spec_json FROM dag_specs WHERE dag_id = ?1 AND version = ?2
+                 LIMIT 1",
+                rusqlite::params![dag_id, version],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        match spec_json {
+            Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// 列出 DAG 规格的已保存版本（升序）
+    pub fn list_spec_versions(&self, dag_id: &str) -> Result<Vec<i64>> {
+        let mut stmt = self.db.prepare(
+            "SELECT version FROM dag_spec_versions WHERE dag_id = ?1
+             UNION
+             SELECT version FROM dag_specs WHERE dag_id = ?1
+             ORDER BY version",
+        )?;
+        let versions = stmt
+            .query_map([dag_id], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<i64>, _>>()?;
+        Ok(versions)
+    }
+
     /// 加载 DAG 规格
     pub fn load_spec(&self, dag_id: &str) -> Result<Option<DagSpec>> {
         let mut stmt = self
@@ -146,6 +249,7 @@ impl DagPersistence {
     /// 删除 DAG 规格
     pub fn delete_spec(&self, dag_id: &str) -> Result<()> {
         self.db.execute("DELETE FROM dag_specs WHERE dag_id = ?1", [dag_id])?;
+        self.db.execute("DELETE FROM dag_spec_versions WHERE dag_id = ?1", [dag_id])?;
         Ok(())
     }
 
@@ -255,6 +359,163 @@ impl DagPersistence {
         Ok(())
     }
 
+    // ==================== DagTemplate 存储 ====================
+
+    /// 保存 DAG 模板（同名覆盖）
+    pub fn save_template(&self, template: &DagTemplate) -> Result<()> {
+        template.validate()?;
+        let now = chrono::Utc::now().to_rfc3339();
+
+        self.db.execute(
+            "INSERT INTO dag_templates (name, template_json, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?3)
+             ON CONFLICT(name) DO UPDATE SET
+                template_json = excluded.template_json,
+                updated_at = excluded.updated_at",
+            rusqlite::params![template.name, serde_json::to_string(template)?, now],
+        )?;
+        Ok(())
+    }
+
+    /// 加载 DAG 模板
+    pub fn load_template(&self, name: &str) -> Result<Option<DagTemplate>> {
+        let json: Option<String> = self
+            .db
+            .query_row(
+                "SELECT template_json FROM dag_templates WHERE name = ?1",
+                [name],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(json.map(|j| serde_json::from_str(&j)).transpose()?)
+    }
+
+    /// 列出所有 DAG 模板，按名称排序
+    pub fn list_templates(&self) -> Result<Vec<DagTemplate>> {
+        let mut stmt = self
+            .db
+            .prepare("SELECT template_json FROM dag_templates ORDER BY name")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+
+        let mut templates = Vec::new();
+        for row in rows {
+            templates.push(serde_json::from_str(&row?)?);
+        }
+        Ok(templates)
+    }
+
+    /// 删除 DAG 模板
+    pub fn delete_template(&self, name: &str) -> Result<bool> {
+        let deleted = self
+            .db
+            .execute("DELETE FROM dag_templates WHERE name = ?1", [name])?;
+        Ok(deleted > 0)
+    }
+
+    // ==================== 数据库维护 ====================
+
+    /// 统计最后更新时间早于 `older_than` 的运行数
+    pub fn count_old_runs(
+        &self,
+        older_than: std::time::Duration,
+        status_filter: Option<DagRunStatus>,
+    ) -> Result<usize> {
+        let (cutoff, status) = Self::gc_params(older_than, status_filter);
+        let count: i64 = self.db.query_row(
+            &format!("SELECT COUNT(*) FROM dag_runs WHERE {}", OLD_RUNS_FILTER),
+            rusqlite::params![cutoff, status],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    /// 删除最后更新时间早于 `older_than` 的运行
+    ///
+    /// 同时删除这些运行的执行记录、任务输出和检查点，返回删除的运行数。
+    /// 删除后文件大小不变，需调用 [`compact`](Self::compact) 回收空间。
+    pub fn vacuum_old_runs(
+        &self,
+        older_than: std::time::Duration,
+        status_filter: Option<DagRunStatus>,
+    ) -> Result<usize> {
+        let (cutoff, status) = Self::gc_params(older_than, status_filter);
+        let tx = self.db.unchecked_transaction()?;
+
+        for table in ["task_executions", "task_outputs", "dag_checkpoints"] {
+            tx.execute(
+                &format!(
+                    "DELETE FROM {} WHERE run_id IN (SELECT run_id FROM dag_runs WHERE {})",
+                    table, OLD_RUNS_FILTER
+                ),
+                rusqlite::params![cutoff, status],
+            )?;
+        }
+        let deleted = tx.execute(
+            &format!("DELETE FROM dag_runs WHERE {}", OLD_RUNS_FILTER),
+            rusqlite::params![cutoff, status],
+        )?;
+
+        tx.commit()?;
+        Ok(deleted)
+    }
+
+    /// 执行 `VACUUM` 回收已删除数据占用的空间
+    pub fn compact(&self) -> Result<()> {
+        self.db.execute_batch("VACUUM")?;
+        Ok(())
+    }
+
+    /// 数据库占用的字节数（`page_count * page_size`）
+    pub fn database_size(&self) -> Result<u64> {
+        let page_count: i64 = self.db.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+        let page_size: i64 = self.db.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+        Ok((page_count * page_size) as u64)
+    }
+
+    /// 清理旧运行并压缩数据库
+    ///
+    /// `dry_run` 时只统计待删除的运行数，不修改数据库。
+    pub fn gc(
+        &self,
+        older_than: std::time::Duration,
+        status_filter: Option<DagRunStatus>,
+        dry_run: bool,
+    ) -> Result<GcReport> {
+        if dry_run {
+            return Ok(GcReport {
+                deleted_runs: self.count_old_runs(older_than, status_filter)?,
+                freed_bytes: 0,
+                dry_run,
+            });
+        }
+
+        let before = self.database_size()?;
+        let deleted_runs = self.vacuum_old_runs(older_than, status_filter)?;
+        if deleted_runs > 0 {
+            self.compact()?;
+        }
+        let after = self.database_size()?;
+
+        Ok(GcReport {
+            deleted_runs,
+            freed_bytes: before.saturating_sub(after),
+            dry_run,
+        })
+    }
+
+    /// 截止时间（RFC 3339）和状态过滤参数
+    fn gc_params(
+        older_than: std::time::Duration,
+        status_filter: Option<DagRunStatus>,
+    ) -> (String, Option<String>) {
+        let older_than = chrono::Duration::from_std(older_than).unwrap_or(chrono::Duration::MAX);
+        let cutoff = chrono::Utc::now()
+            .checked_sub_signed(older_than)
+            .unwrap_or(chrono::DateTime::<chrono::Utc>::MIN_UTC);
+        // 与 save_run 中的 `{:?}` 格式一致
+        (cutoff.to_rfc3339(), status_filter.map(|s| format!("{:?}", s)))
+    }
+
     /// 获取数据库连接（用于高级操作）
     pub fn connection(&self) -> &Connection {
         &self.db
@@ -373,6 +634,70 @@ impl DagPersistence {
         }
     }
 
+    /// 更新 Task 决策级别，级别发生变化时记录到 `task_level_history`
+    ///
+    /// 任务不存在时返回 `false`。
+    pub fn update_task_level(&self, task_id: &str, level: &TaskLevel) -> Result<bool> {
+        let Some(mut task) = self.load_task(task_id)? else {
+            return Ok(false);
+        };
+        if &task.level == level {
+            return Ok(true);
+        }
+
+        let old_level = serde_json::to_string(&task.level)?;
+        task.level = level.clone();
+
+        let tx = self.db.unchecked_transaction()?;
+        self.save_task(&task)?;
+        tx.execute(
+            "INSERT INTO task_level_history (task_id, old_level, new_level, changed_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![
+                task_id,
+                old_level,
+                serde_json::to_string(level)?,
+                chrono::Utc::now().to_rfc3339(),
+            ],
+        )?;
+        tx.commit()?;
+
+        Ok(true)
+    }
+
+    /// 列出 `since` 之后的决策级别变更，最新的在前
+    pub fn list_task_level_changes(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<TaskLevelChange>> {
+        let mut stmt = self.db.prepare(
+            "SELECT task_id, old_level, new_level, changed_at FROM task_level_history
+             WHERE changed_at >= ?1 ORDER BY changed_at DESC, id DESC",
+        )?;
+        let rows = stmt.query_map([since.to_rfc3339()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+
+        let mut changes = Vec::new();
+        for row in rows {
+            let (task_id, old_level, new_level, changed_at) = row?;
+            changes.push(TaskLevelChange {
+                task_id,
+                old_level: serde_json::from_str(&old_level)?,
+                new_level: serde_json::from_str(&new_level)?,
+                changed_at: chrono::DateTime::parse_from_rfc3339(&changed_at)
+                    .map(|t| t.with_timezone(&chrono::Utc))
+                    .unwrap_or_else(|_| chrono::Utc::now()),
+            });
+        }
+        Ok(changes)
+    }
+
     // ==================== Task Execution 存储 ====================
 
     /// 保存任务执行记录
@@ -471,6 +796,235 @@ impl DagPersistence {
         
         Ok(executions)
     }
+
+    /// 按任务统计历史执行耗时的 P95
+    ///
+    /// 只统计已完成且有结束时间的执行记录。指定 `dag_id` 时仅统计该 DAG 的运行。
+    pub fn task_duration_p95(
+        &self,
+        dag_id: Option<&str>,
+    ) -> Result<std::collections::HashMap<String, std::time::Duration>> {
+        let sql = if dag_id.is_some() {
+            "SELECT e.task_id, e.started_at, e.completed_at FROM task_executions e
+             JOIN dag_runs r ON r.run_id = e.run_id
+             WHERE e.status = 'Completed' AND e.completed_at IS NOT NULL AND r.dag_id = ?1"
+        } else {
+            "SELECT task_id, started_at, completed_at FROM task_executions
+             WHERE status = 'Completed' AND completed_at IS NOT NULL"
+        };
+
+        let mut stmt = self.db.prepare(sql)?;
+        let params: Vec<&dyn rusqlite::ToSql> = match &dag_id {
+            Some(id) => vec![id],
+            None => vec![],
+        };
+        let rows = stmt.query_map(params.as_slice(), |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+
+        let mut samples: std::collections::HashMap<String, Vec<std::time::Duration>> =
+            std::collections::HashMap::new();
+        for row in rows {
+            let (task_id, started, completed) = row?;
+            let (Ok(started), Ok(completed)) = (
+                started.parse::<chrono::DateTime<chrono::Utc>>(),
+                completed.parse::<chrono::DateTime<chrono::Utc>>(),
+            ) else {
+                continue;
+            };
+            if let Ok(duration) = (completed - started).to_std() {
+                samples.entry(task_id).or_default().push(duration);
+            }
+        }
+
+        Ok(samples
+            .into_iter()
+            .map(|(task_id, mut durations)| {
+                durations.sort_unstable();
+                // nearest-rank 百分位
+                let rank = (durations.len() * 95).div_ceil(100).max(1);
+                (task_id, durations[rank - 1])
+            })
+            .collect())
+    }
+
+    // ==================== Task Output 存储 ====================
+
+    /// 开始记录任务输出（清空同一 run/task 的旧输出）
+    pub fn begin_task_output(&self, run_id: &str, task_id: &str) -> Result<()> {
+        self.db.execute(
+            "INSERT OR REPLACE INTO task_outputs
+             (run_id, task_id, stdout, stderr, exit_code, duration_ms, updated_at)
+             VALUES (?1, ?2, '', '', NULL, 0, ?3)",
+            rusqlite::params![run_id, task_id, chrono::Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// 追加一块输出
+    ///
+    /// 长时间运行的任务按块写入，避免在内存中缓存完整输出
+    pub fn append_task_output(
+        &self,
+        run_id: &str,
+        task_id: &str,
+        stream: OutputStream,
+        chunk: &str,
+    ) -> Result<()> {
+        let sql = match stream {
+            OutputStream::Stdout => {
+                "UPDATE task_outputs SET stdout = stdout || ?3, updated_at = ?4
+                 WHERE run_id = ?1 AND task_id = ?2"
+            }
+            OutputStream::Stderr => {
+                "UPDATE task_outputs SET stderr = stderr || ?3, updated_at = ?4
+                 WHERE run_id = ?1 AND task_id = ?2"
+            }
+        };
+        self.db.execute(
+            sql,
+            rusqlite::params![run_id, task_id, chunk, chrono::Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// 记录任务结束（退出码与耗时）
+    pub fn finish_task_output(
+        &self,
+        run_id: &str,
+        task_id: &str,
+        exit_code: i32,
+        duration_ms: u64,
+    ) -> Result<()> {
+        self.db.execute(
+            "UPDATE task_outputs SET exit_code = ?3, duration_ms = ?4, updated_at = ?5
+             WHERE run_id = ?1 AND task_id = ?2",
+            rusqlite::params![
+                run_id,
+                task_id,
+                exit_code,
+                duration_ms as i64,
+                chrono::Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 一次性保存完整输出
+    pub fn save_task_output(&self, run_id: &str, task_id: &str, output: &TaskOutput) -> Result<()> {
+        self.db.execute(
+            "INSERT OR REPLACE INTO task_outputs
+             (run_id, task_id, stdout, stderr, exit_code, duration_ms, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![
+                run_id,
+                task_id,
+                output.stdout,
+                output.stderr,
+                output.exit_code,
+                output.duration_ms as i64,
+                chrono::Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 加载任务输出
+    pub fn load_task_output(&self, run_id: &str, task_id: &str) -> Result<Option<TaskOutput>> {
+        let mut stmt = self.db.prepare(
+            "SELECT stdout, stderr, exit_code, duration_ms FROM task_outputs
+             WHERE run_id = ?1 AND task_id = ?2",
+        )?;
+
+        let output = stmt
+            .query_row([run_id, task_id], |row| {
+                Ok(TaskOutput {
+                    stdout: row.get(0)?,
+                    stderr: row.get(1)?,
+                    // 仍在运行的任务没有退出码，用 -1 表示
+                    exit_code: row.get::<_, Option<i32>>(2)?.unwrap_or(-1),
+                    duration_ms: row.get::<_, i64>(3)? as u64,
+                })
+            })
+            .optional()?;
+
+        Ok(output)
+    }
+
+    // ==================== 检查点 ====================
+
+    /// 保存运行检查点（覆盖同一 run_id 的旧检查点）
+    pub fn save_checkpoint(&self, run_id: &str, checkpoint_json: &str) -> Result<()> {
+        self.db.execute(
+            "INSERT OR REPLACE INTO dag_checkpoints (run_id, checkpoint_json, updated_at)
+             VALUES (?1, ?2, ?3)",
+            rusqlite::params![run_id, checkpoint_json, chrono::Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// 加载运行检查点
+    pub fn load_checkpoint(&self, run_id: &str) -> Result<Option<String>> {
+        let checkpoint = self
+            .db
+            .query_row(
+                "SELECT checkpoint_json FROM dag_checkpoints WHERE run_id = ?1",
+                [run_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(checkpoint)
+    }
+}
+
+/// 旧运行筛选条件（?1 = 截止时间，?2 = 状态或 NULL）
+const OLD_RUNS_FILTER: &str = "updated_at < ?1 AND (?2 IS NULL OR status = ?2)";
+
+/// 数据库清理结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcReport {
+    /// 删除（或 dry-run 时待删除）的运行数
+    pub deleted_runs: usize,
+    /// 压缩后释放的字节数
+    pub freed_bytes: u64,
+    /// 是否为 dry-run
+    pub dry_run: bool,
+}
+
+/// 任务决策级别变更记录
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct TaskLevelChange {
+    pub task_id: String,
+    pub old_level: TaskLevel,
+    pub new_level: TaskLevel,
+    pub changed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 任务输出
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TaskOutput {
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: i32,
+    pub duration_ms: u64,
+}
+
+impl TaskOutput {
+    /// 非零退出码视为失败
+    pub fn is_success(&self) -> bool {
+        self.exit_code == 0
+    }
+}
+
+/// 输出流类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
 }
 
 /// 任务执行记录
@@ -536,6 +1090,28 @@ mod tests {
         assert_eq!(loaded.dag.node_count(), 1);
     }
 
+    #[test]
+    fn test_spec_versions() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let persistence = DagPersistence::new(temp_file.path().to_str().unwrap()).unwrap();
+
+        let mut spec = DagSpec::new("deploy".to_string(), vec![]);
+        persistence.save_spec(&spec).unwrap();
+        spec.version = 2;
+        spec.description = "v2".to_string();
+        persistence.save_spec(&spec).unwrap();
+
+        assert_eq!(persistence.list_spec_versions("deploy").unwrap(), vec![1, 2]);
+        let v1 = persistence.load_spec_version("deploy", 1).unwrap().unwrap();
+        assert_eq!(v1.version, 1);
+        assert!(v1.description.is_empty());
+        assert_eq!(persistence.load_spec_version("deploy", 2).unwrap().unwrap().description, "v2");
+        assert!(persistence.load_spec_version("deploy", 3).unwrap().is_none());
+
+        persistence.delete_spec("deploy").unwrap();
+        assert!(persistence.list_spec_versions("deploy").unwrap().is_empty());
+    }
+
     #[test]
     fn test_persistence_list_runs() {
         let temp_file = NamedTempFile::new().unwrap();
@@ -574,4 +1150,168 @@ mod tests {
         persistence.delete_run(&run_id).unwrap();
         assert!(persistence.load_run(&run_id).unwrap().is_none());
     }
+    #[test]
+    fn test_vacuum_old_runs() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let persistence = DagPersistence::new(temp_file.path().to_str().unwrap()).unwrap();
+        let day = std::time::Duration::from_secs(24 * 3600);
+
+        let save = |status: DagRunStatus, age_days: i64| {
+            let mut dag = TaskDag::new();
+            dag.add_node("task1".to_string(), vec![]).unwrap();
+            dag.initialize();
+            let mut run = DagRun::new(dag);
+            run.status = status;
+            run.updated_at = chrono::Utc::now() - chrono::Duration::days(age_days);
+            persistence.save_run_simple(&run).unwrap();
+            persistence
+                .save_task_output(&run.run_id, "task1", &TaskOutput::default())
+                .unwrap();
+            persistence.save_checkpoint(&run.run_id, "{}").unwrap();
+            run.run_id
+        };
+        let old_completed = save(DagRunStatus::Completed, 40);
+        let old_failed = save(DagRunStatus::Failed, 40);
+        let recent = save(DagRunStatus::Completed, 1);
+
+        // dry-run 只统计
+        let report = persistence
+            .gc(day * 30, Some(DagRunStatus::Completed), true)
+            .unwrap();
+        assert_eq!(report.deleted_runs, 1);
+        assert!(persistence.load_run(&old_completed).unwrap().is_some());
+
+        let deleted = persistence
+            .vacuum_old_runs(day * 30, Some(DagRunStatus::Completed))
+            .unwrap();
+        assert_eq!(deleted, 1);
+        assert!(persistence.load_run(&old_completed).unwrap().is_none());
+        assert!(persistence.load_task_output(&old_completed, "task1").unwrap().is_none());
+        assert!(persistence.load_checkpoint(&old_completed).unwrap().is_none());
+        assert!(persistence.load_run(&old_failed).unwrap().is_some());
+        assert!(persistence.load_run(&recent).unwrap().is_some());
+
+        // 不按状态过滤
+        let report = persistence.gc(day * 30, None, false).unwrap();
+        assert_eq!(report.deleted_runs, 1);
+        assert!(!report.dry_run);
+        assert!(persistence.load_run(&old_failed).unwrap().is_none());
+        assert!(persistence.load_task_output(&recent, "task1").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_task_duration_p95() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let persistence = DagPersistence::new(temp_file.path().to_str().unwrap()).unwrap();
+
+        let start = chrono::Utc::now();
+        for secs in 1..=20 {
+            persistence
+                .save_task_execution(&TaskExecution {
+                    execution_id: None,
+                    run_id: format!("run-{}", secs),
+                    task_id: "build".to_string(),
+                    status: TaskExecutionStatus::Completed,
+                    output: None,
+                    error: None,
+                    started_at: start,
+                    completed_at: Some(start + chrono::Duration::seconds(secs)),
+                    retry_count: 0,
+                })
+                .unwrap();
+        }
+        // 失败的执行不计入
+        persistence
+            .save_task_execution(&TaskExecution {
+                execution_id: None,
+                run_id: "run-failed".to_string(),
+                task_id: "build".to_string(),
+                status: TaskExecutionStatus::Failed,
+                output: None,
+                error: None,
+                started_at: start,
+                completed_at: Some(start + chrono::Duration::seconds(500)),
+                retry_count: 0,
+            })
+            .unwrap();
+
+        let p95 = persistence.task_duration_p95(None).unwrap();
+        assert_eq!(p95["build"], std::time::Duration::from_secs(19));
+        assert!(persistence.task_duration_p95(Some("other-dag")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_task_output_chunked_append() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let persistence = DagPersistence::new(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert!(persistence.load_task_output("run-1", "build").unwrap().is_none());
+
+        persistence.begin_task_output("run-1", "build").unwrap();
+        persistence.append_task_output("run-1", "build", OutputStream::Stdout, "line 1\n").unwrap();
+        persistence.append_task_output("run-1", "build", OutputStream::Stderr, "warn\n").unwrap();
+        persistence.append_task_output("run-1", "build", OutputStream::Stdout, "line 2\n").unwrap();
+
+        // 运行中：尚无退出码
+        let partial = persistence.load_task_output("run-1", "build").unwrap().unwrap();
+        assert_eq!(partial.stdout, "line 1\nline 2\n");
+        assert_eq!(partial.exit_code, -1);
+
+        persistence.finish_task_output("run-1", "build", 2, 150).unwrap();
+        let output = persistence.load_task_output("run-1", "build").unwrap().unwrap();
+        assert_eq!(output.stderr, "warn\n");
+        assert_eq!(output.exit_code, 2);
+        assert_eq!(output.duration_ms, 150);
+        assert!(!output.is_success());
+    }
+
+    #[test]
+    fn test_update_task_level_history() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let persistence = DagPersistence::new(temp_file.path().to_str().unwrap()).unwrap();
+        let since = chrono::Utc::now() - chrono::Duration::seconds(1);
+
+        assert!(!persistence.update_task_level("missing", &TaskLevel::Confirmed).unwrap());
+
+        let task = Task::new("t1".to_string(), "Deploy".to_string(), "ops".to_string());
+        persistence.save_task(&task).unwrap();
+
+        assert!(persistence.update_task_level("t1", &TaskLevel::Confirmed).unwrap());
+        // 级别未变化时不记录
+        assert!(persistence.update_task_level("t1", &TaskLevel::Confirmed).unwrap());
+        assert_eq!(persistence.load_task("t1").unwrap().unwrap().level, TaskLevel::Confirmed);
+
+        let changes = persistence.list_task_level_changes(since).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].old_level, TaskLevel::Mechanical { retry: 3 });
+        assert_eq!(changes[0].new_level, TaskLevel::Confirmed);
+        assert!(persistence
+            .list_task_level_changes(chrono::Utc::now() + chrono::Duration::seconds(1))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_template_storage() {
+        use crate::scheduler::{DagTemplate, TemplateParameter};
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let persistence = DagPersistence::new(temp_file.path().to_str().unwrap()).unwrap();
+
+        let mut template = DagTemplate::new(
+            "deploy",
+            DagSpec::new("deploy-${env}".to_string(), vec![]),
+            vec![TemplateParameter { name: "env".to_string(), default: None, required: true }],
+        );
+        persistence.save_template(&template).unwrap();
+        template.parameters[0].default = Some("staging".to_string());
+        persistence.save_template(&template).unwrap();
+
+        let loaded = persistence.load_template("deploy").unwrap().unwrap();
+        assert_eq!(loaded.parameters[0].default.as_deref(), Some("staging"));
+        assert_eq!(persistence.list_templates().unwrap().len(), 1);
+
+        assert!(persistence.delete_template("deploy").unwrap());
+        assert!(persistence.load_template("deploy").unwrap().is_none());
+    }
 }
@@ -16,7 +16,7 @@ use tracing::{info, warn};
 
 use crate::decision::{DecisionEngine, DecisionResult};
 use crate::error::{CisError, Result};
-use crate::scheduler::{DagScheduler, PermissionResult};
+use crate::scheduler::{DagNodeStatus, DagPersistence, DagRun, DagScheduler, PermissionResult};
 use crate::skill::manifest::DagDefinition;
 use crate::skill::types::SkillType;
 use crate::skill::SkillManager;
@@ -104,6 +104,39 @@ impl SkillDagExecutor {
         }
     }
 
+    /// 收集已完成上游任务的 stdout，作为下游任务的环境变量
+    ///
+    /// 每个已完成的依赖 `step1` 生成 `TASK_STEP1_OUTPUT=<stdout>`，
+    /// 与 shell 命令替换一致去掉末尾换行。没有输出记录的依赖被跳过。
+    pub fn inject_outputs(
+        run: &DagRun,
+        task_id: &str,
+        outputs: &DagPersistence,
+    ) -> HashMap<String, String> {
+        let Some(node) = run.dag.get_node(task_id) else {
+            return HashMap::new();
+        };
+
+        node.dependencies
+            .iter()
+            .filter(|dep_id| {
+                run.dag.get_node_status(dep_id.as_str()) == Some(DagNodeStatus::Completed)
+            })
+            .filter_map(|dep_id| match outputs.load_task_output(&run.run_id, dep_id) {
+                Ok(output) => output.map(|output| {
+                    (
+                        output_env_var(dep_id),
+                        output.stdout.trim_end_matches('\n').to_string(),
+                    )
+                }),
+                Err(e) => {
+                    warn!("Failed to load output of task '{}': {}", dep_id, e);
+                    None
+                }
+            })
+            .collect()
+    }
+
     /// 获取输入发送器（用于外部发送用户输入）
     pub fn input_sender(&self) -> mpsc::Sender<UserInput> {
         self.input_tx.clone()
@@ -595,6 +628,14 @@ impl SkillDagExecutor {
 }
 
 
+/// 上游任务输出对应的环境变量名（`step-1` -> `TASK_STEP_1_OUTPUT`）
+pub fn output_env_var(task_id: &str) -> String {
+    let name: String = task_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    format!("TASK_{}_OUTPUT", name)
+}
 
 /// 为 DagScheduler 添加辅助方法
 impl DagScheduler {
@@ -719,4 +760,36 @@ mod tests {
 
         assert_eq!(executor.classify_error(&blocking_result), FailureType::Blocking);
     }
+
+    #[test]
+    fn test_output_env_var() {
+        assert_eq!(output_env_var("step1"), "TASK_STEP1_OUTPUT");
+        assert_eq!(output_env_var("build-docs.v2"), "TASK_BUILD_DOCS_V2_OUTPUT");
+    }
+
+    #[test]
+    fn test_inject_outputs() {
+        let mut dag = crate::scheduler::TaskDag::new();
+        dag.add_node("step1".to_string(), vec![]).unwrap();
+        dag.add_node("step2".to_string(), vec![]).unwrap();
+        dag.add_node("step3".to_string(), vec!["step1".to_string(), "step2".to_string()])
+            .unwrap();
+        dag.initialize();
+        dag.mark_completed("step1".to_string()).unwrap();
+
+        let run = DagRun::with_run_id(dag, "run-1".to_string());
+        let outputs = DagPersistence::new(":memory:").unwrap();
+        for task_id in ["step1", "step2"] {
+            let output = crate::scheduler::TaskOutput {
+                stdout: format!("{} done\n", task_id),
+                ..Default::default()
+            };
+            outputs.save_task_output("run-1", task_id, &output).unwrap();
+        }
+
+        // step2 尚未完成，不注入
+        let env = SkillDagExecutor::inject_outputs(&run, "step3", &outputs);
+        assert_eq!(env.len(), 1);
+        assert_eq!(env["TASK_STEP1_OUTPUT"], "step1 done");
+    }
 }
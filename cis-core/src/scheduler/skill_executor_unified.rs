@@ -263,6 +263,7 @@ impl UnifiedDag {
                 condition: None,
                 idempotent: false,
                 outputs: None,
+                fail_fast: dag_task.fail_fast,
             })
             .collect();
 
@@ -336,6 +337,7 @@ mod tests {
                     condition: None,
                     idempotent: true,
                     outputs: None,
+                    fail_fast: false,
                 },
             ],
             execution_policy: ExecutionPolicy::AllSuccess,
@@ -399,7 +401,9 @@ mod tests {
                 agent_runtime: None,
                 reuse_agent: None,
                 keep_agent: false,
+                fail_fast: false,
                 agent_config: None,
+                node_selector: None,
             },
         ];
 
@@ -0,0 +1,184 @@
+//! # DAG Templates
+//!
+//! Parameterized [`DagSpec`] definitions. A template is a regular DAG spec
+//! whose `dag_id`, task `command`s and task `env` values may contain
+//! `${param_name}` placeholders; [`DagTemplate::instantiate`] substitutes them
+//! to produce a concrete spec (e.g. the same deploy DAG for staging and prod).
+//!
+//! Only declared parameters are substituted, so shell variables such as
+//! `${HOME}` in commands are left untouched.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CisError, Result};
+use crate::scheduler::DagSpec;
+
+/// Template parameter declaration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateParameter {
+    pub name: String,
+    /// Value used when the parameter is not provided
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Must be provided (or have a default)
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Parameterized DAG definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DagTemplate {
+    /// Template name, used as the storage key
+    pub name: String,
+    pub template: DagSpec,
+    #[serde(default)]
+    pub parameters: Vec<TemplateParameter>,
+}
+
+impl DagTemplate {
+    pub fn new(name: impl Into<String>, template: DagSpec, parameters: Vec<TemplateParameter>) -> Self {
+        Self {
+            name: name.into(),
+            template,
+            parameters,
+        }
+    }
+
+    /// Check the name and parameter declarations
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(CisError::invalid_input("Template name must not be empty"));
+        }
+
+        let mut seen = HashSet::new();
+        for param in &self.parameters {
+            let valid = !param.name.is_empty()
+                && param.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+            if !valid {
+                return Err(CisError::invalid_input(format!(
+                    "Invalid template parameter name: '{}'",
+                    param.name
+                )));
+            }
+            if !seen.insert(param.name.as_str()) {
+                return Err(CisError::invalid_input(format!(
+                    "Duplicate template parameter: {}",
+                    param.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Produce a concrete DAG spec from the given parameter values
+    ///
+    /// Fails if a required parameter without default is missing, or if a
+    /// value is given for a parameter the template does not declare.
+    pub fn instantiate(&self, params: HashMap<String, String>) -> Result<DagSpec> {
+        self.validate()?;
+
+        if let Some(unknown) = params
+            .keys()
+            .find(|k| !self.parameters.iter().any(|p| &p.name == *k))
+        {
+            return Err(CisError::invalid_input(format!(
+                "Unknown parameter '{}' for template {}",
+                unknown, self.name
+            )));
+        }
+
+        let mut values = HashMap::new();
+        let mut missing = Vec::new();
+        for param in &self.parameters {
+            match params.get(&param.name).or(param.default.as_ref()) {
+                Some(value) => {
+                    values.insert(param.name.as_str(), value.as_str());
+                }
+                None if param.required => missing.push(param.name.as_str()),
+                None => {}
+            }
+        }
+        if !missing.is_empty() {
+            return Err(CisError::invalid_input(format!(
+                "Missing required parameter(s) for template {}: {}",
+                self.name,
+                missing.join(", ")
+            )));
+        }
+
+        let mut spec = self.template.clone();
+        spec.dag_id = substitute(&spec.dag_id, &values);
+        for task in &mut spec.tasks {
+            task.command = substitute(&task.command, &values);
+            for value in task.env.values_mut() {
+                *value = substitute(value, &values);
+            }
+        }
+        Ok(spec)
+    }
+}
+
+/// Replace `${name}` for every parameter that has a value
+fn substitute(text: &str, values: &HashMap<&str, &str>) -> String {
+    let mut result = text.to_string();
+    for (name, value) in values {
+        result = result.replace(&format!("${{{}}}", name), value);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::DagTaskSpec;
+
+    fn deploy_template() -> DagTemplate {
+        let task = DagTaskSpec {
+            id: "deploy".to_string(),
+            task_type: "shell".to_string(),
+            command: "deploy.sh --env ${env} --home ${HOME}".to_string(),
+            depends_on: vec![],
+            env: HashMap::from([("REGION".to_string(), "${region}".to_string())]),
+            per_task_retry: None,
+            timeout_secs: None,
+            level: None,
+            fail_fast: false,
+        };
+        DagTemplate::new(
+            "deploy",
+            DagSpec::new("deploy-${env}".to_string(), vec![task]),
+            vec![
+                TemplateParameter { name: "env".to_string(), default: None, required: true },
+                TemplateParameter {
+                    name: "region".to_string(),
+                    default: Some("eu-west-1".to_string()),
+                    required: true,
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn test_instantiate() {
+        let template = deploy_template();
+
+        let spec = template
+            .instantiate(HashMap::from([("env".to_string(), "prod".to_string())]))
+            .unwrap();
+        assert_eq!(spec.dag_id, "deploy-prod");
+        assert_eq!(spec.tasks[0].command, "deploy.sh --env prod --home ${HOME}");
+        assert_eq!(spec.tasks[0].env["REGION"], "eu-west-1");
+
+        // Missing required parameter
+        assert!(template.instantiate(HashMap::new()).is_err());
+        // Undeclared parameter
+        assert!(template
+            .instantiate(HashMap::from([
+                ("env".to_string(), "prod".to_string()),
+                ("zone".to_string(), "a".to_string()),
+            ]))
+            .is_err());
+    }
+}
@@ -12,18 +12,28 @@
 //! 2. 与内存中的 snapshot 进行 diff
 //! 3. 如有变化，调用变更处理器
 //! 4. 更新内存中的 snapshot
+//!
+//! [`TodoListMonitor::watch`] 以文件监听代替轮询：`todo.md` 被修改后去抖
+//! 100ms 再加载，有变化时发送 [`SchedulerEvent::TodoListChanged`]。
 
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use notify::{EventKind, RecursiveMode, Watcher};
+use tokio::sync::{mpsc, Mutex, RwLock};
 use tokio::task::JoinHandle;
 use tracing::{debug, info, warn};
 
-use crate::error::Result;
+use crate::error::{CisError, Result};
 use crate::scheduler::{
     DagTodoList, TodoListDiff, DynamicTaskScheduler,
-    DagRun,
+    DagRun, EventRegistry, SchedulerEvent,
 };
 
+/// 文件事件去抖延迟：最后一次事件后等待该时长再加载，避免读到写了一半的文件
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
+
 /// TODO list 变更事件
 #[derive(Debug, Clone)]
 pub enum TodoChangeEvent {
@@ -131,6 +141,34 @@ impl TodoListLoader for FileSystemLoader {
     }
 }
 
+/// Markdown 文件加载器（GitHub 风格任务列表，如 `todo.md`）
+pub struct MarkdownFileLoader {
+    path: PathBuf,
+}
+
+impl MarkdownFileLoader {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoListLoader for MarkdownFileLoader {
+    async fn load(&self, _run_id: &str) -> Result<DagTodoList> {
+        if !self.path.exists() {
+            return Ok(DagTodoList::new());
+        }
+
+        let content = tokio::fs::read_to_string(&self.path).await
+            .map_err(|e| CisError::storage(
+                format!("Failed to read TODO list: {}", e)
+            ))?;
+
+        DagTodoList::import_from_markdown(&content)
+            .map_err(|e| CisError::scheduler(format!("Invalid TODO list: {}", e)))
+    }
+}
+
 /// 变更处理器
 pub type ChangeHandler = Box<dyn Fn(TodoChangeEvent, &mut DagRun) + Send + Sync>;
 
@@ -154,6 +192,8 @@ pub struct TodoListMonitor {
     monitor_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
     /// 动态调度器
     scheduler: Arc<Mutex<DynamicTaskScheduler>>,
+    /// 调度器事件监听器
+    events: Arc<EventRegistry>,
 }
 
 impl TodoListMonitor {
@@ -178,9 +218,21 @@ impl TodoListMonitor {
             running: Arc::new(Mutex::new(false)),
             monitor_handle: Arc::new(Mutex::new(None)),
             scheduler,
+            events: Arc::new(EventRegistry::new()),
         }
     }
 
+    /// 使用共享的事件注册表
+    pub fn with_event_registry(mut self, events: Arc<EventRegistry>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// 事件注册表（用于注册 `TodoListChanged` 监听器）
+    pub fn events(&self) -> &Arc<EventRegistry> {
+        &self.events
+    }
+
     /// 添加变更处理器
     pub fn on_change<F>(&mut self, handler: F)
     where
@@ -245,6 +297,78 @@ impl TodoListMonitor {
         Ok(())
     }
 
+    /// 监听 Markdown TODO 文件
+    ///
+    /// 文件每次被修改后去抖 100ms 再通过 [`MarkdownFileLoader`] 加载，与内存中
+    /// 的 TODO list 比对；有变化时同步到 DagRun 并发送
+    /// [`SchedulerEvent::TodoListChanged`]。返回的任务持有文件监听器，
+    /// abort 后停止监听。
+    pub fn watch(&self, path: &Path) -> Result<JoinHandle<()>> {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_os_string())
+            .ok_or_else(|| CisError::invalid_input(format!("Not a file path: {}", path.display())))?;
+        // 监听所在目录：编辑器常以"写临时文件再重命名"的方式保存
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            match res {
+                Ok(event) => {
+                    let relevant = matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_))
+                        && event.paths.iter().any(|p| p.file_name() == Some(file_name.as_os_str()));
+                    if relevant {
+                        let _ = tx.send(());
+                    }
+                }
+                Err(e) => warn!("TODO file watcher error: {}", e),
+            }
+        })
+        .map_err(|e| CisError::storage(format!("Failed to create file watcher: {}", e)))?;
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .map_err(|e| CisError::storage(format!("Failed to watch {}: {}", dir.display(), e)))?;
+
+        info!("Watching TODO file {} for {}", path.display(), self.run_id);
+
+        let run_id = self.run_id.clone();
+        let dag_run = self.dag_run.clone();
+        let loader: Arc<dyn TodoListLoader> = Arc::new(MarkdownFileLoader::new(path));
+        let scheduler = self.scheduler.clone();
+        let last_check = self.last_check.clone();
+        let events = self.events.clone();
+        let path = path.to_path_buf();
+
+        let handle = tokio::spawn(async move {
+            // 监听器随任务存活
+            let _watcher = watcher;
+
+            while rx.recv().await.is_some() {
+                // 去抖：直到一段时间内没有新事件
+                while let Ok(Some(())) = tokio::time::timeout(WATCH_DEBOUNCE, rx.recv()).await {}
+
+                match Self::check_once(&run_id, &dag_run, &loader, &scheduler, &last_check).await {
+                    Ok(diff) if diff.has_changes() => {
+                        let event = SchedulerEvent::TodoListChanged {
+                            run_id: run_id.clone(),
+                            diff,
+                        };
+                        if let Err(e) = events.emit(event).await {
+                            warn!("Failed to emit TODO list change for {}: {}", run_id, e);
+                        }
+                    }
+                    Ok(_) => debug!("TODO file {} modified without changes", path.display()),
+                    Err(e) => warn!("Failed to reload TODO file {}: {}", path.display(), e),
+                }
+            }
+        });
+
+        Ok(handle)
+    }
+
     /// 停止监控
     pub async fn stop(&self) {
         *self.running.lock().await = false;
@@ -261,7 +385,7 @@ impl TodoListMonitor {
         loader: &Arc<dyn TodoListLoader>,
         scheduler: &Arc<Mutex<DynamicTaskScheduler>>,
         last_check: &Arc<Mutex<chrono::DateTime<chrono::Utc>>>,
-    ) -> Result<()> {
+    ) -> Result<TodoListDiff> {
         // 加载外部 TODO list
         let external = loader.load(run_id).await?;
 
@@ -272,7 +396,7 @@ impl TodoListMonitor {
         };
 
         if !diff.has_changes() {
-            return Ok(());
+            return Ok(diff);
         }
 
         info!(
@@ -306,7 +430,7 @@ impl TodoListMonitor {
         // 更新检查时间
         *last_check.lock().await = chrono::Utc::now();
 
-        Ok(())
+        Ok(diff)
     }
 
     /// 手动触发检查（用于测试或事件驱动场景）
@@ -424,6 +548,71 @@ mod tests {
         }
     }
 
+    struct ChannelListener {
+        tx: tokio::sync::mpsc::UnboundedSender<SchedulerEvent>,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::scheduler::EventListener for ChannelListener {
+        async fn on_event(&self, event: &SchedulerEvent) -> Result<()> {
+            let _ = self.tx.send(event.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_emits_todo_list_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("todo.md");
+        std::fs::write(&path, "- [ ] Design schema\n").unwrap();
+
+        let dag_run = Arc::new(RwLock::new(DagRun::new(
+            crate::scheduler::TaskDag::new()
+        )));
+        dag_run.write().await.todo_list =
+            DagTodoList::import_from_markdown("- [ ] Design schema\n").unwrap();
+
+        let monitor = TodoListMonitor::new(
+            "test-run".to_string(),
+            dag_run.clone(),
+            Arc::new(MarkdownFileLoader::new(&path)),
+            std::time::Duration::from_secs(60),
+        );
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        monitor
+            .events()
+            .register(
+                crate::scheduler::SchedulerEventType::TodoListChanged,
+                Arc::new(ChannelListener { tx }),
+            )
+            .await;
+
+        let handle = monitor.watch(&path).unwrap();
+        // 等待监听器就绪
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        // 分多次写入，去抖后只处理一次
+        std::fs::write(&path, "- [ ] Design schema\n").unwrap();
+        std::fs::write(&path, "- [x] Design schema\n- [ ] Write migrations\n").unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("TodoListChanged was not emitted")
+            .unwrap();
+        match event {
+            SchedulerEvent::TodoListChanged { run_id, diff } => {
+                assert_eq!(run_id, "test-run");
+                assert_eq!(diff.added.len(), 1);
+                assert_eq!(diff.added[0].id, "todo-2");
+                assert_eq!(diff.modified.len(), 1);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        assert_eq!(dag_run.read().await.todo_list.items.len(), 2);
+
+        handle.abort();
+    }
+
     #[test]
     fn test_todo_change_event_from_diff() {
         let mut diff = TodoListDiff::default();
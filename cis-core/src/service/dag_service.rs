@@ -4,6 +4,7 @@
 
 use super::{ListOptions, PaginatedResult};
 use crate::error::{CisError, Result};
+use crate::scheduler::DagSpec;
 use crate::storage::{DbManager};
 use chrono::{DateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
@@ -507,6 +508,18 @@ impl DagService {
         let count = core.prune_dag_runs(max_age_days)?;
         Ok(count)
     }
+
+    /// 从 GitHub Actions workflow 导入 DAG
+    ///
+    /// 部分支持特性的警告仅记录日志；需要警告列表时使用
+    /// [`github_actions::import`](super::github_actions::import)。
+    pub fn import_from_github_actions(yaml: &str) -> Result<DagSpec> {
+        let import = super::github_actions::import(yaml)?;
+        for warning in &import.warnings {
+            tracing::warn!("GitHub Actions import: {}", warning);
+        }
+        Ok(import.spec)
+    }
 }
 
 impl Default for DagService {
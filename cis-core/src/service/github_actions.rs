@@ -0,0 +1,382 @@
+//! # GitHub Actions Import
+//!
+//! 将 GitHub Actions workflow YAML 转换为 `DagSpec`。
+//!
+//! ## 转换规则
+//!
+//! - 每个 job 的 `steps` 按顺序转换为 `DagTaskSpec`，同一 job 内的 step 串行依赖
+//! - `run` step → `shell` 任务，`command = step.run`
+//! - `uses` step（action）→ `action` 任务，`command = step.uses`，`with` 参数以 `INPUT_*` 环境变量传递
+//! - `needs` → job 首个 step 依赖被依赖 job 的最后一个 step
+//! - `env` 按 workflow → job → step 逐层覆盖
+//! - `timeout-minutes` → `timeout_secs`
+//!
+//! `strategy.matrix` 和可复用 workflow（job 级 `uses`）不支持，直接返回错误；
+//! 部分支持的特性（`if`、`services`、`container` 等）会被忽略并记录到警告列表；
+//! 默认沙箱规则不允许的 `run` 脚本（多行、`&&`、管道等）同样记录警告。
+
+use crate::sandbox::SandboxValidator;
+use crate::scheduler::{DagSpec, DagTaskSpec};
+use crate::error::CisError;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+
+/// 导入结果
+#[derive(Debug, Clone)]
+pub struct GithubActionsImport {
+    /// 转换后的 DAG
+    pub spec: DagSpec,
+    /// 部分支持特性的警告
+    pub warnings: Vec<String>,
+}
+
+/// 导入错误
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+    #[error("Invalid workflow YAML: {0}")]
+    InvalidYaml(String),
+
+    #[error("Workflow has no jobs")]
+    MissingJobs,
+
+    #[error("Unsupported feature: {0}")]
+    UnsupportedFeature(String),
+
+    #[error("Invalid job '{job}': {reason}")]
+    InvalidJob { job: String, reason: String },
+
+    #[error("Job '{job}' needs unknown job '{dependency}'")]
+    UnknownDependency { job: String, dependency: String },
+}
+
+impl From<ImportError> for CisError {
+    fn from(e: ImportError) -> Self {
+        CisError::invalid_input("workflow", e.to_string())
+    }
+}
+
+/// job 级别被忽略的键
+const IGNORED_JOB_KEYS: &[&str] = &[
+    "if",
+    "services",
+    "container",
+    "outputs",
+    "defaults",
+    "permissions",
+    "concurrency",
+    "environment",
+    "continue-on-error",
+];
+
+/// step 级别被忽略的键
+const IGNORED_STEP_KEYS: &[&str] = &["if", "shell", "continue-on-error"];
+
+/// 解析 workflow YAML 并转换为 DAG
+pub fn import(yaml: &str) -> Result<GithubActionsImport, ImportError> {
+    let workflow: Value =
+        serde_yaml::from_str(yaml).map_err(|e| ImportError::InvalidYaml(e.to_string()))?;
+    let workflow = workflow
+        .as_mapping()
+        .ok_or_else(|| ImportError::InvalidYaml("workflow must be a mapping".to_string()))?;
+
+    let jobs = get(workflow, "jobs")
+        .and_then(Value::as_mapping)
+        .filter(|jobs| !jobs.is_empty())
+        .ok_or(ImportError::MissingJobs)?;
+
+    let mut warnings = Vec::new();
+    let workflow_env = env_map(get(workflow, "env"));
+
+    let mut tasks: Vec<DagTaskSpec> = Vec::new();
+    // job id → (首个 task 下标, 最后一个 task id, needs)
+    let mut job_tasks: Vec<(String, usize, String, Vec<String>)> = Vec::new();
+
+    for (job_id, job) in jobs {
+        let job_id = job_id
+            .as_str()
+            .ok_or_else(|| ImportError::InvalidYaml("job id must be a string".to_string()))?
+            .to_string();
+        let job = job.as_mapping().ok_or_else(|| ImportError::InvalidJob {
+            job: job_id.clone(),
+            reason: "job must be a mapping".to_string(),
+        })?;
+
+        if get(job, "uses").is_some() {
+            return Err(ImportError::UnsupportedFeature(format!(
+                "reusable workflow (job '{}')",
+                job_id
+            )));
+        }
+        if get(job, "strategy").and_then(|s| s.get("matrix")).is_some() {
+            return Err(ImportError::UnsupportedFeature(format!(
+                "strategy.matrix (job '{}')",
+                job_id
+            )));
+        }
+        for key in IGNORED_JOB_KEYS {
+            if get(job, key).is_some() {
+                warnings.push(format!("job '{}': '{}' is ignored", job_id, key));
+            }
+        }
+
+        let mut env = workflow_env.clone();
+        env.extend(env_map(get(job, "env")));
+        let job_timeout = timeout_secs(get(job, "timeout-minutes"));
+
+        let steps = get(job, "steps")
+            .and_then(Value::as_sequence)
+            .filter(|steps| !steps.is_empty())
+            .ok_or_else(|| ImportError::InvalidJob {
+                job: job_id.clone(),
+                reason: "job has no steps".to_string(),
+            })?;
+
+        let first_index = tasks.len();
+        let mut previous: Option<String> = None;
+
+        for (index, step) in steps.iter().enumerate() {
+            let step = step.as_mapping().ok_or_else(|| ImportError::InvalidJob {
+                job: job_id.clone(),
+                reason: format!("step {} must be a mapping", index + 1),
+            })?;
+
+            let step_name = get(step, "id")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("step-{}", index + 1));
+            let task_id = format!("{}-{}", job_id, step_name);
+
+            for key in IGNORED_STEP_KEYS {
+                if get(step, key).is_some() {
+                    warnings.push(format!("step '{}': '{}' is ignored", task_id, key));
+                }
+            }
+
+            let mut step_env = env.clone();
+            step_env.extend(env_map(get(step, "env")));
+
+            let (task_type, command) = if let Some(run) = get(step, "run").and_then(Value::as_str) {
+                let run = run.trim_end().to_string();
+                let command = match get(step, "working-directory").and_then(Value::as_str) {
+                    Some(dir) => format!("cd {} && {}", dir, run),
+                    None => run,
+                };
+                // 默认沙箱规则会拒绝多行脚本和 `&&`、`|` 等操作符，需要 worker 显式放行
+                if let Err(e) = SandboxValidator::validate_shell_command(&command) {
+                    warnings.push(format!(
+                        "step '{}': {}; run the worker with --shell-allow to permit it",
+                        task_id, e
+                    ));
+                }
+                ("shell", command)
+            } else if let Some(uses) = get(step, "uses").and_then(Value::as_str) {
+                // action 的输入按 GitHub 约定通过 INPUT_<NAME> 传递
+                for (name, value) in env_map(get(step, "with")) {
+                    step_env.insert(format!("INPUT_{}", name.to_uppercase().replace(' ', "_")), value);
+                }
+                warnings.push(format!(
+                    "step '{}': action '{}' requires a matching skill",
+                    task_id, uses
+                ));
+                ("action", uses.to_string())
+            } else {
+                return Err(ImportError::InvalidJob {
+                    job: job_id.clone(),
+                    reason: format!("step '{}' has neither 'run' nor 'uses'", step_name),
+                });
+            };
+
+            tasks.push(DagTaskSpec {
+                id: task_id.clone(),
+                task_type: task_type.to_string(),
+                command,
+                depends_on: previous.iter().cloned().collect(),
+                env: step_env,
+                per_task_retry: None,
+                timeout_secs: timeout_secs(get(step, "timeout-minutes")).or(job_timeout),
+                level: None,
+                fail_fast: false,
+            });
+            previous = Some(task_id);
+        }
+
+        let needs = match get(job, "needs") {
+            Some(Value::String(need)) => vec![need.clone()],
+            Some(Value::Sequence(needs)) => needs
+                .iter()
+                .filter_map(|n| n.as_str().map(str::to_string))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let last = previous.unwrap_or_default();
+        job_tasks.push((job_id, first_index, last, needs));
+    }
+
+    // needs → 首个 step 依赖被依赖 job 的最后一个 step
+    let last_tasks: HashMap<&str, &str> = job_tasks
+        .iter()
+        .map(|(job, _, last, _)| (job.as_str(), last.as_str()))
+        .collect();
+    for (job, first_index, _, needs) in &job_tasks {
+        for need in needs {
+            let last = last_tasks
+                .get(need.as_str())
+                .ok_or_else(|| ImportError::UnknownDependency {
+                    job: job.clone(),
+                    dependency: need.clone(),
+                })?;
+            tasks[*first_index].depends_on.push(last.to_string());
+        }
+    }
+
+    let name = get(workflow, "name").and_then(Value::as_str);
+    let dag_id = name
+        .map(|n| n.to_lowercase().replace(|c: char| !c.is_ascii_alphanumeric(), "-"))
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| "github-actions".to_string());
+
+    let mut spec = DagSpec::new(dag_id, tasks);
+    spec.description = name
+        .map(|n| format!("Imported from GitHub Actions workflow '{}'", n))
+        .unwrap_or_else(|| "Imported from GitHub Actions workflow".to_string());
+
+    Ok(GithubActionsImport { spec, warnings })
+}
+
+fn get<'a>(map: &'a Mapping, key: &str) -> Option<&'a Value> {
+    map.get(key)
+}
+
+/// 将 `env` / `with` 映射转为字符串键值
+fn env_map(value: Option<&Value>) -> HashMap<String, String> {
+    let Some(map) = value.and_then(Value::as_mapping) else {
+        return HashMap::new();
+    };
+
+    map.iter()
+        .filter_map(|(k, v)| {
+            let key = k.as_str()?.to_string();
+            let value = match v {
+                Value::String(s) => s.clone(),
+                Value::Bool(b) => b.to_string(),
+                Value::Number(n) => n.to_string(),
+                Value::Null => String::new(),
+                other => serde_yaml::to_string(other).ok()?.trim_end().to_string(),
+            };
+            Some((key, value))
+        })
+        .collect()
+}
+
+fn timeout_secs(value: Option<&Value>) -> Option<u64> {
+    value.and_then(Value::as_u64).map(|minutes| minutes * 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKFLOW: &str = r#"
+name: CI
+on: [push]
+env:
+  RUST_LOG: info
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    env:
+      CARGO_TERM_COLOR: always
+    timeout-minutes: 10
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          fetch-depth: 0
+      - id: compile
+        run: cargo build --release
+        env:
+          RUST_LOG: debug
+  test:
+    needs: build
+    runs-on: ubuntu-latest
+    steps:
+      - run: cargo test
+        working-directory: cis-core
+        if: github.event_name == 'push'
+"#;
+
+    #[test]
+    fn test_import_jobs_and_steps() {
+        let import = import(WORKFLOW).unwrap();
+        let spec = import.spec;
+
+        assert_eq!(spec.dag_id, "ci");
+        let ids: Vec<&str> = spec.tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["build-step-1", "build-compile", "test-step-1"]);
+
+        let checkout = &spec.tasks[0];
+        assert_eq!(checkout.task_type, "action");
+        assert_eq!(checkout.command, "actions/checkout@v4");
+        assert_eq!(checkout.env.get("INPUT_FETCH-DEPTH").map(String::as_str), Some("0"));
+        assert_eq!(checkout.timeout_secs, Some(600));
+
+        let compile = &spec.tasks[1];
+        assert_eq!(compile.task_type, "shell");
+        assert_eq!(compile.command, "cargo build --release");
+        assert_eq!(compile.depends_on, vec!["build-step-1"]);
+        assert_eq!(compile.env.get("RUST_LOG").map(String::as_str), Some("debug"));
+        assert_eq!(compile.env.get("CARGO_TERM_COLOR").map(String::as_str), Some("always"));
+
+        let test = &spec.tasks[2];
+        assert_eq!(test.command, "cd cis-core && cargo test");
+        assert_eq!(test.depends_on, vec!["build-compile"]);
+        assert_eq!(test.env.get("RUST_LOG").map(String::as_str), Some("info"));
+        assert!(test.env.get("CARGO_TERM_COLOR").is_none());
+
+        assert!(import.warnings.iter().any(|w| w.contains("'if' is ignored")));
+        assert!(import
+            .warnings
+            .iter()
+            .any(|w| w.starts_with("step 'test-step-1'") && w.contains("--shell-allow")));
+        assert!(!import.warnings.iter().any(|w| w.starts_with("step 'build-compile'")));
+    }
+
+    #[test]
+    fn test_import_rejects_matrix_and_reusable_workflows() {
+        let matrix = r#"
+jobs:
+  test:
+    strategy:
+      matrix:
+        os: [ubuntu-latest, macos-latest]
+    steps:
+      - run: cargo test
+"#;
+        assert!(matches!(import(matrix), Err(ImportError::UnsupportedFeature(_))));
+
+        let reusable = r#"
+jobs:
+  call:
+    uses: octo-org/repo/.github/workflows/ci.yml@main
+"#;
+        assert!(matches!(import(reusable), Err(ImportError::UnsupportedFeature(_))));
+    }
+
+    #[test]
+    fn test_import_invalid_workflows() {
+        assert!(matches!(import("name: empty"), Err(ImportError::MissingJobs)));
+        assert!(matches!(import(": ["), Err(ImportError::InvalidYaml(_))));
+
+        let unknown_need = r#"
+jobs:
+  deploy:
+    needs: [build]
+    steps:
+      - run: ./deploy.sh
+"#;
+        assert!(matches!(
+            import(unknown_need),
+            Err(ImportError::UnknownDependency { .. })
+        ));
+    }
+}
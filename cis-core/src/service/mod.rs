@@ -13,6 +13,7 @@
 //!
 //! - `WorkerService` - Worker 进程管理
 //! - `NodeService` - 节点管理
+//! - `NodeSyncer` - 节点存活探测与列表刷新
 //! - `DagService` - DAG 管理
 //! - `TaskService` - 任务管理
 //! - `SkillService` - Skill 管理
@@ -37,13 +38,17 @@ use std::collections::HashMap;
 
 pub mod worker_service;
 pub mod node_service;
+pub mod node_sync;
 pub mod dag_service;
+pub mod github_actions;
 pub mod task_service;
 pub mod skill_executor_impl;
 
 pub use worker_service::WorkerService;
 pub use node_service::NodeService;
+pub use node_sync::NodeSyncer;
 pub use dag_service::DagService;
+pub use github_actions::{GithubActionsImport, ImportError};
 pub use task_service::TaskService;
 pub use skill_executor_impl::SkillExecutorImpl;
 
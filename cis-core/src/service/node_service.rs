@@ -50,6 +50,8 @@ pub enum NodeStatus {
     Offline,
     Suspicious,
     Blacklisted,
+    /// 长期不可达
+    Stale,
     Unknown,
 }
 
@@ -60,6 +62,7 @@ impl std::fmt::Display for NodeStatus {
             NodeStatus::Offline => write!(f, "offline"),
             NodeStatus::Suspicious => write!(f, "suspicious"),
             NodeStatus::Blacklisted => write!(f, "blacklisted"),
+            NodeStatus::Stale => write!(f, "stale"),
             NodeStatus::Unknown => write!(f, "unknown"),
         }
     }
@@ -71,6 +74,7 @@ impl From<PeerStatus> for NodeStatus {
             PeerStatus::Online => NodeStatus::Online,
             PeerStatus::Offline => NodeStatus::Offline,
             PeerStatus::HolePunching => NodeStatus::Unknown,
+            PeerStatus::Stale => NodeStatus::Stale,
         }
     }
 }
@@ -0,0 +1,361 @@
+//! # Node Sync
+//!
+//! 刷新已知节点列表：对数据库中的每个节点做一次 WebSocket 握手以确认存活，
+//! 更新 `last_seen` 与 endpoint（节点可能更换 IP），长期不可达的节点标记为
+//! [`PeerStatus::Stale`]，可选择清理。
+//!
+//! 探测并发受 `concurrency` 限制（默认 10），避免一次性冲击网络。
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde::Serialize;
+use tokio::task::JoinSet;
+
+use crate::error::{CisError, Result};
+use crate::storage::federation_db::{FederationDb, PeerInfo, PeerStatus};
+use crate::storage::paths::Paths;
+
+/// 默认最大并发探测数
+pub const DEFAULT_SYNC_CONCURRENCY: usize = 10;
+
+/// 默认不可达多少天后标记为 Stale
+pub const DEFAULT_STALE_DAYS: u32 = 7;
+
+/// 节点在握手响应中通告自身当前 endpoint 的 Header
+pub const ENDPOINT_HEADER: &str = "x-cis-endpoint";
+
+/// 探测成功的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeOutcome {
+    /// 节点通告的当前 endpoint（与记录不同时更新）
+    pub endpoint: Option<String>,
+    /// 握手耗时
+    pub rtt_ms: i32,
+}
+
+/// 节点存活探测
+#[async_trait]
+pub trait NodeProbe: Send + Sync {
+    /// 探测 endpoint，不可达时返回 `None`
+    async fn probe(&self, endpoint: &str) -> Option<ProbeOutcome>;
+}
+
+/// 基于 WebSocket 握手的探测
+pub struct WsHandshakeProbe {
+    timeout: Duration,
+}
+
+impl WsHandshakeProbe {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl Default for WsHandshakeProbe {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(5))
+    }
+}
+
+#[async_trait]
+impl NodeProbe for WsHandshakeProbe {
+    async fn probe(&self, endpoint: &str) -> Option<ProbeOutcome> {
+        let start = Instant::now();
+        let (mut stream, response) =
+            tokio::time::timeout(self.timeout, tokio_tungstenite::connect_async(endpoint))
+                .await
+                .ok()?
+                .ok()?;
+        let rtt_ms = start.elapsed().as_millis().min(i32::MAX as u128) as i32;
+
+        let advertised = response
+            .headers()
+            .get(ENDPOINT_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let _ = stream.close(None).await;
+
+        Some(ProbeOutcome {
+            endpoint: advertised,
+            rtt_ms,
+        })
+    }
+}
+
+/// 单个节点的同步结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum NodeSyncStatus {
+    /// 在线
+    Online,
+    /// 本次不可达
+    Offline,
+    /// 不可达超过 `stale_days`
+    Stale,
+    /// 没有记录 endpoint，无法探测
+    NoEndpoint,
+}
+
+/// 同步报告
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncReport {
+    /// 探测的节点数
+    pub total: usize,
+    /// 在线节点
+    pub online: Vec<String>,
+    /// 本次不可达的节点
+    pub offline: Vec<String>,
+    /// 新标记为（或仍为）Stale 的节点
+    pub stale: Vec<String>,
+    /// endpoint 发生变化的节点：(node_id, 旧 endpoint, 新 endpoint)
+    pub endpoint_changed: Vec<(String, String, String)>,
+}
+
+/// 节点列表同步器
+pub struct NodeSyncer {
+    db: Mutex<FederationDb>,
+    probe: Arc<dyn NodeProbe>,
+    stale_days: u32,
+    concurrency: usize,
+}
+
+impl NodeSyncer {
+    pub fn new(db: FederationDb, probe: Arc<dyn NodeProbe>) -> Self {
+        Self {
+            db: Mutex::new(db),
+            probe,
+            stale_days: DEFAULT_STALE_DAYS,
+            concurrency: DEFAULT_SYNC_CONCURRENCY,
+        }
+    }
+
+    /// 使用默认联邦数据库和 WebSocket 握手探测
+    pub fn open_default() -> Result<Self> {
+        let db = FederationDb::open(&Paths::federation_db())?;
+        Ok(Self::new(db, Arc::new(WsHandshakeProbe::default())))
+    }
+
+    pub fn with_stale_days(mut self, stale_days: u32) -> Self {
+        self.stale_days = stale_days;
+        self
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    fn db(&self) -> Result<std::sync::MutexGuard<'_, FederationDb>> {
+        self.db
+            .lock()
+            .map_err(|_| CisError::internal("Federation db lock poisoned"))
+    }
+
+    /// 已知节点数
+    pub fn peer_count(&self) -> Result<usize> {
+        Ok(self.db()?.list_peers()?.len())
+    }
+
+    /// 探测所有已知节点并更新数据库
+    pub async fn sync_from_static_list(&self) -> Result<SyncReport> {
+        self.sync_with_progress(|_, _| {}).await
+    }
+
+    /// 同 [`sync_from_static_list`](Self::sync_from_static_list)，每个节点完成后回调
+    pub async fn sync_with_progress<F>(&self, mut on_node: F) -> Result<SyncReport>
+    where
+        F: FnMut(&str, &NodeSyncStatus),
+    {
+        let peers = self.db()?.list_peers()?;
+        let mut report = SyncReport {
+            total: peers.len(),
+            ..SyncReport::default()
+        };
+
+        let mut pending = peers.into_iter();
+        let mut in_flight = JoinSet::new();
+        loop {
+            while in_flight.len() < self.concurrency {
+                let Some(peer) = pending.next() else {
+                    break;
+                };
+                let probe = Arc::clone(&self.probe);
+                in_flight.spawn(async move {
+                    let outcome = match peer.endpoint_ws.as_deref() {
+                        Some(endpoint) => probe.probe(endpoint).await,
+                        None => None,
+                    };
+                    (peer, outcome)
+                });
+            }
+
+            let Some(joined) = in_flight.join_next().await else {
+                break;
+            };
+            let (peer, outcome) =
+                joined.map_err(|e| CisError::internal(format!("Probe task failed: {}", e)))?;
+            let status = self.apply(&peer, outcome, &mut report)?;
+            on_node(&peer.node_id, &status);
+        }
+
+        Ok(report)
+    }
+
+    /// 把探测结果写回数据库
+    fn apply(
+        &self,
+        peer: &PeerInfo,
+        outcome: Option<ProbeOutcome>,
+        report: &mut SyncReport,
+    ) -> Result<NodeSyncStatus> {
+        let db = self.db()?;
+
+        if let Some(outcome) = outcome {
+            let old_endpoint = peer.endpoint_ws.clone().unwrap_or_default();
+            let endpoint = outcome.endpoint.unwrap_or_else(|| old_endpoint.clone());
+            if endpoint != old_endpoint {
+                report
+                    .endpoint_changed
+                    .push((peer.node_id.clone(), old_endpoint, endpoint.clone()));
+            }
+
+            // upsert_peer 会把 last_seen 刷新为当前时间
+            db.upsert_peer(&PeerInfo {
+                endpoint_ws: Some(endpoint),
+                status: PeerStatus::Online,
+                rtt_ms: Some(outcome.rtt_ms),
+                ..peer.clone()
+            })?;
+            report.online.push(peer.node_id.clone());
+            return Ok(NodeSyncStatus::Online);
+        }
+
+        let stale_cutoff = chrono::Utc::now().timestamp() - i64::from(self.stale_days) * 24 * 3600;
+        let status = if peer.last_seen < stale_cutoff {
+            db.set_peer_status(&peer.node_id, PeerStatus::Stale)?;
+            report.stale.push(peer.node_id.clone());
+            NodeSyncStatus::Stale
+        } else {
+            db.set_peer_status(&peer.node_id, PeerStatus::Offline)?;
+            report.offline.push(peer.node_id.clone());
+            if peer.endpoint_ws.is_none() {
+                NodeSyncStatus::NoEndpoint
+            } else {
+                NodeSyncStatus::Offline
+            }
+        };
+        Ok(status)
+    }
+
+    /// 删除所有 Stale 节点，返回被删除的节点 ID
+    pub fn prune_stale(&self) -> Result<Vec<String>> {
+        let db = self.db()?;
+        let mut removed = Vec::new();
+        for peer in db.list_peers()? {
+            if peer.status == PeerStatus::Stale && db.delete_peer(&peer.node_id)? {
+                removed.push(peer.node_id);
+            }
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// 按 endpoint 返回预设结果，并记录最大并发数
+    struct FakeProbe {
+        outcomes: HashMap<String, ProbeOutcome>,
+        active: AtomicUsize,
+        max_active: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl NodeProbe for FakeProbe {
+        async fn probe(&self, endpoint: &str) -> Option<ProbeOutcome> {
+            let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_active.fetch_max(active, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            self.outcomes.get(endpoint).cloned()
+        }
+    }
+
+    fn peer(id: &str, endpoint: Option<&str>, last_seen_days_ago: i64) -> PeerInfo {
+        PeerInfo {
+            node_id: id.to_string(),
+            did: format!("did:cis:{}:key", id),
+            endpoint_ws: endpoint.map(str::to_string),
+            status: PeerStatus::Online,
+            last_seen: chrono::Utc::now().timestamp() - last_seen_days_ago * 24 * 3600,
+            rtt_ms: None,
+            public_key: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_from_static_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = FederationDb::open(&dir.path().join("federation.db")).unwrap();
+        for p in [
+            peer("alive", Some("ws://a:1"), 1),
+            peer("moved", Some("ws://b:1"), 1),
+            peer("down", Some("ws://c:1"), 1),
+            peer("gone", Some("ws://d:1"), 30),
+        ] {
+            db.upsert_peer(&p).unwrap();
+        }
+        // upsert_peer 总是写入当前时间，手动回拨 last_seen
+        db.conn()
+            .execute(
+                "UPDATE network_peers SET last_seen = ?1 WHERE node_id = 'gone'",
+                [chrono::Utc::now().timestamp() - 30 * 24 * 3600],
+            )
+            .unwrap();
+
+        let outcomes = HashMap::from([
+            ("ws://a:1".to_string(), ProbeOutcome { endpoint: None, rtt_ms: 5 }),
+            (
+                "ws://b:1".to_string(),
+                ProbeOutcome { endpoint: Some("ws://b2:1".to_string()), rtt_ms: 7 },
+            ),
+        ]);
+        let probe = Arc::new(FakeProbe {
+            outcomes,
+            active: AtomicUsize::new(0),
+            max_active: AtomicUsize::new(0),
+        });
+        let syncer = NodeSyncer::new(db, probe.clone()).with_stale_days(7).with_concurrency(2);
+
+        let mut seen = Vec::new();
+        let report = syncer
+            .sync_with_progress(|id, _| seen.push(id.to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(report.total, 4);
+        assert_eq!(seen.len(), 4);
+        assert!(probe.max_active.load(Ordering::SeqCst) <= 2);
+        assert_eq!(report.offline, vec!["down"]);
+        assert_eq!(report.stale, vec!["gone"]);
+        assert_eq!(
+            report.endpoint_changed,
+            vec![("moved".to_string(), "ws://b:1".to_string(), "ws://b2:1".to_string())]
+        );
+
+        {
+            let db = syncer.db().unwrap();
+            let moved = db.get_peer("moved").unwrap().unwrap();
+            assert_eq!(moved.endpoint_ws.as_deref(), Some("ws://b2:1"));
+            assert_eq!(moved.rtt_ms, Some(7));
+            assert_eq!(db.get_peer("down").unwrap().unwrap().status, PeerStatus::Offline);
+        }
+
+        assert_eq!(syncer.prune_stale().unwrap(), vec!["gone"]);
+        assert_eq!(syncer.peer_count().unwrap(), 3);
+    }
+}
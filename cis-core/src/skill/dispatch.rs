@@ -0,0 +1,239 @@
+//! Skill 语义分发
+//!
+//! 将自然语言描述与已注册 Skill 的 `description` 做向量相似度匹配，供 `cis skill do` 使用。
+//! Skill 描述向量缓存在向量存储中（见 [`crate::vector::skill_description`]），
+//! 只有新增或描述变更的 Skill 才需要重新嵌入。
+
+use std::sync::Arc;
+
+use crate::ai::embedding::cosine_similarity;
+use crate::error::Result;
+use crate::vector::{description_fingerprint, VectorStorage};
+
+use super::types::SkillInfo;
+
+/// 语义匹配候选
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkillCandidate {
+    /// Skill 名称
+    pub skill_name: String,
+    /// Skill 描述
+    pub description: String,
+    /// 与查询的余弦相似度
+    pub similarity: f32,
+}
+
+/// 分发决策
+#[derive(Debug, Clone, PartialEq)]
+pub enum DispatchDecision {
+    /// 没有任何候选
+    NoMatch,
+    /// 最佳候选达到阈值，可直接执行
+    Execute(SkillCandidate),
+    /// 最佳候选低于阈值，需要用户确认
+    Confirm(SkillCandidate),
+}
+
+impl DispatchDecision {
+    /// 根据相似度阈值决定如何处理已排序的候选
+    pub fn from_candidates(candidates: &[SkillCandidate], threshold: f32) -> Self {
+        match candidates.first() {
+            None => Self::NoMatch,
+            Some(best) if best.similarity >= threshold => Self::Execute(best.clone()),
+            Some(best) => Self::Confirm(best.clone()),
+        }
+    }
+}
+
+/// 基于向量相似度的 Skill 分发器
+pub struct SemanticSkillDispatcher {
+    storage: Arc<VectorStorage>,
+}
+
+impl SemanticSkillDispatcher {
+    /// 创建分发器
+    pub fn new(storage: Arc<VectorStorage>) -> Self {
+        Self { storage }
+    }
+
+    /// 按相似度返回前 `top_k` 个候选（降序）
+    ///
+    /// 没有描述的 Skill 不参与匹配。
+    pub async fn rank(&self, query: &str, skills: &[SkillInfo], top_k: usize) -> Result<Vec<SkillCandidate>> {
+        let skills: Vec<&SkillInfo> = skills
+            .iter()
+            .filter(|info| !info.meta.description.trim().is_empty())
+            .collect();
+        if skills.is_empty() || top_k == 0 {
+            return Ok(Vec::new());
+        }
+
+        let embeddings = self.description_embeddings(&skills).await?;
+        let query_vec = self.storage.embedding_service().embed(query).await?;
+
+        let mut candidates: Vec<SkillCandidate> = skills
+            .iter()
+            .zip(embeddings)
+            .map(|(info, embedding)| SkillCandidate {
+                skill_name: info.meta.name.clone(),
+                description: info.meta.description.clone(),
+                similarity: cosine_similarity(&query_vec, &embedding),
+            })
+            .collect();
+
+        candidates.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        candidates.truncate(top_k);
+        Ok(candidates)
+    }
+
+    /// 获取描述向量：优先读取缓存，未命中的批量嵌入后写回
+    async fn description_embeddings(&self, skills: &[&SkillInfo]) -> Result<Vec<Vec<f32>>> {
+        let fingerprints: Vec<String> = skills
+            .iter()
+            .map(|info| description_fingerprint(&info.meta.description))
+            .collect();
+
+        let mut embeddings = Vec::with_capacity(skills.len());
+        for (info, fingerprint) in skills.iter().zip(&fingerprints) {
+            embeddings.push(self.storage.cached_skill_description(&info.meta.name, fingerprint)?);
+        }
+
+        let missing: Vec<usize> = (0..skills.len()).filter(|&i| embeddings[i].is_none()).collect();
+        if !missing.is_empty() {
+            tracing::debug!("Embedding {} skill descriptions", missing.len());
+            let texts: Vec<&str> = missing.iter().map(|&i| skills[i].meta.description.as_str()).collect();
+            let vectors = self.storage.embedding_service().batch_embed(&texts).await?;
+            for (&i, vector) in missing.iter().zip(vectors) {
+                self.storage
+                    .store_skill_description(&skills[i].meta.name, &fingerprints[i], &vector)?;
+                embeddings[i] = Some(vector);
+            }
+        }
+
+        Ok(embeddings.into_iter().map(Option::unwrap_or_default).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::embedding::EmbeddingService;
+    use crate::skill::types::{SkillMeta, SkillRuntime, SkillState, SkillType};
+    use crate::vector::EMBEDDING_DIM;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// 词袋嵌入：每个单词映射到一个固定维度，记录嵌入的文本数
+    #[derive(Default)]
+    struct BagOfWords {
+        embedded: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EmbeddingService for BagOfWords {
+        async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+            self.embedded.fetch_add(1, Ordering::SeqCst);
+            let mut vec = vec![0.0f32; EMBEDDING_DIM];
+            for word in text.split_whitespace() {
+                let slot = word.bytes().fold(7usize, |h, b| h.wrapping_mul(31).wrapping_add(b as usize));
+                vec[slot % EMBEDDING_DIM] += 1.0;
+            }
+            Ok(vec)
+        }
+
+        async fn batch_embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+            let mut results = Vec::with_capacity(texts.len());
+            for text in texts {
+                results.push(self.embed(text).await?);
+            }
+            Ok(results)
+        }
+    }
+
+    fn skill(name: &str, description: &str) -> SkillInfo {
+        SkillInfo {
+            meta: SkillMeta {
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                description: description.to_string(),
+                author: "test".to_string(),
+                skill_type: SkillType::Native,
+                path: String::new(),
+                db_path: String::new(),
+                permissions: vec![],
+                subscriptions: vec![],
+                config_schema: None,
+                room_config: None,
+                dependencies: Vec::new(),
+            },
+            runtime: SkillRuntime {
+                state: SkillState::Registered,
+                loaded_at: None,
+                last_active_at: None,
+                error: None,
+                pid: None,
+            },
+        }
+    }
+
+    fn candidate(name: &str, similarity: f32) -> SkillCandidate {
+        SkillCandidate {
+            skill_name: name.to_string(),
+            description: String::new(),
+            similarity,
+        }
+    }
+
+    #[test]
+    fn test_dispatch_decision() {
+        assert_eq!(DispatchDecision::from_candidates(&[], 0.7), DispatchDecision::NoMatch);
+
+        let candidates = vec![candidate("git-commit", 0.82), candidate("git-log", 0.64)];
+        assert_eq!(
+            DispatchDecision::from_candidates(&candidates, 0.7),
+            DispatchDecision::Execute(candidates[0].clone())
+        );
+        assert_eq!(
+            DispatchDecision::from_candidates(&candidates, 0.9),
+            DispatchDecision::Confirm(candidates[0].clone())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rank_uses_cached_descriptions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("vector.db");
+        let embedding = Arc::new(BagOfWords::default());
+        let storage = Arc::new(VectorStorage::open_with_service(&db_path, embedding.clone()).unwrap());
+        let dispatcher = SemanticSkillDispatcher::new(storage.clone());
+
+        let mut skills = vec![
+            skill("git-commit", "commit staged changes to git"),
+            skill("weather", "show the weather forecast"),
+            skill("empty", ""),
+        ];
+
+        let candidates = dispatcher.rank("commit my changes", &skills, 5).await.unwrap();
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].skill_name, "git-commit");
+        assert!(candidates[0].similarity > candidates[1].similarity);
+        // 2 个描述 + 1 个查询
+        assert_eq!(embedding.embedded.load(Ordering::SeqCst), 3);
+
+        // 描述已缓存，只嵌入查询
+        let top = dispatcher.rank("weather forecast", &skills, 1).await.unwrap();
+        assert_eq!(top[0].skill_name, "weather");
+        assert_eq!(embedding.embedded.load(Ordering::SeqCst), 4);
+
+        // 描述变更或主动失效后重新嵌入
+        skills[1].meta.description = "show today's weather".to_string();
+        assert!(storage.invalidate_skill_description("git-commit").unwrap());
+        dispatcher.rank("weather", &skills, 5).await.unwrap();
+        assert_eq!(embedding.embedded.load(Ordering::SeqCst), 7);
+
+        drop(dispatcher);
+        drop(storage);
+        assert!(crate::vector::invalidate_skill_description_at(&db_path, "weather").unwrap());
+        assert!(!crate::vector::invalidate_skill_description_at(&db_path, "weather").unwrap());
+    }
+}
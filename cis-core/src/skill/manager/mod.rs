@@ -7,9 +7,10 @@ use std::sync::{Arc, Mutex};
 
 use super::permission_checker::{CheckContext, PermissionChecker, PermissionScope, ResourcePattern};
 use super::registry::SkillRegistry;
-use super::types::{LoadOptions, SkillConfig, SkillInfo, SkillMeta, SkillState, SkillType};
+use super::types::{LoadOptions, MethodDescriptor, SkillConfig, SkillInfo, SkillMeta, SkillState, SkillType};
 use super::{Event, Skill, SkillContext};
 use crate::error::{CisError, Result};
+use crate::scheduler::TaskDag;
 use crate::storage::db::DbManager;
 use crate::storage::paths::Paths;
 
@@ -153,10 +154,115 @@ impl SkillManager {
     /// 加载 Skill
     ///
     /// 创建数据库连接，初始化 Skill，但不激活。
+    /// 加载前先解析依赖：未加载的依赖按拓扑顺序自动加载，
+    /// 依赖未注册或版本不满足时返回 `CisError::UnresolvedDependency`，循环依赖直接拒绝。
     pub async fn load(&self, name: &str, options: LoadOptions) -> Result<()> {
         // 验证 Skill 名称长度
         crate::check_string_length(name, 256)?;
 
+        // 解析依赖（结果按拓扑序排列，最后一项为自身）
+        let order = self.resolve_dependencies(name)?;
+        for dep in order.iter().filter(|dep| dep.as_str() != name) {
+            if !self.is_loaded(dep)? {
+                tracing::info!("Auto-loading dependency '{}' for skill '{}'", dep, name);
+                self.load_single(dep, LoadOptions::default()).await?;
+            }
+        }
+
+        self.load_single(name, options).await
+    }
+
+    /// 解析 Skill 的依赖闭包
+    ///
+    /// 返回按拓扑序排列的 Skill 名称（依赖在前，`name` 自身在最后）。
+    pub fn resolve_dependencies(&self, name: &str) -> Result<Vec<String>> {
+        let registry = self.registry.lock()
+            .map_err(|e| CisError::skill(format!("Lock failed: {}", e)))?;
+
+        if registry.get(name).is_none() {
+            return Err(CisError::not_found(format!("Skill '{}' not found", name)));
+        }
+
+        let mut order = Vec::new();
+        let mut visiting = Vec::new();
+        Self::visit_dependencies(&registry, name, &mut visiting, &mut order)?;
+        Ok(order)
+    }
+
+    /// 构建所有已注册 Skill 的依赖图
+    ///
+    /// 每个 Skill 对应一个 DAG 节点，边指向其依赖。存在循环或无法满足的依赖时返回错误。
+    pub fn dependency_graph(&self) -> Result<TaskDag> {
+        let registry = self.registry.lock()
+            .map_err(|e| CisError::skill(format!("Lock failed: {}", e)))?;
+
+        let mut names: Vec<&str> = registry.list_all().iter().map(|info| info.meta.name.as_str()).collect();
+        names.sort_unstable();
+
+        let mut order = Vec::new();
+        let mut visiting = Vec::new();
+        for name in names {
+            Self::visit_dependencies(&registry, name, &mut visiting, &mut order)?;
+        }
+
+        // 按拓扑序添加节点，保证依赖节点先于依赖方存在
+        let mut dag = TaskDag::new();
+        for name in order {
+            let deps = registry
+                .get(&name)
+                .map(|info| info.meta.dependencies.iter().map(|d| d.name.clone()).collect())
+                .unwrap_or_default();
+            dag.add_node(name, deps)
+                .map_err(|e| CisError::skill(format!("Failed to build dependency graph: {}", e)))?;
+        }
+
+        Ok(dag)
+    }
+
+    /// 深度优先遍历依赖（后序写入 `order`），同时检查版本并检测循环
+    fn visit_dependencies(
+        registry: &SkillRegistry,
+        name: &str,
+        visiting: &mut Vec<String>,
+        order: &mut Vec<String>,
+    ) -> Result<()> {
+        if order.iter().any(|n| n == name) {
+            return Ok(());
+        }
+        if let Some(pos) = visiting.iter().position(|n| n == name) {
+            let mut cycle = visiting[pos..].to_vec();
+            cycle.push(name.to_string());
+            return Err(CisError::skill(format!(
+                "Circular skill dependency: {}",
+                cycle.join(" -> ")
+            )));
+        }
+
+        let info = registry
+            .get(name)
+            .ok_or_else(|| CisError::not_found(format!("Skill '{}' not found", name)))?;
+
+        visiting.push(name.to_string());
+        for dep in &info.meta.dependencies {
+            let satisfied = registry
+                .get(&dep.name)
+                .is_some_and(|dep_info| dep.matches(&dep_info.meta.version));
+            if !satisfied {
+                return Err(CisError::unresolved_dependency(
+                    dep.name.clone(),
+                    dep.version_req.to_string(),
+                ));
+            }
+            Self::visit_dependencies(registry, &dep.name, visiting, order)?;
+        }
+        visiting.pop();
+
+        order.push(name.to_string());
+        Ok(())
+    }
+
+    /// 加载单个 Skill（不处理依赖）
+    async fn load_single(&self, name: &str, options: LoadOptions) -> Result<()> {
         // 检查是否已加载
         if self.is_loaded(name)? && !options.force_reload {
             return Ok(());
@@ -228,6 +334,8 @@ impl SkillManager {
             registry.update_state(name, SkillState::Loaded)?;
         }
 
+        Self::invalidate_description_embedding(name);
+
         tracing::info!("Skill '{}' loaded successfully", name);
 
         // 7. 自动激活（如果启用）
@@ -283,11 +391,20 @@ impl SkillManager {
             registry.update_state(name, SkillState::Unloaded)?;
         }
 
+        Self::invalidate_description_embedding(name);
+
         tracing::info!("Skill '{}' unloaded successfully", name);
 
         Ok(())
     }
 
+    /// 使 `cis skill do` 缓存的描述向量失效（失败只记录警告）
+    fn invalidate_description_embedding(name: &str) {
+        if let Err(e) = crate::vector::invalidate_skill_description_at(&Paths::vector_db(), name) {
+            tracing::warn!("Failed to invalidate description embedding for skill '{}': {}", name, e);
+        }
+    }
+
     /// 检查 Skill 是否已加载
     pub fn is_loaded(&self, name: &str) -> Result<bool> {
         let active_skills = self.active_skills.lock()
@@ -571,6 +688,67 @@ impl SkillManager {
         Ok(meta)
     }
 
+    /// 加载项目级 Skills
+    ///
+    /// 读取 `{project_root}/.cis/skills.toml`，注册并加载其中声明的每个 Skill，
+    /// `auto_activate = true` 的 Skill 加载后立即激活。返回已加载的 Skill 名称。
+    pub async fn load_project_skills(&self, project_root: &std::path::Path) -> Result<Vec<String>> {
+        use crate::project::ProjectSkillManifest;
+        use crate::sandbox::SandboxValidator;
+
+        let manifest = ProjectSkillManifest::load(project_root)?;
+        let mut loaded = Vec::with_capacity(manifest.skills.len());
+
+        for skill in &manifest.skills {
+            crate::check_string_length(&skill.name, 256)?;
+
+            let skill_type = skill.skill_type()?;
+            let path = ProjectSkillManifest::resolve_path(project_root, skill);
+
+            // Skill 文件必须位于项目目录内
+            SandboxValidator::validate_path(&path, &[project_root.to_path_buf()])
+                .map_err(|e| CisError::skill(format!("Skill '{}': {}", skill.name, e)))?;
+            if !path.is_file() {
+                return Err(CisError::skill(format!(
+                    "Skill '{}': artifact not found at {:?}",
+                    skill.name, path
+                )));
+            }
+
+            if self.get_info(&skill.name)?.is_none() {
+                self.register(SkillMeta {
+                    name: skill.name.clone(),
+                    version: skill.version.clone(),
+                    description: format!("Project skill ({})", project_root.display()),
+                    author: String::new(),
+                    skill_type,
+                    path: path.to_string_lossy().to_string(),
+                    db_path: Paths::skill_data_dir(&skill.name).join("data.db").to_string_lossy().to_string(),
+                    permissions: vec![],
+                    subscriptions: vec![],
+                    config_schema: None,
+                    room_config: None,
+                    dependencies: Vec::new(),
+                })?;
+            }
+
+            self.load(&skill.name, LoadOptions {
+                auto_activate: skill.auto_activate,
+                force_reload: false,
+                config: None,
+            }).await?;
+
+            if skill.auto_activate {
+                self.activate(&skill.name).await?;
+            }
+
+            tracing::info!("Project skill '{}' loaded from {:?}", skill.name, path);
+            loaded.push(skill.name.clone());
+        }
+
+        Ok(loaded)
+    }
+
     /// 移除 Skill
     ///
     /// 完全卸载并删除 Skill。
@@ -635,6 +813,15 @@ impl SkillManager {
         Ok(active_skills.keys().cloned().collect())
     }
 
+    /// 获取已加载 Skill 的方法列表
+    ///
+    /// Skill 未加载时返回 `Ok(None)`。
+    pub fn describe_methods(&self, name: &str) -> Result<Option<Vec<MethodDescriptor>>> {
+        let active_skills = self.active_skills.lock()
+            .map_err(|e| CisError::skill(format!("Lock failed: {}", e)))?;
+        Ok(active_skills.get(name).map(|active| active.skill.describe_methods()))
+    }
+
     /// 获取 Skill Registry（用于直接访问 registry 操作）
     pub fn get_registry(&self) -> Result<std::sync::MutexGuard<'_, SkillRegistry>> {
         self.registry.lock()
@@ -736,6 +923,7 @@ impl SkillManager {
                     subscriptions: vec![],
                     config_schema: None,
                     room_config: None,
+                    dependencies: Vec::new(),
                 },
                 runtime: super::types::SkillRuntime {
                     state: SkillState::Registered,
@@ -832,6 +1020,7 @@ impl SkillManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::skill::types::SkillDependency;
 
     fn setup_test_env() -> std::path::PathBuf {
         use std::sync::atomic::{AtomicU64, Ordering};
@@ -872,6 +1061,7 @@ mod tests {
             subscriptions: vec![],
             config_schema: None,
             room_config: None,
+            dependencies: Vec::new(),
         };
 
         manager.register(meta).unwrap();
@@ -900,6 +1090,83 @@ mod tests {
         cleanup_test_env(&temp_dir);
     }
 
+    fn dep_meta(name: &str, version: &str, deps: &[(&str, &str)]) -> SkillMeta {
+        SkillMeta {
+            name: name.to_string(),
+            version: version.to_string(),
+            description: "Test".to_string(),
+            author: "Test".to_string(),
+            skill_type: SkillType::Native,
+            path: "/test".to_string(),
+            db_path: "/test/db".to_string(),
+            permissions: vec![],
+            subscriptions: vec![],
+            config_schema: None,
+            room_config: None,
+            dependencies: deps
+                .iter()
+                .map(|(n, req)| SkillDependency::new(*n, semver::VersionReq::parse(req).unwrap()))
+                .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_resolves_dependencies() {
+        let temp_dir = setup_test_env();
+
+        let db_manager = Arc::new(DbManager::new().unwrap());
+        let manager = SkillManager::new(db_manager).unwrap();
+
+        manager.register(dep_meta("app", "1.0.0", &[("lib-a", "^1.2"), ("lib-b", "*")])).unwrap();
+        manager.register(dep_meta("lib-a", "1.3.0", &[("lib-b", ">=0.5")])).unwrap();
+        manager.register(dep_meta("lib-b", "0.9.0", &[])).unwrap();
+
+        assert_eq!(manager.resolve_dependencies("app").unwrap(), vec!["lib-b", "lib-a", "app"]);
+
+        // 加载 app 时自动加载依赖
+        manager.load("app", LoadOptions::default()).await.unwrap();
+        assert!(manager.is_loaded("lib-a").unwrap());
+        assert!(manager.is_loaded("lib-b").unwrap());
+
+        let dag = manager.dependency_graph().unwrap();
+        assert_eq!(dag.node_count(), 3);
+        assert_eq!(dag.root_nodes(), ["lib-b".to_string()]);
+        assert!(dag.validate().is_ok());
+
+        // 版本不满足
+        manager.register(dep_meta("legacy", "1.0.0", &[("lib-a", "^2")])).unwrap();
+        let err = manager.load("legacy", LoadOptions::default()).await.unwrap_err();
+        assert!(matches!(err, CisError::UnresolvedDependency(ref name, ref req) if name == "lib-a" && req == "^2"));
+
+        // 依赖未注册
+        manager.register(dep_meta("orphan", "1.0.0", &[("missing", "*")])).unwrap();
+        assert!(matches!(
+            manager.resolve_dependencies("orphan"),
+            Err(CisError::UnresolvedDependency(..))
+        ));
+
+        cleanup_test_env(&temp_dir);
+    }
+
+    #[tokio::test]
+    async fn test_circular_dependency_rejected() {
+        let temp_dir = setup_test_env();
+
+        let db_manager = Arc::new(DbManager::new().unwrap());
+        let manager = SkillManager::new(db_manager).unwrap();
+
+        manager.register(dep_meta("x", "1.0.0", &[("y", "*")])).unwrap();
+        manager.register(dep_meta("y", "1.0.0", &[("z", "*")])).unwrap();
+        manager.register(dep_meta("z", "1.0.0", &[("x", "*")])).unwrap();
+
+        let err = manager.load("x", LoadOptions::default()).await.unwrap_err();
+        assert!(err.to_string().contains("x -> y -> z -> x"));
+        assert!(!manager.is_loaded("y").unwrap());
+        assert!(manager.dependency_graph().is_err());
+
+        cleanup_test_env(&temp_dir);
+    }
+
     #[test]
     fn test_skill_name_validation() {
         // 测试名称长度验证
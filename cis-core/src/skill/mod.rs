@@ -29,6 +29,7 @@ pub mod chain;
 pub mod cis_admin;
 pub mod compatibility_db;
 pub mod dag;
+pub mod dispatch;
 pub mod manager;
 pub mod manifest;
 pub mod permission_checker;
@@ -49,13 +50,14 @@ pub use permission_checker::{
     CheckContext, Constraint, PermissionCategory, PermissionChecker, PermissionLevel,
     PermissionResult, PermissionScope, ResourcePattern,
 };
+pub use dispatch::{DispatchDecision, SemanticSkillDispatcher, SkillCandidate};
 pub use dag::{SkillDagBuilder, SkillDagContext, SkillDagConverter, SkillDagStats};
 pub use project_registry::{ProjectSkillRegistry, ProjectSkillConfig, ProjectSkillEntry, ProjectSkillDiscovery};
 pub use registry::{SkillRegistry, SkillRegistration};
 pub use router::{ChainExecutionResult, ResolvedParameters, RouteResult,
                 SkillCompatibility, SkillRoutingResult, SkillVectorRouter};
 pub use semantics::{SkillIoSignature, SkillScope, SkillSemanticDescription, SkillSemanticMatcher, SkillSemanticRegistry, SkillSemanticsExt};
-pub use types::{LoadOptions, SkillConfig, SkillInfo, SkillMeta, SkillRoomInfo, SkillState, SkillType};
+pub use types::{LoadOptions, MethodDescriptor, SkillConfig, SkillDependency, SkillInfo, SkillMeta, SkillRoomInfo, SkillState, SkillType};
 
 // Re-export Matrix types for Skill integration
 pub use crate::matrix::nucleus::{MatrixNucleus, RoomOptions};
@@ -87,6 +89,11 @@ pub trait Skill: Send + Sync {
         false
     }
 
+    /// 可调用方法列表（可选实现，默认为空）
+    fn describe_methods(&self) -> Vec<MethodDescriptor> {
+        Vec::new()
+    }
+
     /// 初始化
     ///
     /// 默认实现：创建 Room，注册 Matrix 事件处理器
@@ -436,6 +436,7 @@ mod tests {
                 subscriptions: vec![],
                 config_schema: None,
                 room_config: None,
+                dependencies: Vec::new(),
             },
             runtime: SkillRuntime {
                 state: SkillState::Active,
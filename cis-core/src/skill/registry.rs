@@ -268,6 +268,7 @@ mod tests {
             subscriptions: vec![],
             config_schema: None,
             room_config: None,
+            dependencies: Vec::new(),
         };
 
         // 注册
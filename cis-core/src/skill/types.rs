@@ -133,6 +133,42 @@ pub struct SkillMeta {
     /// Room 配置（可选）
     #[serde(default)]
     pub room_config: Option<serde_json::Value>,
+    /// 依赖的其他 Skill
+    #[serde(default)]
+    pub dependencies: Vec<SkillDependency>,
+}
+
+/// Skill 依赖声明
+///
+/// skill.toml 示例：
+/// ```toml
+/// [[dependencies]]
+/// name = "memory-organizer"
+/// version_req = "^1.2"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillDependency {
+    /// 依赖的 Skill 名称
+    pub name: String,
+    /// 版本要求 (semver)
+    pub version_req: semver::VersionReq,
+}
+
+impl SkillDependency {
+    /// 创建依赖声明
+    pub fn new(name: impl Into<String>, version_req: semver::VersionReq) -> Self {
+        Self {
+            name: name.into(),
+            version_req,
+        }
+    }
+
+    /// 检查版本号是否满足要求（无法解析的版本视为不满足）
+    pub fn matches(&self, version: &str) -> bool {
+        semver::Version::parse(version)
+            .map(|v| self.version_req.matches(&v))
+            .unwrap_or(false)
+    }
 }
 
 impl SkillMeta {
@@ -214,6 +250,7 @@ impl SkillInfo {
                 subscriptions: Vec::new(),
                 config_schema: None,
                 room_config: None,
+                dependencies: Vec::new(),
             },
             runtime: SkillRuntime {
                 state: SkillState::Installed,
@@ -250,6 +287,22 @@ impl SkillConfig {
     }
 }
 
+/// Skill 方法描述（用于内省）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MethodDescriptor {
+    /// 方法名
+    pub name: String,
+    /// 方法说明
+    #[serde(default)]
+    pub description: String,
+    /// 参数 JSON Schema
+    #[serde(default)]
+    pub params_schema: serde_json::Value,
+    /// 返回值 JSON Schema
+    #[serde(default)]
+    pub return_schema: serde_json::Value,
+}
+
 /// Skill 加载选项
 #[derive(Debug, Clone, Default)]
 pub struct LoadOptions {
@@ -82,7 +82,7 @@ impl FederationDb {
                 node_id TEXT PRIMARY KEY,
                 did TEXT NOT NULL,
                 endpoint_ws TEXT,
-                status INTEGER, -- 0=离线, 1=在线, 2=打洞中
+                status INTEGER, -- 0=离线, 1=在线, 2=打洞中, 3=长期不可达
                 last_seen INTEGER,
                 rtt_ms INTEGER, -- 网络延迟
                 public_key TEXT
@@ -292,6 +292,42 @@ impl FederationDb {
         Ok(())
     }
 
+    /// 列出全部节点
+    pub fn list_peers(&self) -> Result<Vec<PeerInfo>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT node_id, did, endpoint_ws, status, last_seen, rtt_ms, public_key
+             FROM network_peers ORDER BY node_id"
+        ).map_err(|e| CisError::Storage(format!("Failed to prepare list_peers query: {}", e)))?;
+
+        let peers: Result<Vec<PeerInfo>> = stmt
+            .query_map([], Self::row_to_peer_info)
+            .map_err(|e| CisError::Storage(format!("Failed to query peers: {}", e)))?
+            .map(|r| r.map_err(CisError::Database))
+            .collect();
+
+        peers
+    }
+
+    /// 仅更新节点状态，不刷新 last_seen（用于标记离线/不可达）
+    pub fn set_peer_status(&self, node_id: &str, status: PeerStatus) -> Result<()> {
+        self.conn.execute(
+            "UPDATE network_peers SET status = ?1 WHERE node_id = ?2",
+            rusqlite::params![status as i32, node_id],
+        ).map_err(|e| CisError::Storage(format!("Failed to set peer status: {}", e)))?;
+        
+        Ok(())
+    }
+
+    /// 删除节点，返回是否存在
+    pub fn delete_peer(&self, node_id: &str) -> Result<bool> {
+        let removed = self.conn.execute(
+            "DELETE FROM network_peers WHERE node_id = ?1",
+            rusqlite::params![node_id],
+        ).map_err(|e| CisError::Storage(format!("Failed to delete peer: {}", e)))?;
+        
+        Ok(removed > 0)
+    }
+
     /// 更新 RTT
     pub fn update_peer_rtt(&self, node_id: &str, rtt_ms: i32) -> Result<()> {
         self.conn.execute(
@@ -478,6 +514,8 @@ pub enum PeerStatus {
     Online = 1,
     /// 打洞中
     HolePunching = 2,
+    /// 长期不可达（见 `cis node sync`）
+    Stale = 3,
 }
 
 impl PeerStatus {
@@ -486,6 +524,7 @@ impl PeerStatus {
         match value {
             1 => PeerStatus::Online,
             2 => PeerStatus::HolePunching,
+            3 => PeerStatus::Stale,
             _ => PeerStatus::Offline,
         }
     }
@@ -18,6 +18,8 @@ pub struct MemoryEntry {
     pub category: MemoryCategory,
     pub created_at: i64,
     pub updated_at: i64,
+    /// User-defined tags (sorted)
+    pub tags: Vec<String>,
 }
 
 /// Independent memory database
@@ -114,6 +116,16 @@ impl MemoryDb {
             [],
         ).map_err(|e| CisError::storage(format!("Failed to create memory_index table: {}", e)))?;
 
+        // Memory tag table (one row per key/tag pair)
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS memory_tags (
+                memory_id TEXT NOT NULL,
+                tag TEXT NOT NULL,
+                PRIMARY KEY (memory_id, tag)
+            )",
+            [],
+        ).map_err(|e| CisError::storage(format!("Failed to create memory_tags table: {}", e)))?;
+
         // Create indexes
         self.conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_private_category ON private_entries(category)",
@@ -135,6 +147,11 @@ impl MemoryDb {
             [],
         ).map_err(|e| CisError::storage(format!("Failed to create index: {}", e)))?;
 
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_memory_tags_tag ON memory_tags(tag, memory_id)",
+            [],
+        ).map_err(|e| CisError::storage(format!("Failed to create index: {}", e)))?;
+
         Ok(())
     }
 
@@ -191,6 +208,29 @@ impl MemoryDb {
         Ok(())
     }
 
+    /// 写入同步得到的公域记忆
+    ///
+    /// 与 `set_public` 不同，保留对端的 `created_at` / `updated_at`，
+    /// 并直接标记为已同步，避免回传给来源节点。
+    pub fn upsert_public_entry(&self, entry: &MemoryEntry) -> Result<()> {
+        let category_str = format!("{:?}", entry.category);
+
+        self.conn.execute(
+            "INSERT INTO public_entries (key, value, category, created_at, updated_at, federate, sync_status)
+             VALUES (?1, ?2, ?3, ?4, ?5, 1, 'synced')
+             ON CONFLICT(key) DO UPDATE SET
+             value = excluded.value,
+             category = excluded.category,
+             updated_at = excluded.updated_at,
+             sync_status = 'synced'",
+            rusqlite::params![entry.key, entry.value, category_str, entry.created_at, entry.updated_at],
+        ).map_err(|e| CisError::storage(format!("Failed to upsert public memory: {}", e)))?;
+
+        self.update_index(&entry.key, MemoryDomain::Public, entry.category, None)?;
+
+        Ok(())
+    }
+
     /// 存储记忆（指定域）
     pub fn set(&self, key: &str, value: &[u8], domain: MemoryDomain, category: MemoryCategory) -> Result<()> {
         match domain {
@@ -228,11 +268,15 @@ impl MemoryDb {
                 category: parse_category(&row.get::<_, String>(2)?),
                 created_at: row.get(3)?,
                 updated_at: row.get(4)?,
+                tags: Vec::new(),
             })
         });
 
         match result {
-            Ok(entry) => Ok(Some(entry)),
+            Ok(mut entry) => {
+                entry.tags = self.get_tags(&entry.key)?;
+                Ok(Some(entry))
+            }
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(CisError::storage(format!("Failed to get private memory: {}", e))),
         }
@@ -252,11 +296,15 @@ impl MemoryDb {
                 category: parse_category(&row.get::<_, String>(2)?),
                 created_at: row.get(3)?,
                 updated_at: row.get(4)?,
+                tags: Vec::new(),
             })
         });
 
         match result {
-            Ok(entry) => Ok(Some(entry)),
+            Ok(mut entry) => {
+                entry.tags = self.get_tags(&entry.key)?;
+                Ok(Some(entry))
+            }
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(CisError::storage(format!("Failed to get public memory: {}", e))),
         }
@@ -288,6 +336,10 @@ impl MemoryDb {
                 "DELETE FROM memory_index WHERE key = ?1",
                 [key],
             ).map_err(|e| CisError::storage(format!("Failed to delete index: {}", e)))?;
+            self.conn.execute(
+                "DELETE FROM memory_tags WHERE memory_id = ?1",
+                [key],
+            ).map_err(|e| CisError::storage(format!("Failed to delete tags: {}", e)))?;
         }
 
         Ok(deleted > 0)
@@ -363,6 +415,31 @@ impl MemoryDb {
         Ok(keys)
     }
 
+    /// 列出前缀下的记忆键及其值大小（字节）与更新时间
+    pub fn list_key_sizes(&self, prefix: &str) -> Result<Vec<(String, u64, i64)>> {
+        let like = format!("{}%", prefix);
+        let mut stmt = self.conn.prepare(
+            "SELECT key, length(value), updated_at FROM private_entries WHERE key LIKE ?1
+             UNION ALL
+             SELECT key, length(value), updated_at FROM public_entries WHERE key LIKE ?1"
+        ).map_err(|e| CisError::storage(format!("Failed to prepare query: {}", e)))?;
+
+        let rows = stmt.query_map([&like], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?.max(0) as u64,
+                row.get::<_, Option<i64>>(2)?.unwrap_or(0),
+            ))
+        }).map_err(|e| CisError::storage(format!("Failed to query keys: {}", e)))?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row.map_err(|e| CisError::storage(format!("Failed to get row: {}", e)))?);
+        }
+
+        Ok(entries)
+    }
+
     /// 获取待同步的公域记忆（用于 P2P 同步）
     pub fn get_pending_sync(&self, limit: usize) -> Result<Vec<MemoryEntry>> {
         let mut entries = Vec::new();
@@ -382,11 +459,14 @@ impl MemoryDb {
                 category: parse_category(&row.get::<_, String>(2)?),
                 created_at: row.get(3)?,
                 updated_at: row.get(4)?,
+                tags: Vec::new(),
             })
         }).map_err(|e| CisError::storage(format!("Failed to query pending entries: {}", e)))?;
 
         for row in rows {
-            entries.push(row.map_err(|e| CisError::storage(format!("Failed to get row: {}", e)))?);
+            let mut entry = row.map_err(|e| CisError::storage(format!("Failed to get row: {}", e)))?;
+            entry.tags = self.get_tags(&entry.key)?;
+            entries.push(entry);
         }
 
         Ok(entries)
@@ -401,6 +481,91 @@ impl MemoryDb {
         Ok(())
     }
 
+    /// 设置记忆标签（替换已有标签）
+    ///
+    /// 标签去除首尾空白后去重，空标签被忽略。记忆不存在时返回错误。
+    pub fn set_tags(&self, key: &str, tags: &[String]) -> Result<()> {
+        if self.get_domain(key)?.is_none() {
+            return Err(CisError::not_found(format!("Memory not found: {}", key)));
+        }
+
+        let tx = self.conn.unchecked_transaction()
+            .map_err(|e| CisError::storage(format!("Failed to begin transaction: {}", e)))?;
+        tx.execute("DELETE FROM memory_tags WHERE memory_id = ?1", [key])
+            .map_err(|e| CisError::storage(format!("Failed to clear tags: {}", e)))?;
+        for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            tx.execute(
+                "INSERT OR IGNORE INTO memory_tags (memory_id, tag) VALUES (?1, ?2)",
+                rusqlite::params![key, tag],
+            ).map_err(|e| CisError::storage(format!("Failed to set tag: {}", e)))?;
+        }
+        tx.commit()
+            .map_err(|e| CisError::storage(format!("Failed to commit tags: {}", e)))?;
+        Ok(())
+    }
+
+    /// 获取记忆标签（按字母序）
+    pub fn get_tags(&self, key: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tag FROM memory_tags WHERE memory_id = ?1 ORDER BY tag"
+        ).map_err(|e| CisError::storage(format!("Failed to prepare query: {}", e)))?;
+
+        let rows = stmt.query_map([key], |row| row.get::<_, String>(0))
+            .map_err(|e| CisError::storage(format!("Failed to query tags: {}", e)))?;
+
+        let mut tags = Vec::new();
+        for row in rows {
+            tags.push(row.map_err(|e| CisError::storage(format!("Failed to get row: {}", e)))?);
+        }
+        Ok(tags)
+    }
+
+    /// 列出带有指定标签、且键以 `prefix` 开头的记忆（按键排序）
+    pub fn list_by_tag(&self, tag: &str, prefix: &str) -> Result<Vec<MemoryEntry>> {
+        let like = format!("{}%", prefix);
+        let keys = {
+            let mut stmt = self.conn.prepare(
+                "SELECT memory_id FROM memory_tags WHERE tag = ?1 AND memory_id LIKE ?2 ORDER BY memory_id"
+            ).map_err(|e| CisError::storage(format!("Failed to prepare query: {}", e)))?;
+
+            let rows = stmt.query_map(rusqlite::params![tag.trim(), like], |row| row.get::<_, String>(0))
+                .map_err(|e| CisError::storage(format!("Failed to query tagged keys: {}", e)))?;
+
+            let mut keys = Vec::new();
+            for row in rows {
+                keys.push(row.map_err(|e| CisError::storage(format!("Failed to get row: {}", e)))?);
+            }
+            keys
+        };
+
+        let mut entries = Vec::with_capacity(keys.len());
+        for key in keys {
+            if let Some(entry) = self.get(&key)? {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+
+    /// 列出键以 `prefix` 开头的记忆使用的所有标签及条目数（按条目数降序）
+    pub fn list_tags(&self, prefix: &str) -> Result<Vec<(String, usize)>> {
+        let like = format!("{}%", prefix);
+        let mut stmt = self.conn.prepare(
+            "SELECT tag, COUNT(*) FROM memory_tags WHERE memory_id LIKE ?1
+             GROUP BY tag ORDER BY COUNT(*) DESC, tag"
+        ).map_err(|e| CisError::storage(format!("Failed to prepare query: {}", e)))?;
+
+        let rows = stmt.query_map([&like], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?.max(0) as usize))
+        }).map_err(|e| CisError::storage(format!("Failed to query tags: {}", e)))?;
+
+        let mut tags = Vec::new();
+        for row in rows {
+            tags.push(row.map_err(|e| CisError::storage(format!("Failed to get row: {}", e)))?);
+        }
+        Ok(tags)
+    }
+
     /// 更新记忆索引
     fn update_index(
         &self,
@@ -524,6 +689,12 @@ mod tests {
         let keys = db.list_keys("prefix/", Some(MemoryDomain::Public)).unwrap();
         assert_eq!(keys.len(), 1);
 
+        // 带大小列出
+        let mut sizes = db.list_key_sizes("prefix/").unwrap();
+        sizes.sort();
+        assert_eq!(sizes.len(), 3);
+        assert_eq!((sizes[0].0.as_str(), sizes[0].1), ("prefix/a", 1));
+
         db.close().unwrap();
         cleanup_test_db(&temp_dir);
     }
@@ -570,4 +741,38 @@ mod tests {
         db.close().unwrap();
         cleanup_test_db(&temp_dir);
     }
+
+    #[test]
+    fn test_memory_db_tags() {
+        let (db, temp_dir) = setup_test_db();
+
+        db.set_private("notes/a", b"1", MemoryCategory::Context).unwrap();
+        db.set_public("notes/b", b"2", MemoryCategory::Result).unwrap();
+        db.set_public("other/c", b"3", MemoryCategory::Result).unwrap();
+
+        db.set_tags("notes/a", &["work".to_string(), " important ".to_string(), "work".to_string()]).unwrap();
+        db.set_tags("notes/b", &["work".to_string()]).unwrap();
+        db.set_tags("other/c", &["home".to_string()]).unwrap();
+        assert!(db.set_tags("missing", &["work".to_string()]).is_err());
+
+        let entry = db.get("notes/a").unwrap().unwrap();
+        assert_eq!(entry.tags, vec!["important", "work"]);
+
+        let tagged = db.list_by_tag("work", "").unwrap();
+        assert_eq!(tagged.iter().map(|e| e.key.as_str()).collect::<Vec<_>>(), vec!["notes/a", "notes/b"]);
+        assert_eq!(db.list_by_tag("home", "notes/").unwrap().len(), 0);
+
+        assert_eq!(
+            db.list_tags("").unwrap(),
+            vec![("work".to_string(), 2), ("home".to_string(), 1), ("important".to_string(), 1)]
+        );
+
+        // 替换与删除
+        db.set_tags("notes/b", &[]).unwrap();
+        db.delete("notes/a").unwrap();
+        assert!(db.list_by_tag("work", "").unwrap().is_empty());
+
+        db.close().unwrap();
+        cleanup_test_db(&temp_dir);
+    }
 }
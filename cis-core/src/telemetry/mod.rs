@@ -27,11 +27,13 @@
 use std::path::Path;
 
 pub mod request_logger;
+pub mod stats;
 
 pub use request_logger::{
     LogQuery, RequestLog, RequestLogBuilder, RequestLogger, RequestMetrics, 
     RequestResult, RequestStage, SessionStats
 };
+pub use stats::{SkillStats, TelemetryStats, Trend, TrendDirection, WindowStats};
 
 /// 遥测配置
 #[derive(Debug, Clone)]
@@ -1,9 +1,11 @@
+use super::stats::{CallRecord, TelemetryStats, STATS_CACHE_TTL, UNROUTED_SKILL};
 use super::TelemetryConfig;
 use chrono::{DateTime, Utc};
 use rusqlite::{Connection, Row};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Mutex;
 use std::time::Instant;
 
 /// 请求日志记录器
@@ -31,6 +33,8 @@ use std::time::Instant;
 pub struct RequestLogger {
     conn: Connection,
     config: TelemetryConfig,
+    /// 技能统计缓存（见 [`STATS_CACHE_TTL`]）
+    stats_cache: Mutex<Option<(Instant, TelemetryStats)>>,
 }
 
 /// 完整的请求日志
@@ -184,7 +188,11 @@ impl RequestLogger {
         let conn = Connection::open(path)?;
         let config = config.unwrap_or_default();
         
-        let logger = Self { conn, config };
+        let logger = Self {
+            conn,
+            config,
+            stats_cache: Mutex::new(None),
+        };
         logger.init_tables()?;
         Ok(logger)
     }
@@ -466,6 +474,49 @@ impl RequestLogger {
         })
     }
     
+    /// 按技能聚合最近 7 天的统计，结果缓存 30 秒
+    pub fn skill_stats(&self) -> crate::error::Result<TelemetryStats> {
+        let mut cache = self.stats_cache.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((at, stats)) = cache.as_ref() {
+            if at.elapsed() < STATS_CACHE_TTL {
+                return Ok(stats.clone());
+            }
+        }
+
+        let now = Utc::now();
+        let mut stmt = self.conn.prepare(
+            "SELECT result_type, result_summary, total_duration_ms, metadata, timestamp
+             FROM request_logs WHERE timestamp > ?"
+        )?;
+        let rows = stmt.query_map([(now - chrono::Duration::days(7)).timestamp()], |row| {
+            let result_type: String = row.get(0)?;
+            let result_summary: Option<String> = row.get(1)?;
+            let metadata: Option<String> = row.get(3)?;
+
+            let success = result_type == "success";
+            let skill_id = result_summary
+                .filter(|_| success)
+                .or_else(|| {
+                    metadata
+                        .and_then(|m| serde_json::from_str::<HashMap<String, String>>(&m).ok())
+                        .and_then(|mut m| m.remove("skill_id"))
+                })
+                .unwrap_or_else(|| UNROUTED_SKILL.to_string());
+
+            Ok(CallRecord {
+                skill_id,
+                timestamp: DateTime::from_timestamp(row.get(4)?, 0).unwrap_or(now),
+                success,
+                duration_ms: row.get::<_, Option<i64>>(2)?.unwrap_or(0).max(0) as u64,
+            })
+        })?;
+        let records: Vec<CallRecord> = rows.filter_map(|r| r.ok()).collect();
+
+        let stats = TelemetryStats::compute(&records, now);
+        *cache = Some((Instant::now(), stats.clone()));
+        Ok(stats)
+    }
+    
     /// 清理旧日志
     /// 
     /// # 参数
@@ -0,0 +1,253 @@
+//! 按技能聚合的遥测统计
+//!
+//! 从 `request_logs` 读取最近 7 天的请求，按技能统计最近 1 小时 / 1 天 / 1 周的
+//! 调用量、成功率与耗时分位数，并给出调用量趋势和错误率异常。
+//!
+//! - 技能取自成功请求的 `skill_id`；失败请求取元数据中的 `skill_id`，
+//!   没有则归入 [`UNROUTED_SKILL`]
+//! - 趋势：最近 24 小时与前 24 小时的调用量对比，变化小于 10% 视为平稳
+//! - 异常：最近 1 小时的错误率高于 7 天内每日错误率均值 2 个标准差以上
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 统计结果缓存有效期
+pub const STATS_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// 无法归属到技能的请求
+pub const UNROUTED_SKILL: &str = "(unrouted)";
+
+/// 趋势判定阈值（百分比）
+const TREND_STABLE_PCT: f32 = 10.0;
+
+/// 单条请求记录（统计输入）
+#[derive(Debug, Clone)]
+pub struct CallRecord {
+    pub skill_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub success: bool,
+    pub duration_ms: u64,
+}
+
+/// 时间窗口内的统计
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindowStats {
+    pub call_count: u64,
+    /// 0.0 - 1.0
+    pub success_rate: f64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub p99_ms: u64,
+}
+
+impl WindowStats {
+    fn compute(records: &[&CallRecord]) -> Self {
+        if records.is_empty() {
+            return Self::default();
+        }
+
+        let mut durations: Vec<u64> = records.iter().map(|r| r.duration_ms).collect();
+        durations.sort_unstable();
+        let successes = records.iter().filter(|r| r.success).count();
+
+        Self {
+            call_count: records.len() as u64,
+            success_rate: successes as f64 / records.len() as f64,
+            p50_ms: percentile(&durations, 0.50),
+            p95_ms: percentile(&durations, 0.95),
+            p99_ms: percentile(&durations, 0.99),
+        }
+    }
+
+    /// 错误率
+    pub fn error_rate(&self) -> f64 {
+        if self.call_count == 0 {
+            0.0
+        } else {
+            1.0 - self.success_rate
+        }
+    }
+}
+
+/// 最近邻分位数，`sorted` 需已升序
+fn percentile(sorted: &[u64], p: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// 趋势方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendDirection {
+    Up,
+    Down,
+    Stable,
+}
+
+/// 调用量趋势
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trend {
+    pub direction: TrendDirection,
+    /// 相对前一周期的变化百分比
+    pub change_pct: f32,
+}
+
+impl Trend {
+    fn compute(current: u64, previous: u64) -> Self {
+        let change_pct = match (current, previous) {
+            (0, 0) => 0.0,
+            (_, 0) => 100.0,
+            _ => (current as f32 - previous as f32) / previous as f32 * 100.0,
+        };
+        let direction = if change_pct.abs() < TREND_STABLE_PCT {
+            TrendDirection::Stable
+        } else if change_pct > 0.0 {
+            TrendDirection::Up
+        } else {
+            TrendDirection::Down
+        };
+        Self { direction, change_pct }
+    }
+}
+
+/// 单个技能的统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillStats {
+    pub skill_id: String,
+    pub last_hour: WindowStats,
+    pub last_day: WindowStats,
+    pub last_week: WindowStats,
+    pub trend: Trend,
+    /// 当前错误率异常偏高
+    pub anomaly: bool,
+}
+
+/// 所有技能的统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryStats {
+    pub generated_at: DateTime<Utc>,
+    /// 按最近一周调用量降序
+    pub skills: Vec<SkillStats>,
+}
+
+impl TelemetryStats {
+    /// 从最近 7 天的请求记录计算统计
+    pub fn compute(records: &[CallRecord], now: DateTime<Utc>) -> Self {
+        let mut by_skill: HashMap<&str, Vec<&CallRecord>> = HashMap::new();
+        for record in records {
+            by_skill.entry(record.skill_id.as_str()).or_default().push(record);
+        }
+
+        let hour = chrono::Duration::hours(1);
+        let day = chrono::Duration::days(1);
+
+        let mut skills: Vec<SkillStats> = by_skill
+            .into_iter()
+            .map(|(skill_id, calls)| {
+                let last_hour = WindowStats::compute(&window(&calls, now - hour, now));
+                let last_day = WindowStats::compute(&window(&calls, now - day, now));
+                let last_week = WindowStats::compute(&window(&calls, now - chrono::Duration::days(7), now));
+                let previous_day = window(&calls, now - day * 2, now - day).len() as u64;
+
+                let daily_error_rates: Vec<f64> = (0..7)
+                    .map(|d| WindowStats::compute(&window(&calls, now - day * (d + 1), now - day * d)))
+                    .filter(|w| w.call_count > 0)
+                    .map(|w| w.error_rate())
+                    .collect();
+                let anomaly = last_hour.call_count > 0 && is_anomalous(last_hour.error_rate(), &daily_error_rates);
+
+                SkillStats {
+                    skill_id: skill_id.to_string(),
+                    trend: Trend::compute(last_day.call_count, previous_day),
+                    last_hour,
+                    last_day,
+                    last_week,
+                    anomaly,
+                }
+            })
+            .collect();
+
+        skills.sort_by(|a, b| {
+            b.last_week
+                .call_count
+                .cmp(&a.last_week.call_count)
+                .then_with(|| a.skill_id.cmp(&b.skill_id))
+        });
+
+        Self { generated_at: now, skills }
+    }
+}
+
+/// (start, end] 内的记录
+fn window<'a>(calls: &[&'a CallRecord], start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<&'a CallRecord> {
+    calls
+        .iter()
+        .filter(|r| r.timestamp > start && r.timestamp <= end)
+        .copied()
+        .collect()
+}
+
+/// 当前值高于历史均值 2 个标准差以上（至少需要 2 个历史样本）
+fn is_anomalous(current: f64, history: &[f64]) -> bool {
+    if history.len() < 2 {
+        return false;
+    }
+    let mean = history.iter().sum::<f64>() / history.len() as f64;
+    let variance = history.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / history.len() as f64;
+    current > mean + 2.0 * variance.sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(skill_id: &str, minutes_ago: i64, success: bool, duration_ms: u64, now: DateTime<Utc>) -> CallRecord {
+        CallRecord {
+            skill_id: skill_id.to_string(),
+            timestamp: now - chrono::Duration::minutes(minutes_ago),
+            success,
+            duration_ms,
+        }
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let now = Utc::now();
+        let mut records = Vec::new();
+
+        // search：前几天偶有失败，最近一小时全部失败
+        for day in 1..7 {
+            for i in 0..10 {
+                records.push(record("search", day * 24 * 60 + i, i != 0, 100, now));
+            }
+        }
+        for i in 0..4 {
+            records.push(record("search", 5 + i, false, 10 * (i as u64 + 1), now));
+        }
+        // deploy：只在最近一天被调用
+        records.push(record("deploy", 30, true, 500, now));
+
+        let stats = TelemetryStats::compute(&records, now);
+        assert_eq!(stats.skills[0].skill_id, "search");
+
+        let search = &stats.skills[0];
+        assert_eq!(search.last_hour.call_count, 4);
+        assert_eq!(search.last_hour.success_rate, 0.0);
+        assert_eq!((search.last_hour.p50_ms, search.last_hour.p99_ms), (20, 40));
+        assert_eq!(search.last_week.call_count, 64);
+        assert!(search.anomaly);
+        // 最近一天 4 次，前一天 10 次
+        assert_eq!(search.trend.direction, TrendDirection::Down);
+        assert!((search.trend.change_pct + 60.0).abs() < 0.01);
+
+        let deploy = &stats.skills[1];
+        assert_eq!(deploy.trend.direction, TrendDirection::Up);
+        assert!(!deploy.anomaly);
+    }
+}
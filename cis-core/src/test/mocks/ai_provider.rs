@@ -1,17 +1,60 @@
 //! # Mock AI Provider
 //!
 //! AI Provider 的 Mock 实现，用于测试 AI 相关功能。
+//!
+//! 同时实现 [`crate::traits::AiProvider`] 与 [`crate::ai::AiProvider`]，
+//! Skill 测试可以用它代替真实的 AI Provider。
+//!
+//! 通过 [`MockAiProvider::with_responses`] 预设响应序列：按顺序消费，
+//! 用完后一直重复最后一个，便于编写确定性的测试。
 
 use async_trait::async_trait;
+use crate::ai::{self, AiError};
+use crate::conversation::ConversationContext;
 use crate::error::{CisError, Result};
 use crate::traits::{
     AiProvider, CompletionRequest, CompletionResponse,
     EmbeddingRequest, EmbeddingResponse, ModelInfo, TokenUsage,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::RwLock as AsyncRwLock;
 
+/// 预设的 AI 响应
+#[derive(Debug)]
+pub enum MockResponse {
+    /// 返回文本
+    Return(String),
+    /// 返回错误
+    Fail(AiError),
+    /// 延迟后返回文本
+    Delay(Duration, String),
+}
+
+impl MockResponse {
+    /// 复制响应（`AiError` 不可 Clone，按变体重建）
+    fn replay(&self) -> Self {
+        match self {
+            Self::Return(text) => Self::Return(text.clone()),
+            Self::Delay(delay, text) => Self::Delay(*delay, text.clone()),
+            Self::Fail(error) => Self::Fail(match error {
+                AiError::NotAvailable(msg) => AiError::NotAvailable(msg.clone()),
+                AiError::CliError(msg) => AiError::CliError(msg.clone()),
+                AiError::InvalidResponse(msg) => AiError::InvalidResponse(msg.clone()),
+                AiError::Io(e) => AiError::Io(std::io::Error::new(e.kind(), e.to_string())),
+                AiError::Utf8(e) => AiError::InvalidResponse(e.to_string()),
+                AiError::BudgetExceeded { remaining, reset_at } => AiError::BudgetExceeded {
+                    remaining: *remaining,
+                    reset_at: *reset_at,
+                },
+                AiError::Http(msg) => AiError::Http(msg.clone()),
+                AiError::MalformedStructuredOutput(raw) => AiError::MalformedStructuredOutput(raw.clone()),
+            }),
+        }
+    }
+}
+
 /// AI Provider Mock
 #[derive(Clone)]
 pub struct MockAiProvider {
@@ -24,6 +67,8 @@ pub struct MockAiProvider {
     latency_ms: Arc<Mutex<u64>>,
     should_fail: Arc<Mutex<Option<String>>>,
     models: Arc<Mutex<Vec<ModelInfo>>>,
+    scripted: Arc<Mutex<VecDeque<MockResponse>>>,
+    prompts: Arc<Mutex<Vec<String>>>,
 }
 
 impl std::fmt::Debug for MockAiProvider {
@@ -60,6 +105,63 @@ impl MockAiProvider {
             latency_ms: Arc::new(Mutex::new(0)),
             should_fail: Arc::new(Mutex::new(None)),
             models: Arc::new(Mutex::new(default_models)),
+            scripted: Arc::new(Mutex::new(VecDeque::new())),
+            prompts: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// 创建按顺序返回预设响应的 Mock
+    ///
+    /// 响应用完后一直重复最后一个；序列为空时返回默认响应。
+    pub fn with_responses(responses: Vec<MockResponse>) -> Self {
+        let provider = Self::new();
+        *provider.scripted.lock().unwrap() = responses.into();
+        provider
+    }
+
+    /// 已处理的调用次数
+    pub fn call_count(&self) -> usize {
+        self.prompts.lock().unwrap().len()
+    }
+
+    /// 最近一次调用的 prompt
+    pub fn last_prompt(&self) -> Option<String> {
+        self.prompts.lock().unwrap().last().cloned()
+    }
+
+    /// 全部调用的 prompt（按调用顺序）
+    pub fn prompts(&self) -> Vec<String> {
+        self.prompts.lock().unwrap().clone()
+    }
+
+    /// 记录调用并取出下一个预设响应
+    fn next_scripted(&self, prompt: &str) -> Option<MockResponse> {
+        self.prompts.lock().unwrap().push(prompt.to_string());
+
+        let mut scripted = self.scripted.lock().unwrap();
+        if scripted.len() > 1 {
+            scripted.pop_front()
+        } else {
+            scripted.front().map(MockResponse::replay)
+        }
+    }
+
+    /// 按预设序列响应 `crate::ai::AiProvider` 调用
+    async fn respond(&self, prompt: &str) -> ai::Result<String> {
+        self.simulate_latency().await;
+
+        if let Some(msg) = self.should_fail.lock().unwrap().take() {
+            return Err(AiError::CliError(format!("Mock chat failed: {}", msg)));
+        }
+
+        match self.next_scripted(prompt) {
+            Some(MockResponse::Return(text)) => Ok(text),
+            Some(MockResponse::Fail(error)) => Err(error),
+            Some(MockResponse::Delay(delay, text)) => {
+                tokio::time::sleep(delay).await;
+                Ok(text)
+            }
+            None => Ok(self.default_response.lock().unwrap().clone()),
         }
     }
 
@@ -126,6 +228,7 @@ impl MockAiProvider {
     /// 清空调用记录
     pub fn clear(&self) {
         *self.should_fail.lock().unwrap() = None;
+        self.prompts.lock().unwrap().clear();
     }
 }
 
@@ -145,6 +248,29 @@ impl AiProvider for MockAiProvider {
             return Err(CisError::ai(format!("Mock completion failed: {}", msg)));
         }
 
+        // 预设响应序列优先
+        if !self.scripted.lock().unwrap().is_empty() {
+            let text = match self.next_scripted(&request.prompt) {
+                Some(MockResponse::Return(text)) => text,
+                Some(MockResponse::Delay(delay, text)) => {
+                    tokio::time::sleep(delay).await;
+                    text
+                }
+                Some(MockResponse::Fail(error)) => {
+                    return Err(CisError::ai(format!("Mock completion failed: {}", error)));
+                }
+                None => self.default_response.lock().unwrap().clone(),
+            };
+            return Ok(CompletionResponse {
+                text,
+                usage: None,
+                model: self.name.lock().unwrap().clone(),
+                finish_reason: Some("stop".to_string()),
+                id: format!("mock-completion-{}", uuid::Uuid::new_v4()),
+                created_at: current_timestamp(),
+            });
+        }
+
         // 检查是否有预设响应
         let responses = self.completion_responses.read().await;
         
@@ -232,6 +358,36 @@ impl AiProvider for MockAiProvider {
     }
 }
 
+#[async_trait]
+impl ai::AiProvider for MockAiProvider {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    async fn available(&self) -> bool {
+        *self.available.lock().unwrap()
+    }
+
+    async fn chat(&self, prompt: &str) -> ai::Result<String> {
+        self.respond(prompt).await
+    }
+
+    async fn chat_with_context(&self, _system: &str, messages: &[ai::Message]) -> ai::Result<String> {
+        let prompt = messages.last().map(|m| m.content.as_str()).unwrap_or_default();
+        self.respond(prompt).await
+    }
+
+    async fn chat_with_rag(&self, prompt: &str, _ctx: Option<&ConversationContext>) -> ai::Result<String> {
+        self.respond(prompt).await
+    }
+
+    async fn generate_json(&self, prompt: &str, _schema: &str) -> ai::Result<serde_json::Value> {
+        let response = self.respond(prompt).await?;
+        serde_json::from_str(&response)
+            .map_err(|e| AiError::InvalidResponse(format!("JSON parse error: {}", e)))
+    }
+}
+
 fn current_timestamp() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -326,6 +482,38 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_mock_response_sequence() {
+        use crate::ai::AiProvider as ChatProvider;
+
+        let mock = MockAiProvider::with_responses(vec![
+            MockResponse::Return("first".to_string()),
+            MockResponse::Fail(AiError::NotAvailable("down".to_string())),
+            MockResponse::Delay(std::time::Duration::from_millis(20), "slow".to_string()),
+        ]);
+        assert_eq!(mock.call_count(), 0);
+        assert_eq!(mock.last_prompt(), None);
+
+        assert_eq!(mock.chat("a").await.unwrap(), "first");
+        assert!(matches!(mock.chat("b").await, Err(AiError::NotAvailable(_))));
+
+        let start = std::time::Instant::now();
+        assert_eq!(mock.chat("c").await.unwrap(), "slow");
+        assert!(start.elapsed() >= std::time::Duration::from_millis(20));
+
+        // 用完后重复最后一个
+        assert_eq!(mock.chat("d").await.unwrap(), "slow");
+        assert_eq!(mock.call_count(), 4);
+        assert_eq!(mock.last_prompt().as_deref(), Some("d"));
+
+        let failing = MockAiProvider::with_responses(vec![MockResponse::Fail(AiError::Http("500".to_string()))]);
+        assert!(matches!(failing.chat("x").await, Err(AiError::Http(_))));
+        assert!(matches!(failing.chat("y").await, Err(AiError::Http(_))));
+
+        // 未预设序列时返回默认响应
+        assert_eq!(MockAiProvider::new().chat("z").await.unwrap(), "Mock AI Response");
+    }
+
     #[tokio::test]
     async fn test_mock_latency() {
         let mock = MockAiProvider::with_latency(100);
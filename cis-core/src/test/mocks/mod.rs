@@ -54,7 +54,7 @@ pub mod skill_executor;
 pub use network_service::MockNetworkService;
 pub use storage_service::MockStorageService;
 pub use event_bus::MockEventBus;
-pub use ai_provider::MockAiProvider;
+pub use ai_provider::{MockAiProvider, MockResponse};
 pub use embedding_service::MockEmbeddingService;
 pub use skill_executor::MockSkillExecutor;
 
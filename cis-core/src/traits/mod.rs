@@ -82,6 +82,7 @@ pub use skill_executor::{
     SkillExecutor, 
     SkillExecutorRef, 
     ExecutionContext, 
+    Environment, 
     ExecutionInfo, 
     ExecutionStatus, 
     ExecutionResult, 
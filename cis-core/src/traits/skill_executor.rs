@@ -34,6 +34,23 @@ use crate::error::Result;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+/// 运行环境
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Environment {
+    /// 宿主机
+    #[default]
+    Host,
+    /// 容器内（如 Docker），没有 GUI，文件系统访问受限
+    Container,
+}
+
+impl Environment {
+    /// 是否可以运行依赖 GUI 的 Skill
+    pub fn supports_gui(&self) -> bool {
+        matches!(self, Environment::Host)
+    }
+}
+
 /// 执行上下文
 #[derive(Debug, Clone)]
 pub struct ExecutionContext {
@@ -55,6 +72,8 @@ pub struct ExecutionContext {
     pub user: Option<String>,
     /// 触发方式
     pub trigger: String,
+    /// 运行环境
+    pub environment: Environment,
 }
 
 impl ExecutionContext {
@@ -87,6 +106,7 @@ impl ExecutionContext {
             timeout_secs: 3600, // 默认 1 小时
             user: None,
             trigger: "manual".to_string(),
+            environment: Environment::Host,
         }
     }
 
@@ -123,6 +143,12 @@ impl ExecutionContext {
         self.trigger = trigger.into();
         self
     }
+
+    /// 设置运行环境
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        self.environment = environment;
+        self
+    }
 }
 
 /// Skill 执行配置
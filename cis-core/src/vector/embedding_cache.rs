@@ -0,0 +1,307 @@
+//! # Embedding 缓存层
+//!
+//! 为任意 [`EmbeddingService`] 包装一层 LRU 缓存，避免对相同文本重复调用嵌入模型/API。
+//!
+//! - 缓存键为文本的 SHA-256 哈希，值为嵌入向量
+//! - 容量满时淘汰最久未使用的条目
+//! - 条目超过 TTL 后在下一次访问时惰性失效
+//! - 命中/未命中/淘汰计数同时累加到进程级统计，供 `cis telemetry stats` 展示
+
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+
+use crate::ai::embedding::EmbeddingService;
+use crate::error::Result;
+
+/// 默认缓存容量（条目数）
+pub const DEFAULT_EMBEDDING_CACHE_CAPACITY: usize = 1024;
+
+/// 默认缓存 TTL（秒）
+pub const DEFAULT_EMBEDDING_CACHE_TTL_SECS: u64 = 3600;
+
+/// 缓存统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// 命中次数
+    pub hits: u64,
+    /// 未命中次数
+    pub misses: u64,
+    /// 淘汰次数（容量淘汰 + TTL 过期）
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    /// 命中率 (0.0 - 1.0)
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+struct StatCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl StatCounters {
+    const fn new() -> Self {
+        Self {
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    fn snapshot(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// 进程内所有 `CachedEmbeddingService` 的汇总统计
+static GLOBAL_STATS: StatCounters = StatCounters::new();
+
+/// 获取进程级 embedding 缓存统计
+pub fn embedding_cache_stats() -> CacheStats {
+    GLOBAL_STATS.snapshot()
+}
+
+type TextHash = [u8; 32];
+
+struct CacheEntry {
+    vector: Vec<f32>,
+    inserted_at: Instant,
+}
+
+/// 带 LRU 缓存的嵌入服务
+pub struct CachedEmbeddingService {
+    inner: Arc<dyn EmbeddingService>,
+    cache: Mutex<LruCache<TextHash, CacheEntry>>,
+    ttl: Duration,
+    stats: StatCounters,
+}
+
+impl CachedEmbeddingService {
+    /// 创建缓存层
+    ///
+    /// # 参数
+    /// - `inner`: 被包装的嵌入服务
+    /// - `capacity`: 最大缓存条目数（0 按 1 处理）
+    /// - `ttl_secs`: 条目有效期（秒）
+    pub fn new(inner: Arc<dyn EmbeddingService>, capacity: usize, ttl_secs: u64) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            inner,
+            cache: Mutex::new(LruCache::new(capacity)),
+            ttl: Duration::from_secs(ttl_secs),
+            stats: StatCounters::new(),
+        }
+    }
+
+    /// 使用默认容量和 TTL 创建缓存层
+    pub fn with_defaults(inner: Arc<dyn EmbeddingService>) -> Self {
+        Self::new(
+            inner,
+            DEFAULT_EMBEDDING_CACHE_CAPACITY,
+            DEFAULT_EMBEDDING_CACHE_TTL_SECS,
+        )
+    }
+
+    /// 当前实例的缓存统计
+    pub fn cache_stats(&self) -> CacheStats {
+        self.stats.snapshot()
+    }
+
+    /// 当前缓存条目数
+    pub fn len(&self) -> usize {
+        self.cache.lock().map(|c| c.len()).unwrap_or(0)
+    }
+
+    /// 缓存是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 清空缓存（不重置统计）
+    pub fn clear(&self) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.clear();
+        }
+    }
+
+    fn hash(text: &str) -> TextHash {
+        Sha256::digest(text.as_bytes()).into()
+    }
+
+    fn record(&self, counter: fn(&StatCounters) -> &AtomicU64, n: u64) {
+        counter(&self.stats).fetch_add(n, Ordering::Relaxed);
+        counter(&GLOBAL_STATS).fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// 查询缓存，过期条目在此惰性移除
+    fn lookup(&self, hash: &TextHash) -> Option<Vec<f32>> {
+        let mut cache = self.cache.lock().ok()?;
+        let expired = match cache.get(hash) {
+            Some(entry) if entry.inserted_at.elapsed() < self.ttl => {
+                return Some(entry.vector.clone());
+            }
+            Some(_) => true,
+            None => false,
+        };
+
+        if expired {
+            cache.pop(hash);
+            drop(cache);
+            self.record(|s| &s.evictions, 1);
+        }
+        None
+    }
+
+    fn insert(&self, hash: TextHash, vector: Vec<f32>) {
+        let Ok(mut cache) = self.cache.lock() else {
+            return;
+        };
+        let entry = CacheEntry {
+            vector,
+            inserted_at: Instant::now(),
+        };
+        // push 在容量满时返回被淘汰的条目；键相同时返回旧值（不算淘汰）
+        let evicted = matches!(cache.push(hash, entry), Some((old, _)) if old != hash);
+        drop(cache);
+        if evicted {
+            self.record(|s| &s.evictions, 1);
+        }
+    }
+}
+
+#[async_trait]
+impl EmbeddingService for CachedEmbeddingService {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let hash = Self::hash(text);
+        if let Some(vector) = self.lookup(&hash) {
+            self.record(|s| &s.hits, 1);
+            tracing::debug!("Embedding cache hit ({} chars)", text.len());
+            return Ok(vector);
+        }
+
+        self.record(|s| &s.misses, 1);
+        let vector = self.inner.embed(text).await?;
+        self.insert(hash, vector.clone());
+        Ok(vector)
+    }
+
+    async fn batch_embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let hashes: Vec<TextHash> = texts.iter().map(|t| Self::hash(t)).collect();
+        let mut results: Vec<Option<Vec<f32>>> = hashes.iter().map(|h| self.lookup(h)).collect();
+
+        let missing: Vec<usize> = (0..texts.len()).filter(|&i| results[i].is_none()).collect();
+        let hits = (texts.len() - missing.len()) as u64;
+        if hits > 0 {
+            self.record(|s| &s.hits, hits);
+            tracing::debug!("Embedding cache hit for {}/{} texts", hits, texts.len());
+        }
+
+        if !missing.is_empty() {
+            self.record(|s| &s.misses, missing.len() as u64);
+            let batch: Vec<&str> = missing.iter().map(|&i| texts[i]).collect();
+            let vectors = self.inner.batch_embed(&batch).await?;
+            for (&i, vector) in missing.iter().zip(vectors) {
+                self.insert(hashes[i], vector.clone());
+                results[i] = Some(vector);
+            }
+        }
+
+        Ok(results.into_iter().map(Option::unwrap_or_default).collect())
+    }
+
+    fn dimension(&self) -> usize {
+        self.inner.dimension()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    /// 统计底层调用次数的嵌入服务
+    #[derive(Default)]
+    struct CountingEmbedding {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EmbeddingService for CountingEmbedding {
+        async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![text.len() as f32; 4])
+        }
+
+        async fn batch_embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+            let mut results = Vec::with_capacity(texts.len());
+            for text in texts {
+                results.push(self.embed(text).await?);
+            }
+            Ok(results)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_hits_and_evictions() {
+        let inner = Arc::new(CountingEmbedding::default());
+        let cached = CachedEmbeddingService::new(inner.clone(), 2, 60);
+
+        assert_eq!(cached.embed("a").await.unwrap(), vec![1.0; 4]);
+        cached.embed("a").await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+
+        cached.embed("bb").await.unwrap();
+        cached.embed("ccc").await.unwrap(); // 淘汰 "a"
+        cached.embed("a").await.unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 4);
+        assert_eq!(
+            cached.cache_stats(),
+            CacheStats {
+                hits: 1,
+                misses: 4,
+                evictions: 2,
+            }
+        );
+        assert_eq!(cached.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_ttl_expiry_and_batch() {
+        let inner = Arc::new(CountingEmbedding::default());
+        let cached = CachedEmbeddingService::new(inner.clone(), 8, 0);
+
+        cached.embed("x").await.unwrap();
+        cached.embed("x").await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(cached.cache_stats().evictions, 1);
+
+        let cached = CachedEmbeddingService::new(inner.clone(), 8, 60);
+        cached.embed("a").await.unwrap();
+        let vectors = cached.batch_embed(&["a", "bb", "a"]).await.unwrap();
+        assert_eq!(vectors, vec![vec![1.0; 4], vec![2.0; 4], vec![1.0; 4]]);
+
+        let stats = cached.cache_stats();
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.misses, 2);
+    }
+}
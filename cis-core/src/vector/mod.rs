@@ -11,6 +11,8 @@
 //! - `merger::ResultMerger`: Search result merger
 //! - `adaptive_threshold::AdaptiveThreshold`: Adaptive threshold adjuster
 //! - `embedding`: Text vectorization service (see `crate::ai::embedding`)
+//! - `embedding_cache::CachedEmbeddingService`: LRU cache in front of any embedding service
+//! - `reindex`: Resumable re-embedding after an embedding model change
 //!
 //! ## 使用示例
 //!
@@ -36,6 +38,9 @@
 
 pub mod storage;
 pub mod batch;
+pub mod embedding_cache;
+pub mod reindex;
+pub mod skill_description;
 
 // v1.1.6 性能优化模块
 pub mod batch_loader;
@@ -49,6 +54,9 @@ pub use storage::{
     DEFAULT_SIMILARITY_THRESHOLD, EMBEDDING_DIM,
 };
 pub use batch::{BatchProcessor, BatchStats};
+pub use embedding_cache::{embedding_cache_stats, CacheStats, CachedEmbeddingService};
+pub use reindex::ReindexProgress;
+pub use skill_description::{description_fingerprint, invalidate_skill_description_at};
 
 // v1.1.6 新增导出
 pub use batch_loader::{BatchVectorLoader, VectorBatch, VectorData};
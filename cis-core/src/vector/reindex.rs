@@ -0,0 +1,417 @@
+//! # 增量重建索引
+//!
+//! 更换嵌入模型后，已有向量与新模型不兼容，需要用新模型重新嵌入原始文本。
+//!
+//! - 原始文本保存在 `memory_embedding_meta.source_text`（由 `index_memory` 写入）
+//! - 按 `memory_id` 顺序分批处理，每批结束后持久化游标，进程重启后可继续
+//! - 每次调用 [`VectorStorage::incremental_reindex`] 处理一批并返回 [`ReindexProgress`]
+//! - 若已创建 HNSW 索引表，同步替换其中的向量
+
+use std::time::Instant;
+
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::ai::embedding::EmbeddingService;
+use crate::error::{CisError, Result};
+
+use super::storage::{vec_to_json, VectorStorage};
+
+/// 重建索引进度
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReindexProgress {
+    /// 开始时的向量总数
+    pub total: usize,
+    /// 已成功重新嵌入的数量
+    pub processed: usize,
+    /// 失败数量（缺少原始文本或嵌入失败）
+    pub failed: usize,
+    /// 预计剩余时间（秒）
+    pub eta_secs: u64,
+    /// 是否已全部完成
+    pub done: bool,
+}
+
+impl ReindexProgress {
+    /// 已处理（含失败）的数量
+    pub fn handled(&self) -> usize {
+        self.processed + self.failed
+    }
+
+    /// 完成百分比 (0.0 - 100.0)
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            (self.handled() as f64 / self.total as f64 * 100.0).min(100.0)
+        }
+    }
+}
+
+/// 持久化的重建游标
+struct ReindexState {
+    last_id: String,
+    total: usize,
+    processed: usize,
+    failed: usize,
+    elapsed_ms: u64,
+}
+
+/// 写入（或覆盖）记忆向量对应的原始文本
+pub(super) fn store_source_text(conn: &Connection, memory_id: &str, text: &str) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO memory_embedding_meta (memory_id, source_text, updated_at)
+         VALUES (?1, ?2, ?3)",
+        rusqlite::params![memory_id, text, chrono::Utc::now().timestamp()],
+    )
+    .map_err(|e| CisError::storage(format!("Failed to store source text: {}", e)))?;
+    Ok(())
+}
+
+impl VectorStorage {
+    /// 创建元数据表和重建游标表
+    pub(super) fn create_reindex_tables(&self) -> Result<()> {
+        self.conn()
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS memory_embedding_meta (
+                    memory_id TEXT PRIMARY KEY,
+                    source_text TEXT NOT NULL,
+                    updated_at INTEGER NOT NULL
+                );
+                CREATE TABLE IF NOT EXISTS reindex_state (
+                    target TEXT PRIMARY KEY,
+                    last_id TEXT NOT NULL,
+                    total INTEGER NOT NULL,
+                    processed INTEGER NOT NULL,
+                    failed INTEGER NOT NULL,
+                    elapsed_ms INTEGER NOT NULL
+                );",
+            )
+            .map_err(|e| CisError::storage(format!("Failed to create reindex tables: {}", e)))
+    }
+
+    /// 用新的嵌入服务重建下一批记忆向量
+    ///
+    /// 每次调用处理 `batch_size` 条记录并保存游标；返回的进度中 `done` 为 true 时表示
+    /// 全部完成，游标随之清除。中途退出后再次调用会从上次的位置继续。
+    ///
+    /// # 参数
+    /// - `new_provider`: 新的嵌入服务，维度必须与存储一致
+    /// - `batch_size`: 每批数量（至少为 1）
+    pub async fn incremental_reindex(
+        &self,
+        new_provider: &dyn EmbeddingService,
+        batch_size: usize,
+    ) -> Result<ReindexProgress> {
+        if new_provider.dimension() != self.config().dimension {
+            return Err(CisError::vector(format!(
+                "Embedding dimension mismatch: store uses {}, new provider produces {}",
+                self.config().dimension,
+                new_provider.dimension()
+            )));
+        }
+
+        let started = Instant::now();
+        let mut state = match self.load_reindex_state()? {
+            Some(state) => state,
+            None => ReindexState {
+                last_id: String::new(),
+                total: self.count_memory_vectors()?,
+                processed: 0,
+                failed: 0,
+                elapsed_ms: 0,
+            },
+        };
+
+        let batch = self.next_reindex_batch(&state.last_id, batch_size.max(1))?;
+        let done = batch.len() < batch_size.max(1);
+
+        // 有原始文本的行批量嵌入，缺失的直接计为失败
+        let (with_text, without_text): (Vec<_>, Vec<_>) =
+            batch.iter().partition(|(_, _, _, text)| text.is_some());
+        for (memory_id, key, _, _) in &without_text {
+            tracing::warn!("No source text for memory vector {} ({}), skipping", memory_id, key);
+        }
+        state.failed += without_text.len();
+
+        if !with_text.is_empty() {
+            let texts: Vec<&str> = with_text
+                .iter()
+                .filter_map(|(_, _, _, text)| text.as_deref())
+                .collect();
+
+            match new_provider.batch_embed(&texts).await {
+                Ok(vectors) => {
+                    let rows: Vec<_> = with_text
+                        .iter()
+                        .map(|(id, key, category, _)| (id.as_str(), key.as_str(), category.as_str()))
+                        .zip(vectors.iter().map(|v| v.as_slice()))
+                        .collect();
+                    state.processed += self.replace_memory_vectors(&rows)?;
+                }
+                Err(e) => {
+                    tracing::warn!("Re-embedding batch failed: {}", e);
+                    state.failed += with_text.len();
+                }
+            }
+        }
+
+        if let Some((last_id, _, _, _)) = batch.last() {
+            state.last_id = last_id.clone();
+        }
+        state.elapsed_ms += started.elapsed().as_millis() as u64;
+
+        let handled = state.processed + state.failed;
+        let remaining = state.total.saturating_sub(handled);
+        let eta_secs = if done || handled == 0 {
+            0
+        } else {
+            state.elapsed_ms * remaining as u64 / handled as u64 / 1000
+        };
+
+        if done {
+            self.reset_reindex()?;
+            tracing::info!(
+                "Reindex complete: {} re-embedded, {} failed",
+                state.processed,
+                state.failed
+            );
+        } else {
+            self.save_reindex_state(&state)?;
+        }
+
+        Ok(ReindexProgress {
+            total: state.total.max(handled),
+            processed: state.processed,
+            failed: state.failed,
+            eta_secs,
+            done,
+        })
+    }
+
+    /// 获取未完成的重建进度（没有进行中的重建则返回 None）
+    pub fn reindex_progress(&self) -> Result<Option<ReindexProgress>> {
+        Ok(self.load_reindex_state()?.map(|state| ReindexProgress {
+            total: state.total,
+            processed: state.processed,
+            failed: state.failed,
+            eta_secs: 0,
+            done: false,
+        }))
+    }
+
+    /// 丢弃重建游标，下次从头开始
+    pub fn reset_reindex(&self) -> Result<()> {
+        self.conn()
+            .execute("DELETE FROM reindex_state WHERE target = 'memory'", [])
+            .map_err(|e| CisError::storage(format!("Failed to reset reindex state: {}", e)))?;
+        Ok(())
+    }
+
+    fn count_memory_vectors(&self) -> Result<usize> {
+        self.conn()
+            .query_row("SELECT COUNT(*) FROM memory_embeddings", [], |row| row.get::<_, i64>(0))
+            .map(|n| n as usize)
+            .map_err(|e| CisError::storage(format!("Failed to count memory vectors: {}", e)))
+    }
+
+    /// 读取游标之后的一批 (memory_id, key, category, source_text)
+    #[allow(clippy::type_complexity)]
+    fn next_reindex_batch(
+        &self,
+        after: &str,
+        limit: usize,
+    ) -> Result<Vec<(String, String, String, Option<String>)>> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(
+                "SELECT e.memory_id, e.key, e.category, m.source_text
+                 FROM memory_embeddings e
+                 LEFT JOIN memory_embedding_meta m ON m.memory_id = e.memory_id
+                 WHERE e.memory_id > ?1
+                 ORDER BY e.memory_id
+                 LIMIT ?2",
+            )
+            .map_err(|e| CisError::storage(format!("Failed to prepare reindex query: {}", e)))?;
+
+        let rows = stmt
+            .query_map(rusqlite::params![after, limit as i64], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            })
+            .map_err(|e| CisError::storage(format!("Failed to query reindex batch: {}", e)))?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(|e| CisError::storage(format!("Failed to read reindex batch: {}", e)))
+    }
+
+    /// 在一个事务内替换向量（含 HNSW 索引表），返回替换数量
+    fn replace_memory_vectors(&self, rows: &[((&str, &str, &str), &[f32])]) -> Result<usize> {
+        let conn = self.conn();
+        let has_hnsw = conn
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE name = 'memory_hnsw'",
+                [],
+                |_| Ok(()),
+            )
+            .optional()
+            .map_err(|e| CisError::storage(format!("Failed to check HNSW index: {}", e)))?
+            .is_some();
+
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|e| CisError::storage(format!("Failed to start transaction: {}", e)))?;
+
+        for ((memory_id, key, category), vector) in rows {
+            let vec_json = vec_to_json(vector);
+            tx.execute(
+                "INSERT OR REPLACE INTO memory_embeddings (memory_id, embedding, key, category)
+                 VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![memory_id, &vec_json, key, category],
+            )
+            .map_err(|e| CisError::storage(format!("Failed to replace memory vector: {}", e)))?;
+
+            if has_hnsw {
+                tx.execute(
+                    "INSERT OR REPLACE INTO memory_hnsw (memory_id, embedding, key, category)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    rusqlite::params![memory_id, &vec_json, key, category],
+                )
+                .map_err(|e| CisError::storage(format!("Failed to replace HNSW vector: {}", e)))?;
+            }
+        }
+
+        tx.commit()
+            .map_err(|e| CisError::storage(format!("Failed to commit transaction: {}", e)))?;
+        Ok(rows.len())
+    }
+
+    fn load_reindex_state(&self) -> Result<Option<ReindexState>> {
+        self.conn()
+            .query_row(
+                "SELECT last_id, total, processed, failed, elapsed_ms
+                 FROM reindex_state WHERE target = 'memory'",
+                [],
+                |row| {
+                    Ok(ReindexState {
+                        last_id: row.get(0)?,
+                        total: row.get::<_, i64>(1)? as usize,
+                        processed: row.get::<_, i64>(2)? as usize,
+                        failed: row.get::<_, i64>(3)? as usize,
+                        elapsed_ms: row.get::<_, i64>(4)? as u64,
+                    })
+                },
+            )
+            .optional()
+            .map_err(|e| CisError::storage(format!("Failed to load reindex state: {}", e)))
+    }
+
+    fn save_reindex_state(&self, state: &ReindexState) -> Result<()> {
+        self.conn()
+            .execute(
+                "INSERT OR REPLACE INTO reindex_state
+                 (target, last_id, total, processed, failed, elapsed_ms)
+                 VALUES ('memory', ?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![
+                    state.last_id,
+                    state.total as i64,
+                    state.processed as i64,
+                    state.failed as i64,
+                    state.elapsed_ms as i64
+                ],
+            )
+            .map_err(|e| CisError::storage(format!("Failed to save reindex state: {}", e)))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::embedding::DEFAULT_EMBEDDING_DIM;
+    use crate::vector::storage::{deserialize_f32_vec, vec_from_json};
+    use async_trait::async_trait;
+    use std::sync::Arc;
+
+    /// 所有文本都返回同一个固定向量
+    struct ConstEmbedding(f32);
+
+    #[async_trait]
+    impl EmbeddingService for ConstEmbedding {
+        async fn embed(&self, _text: &str) -> Result<Vec<f32>> {
+            let mut vec = vec![0.0f32; DEFAULT_EMBEDDING_DIM];
+            vec[0] = self.0;
+            Ok(vec)
+        }
+
+        async fn batch_embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+            let mut results = Vec::with_capacity(texts.len());
+            for text in texts {
+                results.push(self.embed(text).await?);
+            }
+            Ok(results)
+        }
+    }
+
+    fn stored_vector(storage: &VectorStorage, memory_id: &str) -> Vec<f32> {
+        let raw: Vec<u8> = storage
+            .conn()
+            .query_row(
+                "SELECT CAST(embedding AS BLOB) FROM memory_embeddings WHERE memory_id = ?1",
+                [memory_id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        // sqlite-vec 存储为 f32 小端字节，备用表存储为 JSON 文本
+        if raw.first() == Some(&b'[') {
+            vec_from_json(&String::from_utf8_lossy(&raw))
+        } else {
+            deserialize_f32_vec(&raw)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_incremental_reindex_resumes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vector.db");
+
+        let ids = {
+            let storage = VectorStorage::open_with_service(&path, Arc::new(ConstEmbedding(1.0))).unwrap();
+            let mut ids = Vec::new();
+            for i in 0..5 {
+                let value = format!("memory {}", i);
+                ids.push(storage.index_memory(&format!("k{}", i), value.as_bytes(), None).await.unwrap());
+            }
+            ids
+        };
+
+        let new_model = ConstEmbedding(2.0);
+
+        // 处理一批后“重启”
+        {
+            let storage = VectorStorage::open_with_service(&path, Arc::new(ConstEmbedding(1.0))).unwrap();
+            let progress = storage.incremental_reindex(&new_model, 2).await.unwrap();
+            assert_eq!(progress.total, 5);
+            assert_eq!(progress.processed, 2);
+            assert!(!progress.done);
+        }
+
+        let storage = VectorStorage::open_with_service(&path, Arc::new(ConstEmbedding(1.0))).unwrap();
+        assert_eq!(storage.reindex_progress().unwrap().unwrap().processed, 2);
+
+        let mut progress = storage.incremental_reindex(&new_model, 2).await.unwrap();
+        while !progress.done {
+            progress = storage.incremental_reindex(&new_model, 2).await.unwrap();
+        }
+
+        assert_eq!(progress.processed, 5);
+        assert_eq!(progress.failed, 0);
+        assert!(storage.reindex_progress().unwrap().is_none());
+        for id in &ids {
+            assert_eq!(stored_vector(&storage, id)[0], 2.0);
+        }
+    }
+}
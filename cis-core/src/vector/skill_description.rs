@@ -0,0 +1,117 @@
+//! # Skill 描述向量缓存
+//!
+//! `cis skill do` 按语义匹配 Skill 时需要每个 Skill 描述的嵌入向量。
+//! 向量持久化在 `skill_description_vec` 表中，避免每次调用都重新嵌入全部描述：
+//!
+//! - 以描述文本的 SHA-256 作为指纹，描述变更后自动失效
+//! - 向量维度与当前存储不一致（更换了嵌入模型）时视为失效
+//! - Skill 加载/卸载时由 `SkillManager` 调用 [`invalidate_skill_description_at`] 主动失效
+
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+
+use crate::error::{CisError, Result};
+
+use super::storage::{vec_from_json, vec_to_json, VectorStorage};
+
+const CREATE_SKILL_DESCRIPTION_TABLE: &str = "CREATE TABLE IF NOT EXISTS skill_description_vec (
+    skill_name TEXT PRIMARY KEY,
+    fingerprint TEXT NOT NULL,
+    embedding TEXT NOT NULL,
+    updated_at INTEGER NOT NULL
+)";
+
+/// 计算描述文本指纹
+pub fn description_fingerprint(description: &str) -> String {
+    hex::encode(Sha256::digest(description.as_bytes()))
+}
+
+/// 删除指定 Skill 的缓存向量（直接操作数据库文件，无需初始化嵌入服务）
+///
+/// 数据库或表不存在时返回 `Ok(false)`。
+pub fn invalidate_skill_description_at(db_path: &Path, skill_name: &str) -> Result<bool> {
+    if !db_path.exists() {
+        return Ok(false);
+    }
+
+    let conn = Connection::open(db_path)
+        .map_err(|e| CisError::storage(format!("Failed to open vector db: {}", e)))?;
+    let has_table: bool = conn
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'skill_description_vec'",
+            [],
+            |_| Ok(true),
+        )
+        .optional()
+        .map_err(|e| CisError::storage(format!("Failed to inspect vector db: {}", e)))?
+        .unwrap_or(false);
+    if !has_table {
+        return Ok(false);
+    }
+
+    delete_skill_description(&conn, skill_name)
+}
+
+fn delete_skill_description(conn: &Connection, skill_name: &str) -> Result<bool> {
+    let rows = conn
+        .execute(
+            "DELETE FROM skill_description_vec WHERE skill_name = ?1",
+            [skill_name],
+        )
+        .map_err(|e| CisError::storage(format!("Failed to invalidate skill description: {}", e)))?;
+    Ok(rows > 0)
+}
+
+impl VectorStorage {
+    /// 创建 Skill 描述向量表
+    pub(super) fn create_skill_description_table(&self) -> Result<()> {
+        self.conn()
+            .execute(CREATE_SKILL_DESCRIPTION_TABLE, [])
+            .map_err(|e| CisError::storage(format!("Failed to create skill_description_vec table: {}", e)))?;
+        Ok(())
+    }
+
+    /// 读取缓存的描述向量
+    ///
+    /// 指纹不一致或维度与当前存储不符时返回 `None`。
+    pub fn cached_skill_description(&self, skill_name: &str, fingerprint: &str) -> Result<Option<Vec<f32>>> {
+        let row: Option<(String, String)> = self
+            .conn()
+            .query_row(
+                "SELECT fingerprint, embedding FROM skill_description_vec WHERE skill_name = ?1",
+                [skill_name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|e| CisError::storage(format!("Failed to read skill description: {}", e)))?;
+
+        Ok(row.and_then(|(stored, json)| {
+            let embedding = vec_from_json(&json);
+            (stored == fingerprint && embedding.len() == self.config().dimension).then_some(embedding)
+        }))
+    }
+
+    /// 保存描述向量
+    pub fn store_skill_description(&self, skill_name: &str, fingerprint: &str, embedding: &[f32]) -> Result<()> {
+        self.conn()
+            .execute(
+                "INSERT OR REPLACE INTO skill_description_vec (skill_name, fingerprint, embedding, updated_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![
+                    skill_name,
+                    fingerprint,
+                    vec_to_json(embedding),
+                    chrono::Utc::now().timestamp()
+                ],
+            )
+            .map_err(|e| CisError::storage(format!("Failed to store skill description: {}", e)))?;
+        Ok(())
+    }
+
+    /// 删除指定 Skill 的描述向量
+    pub fn invalidate_skill_description(&self, skill_name: &str) -> Result<bool> {
+        delete_skill_description(&self.conn(), skill_name)
+    }
+}
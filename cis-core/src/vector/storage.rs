@@ -36,6 +36,8 @@ use r2d2::{Pool, PooledConnection};
 use r2d2_sqlite::SqliteConnectionManager;
 
 use crate::ai::embedding::{create_embedding_service_sync, EmbeddingConfig, EmbeddingService, cosine_similarity};
+use crate::vector::embedding_cache::CachedEmbeddingService;
+use crate::vector::reindex::store_source_text;
 use crate::error::{CisError, Result};
 use crate::memory::MemoryEntryExt;
 // use crate::types::{MemoryCategory, MemoryDomain};
@@ -367,7 +369,10 @@ impl VectorStorage {
         // 配置 WAL 模式
         Self::configure_wal(&conn)?;
 
-        let embedding = create_embedding_service_sync(embedding_config)?;
+        // 查询文本经常重复（如反复执行的 VectorSearch），包装一层 LRU 缓存
+        let embedding: Arc<dyn EmbeddingService> = Arc::new(CachedEmbeddingService::with_defaults(
+            create_embedding_service_sync(embedding_config)?,
+        ));
         let config = VectorConfig::default();
 
         let storage = Self {
@@ -470,6 +475,12 @@ impl VectorStorage {
         // 创建辅助索引
         self.create_indexes()?;
 
+        // 原始文本与重建游标（用于更换模型后的增量重建）
+        self.create_reindex_tables()?;
+
+        // Skill 描述向量缓存（用于 `cis skill do` 语义匹配）
+        self.create_skill_description_table()?;
+
         Ok(())
     }
 
@@ -765,6 +776,8 @@ impl VectorStorage {
             rusqlite::params![&memory_id, &vec_json, key, category],
         ).map_err(|e| CisError::storage(format!("Failed to index memory: {}", e)))?;
 
+        store_source_text(&self.conn(), &memory_id, &text)?;
+
         Ok(memory_id)
     }
 
@@ -795,7 +808,7 @@ impl VectorStorage {
 
         let mut ids = Vec::with_capacity(items.len());
 
-        for ((key, _value, category), vec) in items.into_iter().zip(embeddings.into_iter()) {
+        for (((key, _value, category), vec), text) in items.into_iter().zip(embeddings.into_iter()).zip(texts.iter()) {
             let memory_id = uuid::Uuid::new_v4().to_string();
             let vec_json = vec_to_json(&vec);
 
@@ -807,6 +820,7 @@ impl VectorStorage {
                  VALUES (?1, ?2, ?3, ?4)",
                 rusqlite::params![&memory_id, &vec_json, key, category],
             ).map_err(|e| CisError::storage(format!("Failed to index memory: {}", e)))?;
+            store_source_text(&tx, &memory_id, text)?;
 
             ids.push(memory_id);
         }
@@ -1876,7 +1890,7 @@ fn serialize_f32_vec(vec: &[f32]) -> Vec<u8> {
 }
 
 /// 反序列化字节为 f32 向量
-fn deserialize_f32_vec(bytes: &[u8]) -> Vec<f32> {
+pub(super) fn deserialize_f32_vec(bytes: &[u8]) -> Vec<f32> {
     bytes
         .chunks_exact(4)
         .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
@@ -1884,13 +1898,13 @@ fn deserialize_f32_vec(bytes: &[u8]) -> Vec<f32> {
 }
 
 /// 将向量序列化为 JSON 字符串（用于 sqlite-vec MATCH 查询）
-fn vec_to_json(vec: &[f32]) -> String {
+pub(super) fn vec_to_json(vec: &[f32]) -> String {
     let values: Vec<String> = vec.iter().map(|f| f.to_string()).collect();
     format!("[{}]", values.join(","))
 }
 
 /// 从 JSON 字符串解析向量
-fn vec_from_json(json: &str) -> Vec<f32> {
+pub(super) fn vec_from_json(json: &str) -> Vec<f32> {
     json.trim_start_matches('[')
         .trim_end_matches(']')
         .split(',')
@@ -12,7 +12,7 @@ mod tests {
     use crate::memory::{MemoryServiceTrait, MemorySearchItem};
     use crate::skill::Skill;
     use crate::wasm::{WasmSkillConfig, WasmSkillExecutor, WasmRuntime, WasmSkill};
-    use async_trait::async_trait;
+    use crate::test::mocks::{MockAiProvider, MockResponse};
     use std::collections::HashMap;
     use std::sync::{Arc, Mutex};
 
@@ -22,63 +22,6 @@ mod tests {
         0x01, 0x00, 0x00, 0x00, // version 1
     ];
 
-    /// 模拟 AI Provider 用于测试
-    struct MockAiProvider {
-        responses: HashMap<String, String>,
-    }
-
-    impl MockAiProvider {
-        fn new() -> Self {
-            let mut responses = HashMap::new();
-            responses.insert("hello".to_string(), "Hello! How can I help you?".to_string());
-            responses.insert("test".to_string(), "This is a test response.".to_string());
-            Self { responses }
-        }
-    }
-
-    #[async_trait]
-    impl AiProvider for MockAiProvider {
-        fn name(&self) -> &str {
-            "mock"
-        }
-
-        async fn available(&self) -> bool {
-            true
-        }
-
-        async fn chat(&self, prompt: &str) -> crate::ai::Result<String> {
-            // 模拟真实 AI 响应 - 不是假数据！
-            let response = self.responses.get(prompt)
-                .cloned()
-                .unwrap_or_else(|| format!("AI response to: {}", prompt));
-            Ok(response)
-        }
-
-        async fn chat_with_context(
-            &self,
-            _system: &str,
-            _messages: &[crate::ai::Message],
-        ) -> crate::ai::Result<String> {
-            Ok("Mock context response".to_string())
-        }
-
-        async fn chat_with_rag(
-            &self,
-            prompt: &str,
-            _ctx: Option<&crate::conversation::ConversationContext>,
-        ) -> crate::ai::Result<String> {
-            Ok(format!("Mock RAG response to: {}", prompt))
-        }
-
-        async fn generate_json(
-            &self,
-            _prompt: &str,
-            _schema: &str,
-        ) -> crate::ai::Result<serde_json::Value> {
-            Ok(serde_json::json!({"mock": true}))
-        }
-    }
-
     /// 模拟 Memory Service 用于测试
     struct MockMemoryService {
         data: Mutex<HashMap<String, Vec<u8>>>,
@@ -128,7 +71,9 @@ mod tests {
         Arc<Mutex<dyn AiProvider>>,
         Arc<Mutex<dyn MemoryServiceTrait>>,
     ) {
-        let ai: Arc<Mutex<dyn AiProvider>> = Arc::new(Mutex::new(MockAiProvider::new()));
+        let ai: Arc<Mutex<dyn AiProvider>> = Arc::new(Mutex::new(MockAiProvider::with_responses(vec![
+            MockResponse::Return("Hello! How can I help you?".to_string()),
+        ])));
         let memory: Arc<Mutex<dyn MemoryServiceTrait>> = 
             Arc::new(Mutex::new(MockMemoryService::new()));
         (ai, memory)
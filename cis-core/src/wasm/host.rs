@@ -1759,7 +1759,7 @@ mod tests {
         let memory_service: Arc<Mutex<dyn MemoryServiceTrait>> = 
             Arc::new(Mutex::new(DummyMemoryService));
         let ai_provider: Arc<Mutex<dyn AiProvider>> = 
-            Arc::new(Mutex::new(mock_ai::MockAiProvider::new()));
+            Arc::new(Mutex::new(crate::test::mocks::MockAiProvider::new()));
         
         let mut ctx = HostContext::new(memory_service, ai_provider);
         
@@ -1778,62 +1778,3 @@ mod tests {
         assert!(!ctx.is_host_allowed("other.com"));
     }
 }
-
-/// 用于测试的 Mock AI Provider
-#[cfg(test)]
-mod mock_ai {
-    use async_trait::async_trait;
-    use crate::ai::{AiProvider, Message, Result};
-    use crate::conversation::ConversationContext;
-
-    pub struct MockAiProvider;
-
-    impl MockAiProvider {
-        pub fn new() -> Self {
-            Self
-        }
-    }
-
-    #[async_trait]
-    impl AiProvider for MockAiProvider {
-        fn name(&self) -> &str {
-            "mock"
-        }
-
-        async fn available(&self) -> bool {
-            true
-        }
-
-        async fn chat(&self, prompt: &str) -> Result<String> {
-            Ok(format!("Mock response to: {}", prompt))
-        }
-
-        async fn chat_with_context(
-            &self,
-            _system: &str,
-            _messages: &[Message],
-        ) -> Result<String> {
-            Ok("Mock context response".to_string())
-        }
-
-        async fn chat_with_rag(
-            &self,
-            prompt: &str,
-            _ctx: Option<&ConversationContext>,
-        ) -> Result<String> {
-            Ok(format!("Mock RAG response to: {}", prompt))
-        }
-
-        async fn generate_json(
-            &self,
-            _prompt: &str,
-            _schema: &str,
-        ) -> Result<serde_json::Value> {
-            Ok(serde_json::json!({"mock": true}))
-        }
-    }
-}
-
-// 导出 mock_ai 模块供测试使用
-#[cfg(test)]
-pub use mock_ai::MockAiProvider;
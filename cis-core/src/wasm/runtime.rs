@@ -574,6 +574,45 @@ impl WasmSkillInstance {
         Ok(result)
     }
     
+    /// 调用 Skill 方法描述函数
+    ///
+    /// `skill_describe` 导出函数返回 i64，高 32 位为指针、低 32 位为长度，
+    /// 指向一个 `MethodDescriptor` 数组的 JSON。
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Some(bytes))`: 描述 JSON
+    /// - `Ok(None)`: 模块未导出 `skill_describe`
+    /// - `Err(CisError)`: 调用失败
+    pub fn describe(&self) -> Result<Option<Vec<u8>>> {
+        self.check_timeout()?;
+
+        let mut store = self.store.lock()
+            .map_err(|e| CisError::wasm(format!("Store lock failed: {}", e)))?;
+
+        let func = match self.instance.exports.get_function("skill_describe") {
+            Ok(func) => func,
+            Err(_) => {
+                tracing::debug!("No skill_describe function found");
+                return Ok(None);
+            }
+        };
+
+        let res = func.call(&mut *store, &[])
+            .map_err(|e| CisError::wasm(format!("Describe failed: {}", e)))?;
+        let packed = res.first()
+            .and_then(|v| v.i64())
+            .ok_or_else(|| CisError::wasm("skill_describe returned invalid value"))?;
+
+        let ptr = wasmer::WasmPtr::<u8>::new((packed >> 32) as u32);
+        let len = (packed & 0xFFFF_FFFF) as u32;
+        let bytes = self.read_memory(&*store, ptr, len)?;
+
+        let _ = self.free(&mut store, ptr);
+
+        Ok(Some(bytes))
+    }
+
     /// 调用 Skill 关闭函数
     pub fn shutdown(&self) -> Result<()> {
         self.check_timeout()?;
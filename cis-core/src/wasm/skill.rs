@@ -9,7 +9,7 @@ use wasmer::{Instance, Memory, MemoryType};
 use crate::ai::AiProvider;
 use crate::error::{CisError, Result};
 use crate::memory::MemoryServiceTrait;
-use crate::skill::{Event, Skill, SkillConfig, SkillContext};
+use crate::skill::{Event, MethodDescriptor, Skill, SkillConfig, SkillContext};
 use crate::storage::DbManager;
 
 use super::runtime::{WasmRuntime, WasmSkillInstance};
@@ -232,6 +232,25 @@ impl Skill for WasmSkill {
         &self.description
     }
 
+    fn describe_methods(&self) -> Vec<MethodDescriptor> {
+        let instance = match self.runtime_instance {
+            Some(ref instance) => instance,
+            None => return Vec::new(),
+        };
+
+        match instance.describe() {
+            Ok(Some(bytes)) => parse_method_descriptors(&bytes).unwrap_or_else(|e| {
+                tracing::warn!("WASM Skill '{}' returned invalid method list: {}", self.name, e);
+                Vec::new()
+            }),
+            Ok(None) => Vec::new(),
+            Err(e) => {
+                tracing::warn!("Failed to describe WASM Skill '{}': {}", self.name, e);
+                Vec::new()
+            }
+        }
+    }
+
     async fn init(&mut self, config: SkillConfig) -> Result<()> {
         // 实例化 WASM 模块（带真实 AI Provider）
         self.instantiate()?;
@@ -259,6 +278,12 @@ impl Skill for WasmSkill {
     }
 }
 
+/// 解析 `skill_describe` 返回的 JSON
+fn parse_method_descriptors(bytes: &[u8]) -> Result<Vec<MethodDescriptor>> {
+    serde_json::from_slice(bytes)
+        .map_err(|e| CisError::skill(format!("Failed to parse method descriptors: {}", e)))
+}
+
 impl Drop for WasmSkill {
     fn drop(&mut self) {
         // 确保资源被正确释放
@@ -495,7 +520,7 @@ mod tests {
     use crate::wasm::DEFAULT_MEMORY_LIMIT_BYTES;
     use crate::ai::AiProvider;
     use crate::memory::MemoryServiceTrait;
-    use async_trait::async_trait;
+    use crate::test::mocks::MockAiProvider;
 
     // 简单的 WASM 模块：空模块
     const SIMPLE_WASM: &[u8] = &[
@@ -503,48 +528,6 @@ mod tests {
         0x01, 0x00, 0x00, 0x00, // version 1
     ];
 
-    /// 模拟 AI Provider 用于测试
-    struct MockAiProvider;
-
-    #[async_trait]
-    impl AiProvider for MockAiProvider {
-        fn name(&self) -> &str {
-            "mock"
-        }
-
-        async fn available(&self) -> bool {
-            true
-        }
-
-        async fn chat(&self, prompt: &str) -> crate::ai::Result<String> {
-            Ok(format!("Mock AI response to: {}", prompt))
-        }
-
-        async fn chat_with_context(
-            &self,
-            _system: &str,
-            _messages: &[crate::ai::Message],
-        ) -> crate::ai::Result<String> {
-            Ok("Mock context response".to_string())
-        }
-
-        async fn chat_with_rag(
-            &self,
-            prompt: &str,
-            _ctx: Option<&crate::conversation::ConversationContext>,
-        ) -> crate::ai::Result<String> {
-            Ok(format!("Mock RAG response to: {}", prompt))
-        }
-
-        async fn generate_json(
-            &self,
-            _prompt: &str,
-            _schema: &str,
-        ) -> crate::ai::Result<serde_json::Value> {
-            Ok(serde_json::json!({"mock": true}))
-        }
-    }
-
     /// 模拟 Memory Service 用于测试
     struct MockMemoryService {
         data: std::collections::HashMap<String, Vec<u8>>,
@@ -582,7 +565,7 @@ mod tests {
         Arc<Mutex<dyn AiProvider>>,
         Arc<Mutex<dyn MemoryServiceTrait>>,
     ) {
-        let ai: Arc<Mutex<dyn AiProvider>> = Arc::new(Mutex::new(MockAiProvider));
+        let ai: Arc<Mutex<dyn AiProvider>> = Arc::new(Mutex::new(MockAiProvider::new()));
         let memory: Arc<Mutex<dyn MemoryServiceTrait>> = 
             Arc::new(Mutex::new(MockMemoryService::new()));
         (ai, memory)
@@ -799,4 +782,20 @@ mod tests {
         };
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_parse_method_descriptors() {
+        let json = br#"[
+            {"name": "summarize", "description": "Summarize text",
+             "params_schema": {"type": "object"}, "return_schema": {"type": "string"}},
+            {"name": "ping"}
+        ]"#;
+        let methods = parse_method_descriptors(json).unwrap();
+        assert_eq!(methods.len(), 2);
+        assert_eq!(methods[0].name, "summarize");
+        assert_eq!(methods[1].description, "");
+        assert!(methods[1].params_schema.is_null());
+
+        assert!(parse_method_descriptors(b"not json").is_err());
+    }
 }
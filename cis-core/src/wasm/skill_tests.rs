@@ -6,7 +6,7 @@ use super::skill::{WasmSkill, WasmSkillBuilder, WasmSkillExecutor};
 use super::{WasmSkillConfig, DEFAULT_MEMORY_LIMIT_BYTES};
 use crate::skill::Skill;
 use std::sync::{Arc, Mutex};
-use async_trait::async_trait;
+use crate::test::mocks::MockAiProvider;
 
 /// 简单的 WASM 模块
 const SIMPLE_WASM: &[u8] = &[
@@ -14,48 +14,6 @@ const SIMPLE_WASM: &[u8] = &[
     0x01, 0x00, 0x00, 0x00, // version 1
 ];
 
-/// 模拟 AI Provider
-struct MockAiProvider;
-
-#[async_trait]
-impl crate::ai::AiProvider for MockAiProvider {
-    fn name(&self) -> &str {
-        "mock"
-    }
-
-    async fn available(&self) -> bool {
-        true
-    }
-
-    async fn chat(&self, prompt: &str) -> crate::ai::Result<String> {
-        Ok(format!("Mock: {}", prompt))
-    }
-
-    async fn chat_with_context(
-        &self,
-        _system: &str,
-        _messages: &[crate::ai::Message],
-    ) -> crate::ai::Result<String> {
-        Ok("Mock context".to_string())
-    }
-
-    async fn chat_with_rag(
-        &self,
-        prompt: &str,
-        _ctx: Option<&crate::conversation::ConversationContext>,
-    ) -> crate::ai::Result<String> {
-        Ok(format!("Mock RAG: {}", prompt))
-    }
-
-    async fn generate_json(
-        &self,
-        _prompt: &str,
-        _schema: &str,
-    ) -> crate::ai::Result<serde_json::Value> {
-        Ok(serde_json::json!({"result": "mock"}))
-    }
-}
-
 /// 模拟记忆服务
 struct MockMemoryService {
     data: std::collections::HashMap<String, Vec<u8>>,
@@ -91,7 +49,7 @@ fn create_test_services() -> (
     Arc<Mutex<dyn crate::ai::AiProvider>>,
     Arc<Mutex<dyn crate::memory::MemoryServiceTrait>>,
 ) {
-    let ai: Arc<Mutex<dyn crate::ai::AiProvider>> = Arc::new(Mutex::new(MockAiProvider));
+    let ai: Arc<Mutex<dyn crate::ai::AiProvider>> = Arc::new(Mutex::new(MockAiProvider::new()));
     let memory: Arc<Mutex<dyn crate::memory::MemoryServiceTrait>> =
         Arc::new(Mutex::new(MockMemoryService::new()));
     (ai, memory)
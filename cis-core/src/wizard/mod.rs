@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use crate::error::{CisError, Result};
+use crate::identity::NodeClaimService;
 use crate::project::Project;
 use crate::storage::paths::Paths;
 
@@ -144,11 +145,19 @@ impl InitWizard {
     /// 生成节点密钥
     fn generate_node_key(&self) -> Result<()> {
         let key_path = Paths::node_key_file();
+        let claim = NodeClaimService::open_default();
 
-        if key_path.exists() && !self.options.force {
+        // 硬件绑定后明文密钥已删除，绑定证明同样视为已有密钥
+        if claim.has_node_key() && !self.options.force {
             return Ok(()); // 已存在且不强制覆盖
         }
 
+        // 强制重新生成时旧的绑定证明随之失效
+        if claim.attestation_path().exists() {
+            std::fs::remove_file(claim.attestation_path())
+                .map_err(|e| CisError::storage(format!("Failed to remove hardware attestation: {}", e)))?;
+        }
+
         // 生成随机密钥
         let key: Vec<u8> = (0..32).map(|_| rand::random::<u8>()).collect();
 
@@ -242,6 +242,7 @@ pub fn create_dag_node(
         agent_runtime: None,
         reuse_agent: None,
         keep_agent: false,
+        fail_fast: false,
         agent_config: None,
     }
 }
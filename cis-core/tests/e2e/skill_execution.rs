@@ -4,7 +4,7 @@
 
 use cis_core::wasm::{WasmRuntime, WasmSkillConfig, WasmSkillBuilder, WasmSkillExecutor};
 use cis_core::skill::{Skill, SkillConfig};
-use async_trait::async_trait;
+use cis_core::MockAiProvider;
 use std::sync::{Arc, Mutex};
 
 /// 简单的 WASM 模块
@@ -13,60 +13,6 @@ const SIMPLE_WASM: &[u8] = &[
     0x01, 0x00, 0x00, 0x00, // version 1
 ];
 
-/// 模拟 AI Provider
-struct MockAiProvider {
-    responses: Mutex<Vec<String>>,
-}
-
-impl MockAiProvider {
-    fn new() -> Self {
-        Self {
-            responses: Mutex::new(Vec::new()),
-        }
-    }
-}
-
-#[async_trait]
-impl cis_core::ai::AiProvider for MockAiProvider {
-    fn name(&self) -> &str {
-        "mock"
-    }
-
-    async fn available(&self) -> bool {
-        true
-    }
-
-    async fn chat(&self, prompt: &str) -> cis_core::ai::Result<String> {
-        let response = format!("AI Response: {}", prompt);
-        self.responses.lock().unwrap().push(prompt.to_string());
-        Ok(response)
-    }
-
-    async fn chat_with_context(
-        &self,
-        _system: &str,
-        _messages: &[cis_core::ai::Message],
-    ) -> cis_core::ai::Result<String> {
-        Ok("Context response".to_string())
-    }
-
-    async fn chat_with_rag(
-        &self,
-        prompt: &str,
-        _ctx: Option<&cis_core::conversation::ConversationContext>,
-    ) -> cis_core::ai::Result<String> {
-        Ok(format!("RAG: {}", prompt))
-    }
-
-    async fn generate_json(
-        &self,
-        _prompt: &str,
-        _schema: &str,
-    ) -> cis_core::ai::Result<serde_json::Value> {
-        Ok(serde_json::json!({"result": "success"}))
-    }
-}
-
 /// 模拟记忆服务
 struct MockMemoryService {
     data: Mutex<std::collections::HashMap<String, Vec<u8>>>,
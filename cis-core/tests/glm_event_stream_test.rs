@@ -0,0 +1,75 @@
+//! # GLM 事件流集成测试
+//!
+//! 使用 tokio-tungstenite 客户端连接 GLM WebSocket 事件流，验证认证与订阅过滤。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use cis_core::glm::{CisEvent, GlmApiConfig, GlmApiServer};
+use futures::{SinkExt, StreamExt};
+use serde_json::json;
+use tokio::net::TcpListener;
+use tokio::time::timeout;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+
+async fn start_server() -> (Arc<GlmApiServer>, String) {
+    let server = Arc::new(GlmApiServer::new(GlmApiConfig {
+        api_key: Some("secret".to_string()),
+        ..Default::default()
+    }));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}/events", listener.local_addr().unwrap());
+
+    let serving = Arc::clone(&server);
+    tokio::spawn(async move { serving.serve_websocket(listener).await });
+
+    (server, url)
+}
+
+/// 未携带 API Key 的连接在握手阶段被拒绝
+#[tokio::test]
+async fn test_event_stream_rejects_missing_api_key() {
+    let (_server, url) = start_server().await;
+
+    assert!(connect_async(&url).await.is_err());
+    assert!(connect_async(format!("{}?api_key=wrong", url)).await.is_err());
+    assert!(connect_async(format!("{}?api_key=secret", url)).await.is_ok());
+}
+
+/// 订阅后只推送指定类型的事件
+#[tokio::test]
+async fn test_event_stream_subscription() {
+    let (server, url) = start_server().await;
+
+    let mut request = url.into_client_request().unwrap();
+    request
+        .headers_mut()
+        .insert("Authorization", "Bearer secret".parse().unwrap());
+    let (mut ws, _) = connect_async(request).await.expect("Failed to connect");
+
+    ws.send(Message::Text(r#"{"subscribe": ["dag.status"]}"#.to_string()))
+        .await
+        .unwrap();
+    // 等待服务端处理订阅请求
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let state = server.state();
+    state.publish_event(CisEvent::new("task.status", json!({ "task_id": "t1" })));
+    state.publish_event(CisEvent::new("dag.status", json!({ "dag_id": "d1" })));
+
+    let msg = timeout(Duration::from_secs(5), ws.next())
+        .await
+        .expect("Timed out waiting for event")
+        .expect("Stream closed")
+        .unwrap();
+    let Message::Text(text) = msg else {
+        panic!("Expected text frame, got {:?}", msg);
+    };
+
+    assert!(text.ends_with('\n'), "Events are newline-delimited JSON");
+    let event: CisEvent = serde_json::from_str(text.trim_end()).unwrap();
+    assert_eq!(event.event_type, "dag.status");
+    assert_eq!(event.data["dag_id"], "d1");
+}
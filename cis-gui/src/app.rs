@@ -517,6 +517,7 @@ impl CisApp {
                                 CoreNodeStatus::Offline => "○ offline",
                                 CoreNodeStatus::Blacklisted => "✗ blacklisted",
                                 CoreNodeStatus::Suspicious => "⚠ suspicious",
+                                CoreNodeStatus::Stale => "◌ stale",
                                 CoreNodeStatus::Unknown => "? unknown",
                             };
                             let name = if node.name.len() > 20 { 
@@ -2,11 +2,40 @@
 //!
 //! New app implementation using the three-panel Element-style layout.
 
+use std::collections::HashMap;
+use std::sync::{mpsc, Arc};
+
 use eframe::egui::{self, Context, Frame, TopBottomPanel, ViewportCommand};
-use tracing::info;
+use tracing::{info, warn};
+
+use cis_core::network::NetworkAcl;
 
 use crate::layout::{ThreePanelLayout, MainView, Composer, render_content, ContentResponse};
+use crate::node_manager::{ManagedNode, NodeStatus, TrustState};
+use crate::node_tabs::{NodeTabInfo, NodeTabs};
+use crate::remote_session::{RemoteSession, SessionState};
+use crate::terminal_panes::{PaneAction, TerminalPanes};
 use crate::theme::*;
+use crate::view_models::{MainViewModel, PaneId, PaneLayout, PaneState};
+
+/// Per-frame copy of the terminal state used for rendering panes
+struct TerminalSnapshot {
+    layout: PaneLayout,
+    /// Local terminal history
+    history: Vec<String>,
+    /// Remote session state and output by pane
+    remote: HashMap<PaneId, (Option<SessionState>, Vec<String>)>,
+    /// Name of the selected remote node (target for "Connect")
+    selected_node: Option<String>,
+}
+
+/// Requests from pane content
+enum PaneRequest {
+    /// Run a command in the local terminal
+    Command(String),
+    /// Connect a pane to the selected node
+    Connect(PaneId),
+}
 
 /// Main CIS application with Element-style layout
 pub struct CisAppElement {
@@ -18,13 +47,35 @@ pub struct CisAppElement {
     terminal_history: Vec<String>,
     /// Pending message to handle
     pending_response: Option<ContentResponse>,
+    /// Application view model (services + child view models)
+    main_vm: MainViewModel,
+    /// Node tab bar
+    node_tabs: NodeTabs,
+    /// Split terminal view
+    terminal_panes: TerminalPanes,
+    /// Local terminal command input
+    command_input: String,
+    /// Network ACL used for remote Agent sessions
+    acl: Arc<tokio::sync::RwLock<NetworkAcl>>,
+    /// Remote Agent sessions opened from the node tabs, by node ID
+    node_sessions: HashMap<String, RemoteSession>,
+    /// Results of background connect attempts
+    connect_tx: mpsc::Sender<(String, Result<RemoteSession, String>)>,
+    connect_rx: mpsc::Receiver<(String, Result<RemoteSession, String>)>,
 }
 
 impl CisAppElement {
     pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
         info!("Initializing CIS GUI with Element-style layout");
         
-        Self {
+        let mut main_vm = MainViewModel::new();
+        if let Err(e) = main_vm.initialize() {
+            warn!("Failed to initialize MainViewModel: {}", e);
+        }
+        
+        let (connect_tx, connect_rx) = mpsc::channel();
+        
+        let mut app = Self {
             layout: ThreePanelLayout::new(),
             composer: Composer::new(),
             terminal_history: vec![
@@ -33,9 +84,123 @@ impl CisAppElement {
                 "".to_string(),
             ],
             pending_response: None,
+            main_vm,
+            node_tabs: NodeTabs::new(),
+            terminal_panes: TerminalPanes::new(),
+            command_input: String::new(),
+            acl: Arc::new(tokio::sync::RwLock::new(NetworkAcl::new("local"))),
+            node_sessions: HashMap::new(),
+            connect_tx,
+            connect_rx,
+        };
+        app.sync_node_tabs();
+        app
+    }
+    
+    /// Refresh node tabs from the node view model (in display order)
+    fn sync_node_tabs(&mut self) {
+        let node_vm = self.main_vm.get_node_vm();
+        let tabs = self.main_vm.runtime().block_on(async {
+            node_vm.check_refresh_results().await;
+            if node_vm.should_refresh().await {
+                node_vm.refresh_nodes();
+            }
+            
+            let mut tabs = Vec::new();
+            for node in node_vm.get_nodes().await {
+                let connection = node_vm.connection_state(&node.id).await;
+                tabs.push(node_tab_info(&node).with_connection(connection));
+            }
+            tabs
+        });
+        self.node_tabs.set_nodes(tabs);
+    }
+    
+    /// Start connecting to a node's Agent in the background
+    fn connect_node(&mut self, node_id: String) {
+        if self.node_sessions.contains_key(&node_id) {
+            return;
+        }
+        
+        let node_vm = self.main_vm.get_node_vm();
+        let tx = self.connect_tx.clone();
+        self.main_vm.runtime().spawn(async move {
+            let result = node_vm.connect(&node_id).await;
+            let _ = tx.send((node_id, result));
+        });
+    }
+    
+    /// Close a node's Agent session
+    fn disconnect_node(&mut self, node_id: String) {
+        if let Some(session) = self.node_sessions.remove(&node_id) {
+            let node_vm = self.main_vm.get_node_vm();
+            self.main_vm.runtime().spawn(async move {
+                node_vm.disconnect(&node_id, session).await;
+            });
+        }
+    }
+    
+    /// Collect finished connect attempts
+    fn poll_connect_results(&mut self) {
+        while let Ok((node_id, result)) = self.connect_rx.try_recv() {
+            match result {
+                Ok(session) => {
+                    info!("Connected to node {}", node_id);
+                    self.node_sessions.insert(node_id, session);
+                }
+                Err(e) => warn!("{}", e),
+            }
         }
     }
     
+    /// Render the node tab bar and apply reorder requests
+    fn render_node_tabs(&mut self, ctx: &Context) {
+        let mut tabs_response = None;
+        
+        TopBottomPanel::top("node_tabs")
+            .exact_height(50.0)
+            .frame(Frame::default().fill(MAIN_BG))
+            .show(ctx, |ui| {
+                ui.add_space(8.0);
+                tabs_response = Some(self.node_tabs.ui(ui));
+            });
+        
+        let Some(tabs_response) = tabs_response else {
+            return;
+        };
+        
+        let node_vm = self.main_vm.get_node_vm();
+        
+        if let Some((from, to)) = tabs_response.reorder {
+            if let Err(e) = self.main_vm.runtime().block_on(node_vm.reorder_nodes(from, to)) {
+                warn!("{}", e);
+            }
+        }
+        
+        if tabs_response.reset_order_clicked {
+            if let Err(e) = self.main_vm.runtime().block_on(node_vm.reset_order()) {
+                warn!("{}", e);
+            }
+        }
+        
+        if let Some(node_id) = tabs_response.connect_agent {
+            self.connect_node(node_id);
+        }
+        
+        if let Some(node_id) = tabs_response.disconnect_node {
+            self.disconnect_node(node_id);
+        }
+        
+        self.poll_connect_results();
+        
+        if !self.node_tabs.is_dragging() {
+            self.sync_node_tabs();
+        }
+        
+        // Keep polling background node refreshes
+        ctx.request_repaint_after(std::time::Duration::from_secs(1));
+    }
+    
     /// Process any pending response
     fn process_pending(&mut self) {
         if let Some(response) = self.pending_response.take() {
@@ -99,15 +264,40 @@ impl eframe::App for CisAppElement {
                 });
             });
         
+        // Node tabs (drag ⠿ to reorder)
+        self.render_node_tabs(ctx);
+        
+        // Terminal state for the split pane view
+        let terminal = (self.layout.current_view == MainView::Terminal)
+            .then(|| self.terminal_snapshot());
+        let mut pane_actions = Vec::new();
+        let mut pane_requests = Vec::new();
+        
         // Collect response from content area
         let mut response = None;
         
         // Render three-panel layout
         self.layout.render(ctx, |ui, view, selected_session| {
+            if let (MainView::Terminal, Some(terminal)) = (view, &terminal) {
+                pane_actions = self.terminal_panes.ui(ui, &terminal.layout, |ui, pane, is_active| {
+                    render_terminal_pane(
+                        ui,
+                        pane,
+                        is_active,
+                        terminal,
+                        &mut self.command_input,
+                        &mut pane_requests,
+                    );
+                });
+                return;
+            }
             let resp = render_content(ui, view, selected_session, &mut self.composer);
             response = Some(resp);
         });
         
+        self.handle_pane_actions(pane_actions);
+        self.handle_pane_requests(pane_requests);
+        
         // Store response for next frame
         if let Some(resp) = response {
             self.pending_response = Some(resp);
@@ -115,3 +305,197 @@ impl eframe::App for CisAppElement {
         }
     }
 }
+
+impl CisAppElement {
+    /// Collect the terminal state needed to render the pane view
+    fn terminal_snapshot(&self) -> TerminalSnapshot {
+        let terminal_vm = self.main_vm.get_terminal_vm();
+        let selected = self.selected_remote_node().map(|n| n.name);
+        
+        self.main_vm.runtime().block_on(async {
+            terminal_vm.poll_pane_output().await;
+            
+            let layout = terminal_vm.get_panes().await;
+            let mut remote = HashMap::new();
+            for pane in layout.panes.iter().filter(|p| p.remote.is_some()) {
+                remote.insert(
+                    pane.id,
+                    (
+                        terminal_vm.pane_session_state(pane.id).await,
+                        terminal_vm.pane_output(pane.id).await,
+                    ),
+                );
+            }
+            
+            TerminalSnapshot {
+                layout,
+                history: terminal_vm.get_history().await,
+                remote,
+                selected_node: selected,
+            }
+        })
+    }
+    
+    /// The node selected in the node tabs, if it is a remote node
+    fn selected_remote_node(&self) -> Option<ManagedNode> {
+        let active = self.node_tabs.active_node();
+        if active.is_empty() || active == "local" {
+            return None;
+        }
+        let node_vm = self.main_vm.get_node_vm();
+        self.main_vm
+            .runtime()
+            .block_on(node_vm.get_nodes())
+            .into_iter()
+            .find(|n| n.id == active)
+    }
+    
+    /// Apply pane layout actions to the terminal view model
+    fn handle_pane_actions(&mut self, actions: Vec<PaneAction>) {
+        if actions.is_empty() {
+            return;
+        }
+        
+        let terminal_vm = self.main_vm.get_terminal_vm();
+        self.main_vm.runtime().block_on(async {
+            for action in actions {
+                match action {
+                    PaneAction::Split(direction) => {
+                        terminal_vm.split_pane(direction).await;
+                    }
+                    PaneAction::Close(id) => {
+                        terminal_vm.close_pane(id).await;
+                    }
+                    PaneAction::Focus(id) => terminal_vm.focus_pane(id).await,
+                    PaneAction::FocusNext => terminal_vm.focus_next_pane().await,
+                    PaneAction::FocusPrev => terminal_vm.focus_prev_pane().await,
+                    PaneAction::Resize { pane, ratio } => {
+                        terminal_vm.set_split_ratio(pane, ratio).await;
+                    }
+                    PaneAction::ResizeFinished => terminal_vm.save_panes().await,
+                }
+            }
+        });
+    }
+    
+    /// Handle commands and connect requests from pane content
+    fn handle_pane_requests(&mut self, requests: Vec<PaneRequest>) {
+        let terminal_vm = self.main_vm.get_terminal_vm();
+        
+        for request in requests {
+            match request {
+                PaneRequest::Command(cmd) => {
+                    if cmd.trim() == "clear" {
+                        self.main_vm.runtime().block_on(terminal_vm.clear());
+                    } else {
+                        self.main_vm.runtime().block_on(terminal_vm.execute_command(&cmd));
+                    }
+                }
+                PaneRequest::Connect(pane) => {
+                    let Some(node) = self.selected_remote_node() else {
+                        warn!("No remote node selected for pane {}", pane);
+                        continue;
+                    };
+                    let did = node.did.clone().unwrap_or_else(|| node.id.clone());
+                    let host = node.address.split(':').next().unwrap_or(&node.address).to_string();
+                    let acl = Arc::clone(&self.acl);
+                    let result = self
+                        .main_vm
+                        .runtime()
+                        .block_on(terminal_vm.connect_pane(pane, &did, &host, acl));
+                    if let Err(e) = result {
+                        warn!("{}", e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Render the content of one terminal pane
+fn render_terminal_pane(
+    ui: &mut egui::Ui,
+    pane: &PaneState,
+    is_active: bool,
+    terminal: &TerminalSnapshot,
+    command_input: &mut String,
+    requests: &mut Vec<PaneRequest>,
+) {
+    let remote = terminal.remote.get(&pane.id);
+    
+    // Header: title, session state, connect button
+    ui.horizontal(|ui| {
+        let title_color = if is_active { TEXT_PRIMARY } else { TEXT_SECONDARY };
+        ui.label(egui::RichText::new(pane.title()).strong().color(title_color));
+        
+        if let Some((state, _)) = remote {
+            let state = state.unwrap_or(SessionState::Disconnected);
+            let color = match state {
+                SessionState::Connected | SessionState::AgentRunning => STATUS_ONLINE,
+                SessionState::Connecting | SessionState::Authenticating => STATUS_WARNING,
+                SessionState::Error => STATUS_ERROR,
+                SessionState::Disconnected => STATUS_OFFLINE,
+            };
+            ui.label(egui::RichText::new(format!("● {:?}", state)).small().color(color));
+        }
+        
+        if let Some(node) = &terminal.selected_node {
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button(format!("Connect to {}", node)).clicked() {
+                    requests.push(PaneRequest::Connect(pane.id));
+                }
+            });
+        }
+    });
+    ui.separator();
+    
+    let lines = match remote {
+        Some((_, output)) => output,
+        None => &terminal.history,
+    };
+    let input_height = if remote.is_none() && is_active { 28.0 } else { 0.0 };
+    
+    egui::ScrollArea::vertical()
+        .id_salt(("terminal_pane_output", pane.id))
+        .auto_shrink([false; 2])
+        .max_height(ui.available_height() - input_height)
+        .stick_to_bottom(true)
+        .show(ui, |ui| {
+            for line in lines {
+                let color = if line.starts_with('$') { TERMINAL_GREEN } else { TERMINAL_FG };
+                ui.label(egui::RichText::new(line).monospace().color(color).size(13.0));
+            }
+        });
+    
+    // Command input for the active local pane
+    if remote.is_none() && is_active {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("$").monospace().color(TERMINAL_GREEN));
+            let response = ui.add(
+                egui::TextEdit::singleline(command_input)
+                    .font(egui::FontId::monospace(13.0))
+                    .text_color(TERMINAL_FG)
+                    .desired_width(ui.available_width()),
+            );
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                requests.push(PaneRequest::Command(std::mem::take(command_input)));
+                response.request_focus();
+            }
+        });
+    }
+}
+
+/// Convert a managed node into its tab representation
+fn node_tab_info(node: &ManagedNode) -> NodeTabInfo {
+    let mut info = NodeTabInfo::new(node.id.clone(), node.name.clone());
+    if let Some(did) = &node.did {
+        info = info.with_did(did.clone());
+    }
+    if node.trust_state == TrustState::Verified {
+        info = info.verified();
+    }
+    if node.status == NodeStatus::Online {
+        info = info.online();
+    }
+    info
+}
@@ -8,10 +8,18 @@
 //! - Confirmed: Modal dialog, must manually confirm
 //! - Arbitrated: Freeze DAG, open arbitration workspace
 
-use eframe::egui::{self, Color32, CornerRadius, Frame, Margin, RichText, Vec2, Window};
+use std::time::{Duration, Instant};
+
+use eframe::egui::{self, Color32, CornerRadius, Frame, Margin, RichText, Stroke, Vec2, Window};
 
 use crate::theme::*;
-use cis_core::types::TaskLevel;
+use cis_core::types::{Action, TaskLevel};
+
+/// Repaint interval while a countdown is running
+pub const COUNTDOWN_REPAINT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Countdown ring diameter
+const COUNTDOWN_RING_SIZE: f32 = 34.0;
 
 /// Decision action returned by the UI
 #[derive(Debug, Clone)]
@@ -83,6 +91,126 @@ impl PendingDecision {
     }
 }
 
+/// Countdown timer for Recommended level decisions
+#[derive(Debug, Clone)]
+pub struct CountdownTimer {
+    total: f64,
+    remaining: f64,
+    default_action: Action,
+    last_update: Instant,
+    fired: bool,
+}
+
+impl CountdownTimer {
+    pub fn new(seconds: u16, default_action: Action) -> Self {
+        Self {
+            total: f64::from(seconds),
+            remaining: f64::from(seconds),
+            default_action,
+            last_update: Instant::now(),
+            fired: false,
+        }
+    }
+
+    /// Seconds remaining
+    pub fn remaining(&self) -> f64 {
+        self.remaining
+    }
+
+    /// Remaining fraction, from 1.0 (just started) to 0.0 (expired)
+    pub fn progress(&self) -> f32 {
+        if self.total <= 0.0 {
+            0.0
+        } else {
+            (self.remaining / self.total) as f32
+        }
+    }
+
+    /// Action taken when the countdown expires
+    pub fn default_action(&self) -> Action {
+        self.default_action
+    }
+
+    /// Advance by `elapsed` seconds; returns the default action once when reaching zero
+    pub fn tick(&mut self, elapsed: f64) -> Option<Action> {
+        if self.fired {
+            return None;
+        }
+        self.remaining = (self.remaining - elapsed).max(0.0);
+        if self.remaining <= 0.0 {
+            self.fired = true;
+            Some(self.default_action)
+        } else {
+            None
+        }
+    }
+
+    /// Advance by the wall-clock time since the last update
+    pub fn update(&mut self) -> Option<Action> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_update).as_secs_f64();
+        self.last_update = now;
+        self.tick(elapsed)
+    }
+}
+
+/// Map a countdown default action to the decision taken on expiry
+pub fn default_decision_action(action: Action) -> DecisionAction {
+    match action {
+        Action::Execute => DecisionAction::AutoProceed,
+        Action::Skip => DecisionAction::Skip,
+        Action::Abort => DecisionAction::Abort,
+    }
+}
+
+/// Ring color for the remaining fraction: green → yellow → red
+pub fn countdown_color(progress: f32) -> Color32 {
+    let progress = progress.clamp(0.0, 1.0);
+    if progress >= 0.5 {
+        lerp_color(STATUS_WARNING, STATUS_ONLINE, (progress - 0.5) * 2.0)
+    } else {
+        lerp_color(STATUS_ERROR, STATUS_WARNING, progress * 2.0)
+    }
+}
+
+fn lerp_color(from: Color32, to: Color32, t: f32) -> Color32 {
+    let lerp = |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * t).round() as u8;
+    Color32::from_rgb(lerp(from.r(), to.r()), lerp(from.g(), to.g()), lerp(from.b(), to.b()))
+}
+
+/// Draw a circular countdown with the remaining seconds in the center
+fn paint_countdown_ring(ui: &mut egui::Ui, timer: &CountdownTimer) {
+    let (rect, _) = ui.allocate_exact_size(Vec2::splat(COUNTDOWN_RING_SIZE), egui::Sense::hover());
+    let painter = ui.painter();
+    let center = rect.center();
+    let radius = COUNTDOWN_RING_SIZE / 2.0 - 3.0;
+    let progress = timer.progress();
+    let color = countdown_color(progress);
+
+    painter.circle_stroke(center, radius, Stroke::new(3.0, PANEL_BG));
+
+    // Arc from 12 o'clock, clockwise, proportional to the remaining time
+    let segments = 48;
+    let sweep = std::f32::consts::TAU * progress;
+    let points: Vec<egui::Pos2> = (0..=segments)
+        .map(|i| {
+            let angle = -std::f32::consts::FRAC_PI_2 + sweep * i as f32 / segments as f32;
+            center + Vec2::new(angle.cos(), angle.sin()) * radius
+        })
+        .collect();
+    if progress > 0.0 {
+        painter.add(egui::Shape::line(points, Stroke::new(3.0, color)));
+    }
+
+    painter.text(
+        center,
+        egui::Align2::CENTER_CENTER,
+        format!("{}", timer.remaining().ceil() as u64),
+        egui::FontId::proportional(12.0),
+        color,
+    );
+}
+
 /// Decision panel for four-tier decision mechanism
 pub struct DecisionPanel {
     /// Current countdown for Recommended level (seconds remaining)
@@ -97,8 +225,8 @@ pub struct DecisionPanel {
     pub show_arbitration_workspace: bool,
     /// Temporary storage for task modifications
     pub temp_changes: TaskChanges,
-    /// Last update timestamp for countdown
-    last_update: Option<std::time::Instant>,
+    /// Running countdown for Recommended level
+    countdown_timer: Option<CountdownTimer>,
 }
 
 impl DecisionPanel {
@@ -110,7 +238,7 @@ impl DecisionPanel {
             show_modify_dialog: false,
             show_arbitration_workspace: false,
             temp_changes: TaskChanges::default(),
-            last_update: None,
+            countdown_timer: None,
         }
     }
 
@@ -121,11 +249,11 @@ impl DecisionPanel {
                 // Mechanical: no UI, auto-proceed
                 self.pending_task = Some(decision);
             }
-            TaskLevel::Recommended { timeout_secs, .. } => {
+            TaskLevel::Recommended { timeout_secs, default_action } => {
                 // Recommended: start countdown
                 self.countdown = Some(timeout_secs);
+                self.countdown_timer = Some(CountdownTimer::new(timeout_secs, default_action));
                 self.pending_task = Some(decision);
-                self.last_update = Some(std::time::Instant::now());
             }
             TaskLevel::Confirmed => {
                 // Confirmed: show modal
@@ -148,7 +276,24 @@ impl DecisionPanel {
         self.show_arbitration_workspace = false;
         self.pending_task = None;
         self.temp_changes = TaskChanges::default();
-        self.last_update = None;
+        self.countdown_timer = None;
+    }
+
+    /// Stop the countdown and require manual confirmation instead
+    pub fn cancel_countdown(&mut self) {
+        if self.countdown_timer.take().is_none() {
+            return;
+        }
+        self.countdown = None;
+        if let Some(ref mut pending) = self.pending_task {
+            pending.level = TaskLevel::Confirmed;
+            self.show_confirmation = true;
+        }
+    }
+
+    /// Seconds remaining on the countdown, if one is running
+    pub fn countdown_remaining(&self) -> Option<f64> {
+        self.countdown_timer.as_ref().map(|t| t.remaining())
     }
 
     /// Check if there's a pending decision
@@ -161,17 +306,14 @@ impl DecisionPanel {
     pub fn ui(&mut self, ctx: &egui::Context) -> Option<DecisionAction> {
         let mut action = None;
 
-        // Update countdown if needed
-        self.update_countdown();
-
-        // Check for auto-proceed on countdown expiration
-        if let Some(countdown) = self.countdown {
-            if countdown == 0 && self.pending_task.is_some() {
-                // Auto-proceed when countdown reaches 0
-                action = Some(DecisionAction::AutoProceed);
+        // Update countdown; take the default action when it expires
+        if let Some(timer) = self.countdown_timer.as_mut() {
+            if let Some(default_action) = timer.update() {
                 self.clear();
-                return action;
+                return Some(default_decision_action(default_action));
             }
+            self.countdown = Some(timer.remaining().ceil() as u16);
+            ctx.request_repaint_after(COUNTDOWN_REPAINT_INTERVAL);
         }
 
         // Render based on decision level
@@ -207,25 +349,14 @@ impl DecisionPanel {
         action
     }
 
-    /// Update countdown timer
-    fn update_countdown(&mut self) {
-        if let (Some(countdown), Some(last_update)) = (self.countdown.as_mut(), self.last_update) {
-            let now = std::time::Instant::now();
-            let elapsed = now.duration_since(last_update).as_secs() as u16;
-
-            if elapsed > 0 {
-                *countdown = countdown.saturating_sub(elapsed);
-                self.last_update = Some(now);
-            }
-        }
-    }
-
     /// Render Recommended level notification bar
     fn render_notification_bar(&mut self, ctx: &egui::Context) -> Option<DecisionAction> {
         let mut action = None;
 
         let task_name = self.pending_task.as_ref()?.task_name.clone();
         let countdown = self.countdown.unwrap_or(0);
+        let timer = self.countdown_timer.clone();
+        let mut cancel_countdown = false;
 
         // Top panel notification bar
         egui::TopBottomPanel::top("decision_notification")
@@ -235,12 +366,16 @@ impl DecisionPanel {
                 ui.horizontal_centered(|ui| {
                     ui.add_space(16.0);
 
-                    // Lightning icon
-                    ui.label(
-                        RichText::new("⚡")
-                            .size(18.0)
-                            .color(STATUS_WARNING)
-                    );
+                    // Countdown ring (falls back to the lightning icon)
+                    if let Some(ref timer) = timer {
+                        paint_countdown_ring(ui, timer);
+                    } else {
+                        ui.label(
+                            RichText::new("⚡")
+                                .size(18.0)
+                                .color(STATUS_WARNING)
+                        );
+                    }
 
                     ui.add_space(8.0);
 
@@ -304,12 +439,30 @@ impl DecisionPanel {
                         if ui.add(modify_btn).clicked() {
                             self.show_modify_dialog = true;
                         }
+
+                        ui.add_space(8.0);
+
+                        // Cancel countdown button
+                        let cancel_btn = egui::Button::new(
+                            RichText::new("取消倒计时")
+                                .color(TEXT_PRIMARY)
+                                .size(12.0)
+                        )
+                        .fill(PANEL_BG)
+                        .corner_radius(CornerRadius::same(4))
+                        .min_size(Vec2::new(80.0, 28.0));
+
+                        if ui.add(cancel_btn).clicked() {
+                            cancel_countdown = true;
+                        }
                     });
                 });
             });
 
         if action.is_some() {
             self.clear();
+        } else if cancel_countdown {
+            self.cancel_countdown();
         }
 
         action
@@ -705,6 +858,65 @@ mod tests {
         assert!(!panel.show_confirmation);
     }
 
+    fn recommended(default_action: Action, timeout_secs: u16) -> PendingDecision {
+        PendingDecision::new(
+            "task-2".to_string(),
+            "倒计时".to_string(),
+            TaskLevel::Recommended { default_action, timeout_secs },
+        )
+    }
+
+    #[test]
+    fn test_countdown_timer_fires_once() {
+        let mut timer = CountdownTimer::new(3, Action::Skip);
+        assert_eq!(timer.progress(), 1.0);
+
+        assert!(timer.tick(1.0).is_none());
+        assert!((timer.remaining() - 2.0).abs() < f64::EPSILON);
+
+        assert_eq!(timer.tick(5.0), Some(Action::Skip));
+        assert_eq!(timer.remaining(), 0.0);
+        assert!(timer.tick(1.0).is_none());
+    }
+
+    #[test]
+    fn test_countdown_color_gradient() {
+        assert_eq!(countdown_color(1.0), STATUS_ONLINE);
+        assert_eq!(countdown_color(0.5), STATUS_WARNING);
+        assert_eq!(countdown_color(0.0), STATUS_ERROR);
+    }
+
+    #[test]
+    fn test_cancel_countdown_requires_confirmation() {
+        let mut panel = DecisionPanel::new();
+        panel.set_pending_decision(recommended(Action::Execute, 10));
+        assert_eq!(panel.countdown, Some(10));
+        assert!(panel.countdown_remaining().is_some());
+
+        panel.cancel_countdown();
+        assert!(panel.countdown_remaining().is_none());
+        assert!(panel.show_confirmation);
+        assert!(matches!(
+            panel.pending_task.as_ref().unwrap().level,
+            TaskLevel::Confirmed
+        ));
+    }
+
+    #[test]
+    fn test_expired_countdown_takes_default_action() {
+        let ctx = egui::Context::default();
+        let mut panel = DecisionPanel::new();
+        panel.set_pending_decision(recommended(Action::Abort, 0));
+
+        let mut action = None;
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            action = panel.ui(ctx);
+        });
+
+        assert!(matches!(action, Some(DecisionAction::Abort)));
+        assert!(!panel.has_pending_decision());
+    }
+
     #[test]
     fn test_task_changes_default() {
         let changes = TaskChanges::default();
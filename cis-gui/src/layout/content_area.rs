@@ -4,6 +4,7 @@
 //! - Home view: Dashboard with quick actions and recent activity
 //! - DAGs view: DAG visualization and timeline
 //! - Chat view: AI conversation with composer
+//! - Terminal view: rendered by the app (see `terminal_panes`)
 //! - Settings view: Configuration panels
 
 use egui::{Ui, Frame, Margin, RichText, Vec2, Stroke, ScrollArea, Layout};
@@ -34,6 +35,8 @@ pub fn render_content(
             render_composer_area(ui, composer, &mut response);
         }
         MainView::Settings => render_settings_view(ui),
+        // Rendered by the app, which owns the terminal view model
+        MainView::Terminal => {}
     }
     
     response
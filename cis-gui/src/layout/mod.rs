@@ -25,6 +25,8 @@ pub enum MainView {
     Dags,
     /// Chat with AI agent
     Chat,
+    /// Split terminal panes (local / remote Agent sessions)
+    Terminal,
     /// Settings
     Settings,
 }
@@ -35,6 +37,7 @@ impl MainView {
             MainView::Home => "🏠",
             MainView::Dags => "📊",
             MainView::Chat => "💬",
+            MainView::Terminal => "⌨",
             MainView::Settings => "⚙️",
         }
     }
@@ -44,6 +47,7 @@ impl MainView {
             MainView::Home => "Home",
             MainView::Dags => "DAGs",
             MainView::Chat => "Chat",
+            MainView::Terminal => "Terminal",
             MainView::Settings => "Settings",
         }
     }
@@ -54,6 +58,7 @@ impl MainView {
             MainView::Dags => "⌘2",
             MainView::Chat => "⌘3",
             MainView::Settings => "⌘4",
+            MainView::Terminal => "⌘5",
         }
     }
 }
@@ -117,6 +122,10 @@ impl ThreePanelLayout {
             if i.key_pressed(egui::Key::Num4) {
                 self.switch_view(MainView::Settings);
             }
+            // 5: Terminal
+            if i.key_pressed(egui::Key::Num5) {
+                self.switch_view(MainView::Terminal);
+            }
         });
     }
     
@@ -166,6 +175,7 @@ impl ThreePanelLayout {
                         MainView::Home,
                         MainView::Dags,
                         MainView::Chat,
+                        MainView::Terminal,
                     ];
                     
                     for view in nav_items {
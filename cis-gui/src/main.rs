@@ -20,7 +20,9 @@ mod glm_panel;
 mod node_tabs;
 mod node_manager;
 mod terminal_panel;
+mod terminal_panes;
 mod remote_session;
+mod ssh_tunnel;
 mod theme;
 mod layout;
 
@@ -6,6 +6,7 @@ use eframe::egui::{self, Color32, CornerRadius, RichText, Stroke, Vec2};
 use tracing::info;
 
 use crate::theme::*;
+use crate::view_models::ConnectionState;
 
 /// Information about a node for display
 #[derive(Debug, Clone)]
@@ -17,6 +18,7 @@ pub struct NodeTabInfo {
     pub is_verified: bool,
     pub is_online: bool,
     pub has_session: bool,  // Has active remote session
+    pub connection: ConnectionState,
 }
 
 impl NodeTabInfo {
@@ -29,6 +31,7 @@ impl NodeTabInfo {
             is_verified: false,
             is_online: false,
             has_session: false,
+            connection: ConnectionState::Disconnected,
         }
     }
     
@@ -56,6 +59,24 @@ impl NodeTabInfo {
         self.has_session = true;
         self
     }
+    
+    pub fn with_connection(mut self, connection: ConnectionState) -> Self {
+        self.has_session = connection == ConnectionState::Connected;
+        self.connection = connection;
+        self
+    }
+}
+
+/// Default width of one tab slot (drag handle + tab), used before the first layout pass
+const DEFAULT_SLOT_WIDTH: f32 = 120.0;
+
+/// In-progress drag of a node tab
+#[derive(Debug, Clone, Copy)]
+struct TabDrag {
+    /// Index of the dragged tab
+    from: usize,
+    /// Accumulated horizontal drag distance
+    offset: f32,
 }
 
 /// Node tabs component
@@ -63,6 +84,10 @@ pub struct NodeTabs {
     nodes: Vec<NodeTabInfo>,
     active_node: String,
     show_manager: bool,
+    /// Current drag-and-drop operation
+    drag: Option<TabDrag>,
+    /// Measured width of one tab slot
+    slot_width: f32,
 }
 
 impl NodeTabs {
@@ -71,6 +96,8 @@ impl NodeTabs {
             nodes: Vec::new(),
             active_node: String::new(),
             show_manager: false,
+            drag: None,
+            slot_width: DEFAULT_SLOT_WIDTH,
         }
     }
     
@@ -83,7 +110,33 @@ impl NodeTabs {
             nodes,
             active_node,
             show_manager: false,
+            drag: None,
+            slot_width: DEFAULT_SLOT_WIDTH,
+        }
+    }
+
+    /// Replace the displayed nodes, keeping the active selection when possible
+    pub fn set_nodes(&mut self, nodes: Vec<NodeTabInfo>) {
+        if !self.active_node.is_empty()
+            && self.active_node != "local"
+            && !nodes.iter().any(|n| n.id == self.active_node)
+        {
+            self.active_node = nodes.first().map(|n| n.id.clone()).unwrap_or_default();
         }
+        self.nodes = nodes;
+    }
+
+    /// Move a tab from one position to another
+    pub fn move_node(&mut self, from: usize, to: usize) {
+        if from < self.nodes.len() && to < self.nodes.len() {
+            let node = self.nodes.remove(from);
+            self.nodes.insert(to, node);
+        }
+    }
+
+    /// Whether a tab is currently being dragged
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
     }
     
     pub fn add_node(&mut self, node: NodeTabInfo) {
@@ -129,18 +182,32 @@ impl NodeTabs {
             
             // Node tabs
             let nodes = self.nodes.clone(); // Clone to avoid borrow issues
-            for node in &nodes {
+            for (index, node) in nodes.iter().enumerate() {
                 let is_active = node.id == self.active_node;
-                if self.render_node_tab(ui, node, is_active) {
+                let slot_start = ui.cursor().min.x;
+                
+                self.render_drag_handle(ui, index, nodes.len(), &mut response);
+                
+                if self.render_node_tab(ui, node, is_active, &mut response) {
                     self.active_node = node.id.clone();
                     response.node_selected = Some(node.id.clone());
                     info!("Selected node: {}", node.id);
                 }
+                
+                let slot_width = ui.cursor().min.x - slot_start;
+                if slot_width > 0.0 {
+                    self.slot_width = slot_width;
+                }
             }
             
             // Add/Manager button
             ui.separator();
             
+            if ui.small_button("Reset order").clicked() {
+                response.reset_order_clicked = true;
+                info!("Reset node order requested");
+            }
+            
             let manager_btn = egui::Button::new("☰")
                 .min_size(Vec2::new(32.0, 32.0))
                 .fill(PANEL_BG)
@@ -163,6 +230,47 @@ impl NodeTabs {
         response
     }
     
+    /// Render the drag handle in front of a node tab
+    fn render_drag_handle(
+        &mut self,
+        ui: &mut egui::Ui,
+        index: usize,
+        count: usize,
+        response: &mut NodeTabsResponse,
+    ) {
+        let dragging_this = self.drag.is_some_and(|d| d.from == index);
+        let color = if dragging_this { TEXT_PRIMARY } else { TEXT_SECONDARY };
+        
+        let handle = ui
+            .add(
+                egui::Label::new(RichText::new("⠿").color(color).size(16.0))
+                    .sense(egui::Sense::drag()),
+            )
+            .on_hover_cursor(egui::CursorIcon::Grab);
+        
+        if handle.drag_started() {
+            self.drag = Some(TabDrag { from: index, offset: 0.0 });
+        }
+        
+        if handle.dragged() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+            if let Some(drag) = self.drag.as_mut().filter(|d| d.from == index) {
+                drag.offset += handle.drag_delta().x;
+            }
+        }
+        
+        if handle.drag_stopped() {
+            if let Some(drag) = self.drag.take() {
+                let to = drop_index(drag.from, drag.offset, self.slot_width, count);
+                if to != drag.from {
+                    info!("Reordering node tab {} -> {}", drag.from, to);
+                    self.move_node(drag.from, to);
+                    response.reorder = Some((drag.from, to));
+                }
+            }
+        }
+    }
+    
     fn render_local_tab(&self, ui: &mut egui::Ui, response: &mut NodeTabsResponse) -> bool {
         let is_active = self.active_node.is_empty() || self.active_node == "local";
         
@@ -190,7 +298,13 @@ impl NodeTabs {
         clicked
     }
     
-    fn render_node_tab(&self, ui: &mut egui::Ui, node: &NodeTabInfo, is_active: bool) -> bool {
+    fn render_node_tab(
+        &self,
+        ui: &mut egui::Ui,
+        node: &NodeTabInfo,
+        is_active: bool,
+        tabs_response: &mut NodeTabsResponse,
+    ) -> bool {
         let (bg, text) = if node.is_verified {
             if node.is_local {
                 (VERIFIED_LOCAL_BG, VERIFIED_LOCAL_TEXT)
@@ -228,47 +342,38 @@ impl NodeTabs {
         
         let response = ui.add(btn);
         
-        // Context menu
-        let mut connect_agent = None;
-        let mut disconnect_node = None;
-        let mut verify_node = None;
+        // Connection badge
+        let (badge, badge_color) = connection_badge(node.connection);
+        ui.label(RichText::new(badge).size(10.0).color(badge_color))
+            .on_hover_text("Remote Agent session");
         
+        // Context menu (events are handled by the app)
         response.context_menu(|ui| {
             ui.label(format!("DID: {}", node.did.as_deref().unwrap_or("Unknown")));
             ui.label(format!("Status: {}", if node.is_online { "Online" } else { "Offline" }));
             ui.label(format!("Verified: {}", if node.is_verified { "Yes" } else { "No" }));
+            ui.label(format!("Session: {}", badge));
             ui.separator();
             
-            if ui.button("Connect Agent").clicked() {
-                connect_agent = Some(node.id.clone());
-                info!("Connect agent requested for node: {}", node.id);
-                ui.close_menu();
-            }
-            
-            if ui.button("Disconnect").clicked() {
-                disconnect_node = Some(node.id.clone());
+            if node.connection == ConnectionState::Disconnected {
+                if ui.button("Connect Agent").clicked() {
+                    tabs_response.connect_agent = Some(node.id.clone());
+                    info!("Connect agent requested for node: {}", node.id);
+                    ui.close_menu();
+                }
+            } else if ui.button("Disconnect").clicked() {
+                tabs_response.disconnect_node = Some(node.id.clone());
                 info!("Disconnect requested for node: {}", node.id);
                 ui.close_menu();
             }
             
             if !node.is_verified && ui.button("Verify DID").clicked() {
-                verify_node = Some(node.id.clone());
+                tabs_response.verify_node = Some(node.id.clone());
                 info!("Verify DID requested for node: {}", node.id);
                 ui.close_menu();
             }
         });
         
-        // Store events for processing
-        if connect_agent.is_some() {
-            // Event will be handled by the app
-        }
-        if disconnect_node.is_some() {
-            // Event will be handled by the app
-        }
-        if verify_node.is_some() {
-            // Event will be handled by the app
-        }
-        
         response.clicked()
     }
     
@@ -280,6 +385,25 @@ impl NodeTabs {
     }
 }
 
+/// Badge text and color for a remote Agent connection state
+fn connection_badge(state: ConnectionState) -> (&'static str, Color32) {
+    match state {
+        ConnectionState::Connected => ("connected", STATUS_ONLINE),
+        ConnectionState::Connecting => ("connecting", STATUS_WARNING),
+        ConnectionState::Disconnected => ("disconnected", STATUS_OFFLINE),
+    }
+}
+
+/// Compute the target index of a dragged tab from its horizontal offset
+fn drop_index(from: usize, offset: f32, slot_width: f32, count: usize) -> usize {
+    if count == 0 {
+        return 0;
+    }
+    let slots = if slot_width > 0.0 { (offset / slot_width).round() } else { 0.0 };
+    let target = from as f32 + slots;
+    target.clamp(0.0, (count - 1) as f32) as usize
+}
+
 impl Default for NodeTabs {
     fn default() -> Self {
         Self::new()
@@ -294,4 +418,51 @@ pub struct NodeTabsResponse {
     pub connect_agent: Option<String>,
     pub disconnect_node: Option<String>,
     pub verify_node: Option<String>,
+    /// Tab moved by drag-and-drop: (from index, to index)
+    pub reorder: Option<(usize, usize)>,
+    /// "Reset order" button clicked
+    pub reset_order_clicked: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drop_index() {
+        assert_eq!(drop_index(0, 0.0, 100.0, 4), 0);
+        assert_eq!(drop_index(0, 40.0, 100.0, 4), 0);
+        assert_eq!(drop_index(0, 160.0, 100.0, 4), 2);
+        assert_eq!(drop_index(2, -120.0, 100.0, 4), 1);
+        assert_eq!(drop_index(1, -500.0, 100.0, 4), 0);
+        assert_eq!(drop_index(1, 900.0, 100.0, 4), 3);
+        assert_eq!(drop_index(0, 50.0, 0.0, 4), 0);
+    }
+
+    #[test]
+    fn test_with_connection_marks_session() {
+        let node = NodeTabInfo::new("a", "A").with_connection(ConnectionState::Connected);
+        assert!(node.has_session);
+        assert_eq!(connection_badge(node.connection).0, "connected");
+
+        let node = NodeTabInfo::new("b", "B").with_connection(ConnectionState::Connecting);
+        assert!(!node.has_session);
+        assert_eq!(connection_badge(node.connection).1, STATUS_WARNING);
+    }
+
+    #[test]
+    fn test_move_node() {
+        let mut tabs = NodeTabs::with_nodes(vec![
+            NodeTabInfo::new("a", "A"),
+            NodeTabInfo::new("b", "B"),
+            NodeTabInfo::new("c", "C"),
+        ]);
+        tabs.move_node(0, 2);
+        let ids: Vec<_> = tabs.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c", "a"]);
+
+        // Out of range moves are ignored
+        tabs.move_node(5, 0);
+        assert_eq!(tabs.nodes.len(), 3);
+    }
 }
@@ -1,12 +1,14 @@
 //! # Remote Agent Session
 //!
-//! Manages remote Agent connections over Matrix Federation.
+//! Manages remote Agent connections over Matrix Federation, either directly
+//! or through an SSH tunnel (see [`crate::ssh_tunnel`]).
 
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock};
 use tracing::{info, error, warn, debug};
 use futures::{SinkExt, StreamExt};
-use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream};
+use tokio_tungstenite::{client_async, connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpStream;
 use serde::{Deserialize, Serialize};
 use bytes::BytesMut;
@@ -15,6 +17,8 @@ use uuid::Uuid;
 use cis_core::network::NetworkAcl;
 use cis_core::network::SessionControlMessage;
 
+use crate::ssh_tunnel::SshTunnel;
+
 
 /// Default WebSocket port for agent sessions
 pub const AGENT_SESSION_PORT: u16 = 6767;
@@ -133,6 +137,9 @@ pub struct RemoteSession {
     
     /// Connection handle
     connection_handle: Option<tokio::task::JoinHandle<()>>,
+    
+    /// SSH tunnel carrying the session (when connected via SSH)
+    ssh_tunnel: Option<SshTunnel>,
 }
 
 impl RemoteSession {
@@ -148,6 +155,7 @@ impl RemoteSession {
             session_id: None,
             shutdown_tx: None,
             connection_handle: None,
+            ssh_tunnel: None,
         }
     }
     
@@ -166,6 +174,65 @@ impl RemoteSession {
         
         info!("WebSocket connected to {}", self.target_addr);
         
+        self.start_session(ws_stream).await
+    }
+    
+    /// Connect to remote node through an SSH tunnel
+    ///
+    /// The Agent session WebSocket runs over a channel forwarded to the
+    /// node's loopback session port, so all terminal I/O goes through SSH.
+    pub async fn connect_via_ssh(&mut self, tunnel: SshTunnel) -> Result<(), SessionError> {
+        info!(
+            "Connecting to {} via SSH tunnel to {}",
+            self.target_did,
+            tunnel.endpoint().host
+        );
+        self.state = SessionState::Connecting;
+        
+        let stream = match tunnel.open_channel(AGENT_SESSION_PORT).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                self.state = SessionState::Error;
+                tunnel.close().await;
+                return Err(e);
+            }
+        };
+        
+        let ws_url = format!("ws://127.0.0.1:{}/_cis/agent/session", AGENT_SESSION_PORT);
+        let ws_stream = match client_async(ws_url.as_str(), stream).await {
+            Ok((ws_stream, _)) => ws_stream,
+            Err(e) => {
+                self.state = SessionState::Error;
+                tunnel.close().await;
+                return Err(SessionError::ConnectionFailed(format!(
+                    "WebSocket over SSH failed: {}",
+                    e
+                )));
+            }
+        };
+        
+        info!("WebSocket connected to {} over SSH", self.target_addr);
+        self.ssh_tunnel = Some(tunnel);
+        
+        let result = self.start_session(ws_stream).await;
+        if result.is_err() {
+            if let Some(tunnel) = self.ssh_tunnel.take() {
+                tunnel.close().await;
+            }
+        }
+        result
+    }
+    
+    /// Whether the session runs through an SSH tunnel
+    pub fn is_tunneled(&self) -> bool {
+        self.ssh_tunnel.is_some()
+    }
+    
+    /// Authenticate and start PTY forwarding over an established WebSocket
+    async fn start_session<S>(&mut self, ws_stream: WebSocketStream<S>) -> Result<(), SessionError>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
         let (mut ws_sender, mut ws_receiver) = ws_stream.split();
         
         // Perform DID challenge/response
@@ -371,6 +438,11 @@ impl RemoteSession {
         }
     }
     
+    /// Receive output without waiting (for UI polling)
+    pub fn try_receive_output(&mut self) -> Option<Vec<u8>> {
+        self.output_rx.as_mut()?.try_recv().ok()
+    }
+    
     /// Disconnect session
     pub async fn disconnect(&mut self) {
         info!("Disconnecting from {}", self.target_did);
@@ -406,6 +478,11 @@ impl RemoteSession {
         self.session_id = None;
         self.started_at = None;
         
+        // Close the SSH tunnel last, after the WebSocket has shut down
+        if let Some(tunnel) = self.ssh_tunnel.take() {
+            tunnel.close().await;
+        }
+        
         info!("Disconnected from {}", self.target_did);
     }
     
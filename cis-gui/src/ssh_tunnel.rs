@@ -0,0 +1,226 @@
+//! # SSH Tunnel
+//!
+//! Opens an SSH connection to a remote CIS node and forwards a
+//! `direct-tcpip` channel to the node's local Agent session port.
+//!
+//! Authentication uses the local DID Ed25519 key as an SSH public key, so
+//! the remote node must list the DID public key in the SSH user's
+//! `authorized_keys`. The Agent session on top of the tunnel still performs
+//! the DID challenge/response, which is what identifies the remote node.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use russh::client::{self, Handle, Msg};
+use russh::{ChannelStream, Disconnect};
+use russh_keys::key::{KeyPair, PublicKey};
+use tracing::{debug, info, warn};
+
+use cis_core::identity::DIDManager;
+
+use crate::remote_session::SessionError;
+
+/// Default SSH port
+pub const DEFAULT_SSH_PORT: u16 = 22;
+
+/// Default SSH user on CIS nodes
+pub const DEFAULT_SSH_USER: &str = "cis";
+
+/// SSH endpoint of a remote node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshEndpoint {
+    pub user: String,
+    pub host: String,
+    pub port: u16,
+}
+
+impl SshEndpoint {
+    /// Parse a stored node endpoint
+    ///
+    /// `ssh://[user@]host[:port]` is used as-is. Any other endpoint
+    /// (`host:port` of the CIS node) only contributes the host; the SSH
+    /// user and port fall back to the defaults.
+    pub fn parse(endpoint: &str) -> Result<Self, SessionError> {
+        let endpoint = endpoint.trim();
+
+        let (rest, explicit) = match endpoint.strip_prefix("ssh://") {
+            Some(rest) => (rest.trim_end_matches('/'), true),
+            None => (endpoint, false),
+        };
+
+        let (user, host_port) = match rest.split_once('@') {
+            Some((user, host_port)) if explicit => (user.to_string(), host_port),
+            _ => (DEFAULT_SSH_USER.to_string(), rest),
+        };
+
+        let (host, port) = split_host_port(host_port);
+        if host.is_empty() {
+            return Err(SessionError::ConnectionFailed(format!(
+                "Invalid node endpoint: {}",
+                endpoint
+            )));
+        }
+
+        let port = match (explicit, port) {
+            (true, Some(port)) => port
+                .parse()
+                .map_err(|_| SessionError::ConnectionFailed(format!("Invalid SSH port: {}", port)))?,
+            _ => DEFAULT_SSH_PORT,
+        };
+
+        Ok(Self {
+            user,
+            host: host.to_string(),
+            port,
+        })
+    }
+}
+
+/// Split `host[:port]`, keeping bracketed IPv6 hosts intact
+fn split_host_port(s: &str) -> (&str, Option<&str>) {
+    if let Some(rest) = s.strip_prefix('[') {
+        if let Some((host, tail)) = rest.split_once(']') {
+            return (host, tail.strip_prefix(':'));
+        }
+    }
+    match s.rsplit_once(':') {
+        // A bare IPv6 address has more than one colon
+        Some((host, port)) if !host.contains(':') => (host, Some(port)),
+        _ => (s, None),
+    }
+}
+
+/// SSH client callbacks
+struct TunnelClient {
+    host: String,
+}
+
+#[async_trait]
+impl client::Handler for TunnelClient {
+    type Error = russh::Error;
+
+    async fn check_server_key(&mut self, server_public_key: &PublicKey) -> Result<bool, Self::Error> {
+        // The host key is not pinned; the DID handshake over the tunnel
+        // authenticates the remote node.
+        info!(
+            "SSH host key for {}: {}",
+            self.host,
+            server_public_key.fingerprint()
+        );
+        Ok(true)
+    }
+}
+
+/// Open SSH connection to a remote node
+pub struct SshTunnel {
+    endpoint: SshEndpoint,
+    handle: Handle<TunnelClient>,
+}
+
+impl SshTunnel {
+    /// Connect and authenticate with the local DID key
+    pub async fn open(endpoint: &SshEndpoint, identity: &DIDManager) -> Result<Self, SessionError> {
+        info!(
+            "Opening SSH tunnel to {}@{}:{}",
+            endpoint.user, endpoint.host, endpoint.port
+        );
+
+        let config = Arc::new(client::Config::default());
+        let handler = TunnelClient {
+            host: endpoint.host.clone(),
+        };
+
+        let mut handle = client::connect(config, (endpoint.host.as_str(), endpoint.port), handler)
+            .await
+            .map_err(|e| SessionError::ConnectionFailed(format!("SSH connect failed: {}", e)))?;
+
+        let key = Arc::new(KeyPair::Ed25519(identity.signing_key().clone()));
+        let authenticated = handle
+            .authenticate_publickey(endpoint.user.clone(), key)
+            .await
+            .map_err(|e| SessionError::AuthFailed(format!("SSH authentication failed: {}", e)))?;
+
+        if !authenticated {
+            return Err(SessionError::AuthFailed(format!(
+                "SSH key for {} rejected by {}",
+                identity.did(),
+                endpoint.host
+            )));
+        }
+
+        debug!("SSH authenticated as {} on {}", endpoint.user, endpoint.host);
+
+        Ok(Self {
+            endpoint: endpoint.clone(),
+            handle,
+        })
+    }
+
+    /// Forward a channel to `port` on the remote node's loopback interface
+    pub async fn open_channel(&self, port: u16) -> Result<ChannelStream<Msg>, SessionError> {
+        let channel = self
+            .handle
+            .channel_open_direct_tcpip("127.0.0.1", u32::from(port), "127.0.0.1", 0)
+            .await
+            .map_err(|e| {
+                SessionError::ConnectionFailed(format!("SSH port forwarding failed: {}", e))
+            })?;
+
+        Ok(channel.into_stream())
+    }
+
+    /// Remote endpoint
+    pub fn endpoint(&self) -> &SshEndpoint {
+        &self.endpoint
+    }
+
+    /// Close the SSH connection
+    pub async fn close(&self) {
+        if let Err(e) = self
+            .handle
+            .disconnect(Disconnect::ByApplication, "session closed", "en")
+            .await
+        {
+            warn!("Failed to close SSH tunnel to {}: {}", self.endpoint.host, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_node_endpoint_uses_ssh_defaults() {
+        let endpoint = SshEndpoint::parse("192.168.1.105:7676").unwrap();
+        assert_eq!(endpoint.user, DEFAULT_SSH_USER);
+        assert_eq!(endpoint.host, "192.168.1.105");
+        assert_eq!(endpoint.port, DEFAULT_SSH_PORT);
+
+        let endpoint = SshEndpoint::parse("seed.cis.dev").unwrap();
+        assert_eq!(endpoint.host, "seed.cis.dev");
+    }
+
+    #[test]
+    fn test_parse_ssh_url() {
+        let endpoint = SshEndpoint::parse("ssh://munin@10.0.0.2:2222").unwrap();
+        assert_eq!(
+            endpoint,
+            SshEndpoint {
+                user: "munin".to_string(),
+                host: "10.0.0.2".to_string(),
+                port: 2222,
+            }
+        );
+
+        let endpoint = SshEndpoint::parse("ssh://[::1]:2200/").unwrap();
+        assert_eq!(endpoint.host, "::1");
+        assert_eq!(endpoint.port, 2200);
+    }
+
+    #[test]
+    fn test_parse_invalid_endpoint() {
+        assert!(SshEndpoint::parse("").is_err());
+        assert!(SshEndpoint::parse("ssh://host:notaport").is_err());
+    }
+}
@@ -0,0 +1,258 @@
+//! # Terminal Panes
+//!
+//! Split terminal view: renders the pane layout with draggable splitters.
+//!
+//! Shortcuts:
+//! - Ctrl+Shift+H: split horizontally (side by side)
+//! - Ctrl+Shift+V: split vertically (stacked)
+//! - Ctrl+Shift+W: close active pane
+//! - Ctrl+Shift+→ / ←: focus next / previous pane
+
+use eframe::egui::{self, CursorIcon, Key, KeyboardShortcut, Modifiers, Pos2, Rect, Sense, Stroke, Ui};
+
+use crate::theme::*;
+use crate::view_models::{PaneId, PaneLayout, PaneState, SplitDirection};
+
+/// Splitter thickness in points
+const SPLITTER_WIDTH: f32 = 6.0;
+
+const SPLIT_HORIZONTAL: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::CTRL.plus(Modifiers::SHIFT), Key::H);
+const SPLIT_VERTICAL: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::CTRL.plus(Modifiers::SHIFT), Key::V);
+const CLOSE_PANE: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::CTRL.plus(Modifiers::SHIFT), Key::W);
+const FOCUS_NEXT: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::CTRL.plus(Modifiers::SHIFT), Key::ArrowRight);
+const FOCUS_PREV: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::CTRL.plus(Modifiers::SHIFT), Key::ArrowLeft);
+
+/// Action requested by the pane view
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaneAction {
+    /// Split the active pane
+    Split(SplitDirection),
+    /// Close a pane
+    Close(PaneId),
+    /// Focus a pane
+    Focus(PaneId),
+    /// Focus the next pane
+    FocusNext,
+    /// Focus the previous pane
+    FocusPrev,
+    /// Splitter moved (while dragging)
+    Resize { pane: PaneId, ratio: f32 },
+    /// Splitter released, layout should be persisted
+    ResizeFinished,
+}
+
+/// Split terminal view
+#[derive(Default)]
+pub struct TerminalPanes;
+
+impl TerminalPanes {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Consume pane keyboard shortcuts
+    pub fn handle_shortcuts(&self, ctx: &egui::Context, active: PaneId) -> Vec<PaneAction> {
+        ctx.input_mut(|i| {
+            let mut actions = Vec::new();
+            if i.consume_shortcut(&SPLIT_HORIZONTAL) {
+                actions.push(PaneAction::Split(SplitDirection::Horizontal));
+            }
+            if i.consume_shortcut(&SPLIT_VERTICAL) {
+                actions.push(PaneAction::Split(SplitDirection::Vertical));
+            }
+            if i.consume_shortcut(&CLOSE_PANE) {
+                actions.push(PaneAction::Close(active));
+            }
+            if i.consume_shortcut(&FOCUS_NEXT) {
+                actions.push(PaneAction::FocusNext);
+            }
+            if i.consume_shortcut(&FOCUS_PREV) {
+                actions.push(PaneAction::FocusPrev);
+            }
+            actions
+        })
+    }
+
+    /// Render all panes into the available area
+    ///
+    /// `render_pane` draws the content of one pane; the flag tells whether it is active.
+    pub fn ui(
+        &mut self,
+        ui: &mut Ui,
+        layout: &PaneLayout,
+        mut render_pane: impl FnMut(&mut Ui, &PaneState, bool),
+    ) -> Vec<PaneAction> {
+        let mut actions = self.handle_shortcuts(ui.ctx(), layout.active);
+
+        let rect = ui.available_rect_before_wrap();
+        ui.allocate_rect(rect, Sense::hover());
+        self.render_chain(ui, rect, &layout.panes, layout.active, &mut render_pane, &mut actions);
+
+        actions
+    }
+
+    fn render_chain(
+        &self,
+        ui: &mut Ui,
+        rect: Rect,
+        panes: &[PaneState],
+        active: PaneId,
+        render_pane: &mut impl FnMut(&mut Ui, &PaneState, bool),
+        actions: &mut Vec<PaneAction>,
+    ) {
+        let Some((first, rest)) = panes.split_first() else {
+            return;
+        };
+
+        let Some(next) = rest.first() else {
+            self.render_pane(ui, rect, first, first.id == active, render_pane, actions);
+            return;
+        };
+
+        let direction = next.split.unwrap_or(SplitDirection::Horizontal);
+        let (first_rect, splitter_rect, rest_rect) = split_rect(rect, direction, next.ratio);
+
+        self.render_pane(ui, first_rect, first, first.id == active, render_pane, actions);
+        self.render_splitter(ui, rect, splitter_rect, direction, next.id, actions);
+        self.render_chain(ui, rest_rect, rest, active, render_pane, actions);
+    }
+
+    fn render_pane(
+        &self,
+        ui: &mut Ui,
+        rect: Rect,
+        pane: &PaneState,
+        is_active: bool,
+        render_pane: &mut impl FnMut(&mut Ui, &PaneState, bool),
+        actions: &mut Vec<PaneAction>,
+    ) {
+        // Click anywhere in the pane to focus it
+        let focus = ui.interact(rect, ui.id().with(("terminal_pane", pane.id)), Sense::click());
+        if focus.clicked() && !is_active {
+            actions.push(PaneAction::Focus(pane.id));
+        }
+
+        let stroke = if is_active {
+            Stroke::new(1.5, ACCENT_PRIMARY)
+        } else {
+            Stroke::new(1.0, BORDER_COLOR)
+        };
+        ui.painter().rect_filled(rect, 4.0, TERMINAL_BG);
+        ui.painter().rect_stroke(rect, 4.0, stroke, egui::StrokeKind::Inside);
+
+        let mut child = ui.new_child(egui::UiBuilder::new().max_rect(rect.shrink(6.0)));
+        child.set_clip_rect(rect.shrink(2.0));
+        render_pane(&mut child, pane, is_active);
+    }
+
+    fn render_splitter(
+        &self,
+        ui: &mut Ui,
+        parent: Rect,
+        splitter: Rect,
+        direction: SplitDirection,
+        pane: PaneId,
+        actions: &mut Vec<PaneAction>,
+    ) {
+        let response = ui.interact(
+            splitter,
+            ui.id().with(("pane_splitter", pane)),
+            Sense::drag(),
+        );
+        let cursor = match direction {
+            SplitDirection::Horizontal => CursorIcon::ResizeHorizontal,
+            SplitDirection::Vertical => CursorIcon::ResizeVertical,
+        };
+        let response = response.on_hover_cursor(cursor);
+
+        if response.dragged() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                actions.push(PaneAction::Resize {
+                    pane,
+                    ratio: ratio_at(parent, direction, pos),
+                });
+            }
+        }
+        if response.drag_stopped() {
+            actions.push(PaneAction::ResizeFinished);
+        }
+
+        let color = if response.hovered() || response.dragged() {
+            ACCENT_PRIMARY
+        } else {
+            SURFACE_BG
+        };
+        ui.painter().rect_filled(splitter.shrink(1.0), 2.0, color);
+    }
+}
+
+/// Split a rect into (first, splitter, rest) areas
+fn split_rect(rect: Rect, direction: SplitDirection, ratio: f32) -> (Rect, Rect, Rect) {
+    let half = SPLITTER_WIDTH / 2.0;
+    match direction {
+        SplitDirection::Horizontal => {
+            let x = rect.left() + rect.width() * ratio;
+            (
+                Rect::from_min_max(rect.min, Pos2::new(x - half, rect.bottom())),
+                Rect::from_min_max(Pos2::new(x - half, rect.top()), Pos2::new(x + half, rect.bottom())),
+                Rect::from_min_max(Pos2::new(x + half, rect.top()), rect.max),
+            )
+        }
+        SplitDirection::Vertical => {
+            let y = rect.top() + rect.height() * ratio;
+            (
+                Rect::from_min_max(rect.min, Pos2::new(rect.right(), y - half)),
+                Rect::from_min_max(Pos2::new(rect.left(), y - half), Pos2::new(rect.right(), y + half)),
+                Rect::from_min_max(Pos2::new(rect.left(), y + half), rect.max),
+            )
+        }
+    }
+}
+
+/// Split ratio for a pointer position inside `rect`
+fn ratio_at(rect: Rect, direction: SplitDirection, pos: Pos2) -> f32 {
+    match direction {
+        SplitDirection::Horizontal => (pos.x - rect.left()) / rect.width().max(1.0),
+        SplitDirection::Vertical => (pos.y - rect.top()) / rect.height().max(1.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_rect_horizontal() {
+        let rect = Rect::from_min_max(Pos2::new(0.0, 0.0), Pos2::new(200.0, 100.0));
+        let (left, splitter, right) = split_rect(rect, SplitDirection::Horizontal, 0.25);
+
+        assert_eq!(left.right(), 50.0 - SPLITTER_WIDTH / 2.0);
+        assert_eq!(splitter.center().x, 50.0);
+        assert_eq!(right.left(), 50.0 + SPLITTER_WIDTH / 2.0);
+        assert_eq!(right.right(), 200.0);
+        assert_eq!(left.height(), 100.0);
+    }
+
+    #[test]
+    fn test_split_rect_vertical() {
+        let rect = Rect::from_min_max(Pos2::new(0.0, 0.0), Pos2::new(200.0, 100.0));
+        let (top, splitter, bottom) = split_rect(rect, SplitDirection::Vertical, 0.5);
+
+        assert_eq!(top.bottom(), 50.0 - SPLITTER_WIDTH / 2.0);
+        assert_eq!(splitter.center().y, 50.0);
+        assert_eq!(bottom.top(), 50.0 + SPLITTER_WIDTH / 2.0);
+        assert_eq!(top.width(), 200.0);
+    }
+
+    #[test]
+    fn test_ratio_at() {
+        let rect = Rect::from_min_max(Pos2::new(100.0, 0.0), Pos2::new(300.0, 100.0));
+        assert_eq!(ratio_at(rect, SplitDirection::Horizontal, Pos2::new(150.0, 10.0)), 0.25);
+        assert_eq!(ratio_at(rect, SplitDirection::Vertical, Pos2::new(150.0, 75.0)), 0.75);
+    }
+}
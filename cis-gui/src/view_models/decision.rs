@@ -4,9 +4,15 @@
 
 use std::sync::{Arc, Mutex};
 
+use eframe::egui;
 use tracing::info;
 
-use crate::decision_panel::{DecisionPanel, PendingDecision, DecisionAction};
+use cis_core::types::TaskLevel;
+
+use crate::decision_panel::{
+    default_decision_action, CountdownTimer, DecisionAction, DecisionPanel, PendingDecision,
+    COUNTDOWN_REPAINT_INTERVAL,
+};
 use super::{ViewModel, ViewModelState};
 
 /// Decision ViewModel
@@ -22,6 +28,9 @@ pub struct DecisionViewModel {
     /// Current pending decision
     pending_decision: Arc<Mutex<Option<PendingDecision>>>,
 
+    /// Countdown for a Recommended level decision
+    countdown: Arc<Mutex<Option<CountdownTimer>>>,
+
     /// View model state
     state: ViewModelState,
 }
@@ -34,12 +43,21 @@ impl DecisionViewModel {
         Self {
             panel: DecisionPanel::new(),
             pending_decision: Arc::new(Mutex::new(None)),
+            countdown: Arc::new(Mutex::new(None)),
             state: ViewModelState::new(),
         }
     }
 
     /// Set a pending decision
     pub fn set_pending_decision(&self, decision: PendingDecision) {
+        let timer = match decision.level {
+            TaskLevel::Recommended { default_action, timeout_secs } => {
+                Some(CountdownTimer::new(timeout_secs, default_action))
+            }
+            _ => None,
+        };
+        *self.countdown.lock().unwrap() = timer;
+
         let mut pending = self.pending_decision.lock().unwrap();
         *pending = Some(decision);
         self.state.mark_dirty();
@@ -55,9 +73,48 @@ impl DecisionViewModel {
     pub fn clear_pending_decision(&self) {
         let mut pending = self.pending_decision.lock().unwrap();
         *pending = None;
+        *self.countdown.lock().unwrap() = None;
         self.state.mark_dirty();
     }
 
+    /// Seconds remaining on the countdown, if one is running
+    pub fn countdown_remaining(&self) -> Option<f64> {
+        self.countdown.lock().unwrap().as_ref().map(|t| t.remaining())
+    }
+
+    /// Advance the countdown by `elapsed` seconds
+    ///
+    /// When it reaches zero the default action is handled and its message returned.
+    pub fn tick_countdown(&self, elapsed: f64) -> Option<String> {
+        let fired = self.countdown.lock().unwrap().as_mut()?.tick(elapsed);
+        self.finish_countdown(fired)
+    }
+
+    /// Advance the countdown by wall-clock time and schedule the next repaint
+    pub fn update_countdown(&self, ctx: &egui::Context) -> Option<String> {
+        let fired = self.countdown.lock().unwrap().as_mut()?.update();
+        if fired.is_none() {
+            ctx.request_repaint_after(COUNTDOWN_REPAINT_INTERVAL);
+        }
+        self.finish_countdown(fired)
+    }
+
+    /// Stop the countdown and require manual confirmation instead
+    pub fn cancel_countdown(&self) {
+        if self.countdown.lock().unwrap().take().is_none() {
+            return;
+        }
+        if let Some(ref mut pending) = *self.pending_decision.lock().unwrap() {
+            pending.level = TaskLevel::Confirmed;
+        }
+        self.state.mark_dirty();
+    }
+
+    fn finish_countdown(&self, fired: Option<cis_core::types::Action>) -> Option<String> {
+        self.state.mark_dirty();
+        fired.map(|action| self.handle_action(default_decision_action(action)))
+    }
+
     /// Handle a decision action and return a message
     pub fn handle_action(&self, action: DecisionAction) -> String {
         use DecisionAction::*;
@@ -128,7 +185,7 @@ impl ViewModel for DecisionViewModel {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cis_core::types::TaskLevel;
+    use cis_core::types::Action;
 
     #[test]
     fn test_decision_vm_creation() {
@@ -171,6 +228,63 @@ mod tests {
         assert!(vm.get_pending_decision().is_none());
     }
 
+    fn recommended(default_action: Action, timeout_secs: u16) -> PendingDecision {
+        PendingDecision::new(
+            "test-task".to_string(),
+            "Countdown Decision".to_string(),
+            TaskLevel::Recommended { default_action, timeout_secs },
+        )
+    }
+
+    #[test]
+    fn test_countdown_triggers_default_action() {
+        let vm = DecisionViewModel::new();
+        vm.set_pending_decision(recommended(Action::Skip, 3));
+        assert_eq!(vm.countdown_remaining(), Some(3.0));
+
+        assert!(vm.tick_countdown(1.5).is_none());
+        assert_eq!(vm.countdown_remaining(), Some(1.5));
+        assert!(vm.get_pending_decision().is_some());
+
+        let message = vm.tick_countdown(1.5).unwrap();
+        assert!(message.contains("Skip"));
+        assert!(vm.get_pending_decision().is_none());
+        assert!(vm.countdown_remaining().is_none());
+    }
+
+    #[test]
+    fn test_countdown_execute_auto_proceeds() {
+        let vm = DecisionViewModel::new();
+        vm.set_pending_decision(recommended(Action::Execute, 1));
+
+        let message = vm.tick_countdown(2.0).unwrap();
+        assert!(message.contains("Auto-proceeding"));
+    }
+
+    #[test]
+    fn test_cancel_countdown() {
+        let vm = DecisionViewModel::new();
+        vm.set_pending_decision(recommended(Action::Abort, 5));
+
+        vm.cancel_countdown();
+        assert!(vm.countdown_remaining().is_none());
+        assert!(vm.tick_countdown(10.0).is_none());
+
+        let pending = vm.get_pending_decision().unwrap();
+        assert!(matches!(pending.level, TaskLevel::Confirmed));
+    }
+
+    #[test]
+    fn test_no_countdown_for_confirmed_level() {
+        let vm = DecisionViewModel::new();
+        vm.set_pending_decision(PendingDecision::new(
+            "test-task".to_string(),
+            "Test Decision".to_string(),
+            TaskLevel::Confirmed,
+        ));
+        assert!(vm.countdown_remaining().is_none());
+    }
+
     #[test]
     fn test_clear_pending_decision() {
         let vm = DecisionViewModel::new();
@@ -5,6 +5,7 @@
 use std::sync::Arc;
 use tracing::{info, warn};
 
+use cis_core::memory::MemoryService;
 use cis_core::service::{NodeService, DagService};
 
 use super::{ViewModel, ViewModelState};
@@ -70,21 +71,40 @@ impl MainViewModel {
             }
         };
 
+        let memory_service = match MemoryService::open_default("cis-gui") {
+            Ok(service) => {
+                info!("MemoryService initialized successfully");
+                Some(Arc::new(service))
+            }
+            Err(e) => {
+                warn!("Failed to initialize MemoryService: {}", e);
+                None
+            }
+        };
+
         // Create child ViewModels
-        let node_vm = Arc::new(NodeViewModel::new(
+        let mut node_vm = NodeViewModel::new(
             node_service.clone(),
             runtime.handle().clone(),
-        ));
+        );
+        if let Some(ref memory_service) = memory_service {
+            node_vm = node_vm.with_memory_service(Arc::clone(memory_service));
+        }
+        let node_vm = Arc::new(node_vm);
 
         let decision_vm = Arc::new(DecisionViewModel::new());
 
-        let terminal_vm = Arc::new(TerminalViewModel::new(
+        let mut terminal_vm = TerminalViewModel::new(
             Arc::clone(&node_vm),
             Arc::clone(&decision_vm),
             node_service.clone(),
             dag_service.clone(),
             runtime.handle().clone(),
-        ));
+        );
+        if let Some(memory_service) = memory_service {
+            terminal_vm = terminal_vm.with_memory_service(memory_service);
+        }
+        let terminal_vm = Arc::new(terminal_vm);
 
         Self {
             node_service,
@@ -103,6 +123,16 @@ impl MainViewModel {
     pub fn initialize(&mut self) -> Result<(), String> {
         info!("MainViewModel::initialize");
 
+        // Restore the saved node display order
+        if let Err(e) = self.runtime.block_on(self.node_vm.load_display_order()) {
+            warn!("{}", e);
+        }
+
+        // Restore the terminal pane layout
+        if let Err(e) = self.runtime.block_on(self.terminal_vm.load_panes()) {
+            warn!("{}", e);
+        }
+
         // Trigger initial node refresh
         self.node_vm.refresh_nodes();
 
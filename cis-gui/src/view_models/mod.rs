@@ -10,6 +10,6 @@ mod decision;
 
 pub use base::{ViewModel, ViewModelState};
 pub use main::MainViewModel;
-pub use node::NodeViewModel;
-pub use terminal::TerminalViewModel;
+pub use node::{ConnectionState, NodeViewModel};
+pub use terminal::{PaneId, PaneLayout, PaneState, SplitDirection, TerminalViewModel};
 pub use decision::DecisionViewModel;
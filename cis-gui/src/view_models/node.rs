@@ -2,6 +2,7 @@
 //!
 //! Manages node list, status, and operations
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
@@ -9,12 +10,30 @@ use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 
+use cis_core::identity::DIDManager;
+use cis_core::memory::MemoryService;
 use cis_core::service::{NodeService, ListOptions};
 use cis_core::service::node_service::{NodeInfo, BindOptions, TrustLevel};
+use cis_core::storage::Paths;
+use cis_core::types::{MemoryCategory, MemoryDomain};
 
 use crate::node_manager::{ManagedNode, NodeStatus, TrustState};
+use crate::remote_session::{RemoteSession, SessionState};
+use crate::ssh_tunnel::{SshEndpoint, SshTunnel};
 use super::{ViewModel, ViewModelState};
 
+/// Memory key holding the user's custom node display order
+pub const NODE_DISPLAY_ORDER_KEY: &str = "node_display_order";
+
+/// Remote Agent connection state of a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConnectionState {
+    #[default]
+    Disconnected,
+    Connecting,
+    Connected,
+}
+
 /// Result type for node refresh operations
 #[derive(Debug, Clone)]
 pub enum NodeRefreshResult {
@@ -56,6 +75,15 @@ pub struct NodeViewModel {
 
     /// Whether to use real nodes (vs demo nodes)
     use_real_nodes: Arc<AtomicBool>,
+
+    /// Memory service used to persist local display preferences
+    memory_service: Option<Arc<MemoryService>>,
+
+    /// Custom display order (node IDs), `None` means default order
+    display_order: Arc<RwLock<Option<Vec<String>>>>,
+
+    /// Remote Agent connection state by node ID
+    connections: Arc<RwLock<HashMap<String, ConnectionState>>>,
 }
 
 impl NodeViewModel {
@@ -124,17 +152,110 @@ impl NodeViewModel {
             refresh_rx: Arc::new(RwLock::new(refresh_rx)),
             state: ViewModelState::new(),
             use_real_nodes: Arc::new(AtomicBool::new(false)),
+            memory_service: None,
+            display_order: Arc::new(RwLock::new(None)),
+            connections: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
-    /// Get current node list
+    /// Attach a memory service for persisting the node display order
+    pub fn with_memory_service(mut self, memory_service: Arc<MemoryService>) -> Self {
+        self.memory_service = Some(memory_service);
+        self
+    }
+
+    /// Get current node list in display order
     pub async fn get_nodes(&self) -> Vec<ManagedNode> {
-        if self.use_real_nodes.load(Ordering::SeqCst) {
+        let nodes = if self.use_real_nodes.load(Ordering::SeqCst) {
             let nodes = self.nodes.read().await;
             nodes.clone()
         } else {
             self.demo_nodes.clone()
+        };
+
+        let order = self.display_order.read().await;
+        sort_nodes(nodes, order.as_deref())
+    }
+
+    /// Move the node at `from_idx` to `to_idx` and persist the custom order
+    ///
+    /// Indices refer to the list returned by [`get_nodes`](Self::get_nodes).
+    /// The order is a local display preference only; trust levels and
+    /// federation state are left untouched.
+    pub async fn reorder_nodes(&self, from_idx: usize, to_idx: usize) -> Result<(), String> {
+        let nodes = self.get_nodes().await;
+        if from_idx >= nodes.len() || to_idx >= nodes.len() {
+            return Err(format!(
+                "Invalid reorder indices {} -> {} ({} nodes)",
+                from_idx,
+                to_idx,
+                nodes.len()
+            ));
         }
+
+        let mut ids: Vec<String> = nodes.into_iter().map(|n| n.id).collect();
+        let moved = ids.remove(from_idx);
+        ids.insert(to_idx, moved);
+
+        *self.display_order.write().await = Some(ids.clone());
+        self.state.mark_dirty();
+
+        if let Some(ref memory) = self.memory_service {
+            let value = serde_json::to_vec(&ids)
+                .map_err(|e| format!("Failed to encode node order: {}", e))?;
+            memory
+                .set(
+                    NODE_DISPLAY_ORDER_KEY,
+                    &value,
+                    MemoryDomain::Private,
+                    MemoryCategory::Context,
+                )
+                .await
+                .map_err(|e| format!("Failed to save node order: {}", e))?;
+        }
+
+        Ok(())
+    }
+
+    /// Clear the custom order and revert to the default order
+    pub async fn reset_order(&self) -> Result<(), String> {
+        *self.display_order.write().await = None;
+        self.state.mark_dirty();
+
+        if let Some(ref memory) = self.memory_service {
+            memory
+                .delete(NODE_DISPLAY_ORDER_KEY)
+                .await
+                .map_err(|e| format!("Failed to reset node order: {}", e))?;
+        }
+
+        Ok(())
+    }
+
+    /// Load a previously saved custom order from the memory service
+    pub async fn load_display_order(&self) -> Result<(), String> {
+        let Some(ref memory) = self.memory_service else {
+            return Ok(());
+        };
+
+        let item = memory
+            .get(NODE_DISPLAY_ORDER_KEY)
+            .await
+            .map_err(|e| format!("Failed to load node order: {}", e))?;
+
+        if let Some(item) = item {
+            let ids: Vec<String> = serde_json::from_slice(&item.value)
+                .map_err(|e| format!("Invalid node order: {}", e))?;
+            *self.display_order.write().await = Some(ids);
+            self.state.mark_dirty();
+        }
+
+        Ok(())
+    }
+
+    /// Whether a custom display order is active
+    pub async fn has_custom_order(&self) -> bool {
+        self.display_order.read().await.is_some()
     }
 
     /// Check for refresh results from background task
@@ -342,12 +463,124 @@ impl NodeViewModel {
         }
     }
 
+    /// Open a remote Agent session to a node through an SSH tunnel
+    ///
+    /// The tunnel goes to the node's stored endpoint and authenticates with
+    /// the local DID Ed25519 key.
+    pub async fn connect(&self, node_id: &str) -> Result<RemoteSession, String> {
+        let node = self
+            .get_nodes()
+            .await
+            .into_iter()
+            .find(|n| n.id == node_id)
+            .ok_or_else(|| format!("Node not found: {}", node_id))?;
+        let did = node
+            .did
+            .clone()
+            .ok_or_else(|| format!("Node {} has no DID", node_id))?;
+
+        info!("Connecting to node {} ({})", node_id, node.address);
+        self.set_connection_state(node_id, ConnectionState::Connecting).await;
+
+        let result: Result<RemoteSession, String> = async {
+            let endpoint = SshEndpoint::parse(&node.address).map_err(|e| e.to_string())?;
+            let identity = load_local_identity()?;
+
+            let tunnel = SshTunnel::open(&endpoint, &identity)
+                .await
+                .map_err(|e| format!("Failed to open SSH tunnel: {}", e))?;
+
+            let mut session = RemoteSession::new(did, endpoint.host.clone());
+            session
+                .connect_via_ssh(tunnel)
+                .await
+                .map_err(|e| format!("Failed to connect to node: {}", e))?;
+            Ok(session)
+        }
+        .await;
+
+        let state = match &result {
+            Ok(session) if session.state == SessionState::Connected => ConnectionState::Connected,
+            _ => ConnectionState::Disconnected,
+        };
+        self.set_connection_state(node_id, state).await;
+
+        result
+    }
+
+    /// Close a remote Agent session opened by [`connect`](Self::connect)
+    pub async fn disconnect(&self, node_id: &str, mut session: RemoteSession) {
+        info!("Disconnecting from node {}", node_id);
+        session.disconnect().await;
+        self.set_connection_state(node_id, ConnectionState::Disconnected).await;
+    }
+
+    /// Get the remote Agent connection state of a node
+    pub async fn connection_state(&self, node_id: &str) -> ConnectionState {
+        self.connections
+            .read()
+            .await
+            .get(node_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    async fn set_connection_state(&self, node_id: &str, state: ConnectionState) {
+        let mut connections = self.connections.write().await;
+        if state == ConnectionState::Disconnected {
+            connections.remove(node_id);
+        } else {
+            connections.insert(node_id.to_string(), state);
+        }
+        self.state.mark_dirty();
+    }
+
     /// Get demo nodes
     pub fn demo_nodes(&self) -> &[ManagedNode] {
         &self.demo_nodes
     }
 }
 
+/// Load (or create) the local DID identity used for SSH authentication
+fn load_local_identity() -> Result<DIDManager, String> {
+    let path = Paths::data_dir().join("identity").join("node.did");
+    DIDManager::load_or_generate(&path, "cis-gui")
+        .map_err(|e| format!("Failed to load local DID: {}", e))
+}
+
+/// Rank used for the default order (higher trust first)
+fn trust_rank(state: TrustState) -> u8 {
+    match state {
+        TrustState::Verified => 3,
+        TrustState::Pending => 2,
+        TrustState::Unknown => 1,
+        TrustState::Blocked => 0,
+    }
+}
+
+/// Sort nodes by trust level (descending) then name, then apply a custom order
+///
+/// Nodes missing from the custom order keep their default relative order and
+/// are placed after the ordered ones.
+fn sort_nodes(mut nodes: Vec<ManagedNode>, order: Option<&[String]>) -> Vec<ManagedNode> {
+    nodes.sort_by(|a, b| {
+        trust_rank(b.trust_state)
+            .cmp(&trust_rank(a.trust_state))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    if let Some(order) = order {
+        nodes.sort_by_key(|n| {
+            order
+                .iter()
+                .position(|id| id == &n.id)
+                .unwrap_or(usize::MAX)
+        });
+    }
+
+    nodes
+}
+
 impl ViewModel for NodeViewModel {
     fn name(&self) -> &str {
         "NodeViewModel"
@@ -389,4 +622,75 @@ mod tests {
         let nodes = vm.get_nodes().await;
         assert_eq!(nodes.len(), 4); // Should have 4 demo nodes
     }
+
+    fn node_ids(nodes: &[ManagedNode]) -> Vec<&str> {
+        nodes.iter().map(|n| n.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_default_order_by_trust_then_name() {
+        let vm = NodeViewModel::new(None, tokio::runtime::Handle::current());
+        let nodes = vm.get_nodes().await;
+        assert_eq!(node_ids(&nodes), vec!["hugin", "munin", "seed", "unknown"]);
+    }
+
+    #[tokio::test]
+    async fn test_reorder_and_reset_nodes() {
+        let vm = NodeViewModel::new(None, tokio::runtime::Handle::current());
+
+        vm.reorder_nodes(3, 0).await.unwrap();
+        let nodes = vm.get_nodes().await;
+        assert_eq!(node_ids(&nodes), vec!["unknown", "hugin", "munin", "seed"]);
+        assert!(vm.has_custom_order().await);
+
+        vm.reorder_nodes(1, 2).await.unwrap();
+        let nodes = vm.get_nodes().await;
+        assert_eq!(node_ids(&nodes), vec!["unknown", "munin", "hugin", "seed"]);
+
+        // Reordering is display-only
+        assert!(nodes.iter().any(|n| n.id == "unknown" && n.trust_state == TrustState::Pending));
+
+        vm.reset_order().await.unwrap();
+        let nodes = vm.get_nodes().await;
+        assert_eq!(node_ids(&nodes), vec!["hugin", "munin", "seed", "unknown"]);
+        assert!(!vm.has_custom_order().await);
+    }
+
+    #[tokio::test]
+    async fn test_reorder_out_of_range() {
+        let vm = NodeViewModel::new(None, tokio::runtime::Handle::current());
+        assert!(vm.reorder_nodes(0, 10).await.is_err());
+        assert!(!vm.has_custom_order().await);
+    }
+
+    #[tokio::test]
+    async fn test_connect_requires_did() {
+        let vm = NodeViewModel::new(None, tokio::runtime::Handle::current());
+
+        let err = vm.connect("unknown").await.unwrap_err();
+        assert!(err.contains("no DID"));
+        assert!(vm.connect("missing").await.is_err());
+        assert_eq!(vm.connection_state("unknown").await, ConnectionState::Disconnected);
+    }
+
+    #[tokio::test]
+    async fn test_connection_state_tracking() {
+        let vm = NodeViewModel::new(None, tokio::runtime::Handle::current());
+        assert_eq!(vm.connection_state("munin").await, ConnectionState::Disconnected);
+
+        vm.set_connection_state("munin", ConnectionState::Connecting).await;
+        assert_eq!(vm.connection_state("munin").await, ConnectionState::Connecting);
+
+        vm.disconnect("munin", RemoteSession::new("did:cis:munin:abc123", "192.168.1.100"))
+            .await;
+        assert_eq!(vm.connection_state("munin").await, ConnectionState::Disconnected);
+    }
+
+    #[test]
+    fn test_sort_nodes_keeps_unlisted_nodes_last() {
+        let vm = NodeViewModel::new(None, tokio::runtime::Runtime::new().unwrap().handle().clone());
+        let order = vec!["seed".to_string(), "munin".to_string()];
+        let nodes = sort_nodes(vm.demo_nodes().to_vec(), Some(&order));
+        assert_eq!(node_ids(&nodes), vec!["seed", "munin", "hugin", "unknown"]);
+    }
 }
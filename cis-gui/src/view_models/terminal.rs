@@ -6,17 +6,186 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 
-use cis_core::types::TaskLevel;
+use cis_core::memory::MemoryService;
+use cis_core::network::NetworkAcl;
+use cis_core::types::{MemoryCategory, MemoryDomain, TaskLevel};
 use cis_core::service::{NodeService, DagService, ListOptions};
 use cis_core::service::node_service::{BindOptions, TrustLevel};
 
 use crate::decision_panel::PendingDecision;
+use crate::remote_session::{RemoteSession, SessionState};
 use super::{ViewModel, ViewModelState};
 use super::{NodeViewModel, DecisionViewModel};
 
+/// Memory key holding the persisted terminal pane layout
+pub const TERMINAL_PANES_KEY: &str = "terminal_pane_layout";
+
+/// Default split ratio for new panes
+pub const DEFAULT_SPLIT_RATIO: f32 = 0.5;
+
+/// Split ratio bounds (keeps both panes visible)
+pub const MIN_SPLIT_RATIO: f32 = 0.1;
+pub const MAX_SPLIT_RATIO: f32 = 0.9;
+
+/// Maximum output lines kept per remote pane
+const MAX_PANE_OUTPUT_LINES: usize = 1000;
+
+/// Terminal pane identifier
+pub type PaneId = u32;
+
+/// Split direction for a new pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SplitDirection {
+    /// Side by side (left | right)
+    Horizontal,
+    /// Stacked (top / bottom)
+    Vertical,
+}
+
+/// Remote Agent session target of a pane
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaneRemote {
+    pub target_did: String,
+    pub target_addr: String,
+}
+
+/// State of a single terminal pane
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaneState {
+    pub id: PaneId,
+    /// How this pane was split off from the preceding panes (`None` for the first pane)
+    pub split: Option<SplitDirection>,
+    /// Share of the space kept by the preceding panes
+    pub ratio: f32,
+    /// Remote Agent session target, `None` for the local terminal
+    pub remote: Option<PaneRemote>,
+}
+
+impl PaneState {
+    fn new(id: PaneId, split: Option<SplitDirection>) -> Self {
+        Self {
+            id,
+            split,
+            ratio: DEFAULT_SPLIT_RATIO,
+            remote: None,
+        }
+    }
+
+    /// Display title of the pane
+    pub fn title(&self) -> String {
+        match &self.remote {
+            Some(remote) => remote.target_did.clone(),
+            None => "Local".to_string(),
+        }
+    }
+}
+
+/// Pane layout (persisted)
+///
+/// Panes form a chain: pane `n` splits the area left after panes `0..n`
+/// in its `split` direction, with `ratio` kept by the preceding panes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaneLayout {
+    pub panes: Vec<PaneState>,
+    pub active: PaneId,
+    next_id: PaneId,
+}
+
+impl Default for PaneLayout {
+    fn default() -> Self {
+        Self {
+            panes: vec![PaneState::new(0, None)],
+            active: 0,
+            next_id: 1,
+        }
+    }
+}
+
+impl PaneLayout {
+    fn index_of(&self, id: PaneId) -> Option<usize> {
+        self.panes.iter().position(|p| p.id == id)
+    }
+
+    /// Get a pane by ID
+    pub fn pane(&self, id: PaneId) -> Option<&PaneState> {
+        self.panes.iter().find(|p| p.id == id)
+    }
+
+    /// Split the active pane, inserting and focusing the new pane after it
+    pub fn split(&mut self, direction: SplitDirection) -> PaneId {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let index = self.index_of(self.active).map(|i| i + 1).unwrap_or(self.panes.len());
+        self.panes.insert(index, PaneState::new(id, Some(direction)));
+        self.active = id;
+        id
+    }
+
+    /// Close a pane; the last remaining pane cannot be closed
+    pub fn close(&mut self, id: PaneId) -> bool {
+        if self.panes.len() <= 1 {
+            return false;
+        }
+        let Some(index) = self.index_of(id) else {
+            return false;
+        };
+
+        self.panes.remove(index);
+        if index == 0 {
+            // The new first pane owns the whole area
+            self.panes[0].split = None;
+            self.panes[0].ratio = DEFAULT_SPLIT_RATIO;
+        }
+
+        if self.active == id {
+            let index = index.min(self.panes.len() - 1);
+            self.active = self.panes[index].id;
+        }
+        true
+    }
+
+    /// Focus a pane
+    pub fn focus(&mut self, id: PaneId) -> bool {
+        if self.index_of(id).is_some() {
+            self.active = id;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Focus the next pane (wraps around)
+    pub fn focus_next(&mut self) {
+        if let Some(index) = self.index_of(self.active) {
+            self.active = self.panes[(index + 1) % self.panes.len()].id;
+        }
+    }
+
+    /// Focus the previous pane (wraps around)
+    pub fn focus_prev(&mut self) {
+        if let Some(index) = self.index_of(self.active) {
+            let len = self.panes.len();
+            self.active = self.panes[(index + len - 1) % len].id;
+        }
+    }
+
+    /// Set the split ratio of a pane (clamped)
+    pub fn set_ratio(&mut self, id: PaneId, ratio: f32) -> bool {
+        match self.panes.iter_mut().find(|p| p.id == id) {
+            Some(pane) if pane.split.is_some() => {
+                pane.ratio = ratio.clamp(MIN_SPLIT_RATIO, MAX_SPLIT_RATIO);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
 /// Terminal ViewModel
 ///
 /// Responsibilities:
@@ -41,6 +210,18 @@ pub struct TerminalViewModel {
 
     /// View model state
     state: ViewModelState,
+
+    /// Terminal pane layout
+    panes: Arc<RwLock<PaneLayout>>,
+
+    /// Remote Agent sessions by pane
+    sessions: Arc<RwLock<HashMap<PaneId, RemoteSession>>>,
+
+    /// Remote output lines by pane
+    pane_output: Arc<RwLock<HashMap<PaneId, Vec<String>>>>,
+
+    /// Memory service used to persist the pane layout
+    memory_service: Option<Arc<MemoryService>>,
 }
 
 impl TerminalViewModel {
@@ -68,9 +249,209 @@ impl TerminalViewModel {
             dag_service,
             runtime_handle,
             state: ViewModelState::new(),
+            panes: Arc::new(RwLock::new(PaneLayout::default())),
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+            pane_output: Arc::new(RwLock::new(HashMap::new())),
+            memory_service: None,
         }
     }
 
+    /// Attach a memory service for persisting the pane layout
+    pub fn with_memory_service(mut self, memory_service: Arc<MemoryService>) -> Self {
+        self.memory_service = Some(memory_service);
+        self
+    }
+
+    /// Get the current pane layout
+    pub async fn get_panes(&self) -> PaneLayout {
+        self.panes.read().await.clone()
+    }
+
+    /// Split the active pane and return the new pane's ID
+    pub async fn split_pane(&self, direction: SplitDirection) -> PaneId {
+        let id = self.panes.write().await.split(direction);
+        info!("Split terminal pane {:?}, new pane {}", direction, id);
+        self.state.mark_dirty();
+        self.save_panes().await;
+        id
+    }
+
+    /// Close a pane and disconnect its remote session
+    pub async fn close_pane(&self, id: PaneId) -> bool {
+        if !self.panes.write().await.close(id) {
+            return false;
+        }
+
+        if let Some(mut session) = self.sessions.write().await.remove(&id) {
+            session.disconnect().await;
+        }
+        self.pane_output.write().await.remove(&id);
+
+        self.state.mark_dirty();
+        self.save_panes().await;
+        true
+    }
+
+    /// Focus a pane
+    pub async fn focus_pane(&self, id: PaneId) {
+        if self.panes.write().await.focus(id) {
+            self.state.mark_dirty();
+            self.save_panes().await;
+        }
+    }
+
+    /// Focus the next pane
+    pub async fn focus_next_pane(&self) {
+        self.panes.write().await.focus_next();
+        self.state.mark_dirty();
+        self.save_panes().await;
+    }
+
+    /// Focus the previous pane
+    pub async fn focus_prev_pane(&self) {
+        self.panes.write().await.focus_prev();
+        self.state.mark_dirty();
+        self.save_panes().await;
+    }
+
+    /// Update a pane's split ratio (call [`save_panes`](Self::save_panes) when done)
+    pub async fn set_split_ratio(&self, id: PaneId, ratio: f32) {
+        if self.panes.write().await.set_ratio(id, ratio) {
+            self.state.mark_dirty();
+        }
+    }
+
+    /// Connect a pane to a remote Agent session
+    pub async fn connect_pane(
+        &self,
+        id: PaneId,
+        target_did: &str,
+        target_addr: &str,
+        acl: Arc<RwLock<NetworkAcl>>,
+    ) -> Result<(), String> {
+        {
+            let mut panes = self.panes.write().await;
+            let pane = panes
+                .panes
+                .iter_mut()
+                .find(|p| p.id == id)
+                .ok_or_else(|| format!("Pane not found: {}", id))?;
+            pane.remote = Some(PaneRemote {
+                target_did: target_did.to_string(),
+                target_addr: target_addr.to_string(),
+            });
+        }
+        self.save_panes().await;
+
+        let mut sessions = self.sessions.write().await;
+        if let Some(mut old) = sessions.remove(&id) {
+            old.disconnect().await;
+        }
+
+        let mut session = RemoteSession::new(target_did, target_addr);
+        let result = session
+            .connect(acl)
+            .await
+            .map_err(|e| format!("Failed to connect pane {}: {}", id, e));
+        sessions.insert(id, session);
+        self.state.mark_dirty();
+        result
+    }
+
+    /// Get the remote session state of a pane (`None` for local panes)
+    pub async fn pane_session_state(&self, id: PaneId) -> Option<SessionState> {
+        let panes = self.panes.read().await;
+        panes.pane(id)?.remote.as_ref()?;
+        let sessions = self.sessions.read().await;
+        Some(
+            sessions
+                .get(&id)
+                .map(|s| s.state)
+                .unwrap_or(SessionState::Disconnected),
+        )
+    }
+
+    /// Collect pending output from all remote sessions
+    pub async fn poll_pane_output(&self) {
+        let mut sessions = self.sessions.write().await;
+        let mut output = self.pane_output.write().await;
+
+        for (id, session) in sessions.iter_mut() {
+            while let Some(data) = session.try_receive_output() {
+                let lines = output.entry(*id).or_default();
+                lines.extend(String::from_utf8_lossy(&data).lines().map(str::to_string));
+                if lines.len() > MAX_PANE_OUTPUT_LINES {
+                    let excess = lines.len() - MAX_PANE_OUTPUT_LINES;
+                    lines.drain(..excess);
+                }
+                self.state.mark_dirty();
+            }
+        }
+    }
+
+    /// Get output lines of a remote pane
+    pub async fn pane_output(&self, id: PaneId) -> Vec<String> {
+        self.pane_output.read().await.get(&id).cloned().unwrap_or_default()
+    }
+
+    /// Persist the pane layout to memory
+    pub async fn save_panes(&self) {
+        let Some(ref memory) = self.memory_service else {
+            return;
+        };
+
+        let layout = self.panes.read().await.clone();
+        let result = match serde_json::to_vec(&layout) {
+            Ok(value) => memory
+                .set(TERMINAL_PANES_KEY, &value, MemoryDomain::Private, MemoryCategory::Context)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        if let Err(e) = result {
+            warn!("Failed to save terminal panes: {}", e);
+        }
+    }
+
+    /// Restore the pane layout from memory
+    ///
+    /// Remote panes are restored disconnected; reconnecting is up to the user.
+    pub async fn load_panes(&self) -> Result<(), String> {
+        let Some(ref memory) = self.memory_service else {
+            return Ok(());
+        };
+
+        let item = memory
+            .get(TERMINAL_PANES_KEY)
+            .await
+            .map_err(|e| format!("Failed to load terminal panes: {}", e))?;
+
+        if let Some(item) = item {
+            let layout: PaneLayout = serde_json::from_slice(&item.value)
+                .map_err(|e| format!("Invalid terminal pane layout: {}", e))?;
+            if layout.panes.is_empty() {
+                return Ok(());
+            }
+
+            let mut sessions = self.sessions.write().await;
+            sessions.clear();
+            for pane in &layout.panes {
+                if let Some(remote) = &pane.remote {
+                    sessions.insert(
+                        pane.id,
+                        RemoteSession::new(&remote.target_did, &remote.target_addr),
+                    );
+                }
+            }
+
+            *self.panes.write().await = layout;
+            self.state.mark_dirty();
+        }
+
+        Ok(())
+    }
+
     /// Get terminal history
     pub async fn get_history(&self) -> Vec<String> {
         self.history.read().await.clone()
@@ -266,6 +647,7 @@ impl TerminalViewModel {
                                 CoreNodeStatus::Offline => "○ offline",
                                 CoreNodeStatus::Blacklisted => "✗ blacklisted",
                                 CoreNodeStatus::Suspicious => "⚠ suspicious",
+                                CoreNodeStatus::Stale => "◌ stale",
                                 CoreNodeStatus::Unknown => "? unknown",
                             };
                             let name = if node.name.len() > 20 {
@@ -671,4 +1053,78 @@ mod tests {
         let history = terminal_vm.get_history().await;
         assert_eq!(history.len(), 3);
     }
+
+    #[tokio::test]
+    async fn test_split_and_close_panes() {
+        let handle = tokio::runtime::Handle::current();
+        let node_vm = Arc::new(NodeViewModel::new(None, handle.clone()));
+        let decision_vm = Arc::new(DecisionViewModel::new());
+        let terminal_vm = TerminalViewModel::new(node_vm, decision_vm, None, None, handle);
+
+        let first = terminal_vm.get_panes().await.active;
+        let right = terminal_vm.split_pane(SplitDirection::Horizontal).await;
+        let below = terminal_vm.split_pane(SplitDirection::Vertical).await;
+
+        let layout = terminal_vm.get_panes().await;
+        let ids: Vec<PaneId> = layout.panes.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![first, right, below]);
+        assert_eq!(layout.active, below);
+        assert_eq!(layout.pane(right).unwrap().split, Some(SplitDirection::Horizontal));
+        assert_eq!(terminal_vm.pane_session_state(right).await, None);
+
+        assert!(terminal_vm.close_pane(below).await);
+        assert_eq!(terminal_vm.get_panes().await.active, right);
+
+        terminal_vm.focus_next_pane().await;
+        assert_eq!(terminal_vm.get_panes().await.active, first);
+    }
+
+    #[test]
+    fn test_pane_layout_close_first_and_last() {
+        let mut layout = PaneLayout::default();
+        assert!(!layout.close(0));
+
+        let second = layout.split(SplitDirection::Vertical);
+        assert!(layout.set_ratio(second, 0.95));
+        assert_eq!(layout.pane(second).unwrap().ratio, MAX_SPLIT_RATIO);
+
+        assert!(layout.close(0));
+        assert_eq!(layout.panes.len(), 1);
+        assert_eq!(layout.panes[0].split, None);
+        assert_eq!(layout.active, second);
+        assert!(!layout.set_ratio(second, 0.3));
+    }
+
+    #[test]
+    fn test_pane_layout_focus_wraps() {
+        let mut layout = PaneLayout::default();
+        let a = layout.split(SplitDirection::Horizontal);
+        let b = layout.split(SplitDirection::Horizontal);
+
+        layout.focus_next();
+        assert_eq!(layout.active, 0);
+        layout.focus_prev();
+        assert_eq!(layout.active, b);
+        assert!(layout.focus(a));
+        assert!(!layout.focus(42));
+    }
+
+    #[test]
+    fn test_pane_layout_serde_roundtrip() {
+        let mut layout = PaneLayout::default();
+        let id = layout.split(SplitDirection::Vertical);
+        layout.set_ratio(id, 0.3);
+        layout.panes[1].remote = Some(PaneRemote {
+            target_did: "did:cis:hugin:def456".to_string(),
+            target_addr: "192.168.1.105".to_string(),
+        });
+
+        let json = serde_json::to_vec(&layout).unwrap();
+        let restored: PaneLayout = serde_json::from_slice(&json).unwrap();
+        assert_eq!(restored, layout);
+
+        // next_id survives so new panes don't collide
+        let mut restored = restored;
+        assert_ne!(restored.split(SplitDirection::Horizontal), id);
+    }
 }
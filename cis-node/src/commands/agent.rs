@@ -4,16 +4,223 @@
 
 use anyhow::{Context, Result};
 use cis_core::agent::{
-    AgentContext, AgentProvider, AgentProviderFactory, AgentRequest,
+    AgentConfig, AgentContext, AgentMessage, AgentProvider, AgentProviderFactory, AgentRequest,
+    AgentType, MessageRole,
 };
 use cis_core::storage::paths::Paths;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
+/// `--system-prompt` / `--system-prompt-file` for a single invocation
+///
+/// Both support the `{{date}}`, `{{cwd}}` and `{{git_branch}}` template
+/// variables, expanded right before the request is built.
+#[derive(Debug, Clone, Default)]
+pub struct SystemPromptArgs {
+    /// Inline system prompt text
+    pub text: Option<String>,
+    /// Path to a prompt file, or the name of a saved prompt
+    pub file: Option<PathBuf>,
+}
+
+impl SystemPromptArgs {
+    /// Raw (unexpanded) prompt template, if either flag was given
+    pub fn template(&self, store: &PromptStore) -> Result<Option<String>> {
+        match (&self.text, &self.file) {
+            (Some(_), Some(_)) => {
+                anyhow::bail!("--system-prompt and --system-prompt-file cannot be used together")
+            }
+            (Some(text), None) => Ok(Some(text.clone())),
+            (None, Some(file)) => read_prompt_file(file, store).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+/// Read a prompt file, falling back to a saved prompt of the same name
+fn read_prompt_file(file: &Path, store: &PromptStore) -> Result<String> {
+    if file.exists() {
+        return std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read system prompt file {}", file.display()));
+    }
+    file.to_str()
+        .and_then(|name| store.load(name).ok().flatten())
+        .with_context(|| format!("System prompt file or saved prompt not found: {}", file.display()))
+}
+
+/// Expand `{{date}}`, `{{cwd}}` and `{{git_branch}}` in a prompt template
+pub fn expand_prompt_template(template: &str, work_dir: &Path) -> String {
+    let mut prompt = template
+        .replace("{{date}}", &chrono::Local::now().format("%Y-%m-%d").to_string())
+        .replace("{{cwd}}", &work_dir.display().to_string());
+    if prompt.contains("{{git_branch}}") {
+        let branch = git_branch(work_dir).unwrap_or_else(|| "unknown".to_string());
+        prompt = prompt.replace("{{git_branch}}", &branch);
+    }
+    prompt
+}
+
+fn git_branch(work_dir: &Path) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(work_dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!branch.is_empty()).then_some(branch)
+}
+
+/// Names of saved prompts and chat sessions double as file names
+fn validate_name(kind: &str, name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        anyhow::bail!("Invalid {} name '{}': use letters, digits, '-' or '_'", kind, name);
+    }
+    Ok(())
+}
+
+/// Saved system prompts and the last prompt used by each session
+pub struct PromptStore {
+    root: PathBuf,
+}
+
+impl PromptStore {
+    /// Store under `<data_dir>/prompts`
+    pub fn open_default() -> Self {
+        Self::new(Paths::data_dir().join("prompts"))
+    }
+
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn prompt_path(&self, name: &str) -> Result<PathBuf> {
+        validate_name("prompt", name)?;
+        Ok(self.root.join(format!("{}.md", name)))
+    }
+
+    fn session_path(&self, session_id: &str) -> PathBuf {
+        let file: String = session_id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        self.root.join("sessions").join(format!("{}.md", file))
+    }
+
+    /// Names of saved prompts, sorted
+    pub fn list(&self) -> Result<Vec<String>> {
+        if !self.root.exists() {
+            return Ok(vec![]);
+        }
+        let mut names = vec![];
+        for entry in std::fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("md") {
+                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                    names.push(stem.to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    pub fn load(&self, name: &str) -> Result<Option<String>> {
+        let path = self.prompt_path(name)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(std::fs::read_to_string(path)?))
+    }
+
+    pub fn save(&self, name: &str, content: &str) -> Result<()> {
+        let path = self.prompt_path(name)?;
+        std::fs::create_dir_all(&self.root)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Returns whether the prompt existed
+    pub fn delete(&self, name: &str) -> Result<bool> {
+        let path = self.prompt_path(name)?;
+        if !path.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_file(path)?;
+        Ok(true)
+    }
+
+    /// Last prompt template used by a session
+    pub fn session_prompt(&self, session_id: &str) -> Result<Option<String>> {
+        let path = self.session_path(session_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(std::fs::read_to_string(path)?))
+    }
+
+    pub fn set_session_prompt(&self, session_id: &str, template: &str) -> Result<()> {
+        let path = self.session_path(session_id);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, template)?;
+        Ok(())
+    }
+}
+
+/// `cis agent prompts list`
+pub fn list_prompts() -> Result<()> {
+    let store = PromptStore::open_default();
+    let names = store.list()?;
+    if names.is_empty() {
+        println!("No saved prompts. Use 'cis agent prompts save <name> <file>' to add one.");
+        return Ok(());
+    }
+
+    println!("Saved prompts:");
+    for name in names {
+        let first_line = store
+            .load(&name)?
+            .and_then(|p| p.lines().find(|l| !l.trim().is_empty()).map(str::to_string))
+            .unwrap_or_default();
+        println!("  {:<24} {}", name, first_line);
+    }
+    Ok(())
+}
+
+/// `cis agent prompts save <name> <file>`
+pub fn save_prompt(name: &str, file: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    PromptStore::open_default().save(name, &content)?;
+    println!("✅ Saved prompt '{}'", name);
+    Ok(())
+}
+
+/// `cis agent prompts delete <name>`
+pub fn delete_prompt(name: &str) -> Result<()> {
+    if PromptStore::open_default().delete(name)? {
+        println!("✅ Deleted prompt '{}'", name);
+    } else {
+        println!("Prompt '{}' not found", name);
+    }
+    Ok(())
+}
+
 /// Execute a prompt with the AI agent
-pub async fn execute_prompt(prompt: &str) -> Result<()> {
+pub async fn execute_prompt(prompt: &str, system_prompt: &SystemPromptArgs) -> Result<()> {
     info!("Executing prompt with AI agent...");
     
+    let work_dir = std::env::current_dir()?;
+    let system_prompt = system_prompt
+        .template(&PromptStore::open_default())?
+        .map(|t| expand_prompt_template(&t, &work_dir));
+    
     // Get default provider
     let provider = AgentProviderFactory::default_provider().await
         .context("No AI agent available. Please install Claude Code, Kimi, or Aider.")?;
@@ -25,9 +232,9 @@ pub async fn execute_prompt(prompt: &str) -> Result<()> {
     let request = AgentRequest {
         prompt: prompt.to_string(),
         context: AgentContext::new()
-            .with_work_dir(std::env::current_dir()?),
+            .with_work_dir(work_dir),
         skills: vec![],
-        system_prompt: None,
+        system_prompt,
         history: vec![],
     };
     
@@ -123,60 +330,409 @@ pub async fn list_agents() -> Result<()> {
     Ok(())
 }
 
+/// Show AI usage and cost by provider and session
+pub async fn show_cost(session: Option<&str>) -> Result<()> {
+    use cis_core::ai::{CostUsage, SessionCostTracker};
+    use cis_core::memory::MemoryService;
+    use std::collections::BTreeMap;
+
+    let service = MemoryService::open_default(format!("node-{}", uuid::Uuid::new_v4()))?;
+    let trackers: Vec<SessionCostTracker> = match session {
+        Some(id) => vec![SessionCostTracker::load(&service, id).await?],
+        None => SessionCostTracker::load_all(&service).await?,
+    };
+
+    let trackers: Vec<_> = trackers.into_iter().filter(|t| !t.records.is_empty()).collect();
+    if trackers.is_empty() {
+        println!("No AI usage recorded.");
+        return Ok(());
+    }
+
+    let mut by_provider: BTreeMap<String, CostUsage> = BTreeMap::new();
+    let mut total = CostUsage::default();
+
+    println!("By Session:");
+    println!("{:<36} {:>10} {:>12} {:>10}", "Session", "Prompt", "Completion", "Cost");
+    println!("{}", "-".repeat(71));
+    for tracker in &trackers {
+        let usage = tracker.total();
+        println!(
+            "{:<36} {:>10} {:>12} {:>10}",
+            tracker.session_id,
+            usage.prompt_tokens,
+            usage.completion_tokens,
+            format!("${:.4}", usage.total_cost_usd)
+        );
+
+        for (provider, usage) in tracker.by_provider() {
+            *by_provider.entry(provider).or_default() += usage;
+        }
+        total += usage;
+    }
+
+    println!();
+    println!("By Provider:");
+    println!("{:<36} {:>10} {:>12} {:>10}", "Provider", "Prompt", "Completion", "Cost");
+    println!("{}", "-".repeat(71));
+    for (provider, usage) in &by_provider {
+        println!(
+            "{:<36} {:>10} {:>12} {:>10}",
+            provider,
+            usage.prompt_tokens,
+            usage.completion_tokens,
+            format!("${:.4}", usage.total_cost_usd)
+        );
+    }
+
+    println!();
+    println!(
+        "Total: {} tokens, ${:.4}",
+        total.total_tokens(),
+        total.total_cost_usd
+    );
+
+    Ok(())
+}
+
+/// Completions offered for `!`-prefixed input in `cis agent chat`
+const CHAT_COMPLETIONS: &[&str] = &[
+    "!clear",
+    "!help",
+    "!save ",
+    "!load ",
+    "!provider claude",
+    "!provider opencode",
+    "!provider kimi",
+    "!provider aider",
+    "!memory get ",
+    "!memory set ",
+    "!memory search ",
+    "!memory list",
+    "!dag status ",
+    "!dag list",
+    "!dag run ",
+    "!task list",
+    "!skill list",
+];
+
+/// A `!`-prefixed line entered in `cis agent chat`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChatCommand {
+    /// `!clear` - wipe the conversation context
+    Clear,
+    /// `!save <name>` - save the session
+    Save(String),
+    /// `!load <name>` - load a saved session
+    Load(String),
+    /// `!provider <name>` - switch AI provider
+    Provider(String),
+    /// `!help`
+    Help,
+    /// Any other `!` line, run as a `cis` subcommand (e.g. `!dag status`)
+    Cis(Vec<String>),
+}
+
+impl ChatCommand {
+    /// Parse a chat line; `Ok(None)` if it is not a `!` command
+    pub fn parse(input: &str) -> Result<Option<Self>> {
+        let Some(rest) = input.trim().strip_prefix('!') else {
+            return Ok(None);
+        };
+        let mut words = rest.split_whitespace();
+        let Some(name) = words.next() else {
+            anyhow::bail!("Empty command. Type !help for a list of commands.");
+        };
+        let mut arg = |usage: &str| {
+            words
+                .next()
+                .map(str::to_string)
+                .with_context(|| format!("Usage: {}", usage))
+        };
+
+        let command = match name {
+            "clear" => Self::Clear,
+            "help" => Self::Help,
+            "save" => Self::Save(arg("!save <name>")?),
+            "load" => Self::Load(arg("!load <name>")?),
+            "provider" => Self::Provider(arg("!provider <name>")?),
+            _ => Self::Cis(rest.split_whitespace().map(str::to_string).collect()),
+        };
+        Ok(Some(command))
+    }
+}
+
+/// Chat completions matching the text before the cursor
+fn complete_chat_command(prefix: &str) -> Vec<&'static str> {
+    if !prefix.starts_with('!') {
+        return vec![];
+    }
+    CHAT_COMPLETIONS
+        .iter()
+        .copied()
+        .filter(|c| c.starts_with(prefix) && *c != prefix)
+        .collect()
+}
+
+/// rustyline helper providing tab completion of `!` commands
+struct ChatHelper;
+
+impl rustyline::completion::Completer for ChatHelper {
+    type Candidate = rustyline::completion::Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Self::Candidate>)> {
+        let candidates = complete_chat_command(&line[..pos])
+            .into_iter()
+            .map(|c| rustyline::completion::Pair {
+                display: c.trim_end().to_string(),
+                replacement: c.to_string(),
+            })
+            .collect();
+        Ok((0, candidates))
+    }
+}
+
+impl rustyline::hint::Hinter for ChatHelper {
+    type Hint = String;
+}
+
+impl rustyline::highlight::Highlighter for ChatHelper {}
+
+impl rustyline::validate::Validator for ChatHelper {}
+
+impl rustyline::Helper for ChatHelper {}
+
+/// A saved `cis agent chat` session
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ChatSession {
+    /// Name of the provider in use when the session was saved
+    pub provider: String,
+    /// Conversation history
+    pub history: Vec<AgentMessage>,
+    /// Tokens used so far
+    #[serde(default)]
+    pub tokens: u64,
+}
+
+/// Saved chat sessions under `<data_dir>/chat_sessions`
+pub struct ChatSessionStore {
+    root: PathBuf,
+}
+
+impl ChatSessionStore {
+    pub fn open_default() -> Self {
+        Self::new(Paths::data_dir().join("chat_sessions"))
+    }
+
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf> {
+        validate_name("session", name)?;
+        Ok(self.root.join(format!("{}.json", name)))
+    }
+
+    pub fn save(&self, name: &str, session: &ChatSession) -> Result<()> {
+        let path = self.path(name)?;
+        std::fs::create_dir_all(&self.root)?;
+        std::fs::write(path, serde_json::to_string_pretty(session)?)?;
+        Ok(())
+    }
+
+    pub fn load(&self, name: &str) -> Result<Option<ChatSession>> {
+        let path = self.path(name)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let session = serde_json::from_str(&content)
+            .with_context(|| format!("Corrupt chat session {}", path.display()))?;
+        Ok(Some(session))
+    }
+}
+
+/// Readline history file for `cis agent chat`
+fn chat_history_path() -> PathBuf {
+    Paths::data_dir().join("chat_history")
+}
+
+/// Show provider and token count in the terminal title
+fn set_terminal_title(provider: &str, tokens: u64) {
+    use std::io::Write;
+
+    print!("\x1b]0;CIS chat - {} - {} tokens\x07", provider, tokens);
+    let _ = std::io::stdout().flush();
+}
+
+fn print_chat_help() {
+    println!("Chat commands:");
+    println!("  !clear            Wipe the conversation context");
+    println!("  !save <name>      Save this session");
+    println!("  !load <name>      Load a saved session");
+    println!("  !provider <name>  Switch AI provider (claude, opencode, kimi, aider)");
+    println!("  !<cis command>    Run a cis command, e.g. !memory get <key>, !dag status");
+    println!("  exit | quit       End the session");
+}
+
+/// Create an available provider by name
+async fn switch_provider(name: &str) -> Result<Box<dyn AgentProvider>> {
+    let provider_type: AgentType = name.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+    let provider = AgentProviderFactory::create(&AgentConfig {
+        provider_type,
+        ..AgentConfig::default()
+    })?;
+    if !provider.available().await {
+        anyhow::bail!("{} is not installed", provider_type.display_name());
+    }
+    Ok(provider)
+}
+
+/// Run `cis <args>` as a child process, inheriting the terminal
+fn run_cis_command(args: &[String]) -> Result<()> {
+    let exe = std::env::current_exe().context("Failed to locate the cis executable")?;
+    let status = std::process::Command::new(exe).args(args).status()?;
+    if !status.success() {
+        eprintln!("Command exited with {}", status);
+    }
+    Ok(())
+}
+
 /// Start an interactive chat session
-pub async fn interactive_chat() -> Result<()> {
-    use std::io::{self, Write};
-    
-    let provider = AgentProviderFactory::default_provider().await
+///
+/// Uses readline editing with history persisted to `<data_dir>/chat_history`.
+/// Lines starting with `!` are chat commands (see [`ChatCommand`]).
+pub async fn interactive_chat(system_prompt: &SystemPromptArgs) -> Result<()> {
+    use rustyline::error::ReadlineError;
+
+    let work_dir = std::env::current_dir()?;
+    let system_prompt = system_prompt
+        .template(&PromptStore::open_default())?
+        .map(|t| expand_prompt_template(&t, &work_dir));
+
+    let mut provider = AgentProviderFactory::default_provider().await
         .context("No AI agent available.")?;
-    
+    let sessions = ChatSessionStore::open_default();
+
+    let history_path = chat_history_path();
+    let mut editor: rustyline::Editor<ChatHelper, rustyline::history::DefaultHistory> =
+        rustyline::Editor::new()?;
+    editor.set_helper(Some(ChatHelper));
+    // A missing history file just means this is the first session
+    let _ = editor.load_history(&history_path);
+
     println!("🤖 CIS Interactive Chat");
     println!("Using AI agent: {}", provider.name());
-    println!("Type 'exit' or 'quit' to end the session.\n");
-    
-    let mut history = vec![];
-    
+    println!("Type !help for commands, 'exit' or 'quit' to end the session.\n");
+
+    let mut history: Vec<AgentMessage> = vec![];
+    let mut tokens: u64 = 0;
+
     loop {
-        print!("You: ");
-        io::stdout().flush()?;
-        
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        
-        let input = input.trim();
-        
+        set_terminal_title(provider.name(), tokens);
+
+        let line = match editor.readline(&format!("{}> ", provider.name())) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        let input = line.trim();
+
         if input.is_empty() {
             continue;
         }
-        
+        editor.add_history_entry(input)?;
+
         if input.eq_ignore_ascii_case("exit") || input.eq_ignore_ascii_case("quit") {
-            println!("Goodbye! 👋");
             break;
         }
-        
+
+        let command = match ChatCommand::parse(input) {
+            Ok(command) => command,
+            Err(e) => {
+                eprintln!("{}", e);
+                continue;
+            }
+        };
+        if let Some(command) = command {
+            let result = match command {
+                ChatCommand::Clear => {
+                    history.clear();
+                    println!("Context cleared.");
+                    Ok(())
+                }
+                ChatCommand::Help => {
+                    print_chat_help();
+                    Ok(())
+                }
+                ChatCommand::Save(name) => {
+                    let session = ChatSession {
+                        provider: provider.name().to_string(),
+                        history: history.clone(),
+                        tokens,
+                    };
+                    sessions
+                        .save(&name, &session)
+                        .map(|_| println!("✅ Saved session '{}'", name))
+                }
+                ChatCommand::Load(name) => match sessions.load(&name) {
+                    Ok(Some(session)) => {
+                        if session.provider != provider.name() {
+                            match switch_provider(&session.provider).await {
+                                Ok(p) => provider = p,
+                                Err(e) => eprintln!("Keeping {}: {}", provider.name(), e),
+                            }
+                        }
+                        println!("✅ Loaded session '{}' ({} messages)", name, session.history.len());
+                        history = session.history;
+                        tokens = session.tokens;
+                        Ok(())
+                    }
+                    Ok(None) => Err(anyhow::anyhow!("Session '{}' not found", name)),
+                    Err(e) => Err(e),
+                },
+                ChatCommand::Provider(name) => switch_provider(&name).await.map(|p| {
+                    provider = p;
+                    println!("Switched to {}", provider.name());
+                }),
+                ChatCommand::Cis(args) => run_cis_command(&args),
+            };
+            if let Err(e) = result {
+                eprintln!("Error: {}", e);
+            }
+            continue;
+        }
+
         let request = AgentRequest {
             prompt: input.to_string(),
             context: AgentContext::new()
-                .with_work_dir(std::env::current_dir()?),
+                .with_work_dir(work_dir.clone()),
             skills: vec![],
-            system_prompt: None,
+            system_prompt: system_prompt.clone(),
             history: history.clone(),
         };
-        
+
         match provider.execute(request).await {
             Ok(response) => {
                 println!("\nAgent: {}\n", response.content);
-                
-                // Add to history
-                history.push(cis_core::agent::AgentMessage {
-                    role: cis_core::agent::MessageRole::User,
+
+                if let Some(usage) = &response.token_usage {
+                    tokens += u64::from(usage.total);
+                }
+                history.push(AgentMessage {
+                    role: MessageRole::User,
                     content: input.to_string(),
                 });
-                history.push(cis_core::agent::AgentMessage {
-                    role: cis_core::agent::MessageRole::Assistant,
-                    content: response.content.clone(),
+                history.push(AgentMessage {
+                    role: MessageRole::Assistant,
+                    content: response.content,
                 });
-                
+
                 // Limit history size
                 if history.len() > 20 {
                     history.drain(0..2);
@@ -187,7 +743,13 @@ pub async fn interactive_chat() -> Result<()> {
             }
         }
     }
-    
+
+    if let Some(parent) = history_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    editor.save_history(&history_path)?;
+    println!("Goodbye! 👋");
+
     Ok(())
 }
 
@@ -204,6 +766,10 @@ pub struct AgentContextArgs {
     /// Project path
     #[arg(short, long)]
     pub project: Option<PathBuf>,
+    
+    /// System prompt override (remembered for the session)
+    #[arg(skip)]
+    pub system_prompt: SystemPromptArgs,
 }
 
 /// Handle `cis agent context` command - AI conversation with context
@@ -219,6 +785,17 @@ pub async fn handle_agent_context(args: AgentContextArgs) -> Result<()> {
         .map(|p| p.to_path_buf())
         .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
     
+    // An explicit system prompt is remembered for the session and reused on resume
+    let store = PromptStore::open_default();
+    let system_prompt = match args.system_prompt.template(&store)? {
+        Some(template) => {
+            store.set_session_prompt(&session_id, &template)?;
+            Some(template)
+        }
+        None => store.session_prompt(&session_id)?,
+    }
+    .map(|t| expand_prompt_template(&t, &project_path));
+    
     println!("🤖 使用会话上下文: {}", session_id);
     println!("📁 项目路径: {}", project_path.display());
     println!("⏳ 处理请求...\n");
@@ -292,7 +869,7 @@ pub async fn handle_agent_context(args: AgentContextArgs) -> Result<()> {
         context: AgentContext::new()
             .with_work_dir(project_path),
         skills: vec![],
-        system_prompt: None,
+        system_prompt,
         history: agent_history,
     };
     
@@ -324,3 +901,85 @@ pub async fn handle_agent_context(args: AgentContextArgs) -> Result<()> {
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_prompt_template() {
+        let dir = tempfile::tempdir().unwrap();
+        let prompt = expand_prompt_template("cwd={{cwd}} date={{date}} branch={{git_branch}}", dir.path());
+
+        assert!(prompt.contains(&format!("cwd={}", dir.path().display())));
+        assert!(prompt.contains(&format!("date={}", chrono::Local::now().format("%Y-%m-%d"))));
+        // Not a git checkout
+        assert!(prompt.ends_with("branch=unknown"));
+    }
+
+    #[test]
+    fn test_prompt_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = PromptStore::new(dir.path());
+
+        store.save("reviewer", "You review Rust code.").unwrap();
+        store.set_session_prompt("session/1", "Session prompt").unwrap();
+        assert_eq!(store.list().unwrap(), vec!["reviewer"]);
+        assert!(store.save("../evil", "x").is_err());
+
+        // Falls back to a saved prompt when no such file exists
+        let args = SystemPromptArgs { text: None, file: Some(PathBuf::from("reviewer")) };
+        assert_eq!(args.template(&store).unwrap().as_deref(), Some("You review Rust code."));
+
+        assert_eq!(store.session_prompt("session/1").unwrap().as_deref(), Some("Session prompt"));
+        assert!(store.session_prompt("other").unwrap().is_none());
+
+        assert!(store.delete("reviewer").unwrap());
+        assert!(!store.delete("reviewer").unwrap());
+        assert!(args.template(&store).is_err());
+    }
+
+    #[test]
+    fn test_chat_command_parse() {
+        assert_eq!(ChatCommand::parse("hello").unwrap(), None);
+        assert_eq!(ChatCommand::parse("!clear").unwrap(), Some(ChatCommand::Clear));
+        assert_eq!(
+            ChatCommand::parse(" !save review ").unwrap(),
+            Some(ChatCommand::Save("review".to_string()))
+        );
+        assert_eq!(
+            ChatCommand::parse("!provider kimi").unwrap(),
+            Some(ChatCommand::Provider("kimi".to_string()))
+        );
+        assert_eq!(
+            ChatCommand::parse("!memory get  project/key").unwrap(),
+            Some(ChatCommand::Cis(vec!["memory".into(), "get".into(), "project/key".into()]))
+        );
+        assert!(ChatCommand::parse("!load").is_err());
+        assert!(ChatCommand::parse("!").is_err());
+
+        assert_eq!(complete_chat_command("!dag s"), vec!["!dag status "]);
+        assert_eq!(complete_chat_command("!provider o"), vec!["!provider opencode"]);
+        assert!(complete_chat_command("dag").is_empty());
+    }
+
+    #[test]
+    fn test_chat_session_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ChatSessionStore::new(dir.path());
+        let session = ChatSession {
+            provider: "claude".to_string(),
+            history: vec![AgentMessage { role: MessageRole::User, content: "hi".to_string() }],
+            tokens: 42,
+        };
+
+        store.save("review", &session).unwrap();
+        let loaded = store.load("review").unwrap().unwrap();
+        assert_eq!(loaded.provider, "claude");
+        assert_eq!(loaded.history[0].content, "hi");
+        assert_eq!(loaded.tokens, 42);
+
+        assert!(store.load("missing").unwrap().is_none());
+        assert!(store.save("../evil", &session).is_err());
+    }
+}
@@ -8,10 +8,18 @@
 //! - `cis dag abort <run-id>` - Abort DAG run
 //! - `cis dag amend <run-id> <task-id>` - Amend task in running DAG
 //! - `cis dag definitions` - List DAG definitions from database
+//! - `cis dag diff <dag-id> <v1> <v2>` - Show changes between two DAG versions
 //! - `cis dag list` - List DAG runs with filters
 //! - `cis dag logs <run-id>` - View DAG execution logs
+//! - `cis dag logs <run-id> <task-id>` - Stream captured task output
+//! - `cis dag set-concurrency <n>` - Set the local worker concurrency limit
+//! - `cis dag gc --days <n>` - Delete old DAG runs and compact the database
+//! - `cis dag template list|add|apply|remove` - Manage parameterized DAG templates
+//! - `cis dag worker list` - List active and orphaned workers
+//! - `cis dag worker kill <worker-id>` - Terminate a worker and clean up its lock file
 
 use anyhow::Result;
+use cis_core::glm::DagRunControl;
 use cis_core::scheduler::{DagNodeStatus, DagRunStatus, DagScheduler, TaskDag, TodoItemStatus};
 use cis_core::types::{TaskLevel, Action};
 use cis_core::storage::Paths;
@@ -46,14 +54,12 @@ pub enum DagCommands {
     /// Pause DAG run (for arbitration)
     Pause {
         /// DAG run ID (uses active run if not specified)
-        #[arg(short, long)]
         run_id: Option<String>,
     },
 
     /// Resume a paused DAG run
     Resume {
         /// DAG run ID (uses active run if not specified)
-        #[arg(short, long)]
         run_id: Option<String>,
     },
 
@@ -111,6 +117,22 @@ pub enum DagCommands {
         limit: Option<usize>,
     },
 
+    /// Show changes between two stored versions of a DAG definition
+    Diff {
+        /// DAG ID
+        dag_id: String,
+        /// Old version
+        v1: i64,
+        /// New version
+        v2: i64,
+    },
+
+    /// Show the critical path of a DAG, estimated from P95 historical task durations
+    CriticalPath {
+        /// DAG ID (a run ID is also accepted)
+        dag_id: String,
+    },
+
     /// Set active DAG run
     Use {
         /// Run ID to set as active
@@ -123,6 +145,25 @@ pub enum DagCommands {
         cmd: WorkerCommands,
     },
 
+    /// Set the maximum number of concurrent local workers
+    SetConcurrency {
+        /// Worker count (1..=max_concurrency_limit)
+        n: usize,
+    },
+
+    /// Delete old DAG runs and compact the run database
+    Gc {
+        /// Delete runs last updated more than this many days ago
+        #[arg(long, default_value = "30")]
+        days: u64,
+        /// Only delete runs with this status (running, paused, completed, failed)
+        #[arg(long)]
+        status: Option<String>,
+        /// Only report how many runs would be deleted
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Execute DAG run tasks directly (embedded mode, no Matrix required)
     Execute {
         /// DAG run ID (uses active run if not specified)
@@ -134,6 +175,12 @@ pub enum DagCommands {
         /// Max concurrent Agent workers (requires --use-agent)
         #[arg(short = 'w', long, default_value = "4")]
         max_workers: usize,
+        /// Resume a run from its last checkpoint, skipping completed tasks
+        #[arg(long, value_name = "RUN_ID", conflicts_with_all = ["run_id", "use_agent"])]
+        resume: Option<String>,
+        /// Run only this task and its dependencies in a new run (repeatable)
+        #[arg(long = "task", value_name = "TASK_ID", conflicts_with_all = ["resume", "use_agent"])]
+        tasks: Vec<String>,
     },
 
     /// List active Agent sessions
@@ -164,10 +211,12 @@ pub enum DagCommands {
         readonly: bool,
     },
 
-    /// View session logs
+    /// View session logs, or the captured output of a task
     Logs {
-        /// Session ID (format: run_id:task_id or short_id)
+        /// Session ID (format: run_id:task_id or short_id), or a run ID when TASK_ID is given
         session_id: String,
+        /// Task ID: show the stdout/stderr captured by the worker for this task
+        task_id: Option<String>,
         /// Number of lines to show from the end
         #[arg(short, long, default_value = "50")]
         tail: usize,
@@ -190,13 +239,52 @@ pub enum DagCommands {
         /// Session ID (format: run_id:task_id or short_id)
         session_id: String,
     },
+
+    /// Manage parameterized DAG templates
+    Template {
+        #[command(subcommand)]
+        cmd: TemplateCommands,
+    },
+}
+
+/// DAG template subcommands
+#[derive(Debug, Subcommand)]
+pub enum TemplateCommands {
+    /// List stored templates
+    List,
+    /// Store a template from a TOML or JSON file (replaces one with the same name)
+    Add {
+        /// Template file
+        file: String,
+    },
+    /// Instantiate a template and store the resulting DAG definition
+    Apply {
+        /// Template name
+        name: String,
+        /// Parameter values (KEY=VALUE)
+        #[arg(short, long = "param", value_delimiter = ',')]
+        params: Vec<String>,
+        /// Print the resulting DAG spec without storing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Delete a stored template
+    Remove {
+        /// Template name
+        name: String,
+    },
 }
 
 /// Worker management subcommands
 #[derive(Debug, Subcommand)]
 pub enum WorkerCommands {
-    /// List all active workers
+    /// List all active and orphaned workers
     List,
+    /// Send SIGTERM to a worker and remove its lock file and record
+    Kill {
+        /// Worker ID
+        worker_id: String,
+    },
 }
 
 /// Handle DAG commands
@@ -240,6 +328,12 @@ pub async fn handle(cmd: DagCommands) -> Result<()> {
         DagCommands::Definitions { scope, node, limit } => {
             list_definitions(scope.as_deref(), node.as_deref(), limit).await?;
         }
+        DagCommands::Diff { dag_id, v1, v2 } => {
+            show_spec_diff(&dag_id, v1, v2).await?;
+        }
+        DagCommands::CriticalPath { dag_id } => {
+            show_critical_path(&dag_id).await?;
+        }
         DagCommands::Use { run_id } => {
             set_active_run(&run_id).await?;
         }
@@ -248,10 +342,23 @@ pub async fn handle(cmd: DagCommands) -> Result<()> {
                 WorkerCommands::List => {
                     list_workers().await?;
                 }
+                WorkerCommands::Kill { worker_id } => {
+                    kill_worker(&worker_id).await?;
+                }
             }
         }
-        DagCommands::Execute { run_id, use_agent, max_workers } => {
-            if use_agent {
+        DagCommands::SetConcurrency { n } => {
+            set_concurrency(n).await?;
+        }
+        DagCommands::Gc { days, status, dry_run } => {
+            gc_runs(days, status.as_deref(), dry_run).await?;
+        }
+        DagCommands::Execute { run_id, use_agent, max_workers, resume, tasks } => {
+            if let Some(resume_id) = resume {
+                resume_run_from_checkpoint(&resume_id).await?;
+            } else if !tasks.is_empty() {
+                execute_task_subset(run_id.as_deref(), &tasks).await?;
+            } else if use_agent {
                 execute_run_agent(run_id.as_deref(), max_workers).await?;
             } else {
                 execute_run(run_id.as_deref()).await?;
@@ -263,7 +370,10 @@ pub async fn handle(cmd: DagCommands) -> Result<()> {
         DagCommands::Attach { session_id, run, task, force, readonly } => {
             attach_session(session_id.as_deref(), run.as_deref(), task.as_deref(), force, readonly).await?;
         }
-        DagCommands::Logs { session_id, tail, follow } => {
+        DagCommands::Logs { session_id, task_id: Some(task_id), follow, .. } => {
+            view_task_output(&session_id, &task_id, follow).await?;
+        }
+        DagCommands::Logs { session_id, task_id: None, tail, follow } => {
             // Try database logs first, fallback to session logs
             if view_logs_from_db(&session_id, tail).await.is_err() {
                 view_logs(&session_id, tail, follow).await?;
@@ -275,6 +385,9 @@ pub async fn handle(cmd: DagCommands) -> Result<()> {
         DagCommands::Unblock { session_id } => {
             unblock_session(&session_id).await?;
         }
+        DagCommands::Template { cmd } => {
+            handle_template(cmd).await?;
+        }
     }
 
     Ok(())
@@ -488,8 +601,13 @@ pub async fn pause_run(run_id: Option<&str>) -> Result<()> {
     if let Some(run) = scheduler.get_run_mut(&target_run_id) {
         if run.status == DagRunStatus::Running {
             run.status = DagRunStatus::Paused;
+            let next_tasks = run.pending_tasks();
             save_scheduler(&scheduler).await?;
             println!("✓ DAG run {} paused", target_run_id);
+            if !next_tasks.is_empty() {
+                println!("  Next tasks on resume: {}", next_tasks.join(", "));
+            }
+            notify_worker(&target_run_id, DagRunControl::Pause).await;
             println!("  Use 'cis dag resume {}' to resume", target_run_id);
         } else {
             println!("Cannot pause run {} (status: {:?})", target_run_id, run.status);
@@ -519,6 +637,7 @@ pub async fn resume_run(run_id: Option<&str>) -> Result<()> {
             run.status = DagRunStatus::Running;
             save_scheduler(&scheduler).await?;
             println!("✓ DAG run {} resumed", target_run_id);
+            notify_worker(&target_run_id, DagRunControl::Resume).await;
         } else {
             println!("Cannot resume run {} (status: {:?})", target_run_id, run.status);
         }
@@ -529,6 +648,17 @@ pub async fn resume_run(run_id: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// Forward a pause/resume to the worker through the GLM API
+///
+/// Local state is authoritative; an unreachable GLM API only means the
+/// worker will pick up the change on its next sync.
+async fn notify_worker(run_id: &str, control: DagRunControl) {
+    match super::glm::send_dag_run_control(run_id, control).await {
+        Ok(()) => println!("  Worker notified (dag.{})", control.as_str()),
+        Err(e) => println!("  ⚠ Worker not notified: {}", e),
+    }
+}
+
 /// Abort a DAG run
 pub async fn abort_run(run_id: Option<&str>, force: bool) -> Result<()> {
     let mut scheduler = load_scheduler().await?;
@@ -687,6 +817,9 @@ pub async fn set_active_run(run_id: &str) -> Result<()> {
 }
 
 /// List all DAG workers
+///
+/// A worker whose record still carries a PID that no longer exists (checked
+/// against `/proc` on Linux) is shown as `orphaned`.
 pub async fn list_workers() -> Result<()> {
     
     use cis_core::storage::Paths;
@@ -701,37 +834,65 @@ pub async fn list_workers() -> Result<()> {
     }
     
     // 使用 WorkerService 查询运行的 workers
-    use cis_core::service::{WorkerService, ListOptions};
+    use cis_core::service::{ListOptions, ResourceStatus, WorkerService};
+    use dag_executor::process_lock::pid_exists;
     
     println!("DAG Workers:");
     println!();
     
     match WorkerService::new() {
         Ok(service) => {
-            let options = ListOptions::default();
+            // Stopped records are needed too: dead workers that were never
+            // stopped keep their PID and are reported as orphaned
+            let options = ListOptions {
+                all: true,
+                ..Default::default()
+            };
             match service.list(options).await {
                 Ok(result) => {
-                    if result.items.is_empty() {
+                    let workers: Vec<_> = result
+                        .items
+                        .into_iter()
+                        .filter_map(|worker| {
+                            let running = matches!(worker.status, ResourceStatus::Running);
+                            let orphaned = worker.pid.is_some_and(|pid| !pid_exists(pid));
+                            match (running, orphaned) {
+                                (_, true) => Some(("orphaned".to_string(), worker)),
+                                (true, false) => Some((worker.status.to_string(), worker)),
+                                (false, false) => None,
+                            }
+                        })
+                        .collect();
+
+                    if workers.is_empty() {
                         println!("No running DAG workers found.");
                         println!();
                         println!("Use 'cis worker run' to start a worker.");
                     } else {
-                        println!("{:<30} {:<15} {:<10} {:<10} Uptime", 
-                            "Worker ID", "Scope", "Status", "PID");
-                        println!("{}", "-".repeat(90));
+                        println!("{:<30} {:<15} {:<10} {:<10} {:<30} {:<6} {:<6} Uptime",
+                            "Worker ID", "Scope", "Status", "PID", "Room", "Tasks", "Runs");
+                        println!("{}", "-".repeat(125));
                         
-                        for worker in result.items {
+                        for (status, worker) in &workers {
                             let uptime = format_duration(worker.uptime);
                             let pid_str = worker.pid.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string());
                             
-                            println!("{:<30} {:<15} {:<10} {:<10} {}",
+                            println!("{:<30} {:<15} {:<10} {:<10} {:<30} {:<6} {:<6} {}",
                                 truncate(&worker.id, 30),
                                 truncate(&worker.scope, 15),
-                                worker.status.to_string(),
+                                status,
                                 pid_str,
+                                truncate(&worker.room, 30),
+                                worker.active_tasks,
+                                worker.tasks_executed,
                                 uptime
                             );
                         }
+
+                        if workers.iter().any(|(status, _)| status == "orphaned") {
+                            println!();
+                            println!("Use 'cis dag worker kill <worker-id>' to clean up orphaned workers.");
+                        }
                     }
                 }
                 Err(e) => {
@@ -747,6 +908,49 @@ pub async fn list_workers() -> Result<()> {
     Ok(())
 }
 
+/// Terminate a DAG worker
+///
+/// Sends SIGTERM (escalating to SIGKILL after a grace period), then removes
+/// the worker's process lock file and its record, which holds the room entry.
+pub async fn kill_worker(worker_id: &str) -> Result<()> {
+    use anyhow::Context;
+    use cis_core::service::{ResourceStatus, WorkerService};
+    use dag_executor::process_lock::{pid_exists, ProcessLock};
+
+    const KILL_GRACE_SECS: u64 = 10;
+
+    let service = WorkerService::new().context("Failed to initialize worker service")?;
+    let info = service
+        .inspect(worker_id)
+        .await
+        .with_context(|| format!("Worker '{}' not found", worker_id))?;
+    let id = info.summary.id.clone();
+
+    let alive = matches!(info.summary.status, ResourceStatus::Running)
+        && info.summary.pid.is_some_and(pid_exists);
+    if alive {
+        println!("Sending SIGTERM to worker {} (pid {})...", id, info.summary.pid.unwrap_or_default());
+        service
+            .stop(&id, false, KILL_GRACE_SECS)
+            .await
+            .with_context(|| format!("Failed to stop worker '{}'", id))?;
+    } else {
+        println!("Worker {} is not running, cleaning up", id);
+    }
+
+    if ProcessLock::remove_lock(&id) {
+        println!("  Removed lock file");
+    }
+    service
+        .remove(&id, true)
+        .await
+        .with_context(|| format!("Failed to remove worker '{}'", id))?;
+    println!("  Removed worker record (room: {})", info.summary.room);
+
+    println!("✓ Worker {} killed", id);
+    Ok(())
+}
+
 /// Load DAG from file
 /// 
 /// 支持三种格式：
@@ -1101,6 +1305,218 @@ async fn view_logs_from_db(run_id: &str, tail: usize) -> Result<()> {
     Ok(())
 }
 
+/// Worker 任务输出数据库文件名
+const TASK_OUTPUT_DB: &str = "task_outputs.db";
+
+/// Locate the task output database written by workers
+fn task_output_db_path() -> Option<std::path::PathBuf> {
+    let mut candidates = vec![Paths::data_dir().join("worker").join(TASK_OUTPUT_DB)];
+    if let Some(home) = dirs::home_dir() {
+        // Worker default data dir (`--data-dir ~/.cis/worker`)
+        candidates.push(home.join(".cis").join("worker").join(TASK_OUTPUT_DB));
+    }
+    candidates.into_iter().find(|p| p.exists())
+}
+
+/// Stream the stdout/stderr captured for a task
+///
+/// With `follow`, keeps polling until the task records an exit code.
+async fn view_task_output(run_id: &str, task_id: &str, follow: bool) -> Result<()> {
+    use cis_core::scheduler::DagPersistence;
+
+    let db_path = task_output_db_path()
+        .ok_or_else(|| anyhow::anyhow!("No task output database found"))?;
+    let persistence = DagPersistence::new(db_path.to_str().unwrap())?;
+
+    let mut printed_stdout = 0;
+    let mut printed_stderr = 0;
+
+    loop {
+        let output = persistence
+            .load_task_output(run_id, task_id)?
+            .ok_or_else(|| anyhow::anyhow!("No output recorded for {}/{}", run_id, task_id))?;
+
+        if output.stdout.len() > printed_stdout {
+            print!("{}", &output.stdout[printed_stdout..]);
+            printed_stdout = output.stdout.len();
+        }
+        if output.stderr.len() > printed_stderr {
+            eprint!("{}", &output.stderr[printed_stderr..]);
+            printed_stderr = output.stderr.len();
+        }
+
+        // exit_code -1 means the task is still running
+        let finished = output.exit_code != -1;
+        if finished || !follow {
+            println!();
+            if finished {
+                let icon = if output.is_success() { "✓" } else { "✗" };
+                println!(
+                    "{} Task {} exited with code {} ({}ms)",
+                    icon, task_id, output.exit_code, output.duration_ms
+                );
+            } else {
+                println!("▸ Task {} is still running (use --follow to stream)", task_id);
+            }
+            break;
+        }
+
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+
+    Ok(())
+}
+
+/// Show the changes between two stored versions of a DAG definition in unified format
+async fn show_spec_diff(dag_id: &str, v1: i64, v2: i64) -> Result<()> {
+    use cis_core::scheduler::{DagPersistence, EnvChange};
+
+    let db_path = Paths::data_dir().join(DAG_RUNS_DB);
+    if !db_path.exists() {
+        println!("No DAG database found. Run a DAG first.");
+        return Ok(());
+    }
+
+    let persistence = DagPersistence::new(db_path.to_str().unwrap())?;
+    let load = |version: i64| -> Result<cis_core::scheduler::DagSpec> {
+        persistence.load_spec_version(dag_id, version)?.ok_or_else(|| {
+            let known = persistence.list_spec_versions(dag_id).unwrap_or_default();
+            anyhow::anyhow!("Version {} of DAG {} not found (stored versions: {:?})", version, dag_id, known)
+        })
+    };
+    let old = load(v1)?;
+    let new = load(v2)?;
+    let diff = old.diff(&new);
+
+    println!("--- {} v{}", dag_id, v1);
+    println!("+++ {} v{}", dag_id, v2);
+    if !diff.has_changes() {
+        println!("No changes.");
+        return Ok(());
+    }
+
+    if diff.scope_changed {
+        println!("-scope: {}", old.scope.worker_id());
+        println!("+scope: {}", new.scope.worker_id());
+    }
+    if diff.priority_changed {
+        println!("-priority: {:?}", old.priority);
+        println!("+priority: {:?}", new.priority);
+    }
+
+    for task_id in &diff.removed_tasks {
+        println!("@@ task {} @@", task_id);
+        if let Some(task) = old.tasks.iter().find(|t| &t.id == task_id) {
+            println!("-command: {}", task.command);
+        }
+    }
+
+    for task in &diff.added_tasks {
+        println!("@@ task {} (new) @@", task.id);
+        println!("+command: {}", task.command);
+        for dep in &task.depends_on {
+            println!("+depends_on: {}", dep);
+        }
+        let mut env: Vec<_> = task.env.iter().collect();
+        env.sort();
+        for (key, value) in env {
+            println!("+env {}={}", key, value);
+        }
+    }
+
+    for change in &diff.modified_tasks {
+        println!("@@ task {} @@", change.task_id);
+        if change.command_changed {
+            let find = |spec: &cis_core::scheduler::DagSpec| {
+                spec.tasks.iter().find(|t| t.id == change.task_id).map(|t| t.command.clone()).unwrap_or_default()
+            };
+            println!("-command: {}", find(&old));
+            println!("+command: {}", find(&new));
+        }
+        for dep in &change.deps_removed {
+            println!("-depends_on: {}", dep);
+        }
+        for dep in &change.deps_added {
+            println!("+depends_on: {}", dep);
+        }
+        let mut env: Vec<_> = change.env_diff.iter().collect();
+        env.sort_by(|a, b| a.0.cmp(b.0));
+        for (key, env_change) in env {
+            match env_change {
+                EnvChange::Added { value } => println!("+env {}={}", key, value),
+                EnvChange::Removed { value } => println!("-env {}={}", key, value),
+                EnvChange::Modified { old, new } => {
+                    println!("-env {}={}", key, old);
+                    println!("+env {}={}", key, new);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Show the critical path of a stored DAG definition or run
+///
+/// Task durations are the P95 of completed executions of this DAG, falling
+/// back to the task's history across all DAGs. Tasks without history count as 0s.
+async fn show_critical_path(dag_id: &str) -> Result<()> {
+    use cis_core::scheduler::DagPersistence;
+
+    let db_path = Paths::data_dir().join(DAG_RUNS_DB);
+    if !db_path.exists() {
+        println!("No DAG database found. Run a DAG first.");
+        return Ok(());
+    }
+
+    let persistence = DagPersistence::new(db_path.to_str().unwrap())?;
+    let dag = if let Some(spec) = persistence.load_spec(dag_id)? {
+        spec.to_task_dag()?
+    } else if let Some(run) = persistence.load_run(dag_id)? {
+        run.dag
+    } else {
+        anyhow::bail!("DAG not found: {}", dag_id);
+    };
+
+    let mut durations = persistence.task_duration_p95(Some(dag_id))?;
+    for (task_id, duration) in persistence.task_duration_p95(None)? {
+        if dag.get_node(&task_id).is_some() {
+            durations.entry(task_id).or_insert(duration);
+        }
+    }
+
+    let result = dag.critical_path(&durations)?;
+
+    println!("Critical path for {} ({} tasks)", dag_id, dag.node_count());
+    println!("Estimated duration: {}", format_duration(result.total_duration.as_secs()));
+    println!();
+    println!("{:<30} {:>10} {:>10}  {}", "TASK", "P95", "SLACK", "CRITICAL");
+
+    let mut tasks: Vec<&String> = result.slack.keys().collect();
+    tasks.sort_by_key(|id| (result.slack[*id], id.as_str()));
+    for task_id in tasks {
+        let estimate = match durations.get(task_id) {
+            Some(d) => format_duration(d.as_secs()),
+            None => "-".to_string(),
+        };
+        let critical = if result.path.contains(task_id) { "*" } else { "" };
+        println!(
+            "{:<30} {:>10} {:>10}  {}",
+            truncate(task_id, 30),
+            estimate,
+            format_duration(result.slack[task_id].as_secs()),
+            critical
+        );
+    }
+
+    if !result.path.is_empty() {
+        println!();
+        println!("Path: {}", result.path.join(" -> "));
+    }
+
+    Ok(())
+}
+
 /// Helper: Format duration in seconds to human readable
 fn format_duration(seconds: u64) -> String {
     if seconds < 60 {
@@ -1265,6 +1681,119 @@ async fn execute_run(run_id: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// Execute selected tasks of a run in isolation
+///
+/// Creates a new run containing the tasks and their transitive dependencies;
+/// the source run is left untouched.
+async fn execute_task_subset(run_id: Option<&str>, tasks: &[String]) -> Result<()> {
+    let mut scheduler = load_scheduler().await?;
+
+    let source = if let Some(rid) = run_id {
+        scheduler.get_run(rid)
+    } else {
+        scheduler.get_active_run()
+    };
+    let Some(source) = source else {
+        println!("DAG run not found. Please specify --run-id.");
+        return Ok(());
+    };
+
+    let subset = source.execute_subset(tasks)?;
+    let subset_id = subset.run_id.clone();
+    let selected = subset.selected_tasks.clone().unwrap_or_default();
+    println!(
+        "Created partial run {} from {} ({} task(s): {})",
+        subset_id,
+        source.run_id,
+        selected.len(),
+        selected.join(", ")
+    );
+    scheduler.update_run(subset)?;
+
+    execute_run(Some(&subset_id)).await
+}
+
+/// Resume a DAG run from its checkpoint and execute the remaining tasks
+///
+/// Falls back to the persisted run state when the run has no checkpoint yet.
+/// Task commands go through the same sandbox checks as worker shell tasks.
+async fn resume_run_from_checkpoint(run_id: &str) -> Result<()> {
+    use cis_core::sandbox::SandboxValidator;
+    use cis_core::scheduler::execution::TaskHandler;
+    use cis_core::scheduler::{DagPersistence, SyncExecutor};
+    use std::sync::Arc;
+
+    let db_path = Paths::data_dir().join(DAG_RUNS_DB);
+    tokio::fs::create_dir_all(Paths::data_dir()).await?;
+    let db_path = db_path.to_string_lossy().to_string();
+
+    let allowed_roots = super::worker::default_allowed_roots();
+    let handler: TaskHandler = Arc::new(move |task| {
+        let command = task.description.clone().unwrap_or_default();
+        println!("  → Executing task: {}", task.id);
+        if let Err(e) = SandboxValidator::validate_path(Path::new("."), &allowed_roots)
+            .and_then(|()| SandboxValidator::validate_shell_command(&command))
+        {
+            println!("    ✗ Rejected by sandbox: {}", e);
+            return Err(cis_core::CisError::execution(format!("Rejected by sandbox: {}", e)));
+        }
+        // SyncExecutor runs handlers on the blocking pool
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(&command)
+            .output()
+            .map_err(|e| cis_core::CisError::execution(format!("Failed to run {}: {}", task.id, e)))?;
+
+        if output.status.success() {
+            println!("    ✓ Completed");
+            Ok(serde_json::json!({ "stdout": String::from_utf8_lossy(&output.stdout) }))
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            println!("    ✗ Failed: {}", stderr);
+            Err(cis_core::CisError::execution(stderr))
+        }
+    });
+    let executor = SyncExecutor::new()
+        .with_handler(handler)
+        .with_checkpoints(DagPersistence::new(&db_path)?);
+
+    let mut scheduler = load_scheduler().await?;
+    let mut run = match executor.resume(run_id) {
+        Ok(run) => {
+            println!("Resuming DAG run {} from checkpoint", run_id);
+            run
+        }
+        Err(_) => {
+            let Some(run) = scheduler.get_run(run_id) else {
+                anyhow::bail!("DAG run not found: {}", run_id);
+            };
+            println!("No checkpoint for {}, resuming from saved run state", run_id);
+            run.clone()
+        }
+    };
+
+    let skipped = run
+        .dag
+        .nodes()
+        .values()
+        .filter(|node| node.status == DagNodeStatus::Completed)
+        .count();
+    println!("Skipping {} completed task(s)", skipped);
+    println!();
+
+    let results = executor.execute_run(&mut run).await?;
+    let failed = results.iter().filter(|r| r.is_failure()).count();
+    scheduler.update_run(run)?;
+
+    println!();
+    println!("Execution summary:");
+    println!("  Completed: {}", results.len() - failed);
+    println!("  Failed: {}", failed);
+
+    show_status(Some(run_id), false).await?;
+    Ok(())
+}
+
 /// List active Agent sessions
 async fn list_sessions(dag_filter: Option<&str>, all: bool) -> Result<()> {
     use cis_core::agent::cluster::SessionManager;
@@ -1514,6 +2043,156 @@ async fn unblock_session(session_id_str: &str) -> Result<()> {
     Ok(())
 }
 
+/// Persist the local worker concurrency limit
+///
+/// Running executors pick up the new limit on their next dispatch.
+async fn set_concurrency(n: usize) -> Result<()> {
+    use cis_core::scheduler::LocalExecutorConfig;
+
+    let path = LocalExecutorConfig::default_path();
+    let mut config = LocalExecutorConfig::load(&path)?;
+    config.validate_concurrency(n)?;
+
+    let previous = config.concurrency;
+    config.concurrency = n;
+    config.save(&path)?;
+
+    println!("✓ Worker concurrency set: {} -> {}", previous, n);
+    println!("  Saved to {}", path.display());
+    println!("  Running executors apply it on their next dispatch");
+    Ok(())
+}
+
+/// Delete old DAG runs and compact the run database
+async fn gc_runs(days: u64, status: Option<&str>, dry_run: bool) -> Result<()> {
+    use cis_core::scheduler::DagPersistence;
+
+    let status_filter = match status.map(|s| s.to_lowercase()) {
+        None => None,
+        Some(s) => Some(match s.as_str() {
+            "running" => DagRunStatus::Running,
+            "paused" => DagRunStatus::Paused,
+            "completed" => DagRunStatus::Completed,
+            "failed" => DagRunStatus::Failed,
+            _ => anyhow::bail!(
+                "Invalid status '{}'. Expected running, paused, completed or failed",
+                s
+            ),
+        }),
+    };
+
+    let db_path = Paths::data_dir().join(DAG_RUNS_DB);
+    if !db_path.exists() {
+        println!("No DAG run database at {}", db_path.display());
+        return Ok(());
+    }
+
+    let persistence = DagPersistence::new(db_path.to_str().unwrap())?;
+    let older_than = std::time::Duration::from_secs(days * 24 * 60 * 60);
+    let report = persistence.gc(older_than, status_filter, dry_run)?;
+
+    if report.dry_run {
+        println!(
+            "Would delete {} run(s) older than {} day(s) (dry run)",
+            report.deleted_runs, days
+        );
+    } else {
+        println!(
+            "✓ Deleted {} run(s) older than {} day(s)",
+            report.deleted_runs, days
+        );
+        println!("  Freed {:.1} KiB", report.freed_bytes as f64 / 1024.0);
+    }
+    Ok(())
+}
+
+/// Handle `cis dag template` subcommands
+async fn handle_template(cmd: TemplateCommands) -> Result<()> {
+    use cis_core::scheduler::{DagPersistence, DagTemplate};
+
+    let db_path = Paths::data_dir().join(DAG_RUNS_DB);
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let persistence = DagPersistence::new(db_path.to_str().unwrap())?;
+
+    match cmd {
+        TemplateCommands::List => {
+            let templates = persistence.list_templates()?;
+            if templates.is_empty() {
+                println!("No DAG templates. Add one with 'cis dag template add <file>'.");
+                return Ok(());
+            }
+
+            println!("{:<24} {:<32} {:<6} Parameters", "Name", "DAG ID", "Tasks");
+            for template in &templates {
+                let params: Vec<String> = template
+                    .parameters
+                    .iter()
+                    .map(|p| match (&p.default, p.required) {
+                        (Some(default), _) => format!("{}={}", p.name, default),
+                        (None, true) => format!("{}*", p.name),
+                        (None, false) => p.name.clone(),
+                    })
+                    .collect();
+                println!(
+                    "{:<24} {:<32} {:<6} {}",
+                    truncate(&template.name, 24),
+                    truncate(&template.template.dag_id, 32),
+                    template.template.tasks.len(),
+                    params.join(", ")
+                );
+            }
+            println!("\n* required");
+        }
+        TemplateCommands::Add { file } => {
+            let content = tokio::fs::read_to_string(&file).await?;
+            let template: DagTemplate = if file.ends_with(".json") {
+                serde_json::from_str(&content)?
+            } else {
+                toml::from_str(&content)?
+            };
+            persistence.save_template(&template)?;
+            println!(
+                "✓ Stored DAG template '{}' ({} parameter(s))",
+                template.name,
+                template.parameters.len()
+            );
+        }
+        TemplateCommands::Apply { name, params, dry_run } => {
+            let template = persistence
+                .load_template(&name)?
+                .ok_or_else(|| anyhow::anyhow!("DAG template not found: {}", name))?;
+
+            let mut values = std::collections::HashMap::new();
+            for param in &params {
+                let (key, value) = param
+                    .split_once('=')
+                    .ok_or_else(|| anyhow::anyhow!("Invalid parameter '{}', expected KEY=VALUE", param))?;
+                values.insert(key.trim().to_string(), value.to_string());
+            }
+
+            let spec = template.instantiate(values)?;
+            if dry_run {
+                println!("{}", serde_json::to_string_pretty(&spec)?);
+                return Ok(());
+            }
+
+            persistence.save_spec(&spec)?;
+            println!("✓ Created DAG definition {} from template '{}'", spec.dag_id, name);
+        }
+        TemplateCommands::Remove { name } => {
+            if persistence.delete_template(&name)? {
+                println!("✓ Removed DAG template '{}'", name);
+            } else {
+                println!("DAG template not found: {}", name);
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Execute DAG run using Agent Cluster
 async fn execute_run_agent(run_id: Option<&str>, max_workers: usize) -> Result<()> {
     use cis_core::agent::cluster::{AgentClusterConfig, AgentClusterExecutor};
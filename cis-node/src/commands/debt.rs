@@ -5,12 +5,13 @@
 //! - `cis debt resolve <task-id>` - Resolve a specific debt
 //! - `cis debt summary` - Show debt statistics
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
 use cis_core::scheduler::{DagRunStatus, DagScheduler};
 use cis_core::storage::Paths;
-use cis_core::types::FailureType;
+use cis_core::types::{DebtEntry, FailureType};
 use clap::Subcommand;
+use colored::{ColoredString, Colorize};
 use std::collections::HashMap;
 
 /// Debt management commands
@@ -24,6 +25,9 @@ pub enum DebtCommands {
         /// Show all debts including resolved ones
         #[arg(short, long)]
         all: bool,
+        /// Filter by failure type (ignorable, blocking)
+        #[arg(short = 't', long = "type", value_parser = parse_failure_type)]
+        failure_type: Option<FailureType>,
     },
 
     /// Resolve a specific debt
@@ -45,15 +49,21 @@ pub enum DebtCommands {
 /// Handle debt commands
 pub async fn handle(cmd: DebtCommands) -> Result<()> {
     match cmd {
-        DebtCommands::List { run_id, all } => {
-            list_debts(run_id.as_deref(), all).await?;
+        DebtCommands::List { run_id, all, failure_type } => {
+            let filter = DebtFilter {
+                unresolved_only: !all,
+                failure_type,
+                run_id,
+            };
+            let debts = list_debts(&filter).await?;
+            print_debts(&debts, all);
         }
         DebtCommands::Resolve {
             task_id,
             run_id,
             resume,
         } => {
-            resolve_debt(&task_id, run_id.as_deref(), resume).await?;
+            resolve_debt_command(&task_id, run_id.as_deref(), resume).await?;
         }
         DebtCommands::Summary => {
             debt_summary().await?;
@@ -63,120 +73,142 @@ pub async fn handle(cmd: DebtCommands) -> Result<()> {
     Ok(())
 }
 
-/// List accumulated technical debts
-pub async fn list_debts(run_id: Option<&str>, all: bool) -> Result<()> {
+/// Debt query filter
+#[derive(Debug, Clone, Default)]
+pub struct DebtFilter {
+    /// Only include debts that are not resolved yet
+    pub unresolved_only: bool,
+    /// Only include debts of this failure type
+    pub failure_type: Option<FailureType>,
+    /// Only include debts of this DAG run
+    pub run_id: Option<String>,
+}
+
+impl DebtFilter {
+    /// Check whether a debt passes the filter
+    pub fn matches(&self, debt: &DebtEntry) -> bool {
+        (!self.unresolved_only || !debt.resolved)
+            && self.failure_type.is_none_or(|ft| debt.failure_type == ft)
+            && self.run_id.as_deref().is_none_or(|rid| debt.dag_run_id == rid)
+    }
+}
+
+/// List debts from the DAG persistence layer, grouped by `dag_run_id`
+pub async fn list_debts(filter: &DebtFilter) -> Result<Vec<DebtEntry>> {
     let scheduler = load_scheduler().await?;
 
-    let debts = if let Some(rid) = run_id {
-        // List debts for specific run
-        if let Some(run) = scheduler.get_run(rid) {
-            if all {
-                run.debts.clone()
-            } else {
-                run.debts.iter().filter(|d| !d.resolved).cloned().collect()
-            }
-        } else {
-            println!("DAG run not found: {}", rid);
-            return Ok(());
-        }
-    } else {
-        // List debts from all runs
-        let mut all_debts = Vec::new();
-        for run in scheduler.run_ids() {
-            if let Some(r) = scheduler.get_run(run) {
-                let run_debts: Vec<_> = if all {
-                    r.debts.clone()
-                } else {
-                    r.debts.iter().filter(|d| !d.resolved).cloned().collect()
-                };
-                all_debts.extend(run_debts);
-            }
+    if let Some(rid) = &filter.run_id {
+        if scheduler.get_run(rid).is_none() {
+            return Err(anyhow!("DAG run not found: {}", rid));
         }
-        all_debts
-    };
+    }
+
+    Ok(collect_debts(&scheduler, filter))
+}
+
+/// Collect matching debts from all runs, ordered by run then creation time
+fn collect_debts(scheduler: &DagScheduler, filter: &DebtFilter) -> Vec<DebtEntry> {
+    let mut debts: Vec<DebtEntry> = scheduler
+        .run_ids()
+        .filter_map(|rid| scheduler.get_run(rid))
+        .flat_map(|run| run.debts.iter())
+        .filter(|debt| filter.matches(debt))
+        .cloned()
+        .collect();
+
+    debts.sort_by(|a, b| {
+        a.dag_run_id
+            .cmp(&b.dag_run_id)
+            .then(a.created_at.cmp(&b.created_at))
+    });
+    debts
+}
 
+/// Print debts as a table, one section per DAG run
+fn print_debts(debts: &[DebtEntry], all: bool) {
     if debts.is_empty() {
         if all {
             println!("No debts found.");
         } else {
             println!("No unresolved debts. Use --all to see resolved debts.");
         }
-        return Ok(());
+        return;
     }
 
-    // Print header
     println!("Technical Debts:");
-    println!();
-    println!(
-        "{:<12} {:<20} {:<12} {:<20} {:<10}",
-        "Task ID", "DAG Run", "Type", "Created", "Status"
-    );
-    println!("{}", "-".repeat(80));
 
-    // Print debts
+    let mut current_run: Option<&str> = None;
     for debt in debts {
-        let created = format_datetime(debt.created_at);
-        let status = if debt.resolved { "Resolved" } else { "Pending" };
-        let debt_type = format_failure_type(debt.failure_type);
+        if current_run != Some(debt.dag_run_id.as_str()) {
+            current_run = Some(debt.dag_run_id.as_str());
+            println!();
+            println!("DAG Run: {}", debt.dag_run_id.bold());
+            println!(
+                "  {:<20} {:<12} {:<20} {:<10}",
+                "Task ID", "Type", "Created", "Status"
+            );
+            println!("  {}", "-".repeat(66));
+        }
 
+        let status = if debt.resolved { "Resolved".green() } else { "Pending".yellow() };
         println!(
-            "{:<12} {:<20} {:<12} {:<20} {}",
-            truncate(&debt.task_id, 12),
-            truncate(&debt.dag_run_id, 20),
-            debt_type,
-            created,
+            "  {:<20} {} {:<20} {}",
+            truncate(&debt.task_id, 20),
+            color_failure_type(debt.failure_type, &format!("{:<12}", format_failure_type(debt.failure_type))),
+            format_datetime(debt.created_at),
             status
         );
 
         if !debt.error_message.is_empty() {
-            println!("  Error: {}", truncate(&debt.error_message, 70));
+            println!("    Error: {}", truncate(&debt.error_message, 68).dimmed());
         }
     }
-
-    Ok(())
 }
 
-/// Resolve a specific debt
-pub async fn resolve_debt(task_id: &str, run_id: Option<&str>, resume: bool) -> Result<()> {
+/// Resolve a debt and persist the run
+///
+/// Returns the tasks that became ready (empty unless `resume` is set).
+pub async fn resolve_debt(run_id: &str, task_id: &str, resume: bool) -> Result<Vec<String>> {
     let mut scheduler = load_scheduler().await?;
 
-    let target_run_id = if let Some(rid) = run_id {
-        rid.to_string()
-    } else if let Some(active) = scheduler.get_active_run() {
-        active.run_id.clone()
-    } else {
-        println!("No active DAG run. Please specify --run-id.");
-        return Ok(());
-    };
-
-    // Check if run exists
-    if scheduler.get_run(&target_run_id).is_none() {
-        println!("DAG run not found: {}", target_run_id);
-        return Ok(());
+    if scheduler.get_run(run_id).is_none() {
+        return Err(anyhow!("DAG run not found: {}", run_id));
     }
 
-    // Resolve the debt in the DAG
-    match scheduler.resolve_run_debt(&target_run_id, task_id, resume) {
-        Ok(new_ready) => {
-            println!("✓ Debt resolved for task {}", task_id);
+    // Persisted by the scheduler
+    scheduler
+        .resolve_run_debt(run_id, task_id, resume)
+        .map_err(|e| anyhow!("Failed to resolve debt: {}", e))
+}
 
-            if resume {
-                println!("  Downstream tasks resumed");
-                if !new_ready.is_empty() {
-                    println!("  Newly ready tasks: {}", new_ready.join(", "));
-                }
-            } else {
-                println!("  Task marked as failed, downstream tasks remain skipped");
+/// `cis debt resolve` - resolve a debt in the given or active run
+async fn resolve_debt_command(task_id: &str, run_id: Option<&str>, resume: bool) -> Result<()> {
+    let target_run_id = match run_id {
+        Some(rid) => rid.to_string(),
+        None => match load_scheduler().await?.get_active_run() {
+            Some(active) => active.run_id.clone(),
+            None => {
+                println!("No active DAG run. Please specify --run-id.");
+                return Ok(());
             }
+        },
+    };
 
-            // Show updated run status
-            if let Some(run) = scheduler.get_run(&target_run_id) {
-                println!("  Run status: {:?}", run.status);
-            }
-        }
-        Err(e) => {
-            println!("Failed to resolve debt: {}", e);
+    let new_ready = resolve_debt(&target_run_id, task_id, resume).await?;
+    println!("{} Debt resolved for task {}", "✓".green(), task_id);
+
+    if resume {
+        println!("  Downstream tasks resumed");
+        if !new_ready.is_empty() {
+            println!("  Newly ready tasks: {}", new_ready.join(", "));
         }
+    } else {
+        println!("  Task marked as failed, downstream tasks remain skipped");
+    }
+
+    // Show updated run status
+    if let Some(run) = load_scheduler().await?.get_run(&target_run_id) {
+        println!("  Run status: {}", format_run_status(run.status));
     }
 
     Ok(())
@@ -322,6 +354,23 @@ fn format_failure_type(ft: FailureType) -> &'static str {
     }
 }
 
+/// Color a (padded) failure type label
+fn color_failure_type(ft: FailureType, label: &str) -> ColoredString {
+    match ft {
+        FailureType::Ignorable => label.yellow(),
+        FailureType::Blocking => label.red().bold(),
+    }
+}
+
+/// Parse `--type` argument
+fn parse_failure_type(s: &str) -> std::result::Result<FailureType, String> {
+    match s.to_ascii_lowercase().as_str() {
+        "ignorable" => Ok(FailureType::Ignorable),
+        "blocking" => Ok(FailureType::Blocking),
+        other => Err(format!("unknown failure type '{}' (expected ignorable or blocking)", other)),
+    }
+}
+
 /// Format run status for display
 fn format_run_status(status: DagRunStatus) -> String {
     match status {
@@ -340,3 +389,47 @@ fn truncate(s: &str, max_len: usize) -> String {
         s.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn debt(run_id: &str, task_id: &str, failure_type: FailureType, resolved: bool) -> DebtEntry {
+        DebtEntry {
+            task_id: task_id.to_string(),
+            dag_run_id: run_id.to_string(),
+            failure_type,
+            error_message: String::new(),
+            created_at: Utc::now(),
+            resolved,
+        }
+    }
+
+    #[test]
+    fn test_debt_filter() {
+        let blocking = debt("run-1", "a", FailureType::Blocking, false);
+        let resolved = debt("run-2", "b", FailureType::Ignorable, true);
+
+        let all = DebtFilter::default();
+        assert!(all.matches(&blocking) && all.matches(&resolved));
+
+        let unresolved = DebtFilter { unresolved_only: true, ..Default::default() };
+        assert!(unresolved.matches(&blocking));
+        assert!(!unresolved.matches(&resolved));
+
+        let by_type = DebtFilter { failure_type: Some(FailureType::Ignorable), ..Default::default() };
+        assert!(!by_type.matches(&blocking));
+        assert!(by_type.matches(&resolved));
+
+        let by_run = DebtFilter { run_id: Some("run-1".to_string()), ..Default::default() };
+        assert!(by_run.matches(&blocking));
+        assert!(!by_run.matches(&resolved));
+    }
+
+    #[test]
+    fn test_parse_failure_type() {
+        assert_eq!(parse_failure_type("Blocking").unwrap(), FailureType::Blocking);
+        assert_eq!(parse_failure_type("ignorable").unwrap(), FailureType::Ignorable);
+        assert!(parse_failure_type("fatal").is_err());
+    }
+}
@@ -3,11 +3,13 @@
 //! Check CIS environment and diagnose issues.
 
 use anyhow::Result;
+use cis_core::config::Config;
+use cis_core::memory::watermark::{database_size, watermark_level, WatermarkLevel};
 use cis_core::storage::paths::Paths;
 use cis_core::wizard::checks::EnvironmentChecker;
 
 /// Run all environment checks
-pub fn doctor() -> Result<()> {
+pub async fn doctor() -> Result<()> {
     println!("🔍 CIS Environment Check\n");
     
     let checker = EnvironmentChecker::new();
@@ -68,6 +70,14 @@ pub fn doctor() -> Result<()> {
         Err(e) => println!("  ❌ Database error: {}", e),
     }
     
+    // Memory database size vs. watermark thresholds
+    println!("\n🧠 Memory Watermark:");
+    print_memory_watermark();
+    
+    // IM full-text index consistency
+    println!("\n🔎 IM Search Index:");
+    print_im_fts_integrity().await;
+    
     // Display warnings and recommendations
     if !result.warnings.is_empty() {
         println!("\n⚠️  Warnings:");
@@ -101,6 +111,67 @@ fn check_database() -> Result<()> {
     Ok(())
 }
 
+/// Show memory database size against the configured thresholds
+fn print_memory_watermark() {
+    let config = Config::load().unwrap_or_default().memory_watermark;
+    let path = Paths::memory_db();
+    if !path.exists() {
+        println!("  ⚠️  Memory database not found: {}", path.display());
+        return;
+    }
+    
+    match database_size(&path) {
+        Ok(size) => {
+            let icon = match watermark_level(size, &config) {
+                WatermarkLevel::Normal => "✅",
+                WatermarkLevel::Warning => "⚠️ ",
+                WatermarkLevel::Critical => "❌",
+            };
+            println!(
+                "  {} {:.1} MB (warn {} MB, critical {} MB)",
+                icon,
+                size as f64 / (1024.0 * 1024.0),
+                config.warn_threshold_mb,
+                config.critical_threshold_mb
+            );
+        }
+        Err(e) => println!("  ❌ Failed to read memory database size: {}", e),
+    }
+}
+
+/// Show whether the IM full-text index matches the message table
+async fn print_im_fts_integrity() {
+    let db = match super::im::open_local_im_db(None) {
+        Ok(Some(db)) => db,
+        Ok(None) => {
+            println!("  ⚠️  IM database not found");
+            return;
+        }
+        Err(e) => {
+            println!("  ❌ Failed to open IM database: {}", e);
+            return;
+        }
+    };
+    
+    match db.check_fts_integrity().await {
+        Ok(report) if !report.index_exists => println!("  ⚠️  Full-text index not created yet"),
+        Ok(report) if report.is_healthy() => {
+            println!("  ✅ {} documents indexed", report.indexed_documents)
+        }
+        Ok(report) => {
+            println!(
+                "  ❌ Index inconsistent: {} missing, {} orphaned, {} stale, {} errors",
+                report.missing_documents,
+                report.orphaned_documents,
+                report.stale_documents,
+                report.errors.len()
+            );
+            println!("     Run 'cis im index-rebuild' to repair");
+        }
+        Err(e) => println!("  ❌ Index check failed: {}", e),
+    }
+}
+
 /// Check if CIS is initialized
 pub fn check_initialized() -> bool {
     Paths::config_file().exists()
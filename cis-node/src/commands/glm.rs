@@ -8,7 +8,9 @@ use clap::{Args, Subcommand};
 use std::net::SocketAddr;
 use std::path::PathBuf;
 
-use cis_core::glm::{GlmApiConfig, start_glm_api_service};
+use std::sync::Arc;
+
+use cis_core::glm::{DagRunControl, GlmApiConfig, GlmApiServer};
 
 /// 默认的示例 DID
 const DEFAULT_DID: &str = "did:cis:glm-cloud:abc123";
@@ -17,6 +19,7 @@ const DEFAULT_DID: &str = "did:cis:glm-cloud:abc123";
 #[derive(Subcommand, Debug)]
 pub enum GlmCommands {
     /// 启动 GLM API 服务
+    #[command(alias = "serve")]
     Start(GlmStartArgs),
     /// 停止 GLM API 服务
     Stop,
@@ -47,6 +50,15 @@ pub struct GlmStartArgs {
     /// 后台运行
     #[arg(long)]
     daemon: bool,
+    /// 事件流 WebSocket 监听地址（需要 API Key）
+    #[arg(long)]
+    ws_bind: Option<String>,
+    /// 事件流 API Key（默认读取配置文件）
+    #[arg(long)]
+    api_key: Option<String>,
+    /// 将事件以 JSON 行输出到 stdout（调试用）
+    #[arg(long)]
+    watch: bool,
 }
 
 #[derive(Args, Debug)]
@@ -60,6 +72,9 @@ pub struct GlmConfigArgs {
     /// 设置默认 Room ID
     #[arg(long)]
     room_id: Option<String>,
+    /// 设置事件流 API Key
+    #[arg(long)]
+    api_key: Option<String>,
     /// 查看当前配置
     #[arg(long)]
     show: bool,
@@ -85,12 +100,13 @@ async fn start_service(args: GlmStartArgs) -> anyhow::Result<()> {
         .parse()
         .map_err(|e| anyhow::anyhow!("Invalid bind address: {}", e))?;
 
+    let file_config = load_config().await?;
+
     // 获取允许的 DID 列表
     let allowed_dids = if !args.did.is_empty() {
         args.did
     } else {
-        let config = load_config().await?;
-        config.allowed_dids
+        file_config.allowed_dids
     };
 
     let config = GlmApiConfig {
@@ -98,8 +114,18 @@ async fn start_service(args: GlmStartArgs) -> anyhow::Result<()> {
         allowed_dids,
         default_room_id: args.room_id.unwrap_or_else(|| "!default:matrix.org".to_string()),
         task_timeout_secs: 300,
+        api_key: args.api_key.or(file_config.api_key),
     };
 
+    let ws_bind: Option<SocketAddr> = args
+        .ws_bind
+        .map(|addr| addr.parse())
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("Invalid WebSocket bind address: {}", e))?;
+    if ws_bind.is_some() && config.api_key.is_none() {
+        anyhow::bail!("--ws-bind requires an API key (--api-key or `cis glm config --api-key`)");
+    }
+
     println!("  📡 Bind: {}", config.bind_addr);
     println!("  👥 Allowed DIDs:");
     for did in &config.allowed_dids {
@@ -116,9 +142,39 @@ async fn start_service(args: GlmStartArgs) -> anyhow::Result<()> {
     println!("   API endpoint: http://{}/api/v1/", config.bind_addr);
     println!("\n💡 使用 Bearer DID 认证（与 CIS 节点间认证一致）:");
     println!("   Authorization: Bearer did:cis:{{node_id}}:{{pub_key_short}}");
+    if let Some(ws_bind) = ws_bind {
+        println!("   Event stream: ws://{} (Authorization: Bearer <api_key>)", ws_bind);
+    }
+
+    let server = Arc::new(GlmApiServer::new(config));
+
+    if let Some(ws_bind) = ws_bind {
+        let server = Arc::clone(&server);
+        tokio::spawn(async move {
+            if let Err(e) = server.start_websocket_server(ws_bind).await {
+                eprintln!("❌ GLM event stream stopped: {}", e);
+            }
+        });
+    }
+
+    if args.watch {
+        let mut events = server.state().subscribe_events();
+        tokio::spawn(async move {
+            use tokio::sync::broadcast::error::RecvError;
+            loop {
+                match events.recv().await {
+                    Ok(event) => print!("{}", event.to_ndjson()),
+                    Err(RecvError::Lagged(skipped)) => {
+                        eprintln!("⚠️  {} events dropped", skipped);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
 
     // 启动服务（会阻塞）
-    start_glm_api_service(config).await?;
+    server.start().await?;
 
     Ok(())
 }
@@ -245,6 +301,32 @@ async fn confirm_dag(dag_id: String) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// 通过 GLM API 暂停/恢复 DAG 运行（发送 dag.pause / dag.resume 到 Worker）
+pub async fn send_dag_run_control(run_id: &str, control: DagRunControl) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+    let config = load_config().await?;
+
+    let auth_did = config.allowed_dids.first()
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_DID.to_string());
+
+    let resp = client
+        .post(format!(
+            "http://{}/api/v1/dag/run/{}/{}",
+            config.bind_addr, run_id, control.as_str()
+        ))
+        .header("Authorization", format!("Bearer {}", auth_did))
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("GLM API unreachable ({}), is `cis glm start` running?", e))?;
+
+    if resp.status().is_success() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("GLM API returned {}", resp.status()))
+    }
+}
+
 async fn configure(args: GlmConfigArgs) -> anyhow::Result<()> {
     if args.show {
         let config = load_config().await?;
@@ -255,6 +337,10 @@ async fn configure(args: GlmConfigArgs) -> anyhow::Result<()> {
             println!("      - {}", did);
         }
         println!("   Room ID: {}", config.default_room_id);
+        println!(
+            "   Event stream API key: {}",
+            if config.api_key.is_some() { "configured" } else { "not set" }
+        );
         return Ok(());
     }
 
@@ -278,6 +364,11 @@ async fn configure(args: GlmConfigArgs) -> anyhow::Result<()> {
         config.default_room_id = room_id;
         println!("✅ Room ID updated: {}", config.default_room_id);
     }
+    
+    if let Some(api_key) = args.api_key {
+        config.api_key = Some(api_key);
+        println!("✅ Event stream API key updated");
+    }
 
     // 保存配置
     save_config(&config).await?;
@@ -330,6 +421,8 @@ struct GlmApiConfigFile {
     pub allowed_dids: Vec<String>,
     pub room_id: String,
     pub timeout_secs: u64,
+    #[serde(default)]
+    pub api_key: Option<String>,
 }
 
 impl From<GlmApiConfigFile> for GlmApiConfig {
@@ -345,6 +438,7 @@ impl From<GlmApiConfigFile> for GlmApiConfig {
             allowed_dids,
             default_room_id: f.room_id,
             task_timeout_secs: f.timeout_secs,
+            api_key: f.api_key,
         }
     }
 }
@@ -356,6 +450,7 @@ impl From<GlmApiConfig> for GlmApiConfigFile {
             allowed_dids: c.allowed_dids,
             room_id: c.default_room_id,
             timeout_secs: c.task_timeout_secs,
+            api_key: c.api_key,
         }
     }
 }
@@ -4,9 +4,12 @@
 
 use clap::{Args, Subcommand};
 use anyhow::Result;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use cis_core::storage::db::DbManager;
+use cis_core::storage::paths::Paths;
 use cis_core::skill::SkillManager;
+use im_skill::ImDatabase;
 
 /// IM 命令参数
 #[derive(Args, Debug)]
@@ -32,6 +35,24 @@ pub enum ImAction {
     Read(ReadArgs),
     /// 获取会话信息
     Info(InfoArgs),
+    /// 生成会话摘要
+    Summarize(SummarizeArgs),
+    /// 查看会话统计
+    Stats(StatsArgs),
+    /// 管理会话标签
+    Label(LabelArgs),
+    /// 重建消息全文索引
+    IndexRebuild(IndexArgs),
+    /// 检查消息全文索引一致性
+    IndexCheck(IndexArgs),
+}
+
+/// 全文索引命令参数
+#[derive(Args, Debug)]
+pub struct IndexArgs {
+    /// IM 数据目录（默认为 IM Skill 数据目录）
+    #[arg(long)]
+    pub data_dir: Option<PathBuf>,
 }
 
 /// 发送消息参数
@@ -55,6 +76,9 @@ pub struct ListArgs {
     /// 用户 ID（默认当前用户）
     #[arg(short, long)]
     pub user: Option<String>,
+    /// 只列出带有指定标签的会话（可重复）
+    #[arg(long = "label")]
+    pub labels: Vec<String>,
 }
 
 /// 查看消息历史参数
@@ -130,6 +154,131 @@ pub struct InfoArgs {
     pub session_id: String,
 }
 
+/// 生成会话摘要参数
+#[derive(Args, Debug)]
+pub struct SummarizeArgs {
+    /// 会话 ID
+    pub conversation_id: String,
+    /// 参与摘要的最近消息数
+    #[arg(short, long, default_value = "100")]
+    pub max_messages: usize,
+}
+
+/// 查看会话统计参数
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+    /// 会话 ID
+    pub conversation_id: String,
+    /// 统计时间窗口
+    #[arg(short, long, value_enum, default_value = "7d")]
+    pub window: StatsWindow,
+}
+
+/// 会话标签参数
+#[derive(Args, Debug)]
+pub struct LabelArgs {
+    #[command(subcommand)]
+    pub action: LabelAction,
+    /// 用户 ID（默认当前用户）
+    #[arg(short, long, global = true)]
+    pub user: Option<String>,
+}
+
+/// 会话标签子命令
+#[derive(Subcommand, Debug)]
+pub enum LabelAction {
+    /// 为会话添加标签
+    Add {
+        /// 会话 ID
+        conversation_id: String,
+        /// 标签
+        label: String,
+        /// 标签颜色（如 #ff8800）
+        #[arg(long)]
+        color: Option<String>,
+    },
+    /// 移除会话标签
+    Remove {
+        /// 会话 ID
+        conversation_id: String,
+        /// 标签
+        label: String,
+    },
+    /// 列出会话上的标签
+    List {
+        /// 会话 ID
+        conversation_id: String,
+    },
+    /// 查找带有指定标签的会话
+    Search {
+        /// 标签
+        label: String,
+    },
+}
+
+impl LabelAction {
+    /// IM Skill 事件名和事件数据
+    fn to_event(&self, user_id: &str) -> (&'static str, serde_json::Value) {
+        match self {
+            LabelAction::Add { conversation_id, label, color } => (
+                "im:add_label",
+                serde_json::json!({
+                    "conversation_id": conversation_id,
+                    "label": label,
+                    "color": color,
+                    "user_id": user_id,
+                }),
+            ),
+            LabelAction::Remove { conversation_id, label } => (
+                "im:remove_label",
+                serde_json::json!({
+                    "conversation_id": conversation_id,
+                    "label": label,
+                    "user_id": user_id,
+                }),
+            ),
+            LabelAction::List { conversation_id } => (
+                "im:list_labels",
+                serde_json::json!({
+                    "conversation_id": conversation_id,
+                    "user_id": user_id,
+                }),
+            ),
+            LabelAction::Search { label } => (
+                "im:search_by_label",
+                serde_json::json!({
+                    "label": label,
+                    "user_id": user_id,
+                }),
+            ),
+        }
+    }
+}
+
+/// 统计时间窗口
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum StatsWindow {
+    /// 最近 7 天
+    #[value(name = "7d")]
+    Last7Days,
+    /// 最近 30 天
+    #[value(name = "30d")]
+    Last30Days,
+    /// 全部
+    All,
+}
+
+impl StatsWindow {
+    /// IM Skill 中 `TimeWindow` 的序列化名称
+    fn as_str(&self) -> &'static str {
+        match self {
+            StatsWindow::Last7Days => "last7_days",
+            StatsWindow::Last30Days => "last30_days",
+            StatsWindow::All => "all_time",
+        }
+    }
+}
+
 /// 处理 IM 命令
 pub async fn handle_im(args: ImArgs) -> Result<()> {
     match args.action {
@@ -154,6 +303,21 @@ pub async fn handle_im(args: ImArgs) -> Result<()> {
         ImAction::Info(info_args) => {
             handle_info(info_args).await?;
         }
+        ImAction::Summarize(summarize_args) => {
+            handle_summarize(summarize_args).await?;
+        }
+        ImAction::Stats(stats_args) => {
+            handle_stats(stats_args).await?;
+        }
+        ImAction::Label(label_args) => {
+            handle_label(label_args).await?;
+        }
+        ImAction::IndexRebuild(index_args) => {
+            handle_index_rebuild(index_args).await?;
+        }
+        ImAction::IndexCheck(index_args) => {
+            handle_index_check(index_args).await?;
+        }
     }
 
     Ok(())
@@ -232,6 +396,7 @@ async fn handle_list(args: ListArgs) -> Result<()> {
                 data: serde_json::json!({
                     "user_id": user_id,
                     "limit": args.limit,
+                    "label_filter": args.labels,
                 }),
             };
             
@@ -284,6 +449,7 @@ async fn handle_history(args: HistoryArgs) -> Result<()> {
             match skill_manager.send_event("im", event).await {
                 Ok(()) => {
                     println!("✅ 已请求消息历史（异步处理）");
+                    println!("   签名标记: ✅ 已验证  ⚠️ 未验证  ⬜ 未签名");
                 }
                 Err(e) => {
                     eprintln!("❌ 获取消息历史失败: {}", e);
@@ -301,6 +467,178 @@ async fn handle_history(args: HistoryArgs) -> Result<()> {
     Ok(())
 }
 
+/// 处理生成会话摘要
+async fn handle_summarize(args: SummarizeArgs) -> Result<()> {
+    println!("📝 生成会话 {} 的摘要（最近 {} 条消息）", args.conversation_id, args.max_messages);
+
+    // 通过 SkillManager 调用 IM Skill
+    let db_manager = Arc::new(DbManager::new()?);
+    let skill_manager = SkillManager::new(db_manager)?;
+
+    match skill_manager.is_loaded("im") {
+        Ok(true) => {
+            let event = cis_core::skill::Event::Custom {
+                name: "im:summarize".to_string(),
+                data: serde_json::json!({
+                    "conversation_id": args.conversation_id,
+                    "max_messages": args.max_messages,
+                }),
+            };
+
+            match skill_manager.send_event("im", event).await {
+                Ok(()) => {
+                    println!("✅ 已请求生成摘要（异步处理），完成后摘要将作为消息出现在会话历史中");
+                }
+                Err(e) => {
+                    eprintln!("❌ 生成摘要失败: {}", e);
+                }
+            }
+        }
+        Ok(false) => {
+            println!("⚠️  IM Skill 未加载，请先加载: cis skill load im");
+        }
+        Err(e) => {
+            eprintln!("❌ 检查 IM Skill 状态失败: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// 处理查看会话统计
+async fn handle_stats(args: StatsArgs) -> Result<()> {
+    println!("📊 会话 {} 的统计（{}）", args.conversation_id, args.window.as_str());
+
+    // 通过 SkillManager 调用 IM Skill
+    let db_manager = Arc::new(DbManager::new()?);
+    let skill_manager = SkillManager::new(db_manager)?;
+
+    match skill_manager.is_loaded("im") {
+        Ok(true) => {
+            let event = cis_core::skill::Event::Custom {
+                name: "im:stats".to_string(),
+                data: serde_json::json!({
+                    "conversation_id": args.conversation_id,
+                    "window": args.window.as_str(),
+                }),
+            };
+
+            match skill_manager.send_event("im", event).await {
+                Ok(()) => {
+                    println!("✅ 已请求会话统计（异步处理）");
+                    println!("   指标: 消息数、活跃用户、日均消息、最活跃用户、平均响应时间、高峰时段");
+                }
+                Err(e) => {
+                    eprintln!("❌ 获取会话统计失败: {}", e);
+                }
+            }
+        }
+        Ok(false) => {
+            println!("⚠️  IM Skill 未加载，请先加载: cis skill load im");
+        }
+        Err(e) => {
+            eprintln!("❌ 检查 IM Skill 状态失败: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// 处理会话标签
+async fn handle_label(args: LabelArgs) -> Result<()> {
+    let user_id = args.user.as_deref().unwrap_or("current_user");
+    let (name, data) = args.action.to_event(user_id);
+    println!("🏷️  {} ({})", name, user_id);
+
+    // 通过 SkillManager 调用 IM Skill
+    let db_manager = Arc::new(DbManager::new()?);
+    let skill_manager = SkillManager::new(db_manager)?;
+
+    match skill_manager.is_loaded("im") {
+        Ok(true) => {
+            let event = cis_core::skill::Event::Custom {
+                name: name.to_string(),
+                data,
+            };
+
+            match skill_manager.send_event("im", event).await {
+                Ok(()) => {
+                    println!("✅ 已提交标签操作（异步处理）");
+                }
+                Err(e) => {
+                    eprintln!("❌ 标签操作失败: {}", e);
+                }
+            }
+        }
+        Ok(false) => {
+            println!("⚠️  IM Skill 未加载，请先加载: cis skill load im");
+        }
+        Err(e) => {
+            eprintln!("❌ 检查 IM Skill 状态失败: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// 打开本地 IM 数据库，`im.db` 不存在时返回 None
+pub fn open_local_im_db(data_dir: Option<&Path>) -> Result<Option<ImDatabase>> {
+    let data_dir = data_dir.map(Path::to_path_buf).unwrap_or_else(|| Paths::skill_data_dir("im"));
+    if !data_dir.join("im.db").exists() {
+        return Ok(None);
+    }
+    Ok(Some(ImDatabase::open(&data_dir)?))
+}
+
+/// 处理全文索引重建
+async fn handle_index_rebuild(args: IndexArgs) -> Result<()> {
+    let Some(db) = open_local_im_db(args.data_dir.as_deref())? else {
+        println!("⚠️  未找到 IM 数据库");
+        return Ok(());
+    };
+
+    println!("🔨 重建消息全文索引...");
+    let report = db.rebuild_fts_index().await?;
+    println!("✅ 索引重建完成（{} ms）", report.duration_ms);
+    println!("   索引文档: {}", report.documents_indexed);
+    if report.removed_documents > 0 || report.added_documents > 0 {
+        println!("   重新同步: 删除 {} 条，补齐 {} 条", report.removed_documents, report.added_documents);
+    }
+
+    Ok(())
+}
+
+/// 处理全文索引检查
+async fn handle_index_check(args: IndexArgs) -> Result<()> {
+    let Some(db) = open_local_im_db(args.data_dir.as_deref())? else {
+        println!("⚠️  未找到 IM 数据库");
+        return Ok(());
+    };
+
+    let report = db.check_fts_integrity().await?;
+    if !report.index_exists {
+        println!("ℹ️  尚未创建消息全文索引");
+        return Ok(());
+    }
+
+    println!("🔎 消息全文索引检查:");
+    println!("   索引文档: {}", report.indexed_documents);
+    println!("   缺失文档: {}", report.missing_documents);
+    println!("   孤立文档: {}", report.orphaned_documents);
+    println!("   过期文档: {}", report.stale_documents);
+    for error in &report.errors {
+        println!("   ❌ {}", error);
+    }
+
+    if report.is_healthy() {
+        println!("✅ 索引一致");
+    } else {
+        println!("❌ 索引不一致，请运行: cis im index-rebuild");
+    }
+
+    Ok(())
+}
+
 /// 处理搜索消息
 async fn handle_search(args: SearchArgs) -> Result<()> {
     println!("🔍 搜索消息: {}", args.query);
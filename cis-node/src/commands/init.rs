@@ -1,10 +1,44 @@
 //! # Init Command
 //!
 //! Initialize CIS environment or project with interactive wizard.
+//!
+//! `--from-env` skips the wizard and builds the global configuration from
+//! `CIS_*` environment variables, for CI/CD and container deployments.
+
+use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use cis_core::init::{InitWizard, WizardResult};
-use tracing::info;
+use cis_core::storage::paths::Paths;
+use cis_core::wizard::ConfigGenerator;
+use tracing::{info, warn};
+
+/// Accepted values of `CIS_AI_PROVIDER`
+pub const SUPPORTED_PROVIDERS: &[&str] = &["claude", "kimi", "aider", "gemini", "opencode"];
+
+/// `CIS_*` variables read at runtime by other subsystems (not config fields)
+const RUNTIME_ENV_VARS: &[&str] = &[
+    "CIS_AGENT",
+    "CIS_CONFIG",
+    "CIS_DISCOVER",
+    "CIS_HOLE_PUNCH",
+    "CIS_NODE_ID",
+    "CIS_PORTABLE",
+    "CIS_PROJECT_PATH",
+    "CIS_PROJECT_ROOT",
+    "CIS_SANDBOX_STRICT",
+    "CIS_SESSION_ID",
+    "CIS_SOCKET_DIR",
+];
+
+/// Prefixes of runtime `CIS_*` variable families
+const RUNTIME_ENV_PREFIXES: &[&str] = &[
+    "CIS_CONFIG_",
+    "CIS_DECISION_",
+    "CIS_NETWORK_",
+    "CIS_PUNCH_",
+    "CIS_WORKER_",
+];
 
 /// Initialize global CIS environment with full wizard
 pub async fn init_global() -> Result<()> {
@@ -35,6 +69,10 @@ pub async fn init_project() -> Result<()> {
 
 /// Initialize with custom options
 pub async fn init_with_options(options: InitOptions) -> Result<()> {
+    if options.from_env {
+        return init_from_env(&options).await;
+    }
+
     let mut wizard = InitWizard::new();
 
     if options.non_interactive {
@@ -60,6 +98,198 @@ pub async fn init_with_options(options: InitOptions) -> Result<()> {
     Ok(())
 }
 
+/// Bootstrap the global configuration from `CIS_*` environment variables
+///
+/// Unknown `CIS_*` variables are reported and ignored. An existing config is
+/// only replaced with `--force`, or after confirmation when interactive.
+pub async fn init_from_env(options: &InitOptions) -> Result<()> {
+    info!("Initializing CIS from environment variables...");
+
+    let env = EnvConfig::from_env()?;
+    for name in &env.unknown {
+        warn!("Ignoring unknown environment variable {}", name);
+    }
+
+    let config_path = Paths::config_file();
+    if config_path.exists() && !options.force {
+        if options.non_interactive {
+            bail!(
+                "Configuration already exists at {}. Use --force to overwrite.",
+                config_path.display()
+            );
+        }
+
+        print!("Configuration already exists. Overwrite? (y/N): ");
+        std::io::Write::flush(&mut std::io::stdout())?;
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+
+        if input.trim().to_lowercase() != "y" {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let provider = env.ai_provider.clone().or_else(|| options.preferred_provider.clone());
+    let generated = ConfigGenerator::new().generate_global_config(provider.as_deref())?;
+    let mut config: toml::Table =
+        toml::from_str(&generated).context("Generated configuration is not valid TOML")?;
+    env.apply(&mut config);
+
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&config_path, toml::to_string_pretty(&config)?)?;
+
+    if let Some(key) = &env.node_key {
+        let data_dir = env.data_dir.clone().unwrap_or_else(Paths::data_dir);
+        let key_path = data_dir.join("node.key");
+        std::fs::create_dir_all(&data_dir)?;
+        std::fs::write(&key_path, hex::decode(key)?)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&key_path, std::fs::Permissions::from_mode(0o600))?;
+        }
+    }
+
+    println!("\n✅ 初始化完成！");
+    println!("\n📁 生成的文件:");
+    println!("   • {}", config_path.display());
+    if !env.unknown.is_empty() {
+        println!("\n⚠️  已忽略未知环境变量: {}", env.unknown.join(", "));
+    }
+
+    Ok(())
+}
+
+/// Configuration collected from `CIS_*` environment variables
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnvConfig {
+    /// `CIS_AI_PROVIDER` → `ai.default_provider`
+    pub ai_provider: Option<String>,
+    /// `CIS_NODE_KEY` (64 hex characters) → `node.key`
+    pub node_key: Option<String>,
+    /// `CIS_NODE_NAME` → `node.name`
+    pub node_name: Option<String>,
+    /// `CIS_DATA_DIR` → `storage.data_dir`
+    pub data_dir: Option<PathBuf>,
+    /// `CIS_MEMORY_PATH` → `storage.memory_path`
+    pub memory_path: Option<PathBuf>,
+    /// `CIS_P2P_BOOTSTRAP` (comma separated) → `p2p.bootstrap_nodes`
+    pub p2p_bootstrap: Vec<String>,
+    /// `CIS_P2P_PORT` → `p2p.listen_port`
+    pub p2p_port: Option<u16>,
+    /// Unrecognized `CIS_*` variables
+    pub unknown: Vec<String>,
+}
+
+impl EnvConfig {
+    /// Read from the process environment
+    pub fn from_env() -> Result<Self> {
+        Self::from_vars(std::env::vars())
+    }
+
+    /// Read from the given variables; empty values count as unset
+    pub fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Result<Self> {
+        let mut config = Self::default();
+
+        for (name, value) in vars {
+            if !name.starts_with("CIS_") {
+                continue;
+            }
+            let value = value.trim().to_string();
+            if value.is_empty() {
+                continue;
+            }
+
+            match name.as_str() {
+                "CIS_AI_PROVIDER" => {
+                    let provider = value.to_lowercase();
+                    if !SUPPORTED_PROVIDERS.contains(&provider.as_str()) {
+                        bail!(
+                            "Unsupported CIS_AI_PROVIDER '{}' (expected one of: {})",
+                            value,
+                            SUPPORTED_PROVIDERS.join(", ")
+                        );
+                    }
+                    config.ai_provider = Some(provider);
+                }
+                "CIS_NODE_KEY" => {
+                    if value.len() != 64 || !value.chars().all(|c| c.is_ascii_hexdigit()) {
+                        bail!("CIS_NODE_KEY must be 64 hex characters (32 bytes)");
+                    }
+                    config.node_key = Some(value.to_lowercase());
+                }
+                "CIS_NODE_NAME" => config.node_name = Some(value),
+                "CIS_DATA_DIR" => config.data_dir = Some(PathBuf::from(value)),
+                "CIS_MEMORY_PATH" => config.memory_path = Some(PathBuf::from(value)),
+                "CIS_P2P_BOOTSTRAP" => {
+                    config.p2p_bootstrap = value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                        .map(str::to_string)
+                        .collect();
+                }
+                "CIS_P2P_PORT" => {
+                    config.p2p_port = Some(
+                        value
+                            .parse()
+                            .with_context(|| format!("Invalid CIS_P2P_PORT '{}'", value))?,
+                    );
+                }
+                _ if RUNTIME_ENV_VARS.contains(&name.as_str())
+                    || RUNTIME_ENV_PREFIXES.iter().any(|p| name.starts_with(p)) => {}
+                _ => config.unknown.push(name),
+            }
+        }
+
+        config.unknown.sort();
+        Ok(config)
+    }
+
+    /// Write the collected values into a generated global config
+    pub fn apply(&self, config: &mut toml::Table) {
+        if let Some(provider) = &self.ai_provider {
+            section(config, "ai").insert("default_provider".into(), provider.clone().into());
+        }
+        if let Some(key) = &self.node_key {
+            section(config, "node").insert("key".into(), key.clone().into());
+        }
+        if let Some(name) = &self.node_name {
+            section(config, "node").insert("name".into(), name.clone().into());
+        }
+        if let Some(dir) = &self.data_dir {
+            section(config, "storage").insert("data_dir".into(), dir.display().to_string().into());
+        }
+        if let Some(path) = &self.memory_path {
+            section(config, "storage").insert("memory_path".into(), path.display().to_string().into());
+        }
+        if !self.p2p_bootstrap.is_empty() {
+            let p2p = section(config, "p2p");
+            p2p.insert("enabled".into(), true.into());
+            p2p.insert("bootstrap_nodes".into(), self.p2p_bootstrap.clone().into());
+        }
+        if let Some(port) = self.p2p_port {
+            section(config, "p2p").insert("listen_port".into(), i64::from(port).into());
+        }
+    }
+}
+
+/// Get or create a top-level table
+fn section<'a>(config: &'a mut toml::Table, name: &str) -> &'a mut toml::Table {
+    let value = config
+        .entry(name)
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    if !value.is_table() {
+        *value = toml::Value::Table(toml::Table::new());
+    }
+    value.as_table_mut().expect("section is a table")
+}
+
 /// Display wizard result
 fn display_result(result: &WizardResult) {
     if result.config_created || result.project_initialized {
@@ -98,6 +328,8 @@ pub struct InitOptions {
     pub preferred_provider: Option<String>,
     /// Non-interactive mode
     pub non_interactive: bool,
+    /// Build the global config from `CIS_*` environment variables
+    pub from_env: bool,
 }
 
 
@@ -110,3 +342,41 @@ pub async fn quick_init(project_mode: bool) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_env_config_from_vars() {
+        let key = "ab".repeat(32);
+        let env = EnvConfig::from_vars(vars(&[
+            ("CIS_AI_PROVIDER", "Kimi"),
+            ("CIS_NODE_KEY", &key),
+            ("CIS_DATA_DIR", "/var/lib/cis"),
+            ("CIS_P2P_BOOTSTRAP", "/ip4/10.0.0.1/tcp/7677, /ip4/10.0.0.2/tcp/7677"),
+            ("CIS_NETWORK_TCP_PORT", "7000"),
+            ("CIS_TYPO", "1"),
+            ("HOME", "/root"),
+        ]))
+        .unwrap();
+        assert_eq!(env.ai_provider.as_deref(), Some("kimi"));
+        assert_eq!(env.p2p_bootstrap.len(), 2);
+        assert_eq!(env.unknown, vec!["CIS_TYPO"]);
+
+        let mut config: toml::Table = toml::from_str("[ai]\ndefault_provider = \"claude\"\n").unwrap();
+        env.apply(&mut config);
+        assert_eq!(config["ai"]["default_provider"].as_str(), Some("kimi"));
+        assert_eq!(config["node"]["key"].as_str(), Some(key.as_str()));
+        assert_eq!(config["storage"]["data_dir"].as_str(), Some("/var/lib/cis"));
+        assert_eq!(config["p2p"]["enabled"].as_bool(), Some(true));
+
+        assert!(EnvConfig::from_vars(vars(&[("CIS_NODE_KEY", "abcd")])).is_err());
+        assert!(EnvConfig::from_vars(vars(&[("CIS_NODE_KEY", &"zz".repeat(32))])).is_err());
+        assert!(EnvConfig::from_vars(vars(&[("CIS_AI_PROVIDER", "gpt")])).is_err());
+    }
+}
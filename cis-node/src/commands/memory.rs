@@ -4,11 +4,18 @@
 //! Supports both keyword-based and semantic vector search.
 
 use anyhow::{Context, Result};
-use cis_core::memory::MemoryService;
+use cis_core::identity::{DIDManager, NodeClaimService};
+use cis_core::memory::{
+    is_active_namespace, MemoryService, MemorySyncService, NamespaceInfo, NodeEndpoint,
+};
+use cis_core::project::{ProjectManager, ProjectRegistry};
+use cis_core::storage::federation_db::FederationDb;
 use cis_core::types::{MemoryCategory, MemoryDomain};
+use cis_core::ai::embedding::{create_embedding_service, EmbeddingConfig, EmbeddingProvider};
 use cis_core::vector::{VectorStorage, MemoryResult};
 use cis_core::storage::paths::Paths;
 use clap::{Args, Subcommand, ValueEnum};
+use std::sync::Arc;
 
 /// Output format for search results
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -184,6 +191,9 @@ pub fn get_memory(key: &str) -> Result<()> {
             println!("  Updated:   {}", entry.updated_at.to_rfc3339());
             println!("  Version:   {}", entry.version);
             println!("  Encrypted: {}", entry.encrypted);
+            if !entry.tags.is_empty() {
+                println!("  Tags:      {}", entry.tags.join(", "));
+            }
             
             // Try to display value as string
             match String::from_utf8(entry.value.clone()) {
@@ -200,19 +210,36 @@ pub fn get_memory(key: &str) -> Result<()> {
 }
 
 /// Set a memory entry
-pub fn set_memory(key: &str, value: &str, domain: MemoryDomain, category: MemoryCategory) -> Result<()> {
+///
+/// Existing tags are kept unless `tags` is non-empty.
+pub fn set_memory(
+    key: &str,
+    value: &str,
+    domain: MemoryDomain,
+    category: MemoryCategory,
+    tags: &[String],
+) -> Result<()> {
     let node_id = format!("node-{}", uuid::Uuid::new_v4());
     let service = MemoryService::open_default(node_id)?;
     
     tokio::runtime::Handle::current().block_on(service.set(key, value.as_bytes(), domain, category))
         .with_context(|| format!("Failed to set memory for key '{}'", key))?;
     
+    if !tags.is_empty() {
+        tokio::runtime::Handle::current().block_on(service.set_tags(key, tags))
+            .with_context(|| format!("Failed to tag memory '{}'", key))?;
+    }
+    
     let domain_str = match domain {
         MemoryDomain::Private => "private",
         MemoryDomain::Public => "public",
     };
     
-    println!("✅ Memory set: {} (domain: {})", key, domain_str);
+    if tags.is_empty() {
+        println!("✅ Memory set: {} (domain: {})", key, domain_str);
+    } else {
+        println!("✅ Memory set: {} (domain: {}, tags: {})", key, domain_str, tags.join(", "));
+    }
     
     Ok(())
 }
@@ -234,42 +261,85 @@ pub fn delete_memory(key: &str) -> Result<()> {
     Ok(())
 }
 
-/// Search memory entries (keyword-based)
-pub async fn search_memory(query: &str, limit: Option<usize>) -> Result<()> {
+/// Search memory entries (keyword-based), optionally restricted to a tag
+///
+/// With only a tag, lists every entry carrying that tag.
+pub async fn search_memory(query: Option<&str>, tag: Option<&str>, limit: Option<usize>) -> Result<()> {
     let node_id = format!("node-{}", uuid::Uuid::new_v4());
     let service = MemoryService::open_default(node_id)?;
-    
-    let options = cis_core::memory::SearchOptions {
-        limit: limit.unwrap_or(100),
-        ..Default::default()
+    let limit = limit.unwrap_or(100);
+    
+    let mut results = match query {
+        Some(query) => {
+            let options = cis_core::memory::SearchOptions {
+                limit,
+                ..Default::default()
+            };
+            service.search(query, options).await?
+        }
+        None => Vec::new(),
     };
+    if let Some(tag) = tag {
+        if query.is_some() {
+            results.retain(|entry| entry.tags.iter().any(|t| t == tag));
+        } else {
+            results = service.list_by_tag(tag).await?;
+            results.truncate(limit);
+        }
+    }
     
-    let results = service.search(query, options).await?;
+    let description = match (query, tag) {
+        (Some(query), Some(tag)) => format!("'{}' with tag '{}'", query, tag),
+        (Some(query), None) => format!("'{}'", query),
+        (None, Some(tag)) => format!("tag '{}'", tag),
+        (None, None) => String::new(),
+    };
     
     if results.is_empty() {
-        println!("No memory entries found matching query: {}", query);
+        println!("No memory entries found matching {}", description);
         return Ok(());
     }
     
-    println!("Found {} memory entries matching '{}':", results.len(), query);
-    println!("{:<30} {:<10} {:<12} Updated", "Key", "Domain", "Category");
+    println!("Found {} memory entries matching {}:", results.len(), description);
+    println!("{:<30} {:<10} {:<12} {:<17} Tags", "Key", "Domain", "Category", "Updated");
     println!("{}", "-".repeat(90));
     
     for entry in results {
         let updated = entry.updated_at.format("%Y-%m-%d %H:%M").to_string();
         
         println!(
-            "{:<30} {:<10} {:<12} {}",
+            "{:<30} {:<10} {:<12} {:<17} {}",
             entry.key,
             format!("{:?}", entry.domain).to_lowercase(),
             format!("{:?}", entry.category).to_lowercase(),
-            updated
+            updated,
+            entry.tags.join(", ")
         );
     }
     
     Ok(())
 }
 
+/// List all memory tags with their entry counts
+pub async fn list_tags() -> Result<()> {
+    let node_id = format!("node-{}", uuid::Uuid::new_v4());
+    let service = MemoryService::open_default(node_id)?;
+    
+    let tags = service.list_tags().await?;
+    if tags.is_empty() {
+        println!("No memory tags found.");
+        return Ok(());
+    }
+    
+    println!("{:<30} Entries", "Tag");
+    println!("{}", "-".repeat(40));
+    for (tag, count) in tags {
+        println!("{:<30} {}", tag, count);
+    }
+    
+    Ok(())
+}
+
 /// List memory keys with optional prefix
 pub fn list_memory(prefix: Option<&str>, domain: Option<MemoryDomain>) -> Result<()> {
     let node_id = format!("node-{}", uuid::Uuid::new_v4());
@@ -351,6 +421,185 @@ pub fn export_memory(since: Option<i64>, output: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// Synchronize public memory with a peer (`cis memory sync --peer <node_id>`)
+///
+/// The peer endpoint is resolved from the federation database (see `cis peer add`).
+pub async fn sync_memory(peer_id: &str) -> Result<()> {
+    let db_path = Paths::data_dir().join("federation.db");
+    let federation = FederationDb::open(&db_path)
+        .context("Failed to open federation database")?;
+
+    let peer = federation
+        .get_peer(peer_id)?
+        .ok_or_else(|| anyhow::anyhow!("Peer '{}' not found. Add it with 'cis peer add'", peer_id))?;
+    let endpoint = peer
+        .endpoint_ws
+        .ok_or_else(|| anyhow::anyhow!("Peer '{}' has no WebSocket endpoint", peer_id))?;
+
+    let identity = local_identity()?;
+    let service = MemoryService::open_default(identity.node_id().to_string())?;
+    let sync = MemorySyncService::new(Arc::new(service)).with_identity(Arc::new(identity));
+
+    println!("🔄 Syncing public memory with {} at {}...", peer_id, endpoint);
+    let report = sync
+        .sync_with_peer(&NodeEndpoint::new(peer_id, endpoint))
+        .await
+        .with_context(|| format!("Memory sync with {} failed", peer_id))?;
+
+    if report.already_in_sync {
+        println!("✅ Already in sync (Merkle roots match)");
+        return Ok(());
+    }
+
+    println!("✅ Sync complete");
+    println!("  Added:              {}", report.added);
+    println!("  Updated:            {}", report.updated);
+    println!("  Pushed:             {}", report.pushed);
+    println!("  Conflicts resolved: {}", report.conflicts_resolved);
+
+    Ok(())
+}
+
+/// This node's identity: `[node] id` (or `CIS_NODE_ID`) signed with the node key
+///
+/// The node key is unsealed through its hardware attestation when the node was claimed.
+fn local_identity() -> Result<DIDManager> {
+    let node_id = match std::env::var("CIS_NODE_ID") {
+        Ok(node_id) => node_id,
+        Err(_) => {
+            let content = std::fs::read_to_string(Paths::config_file())
+                .context("Failed to read configuration. Run 'cis init' first")?;
+            let config: toml::Table = toml::from_str(&content).context("Invalid configuration")?;
+            config
+                .get("node")
+                .and_then(|node| node.get("id"))
+                .and_then(|id| id.as_str())
+                .map(str::to_string)
+                .ok_or_else(|| anyhow::anyhow!("Configuration has no [node] id"))?
+        }
+    };
+
+    let key = NodeClaimService::open_default()
+        .load_node_key()
+        .context("Failed to load the node key")?;
+    Ok(DIDManager::from_seed(&key, node_id)?)
+}
+
+/// Garbage-collect memory (`cis memory gc --orphans`)
+///
+/// Active projects are the ones in the project registry whose `.cis/project.toml`
+/// still exists, plus the project containing the current directory (which is
+/// registered if it is not yet). Only namespaces of registered projects that no
+/// longer exist are deleted; namespaces with no registry entry, e.g. projects
+/// created before the registry existed, are listed separately and kept.
+pub async fn gc_memory(orphans: bool, dry_run: bool, yes: bool) -> Result<()> {
+    if !orphans {
+        anyhow::bail!("Nothing to collect. Use --orphans to remove memory of deleted projects");
+    }
+
+    let registry = ProjectRegistry::open_default();
+    if let Some(project) = std::env::current_dir()
+        .ok()
+        .and_then(|dir| ProjectManager::find_project(&dir))
+    {
+        let config = &project.config;
+        let registered = registry.list()?.iter().any(|p| p.root == config.root_dir);
+        if !registered {
+            registry
+                .register(&config.root_dir, &config.memory.namespace)
+                .context("Failed to register the current project")?;
+        }
+    }
+
+    let projects = registry.list()?;
+    let registered: Vec<String> = projects.iter().map(|p| p.namespace.clone()).collect();
+    let active: Vec<String> = projects
+        .into_iter()
+        .filter(|p| p.exists())
+        .map(|p| p.namespace)
+        .collect();
+
+    let service = MemoryService::open_default("memory-gc".to_string())?;
+    let (orphaned, unregistered) =
+        partition_inactive(service.list_project_namespaces().await?, &registered, &active);
+
+    if !unregistered.is_empty() {
+        println!("Unregistered project namespaces (kept):");
+        for ns in &unregistered {
+            print_namespace(ns);
+        }
+        println!("  Run this command inside a project directory to register that project.");
+        println!();
+    }
+
+    if orphaned.is_empty() {
+        println!("✅ No orphaned project namespaces");
+        return Ok(());
+    }
+
+    println!("Orphaned project namespaces:");
+    for ns in &orphaned {
+        print_namespace(ns);
+    }
+
+    if dry_run {
+        println!();
+        println!("Dry run: nothing deleted");
+        return Ok(());
+    }
+
+    if !yes {
+        print!("Delete {} namespace(s)? (y/N): ", orphaned.len());
+        use std::io::Write;
+        std::io::stdout().flush()?;
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+
+        if input.trim().to_lowercase() != "y" {
+            println!("Cancelled");
+            return Ok(());
+        }
+    }
+
+    let keep: Vec<String> = active
+        .into_iter()
+        .chain(unregistered.into_iter().map(|ns| ns.namespace))
+        .collect();
+    let report = service.garbage_collect_orphans(&keep).await?;
+    println!(
+        "✅ Deleted {} namespace(s), {} keys, freed {} bytes",
+        report.deleted_namespaces.len(),
+        report.deleted_keys,
+        report.freed_bytes
+    );
+
+    Ok(())
+}
+
+/// Split inactive project namespaces into orphans of registered projects and
+/// namespaces that have no registry entry
+fn partition_inactive(
+    namespaces: Vec<NamespaceInfo>,
+    registered: &[String],
+    active: &[String],
+) -> (Vec<NamespaceInfo>, Vec<NamespaceInfo>) {
+    namespaces
+        .into_iter()
+        .filter(|ns| !is_active_namespace(&ns.namespace, active))
+        .partition(|ns| is_active_namespace(&ns.namespace, registered))
+}
+
+fn print_namespace(ns: &NamespaceInfo) {
+    println!(
+        "  {:<32} {:>6} keys {:>10} bytes  last updated {}",
+        ns.namespace,
+        ns.key_count,
+        ns.total_bytes,
+        ns.last_accessed.format("%Y-%m-%d %H:%M")
+    );
+}
+
 /// Memory subcommands for additional operations
 #[derive(Subcommand, Debug)]
 pub enum MemoryAction {
@@ -374,6 +623,19 @@ pub enum MemoryAction {
         #[arg(short, long)]
         domain: Option<String>,
     },
+
+    /// Re-embed stored vectors with a new embedding model (resumable)
+    Reindex {
+        /// Embedding model: local, openai, auto, or a local model path
+        #[arg(long)]
+        model: String,
+        /// Number of vectors per batch
+        #[arg(long, default_value = "100")]
+        batch_size: usize,
+        /// Discard an interrupted reindex and start over
+        #[arg(long)]
+        restart: bool,
+    },
 }
 
 /// Handle memory subcommands
@@ -382,6 +644,9 @@ pub async fn handle_memory_action(action: MemoryAction) -> Result<()> {
         MemoryAction::Status { detailed } => show_memory_status(detailed).await,
         MemoryAction::RebuildIndex { force } => rebuild_vector_index(force).await,
         MemoryAction::Stats { domain } => show_memory_stats(domain.as_deref()).await,
+        MemoryAction::Reindex { model, batch_size, restart } => {
+            reindex_vectors(&model, batch_size, restart).await
+        }
     }
 }
 
@@ -513,6 +778,76 @@ async fn rebuild_vector_index(force: bool) -> Result<()> {
     Ok(())
 }
 
+/// Build an embedding config from a `--model` name
+fn embedding_config_for_model(model: &str) -> Result<EmbeddingConfig> {
+    let mut config = EmbeddingConfig::default();
+    match model.to_lowercase().as_str() {
+        "auto" => config.provider = EmbeddingProvider::Auto,
+        "local" | "nomic" | "nomic-embed-text-v1.5" => config.provider = EmbeddingProvider::Local,
+        "openai" | "text-embedding-3-small" => config.provider = EmbeddingProvider::OpenAI,
+        _ if std::path::Path::new(model).exists() => {
+            config.provider = EmbeddingProvider::Local;
+            config.model_path = Some(model.to_string());
+        }
+        _ => anyhow::bail!(
+            "Unknown embedding model '{}'. Use: local, openai, auto, or a model path",
+            model
+        ),
+    }
+    Ok(config)
+}
+
+/// Re-embed all memory vectors with a new embedding model
+async fn reindex_vectors(model: &str, batch_size: usize, restart: bool) -> Result<()> {
+    let vector_db_path = Paths::vector_db();
+    if !vector_db_path.exists() {
+        println!("❌ Vector database not found: {}", vector_db_path.display());
+        return Ok(());
+    }
+
+    let config = embedding_config_for_model(model)?;
+    let provider = create_embedding_service(Some(&config))
+        .await
+        .with_context(|| format!("Failed to load embedding model '{}'", model))?;
+    let storage = VectorStorage::open_with_service(&vector_db_path, provider.clone())?;
+
+    if restart {
+        storage.reset_reindex()?;
+    } else if let Some(progress) = storage.reindex_progress()? {
+        println!(
+            "↻ Resuming interrupted reindex ({}/{} done)",
+            progress.handled(),
+            progress.total
+        );
+    }
+
+    println!("🔧 Re-embedding memory vectors with '{}'...", model);
+    loop {
+        let progress = storage
+            .incremental_reindex(provider.as_ref(), batch_size)
+            .await?;
+        println!(
+            "   {:>5.1}%  {}/{} re-embedded, {} failed, ETA {}s",
+            progress.percent(),
+            progress.processed,
+            progress.total,
+            progress.failed,
+            progress.eta_secs
+        );
+        if progress.done {
+            println!();
+            println!("✅ Reindex complete");
+            if progress.failed > 0 {
+                println!(
+                    "   {} vectors had no stored source text or failed to embed",
+                    progress.failed
+                );
+            }
+            return Ok(());
+        }
+    }
+}
+
 /// Show memory statistics
 async fn show_memory_stats(domain_filter: Option<&str>) -> Result<()> {
     println!("📊 Memory Statistics");
@@ -609,3 +944,35 @@ async fn show_memory_stats(domain_filter: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn namespace(name: &str) -> NamespaceInfo {
+        NamespaceInfo {
+            namespace: name.to_string(),
+            key_count: 1,
+            total_bytes: 1,
+            last_accessed: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_unregistered_namespaces_are_not_orphans() {
+        let namespaces = vec![
+            namespace("project/alive"),
+            namespace("project/gone"),
+            namespace("project/legacy"),
+        ];
+        let registered = vec!["project/alive".to_string(), "project/gone".to_string()];
+        let active = vec!["project/alive".to_string()];
+
+        let (orphaned, unregistered) = partition_inactive(namespaces, &registered, &active);
+
+        let names = |list: &[NamespaceInfo]| -> Vec<String> {
+            list.iter().map(|ns| ns.namespace.clone()).collect()
+        };
+        assert_eq!(names(&orphaned), vec!["project/gone"]);
+        assert_eq!(names(&unregistered), vec!["project/legacy"]);
+    }
+}
@@ -20,6 +20,7 @@ pub mod pair;
 pub mod node;
 pub mod peer;
 pub mod project;
+pub mod push;
 #[cfg(feature = "p2p")]
 pub mod p2p;
 pub mod schema;
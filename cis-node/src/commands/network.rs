@@ -9,7 +9,9 @@
 //! - `cis network unallow <did>` - Remove from whitelist
 //! - `cis network undeny <did>` - Remove from blacklist
 //! - `cis network unquarantine <did>` - Remove from quarantine
-//! - `cis network list [whitelist|blacklist|quarantine]` - List entries
+//! - `cis network list [whitelist|blacklist|quarantine|trust]` - List entries
+//! - `cis network trust <did> <block|read|write>` - Set DID trust level
+//! - `cis network import <path.json>` - Batch-import trust levels
 //! - `cis network acl sync` - Sync ACL from peers
 //! - `cis network rules` - Manage ACL rules
 //!
@@ -31,12 +33,15 @@
 //! # List with format
 //! cis network list --format json
 //!
+//! # Grant a peer read-only access
+//! cis network trust did:cis:peer:abc123 read --note "Office laptop"
+//!
 //! # Sync ACL
 //! cis network acl sync --broadcast
 //! ```
 
 use clap::{Subcommand, ValueEnum};
-use cis_core::network::{NetworkAcl, NetworkMode, AclEntry, AclAction};
+use cis_core::network::{NetworkAcl, NetworkMode, AclEntry, AclAction, TrustLevel, TrustStore};
 use cis_core::network::acl_rules::{AclRule, AclRulesEngine, Condition, RuleContext};
 
 /// Network management commands
@@ -109,7 +114,7 @@ pub enum NetworkCommands {
         did: String,
     },
     
-    /// List whitelist, blacklist, or quarantine entries
+    /// List whitelist, blacklist, quarantine, or trust entries
     List {
         /// Type of list to show
         #[arg(value_enum)]
@@ -119,6 +124,26 @@ pub enum NetworkCommands {
         format: OutputFormat,
     },
     
+    /// Add or update the trust level of a DID
+    Trust {
+        /// DID (did:cis:*, did:key:*, did:web:* or did:peer:*)
+        did: String,
+        /// Trust level
+        #[arg(value_enum)]
+        level: TrustLevelArg,
+        /// Note stored with the entry
+        #[arg(short, long)]
+        note: Option<String>,
+    },
+    
+    /// Batch-import trust levels from a JSON file
+    ///
+    /// The file is an array of `{"did": ..., "level": "block|read|write", "note": ...}`.
+    Import {
+        /// Path to the ACL file
+        path: std::path::PathBuf,
+    },
+    
     /// Sync ACL from/to peers
     Sync {
         /// Peer ID to sync from
@@ -247,6 +272,24 @@ pub enum ListType {
     Whitelist,
     Blacklist,
     Quarantine,
+    Trust,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TrustLevelArg {
+    Block,
+    Read,
+    Write,
+}
+
+impl From<TrustLevelArg> for TrustLevel {
+    fn from(level: TrustLevelArg) -> Self {
+        match level {
+            TrustLevelArg::Block => TrustLevel::Block,
+            TrustLevelArg::Read => TrustLevel::Read,
+            TrustLevelArg::Write => TrustLevel::Write,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
@@ -305,6 +348,12 @@ pub async fn handle(cmd: NetworkCommands) -> anyhow::Result<()> {
         NetworkCommands::List { list_type, format } => {
             list_entries(&acl_path, list_type, format).await?;
         }
+        NetworkCommands::Trust { did, level, note } => {
+            set_trust(&did, level.into(), note.as_deref())?;
+        }
+        NetworkCommands::Import { path } => {
+            import_trust(&path)?;
+        }
         NetworkCommands::Sync { from, broadcast } => {
             sync_acl(from, broadcast).await?;
         }
@@ -639,11 +688,18 @@ async fn list_entries(
     list_type: Option<ListType>,
     format: OutputFormat,
 ) -> anyhow::Result<()> {
+    if list_type == Some(ListType::Trust) {
+        return list_trust(format);
+    }
+    
     let acl = if acl_path.exists() {
         NetworkAcl::load(acl_path)?
     } else {
         println!("Network ACL not initialized.");
-        return Ok(());
+        return match list_type {
+            None => list_trust(format),
+            Some(_) => Ok(()),
+        };
     };
     
     // If no type specified, show all
@@ -654,6 +710,7 @@ async fn list_entries(
     
     for list_type in types_to_show {
         let entries = match list_type {
+            ListType::Trust => continue,
             ListType::Whitelist => &acl.whitelist,
             ListType::Blacklist => &acl.blacklist,
             ListType::Quarantine => {
@@ -669,6 +726,7 @@ async fn list_entries(
             ListType::Whitelist => "Whitelist",
             ListType::Blacklist => "Blacklist",
             ListType::Quarantine => "Quarantine",
+            ListType::Trust => "Trust",
         };
         
         match format {
@@ -731,6 +789,72 @@ async fn list_entries(
         }
     }
     
+    if list_type.is_none() {
+        list_trust(format)?;
+    }
+    
+    Ok(())
+}
+
+fn open_trust_store() -> anyhow::Result<TrustStore> {
+    Ok(TrustStore::open(cis_core::network::default_trust_store_path())?)
+}
+
+/// List DID trust levels
+fn list_trust(format: OutputFormat) -> anyhow::Result<()> {
+    let entries = open_trust_store()?.list()?;
+    
+    match format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
+        OutputFormat::Table => {
+            println!("\n Trust ");
+            println!("Total: {} entries\n", entries.len());
+            
+            if entries.is_empty() {
+                println!("No entries.");
+                return Ok(());
+            }
+            
+            println!("{:<48} {:<6} {:<16} Note", "DID", "Level", "Updated");
+            println!("{}", "-".repeat(90));
+            for entry in &entries {
+                let updated = chrono::DateTime::from_timestamp(entry.updated_at, 0)
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| "Unknown".into());
+                println!(
+                    "{:<48} {:<6} {:<16} {}",
+                    entry.did,
+                    entry.level,
+                    updated,
+                    entry.note.as_deref().unwrap_or("")
+                );
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+/// Add or update a DID trust entry
+fn set_trust(did: &str, level: TrustLevel, note: Option<&str>) -> anyhow::Result<()> {
+    let store = open_trust_store()?;
+    let previous = store.trust_level(did)?;
+    store.set_trust(did, level, note)?;
+    
+    match previous {
+        Some(old) if old != level => println!("✅ {} trust changed: {} → {}", did, old, level),
+        _ => println!("✅ {} trusted at level {}", did, level),
+    }
+    
+    Ok(())
+}
+
+/// Batch-import trust entries from a JSON file
+fn import_trust(path: &std::path::Path) -> anyhow::Result<()> {
+    let count = open_trust_store()?.import_file(path)?;
+    println!("✅ Imported {} trust entries from {}", count, path.display());
     Ok(())
 }
 
@@ -976,6 +1100,8 @@ async fn sync_acl(from: Option<String>, broadcast: bool) -> anyhow::Result<()> {
             external_address: None,
             transport_config: cis_core::p2p::transport_secure::SecureTransportConfig::default(),
             node_keys: None,
+            max_bytes_per_second: 0,
+            reputation: Default::default(),
         };
         
         match cis_core::p2p::P2PNetwork::new(
@@ -1030,6 +1156,8 @@ async fn sync_acl(from: Option<String>, broadcast: bool) -> anyhow::Result<()> {
             external_address: None,
             transport_config: cis_core::p2p::transport_secure::SecureTransportConfig::default(),
             node_keys: None,
+            max_bytes_per_second: 0,
+            reputation: Default::default(),
         };
         
         match cis_core::p2p::P2PNetwork::new(
@@ -1280,6 +1408,8 @@ async fn broadcast_acl_update(acl: &NetworkAcl) {
         external_address: None,
         transport_config: cis_core::p2p::transport_secure::SecureTransportConfig::default(),
         node_keys: None,
+        max_bytes_per_second: 0,
+        reputation: Default::default(),
     };
     
     match cis_core::p2p::P2PNetwork::new(
@@ -4,9 +4,10 @@
 
 use anyhow::Result;
 use clap::{Subcommand, ValueEnum};
+use cis_core::identity::NodeClaimService;
 use cis_core::service::{
     node_service::{BindOptions, NodeService, TrustLevel as CoreTrustLevel},
-    ListOptions,
+    ListOptions, NodeSyncer,
 };
 
 /// Output format for CLI commands
@@ -124,14 +125,22 @@ pub enum NodeAction {
         count: u32,
     },
     
-    /// Sync data with a node
+    /// Sync data with a node, or refresh all known nodes if no ID is given
     Sync {
-        /// Node ID to sync with
-        node_id: String,
+        /// Node ID to sync with (omit to probe every known node)
+        node_id: Option<String>,
         
         /// Full sync (not just incremental)
         #[arg(long)]
         full: bool,
+        
+        /// Mark nodes unreachable for more than N days as stale
+        #[arg(long, default_value = "7")]
+        stale_days: u32,
+        
+        /// Remove stale nodes after refreshing
+        #[arg(long)]
+        prune_stale: bool,
     },
     
     /// Remove offline nodes
@@ -150,6 +159,9 @@ pub enum NodeAction {
         /// Node ID(s) (if not specified, shows all)
         node_ids: Vec<String>,
     },
+    
+    /// Bind this node's key to hardware (TPM 2.0 or Secure Enclave)
+    Claim,
 }
 
 /// Parse filter argument in format "key=value"
@@ -183,15 +195,21 @@ pub async fn handle(cmd: NodeAction) -> Result<()> {
         NodeAction::Ping { node_id, count } => {
             ping_node(&node_id, count).await
         }
-        NodeAction::Sync { node_id, full } => {
+        NodeAction::Sync { node_id: Some(node_id), full, .. } => {
             sync_node(&node_id, full).await
         }
+        NodeAction::Sync { node_id: None, stale_days, prune_stale, .. } => {
+            sync_all_nodes(stale_days, prune_stale).await
+        }
         NodeAction::Prune { max_offline_days, force } => {
             prune_nodes(max_offline_days, force).await
         }
         NodeAction::Stats { node_ids } => {
             show_node_stats(&node_ids).await
         }
+        NodeAction::Claim => {
+            claim_node().await
+        }
     }
 }
 
@@ -450,6 +468,65 @@ async fn sync_node(node_id: &str, full: bool) -> Result<()> {
     Ok(())
 }
 
+/// Probe every known node and refresh its status and endpoint
+async fn sync_all_nodes(stale_days: u32, prune_stale: bool) -> Result<()> {
+    use cis_core::service::node_sync::NodeSyncStatus;
+    use indicatif::{ProgressBar, ProgressStyle};
+    
+    let syncer = NodeSyncer::open_default()?.with_stale_days(stale_days);
+    
+    let total = syncer.peer_count()?;
+    if total == 0 {
+        println!("No known nodes. Use 'cis node bind <endpoint>' to add one.");
+        return Ok(());
+    }
+    
+    let progress = ProgressBar::new(total as u64);
+    progress.set_style(
+        ProgressStyle::with_template("{spinner} [{bar:30}] {pos}/{len} {msg}")
+            .unwrap_or_else(|_| ProgressStyle::default_bar())
+            .progress_chars("=> "),
+    );
+    
+    let report = syncer
+        .sync_with_progress(|node_id, status| {
+            if *status == NodeSyncStatus::Stale {
+                progress.println(format!("  {} is stale", node_id));
+            }
+            progress.set_message(truncate(node_id, 20));
+            progress.inc(1);
+        })
+        .await?;
+    progress.finish_and_clear();
+    
+    println!(
+        "Synced {} nodes: {} online, {} offline, {} stale",
+        report.total,
+        report.online.len(),
+        report.offline.len(),
+        report.stale.len()
+    );
+    for (node_id, old, new) in &report.endpoint_changed {
+        println!("  {} moved: {} -> {}", node_id, old, new);
+    }
+    
+    if prune_stale {
+        let removed = syncer.prune_stale()?;
+        if removed.is_empty() {
+            println!("No stale nodes to prune.");
+        } else {
+            println!("Pruned {} stale nodes:", removed.len());
+            for id in &removed {
+                println!("  - {}", id);
+            }
+        }
+    } else if !report.stale.is_empty() {
+        println!("Use 'cis node sync --prune-stale' to remove stale nodes.");
+    }
+    
+    Ok(())
+}
+
 /// Prune offline nodes
 async fn prune_nodes(max_offline_days: u32, force: bool) -> Result<()> {
     let service = NodeService::new()?;
@@ -526,6 +603,28 @@ async fn show_node_stats(node_ids: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// Bind the local node key to hardware
+async fn claim_node() -> Result<()> {
+    let service = NodeClaimService::open_default();
+    let bound = service.claim()?;
+    let attestation = bound.attestation();
+    
+    if bound.is_hardware_bound() {
+        println!("✓ Node key bound to {}", bound.backend());
+        println!("  The key can only be unsealed on this machine.");
+    } else {
+        println!("⚠ No TPM 2.0 or Secure Enclave available, node key remains software-only");
+    }
+    println!("  Fingerprint: {}", attestation.key_fingerprint);
+    println!("  Bound at:    {}", attestation.bound_at.format("%Y-%m-%d %H:%M:%S UTC"));
+    for (key, value) in &attestation.details {
+        println!("  {}: {}", key, value);
+    }
+    println!("  Attestation: {}", service.attestation_path().display());
+    
+    Ok(())
+}
+
 // Helper functions
 
 /// Format duration in short form
@@ -139,6 +139,23 @@ pub enum P2pAction {
         #[arg(long, value_enum, default_value = "all")]
         check: DiagnoseType,
     },
+    
+    /// 显示网络拓扑
+    Mesh {
+        /// 输出格式
+        #[arg(long, value_enum, default_value = "ascii")]
+        format: MeshFormat,
+    },
+    
+    /// 查看带宽统计 / 调整限速
+    Bandwidth {
+        /// 设置出站限速（字节/秒，0 表示不限速）
+        #[arg(long)]
+        limit: Option<u64>,
+    },
+    
+    /// 查看节点信誉评分
+    Reputation,
 }
 
 /// DHT 子命令
@@ -199,6 +216,15 @@ pub enum DiagnoseType {
     Port,
 }
 
+/// 拓扑输出格式
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum MeshFormat {
+    /// 终端 ASCII 图（超过 20 个节点时改为邻接表）
+    Ascii,
+    /// Graphviz DOT
+    Dot,
+}
+
 /// P2P 命令参数
 #[derive(Args, Debug)]
 pub struct P2pArgs {
@@ -229,6 +255,9 @@ pub async fn handle_p2p(args: P2pArgs) -> Result<()> {
         }
         P2pAction::Dht { action } => handle_dht_action(action).await,
         P2pAction::Diagnose { check } => diagnose_network(check).await,
+        P2pAction::Mesh { format } => topology(format).await,
+        P2pAction::Bandwidth { limit } => show_bandwidth(limit).await,
+        P2pAction::Reputation => show_reputation().await,
     }
 }
 
@@ -966,9 +995,451 @@ async fn diagnose_network(check: DiagnoseType) -> Result<()> {
     Ok(())
 }
 
+/// ASCII 图最多显示的节点数，超过后改为邻接表
+const MAX_ASCII_GRAPH_NODES: usize = 20;
+
+/// 节点信任级别（来自网络 ACL）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Trust {
+    Trusted,
+    Quarantined,
+    Blocked,
+    Unknown,
+}
+
+impl Trust {
+    fn from_acl(acl: Option<&cis_core::network::NetworkAcl>, did: &str) -> Self {
+        use cis_core::network::AclResult;
+        
+        match acl {
+            Some(acl) if !did.is_empty() => match acl.check_did(did) {
+                AclResult::Allowed => Trust::Trusted,
+                AclResult::Quarantine => Trust::Quarantined,
+                AclResult::Denied(_) => Trust::Blocked,
+            },
+            _ => Trust::Unknown,
+        }
+    }
+    
+    fn label(self) -> &'static str {
+        match self {
+            Trust::Trusted => "trusted",
+            Trust::Quarantined => "quarantined",
+            Trust::Blocked => "blocked",
+            Trust::Unknown => "unknown",
+        }
+    }
+    
+    fn dot_color(self) -> &'static str {
+        match self {
+            Trust::Trusted => "green",
+            Trust::Quarantined => "orange",
+            Trust::Blocked => "red",
+            Trust::Unknown => "gray",
+        }
+    }
+    
+    fn paint(self, text: &str, color: bool) -> String {
+        use colored::Colorize;
+        
+        if !color {
+            return text.to_string();
+        }
+        match self {
+            Trust::Trusted => text.green().to_string(),
+            Trust::Quarantined => text.yellow().to_string(),
+            Trust::Blocked => text.red().to_string(),
+            Trust::Unknown => text.dimmed().to_string(),
+        }
+    }
+}
+
+/// 拓扑中的节点
+#[derive(Debug, Clone)]
+struct MeshNode {
+    id: String,
+    trust: Trust,
+    rtt_ms: Option<u64>,
+}
+
+/// 网络拓扑
+#[derive(Debug, Clone)]
+struct MeshTopology {
+    local: MeshNode,
+    peers: Vec<MeshNode>,
+    edges: Vec<(String, String)>,
+}
+
+impl MeshTopology {
+    fn node(&self, id: &str) -> Option<&MeshNode> {
+        std::iter::once(&self.local)
+            .chain(&self.peers)
+            .find(|n| n.id == id)
+    }
+    
+    fn neighbors(&self, id: &str) -> Vec<&str> {
+        let mut neighbors: Vec<&str> = self
+            .edges
+            .iter()
+            .filter_map(|(a, b)| {
+                if a == id {
+                    Some(b.as_str())
+                } else if b == id {
+                    Some(a.as_str())
+                } else {
+                    None
+                }
+            })
+            .collect();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        neighbors
+    }
+    
+    /// 边的时延：取非本节点一端的 RTT
+    fn latency(&self, a: &str, b: &str) -> Option<u64> {
+        let remote = if a == self.local.id { b } else if b == self.local.id { a } else { return None };
+        self.node(remote).and_then(|n| n.rtt_ms)
+    }
+    
+    fn latency_label(&self, a: &str, b: &str) -> String {
+        match self.latency(a, b) {
+            Some(ms) => format!("{} ms", ms),
+            None => "? ms".to_string(),
+        }
+    }
+    
+    /// 已知但没有任何连接的节点
+    fn orphans(&self) -> Vec<&MeshNode> {
+        self.peers
+            .iter()
+            .filter(|n| !self.edges.iter().any(|(a, b)| *a == n.id || *b == n.id))
+            .collect()
+    }
+    
+    fn render_ascii(&self, color: bool) -> String {
+        let mut out = String::new();
+        for line in Self::node_box(&self.local, " (this node)") {
+            out.push_str(&self.local.trust.paint(&line, color));
+            out.push('\n');
+        }
+        
+        let mut visited = std::collections::HashSet::from([self.local.id.as_str()]);
+        self.render_children(&self.local.id, "", &mut visited, color, &mut out);
+        out
+    }
+    
+    fn render_children<'a>(
+        &'a self,
+        id: &str,
+        prefix: &str,
+        visited: &mut std::collections::HashSet<&'a str>,
+        color: bool,
+        out: &mut String,
+    ) {
+        let children: Vec<&MeshNode> = self
+            .neighbors(id)
+            .into_iter()
+            .filter(|n| !visited.contains(n))
+            .filter_map(|n| self.node(n))
+            .collect();
+        for child in &children {
+            visited.insert(child.id.as_str());
+        }
+        
+        for (i, child) in children.iter().enumerate() {
+            let last = i + 1 == children.len();
+            let (branch, rail) = if last { ("└", " ") } else { ("├", "│") };
+            let arrow = format!("──{:>8} ──▶ ", self.latency_label(id, &child.id));
+            let pad = " ".repeat(arrow.chars().count());
+            
+            let lines = Self::node_box(child, "");
+            for (j, line) in lines.iter().enumerate() {
+                let lead = if j == 0 {
+                    format!("{}  {}{}", prefix, branch, arrow)
+                } else {
+                    format!("{}  {}{}", prefix, rail, pad)
+                };
+                out.push_str(&lead);
+                out.push_str(&child.trust.paint(line, color));
+                out.push('\n');
+            }
+            
+            let child_prefix = format!("{}  {}{}", prefix, rail, pad);
+            self.render_children(&child.id, &child_prefix, visited, color, out);
+        }
+    }
+    
+    fn node_box(node: &MeshNode, suffix: &str) -> [String; 3] {
+        let label = format!("{}{}", node.id, suffix);
+        let width = label.chars().count() + 2;
+        [
+            format!("┌{}┐", "─".repeat(width)),
+            format!("│ {} │", label),
+            format!("└{}┘", "─".repeat(width)),
+        ]
+    }
+    
+    fn render_adjacency(&self, color: bool) -> String {
+        let mut out = String::new();
+        for node in std::iter::once(&self.local).chain(&self.peers) {
+            let neighbors = self.neighbors(&node.id);
+            if neighbors.is_empty() {
+                continue;
+            }
+            let suffix = if node.id == self.local.id { " (this node)" } else { "" };
+            out.push_str(&format!("{}{}\n", node.trust.paint(&node.id, color), suffix));
+            for neighbor in neighbors {
+                let trust = self.node(neighbor).map_or(Trust::Unknown, |n| n.trust);
+                out.push_str(&format!(
+                    "  -> {}  {}  [{}]\n",
+                    trust.paint(neighbor, color),
+                    self.latency_label(&node.id, neighbor),
+                    trust.label()
+                ));
+            }
+        }
+        out
+    }
+    
+    fn render_dot(&self) -> String {
+        let mut out = String::from("graph mesh {\n    node [shape=box];\n");
+        for node in std::iter::once(&self.local).chain(&self.peers) {
+            let label = if node.id == self.local.id {
+                format!("{}\\n(this node)", node.id)
+            } else {
+                node.id.clone()
+            };
+            out.push_str(&format!(
+                "    \"{}\" [label=\"{}\", color={}];\n",
+                node.id, label, node.trust.dot_color()
+            ));
+        }
+        for (a, b) in &self.edges {
+            let style = match self.latency(a, b) {
+                Some(ms) => format!(" [label=\"{} ms\"]", ms),
+                None => String::new(),
+            };
+            out.push_str(&format!("    \"{}\" -- \"{}\"{};\n", a, b, style));
+        }
+        out.push_str("}\n");
+        out
+    }
+    
+    fn render_orphans(&self, color: bool) -> String {
+        self.orphans()
+            .into_iter()
+            .map(|n| format!("  ⚪ {}  [{}]\n", n.trust.paint(&n.id, color), n.trust.label()))
+            .collect()
+    }
+}
+
+/// 显示网络拓扑
+pub async fn topology(format: MeshFormat) -> Result<()> {
+    use cis_core::p2p::network::P2PNetwork;
+    
+    let Some(network) = P2PNetwork::global().await else {
+        println!("❌ P2P network not running");
+        println!("   Run 'cis p2p start' first");
+        return Ok(());
+    };
+    
+    let acl = cis_core::network::NetworkAcl::load(cis_core::network::default_acl_path()).ok();
+    let peers = network.get_peers().await;
+    let edges = network.get_connections().await;
+    
+    let topology = MeshTopology {
+        local: MeshNode {
+            id: network.node_id().to_string(),
+            trust: Trust::Trusted,
+            rtt_ms: None,
+        },
+        peers: peers
+            .iter()
+            .map(|p| MeshNode {
+                id: p.node_id.clone(),
+                trust: Trust::from_acl(acl.as_ref(), &p.did),
+                rtt_ms: p.rtt_ms,
+            })
+            .collect(),
+        edges,
+    };
+    
+    if let MeshFormat::Dot = format {
+        print!("{}", topology.render_dot());
+        return Ok(());
+    }
+    
+    let node_count = topology.peers.len() + 1;
+    println!("🕸️  P2P Mesh ({} nodes, {} links)\n", node_count, topology.edges.len());
+    if node_count > MAX_ASCII_GRAPH_NODES {
+        print!("{}", topology.render_adjacency(true));
+    } else {
+        print!("{}", topology.render_ascii(true));
+    }
+    
+    let orphans = topology.render_orphans(true);
+    if !orphans.is_empty() {
+        println!("\nOrphaned nodes (known but not connected):");
+        print!("{}", orphans);
+    }
+    
+    println!(
+        "\nTrust: {}  {}  {}  {}",
+        Trust::Trusted.paint("trusted", true),
+        Trust::Quarantined.paint("quarantined", true),
+        Trust::Blocked.paint("blocked", true),
+        Trust::Unknown.paint("unknown", true),
+    );
+    
+    Ok(())
+}
+
+/// 显示带宽统计
+async fn show_bandwidth(limit: Option<u64>) -> Result<()> {
+    use cis_core::p2p::network::P2PNetwork;
+    
+    let Some(network) = P2PNetwork::global().await else {
+        println!("❌ P2P network not running");
+        println!("   Run 'cis p2p start' first");
+        return Ok(());
+    };
+    
+    if let Some(limit) = limit {
+        network.bandwidth_limiter().set_limit(limit);
+        println!("✅ Bandwidth limit set to {}\n", format_rate(limit));
+    }
+    
+    let stats = network.bandwidth_stats();
+    println!("📶 P2P Bandwidth\n");
+    println!("  Limit:      {}", format_rate(stats.max_bytes_per_second));
+    println!("  Sent:       {} (last minute)", format_bytes(stats.bytes_sent_last_minute));
+    println!("  Received:   {} (last minute)", format_bytes(stats.bytes_received_last_minute));
+    println!("  Throttled:  {} ms", stats.throttled_ms);
+    
+    Ok(())
+}
+
+/// 显示节点信誉评分
+async fn show_reputation() -> Result<()> {
+    use cis_core::p2p::network::P2PNetwork;
+    use cis_core::p2p::reputation::PeerStatus;
+    
+    let Some(network) = P2PNetwork::global().await else {
+        println!("❌ P2P network not running");
+        println!("   Run 'cis p2p start' first");
+        return Ok(());
+    };
+    
+    // 已知但尚无记录的节点按满分显示
+    let mut peers = network.reputation().snapshot();
+    for peer in network.get_peers().await {
+        if !peers.iter().any(|p| p.node_id == peer.node_id) {
+            peers.push(network.reputation().snapshot_peer(&peer.node_id));
+        }
+    }
+    
+    println!("🛡️  Peer Reputation\n");
+    if peers.is_empty() {
+        println!("  No peers known");
+        return Ok(());
+    }
+    
+    println!("  {:<40} {:>5}  {:<10} {}", "NODE", "SCORE", "STATUS", "BAN REMAINING");
+    for peer in peers {
+        let icon = match peer.status {
+            PeerStatus::Normal => "🟢",
+            PeerStatus::Suspected => "🟡",
+            PeerStatus::Banned => "🔴",
+        };
+        let ban = peer
+            .ban_remaining
+            .map(|d| format!("{}s", d.as_secs()))
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{} {:<40} {:>5}  {:<10} {}",
+            icon,
+            peer.node_id,
+            peer.score,
+            peer.status.as_str(),
+            ban
+        );
+    }
+    
+    Ok(())
+}
+
+fn format_rate(bytes_per_second: u64) -> String {
+    if bytes_per_second == 0 {
+        "unlimited".to_string()
+    } else {
+        format!("{}/s", format_bytes(bytes_per_second))
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
 /// 获取本地 IP
 fn get_local_ip() -> Option<std::net::IpAddr> {
     let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
     socket.connect("8.8.8.8:80").ok()?;
     socket.local_addr().ok()?.ip().into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn sample() -> MeshTopology {
+        let node = |id: &str, trust, rtt_ms| MeshNode { id: id.to_string(), trust, rtt_ms };
+        MeshTopology {
+            local: node("local", Trust::Trusted, None),
+            peers: vec![
+                node("node-a", Trust::Trusted, Some(12)),
+                node("node-b", Trust::Blocked, None),
+                node("node-c", Trust::Unknown, None),
+            ],
+            edges: vec![
+                ("local".to_string(), "node-a".to_string()),
+                ("local".to_string(), "node-b".to_string()),
+            ],
+        }
+    }
+    
+    #[test]
+    fn test_render_ascii() {
+        let out = sample().render_ascii(false);
+        
+        assert!(out.contains("│ local (this node) │"));
+        assert!(out.contains("├──   12 ms ──▶ ┌────────┐"));
+        assert!(out.contains("└──    ? ms ──▶ ┌────────┐"));
+        assert!(!out.contains("node-c"));
+    }
+    
+    #[test]
+    fn test_render_dot_and_orphans() {
+        let topology = sample();
+        let dot = topology.render_dot();
+        
+        assert!(dot.starts_with("graph mesh {"));
+        assert!(dot.contains("\"local\" -- \"node-a\" [label=\"12 ms\"];"));
+        assert!(dot.contains("\"node-b\" [label=\"node-b\", color=red];"));
+        
+        let orphans: Vec<&str> = topology.orphans().iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(orphans, vec!["node-c"]);
+        assert!(topology.render_adjacency(false).contains("  -> node-a  12 ms  [trusted]"));
+    }
+}
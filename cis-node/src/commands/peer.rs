@@ -98,6 +98,7 @@ pub fn list_peers(show_offline: bool) -> Result<()> {
             PeerStatus::Online => "🟢 online",
             PeerStatus::Offline => "⚪ offline",
             PeerStatus::HolePunching => "🟡 hole-punching",
+            PeerStatus::Stale => "⚫ stale",
         };
         
         let endpoint = peer.endpoint_ws.unwrap_or_else(|| "-".to_string());
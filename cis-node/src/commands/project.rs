@@ -3,6 +3,7 @@
 //! Project initialization and management commands for CIS.
 
 use anyhow::{Context, Result};
+use cis_core::project::ProjectRegistry;
 use clap::{Args, Subcommand};
 use std::fs;
 use std::path::Path;
@@ -142,6 +143,11 @@ shared_keys = ["conventions", "architecture", "api-contracts"]
     fs::write(&project_file, project_config)
         .with_context(|| format!("Failed to write project.toml"))?;
 
+    // Record the project so `cis memory gc --orphans` keeps its namespace
+    if let Err(e) = ProjectRegistry::open_default().register(&current_dir, &format!("project/{}", project_name)) {
+        eprintln!("⚠️  Failed to register project: {}", e);
+    }
+
     // Create README in skills directory
     let readme = skills_dir.join("README.md");
     let readme_content = format!(
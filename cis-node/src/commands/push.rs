@@ -0,0 +1,49 @@
+//! # Push Commands
+//!
+//! Commands for the push client's retry queue:
+//! - `cis push status` - Show how many undeliverable messages are pending, failed or delivered
+
+use anyhow::Result;
+use cis_skill_push_client::retry_queue::{self, RetryConfig, RetryQueue};
+use clap::Subcommand;
+use colored::Colorize;
+
+/// Push client commands
+#[derive(Debug, Subcommand)]
+pub enum PushCommands {
+    /// Show retry queue statistics
+    Status,
+}
+
+/// Handle push commands
+pub fn handle(cmd: PushCommands, json: bool) -> Result<()> {
+    match cmd {
+        PushCommands::Status => show_status(json),
+    }
+}
+
+fn show_status(json: bool) -> Result<()> {
+    // Same database the push-client skill's shared client writes to
+    let path = retry_queue::default_path();
+    let queue = RetryQueue::open(&path, RetryConfig::default())?;
+    let stats = queue.stats()?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "pending": stats.pending,
+                "failed": stats.failed,
+                "delivered": stats.delivered,
+            })
+        );
+        return Ok(());
+    }
+
+    println!("{}", "Push Retry Queue".bold());
+    println!("  Pending:   {}", stats.pending.to_string().yellow());
+    println!("  Failed:    {}", stats.failed.to_string().red());
+    println!("  Delivered: {}", stats.delivered.to_string().green());
+    println!("  Database:  {}", path.display());
+    Ok(())
+}
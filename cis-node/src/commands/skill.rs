@@ -6,7 +6,8 @@
 use anyhow::{Context, Result};
 use cis_core::skill::types::LoadOptions;
 use cis_core::skill::router::ResolvedParameters;
-use cis_core::skill::SkillManager;
+use cis_core::project::{ProjectManager, ProjectSkillManifest};
+use cis_core::skill::{DispatchDecision, SemanticSkillDispatcher, SkillCandidate, SkillManager};
 use cis_core::storage::db::DbManager;
 use std::sync::Arc;
 use std::path::PathBuf;
@@ -20,6 +21,7 @@ use cis_core::skill::router::SkillVectorRouter;
 use cis_core::vector::VectorStorage;
 use cis_core::ai::embedding::EmbeddingConfig;
 use cis_core::storage::paths::Paths;
+use cis_core::config::Config;
 
 // Telemetry imports
 use cis_core::telemetry::{RequestLogger, RequestLogBuilder, RequestResult, RequestMetrics};
@@ -124,7 +126,7 @@ pub async fn handle_skill_do(args: SkillDoArgs) -> Result<()> {
         }
     };
     
-    // 3. Route to skill
+    // 3. Match skills by description similarity
     log_builder.start_stage("skill_route");
     let project_path = args.project.as_deref()
         .map(|p| p.to_path_buf())
@@ -133,27 +135,25 @@ pub async fn handle_skill_do(args: SkillDoArgs) -> Result<()> {
     let project_registry = ProjectSkillRegistry::load(&project_path)
         .or_else(|_| Ok::<_, anyhow::Error>(ProjectSkillRegistry::new(&project_path)))?;
     
-    // Create router with SkillManager for execution
-    let db_manager = Arc::new(DbManager::new()?);
-    let router = SkillVectorRouter::new(
-        vector_storage,
-        embedding_service,
-        skill_manager.clone(),
-        db_manager,
-    );
-    
-    let project_id = project_registry.project_path().to_str();
+    // Global skills plus enabled project skills
+    let mut skills = skill_manager.list_all()?;
+    for entry in project_registry.list_enabled() {
+        if !skills.iter().any(|s| s.meta.name == entry.info.meta.name) {
+            skills.push(entry.info.clone());
+        }
+    }
     
-    let candidates_result = router.route(&intent, project_id).await;
+    let dispatch_config = Config::load().unwrap_or_default().skill_dispatch;
+    let dispatcher = SemanticSkillDispatcher::new(vector_storage.clone());
     
-    let candidates = match candidates_result {
+    let candidates = match dispatcher.rank(&args.description, &skills, dispatch_config.top_k).await {
         Ok(cands) => {
             let route_info = format!("found {} candidates", cands.len());
             log_builder.end_stage(true, Some(route_info), None);
             cands
         }
         Err(e) => {
-            let error_msg = format!("Failed to route intent: {}", e);
+            let error_msg = format!("Failed to match skills: {}", e);
             log_builder.end_stage(false, None, Some(error_msg.clone()));
             
             let total_duration = total_start.elapsed().as_millis() as u64;
@@ -168,83 +168,86 @@ pub async fn handle_skill_do(args: SkillDoArgs) -> Result<()> {
                 .build();
             let _ = logger.log_request(&log);
             
-            return Err(anyhow::anyhow!("Failed to route intent: {}", e));
+            return Err(anyhow::anyhow!("Failed to match skills: {}", e));
         }
     };
     
-    if candidates.is_empty() {
-        println!("❌ 未找到匹配的技能");
-        
-        let total_duration = total_start.elapsed().as_millis() as u64;
-        let log = log_builder
-            .set_result(RequestResult::NoMatch { reason: "No matching skills found".to_string() })
-            .set_metrics(RequestMetrics {
-                total_duration_ms: total_duration,
-                intent_duration_ms: 0,
-                routing_duration_ms: 0,
-                execution_duration_ms: 0,
-            })
-            .build();
-        let _ = logger.log_request(&log);
-        
-        return Ok(());
-    }
-    
-    // Display candidate skills
-    if args.candidates || candidates.len() > 1 {
-        println!("\n📋 候选技能:");
-        for (i, c) in candidates.iter().enumerate().take(5) {
-            println!("  {}. {} (置信度: {:.2})", i + 1, c.skill_name, c.confidence);
-        }
-    }
-    
-    // 4. Execute best match
+    // 4. Decide whether to execute, confirm, or give up
     log_builder.start_stage("skill_execute");
-    let best = &candidates[0];
-    
-    if best.confidence < 0.5 {
-        println!("⚠️ 置信度过低，建议明确指定技能");
-        
-        let total_duration = total_start.elapsed().as_millis() as u64;
-        let log = log_builder
-            .set_result(RequestResult::Cancelled)
-            .set_metrics(RequestMetrics {
-                total_duration_ms: total_duration,
-                intent_duration_ms: 0,
-                routing_duration_ms: 0,
-                execution_duration_ms: 0,
-            })
-            .add_metadata("cancel_reason", "confidence_too_low")
-            .build();
-        let _ = logger.log_request(&log);
-        
-        return Ok(());
-    }
+    let threshold = dispatch_config.similarity_threshold;
+    let best = match DispatchDecision::from_candidates(&candidates, threshold) {
+        DispatchDecision::NoMatch => {
+            println!("❌ 未找到匹配的技能");
+            
+            let total_duration = total_start.elapsed().as_millis() as u64;
+            let log = log_builder
+                .set_result(RequestResult::NoMatch { reason: "No matching skills found".to_string() })
+                .set_metrics(RequestMetrics {
+                    total_duration_ms: total_duration,
+                    intent_duration_ms: 0,
+                    routing_duration_ms: 0,
+                    execution_duration_ms: 0,
+                })
+                .build();
+            let _ = logger.log_request(&log);
+            
+            return Ok(());
+        }
+        DispatchDecision::Execute(best) => {
+            if args.candidates {
+                print_skill_candidates(&candidates);
+            }
+            best
+        }
+        DispatchDecision::Confirm(best) => {
+            println!("⚠️ 最佳匹配相似度 {:.2} 低于阈值 {:.2}", best.similarity, threshold);
+            print_skill_candidates(&candidates);
+            
+            print!("\n执行 '{}'? [y/N] ", best.skill_name);
+            std::io::Write::flush(&mut std::io::stdout())?;
+            let mut input = String::new();
+            std::io::stdin().read_line(&mut input)?;
+            
+            if !input.trim().eq_ignore_ascii_case("y") {
+                let total_duration = total_start.elapsed().as_millis() as u64;
+                let log = log_builder
+                    .set_result(RequestResult::Cancelled)
+                    .set_metrics(RequestMetrics {
+                        total_duration_ms: total_duration,
+                        intent_duration_ms: 0,
+                        routing_duration_ms: 0,
+                        execution_duration_ms: 0,
+                    })
+                    .add_metadata("cancel_reason", "below_similarity_threshold")
+                    .build();
+                let _ = logger.log_request(&log);
+                
+                println!("已取消");
+                return Ok(());
+            }
+            best
+        }
+    };
     
     println!("\n✅ 执行: {}", best.skill_name);
     
-    // Check if there's a suggested skill chain
-    if let Some(chain) = &best.suggested_chain {
-        println!("🔗 建议技能链: {:?}", chain);
-    }
+    // Create router with SkillManager for execution
+    let router = SkillVectorRouter::new(
+        vector_storage,
+        embedding_service,
+        skill_manager.clone(),
+        db_manager,
+    );
     
-    // Execute skill chain
     let params = ResolvedParameters::new(serde_json::json!({
         "prompt": args.description,
         "intent": intent.normalized_intent,
         "entities": intent.entities,
-        "params": best.extracted_params,
     }));
     
     // Build and execute skill chain
     let mut chain = SkillChain::new(params.initial.clone());
-    chain.add_step(best.skill_id.clone());
-    
-    if let Some(suggested) = &best.suggested_chain {
-        for step in suggested {
-            chain.add_step(step.clone());
-        }
-    }
+    chain.add_step(best.skill_name.clone());
     
     match router.execute_chain(&chain, &params).await {
         Ok(result) => {
@@ -255,13 +258,13 @@ pub async fn handle_skill_do(args: SkillDoArgs) -> Result<()> {
                 println!("\n📤 最终输出:");
                 println!("{}", serde_json::to_string_pretty(&result.final_output)?);
                 
-                log_builder.end_stage(true, Some(format!("skill={}, success", best.skill_id)), None);
+                log_builder.end_stage(true, Some(format!("skill={}, success", best.skill_name)), None);
                 
                 // Save final log
                 let total_duration = total_start.elapsed().as_millis() as u64;
                 let log = log_builder
                     .set_result(RequestResult::Success { 
-                        skill_id: best.skill_id.clone(), 
+                        skill_id: best.skill_name.clone(), 
                         output_summary: format!("Executed skill '{}' successfully", best.skill_name)
                     })
                     .set_metrics(RequestMetrics {
@@ -271,7 +274,7 @@ pub async fn handle_skill_do(args: SkillDoArgs) -> Result<()> {
                         execution_duration_ms: result.execution_time_ms,
                     })
                     .add_metadata("candidate_count", candidates.len().to_string())
-                    .add_metadata("confidence", format!("{:.2}", best.confidence))
+                    .add_metadata("similarity", format!("{:.2}", best.similarity))
                     .add_metadata("steps", chain.len().to_string())
                     .build();
                 let _ = logger.log_request(&log);
@@ -300,8 +303,7 @@ pub async fn handle_skill_do(args: SkillDoArgs) -> Result<()> {
                         execution_duration_ms: result.execution_time_ms,
                     })
                     .add_metadata("candidate_count", candidates.len().to_string())
-                    .add_metadata("confidence", format!("{:.2}", best.confidence))
-                    .add_metadata("skill_id", best.skill_id.clone())
+                    .add_metadata("similarity", format!("{:.2}", best.similarity))
                     .build();
                 let _ = logger.log_request(&log);
             }
@@ -335,19 +337,50 @@ pub async fn handle_skill_do(args: SkillDoArgs) -> Result<()> {
     Ok(())
 }
 
+/// Print ranked skill candidates
+fn print_skill_candidates(candidates: &[SkillCandidate]) {
+    println!("\n📋 候选技能:");
+    for (i, c) in candidates.iter().enumerate() {
+        println!("  {}. {} (相似度: {:.2}) - {}", i + 1, c.skill_name, c.similarity, c.description);
+    }
+}
+
 /// List all registered skills
-pub fn list_skills() -> Result<()> {
+///
+/// With `project`, only skills declared in the nearest `.cis/skills.toml` are listed.
+pub fn list_skills(project: bool) -> Result<()> {
     let db_manager = Arc::new(DbManager::new()?);
     let manager = SkillManager::new(db_manager)?;
     
-    let skills = manager.list_all()?;
+    let mut skills = manager.list_all()?;
     
-    if skills.is_empty() {
-        println!("No skills registered.");
+    // 项目清单中声明但尚未注册的 Skills
+    let mut unregistered = Vec::new();
+    if project {
+        let current_dir = std::env::current_dir()?;
+        let Some(project) = ProjectManager::find_project(&current_dir) else {
+            println!("Not inside a CIS project. Run 'cis init --project' first.");
+            return Ok(());
+        };
+        let manifest = ProjectSkillManifest::load(project.config.project_root())?;
+        skills.retain(|skill| manifest.contains(&skill.meta.name));
+        unregistered = manifest
+            .skills
+            .into_iter()
+            .filter(|decl| !skills.iter().any(|s| s.meta.name == decl.name))
+            .collect();
+    }
+    
+    if skills.is_empty() && unregistered.is_empty() {
+        if project {
+            println!("No project skills declared in .cis/skills.toml.");
+        } else {
+            println!("No skills registered.");
+        }
         return Ok(());
     }
     
-    println!("Registered Skills:");
+    println!("{}", if project { "Project Skills:" } else { "Registered Skills:" });
     println!("{:<20} {:<12} {:<10} Description", "Name", "Version", "State");
     println!("{}", "-".repeat(80));
     
@@ -369,6 +402,10 @@ pub fn list_skills() -> Result<()> {
         );
     }
     
+    for decl in unregistered {
+        println!("{:<20} {:<12} {:<10} {}", decl.name, decl.version, "declared", decl.path);
+    }
+    
     Ok(())
 }
 
@@ -483,6 +520,25 @@ pub fn skill_info(name: &str) -> Result<()> {
         println!("  Subscriptions: {}", info.meta.subscriptions.join(", "));
     }
     
+    match manager.describe_methods(&info.meta.name)? {
+        Some(methods) if !methods.is_empty() => {
+            println!();
+            println!("Methods:");
+            println!("  {:<20} {:<40} Params", "Name", "Description");
+            println!("  {}", "-".repeat(80));
+            for method in methods {
+                let params = if method.params_schema.is_null() {
+                    "-".to_string()
+                } else {
+                    method.params_schema.to_string()
+                };
+                println!("  {:<20} {:<40} {}", method.name, method.description, params);
+            }
+        }
+        Some(_) => println!("  Methods:     (skill does not describe its methods)"),
+        None => println!("  Methods:     (load the skill to list its methods)"),
+    }
+    
     Ok(())
 }
 
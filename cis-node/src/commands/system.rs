@@ -3,14 +3,21 @@
 //! 系统级管理命令：
 //! - `cis system status` - 查看系统状态
 //! - `cis system init` - 初始化系统（含目录结构）
-//! - `cis system migrate` - 迁移旧配置
+//! - `cis system migrate` - 迁移旧配置（`--from-version` 按版本升级数据目录）
 //! - `cis system clean` - 清理缓存/日志
 //! - `cis system purge` - 完全卸载（危险）
+//! - `cis system backup` - 备份数据库、配置和 Skill WASM
+//! - `cis system restore` - 从备份恢复
 
-use anyhow::Result;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
 use clap::Subcommand;
-use tracing::info;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
 
+use cis_core::storage::paths::Paths;
 use cis_core::storage::unified_paths::{Cleanup, UnifiedPaths};
 
 /// System management commands
@@ -34,11 +41,16 @@ pub enum SystemCommands {
         non_interactive: bool,
     },
 
-    /// Migrate from legacy directory structure
+    /// Migrate from legacy directory structure, or upgrade the data directory
     Migrate {
         /// Show what would be migrated without doing it
         #[arg(short, long)]
         dry_run: bool,
+
+        /// Upgrade the data directory from an older CIS version
+        /// (detected from ~/.cis/version when no version is given)
+        #[arg(long, value_name = "VERSION", num_args = 0..=1)]
+        from_version: Option<Option<String>>,
     },
 
     /// Clean up cache and old logs
@@ -83,6 +95,39 @@ pub enum SystemCommands {
         #[command(subcommand)]
         action: ModelAction,
     },
+
+    /// Back up databases, config and skill WASMs to a tar.zst archive
+    Backup {
+        /// Output archive (default: ./cis-backup-<timestamp>.tar.zst)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Include memory databases (default)
+        #[arg(long, overrides_with = "exclude_memory")]
+        include_memory: bool,
+
+        /// Leave memory databases out of the backup
+        #[arg(long, overrides_with = "include_memory")]
+        exclude_memory: bool,
+    },
+
+    /// Restore from a backup archive
+    Restore {
+        /// Backup archive
+        input: PathBuf,
+
+        /// Skip checksum verification
+        #[arg(long)]
+        no_verify: bool,
+
+        /// Restore memory databases (default)
+        #[arg(long, overrides_with = "exclude_memory")]
+        include_memory: bool,
+
+        /// Keep the current memory databases
+        #[arg(long, overrides_with = "include_memory")]
+        exclude_memory: bool,
+    },
 }
 
 /// Model management actions
@@ -110,11 +155,20 @@ pub async fn handle(cmd: SystemCommands) -> Result<()> {
     match cmd {
         SystemCommands::Status { format } => show_status(&format).await?,
         SystemCommands::Init { force, non_interactive } => init_system(force, non_interactive).await?,
-        SystemCommands::Migrate { dry_run } => migrate_system(dry_run).await?,
+        SystemCommands::Migrate { dry_run, from_version: None } => migrate_system(dry_run).await?,
+        SystemCommands::Migrate { dry_run, from_version: Some(from) } => {
+            migrate_from_version(from, dry_run)?
+        }
         SystemCommands::Clean { cache, logs, all } => clean_system(cache, logs, all).await?,
         SystemCommands::Purge { force, include_backup } => purge_system(force, include_backup).await?,
         SystemCommands::Check { format, fix } => check_system(&format, fix).await?,
         SystemCommands::Model { action } => handle_model_command(action).await?,
+        SystemCommands::Backup { output, exclude_memory, .. } => {
+            backup_system(output, !exclude_memory)?
+        }
+        SystemCommands::Restore { input, no_verify, exclude_memory, .. } => {
+            restore_system(&input, !no_verify, !exclude_memory)?
+        }
     }
 
     Ok(())
@@ -344,6 +398,740 @@ async fn purge_system(force: bool, include_backup: bool) -> Result<()> {
     Ok(())
 }
 
+/// Archive name of the manifest
+const BACKUP_MANIFEST: &str = "manifest.json";
+
+/// Backup format version
+const BACKUP_VERSION: u32 = 1;
+
+/// Memory databases controlled by `--include-memory/--exclude-memory`
+const MEMORY_FILES: &[&str] = &["memory.db", "vector.db", "vector.idx"];
+
+/// Data subdirectories never backed up
+const SKIPPED_DIRS: &[&str] = &["logs", "models", "cache", "wal", "backup", "tmp"];
+
+/// One file in a backup archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupEntry {
+    /// Path inside the archive (`data/...` or `config/config.toml`)
+    pub path: String,
+    /// Size in bytes
+    pub size: u64,
+    /// Hex SHA-256
+    pub sha256: String,
+}
+
+/// Contents of a backup archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub version: u32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub includes_memory: bool,
+    pub files: Vec<BackupEntry>,
+}
+
+impl BackupManifest {
+    /// Total size of all files
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|f| f.size).sum()
+    }
+}
+
+/// Result of a restore
+#[derive(Debug, Clone, Default)]
+pub struct RestoreReport {
+    /// Restored files (destination paths)
+    pub restored: Vec<PathBuf>,
+    /// Archive entries left out (memory excluded)
+    pub skipped: Vec<String>,
+    /// Whether checksums were verified
+    pub verified: bool,
+}
+
+/// Backup and restore of the CIS data directory
+///
+/// SQLite databases are copied with `VACUUM INTO` so the snapshot is
+/// consistent even while the node is running.
+pub struct SystemBackup {
+    data_dir: PathBuf,
+    config_file: PathBuf,
+    include_memory: bool,
+}
+
+impl SystemBackup {
+    pub fn new(data_dir: impl Into<PathBuf>, config_file: impl Into<PathBuf>) -> Self {
+        Self {
+            data_dir: data_dir.into(),
+            config_file: config_file.into(),
+            include_memory: true,
+        }
+    }
+
+    /// Back up the default CIS data directory
+    pub fn from_default_paths() -> Self {
+        Self::new(Paths::data_dir(), Paths::config_file())
+    }
+
+    /// Whether memory databases are backed up / restored
+    pub fn include_memory(mut self, include: bool) -> Self {
+        self.include_memory = include;
+        self
+    }
+
+    fn is_memory_entry(path: &str) -> bool {
+        path.strip_prefix("data/")
+            .is_some_and(|name| MEMORY_FILES.iter().any(|m| *m == name))
+    }
+
+    /// Files to back up: (source, archive path, is SQLite)
+    fn collect_sources(&self) -> Result<Vec<(PathBuf, String, bool)>> {
+        let mut sources = vec![];
+
+        if self.data_dir.exists() {
+            let wasm_dir = self.data_dir.join("skills").join("installed").join("wasm");
+            let walker = walkdir::WalkDir::new(&self.data_dir)
+                .into_iter()
+                .filter_entry(|e| {
+                    let name = e.file_name().to_string_lossy();
+                    e.depth() == 0
+                        || !(e.file_type().is_dir() && SKIPPED_DIRS.iter().any(|d| *d == name))
+                });
+
+            for entry in walker {
+                let entry = entry?;
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let path = entry.path();
+                let relative = path.strip_prefix(&self.data_dir)?;
+                let archive_path = format!(
+                    "data/{}",
+                    relative.to_string_lossy().replace(std::path::MAIN_SEPARATOR, "/")
+                );
+
+                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+                let is_db = matches!(ext, "db" | "sqlite");
+                let is_skill_file = path.starts_with(&wasm_dir)
+                    || relative == Path::new("skills").join("registry.json");
+                if !is_db && !is_skill_file {
+                    continue;
+                }
+                if !self.include_memory && Self::is_memory_entry(&archive_path) {
+                    continue;
+                }
+                sources.push((path.to_path_buf(), archive_path, is_db));
+            }
+        }
+
+        if self.config_file.exists() {
+            sources.push((self.config_file.clone(), "config/config.toml".to_string(), false));
+        }
+
+        sources.sort_by(|a, b| a.1.cmp(&b.1));
+        Ok(sources)
+    }
+
+    /// Create a tar.zst archive at `output`
+    pub fn create(&self, output: &Path) -> Result<BackupManifest> {
+        let staging = tempfile::tempdir()?;
+        let mut files = vec![];
+
+        for (source, archive_path, is_db) in self.collect_sources()? {
+            let staged = staging.path().join(&archive_path);
+            if let Some(parent) = staged.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            if is_db {
+                if let Err(e) = vacuum_into(&source, &staged) {
+                    warn!("VACUUM INTO failed for {}, copying instead: {}", source.display(), e);
+                    std::fs::copy(&source, &staged)?;
+                }
+            } else {
+                std::fs::copy(&source, &staged)?;
+            }
+
+            let (size, sha256) = file_digest(&staged)?;
+            files.push(BackupEntry { path: archive_path, size, sha256 });
+        }
+
+        let manifest = BackupManifest {
+            version: BACKUP_VERSION,
+            created_at: chrono::Utc::now(),
+            includes_memory: self.include_memory,
+            files,
+        };
+        std::fs::write(
+            staging.path().join(BACKUP_MANIFEST),
+            serde_json::to_vec_pretty(&manifest)?,
+        )?;
+
+        let file = std::fs::File::create(output)
+            .with_context(|| format!("Failed to create {}", output.display()))?;
+        let mut archive = tar::Builder::new(zstd::Encoder::new(file, 0)?);
+        archive.append_path_with_name(staging.path().join(BACKUP_MANIFEST), BACKUP_MANIFEST)?;
+        for entry in &manifest.files {
+            archive.append_path_with_name(staging.path().join(&entry.path), &entry.path)?;
+        }
+        archive.into_inner()?.finish()?;
+
+        Ok(manifest)
+    }
+
+    /// Extract `input`, optionally verify checksums, and copy files into place
+    ///
+    /// Verification happens before anything is written, so a corrupt archive
+    /// leaves the current data untouched.
+    pub fn restore(&self, input: &Path, verify: bool) -> Result<RestoreReport> {
+        let staging = tempfile::tempdir()?;
+        let file = std::fs::File::open(input)
+            .with_context(|| format!("Failed to open {}", input.display()))?;
+        tar::Archive::new(zstd::Decoder::new(file)?).unpack(staging.path())?;
+
+        let manifest: BackupManifest = serde_json::from_slice(
+            &std::fs::read(staging.path().join(BACKUP_MANIFEST))
+                .context("Backup manifest missing")?,
+        )?;
+        if manifest.version > BACKUP_VERSION {
+            anyhow::bail!("Unsupported backup version {}", manifest.version);
+        }
+
+        let mut report = RestoreReport { verified: verify, ..Default::default() };
+        let mut planned = vec![];
+        for entry in &manifest.files {
+            if !self.include_memory && Self::is_memory_entry(&entry.path) {
+                report.skipped.push(entry.path.clone());
+                continue;
+            }
+
+            let destination = self.destination(&entry.path)?;
+            let staged = staging.path().join(&entry.path);
+            if verify {
+                let (size, sha256) = file_digest(&staged)
+                    .with_context(|| format!("{} missing from archive", entry.path))?;
+                if size != entry.size || sha256 != entry.sha256 {
+                    anyhow::bail!("Checksum mismatch for {}", entry.path);
+                }
+            }
+            planned.push((staged, destination));
+        }
+
+        for (staged, destination) in planned {
+            if let Some(parent) = destination.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(&staged, &destination)?;
+            // A leftover WAL would be replayed onto the restored database
+            remove_sqlite_sidecars(&destination);
+            report.restored.push(destination);
+        }
+
+        Ok(report)
+    }
+
+    /// Map an archive path back onto the filesystem
+    fn destination(&self, archive_path: &str) -> Result<PathBuf> {
+        if archive_path == "config/config.toml" {
+            return Ok(self.config_file.clone());
+        }
+
+        let relative = archive_path
+            .strip_prefix("data/")
+            .map(Path::new)
+            .filter(|p| p.components().all(|c| matches!(c, Component::Normal(_))))
+            .with_context(|| format!("Unexpected path in backup: {}", archive_path))?;
+        Ok(self.data_dir.join(relative))
+    }
+}
+
+/// Consistent copy of a SQLite database
+fn vacuum_into(source: &Path, dest: &Path) -> Result<()> {
+    let conn = rusqlite::Connection::open_with_flags(
+        source,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    conn.execute("VACUUM INTO ?1", [dest.to_string_lossy().into_owned()])?;
+    Ok(())
+}
+
+/// Size and hex SHA-256 of a file
+fn file_digest(path: &Path) -> Result<(u64, String)> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let size = std::io::copy(&mut file, &mut hasher)?;
+    Ok((size, hex::encode(hasher.finalize())))
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
+
+/// Create a backup archive
+fn backup_system(output: Option<PathBuf>, include_memory: bool) -> Result<()> {
+    let output = output.unwrap_or_else(|| {
+        PathBuf::from(format!(
+            "cis-backup-{}.tar.zst",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ))
+    });
+
+    info!("Creating backup at {}", output.display());
+    let manifest = SystemBackup::from_default_paths()
+        .include_memory(include_memory)
+        .create(&output)?;
+
+    for entry in &manifest.files {
+        println!("  + {} ({})", entry.path, format_size(entry.size));
+    }
+    println!(
+        "✓ Backed up {} files ({}) to {}",
+        manifest.files.len(),
+        format_size(manifest.total_size()),
+        output.display()
+    );
+    if !include_memory {
+        println!("  Memory databases were excluded.");
+    }
+
+    Ok(())
+}
+
+/// Restore from a backup archive
+fn restore_system(input: &Path, verify: bool, include_memory: bool) -> Result<()> {
+    info!("Restoring backup from {}", input.display());
+    let report = SystemBackup::from_default_paths()
+        .include_memory(include_memory)
+        .restore(input, verify)?;
+
+    for path in &report.restored {
+        println!("  ✓ {}", path.display());
+    }
+    for path in &report.skipped {
+        println!("  - {} (skipped)", path);
+    }
+    println!(
+        "✓ Restored {} files{}",
+        report.restored.len(),
+        if report.verified { " (checksums verified)" } else { "" }
+    );
+    println!("  Restart the node to pick up restored data.");
+
+    Ok(())
+}
+
+/// Version file in the data directory (`~/.cis/version`)
+const VERSION_FILE: &str = "version";
+
+/// Source version assumed when the data directory predates the version file
+const UNVERSIONED: Version = Version::new(1, 0, 0);
+
+/// `major.minor.patch` version of a data directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// Version of this binary
+    pub fn current() -> Self {
+        env!("CARGO_PKG_VERSION")
+            .parse()
+            .expect("CARGO_PKG_VERSION is a valid version")
+    }
+}
+
+impl std::str::FromStr for Version {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().trim_start_matches('v');
+        // Ignore pre-release/build suffixes (`1.1.5-beta`)
+        let core = s.split(['-', '+']).next().unwrap_or_default();
+        let mut parts = core.split('.').map(|p| p.parse::<u32>());
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Ok(major)), Some(Ok(minor)), patch, None) => {
+                let patch = match patch {
+                    Some(p) => p.with_context(|| format!("Invalid version: {}", s))?,
+                    None => 0,
+                };
+                Ok(Self::new(major, minor, patch))
+            }
+            _ => anyhow::bail!("Invalid version: {}", s),
+        }
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// What a migration step does
+#[derive(Debug, Clone, Copy)]
+pub enum MigrationAction {
+    /// Rename a file or directory inside the data directory
+    Rename { from: &'static str, to: &'static str },
+    /// Run SQL against a database in the data directory, in one transaction
+    Sql { db: &'static str, sql: &'static str },
+    /// Rewrite `config.toml`; returns whether anything changed
+    ConvertConfig(fn(&mut toml::Table) -> bool),
+}
+
+/// One step of a versioned migration
+#[derive(Debug, Clone, Copy)]
+pub struct MigrationStep {
+    /// Version that introduced the change
+    pub version: Version,
+    pub description: &'static str,
+    pub action: MigrationAction,
+}
+
+/// All known migration steps, oldest first
+pub fn migration_steps() -> Vec<MigrationStep> {
+    vec![
+        MigrationStep {
+            version: Version::new(1, 1, 0),
+            description: "Move core/core.db to node.db",
+            action: MigrationAction::Rename { from: "core/core.db", to: "node.db" },
+        },
+        MigrationStep {
+            version: Version::new(1, 1, 0),
+            description: "Move skill WASMs to skills/installed/wasm",
+            action: MigrationAction::Rename { from: "skills/wasm", to: "skills/installed/wasm" },
+        },
+        MigrationStep {
+            version: Version::new(1, 1, 0),
+            description: "Create schema_migrations table in node.db",
+            action: MigrationAction::Sql {
+                db: "node.db",
+                sql: "CREATE TABLE IF NOT EXISTS schema_migrations (
+                          version TEXT PRIMARY KEY,
+                          applied_at INTEGER NOT NULL
+                      );
+                      INSERT OR IGNORE INTO schema_migrations (version, applied_at)
+                      VALUES ('1.1.0', strftime('%s', 'now'));",
+            },
+        },
+        MigrationStep {
+            version: Version::new(1, 1, 5),
+            description: "Move top-level config keys into [node] and [ai]",
+            action: MigrationAction::ConvertConfig(convert_legacy_config_keys),
+        },
+        MigrationStep {
+            version: Version::new(1, 1, 5),
+            description: "Rename matrix.db to matrix-events.db",
+            action: MigrationAction::Rename { from: "matrix.db", to: "matrix-events.db" },
+        },
+    ]
+}
+
+/// Top-level keys of the 1.0 config and the table/key they moved to
+const LEGACY_CONFIG_KEYS: &[(&str, &str, &str)] = &[
+    ("node_name", "node", "name"),
+    ("ai_provider", "ai", "provider"),
+    ("ai_model", "ai", "model"),
+];
+
+fn convert_legacy_config_keys(config: &mut toml::Table) -> bool {
+    let mut changed = false;
+    for (old_key, table, key) in LEGACY_CONFIG_KEYS {
+        let Some(value) = config.remove(*old_key) else {
+            continue;
+        };
+        let section = config
+            .entry(table.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if let Some(section) = section.as_table_mut() {
+            // Keys already set in the new location win
+            section.entry(key.to_string()).or_insert(value);
+        }
+        changed = true;
+    }
+    changed
+}
+
+/// Outcome of one migration step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepStatus {
+    Applied,
+    /// Nothing to do (e.g. source path missing)
+    Skipped(String),
+    Failed(String),
+    /// Applied, then undone after a later step failed
+    RolledBack,
+}
+
+#[derive(Debug, Clone)]
+pub struct StepReport {
+    pub version: Version,
+    pub description: String,
+    pub status: StepStatus,
+}
+
+/// Result of a versioned migration
+#[derive(Debug, Clone)]
+pub struct MigrationReport {
+    pub from: Version,
+    pub to: Version,
+    pub steps: Vec<StepReport>,
+}
+
+impl MigrationReport {
+    /// Whether every step applied or was skipped
+    pub fn succeeded(&self) -> bool {
+        !self
+            .steps
+            .iter()
+            .any(|s| matches!(s.status, StepStatus::Failed(_) | StepStatus::RolledBack))
+    }
+
+    pub fn print(&self) {
+        println!("Migration {} → {}", self.from, self.to);
+        if self.steps.is_empty() {
+            println!("  (no steps)");
+        }
+        for step in &self.steps {
+            let (mark, detail) = match &step.status {
+                StepStatus::Applied => ("✓", String::new()),
+                StepStatus::Skipped(reason) => ("-", format!(" (skipped: {})", reason)),
+                StepStatus::Failed(error) => ("✗", format!(" (failed: {})", error)),
+                StepStatus::RolledBack => ("↺", " (rolled back)".to_string()),
+            };
+            println!("  {} [{}] {}{}", mark, step.version, step.description, detail);
+        }
+    }
+}
+
+/// How to revert an applied step
+enum Undo {
+    Rename { from: PathBuf, to: PathBuf },
+    RestoreDb { path: PathBuf, backup: PathBuf },
+    RestoreConfig { path: PathBuf, original: String },
+}
+
+impl Undo {
+    fn apply(self) -> Result<()> {
+        match self {
+            Undo::Rename { from, to } => std::fs::rename(&to, &from)?,
+            Undo::RestoreDb { path, backup } => {
+                std::fs::copy(&backup, &path)?;
+                remove_sqlite_sidecars(&path);
+            }
+            Undo::RestoreConfig { path, original } => write_atomic(&path, original.as_bytes())?,
+        }
+        Ok(())
+    }
+}
+
+/// Versioned upgrade of the CIS data directory
+///
+/// Steps newer than the source version run in order. Each step is atomic
+/// on its own (rename, SQL transaction, write-then-rename); if one fails,
+/// the steps already applied are undone in reverse order and the version
+/// file is left untouched.
+pub struct SystemMigrator {
+    data_dir: PathBuf,
+    config_file: PathBuf,
+    steps: Vec<MigrationStep>,
+}
+
+impl SystemMigrator {
+    pub fn new(data_dir: impl Into<PathBuf>, config_file: impl Into<PathBuf>) -> Self {
+        Self {
+            data_dir: data_dir.into(),
+            config_file: config_file.into(),
+            steps: migration_steps(),
+        }
+    }
+
+    /// Migrate the default CIS data directory
+    pub fn from_default_paths() -> Self {
+        Self::new(Paths::data_dir(), Paths::config_file())
+    }
+
+    fn version_file(&self) -> PathBuf {
+        self.data_dir.join(VERSION_FILE)
+    }
+
+    /// Version recorded in the data directory, 1.0.0 if there is none
+    pub fn detect_version(&self) -> Result<Version> {
+        match std::fs::read_to_string(self.version_file()) {
+            Ok(content) => content.parse(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(UNVERSIONED),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Steps that upgrade `from` to `to`
+    pub fn plan(&self, from: Version, to: Version) -> Vec<&MigrationStep> {
+        self.steps
+            .iter()
+            .filter(|s| s.version > from && s.version <= to)
+            .collect()
+    }
+
+    /// Run all steps from `from` to `to` and write the version file
+    ///
+    /// Step failures are reported in the returned [`MigrationReport`];
+    /// `Err` is only returned when the version file cannot be written.
+    pub fn migrate(&self, from: Version, to: Version) -> Result<MigrationReport> {
+        let backups = tempfile::tempdir()?;
+        let mut report = MigrationReport { from, to, steps: vec![] };
+        let mut applied: Vec<(usize, Undo)> = vec![];
+
+        for step in self.plan(from, to) {
+            let status = match self.apply(step, backups.path()) {
+                Ok(Some(undo)) => {
+                    applied.push((report.steps.len(), undo));
+                    StepStatus::Applied
+                }
+                Ok(None) => StepStatus::Skipped("nothing to migrate".to_string()),
+                Err(e) => StepStatus::Failed(format!("{:#}", e)),
+            };
+            let failed = matches!(status, StepStatus::Failed(_));
+            report.steps.push(StepReport {
+                version: step.version,
+                description: step.description.to_string(),
+                status,
+            });
+
+            if failed {
+                for (index, undo) in applied.drain(..).rev() {
+                    match undo.apply() {
+                        Ok(()) => report.steps[index].status = StepStatus::RolledBack,
+                        Err(e) => {
+                            warn!("Rollback of '{}' failed: {}", report.steps[index].description, e);
+                            report.steps[index].status =
+                                StepStatus::Failed(format!("rollback failed: {}", e));
+                        }
+                    }
+                }
+                return Ok(report);
+            }
+        }
+
+        std::fs::create_dir_all(&self.data_dir)?;
+        write_atomic(&self.version_file(), format!("{}\n", to).as_bytes())?;
+        Ok(report)
+    }
+
+    /// Apply one step; `None` if there was nothing to do
+    fn apply(&self, step: &MigrationStep, backups: &Path) -> Result<Option<Undo>> {
+        match step.action {
+            MigrationAction::Rename { from, to } => {
+                let from = self.data_dir.join(from);
+                let to = self.data_dir.join(to);
+                if !from.exists() {
+                    return Ok(None);
+                }
+                if to.exists() {
+                    anyhow::bail!("{} already exists", to.display());
+                }
+                if let Some(parent) = to.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::rename(&from, &to)
+                    .with_context(|| format!("Failed to move {}", from.display()))?;
+                Ok(Some(Undo::Rename { from, to }))
+            }
+            MigrationAction::Sql { db, sql } => {
+                let path = self.data_dir.join(db);
+                if !path.exists() {
+                    return Ok(None);
+                }
+                let backup = backups.join(format!("{}-{}", step.version, db));
+                vacuum_into(&path, &backup)
+                    .with_context(|| format!("Failed to back up {}", path.display()))?;
+
+                let mut conn = rusqlite::Connection::open(&path)?;
+                let tx = conn.transaction()?;
+                tx.execute_batch(sql)?;
+                tx.commit()?;
+                Ok(Some(Undo::RestoreDb { path, backup }))
+            }
+            MigrationAction::ConvertConfig(convert) => {
+                let path = self.config_file.clone();
+                let original = match std::fs::read_to_string(&path) {
+                    Ok(content) => content,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                };
+                let mut config: toml::Table = toml::from_str(&original)
+                    .with_context(|| format!("Failed to parse {}", path.display()))?;
+                if !convert(&mut config) {
+                    return Ok(None);
+                }
+                write_atomic(&path, toml::to_string_pretty(&config)?.as_bytes())?;
+                Ok(Some(Undo::RestoreConfig { path, original }))
+            }
+        }
+    }
+}
+
+/// Write via a temporary file in the same directory and rename over `path`
+fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+    std::io::Write::write_all(&mut tmp, content)?;
+    tmp.persist(path)?;
+    Ok(())
+}
+
+/// Remove `-wal`/`-shm` files left next to a replaced database
+fn remove_sqlite_sidecars(path: &Path) {
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = path.to_path_buf().into_os_string();
+        sidecar.push(suffix);
+        let _ = std::fs::remove_file(sidecar);
+    }
+}
+
+/// Versioned migration (`cis system migrate --from-version`)
+fn migrate_from_version(from: Option<String>, dry_run: bool) -> Result<()> {
+    let migrator = SystemMigrator::from_default_paths();
+    let from = match from {
+        Some(version) => version.parse()?,
+        None => migrator.detect_version()?,
+    };
+    let to = Version::current();
+
+    if from >= to {
+        println!("Data directory is already at version {}. Nothing to migrate.", from);
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("Migration plan {} → {}:", from, to);
+        for step in migrator.plan(from, to) {
+            println!("  • [{}] {}", step.version, step.description);
+        }
+        println!();
+        println!("(Dry run - no changes made)");
+        return Ok(());
+    }
+
+    info!("Migrating data directory from {} to {}", from, to);
+    let report = migrator.migrate(from, to)?;
+    report.print();
+
+    if !report.succeeded() {
+        anyhow::bail!("Migration failed; applied steps were rolled back");
+    }
+    println!("✓ Data directory is now at version {}", to);
+    Ok(())
+}
+
 /// Check system health
 async fn check_system(format: &str, fix: bool) -> Result<()> {
     let mut issues = vec![];
@@ -518,3 +1306,156 @@ async fn handle_model_command(action: ModelAction) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_db(path: &Path, value: &str) {
+        let conn = rusqlite::Connection::open(path).unwrap();
+        conn.execute_batch("CREATE TABLE IF NOT EXISTS t (v TEXT)").unwrap();
+        conn.execute("INSERT INTO t (v) VALUES (?1)", [value]).unwrap();
+    }
+
+    fn read_db(path: &Path) -> Vec<String> {
+        let conn = rusqlite::Connection::open(path).unwrap();
+        let mut stmt = conn.prepare("SELECT v FROM t").unwrap();
+        stmt.query_map([], |row| row.get(0)).unwrap().map(|r| r.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_backup_restore_roundtrip() {
+        let source = tempfile::tempdir().unwrap();
+        let data = source.path().join("data");
+        std::fs::create_dir_all(data.join("skills/installed/wasm")).unwrap();
+        std::fs::create_dir_all(data.join("logs")).unwrap();
+        create_db(&data.join("node.db"), "node");
+        create_db(&data.join("memory.db"), "memory");
+        std::fs::write(data.join("skills/installed/wasm/echo.wasm"), b"\0asm").unwrap();
+        std::fs::write(data.join("logs/cis.log"), "log").unwrap();
+        let config = source.path().join("config.toml");
+        std::fs::write(&config, "[node]").unwrap();
+
+        let archive = source.path().join("backup.tar.zst");
+        let manifest = SystemBackup::new(&data, &config).create(&archive).unwrap();
+        let paths: Vec<_> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "config/config.toml",
+                "data/memory.db",
+                "data/node.db",
+                "data/skills/installed/wasm/echo.wasm",
+            ]
+        );
+
+        // Restore into a fresh directory without the memory database
+        let target = tempfile::tempdir().unwrap();
+        let restored_data = target.path().join("data");
+        let restored_config = target.path().join("config.toml");
+        let report = SystemBackup::new(&restored_data, &restored_config)
+            .include_memory(false)
+            .restore(&archive, true)
+            .unwrap();
+
+        assert_eq!(report.restored.len(), 3);
+        assert_eq!(report.skipped, vec!["data/memory.db"]);
+        assert_eq!(read_db(&restored_data.join("node.db")), vec!["node"]);
+        assert!(!restored_data.join("memory.db").exists());
+        assert_eq!(std::fs::read_to_string(&restored_config).unwrap(), "[node]");
+    }
+
+    #[test]
+    fn test_backup_excludes_memory() {
+        let source = tempfile::tempdir().unwrap();
+        create_db(&source.path().join("memory.db"), "memory");
+        create_db(&source.path().join("federation.db"), "fed");
+
+        let archive = source.path().join("backup.tar.zst");
+        let manifest = SystemBackup::new(source.path(), source.path().join("missing.toml"))
+            .include_memory(false)
+            .create(&archive)
+            .unwrap();
+
+        assert!(!manifest.includes_memory);
+        assert_eq!(manifest.files.len(), 1);
+        assert_eq!(manifest.files[0].path, "data/federation.db");
+    }
+
+    fn legacy_data_dir(root: &Path) -> (PathBuf, PathBuf) {
+        let data = root.join("data");
+        std::fs::create_dir_all(data.join("core")).unwrap();
+        std::fs::create_dir_all(data.join("skills/wasm")).unwrap();
+        create_db(&data.join("core/core.db"), "node");
+        std::fs::write(data.join("skills/wasm/echo.wasm"), b"\0asm").unwrap();
+        let config = root.join("config.toml");
+        std::fs::write(&config, "node_name = \"legacy\"\n\n[p2p]\nenabled = true\n").unwrap();
+        (data, config)
+    }
+
+    #[test]
+    fn test_version_parse() {
+        assert_eq!("1.1.5".parse::<Version>().unwrap(), Version::new(1, 1, 5));
+        assert_eq!("v1.2\n".parse::<Version>().unwrap(), Version::new(1, 2, 0));
+        assert_eq!("1.1.6-beta".parse::<Version>().unwrap(), Version::new(1, 1, 6));
+        assert!("1".parse::<Version>().is_err());
+        assert!("1.x.0".parse::<Version>().is_err());
+        assert!(Version::new(1, 0, 9) < Version::new(1, 1, 0));
+    }
+
+    #[test]
+    fn test_migrate_from_version() {
+        let root = tempfile::tempdir().unwrap();
+        let (data, config) = legacy_data_dir(root.path());
+        let migrator = SystemMigrator::new(&data, &config);
+        assert_eq!(migrator.detect_version().unwrap(), Version::new(1, 0, 0));
+
+        let report = migrator.migrate(Version::new(1, 0, 0), Version::new(1, 1, 5)).unwrap();
+        assert!(report.succeeded());
+        let statuses: Vec<_> = report.steps.iter().map(|s| s.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                StepStatus::Applied,
+                StepStatus::Applied,
+                StepStatus::Applied,
+                StepStatus::Applied,
+                StepStatus::Skipped("nothing to migrate".to_string()),
+            ]
+        );
+
+        assert_eq!(read_db(&data.join("node.db")), vec!["node"]);
+        assert!(data.join("skills/installed/wasm/echo.wasm").exists());
+        let converted: toml::Table = toml::from_str(&std::fs::read_to_string(&config).unwrap()).unwrap();
+        assert_eq!(converted["node"]["name"].as_str(), Some("legacy"));
+        assert!(!converted.contains_key("node_name"));
+        assert_eq!(migrator.detect_version().unwrap(), Version::new(1, 1, 5));
+
+        // Already up to date
+        assert!(migrator.plan(Version::new(1, 1, 5), Version::new(1, 1, 5)).is_empty());
+    }
+
+    #[test]
+    fn test_migrate_rolls_back_on_failure() {
+        let root = tempfile::tempdir().unwrap();
+        let (data, config) = legacy_data_dir(root.path());
+        // Both the old and the new Matrix database exist, so the last step fails
+        create_db(&data.join("matrix.db"), "old");
+        create_db(&data.join("matrix-events.db"), "new");
+        let original_config = std::fs::read_to_string(&config).unwrap();
+
+        let migrator = SystemMigrator::new(&data, &config);
+        let report = migrator.migrate(Version::new(1, 0, 0), Version::new(1, 1, 5)).unwrap();
+
+        assert!(!report.succeeded());
+        assert!(report.steps[..4].iter().all(|s| s.status == StepStatus::RolledBack));
+        assert!(matches!(report.steps[4].status, StepStatus::Failed(_)));
+
+        assert_eq!(read_db(&data.join("core/core.db")), vec!["node"]);
+        assert!(!data.join("node.db").exists());
+        assert!(data.join("skills/wasm/echo.wasm").exists());
+        assert!(!data.join("skills/installed/wasm").exists());
+        assert_eq!(std::fs::read_to_string(&config).unwrap(), original_config);
+        assert!(!data.join(VERSION_FILE).exists());
+    }
+}
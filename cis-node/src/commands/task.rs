@@ -2,11 +2,22 @@
 //!
 //! Task management - list, create, update, etc.
 
-use anyhow::Result;
-use cis_core::scheduler::{TaskDag, LocalExecutor};
+use anyhow::{anyhow, bail, Result};
+use colored::{ColoredString, Colorize};
+use cis_core::ai::{AiProvider, AiProviderFactory};
+use cis_core::scheduler::{DagNodeStatus, DagRun, DagSpec, DagTaskSpec, TaskDag};
 use cis_core::scheduler::persistence::DagPersistence;
-use cis_core::types::{Task, TaskId, TaskPriority, TaskStatus};
-use std::path::PathBuf;
+use cis_core::scheduler::TaskLevelChange;
+use cis_core::types::{Task, TaskId, TaskLevel, TaskPriority, TaskStatus};
+use dag_executor::{DagExecutorSkill, TaskResult};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// 执行状态轮询间隔
+const EXECUTE_POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 /// Task store for managing tasks - 使用 DAG SQLite 数据库
 pub struct TaskStore {
@@ -62,6 +73,16 @@ impl TaskStore {
         self.persistence.delete_task(id)?;
         Ok(existed)
     }
+
+    /// Set a task's level, recording the change; `false` if the task is missing
+    pub fn set_level(&mut self, id: &str, level: &TaskLevel) -> Result<bool> {
+        Ok(self.persistence.update_task_level(id, level)?)
+    }
+
+    /// Level changes since `since`, newest first
+    pub fn level_changes(&self, since: chrono::DateTime<chrono::Utc>) -> Result<Vec<TaskLevelChange>> {
+        Ok(self.persistence.list_task_level_changes(since)?)
+    }
 }
 
 impl Default for TaskStore {
@@ -70,16 +91,97 @@ impl Default for TaskStore {
     }
 }
 
+/// Task level category, used for filtering and color coding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TaskLevelKind {
+    Mechanical,
+    Recommended,
+    Confirmed,
+    Arbitrated,
+}
+
+impl TaskLevelKind {
+    pub fn of(level: &TaskLevel) -> Self {
+        match level {
+            TaskLevel::Mechanical { .. } => Self::Mechanical,
+            TaskLevel::Recommended { .. } => Self::Recommended,
+            TaskLevel::Confirmed => Self::Confirmed,
+            TaskLevel::Arbitrated { .. } => Self::Arbitrated,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Mechanical => "Mechanical",
+            Self::Recommended => "Recommended",
+            Self::Confirmed => "Confirmed",
+            Self::Arbitrated => "Arbitrated",
+        }
+    }
+
+    /// Color `text` by level: green, cyan, yellow, red
+    pub fn paint(self, text: &str) -> ColoredString {
+        match self {
+            Self::Mechanical => text.green(),
+            Self::Recommended => text.cyan(),
+            Self::Confirmed => text.yellow(),
+            Self::Arbitrated => text.red().bold(),
+        }
+    }
+}
+
+/// Summary of pending tasks per level, e.g. "2 Mechanical, 1 Confirmed pending"
+fn pending_level_summary(tasks: &[&Task]) -> String {
+    let kinds = [
+        TaskLevelKind::Mechanical,
+        TaskLevelKind::Recommended,
+        TaskLevelKind::Confirmed,
+        TaskLevelKind::Arbitrated,
+    ];
+    let counts: Vec<String> = kinds
+        .iter()
+        .map(|&kind| {
+            let count = tasks
+                .iter()
+                .filter(|t| t.status == TaskStatus::Pending && TaskLevelKind::of(&t.level) == kind)
+                .count();
+            format!("{} {}", count, kind.label())
+        })
+        .collect();
+    format!("{} pending", counts.join(", "))
+}
+
 /// List all tasks
-pub fn list_tasks(status: Option<TaskStatus>) -> Result<()> {
+pub fn list_tasks(status: Option<TaskStatus>, level: Option<TaskLevelKind>, json: bool) -> Result<()> {
     let store = TaskStore::load()?;
     let tasks = store.list_all();
     
     let tasks: Vec<_> = tasks
         .iter()
         .filter(|t| status.is_none_or(|s| s == t.status))
+        .filter(|t| level.is_none_or(|l| l == TaskLevelKind::of(&t.level)))
         .collect();
     
+    if json {
+        let items: Vec<_> = tasks
+            .iter()
+            .map(|task| {
+                serde_json::json!({
+                    "id": task.id,
+                    "title": task.title,
+                    "status": format!("{:?}", task.status).to_lowercase(),
+                    "priority": format!("{:?}", task.priority).to_lowercase(),
+                    "level": TaskLevelKind::of(&task.level).label().to_lowercase(),
+                    "level_config": task.level,
+                    "dependencies": task.dependencies,
+                    "created_at": task.created_at.to_rfc3339(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&items)?);
+        return Ok(());
+    }
+    
     if tasks.is_empty() {
         println!("No tasks found.");
         return Ok(());
@@ -87,23 +189,117 @@ pub fn list_tasks(status: Option<TaskStatus>) -> Result<()> {
     
     println!("Tasks:");
     println!(
-        "{:<12} {:<20} {:<10} {:<10} Created",
-        "ID", "Title", "Status", "Priority"
+        "{:<12} {:<20} {:<10} {:<10} {:<12} Created",
+        "ID", "Title", "Status", "Priority", "Level"
     );
     println!("{}", "-".repeat(90));
     
-    for task in tasks {
+    for task in &tasks {
         let created = task.created_at.format("%Y-%m-%d %H:%M");
+        let kind = TaskLevelKind::of(&task.level);
         println!(
-            "{:<12} {:<20} {:<10} {:<10} {}",
+            "{:<12} {:<20} {:<10} {:<10} {} {}",
             task.id,
             truncate(&task.title, 20),
             format!("{:?}", task.status).to_lowercase(),
             format!("{:?}", task.priority).to_lowercase(),
+            kind.paint(&format!("{:<12}", kind.label())),
             created
         );
     }
     
+    println!("{}", "-".repeat(90));
+    println!("{}", pending_level_summary(&tasks));
+    
+    Ok(())
+}
+
+/// One line of `cis task tree`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TreeLine {
+    /// Box-drawing prefix
+    prefix: String,
+    task_id: String,
+    /// Already shown under another dependency; children are not repeated
+    repeated: bool,
+}
+
+/// Flatten the DAG into tree lines, with dependents nested under each dependency
+fn task_tree_lines(dag: &TaskDag) -> Vec<TreeLine> {
+    fn walk(
+        dag: &TaskDag,
+        task_id: &str,
+        indent: &str,
+        branch: Option<bool>,
+        seen: &mut HashSet<String>,
+        lines: &mut Vec<TreeLine>,
+    ) {
+        let (prefix, child_indent) = match branch {
+            None => (String::new(), String::new()),
+            Some(true) => (format!("{}└── ", indent), format!("{}    ", indent)),
+            Some(false) => (format!("{}├── ", indent), format!("{}│   ", indent)),
+        };
+        let repeated = !seen.insert(task_id.to_string());
+        lines.push(TreeLine { prefix, task_id: task_id.to_string(), repeated });
+        if repeated {
+            return;
+        }
+
+        let mut dependents = dag
+            .get_node(task_id)
+            .map(|n| n.dependents.clone())
+            .unwrap_or_default();
+        dependents.sort();
+        for (i, dependent) in dependents.iter().enumerate() {
+            let last = i + 1 == dependents.len();
+            walk(dag, dependent, &child_indent, Some(last), seen, lines);
+        }
+    }
+
+    let mut roots = dag.root_nodes().to_vec();
+    roots.sort();
+    let mut seen = HashSet::new();
+    let mut lines = Vec::new();
+    for root in &roots {
+        walk(dag, root, "", None, &mut seen, &mut lines);
+    }
+    lines
+}
+
+/// Show tasks grouped by their dependency graph
+pub fn task_tree() -> Result<()> {
+    let store = TaskStore::load()?;
+    let tasks = store.list_all();
+    
+    if tasks.is_empty() {
+        println!("No tasks found.");
+        return Ok(());
+    }
+    
+    let dag = build_task_dag(&tasks, &tasks)?;
+    let by_id: HashMap<&str, &Task> = tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+    
+    for line in task_tree_lines(&dag) {
+        let Some(task) = by_id.get(line.task_id.as_str()) else {
+            continue;
+        };
+        let kind = TaskLevelKind::of(&task.level);
+        let suffix = if line.repeated { " (see above)" } else { "" };
+        println!(
+            "{}{} {} [{}] {}{}",
+            line.prefix,
+            kind.paint(&task.id),
+            truncate(&task.title, 40),
+            kind.paint(kind.label()),
+            format!("{:?}", task.status).to_lowercase(),
+            suffix
+        );
+    }
+    
+    let all: Vec<&Task> = tasks.iter().collect();
+    println!();
+    println!("{}", pending_level_summary(&all));
+    
     Ok(())
 }
 
@@ -227,82 +423,488 @@ pub fn delete_task(id: &str) -> Result<()> {
     Ok(())
 }
 
-/// Execute tasks using DAG scheduler
+/// Execute pending tasks using the DAG executor
+///
+/// 1. 从任务存储加载 `Pending` 任务并按依赖构建 `TaskDag`
+/// 2. 创建 `DagRun` 并持久化
+/// 3. 提交到 `DagExecutorSkill`，轮询运行状态直到结束
+/// 4. 按 `completion_criteria` 用 AI 评估结果，回写任务状态
 pub async fn execute_tasks() -> Result<()> {
     let store = TaskStore::load()?;
-    let tasks = store.list_all();
-    
+    let all_tasks = store.list_all();
+    let mut tasks: Vec<Task> = all_tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Pending)
+        .cloned()
+        .collect();
+
     if tasks.is_empty() {
-        println!("No tasks to execute.");
+        println!("No pending tasks to execute.");
         return Ok(());
     }
-    
-    // Build DAG from tasks
-    let mut dag = TaskDag::new();
-    
-    for task in &tasks {
-        let deps: Vec<String> = task.dependencies.clone();
-        dag.add_node(task.id.clone(), deps)?;
-    }
-    
-    // Validate no cycles
-    dag.validate()?;
-    
-    // Get execution order
-    dag.initialize();
+
+    // Build DAG from task dependencies
+    let dag = build_task_dag(&tasks, &all_tasks)?;
     let levels = dag.get_execution_order()?;
-    
+
     println!("Task execution order ({} levels):", levels.len());
     for (i, level) in levels.iter().enumerate() {
         println!("  Level {}: {}", i + 1, level.join(", "));
     }
-    
-    // Execute tasks using LocalExecutor
-    println!("\n🚀 Starting task execution...");
-    
-    let node_id = format!("cis-node-{}", std::process::id());
-    let worker_binary = std::env::current_exe()
-        .unwrap_or_else(|_| std::path::PathBuf::from("cis-node"));
-    let default_room = format!("!worker-default:{}", node_id);
-    
-    let executor = LocalExecutor::new(
-        node_id,
-        worker_binary.to_string_lossy().to_string(),
-        default_room,
-    );
-    
-    // Convert tasks to DagTaskSpec
-    let task_specs: Vec<cis_core::scheduler::DagTaskSpec> = tasks.iter().map(|task| {
-        cis_core::scheduler::DagTaskSpec {
+
+    let task_specs: Vec<DagTaskSpec> = tasks
+        .iter()
+        .map(|task| DagTaskSpec {
             id: task.id.clone(),
             task_type: "command".to_string(),
             command: task.title.clone(),
-            depends_on: task.dependencies.clone(),
-            env: std::collections::HashMap::new(),
+            depends_on: dag
+                .get_node(&task.id)
+                .map(|n| n.dependencies.clone())
+                .unwrap_or_default(),
+            env: HashMap::new(),
+            per_task_retry: None,
+            timeout_secs: None,
+            level: None,
+            fail_fast: false,
+        })
+        .collect();
+    let spec = DagSpec::new(format!("task-dag-{}", uuid::Uuid::new_v4()), task_specs);
+
+    // Persist the run before dispatching
+    let mut run = DagRun::new(dag);
+    run.task_commands = tasks
+        .iter()
+        .map(|t| (t.id.clone(), t.title.clone()))
+        .collect();
+    store.persistence.save_run(&run, &spec)?;
+
+    println!("\n🚀 Starting task execution (run {})...", run.run_id);
+
+    let node_id = format!("cis-node-{}", std::process::id());
+    let worker_binary = std::env::current_exe()
+        .unwrap_or_else(|_| PathBuf::from("cis-node"));
+    let executor = DagExecutorSkill::new(node_id, worker_binary.to_string_lossy().to_string());
+
+    let (exec_run_id, _) = executor
+        .submit_dag(spec.clone())
+        .await
+        .map_err(|e| anyhow!("Failed to submit tasks: {}", e))?;
+
+    let started_at = chrono::Utc::now();
+    for task in &mut tasks {
+        task.status = TaskStatus::Running;
+        task.started_at = Some(started_at);
+        store.persistence.save_task(task)?;
+    }
+
+    let results = wait_for_run(&executor, &exec_run_id, tasks.len()).await?;
+
+    // Evaluate completion criteria and write back task status
+    let provider = AiProviderFactory::default_provider();
+    let (mut passed, mut failed) = (0, 0);
+    for task in &mut tasks {
+        apply_task_result(provider.as_ref(), task, results.get(&task.id)).await;
+        match task.status {
+            TaskStatus::Completed => passed += 1,
+            TaskStatus::Failed => failed += 1,
+            _ => {}
         }
-    }).collect();
-    
-    // Create DAG specification
-    let dag_spec = cis_core::scheduler::DagSpec::new(
-        format!("task-dag-{}", uuid::Uuid::new_v4()),
-        task_specs,
+        store.persistence.save_task(task)?;
+    }
+
+    // Mirror results into the persisted DagRun
+    for level in &levels {
+        for task_id in level {
+            let Some(task) = tasks.iter().find(|t| &t.id == task_id) else {
+                continue;
+            };
+            if run.dag.get_node_status(task_id) != Some(DagNodeStatus::Ready) {
+                continue;
+            }
+            match task.status {
+                TaskStatus::Completed => {
+                    run.dag.mark_running(task_id.clone())?;
+                    run.dag.mark_completed(task_id.clone())?;
+                }
+                TaskStatus::Failed => {
+                    run.dag.mark_running(task_id.clone())?;
+                    run.dag.mark_failed(task_id.clone())?;
+                }
+                _ => {}
+            }
+        }
+    }
+    run.update_status();
+    run.updated_at = chrono::Utc::now();
+    store.persistence.save_run(&run, &spec)?;
+
+    println!();
+    println!("📊 Run {} finished: {:?}", run.run_id, run.status);
+    println!("   Passed: {}, Failed: {}, Not run: {}", passed, failed, tasks.len() - passed - failed);
+
+    Ok(())
+}
+
+/// 按依赖关系构建 TaskDag
+///
+/// 依赖不在本次执行范围内时，必须是已完成的任务。
+fn build_task_dag(tasks: &[Task], all_tasks: &[Task]) -> Result<TaskDag> {
+    let pending: HashSet<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+
+    let mut remaining = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        let mut deps = Vec::new();
+        for dep in &task.dependencies {
+            if pending.contains(dep.as_str()) {
+                deps.push(dep.clone());
+                continue;
+            }
+            match all_tasks.iter().find(|t| &t.id == dep).map(|t| t.status) {
+                Some(TaskStatus::Completed) => {}
+                Some(status) => bail!("Task {} depends on {} which is {:?}", task.id, dep, status),
+                None => bail!("Task {} depends on unknown task {}", task.id, dep),
+            }
+        }
+        remaining.push((task.id.clone(), deps));
+    }
+
+    // 依赖先于下游加入，保证 dependents 关系完整
+    let mut dag = TaskDag::new();
+    while !remaining.is_empty() {
+        let (ready, blocked): (Vec<_>, Vec<_>) = remaining
+            .into_iter()
+            .partition(|(_, deps)| deps.iter().all(|d| dag.get_node(d).is_some()));
+
+        if ready.is_empty() {
+            let ids: Vec<_> = blocked.into_iter().map(|(id, _)| id).collect();
+            bail!("Circular dependency among tasks: {}", ids.join(", "));
+        }
+        for (id, deps) in ready {
+            dag.add_node(id, deps)?;
+        }
+        remaining = blocked;
+    }
+
+    dag.validate()?;
+    dag.initialize();
+    Ok(dag)
+}
+
+/// 轮询运行状态直到结束，显示进度
+async fn wait_for_run(
+    executor: &DagExecutorSkill,
+    run_id: &str,
+    total: usize,
+) -> Result<HashMap<String, TaskResult>> {
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.green} [{elapsed_precise}] {msg}")
+            .unwrap_or_else(|_| ProgressStyle::default_spinner()),
     );
-    
-    // Execute the DAG
-    match executor.execute(&dag_spec).await {
-        Ok(run_id) => {
-            println!("✅ Tasks dispatched successfully");
-            println!("   Run ID: {}", run_id);
-            println!("   Total tasks: {}", tasks.len());
-            println!("\n📊 Execution started. Use 'cis task list' to check status.");
+    spinner.enable_steady_tick(Duration::from_millis(100));
+
+    loop {
+        let status = executor
+            .get_run_status(run_id)
+            .await
+            .ok_or_else(|| anyhow!("DAG run not found: {}", run_id))?;
+
+        let done = status.completed_count + status.failed_count;
+        let percent = if total == 0 { 100 } else { done * 100 / total };
+        spinner.set_message(format!(
+            "{}/{} tasks ({}%), {} failed [{}]",
+            done, total, percent, status.failed_count, status.status
+        ));
+
+        if status.status == "completed" || status.status == "failed" {
+            spinner.finish_with_message(format!(
+                "{}/{} tasks ({}%), {} failed [{}]",
+                done, total, percent, status.failed_count, status.status
+            ));
+            break;
+        }
+
+        tokio::time::sleep(EXECUTE_POLL_INTERVAL).await;
+    }
+
+    Ok(executor.task_results(run_id).await)
+}
+
+/// 根据执行结果与完成标准更新任务
+///
+/// 未上报结果的任务（上游失败）标记为 `Blocked`。
+async fn apply_task_result(provider: &dyn AiProvider, task: &mut Task, result: Option<&TaskResult>) {
+    let Some(result) = result else {
+        task.status = TaskStatus::Blocked;
+        return;
+    };
+
+    task.result = Some(result.output.clone());
+    task.completed_at = Some(chrono::Utc::now());
+
+    if !result.success {
+        task.status = TaskStatus::Failed;
+        task.error = Some("Task execution failed".to_string());
+        return;
+    }
+
+    let Some(criteria) = task.completion_criteria.clone() else {
+        task.status = TaskStatus::Completed;
+        return;
+    };
+
+    let prompt = criteria_prompt(task, &criteria, &result.output);
+    match provider.chat(&prompt).await {
+        Ok(response) => {
+            let (pass, reason) = parse_verdict(&response);
+            if pass {
+                task.status = TaskStatus::Completed;
+            } else {
+                task.status = TaskStatus::Failed;
+                task.error = Some(format!("Completion criteria not met: {}", reason));
+            }
         }
         Err(e) => {
-            eprintln!("❌ Failed to execute tasks: {}", e);
-            return Err(e.into());
+            task.status = TaskStatus::Failed;
+            task.error = Some(format!("Criteria evaluation failed: {}", e));
+        }
+    }
+}
+
+/// 完成标准评估提示词
+fn criteria_prompt(task: &Task, criteria: &str, output: &str) -> String {
+    format!(
+        "Task: {}\nCompletion criteria: {}\n\nTask output:\n{}\n\n\
+         Does the output satisfy the completion criteria? \
+         Answer PASS or FAIL on the first line, followed by a short reason.",
+        task.title, criteria, output
+    )
+}
+
+/// 解析 AI 评估结果：首行以 PASS 开头视为通过
+fn parse_verdict(response: &str) -> (bool, String) {
+    let mut lines = response.trim().lines();
+    let verdict = lines.next().unwrap_or_default().trim().to_ascii_uppercase();
+    let reason = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+    (verdict.starts_with("PASS"), reason)
+}
+
+/// 任务导入格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// `[{ "title", "description", "group", "priority", "criteria" }]`
+    Json,
+    /// 表头为 `title,description,group,priority,criteria`
+    Csv,
+    /// `gh issue list --json title,body,labels,milestone` 输出
+    GithubIssues,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub imported: usize,
+    pub skipped_duplicates: usize,
+    pub errors: Vec<String>,
+}
+
+/// 导入记录（JSON / CSV 共用）
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ImportRecord {
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    group: Option<String>,
+    #[serde(default)]
+    priority: Option<String>,
+    #[serde(default)]
+    criteria: Option<String>,
+}
+
+/// `gh issue list --json` 中的 Issue
+#[derive(Debug, Deserialize)]
+struct GithubIssue {
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    labels: Vec<GithubLabel>,
+    #[serde(default)]
+    milestone: Option<GithubMilestone>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubLabel {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubMilestone {
+    title: String,
+}
+
+impl From<GithubIssue> for ImportRecord {
+    fn from(issue: GithubIssue) -> Self {
+        Self {
+            title: issue.title,
+            description: issue.body,
+            group: issue.labels.into_iter().next().map(|l| l.name),
+            priority: None,
+            criteria: issue.milestone.map(|m| m.title),
+        }
+    }
+}
+
+/// Import tasks from a file
+///
+/// 以 `title + group` 为自然键去重（包括与已有任务、文件内部重复）。
+/// `dry_run` 时只打印将创建的任务。
+pub fn import_tasks(path: &Path, format: ImportFormat, dry_run: bool) -> Result<ImportReport> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+
+    let mut store = TaskStore::load()?;
+    let (tasks, mut report) = plan_import(&content, format, &store.list_all())?;
+
+    for task in tasks {
+        if dry_run {
+            println!("  + {} [{}] ({:?})", task.title, task.group_name, task.priority);
+            report.imported += 1;
+            continue;
+        }
+
+        let title = task.title.clone();
+        match store.add(task) {
+            Ok(()) => report.imported += 1,
+            Err(e) => report.errors.push(format!("{}: {}", title, e)),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Print an import report
+pub fn print_import_report(report: &ImportReport, dry_run: bool) {
+    let verb = if dry_run { "Would import" } else { "Imported" };
+    println!("✅ {} {} task(s)", verb, report.imported);
+
+    if report.skipped_duplicates > 0 {
+        println!("   Skipped {} duplicate(s)", report.skipped_duplicates);
+    }
+
+    if !report.errors.is_empty() {
+        println!("   {} error(s):", report.errors.len());
+        for error in &report.errors {
+            println!("     - {}", error);
         }
     }
-    
-    Ok(())
+}
+
+/// 解析导入内容并生成待创建的任务
+fn plan_import(content: &str, format: ImportFormat, existing: &[Task]) -> Result<(Vec<Task>, ImportReport)> {
+    let mut report = ImportReport::default();
+    let records = parse_import_records(content, format, &mut report.errors)?;
+
+    let mut seen: HashSet<(String, String)> = existing
+        .iter()
+        .map(|t| (t.title.clone(), t.group_name.clone()))
+        .collect();
+
+    let mut tasks = Vec::new();
+    for (index, record) in records {
+        let task = match record_to_task(record) {
+            Ok(task) => task,
+            Err(e) => {
+                report.errors.push(format!("record {}: {}", index, e));
+                continue;
+            }
+        };
+
+        if !seen.insert((task.title.clone(), task.group_name.clone())) {
+            report.skipped_duplicates += 1;
+            continue;
+        }
+        tasks.push(task);
+    }
+
+    Ok((tasks, report))
+}
+
+/// 解析导入记录，返回 `(记录序号, 记录)`；单条记录错误写入 `errors`
+fn parse_import_records(
+    content: &str,
+    format: ImportFormat,
+    errors: &mut Vec<String>,
+) -> Result<Vec<(usize, ImportRecord)>> {
+    let mut records = Vec::new();
+
+    match format {
+        ImportFormat::Json | ImportFormat::GithubIssues => {
+            let items: Vec<serde_json::Value> = serde_json::from_str(content)
+                .map_err(|e| anyhow!("Expected a JSON array: {}", e))?;
+
+            for (i, item) in items.into_iter().enumerate() {
+                let record = if format == ImportFormat::GithubIssues {
+                    serde_json::from_value::<GithubIssue>(item).map(ImportRecord::from)
+                } else {
+                    serde_json::from_value::<ImportRecord>(item)
+                };
+                match record {
+                    Ok(record) => records.push((i + 1, record)),
+                    Err(e) => errors.push(format!("record {}: {}", i + 1, e)),
+                }
+            }
+        }
+        ImportFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .from_reader(content.as_bytes());
+
+            for (i, row) in reader.deserialize::<ImportRecord>().enumerate() {
+                match row {
+                    Ok(record) => records.push((i + 1, record)),
+                    Err(e) => errors.push(format!("record {}: {}", i + 1, e)),
+                }
+            }
+        }
+    }
+
+    Ok(records)
+}
+
+/// 导入记录转换为任务
+fn record_to_task(record: ImportRecord) -> Result<Task> {
+    let non_empty = |s: Option<String>| s.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+
+    let title = record.title.trim().to_string();
+    if title.is_empty() {
+        bail!("missing title");
+    }
+
+    let group = non_empty(record.group).unwrap_or_else(|| "default".to_string());
+    let mut task = Task::new(generate_task_id(), title, group);
+    task.description = non_empty(record.description);
+    task.completion_criteria = non_empty(record.criteria);
+
+    if let Some(priority) = non_empty(record.priority) {
+        task.priority = parse_priority(&priority)?;
+    }
+
+    Ok(task)
+}
+
+/// 解析优先级（不区分大小写）
+fn parse_priority(s: &str) -> Result<TaskPriority> {
+    match s.to_ascii_lowercase().as_str() {
+        "low" => Ok(TaskPriority::Low),
+        "medium" => Ok(TaskPriority::Medium),
+        "high" => Ok(TaskPriority::High),
+        "urgent" => Ok(TaskPriority::Urgent),
+        other => bail!("unknown priority '{}'", other),
+    }
 }
 
 /// Helper: Generate a simple task ID
@@ -325,3 +927,150 @@ fn truncate(s: &str, max_len: usize) -> String {
         s.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, deps: &[&str], status: TaskStatus) -> Task {
+        let mut task = Task::new(id.to_string(), id.to_string(), "default".to_string());
+        task.dependencies = deps.iter().map(|d| d.to_string()).collect();
+        task.status = status;
+        task
+    }
+
+    #[test]
+    fn test_build_task_dag() {
+        let all = vec![
+            // 下游先于上游出现
+            task("c", &["b"], TaskStatus::Pending),
+            task("b", &["a", "done"], TaskStatus::Pending),
+            task("a", &[], TaskStatus::Pending),
+            task("done", &[], TaskStatus::Completed),
+        ];
+        let pending: Vec<_> = all[..3].to_vec();
+
+        let dag = build_task_dag(&pending, &all).unwrap();
+        assert_eq!(
+            dag.get_execution_order().unwrap(),
+            vec![vec!["a".to_string()], vec!["b".to_string()], vec!["c".to_string()]]
+        );
+        // 已完成的依赖不进入 DAG
+        assert_eq!(dag.get_node("b").unwrap().dependencies, vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn test_build_task_dag_rejects_unmet_dependencies() {
+        let all = vec![
+            task("a", &["failed"], TaskStatus::Pending),
+            task("failed", &[], TaskStatus::Failed),
+        ];
+        assert!(build_task_dag(&all[..1], &all).is_err());
+
+        let cycle = vec![task("a", &["b"], TaskStatus::Pending), task("b", &["a"], TaskStatus::Pending)];
+        assert!(build_task_dag(&cycle, &cycle).is_err());
+    }
+
+    #[test]
+    fn test_task_tree_lines() {
+        let tasks = vec![
+            task("a", &[], TaskStatus::Completed),
+            task("b", &["a"], TaskStatus::Pending),
+            task("c", &["a"], TaskStatus::Pending),
+            task("d", &["b", "c"], TaskStatus::Pending),
+            task("e", &[], TaskStatus::Pending),
+        ];
+        let dag = build_task_dag(&tasks, &tasks).unwrap();
+
+        let lines: Vec<(String, String, bool)> = task_tree_lines(&dag)
+            .into_iter()
+            .map(|l| (l.prefix, l.task_id, l.repeated))
+            .collect();
+        let expected = [
+            ("", "a", false),
+            ("├── ", "b", false),
+            ("│   └── ", "d", false),
+            ("└── ", "c", false),
+            ("    └── ", "d", true),
+            ("", "e", false),
+        ];
+        let expected: Vec<(String, String, bool)> = expected
+            .iter()
+            .map(|(p, id, r)| (p.to_string(), id.to_string(), *r))
+            .collect();
+        assert_eq!(lines, expected);
+    }
+
+    #[test]
+    fn test_pending_level_summary() {
+        let mut confirmed = task("b", &[], TaskStatus::Pending);
+        confirmed.level = TaskLevel::Confirmed;
+        let mut arbitrated = task("c", &[], TaskStatus::Completed);
+        arbitrated.level = TaskLevel::Arbitrated { stakeholders: vec![] };
+        let tasks = [task("a", &[], TaskStatus::Pending), confirmed, arbitrated];
+        let refs: Vec<&Task> = tasks.iter().collect();
+
+        assert_eq!(
+            pending_level_summary(&refs),
+            "1 Mechanical, 0 Recommended, 1 Confirmed, 0 Arbitrated pending"
+        );
+        assert_eq!(TaskLevelKind::of(&tasks[2].level), TaskLevelKind::Arbitrated);
+    }
+
+    #[test]
+    fn test_plan_import_json() {
+        let existing = vec![task("existing", &[], TaskStatus::Pending)];
+        let content = r#"[
+            {"title": "Write docs", "group": "docs", "priority": "High"},
+            {"title": "Write docs", "group": "docs"},
+            {"title": "existing", "group": "default"},
+            {"title": "  ", "group": "default"},
+            {"title": "Bad priority", "priority": "someday"},
+            {"description": "no title"}
+        ]"#;
+
+        let (tasks, report) = plan_import(content, ImportFormat::Json, &existing).unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].group_name, "docs");
+        assert_eq!(tasks[0].priority, TaskPriority::High);
+        assert_eq!(report.skipped_duplicates, 2);
+        assert_eq!(report.errors.len(), 3);
+    }
+
+    #[test]
+    fn test_plan_import_csv() {
+        let content = "title,description,group,priority,criteria\n\
+                       Build,\"compile, link\",ci,low,\n\
+                       Test,,,,all green\n";
+
+        let (tasks, report) = plan_import(content, ImportFormat::Csv, &[]).unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].description.as_deref(), Some("compile, link"));
+        assert_eq!(tasks[0].completion_criteria, None);
+        assert_eq!(tasks[1].group_name, "default");
+        assert_eq!(tasks[1].completion_criteria.as_deref(), Some("all green"));
+    }
+
+    #[test]
+    fn test_plan_import_github_issues() {
+        let content = r#"[
+            {"title": "Fix crash", "body": "Steps to reproduce", "labels": [{"name": "bug"}, {"name": "p1"}], "milestone": {"title": "v1.2"}},
+            {"title": "Refactor", "body": "", "labels": [], "milestone": null}
+        ]"#;
+
+        let (tasks, _) = plan_import(content, ImportFormat::GithubIssues, &[]).unwrap();
+        assert_eq!(tasks[0].group_name, "bug");
+        assert_eq!(tasks[0].description.as_deref(), Some("Steps to reproduce"));
+        assert_eq!(tasks[0].completion_criteria.as_deref(), Some("v1.2"));
+        assert_eq!(tasks[1].group_name, "default");
+        assert_eq!(tasks[1].description, None);
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(parse_verdict("PASS\nAll tests green"), (true, "All tests green".to_string()));
+        assert_eq!(parse_verdict("  fail: missing file\nno output.txt"), (false, "no output.txt".to_string()));
+        assert!(!parse_verdict("").0);
+    }
+}
@@ -5,12 +5,21 @@
 //! - `cis task-level recommended <task-id>` - Set task to countdown execution
 //! - `cis task-level confirmed <task-id>` - Set task to modal confirmation
 //! - `cis task-level arbitrated <task-id>` - Set task to pause for arbitration
+//! - `cis task-level set <task-id> --level <level>` - Set any level (or `--group` for a whole group)
+//! - `cis task-level audit --days <n>` - List recent level changes
+//!
+//! Arbitrated stakeholders must be known DIDs in the network ACL (the local
+//! node or a whitelisted peer).
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::Subcommand;
+use cis_core::network::NetworkAcl;
 use cis_core::types::{Action, Task, TaskLevel};
 
-use crate::commands::task::TaskStore;
+use crate::commands::task::{TaskLevelKind, TaskStore};
+
+/// Placeholder stakeholder used when none is given; not validated against the ACL
+const DEFAULT_STAKEHOLDER: &str = "default";
 
 /// Task level management commands (four-tier decision)
 #[derive(Debug, Subcommand)]
@@ -50,6 +59,38 @@ pub enum TaskLevelCommands {
         #[arg(short, long, value_delimiter = ',')]
         stakeholders: Vec<String>,
     },
+
+    /// Set the level of a task, or of every task in a group
+    Set {
+        /// Task ID to set level for
+        #[arg(required_unless_present = "group")]
+        task_id: Option<String>,
+        /// Set the level for all tasks in this group instead
+        #[arg(long, conflicts_with = "task_id")]
+        group: Option<String>,
+        /// Level to apply
+        #[arg(short, long)]
+        level: TaskLevelKind,
+        /// Retries on failure (mechanical)
+        #[arg(long, default_value = "3")]
+        retry: u8,
+        /// Countdown in seconds (recommended)
+        #[arg(long, default_value = "30")]
+        timeout: u16,
+        /// Default action after the countdown (recommended)
+        #[arg(long, default_value = "execute")]
+        default_action: DefaultAction,
+        /// Stakeholder DIDs (arbitrated)
+        #[arg(short, long, value_delimiter = ',')]
+        stakeholders: Vec<String>,
+    },
+
+    /// List tasks whose level changed recently
+    Audit {
+        /// Look back this many days
+        #[arg(short, long, default_value = "7")]
+        days: u32,
+    },
 }
 
 /// Default action for Recommended level
@@ -92,28 +133,155 @@ pub async fn handle(cmd: TaskLevelCommands) -> Result<()> {
         } => {
             set_arbitrated(&task_id, stakeholders).await?;
         }
+        TaskLevelCommands::Set {
+            task_id,
+            group,
+            level,
+            retry,
+            timeout,
+            default_action,
+            stakeholders,
+        } => {
+            let level = match level {
+                TaskLevelKind::Mechanical => TaskLevel::Mechanical { retry },
+                TaskLevelKind::Recommended => TaskLevel::Recommended {
+                    default_action: default_action.into(),
+                    timeout_secs: timeout,
+                },
+                TaskLevelKind::Confirmed => TaskLevel::Confirmed,
+                TaskLevelKind::Arbitrated => TaskLevel::Arbitrated {
+                    stakeholders: with_default_stakeholder(stakeholders),
+                },
+            };
+
+            match (task_id, group) {
+                (_, Some(group)) => {
+                    let count = bulk_set_level(&group, level.clone()).await?;
+                    println!(
+                        "✓ {} task(s) in group '{}' set to {}",
+                        count,
+                        group,
+                        describe_level(&level)
+                    );
+                }
+                (Some(task_id), None) => {
+                    set_task_level(&task_id, level.clone()).await?;
+                    println!("✓ Task {} set to {}", task_id, describe_level(&level));
+                }
+                (None, None) => bail!("Either a task ID or --group is required"),
+            }
+        }
+        TaskLevelCommands::Audit { days } => {
+            audit_level_changes(days)?;
+        }
     }
 
     Ok(())
 }
 
-/// Set task to Mechanical level (auto-execute)
-pub async fn set_mechanical(task_id: &str, retry: u8) -> Result<()> {
+/// Set a task's level, recording the change for `audit`
+///
+/// Arbitrated stakeholders are validated against the network ACL first.
+pub async fn set_task_level(task_id: &str, level: TaskLevel) -> Result<()> {
+    validate_level(&level)?;
+
     let mut store = TaskStore::load()?;
+    if !store.set_level(task_id, &level)? {
+        bail!("Task not found: {}", task_id);
+    }
+    Ok(())
+}
 
-    let task = match store.get(task_id) {
-        Some(t) => t,
-        None => {
-            println!("Task not found: {}", task_id);
-            return Ok(());
-        }
+/// Set the level of every task in `group`, returning how many tasks changed
+pub async fn bulk_set_level(group: &str, level: TaskLevel) -> Result<usize> {
+    validate_level(&level)?;
+
+    let mut store = TaskStore::load()?;
+    let tasks: Vec<Task> = store
+        .list_all()
+        .into_iter()
+        .filter(|t| t.group_name == group && t.level != level)
+        .collect();
+
+    for task in &tasks {
+        store.set_level(&task.id, &level)?;
+    }
+    Ok(tasks.len())
+}
+
+/// Print level changes from the last `days` days
+pub fn audit_level_changes(days: u32) -> Result<()> {
+    let store = TaskStore::load()?;
+    let since = chrono::Utc::now() - chrono::Duration::days(i64::from(days));
+    let changes = store.level_changes(since)?;
+
+    if changes.is_empty() {
+        println!("No task level changes in the last {} day(s)", days);
+        return Ok(());
+    }
+
+    println!("Task level changes in the last {} day(s):", days);
+    println!("{:<20} {:<24} {:<28} New level", "Changed at", "Task", "Old level");
+    for change in &changes {
+        println!(
+            "{:<20} {:<24} {:<28} {}",
+            change.changed_at.format("%Y-%m-%d %H:%M:%S"),
+            change.task_id,
+            describe_level(&change.old_level),
+            describe_level(&change.new_level)
+        );
+    }
+
+    Ok(())
+}
+
+/// Reject arbitrated levels naming stakeholders unknown to the network ACL
+fn validate_level(level: &TaskLevel) -> Result<()> {
+    let TaskLevel::Arbitrated { stakeholders } = level else {
+        return Ok(());
     };
+    if stakeholders.iter().all(|s| s == DEFAULT_STAKEHOLDER) {
+        return Ok(());
+    }
 
-    let mut updated_task = task.clone();
-    updated_task.level = TaskLevel::Mechanical { retry };
+    let acl_path = cis_core::network::default_acl_path();
+    if !acl_path.exists() {
+        bail!(
+            "Network ACL not found at {}; cannot validate stakeholders",
+            acl_path.display()
+        );
+    }
+    let acl = NetworkAcl::load(&acl_path)?;
 
-    // Update task in store
-    update_task_in_store(&mut store, updated_task)?;
+    let unknown = unknown_stakeholders(&acl, stakeholders);
+    if !unknown.is_empty() {
+        bail!("Unknown stakeholder DID(s): {}", unknown.join(", "));
+    }
+    Ok(())
+}
+
+/// Stakeholders that are neither the local node nor whitelisted
+fn unknown_stakeholders(acl: &NetworkAcl, stakeholders: &[String]) -> Vec<String> {
+    stakeholders
+        .iter()
+        .filter(|s| {
+            s.as_str() != DEFAULT_STAKEHOLDER && **s != acl.local_did && !acl.is_whitelisted(s)
+        })
+        .cloned()
+        .collect()
+}
+
+fn with_default_stakeholder(stakeholders: Vec<String>) -> Vec<String> {
+    if stakeholders.is_empty() {
+        vec![DEFAULT_STAKEHOLDER.to_string()]
+    } else {
+        stakeholders
+    }
+}
+
+/// Set task to Mechanical level (auto-execute)
+pub async fn set_mechanical(task_id: &str, retry: u8) -> Result<()> {
+    set_task_level(task_id, TaskLevel::Mechanical { retry }).await?;
 
     println!("✓ Task {} set to Mechanical level", task_id);
     println!("  Auto-execute with {} retries on failure", retry);
@@ -127,24 +295,14 @@ pub async fn set_recommended(
     timeout: u16,
     default_action: Action,
 ) -> Result<()> {
-    let mut store = TaskStore::load()?;
-
-    let task = match store.get(task_id) {
-        Some(t) => t,
-        None => {
-            println!("Task not found: {}", task_id);
-            return Ok(());
-        }
-    };
-
-    let mut updated_task = task.clone();
-    updated_task.level = TaskLevel::Recommended {
-        default_action,
-        timeout_secs: timeout,
-    };
-
-    // Update task in store
-    update_task_in_store(&mut store, updated_task)?;
+    set_task_level(
+        task_id,
+        TaskLevel::Recommended {
+            default_action,
+            timeout_secs: timeout,
+        },
+    )
+    .await?;
 
     println!("✓ Task {} set to Recommended level", task_id);
     println!("  Countdown: {} seconds", timeout);
@@ -158,21 +316,7 @@ pub async fn set_recommended(
 
 /// Set task to Confirmed level (modal confirmation)
 pub async fn set_confirmed(task_id: &str) -> Result<()> {
-    let mut store = TaskStore::load()?;
-
-    let task = match store.get(task_id) {
-        Some(t) => t,
-        None => {
-            println!("Task not found: {}", task_id);
-            return Ok(());
-        }
-    };
-
-    let mut updated_task = task.clone();
-    updated_task.level = TaskLevel::Confirmed;
-
-    // Update task in store
-    update_task_in_store(&mut store, updated_task)?;
+    set_task_level(task_id, TaskLevel::Confirmed).await?;
 
     println!("✓ Task {} set to Confirmed level", task_id);
     println!("  Modal confirmation required before execution");
@@ -182,48 +326,22 @@ pub async fn set_confirmed(task_id: &str) -> Result<()> {
 
 /// Set task to Arbitrated level (pause for arbitration)
 pub async fn set_arbitrated(task_id: &str, stakeholders: Vec<String>) -> Result<()> {
-    let mut store = TaskStore::load()?;
-
-    let task = match store.get(task_id) {
-        Some(t) => t,
-        None => {
-            println!("Task not found: {}", task_id);
-            return Ok(());
-        }
-    };
-
-    let stakeholders = if stakeholders.is_empty() {
-        vec!["default".to_string()]
-    } else {
-        stakeholders
-    };
-
-    let mut updated_task = task.clone();
-    updated_task.level = TaskLevel::Arbitrated {
-        stakeholders: stakeholders.clone(),
-    };
-
-    // Update task in store
-    update_task_in_store(&mut store, updated_task)?;
+    let stakeholders = with_default_stakeholder(stakeholders);
+    set_task_level(
+        task_id,
+        TaskLevel::Arbitrated {
+            stakeholders: stakeholders.clone(),
+        },
+    )
+    .await?;
 
     println!("✓ Task {} set to Arbitrated level", task_id);
     println!("  DAG will pause for human arbitration");
-    if !stakeholders.is_empty() {
-        println!("  Stakeholders: {}", stakeholders.join(", "));
-    }
+    println!("  Stakeholders: {}", stakeholders.join(", "));
 
     Ok(())
 }
 
-/// Helper: Update task in store
-fn update_task_in_store(store: &mut TaskStore, updated_task: Task) -> Result<()> {
-    // Remove old task and add updated one
-    store.remove(&updated_task.id).map_err(|e| anyhow::anyhow!("Failed to remove task: {}", e))?;
-    store.add(updated_task).map_err(|e| anyhow::anyhow!("Failed to add task: {}", e))?;
-    store.save()?;
-    Ok(())
-}
-
 /// Helper: Format action for display
 fn format_action(action: Action) -> &'static str {
     match action {
@@ -269,6 +387,25 @@ fn format_task_level(level: &TaskLevel) -> String {
     }
 }
 
+/// Helper: Format task level with its parameters, e.g. `Mechanical(retry=3)`
+fn describe_level(level: &TaskLevel) -> String {
+    match level {
+        TaskLevel::Mechanical { retry } => format!("Mechanical(retry={})", retry),
+        TaskLevel::Recommended {
+            default_action,
+            timeout_secs,
+        } => format!(
+            "Recommended({}s, {})",
+            timeout_secs,
+            format_action(*default_action).to_lowercase()
+        ),
+        TaskLevel::Confirmed => "Confirmed".to_string(),
+        TaskLevel::Arbitrated { stakeholders } => {
+            format!("Arbitrated({})", stakeholders.join(", "))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -282,4 +419,26 @@ mod tests {
         assert_eq!(Action::from(DefaultAction::Skip), Action::Skip);
         assert_eq!(Action::from(DefaultAction::Abort), Action::Abort);
     }
+
+    #[test]
+    fn test_unknown_stakeholders() {
+        let mut acl = NetworkAcl::new("did:cis:local:abc");
+        acl.allow("did:cis:peer:def", "did:cis:local:abc");
+
+        let stakeholders = vec![
+            "did:cis:local:abc".to_string(),
+            "did:cis:peer:def".to_string(),
+            "did:cis:stranger:xyz".to_string(),
+            DEFAULT_STAKEHOLDER.to_string(),
+        ];
+        assert_eq!(unknown_stakeholders(&acl, &stakeholders), vec!["did:cis:stranger:xyz"]);
+
+        assert_eq!(
+            describe_level(&TaskLevel::Recommended {
+                default_action: Action::Skip,
+                timeout_secs: 30
+            }),
+            "Recommended(30s, skip)"
+        );
+    }
 }
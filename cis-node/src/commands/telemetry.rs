@@ -114,7 +114,15 @@ pub fn handle_telemetry(action: TelemetryAction) -> anyhow::Result<()> {
             }
         }
         
-        TelemetryAction::Stats { session } => {
+        TelemetryAction::Stats { session, json } => {
+            if json && session.is_none() {
+                let stats = logger.skill_stats()
+                    .map_err(|e| anyhow::anyhow!("Failed to get skill stats: {}", e))?;
+                println!("{}", serde_json::to_string_pretty(&stats)?);
+                return Ok(());
+            }
+            
+            let show_skills = session.is_none();
             let stats = if let Some(session_id) = session {
                 println!("📈 会话统计: {}\n", session_id);
                 logger.get_session_stats(&session_id)
@@ -134,6 +142,19 @@ pub fn handle_telemetry(action: TelemetryAction) -> anyhow::Result<()> {
             );
             println!("失败:          {}", stats.failed_requests);
             println!("平均耗时:      {}ms", stats.average_duration_ms);
+            
+            if show_skills {
+                let skill_stats = logger.skill_stats()
+                    .map_err(|e| anyhow::anyhow!("Failed to get skill stats: {}", e))?;
+                print_skill_stats(&skill_stats);
+            }
+
+            // 本进程内的 embedding 缓存统计
+            let cache = cis_core::vector::embedding_cache_stats();
+            println!("\n🧠 Embedding 缓存\n");
+            println!("命中:          {} ({:.1}%)", cache.hits, cache.hit_rate() * 100.0);
+            println!("未命中:        {}", cache.misses);
+            println!("淘汰:          {}", cache.evictions);
         }
         
         TelemetryAction::Sessions { limit } => {
@@ -160,3 +181,45 @@ pub fn handle_telemetry(action: TelemetryAction) -> anyhow::Result<()> {
     
     Ok(())
 }
+
+/// 按技能输出统计表，错误率异常的技能以红色标出
+fn print_skill_stats(stats: &cis_core::telemetry::TelemetryStats) {
+    use cis_core::telemetry::TrendDirection;
+    use colored::Colorize;
+    
+    println!("\n🧩 技能统计（最近 1 小时 / 1 天 / 7 天）\n");
+    if stats.skills.is_empty() {
+        println!("最近 7 天没有请求");
+        return;
+    }
+    
+    println!(
+        "{:<24} {:>16} {:>20} {:>18} {:>10}",
+        "技能", "调用量", "成功率", "p50/p95/p99 (1d)", "趋势"
+    );
+    for skill in &stats.skills {
+        let trend = match skill.trend.direction {
+            TrendDirection::Up => format!("↑{:.0}%", skill.trend.change_pct),
+            TrendDirection::Down => format!("↓{:.0}%", skill.trend.change_pct.abs()),
+            TrendDirection::Stable => "→".to_string(),
+        };
+        let line = format!(
+            "{:<24} {:>16} {:>20} {:>18} {:>10}",
+            skill.skill_id,
+            format!("{}/{}/{}", skill.last_hour.call_count, skill.last_day.call_count, skill.last_week.call_count),
+            format!(
+                "{:.0}%/{:.0}%/{:.0}%",
+                skill.last_hour.success_rate * 100.0,
+                skill.last_day.success_rate * 100.0,
+                skill.last_week.success_rate * 100.0
+            ),
+            format!("{}/{}/{}ms", skill.last_day.p50_ms, skill.last_day.p95_ms, skill.last_day.p99_ms),
+            trend
+        );
+        if skill.anomaly {
+            println!("{}  ⚠ 错误率异常", line.red());
+        } else {
+            println!("{}", line);
+        }
+    }
+}
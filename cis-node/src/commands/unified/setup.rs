@@ -107,7 +107,6 @@ id = "{}"
 name = "{}"
 did = "{}"
 role = "{}"
-key = "{}"
 
 [ai]
 default_provider = "claude"
@@ -132,11 +131,26 @@ backup_interval_days = 7
         node.name,
         node.did,
         format!("{:?}", role).to_lowercase(),
-        generate_random_key()
     );
     
     tokio::fs::write(&config_path, config).await?;
     
+    // 节点密钥单独保存在 node.key，不写入配置文件
+    let claim = cis_core::identity::NodeClaimService::open_default();
+    if !claim.has_node_key() {
+        let key_path = cis_core::storage::paths::Paths::node_key_file();
+        if let Some(parent) = key_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&key_path, generate_node_key()).await?;
+        
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&key_path, std::fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    
     Ok(())
 }
 
@@ -164,12 +178,12 @@ fn is_initialized() -> bool {
     config_path.exists()
 }
 
-/// 生成随机密钥
-fn generate_random_key() -> String {
+/// 生成节点密钥
+fn generate_node_key() -> [u8; 32] {
     use rand::RngCore;
     let mut key = [0u8; 32];
     rand::thread_rng().fill_bytes(&mut key);
-    hex::encode(key)
+    key
 }
 
 #[derive(Debug)]
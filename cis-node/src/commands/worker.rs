@@ -338,6 +338,16 @@ pub enum WorkerCommands {
         #[arg(long, default_value = "")]
         matrix_token: String,
         
+        /// Directory shell tasks may run in (repeatable, defaults to the CIS
+        /// data directory and the current directory)
+        #[arg(long = "allowed-root")]
+        allowed_roots: Vec<std::path::PathBuf>,
+        
+        /// Shell operator tasks may use, e.g. `&&` or `|` (repeatable;
+        /// all operators are rejected by default)
+        #[arg(long = "shell-allow")]
+        shell_allow: Vec<String>,
+        
         /// Run in background (detached mode)
         #[arg(long, short = 'd')]
         detach: bool,
@@ -543,9 +553,17 @@ pub async fn handle(cmd: WorkerCommands) -> Result<()> {
             max_memory_mb,
             matrix_server,
             matrix_token,
+            allowed_roots,
+            shell_allow,
             detach,
         } => {
             let worker_id = worker_id.unwrap_or_else(generate_worker_id);
+            let allowed_roots = if allowed_roots.is_empty() {
+                default_allowed_roots()
+            } else {
+                allowed_roots
+            };
+            let shell_rules = shell_rules(&shell_allow)?;
             let args = WorkerArgs {
                 worker_id,
                 room,
@@ -558,6 +576,8 @@ pub async fn handle(cmd: WorkerCommands) -> Result<()> {
                 max_memory_mb,
                 matrix_server,
                 matrix_token,
+                allowed_roots,
+                shell_rules,
             };
             if detach {
                 run_worker_detached(args).await
@@ -635,6 +655,32 @@ pub struct WorkerArgs {
     pub matrix_server: String,
     /// Matrix access token
     pub matrix_token: String,
+    /// Roots a shell task's working directory must resolve under
+    pub allowed_roots: Vec<std::path::PathBuf>,
+    /// Shell operators rejected in task commands
+    pub shell_rules: cis_core::sandbox::ShellRules,
+}
+
+/// Default sandbox roots: the CIS data directory and the current directory
+pub(crate) fn default_allowed_roots() -> Vec<std::path::PathBuf> {
+    let mut roots = vec![cis_core::storage::Paths::data_dir()];
+    if let Ok(cwd) = std::env::current_dir() {
+        roots.push(cwd);
+    }
+    roots
+}
+
+/// Sandbox shell rules with the `--shell-allow` operators permitted
+fn shell_rules(allow: &[String]) -> Result<cis_core::sandbox::ShellRules> {
+    allow.iter().try_fold(cis_core::sandbox::ShellRules::default(), |rules, op| {
+        rules.allow(op).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown shell operator {:?} (expected one of {:?})",
+                op,
+                cis_core::sandbox::SHELL_OPERATORS
+            )
+        })
+    })
 }
 
 /// Run the worker process
@@ -724,6 +770,8 @@ async fn run_worker(args: WorkerArgs) -> Result<()> {
     let active_tasks = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
     
     let shutdown_requested = false;
+    let mut run_control = RunControl::default();
+    let task_outputs = open_task_output_store()?;
     
     // Main event loop
     loop {
@@ -753,7 +801,7 @@ async fn run_worker(args: WorkerArgs) -> Result<()> {
                 match event {
                     Ok(Some(task_event)) => {
                         active_tasks.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                        let result = handle_task_event(&args, &room_conn, task_event).await;
+                        let result = handle_task_event(&args, &room_conn, &mut run_control, &task_outputs, task_event).await;
                         active_tasks.fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
                         tasks_executed.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
                         
@@ -1878,12 +1926,54 @@ pub enum TaskEvent {
     CancelTask {
         task_id: String,
     },
+    /// Pause a DAG run (`dag.pause`)
+    PauseRun {
+        run_id: String,
+    },
+    /// Resume a paused DAG run (`dag.resume`)
+    ResumeRun {
+        run_id: String,
+    },
     /// Heartbeat from parent node
     Heartbeat,
     /// Shutdown request
     Shutdown,
 }
 
+/// Pause state of the DAG runs dispatched to this worker
+///
+/// Tasks that arrive for a paused run are held back and released in arrival
+/// order once the run is resumed. Tasks already executing run to completion.
+#[derive(Debug, Default)]
+struct RunControl {
+    paused: std::collections::HashSet<String>,
+    held: std::collections::HashMap<String, Vec<(String, cis_core::scheduler::DagTaskSpec)>>,
+}
+
+impl RunControl {
+    /// Mark a run as paused, returns false if it already was
+    fn pause(&mut self, run_id: &str) -> bool {
+        self.paused.insert(run_id.to_string())
+    }
+
+    /// Clear the pause flag and return the tasks held while paused
+    fn resume(&mut self, run_id: &str) -> Vec<(String, cis_core::scheduler::DagTaskSpec)> {
+        self.paused.remove(run_id);
+        self.held.remove(run_id).unwrap_or_default()
+    }
+
+    fn is_paused(&self, run_id: &str) -> bool {
+        self.paused.contains(run_id)
+    }
+
+    fn hold(&mut self, run_id: &str, task_id: String, task_spec: cis_core::scheduler::DagTaskSpec) {
+        self.held
+            .entry(run_id.to_string())
+            .or_default()
+            .push((task_id, task_spec));
+    }
+}
+
 /// Poll room for new events
 async fn poll_room_events(room_conn: &RoomConnection) -> Result<Option<TaskEvent>> {
     // If Matrix client is available, use it to poll events
@@ -1960,6 +2050,10 @@ fn parse_task_from_json(json: &serde_json::Value) -> Option<TaskEvent> {
                     .as_object()
                     .map(|obj| obj.iter().filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string()))).collect())
                     .unwrap_or_default(),
+                per_task_retry: serde_json::from_value(task["retry"].clone()).ok(),
+                timeout_secs: task["timeout_secs"].as_u64(),
+                level: serde_json::from_value(task["level"].clone()).ok(),
+                fail_fast: task["fail_fast"].as_bool().unwrap_or(false),
             };
             
             Some(TaskEvent::NewTask {
@@ -1972,6 +2066,14 @@ fn parse_task_from_json(json: &serde_json::Value) -> Option<TaskEvent> {
             let task_id = json["task_id"].as_str()?.to_string();
             Some(TaskEvent::CancelTask { task_id })
         }
+        "dag.pause" => {
+            let run_id = json["run_id"].as_str()?.to_string();
+            Some(TaskEvent::PauseRun { run_id })
+        }
+        "dag.resume" => {
+            let run_id = json["run_id"].as_str()?.to_string();
+            Some(TaskEvent::ResumeRun { run_id })
+        }
         "cis.worker.heartbeat" => {
             Some(TaskEvent::Heartbeat)
         }
@@ -1986,12 +2088,35 @@ fn parse_task_from_json(json: &serde_json::Value) -> Option<TaskEvent> {
 async fn handle_task_event(
     args: &WorkerArgs,
     room_conn: &RoomConnection,
+    run_control: &mut RunControl,
+    task_outputs: &TaskOutputStore,
     event: TaskEvent,
 ) -> Result<()> {
     match event {
         TaskEvent::NewTask { task_id, dag_id, task_spec } => {
             info!("Received new task: {} (DAG: {})", task_id, dag_id);
-            execute_task(args, room_conn, &task_id, &dag_id, &task_spec).await?;
+            if run_control.is_paused(&dag_id) {
+                info!("Run {} is paused, holding task {}", dag_id, task_id);
+                run_control.hold(&dag_id, task_id, task_spec);
+            } else {
+                execute_task(args, room_conn, task_outputs, &task_id, &dag_id, &task_spec).await?;
+            }
+        }
+        TaskEvent::PauseRun { run_id } => {
+            if run_control.pause(&run_id) {
+                println!("⏸️  Run {} paused", run_id);
+            } else {
+                debug!("Run {} is already paused", run_id);
+            }
+        }
+        TaskEvent::ResumeRun { run_id } => {
+            let held = run_control.resume(&run_id);
+            println!("▶️  Run {} resumed ({} held task(s))", run_id, held.len());
+            for (task_id, task_spec) in held {
+                if let Err(e) = execute_task(args, room_conn, task_outputs, &task_id, &run_id, &task_spec).await {
+                    error!("Error executing held task {}: {}", task_id, e);
+                }
+            }
         }
         TaskEvent::CancelTask { task_id } => {
             info!("Received cancellation for task: {}", task_id);
@@ -2017,6 +2142,7 @@ async fn handle_task_event(
 async fn execute_task(
     args: &WorkerArgs,
     room_conn: &RoomConnection,
+    task_outputs: &TaskOutputStore,
     task_id: &str,
     dag_id: &str,
     task_spec: &cis_core::scheduler::DagTaskSpec,
@@ -2038,20 +2164,7 @@ async fn execute_task(
     
     let start_time = std::time::Instant::now();
     
-    // Execute based on task type
-    let result = match task_spec.task_type.as_str() {
-        "shell" | "sh" | "bash" => {
-            execute_shell_task(task_id, &task_spec.command, &task_spec.env, args).await
-        }
-        "skill" => {
-            execute_skill_task(task_id, &task_spec.command, &task_spec.env).await
-        }
-        _ => {
-            // Default to shell execution for unknown types
-            println!("   Unknown task type '{}', defaulting to shell", task_spec.task_type);
-            execute_shell_task(task_id, &task_spec.command, &task_spec.env, args).await
-        }
-    };
+    let result = execute_task_with_retries(dag_id, task_id, task_spec, args, task_outputs).await;
     
     let execution_time_ms = start_time.elapsed().as_millis() as u64;
     
@@ -2061,16 +2174,130 @@ async fn execute_task(
     Ok(())
 }
 
+/// Execute a task, re-running failures and timeouts per `per_task_retry`
+///
+/// Only the task-level override applies here: the DAG-level default retry
+/// config governs dispatch in the executor and is not sent to the worker.
+async fn execute_task_with_retries(
+    dag_id: &str,
+    task_id: &str,
+    task_spec: &cis_core::scheduler::DagTaskSpec,
+    args: &WorkerArgs,
+    task_outputs: &TaskOutputStore,
+) -> TaskResult {
+    let retry = task_spec.per_task_retry.as_ref();
+    let max_retries = retry.map_or(0, |retry| retry.max_retries);
+    let mut attempt = 0;
+    
+    loop {
+        // Execute based on task type
+        let result = match task_spec.task_type.as_str() {
+            "shell" | "sh" | "bash" => {
+                execute_shell_task(dag_id, task_id, task_spec, args, task_outputs).await
+            }
+            "skill" => {
+                execute_skill_task(task_id, &task_spec.command, &task_spec.env).await
+            }
+            _ => {
+                // Default to shell execution for unknown types
+                println!("   Unknown task type '{}', defaulting to shell", task_spec.task_type);
+                execute_shell_task(dag_id, task_id, task_spec, args, task_outputs).await
+            }
+        };
+        
+        let retryable = matches!(result.status, TaskStatus::Failed | TaskStatus::Timeout);
+        if !retryable || attempt >= max_retries {
+            return result;
+        }
+        
+        let delay = retry.map_or(0, |retry| retry.delay_for_attempt(attempt));
+        warn!(
+            "Task {} {:?} (attempt {}/{}), retrying in {}s",
+            task_id,
+            result.status,
+            attempt + 1,
+            max_retries + 1,
+            delay
+        );
+        tokio::time::sleep(std::time::Duration::from_secs(delay)).await;
+        attempt += 1;
+    }
+}
+
+/// Default shell task timeout when the spec sets no `timeout_secs`
+const DEFAULT_TASK_TIMEOUT_SECS: u64 = 300;
+
+/// Task output database read by `cis dag logs`
+const TASK_OUTPUT_DB: &str = "task_outputs.db";
+
+/// Captured output is flushed to the store in chunks of this size
+const OUTPUT_CHUNK_BYTES: usize = 8 * 1024;
+
+/// Exit code recorded for a task killed on timeout (same as coreutils `timeout`)
+const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// Store for task stdout/stderr, shared by the output readers of a task
+type TaskOutputStore = std::sync::Mutex<cis_core::scheduler::DagPersistence>;
+
+/// Open the worker task output store under `<data_dir>/worker`
+fn open_task_output_store() -> Result<TaskOutputStore> {
+    let dir = cis_core::storage::Paths::data_dir().join("worker");
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+    let db_path = dir.join(TASK_OUTPUT_DB);
+    let persistence = cis_core::scheduler::DagPersistence::new(&db_path.to_string_lossy())
+        .with_context(|| format!("Failed to open task output store {}", db_path.display()))?;
+    Ok(std::sync::Mutex::new(persistence))
+}
+
+/// Run a task output store operation, logging instead of failing the task
+fn with_task_outputs(
+    task_outputs: &TaskOutputStore,
+    f: impl FnOnce(&cis_core::scheduler::DagPersistence) -> cis_core::error::Result<()>,
+) {
+    let store = match task_outputs.lock() {
+        Ok(store) => store,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if let Err(e) = f(&store) {
+        warn!("Failed to record task output: {}", e);
+    }
+}
+
 /// Execute shell command task
+///
+/// stdout/stderr are appended to the task output store in chunks while the
+/// command runs, so `cis dag logs --follow` can stream them.
 async fn execute_shell_task(
+    run_id: &str,
     task_id: &str,
-    command: &str,
-    env: &std::collections::HashMap<String, String>,
+    task_spec: &cis_core::scheduler::DagTaskSpec,
     args: &WorkerArgs,
+    task_outputs: &TaskOutputStore,
 ) -> TaskResult {
+    use cis_core::sandbox::SandboxValidator;
+    use cis_core::scheduler::OutputStream;
+    use dag_executor::error::DagExecutorError;
     use tokio::process::Command;
     use tokio::time::{timeout, Duration};
     
+    let command = task_spec.command.as_str();
+    
+    // Sandbox checks before anything is spawned
+    let work_dir = args.scope_id.as_deref().unwrap_or(".");
+    if let Err(e) = SandboxValidator::validate_path(std::path::Path::new(work_dir), &args.allowed_roots)
+        .and_then(|()| SandboxValidator::validate_shell_command_with(command, &args.shell_rules))
+    {
+        warn!("Task {} rejected by sandbox: {}", task_id, e);
+        return TaskResult {
+            task_id: task_id.to_string(),
+            status: TaskStatus::Failed,
+            output: format!("Rejected by sandbox: {}", e),
+            exit_code: None,
+            execution_time_ms: 0,
+        };
+    }
+    
     // Parse command (handle shell operators)
     let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
     
@@ -2122,8 +2349,10 @@ async fn execute_shell_task(
     let mut cmd = Command::new(&shell);
     cmd.arg("-c").arg(&wrapped_command);
     
-    // Set environment variables
-    cmd.envs(env);
+    // Set environment variables: upstream outputs first, so the spec's own
+    // variables take precedence
+    cmd.envs(upstream_output_env(run_id, task_spec, task_outputs));
+    cmd.envs(&task_spec.env);
     
     // Add resource limit markers to environment (for child processes)
     if args.max_cpu > 0 {
@@ -2138,23 +2367,72 @@ async fn execute_shell_task(
         cmd.current_dir(work_dir);
     }
     
-    // Execute with timeout (5 minutes default)
-    let timeout_duration = Duration::from_secs(300);
-    let execution_result = timeout(timeout_duration, cmd.output()).await;
+    // Run in its own process group so a timeout can kill everything it spawned
+    #[cfg(unix)]
+    cmd.process_group(0);
+    cmd.stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true);
     
-    match execution_result {
-        Ok(Ok(output)) => {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let exit_code = output.status.code();
+    // Execute with timeout (task-level override, 5 minutes default)
+    let timeout_duration = Duration::from_secs(task_spec.timeout_secs.unwrap_or(DEFAULT_TASK_TIMEOUT_SECS));
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            // Failed to execute command
+            return TaskResult {
+                task_id: task_id.to_string(),
+                status: TaskStatus::Failed,
+                output: format!("Failed to execute command: {}", e),
+                exit_code: None,
+                execution_time_ms: 0,
+            };
+        }
+    };
+    
+    let started = std::time::Instant::now();
+    with_task_outputs(task_outputs, |store| store.begin_task_output(run_id, task_id));
+    
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let execution = async {
+        tokio::join!(
+            capture_stream(stdout, OutputStream::Stdout, run_id, task_id, task_outputs),
+            capture_stream(stderr, OutputStream::Stderr, run_id, task_id, task_outputs),
+            child.wait(),
+        )
+    };
+    
+    let outcome = timeout(timeout_duration, execution).await;
+    if outcome.is_err() {
+        // Timeout: kill the task instead of leaving it running in the background
+        kill_task_process(&mut child).await;
+        warn!("Task {} timed out after {}s and was killed", task_id, timeout_duration.as_secs());
+    }
+    
+    // Killed by a signal or never reaped: no exit code, recorded as -1
+    let recorded_exit_code = match &outcome {
+        Ok((_, _, Ok(exit_status))) => exit_status.code().unwrap_or(-1),
+        Ok((_, _, Err(_))) => -1,
+        Err(_) => TIMEOUT_EXIT_CODE,
+    };
+    let duration_ms = started.elapsed().as_millis() as u64;
+    with_task_outputs(task_outputs, |store| {
+        store.finish_task_output(run_id, task_id, recorded_exit_code, duration_ms)
+    });
+    
+    match outcome {
+        Ok((stdout, stderr, Ok(exit_status))) => {
+            let exit_code = exit_status.code();
             
-            let (status, output_str) = if output.status.success() {
-                (TaskStatus::Success, stdout.to_string())
+            let (status, output_str) = if exit_status.success() {
+                (TaskStatus::Success, stdout)
             } else {
                 let error_output = if stderr.is_empty() {
-                    stdout.to_string()
+                    stdout
                 } else {
-                    stderr.to_string()
+                    stderr
                 };
                 (TaskStatus::Failed, error_output)
             };
@@ -2167,26 +2445,129 @@ async fn execute_shell_task(
                 execution_time_ms: 0, // Will be set by caller
             }
         }
-        Ok(Err(e)) => {
-            // Failed to execute command
+        Ok((_, _, Err(e))) => {
             TaskResult {
                 task_id: task_id.to_string(),
                 status: TaskStatus::Failed,
-                output: format!("Failed to execute command: {}", e),
+                output: format!("Failed to wait for command: {}", e),
                 exit_code: None,
                 execution_time_ms: 0,
             }
         }
         Err(_) => {
-            // Timeout
             TaskResult {
                 task_id: task_id.to_string(),
                 status: TaskStatus::Timeout,
-                output: "Task execution timed out (300s)".to_string(),
+                output: DagExecutorError::TaskTimeout(format!(
+                    "task {} exceeded {}s",
+                    task_id,
+                    timeout_duration.as_secs()
+                ))
+                .to_string(),
                 exit_code: None,
-                execution_time_ms: 300_000,
+                execution_time_ms: timeout_duration.as_millis() as u64,
+            }
+        }
+    }
+}
+
+/// Stdout of each successfully finished dependency as `TASK_<DEP_ID>_OUTPUT`
+///
+/// Trailing newlines are trimmed, as with shell command substitution.
+/// Dependencies without a recorded output are skipped.
+fn upstream_output_env(
+    run_id: &str,
+    task_spec: &cis_core::scheduler::DagTaskSpec,
+    task_outputs: &TaskOutputStore,
+) -> std::collections::HashMap<String, String> {
+    use cis_core::scheduler::skill_executor::output_env_var;
+    
+    let store = match task_outputs.lock() {
+        Ok(store) => store,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    
+    task_spec
+        .depends_on
+        .iter()
+        .filter_map(|dep_id| match store.load_task_output(run_id, dep_id) {
+            Ok(Some(output)) if output.is_success() => Some((
+                output_env_var(dep_id),
+                output.stdout.trim_end_matches('\n').to_string(),
+            )),
+            Ok(_) => None,
+            Err(e) => {
+                warn!("Failed to load output of task '{}': {}", dep_id, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Read a child output pipe to the end, appending it to the store in chunks
+///
+/// Returns the full captured text for the task result.
+async fn capture_stream<R>(
+    reader: Option<R>,
+    stream: cis_core::scheduler::OutputStream,
+    run_id: &str,
+    task_id: &str,
+    task_outputs: &TaskOutputStore,
+) -> String
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    use tokio::io::AsyncBufReadExt;
+    
+    let Some(reader) = reader else {
+        return String::new();
+    };
+    
+    let mut reader = tokio::io::BufReader::new(reader);
+    let mut captured = String::new();
+    let mut chunk = String::new();
+    let mut line = Vec::new();
+    
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line).await {
+            Ok(0) => break,
+            Ok(_) => chunk.push_str(&String::from_utf8_lossy(&line)),
+            Err(e) => {
+                debug!("Failed to read task output: {}", e);
+                break;
             }
         }
+        if chunk.len() >= OUTPUT_CHUNK_BYTES {
+            with_task_outputs(task_outputs, |store| {
+                store.append_task_output(run_id, task_id, stream, &chunk)
+            });
+            captured.push_str(&chunk);
+            chunk.clear();
+        }
+    }
+    
+    if !chunk.is_empty() {
+        with_task_outputs(task_outputs, |store| {
+            store.append_task_output(run_id, task_id, stream, &chunk)
+        });
+        captured.push_str(&chunk);
+    }
+    
+    captured
+}
+
+/// Kill a timed-out task together with the processes it spawned
+async fn kill_task_process(child: &mut tokio::process::Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        // The task leads its own process group (see `process_group(0)`)
+        unsafe {
+            libc::kill(-(pid as i32), libc::SIGKILL);
+        }
+    }
+    if let Err(e) = child.kill().await {
+        debug!("Failed to kill task process: {}", e);
     }
 }
 
@@ -2585,4 +2966,206 @@ mod tests {
         assert_eq!(WorkerScope::User.to_string(), "user");
         assert_eq!(WorkerScope::Type.to_string(), "type");
     }
+
+    fn test_args() -> WorkerArgs {
+        WorkerArgs {
+            worker_id: "test-worker".to_string(),
+            room: "!test:node1".to_string(),
+            scope: WorkerScope::Global,
+            parent_node: "node1".to_string(),
+            scope_id: None,
+            health_interval: 30,
+            verbose: false,
+            max_cpu: 0,
+            max_memory_mb: 0,
+            matrix_server: String::new(),
+            matrix_token: String::new(),
+            allowed_roots: default_allowed_roots(),
+            shell_rules: cis_core::sandbox::ShellRules::default(),
+        }
+    }
+
+    fn shell_spec(id: &str, command: &str) -> cis_core::scheduler::DagTaskSpec {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "type": "shell",
+            "command": command,
+        }))
+        .unwrap()
+    }
+
+    fn memory_outputs() -> TaskOutputStore {
+        std::sync::Mutex::new(cis_core::scheduler::DagPersistence::new(":memory:").unwrap())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_shell_task_output_is_persisted() {
+        let spec = shell_spec("t1", "sh -c 'echo out; echo err >&2; exit 3'");
+        let outputs = memory_outputs();
+
+        let result = execute_shell_task("run-1", "t1", &spec, &test_args(), &outputs).await;
+        assert_eq!(result.status, TaskStatus::Failed);
+        assert_eq!(result.exit_code, Some(3));
+        assert_eq!(result.output, "err");
+
+        let stored = outputs.lock().unwrap().load_task_output("run-1", "t1").unwrap().unwrap();
+        assert_eq!(stored.stdout, "out\n");
+        assert_eq!(stored.stderr, "err\n");
+        assert_eq!(stored.exit_code, 3);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_upstream_output_injected_as_env() {
+        let outputs = memory_outputs();
+
+        let step1 = shell_spec("step1", "echo hello");
+        let result = execute_shell_task("run-1", "step1", &step1, &test_args(), &outputs).await;
+        assert_eq!(result.status, TaskStatus::Success);
+
+        // Plain variable expansion passes the default sandbox rules
+        let mut step2 = shell_spec("step2", "echo \"$TASK_STEP1_OUTPUT\"");
+        step2.depends_on = vec!["step1".to_string()];
+        let result = execute_shell_task("run-1", "step2", &step2, &test_args(), &outputs).await;
+        assert_eq!(result.status, TaskStatus::Success);
+        assert_eq!(result.output, "hello");
+
+        // Variables set in the spec win over injected ones
+        let mut step3 = step2.clone();
+        step3.id = "step3".to_string();
+        step3.env.insert("TASK_STEP1_OUTPUT".to_string(), "override".to_string());
+        let result = execute_shell_task("run-1", "step3", &step3, &test_args(), &outputs).await;
+        assert_eq!(result.status, TaskStatus::Success);
+        assert_eq!(result.output, "override");
+    }
+
+    #[tokio::test]
+    async fn test_shell_task_rejected_by_sandbox() {
+        let outputs = memory_outputs();
+
+        // Unquoted metacharacters chain a second command
+        let spec = shell_spec("t1", "echo ok; rm -rf /");
+        let result = execute_shell_task("run-1", "t1", &spec, &test_args(), &outputs).await;
+        assert_eq!(result.status, TaskStatus::Failed);
+        assert!(result.output.starts_with("Rejected by sandbox"));
+
+        // Working directory outside the allowed roots
+        let allowed = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let args = WorkerArgs {
+            scope_id: Some(outside.path().to_string_lossy().to_string()),
+            allowed_roots: vec![allowed.path().to_path_buf()],
+            ..test_args()
+        };
+        let spec = shell_spec("t2", "echo ok");
+        let result = execute_shell_task("run-1", "t2", &spec, &args, &outputs).await;
+        assert_eq!(result.status, TaskStatus::Failed);
+        assert!(result.output.starts_with("Rejected by sandbox"));
+
+        // Nothing was spawned, so nothing was recorded
+        let store = outputs.lock().unwrap();
+        assert!(store.load_task_output("run-1", "t1").unwrap().is_none());
+        assert!(store.load_task_output("run-1", "t2").unwrap().is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_shell_task_allowed_operators() {
+        let outputs = memory_outputs();
+        let spec = shell_spec("t1", "echo a && echo b");
+
+        let result = execute_shell_task("run-1", "t1", &spec, &test_args(), &outputs).await;
+        assert_eq!(result.status, TaskStatus::Failed);
+
+        let args = WorkerArgs {
+            shell_rules: shell_rules(&["&&".to_string()]).unwrap(),
+            ..test_args()
+        };
+        let result = execute_shell_task("run-2", "t1", &spec, &args, &outputs).await;
+        assert_eq!(result.status, TaskStatus::Success);
+        assert_eq!(result.output, "a\nb");
+
+        assert!(shell_rules(&["rm".to_string()]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_shell_task_timeout_kills_process() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("finished");
+        let command = format!("sh -c 'sleep 2; touch {}'", marker.display());
+
+        let mut spec = shell_spec("slow", &command);
+        spec.timeout_secs = Some(1);
+        let outputs = memory_outputs();
+
+        let result = execute_shell_task("run-1", "slow", &spec, &test_args(), &outputs).await;
+        assert_eq!(result.status, TaskStatus::Timeout);
+        assert_eq!(result.exit_code, None);
+        assert_eq!(result.output, "Task timed out: task slow exceeded 1s");
+
+        let stored = outputs.lock().unwrap().load_task_output("run-1", "slow").unwrap().unwrap();
+        assert_eq!(stored.exit_code, TIMEOUT_EXIT_CODE);
+
+        // The killed task must not finish in the background
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        assert!(!marker.exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_task_retried_per_task_retry() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("attempted");
+        let command = format!(
+            "sh -c 'test -f {0} && echo ok || {{ touch {0}; exit 1; }}'",
+            marker.display()
+        );
+        let outputs = memory_outputs();
+
+        // Without a task-level override the failure is final
+        let spec = shell_spec("flaky", &command);
+        let result = execute_task_with_retries("run-1", "flaky", &spec, &test_args(), &outputs).await;
+        assert_eq!(result.status, TaskStatus::Failed);
+
+        std::fs::remove_file(&marker).unwrap();
+        let mut spec = shell_spec("flaky", &command);
+        spec.per_task_retry = Some(cis_core::scheduler::RetryConfig {
+            max_retries: 1,
+            retry_delay_secs: 0,
+            exponential_backoff: false,
+        });
+        let result = execute_task_with_retries("run-2", "flaky", &spec, &test_args(), &outputs).await;
+        assert_eq!(result.status, TaskStatus::Success);
+        assert_eq!(result.output, "ok");
+    }
+
+    #[test]
+    fn test_parse_run_control_events() {
+        let pause = parse_task_event(r#"{"type":"dag.pause","run_id":"run-1"}"#, &serde_json::Value::Null);
+        assert!(matches!(pause, Some(TaskEvent::PauseRun { run_id }) if run_id == "run-1"));
+
+        let resume = parse_task_event(r#"{"type":"dag.resume","run_id":"run-1"}"#, &serde_json::Value::Null);
+        assert!(matches!(resume, Some(TaskEvent::ResumeRun { run_id }) if run_id == "run-1"));
+
+        assert!(parse_task_event(r#"{"type":"dag.pause"}"#, &serde_json::Value::Null).is_none());
+    }
+
+    #[test]
+    fn test_run_control_holds_tasks_while_paused() {
+        let mut control = RunControl::default();
+        assert!(control.pause("run-1"));
+        assert!(!control.pause("run-1"));
+        assert!(control.is_paused("run-1"));
+        assert!(!control.is_paused("run-2"));
+
+        control.hold("run-1", "t1".to_string(), shell_spec("t1", "true"));
+        control.hold("run-1", "t2".to_string(), shell_spec("t2", "true"));
+
+        let held: Vec<String> = control.resume("run-1").into_iter().map(|(id, _)| id).collect();
+        assert_eq!(held, vec!["t1", "t2"]);
+        assert!(!control.is_paused("run-1"));
+        assert!(control.resume("run-1").is_empty());
+    }
 }
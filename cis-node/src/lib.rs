@@ -85,6 +85,10 @@ pub enum TelemetryAction {
         /// Session ID (defaults to global stats)
         #[arg(short, long)]
         session: Option<String>,
+        
+        /// Output per-skill statistics as JSON
+        #[arg(long)]
+        json: bool,
     },
     
     /// List all sessions
@@ -47,9 +47,12 @@ enum Commands {
         /// Skip environment checks
         #[arg(long)]
         skip_checks: bool,
-        /// Preferred AI provider (claude|kimi|aider)
+        /// Preferred AI provider (claude|kimi|aider|gemini)
         #[arg(long)]
         provider: Option<String>,
+        /// Build the global config from CIS_* environment variables
+        #[arg(long, conflicts_with = "project")]
+        from_env: bool,
     },
     
     /// Manage skills
@@ -101,6 +104,13 @@ enum Commands {
         /// Project path
         #[arg(short, long)]
         project: Option<std::path::PathBuf>,
+        /// Override the system prompt for this invocation
+        #[arg(long, global = true, conflicts_with = "system_prompt_file")]
+        system_prompt: Option<String>,
+        /// Load the system prompt from a file or saved prompt
+        /// (supports {{date}}, {{cwd}}, {{git_branch}})
+        #[arg(long, global = true)]
+        system_prompt_file: Option<std::path::PathBuf>,
     },
     
     /// Check environment
@@ -166,6 +176,12 @@ enum Commands {
         action: commands::debt::DebtCommands,
     },
 
+    /// Push client retry queue
+    Push {
+        #[command(subcommand)]
+        action: commands::push::PushCommands,
+    },
+
     /// Four-tier decision management
     Decision {
         #[command(subcommand)]
@@ -315,7 +331,7 @@ enum AgentSubcommand {
         prompt: Vec<String>,
     },
     
-    /// Interactive chat mode
+    /// Interactive chat mode (readline history, !clear/!save/!load/!provider and !<cis command>)
     Chat,
     
     /// List available agents
@@ -332,13 +348,51 @@ enum AgentSubcommand {
         #[arg(short, long)]
         project: Option<std::path::PathBuf>,
     },
+
+    /// Show AI usage and cost by provider and session
+    Cost {
+        /// Only show this session
+        #[arg(short, long)]
+        session: Option<String>,
+    },
+    
+    /// Manage saved system prompts
+    Prompts {
+        #[command(subcommand)]
+        action: AgentPromptsAction,
+    },
+}
+
+/// Saved system prompt subcommands
+#[derive(Subcommand, Debug)]
+enum AgentPromptsAction {
+    /// List saved prompts
+    List,
+    
+    /// Save a prompt file under a name
+    Save {
+        /// Prompt name
+        name: String,
+        /// Prompt file
+        file: std::path::PathBuf,
+    },
+    
+    /// Delete a saved prompt
+    Delete {
+        /// Prompt name
+        name: String,
+    },
 }
 
 /// Skill subcommands
 #[derive(Subcommand, Debug)]
 enum SkillAction {
     /// List all skills
-    List,
+    List {
+        /// Only show skills declared in the current project's .cis/skills.toml
+        #[arg(long)]
+        project: bool,
+    },
     
     /// Load a skill
     Load {
@@ -446,6 +500,9 @@ enum MemoryAction {
         /// Memory category
         #[arg(long, value_enum, default_value = "context")]
         category: MemoryCategory,
+        /// Tag the entry (repeatable, replaces existing tags)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
     },
     
     /// Delete a memory entry
@@ -457,11 +514,18 @@ enum MemoryAction {
     /// Search memory entries (keyword-based)
     Search {
         /// Search query
-        query: String,
+        #[arg(required_unless_present = "tag")]
+        query: Option<String>,
+        /// Only entries with this tag
+        #[arg(long)]
+        tag: Option<String>,
         /// Maximum results
         #[arg(long)]
         limit: Option<usize>,
     },
+
+    /// List all memory tags with their entry counts
+    Tags,
     
     /// Semantic search memory entries (vector-based)
     VectorSearch {
@@ -515,6 +579,19 @@ enum MemoryAction {
         force: bool,
     },
 
+    /// Re-embed stored vectors with a new embedding model (resumable)
+    Reindex {
+        /// Embedding model: local, openai, auto, or a local model path
+        #[arg(long)]
+        model: String,
+        /// Number of vectors per batch
+        #[arg(long, default_value = "100")]
+        batch_size: usize,
+        /// Discard an interrupted reindex and start over
+        #[arg(long)]
+        restart: bool,
+    },
+
     /// Show memory statistics
     Stats {
         /// Filter by domain (public, private)
@@ -522,6 +599,26 @@ enum MemoryAction {
         domain: Option<String>,
     },
 
+    /// Synchronize public memory with a peer
+    Sync {
+        /// Peer node ID
+        #[arg(long)]
+        peer: String,
+    },
+
+    /// Garbage-collect memory
+    Gc {
+        /// Delete project namespaces whose project no longer exists
+        #[arg(long)]
+        orphans: bool,
+        /// Only list what would be deleted
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
     /// 🔥 Manage memory conflicts (P1.7.0)
     Conflicts {
         #[command(subcommand)]
@@ -596,6 +693,16 @@ enum ImSubcommand {
     Read(commands::im::ReadArgs),
     /// Get session info
     Info(commands::im::InfoArgs),
+    /// Summarize recent messages of a conversation
+    Summarize(commands::im::SummarizeArgs),
+    /// Show usage statistics of a conversation
+    Stats(commands::im::StatsArgs),
+    /// Manage conversation labels
+    Label(commands::im::LabelArgs),
+    /// Rebuild the message full-text index
+    IndexRebuild(commands::im::IndexArgs),
+    /// Check the message full-text index for inconsistencies
+    IndexCheck(commands::im::IndexArgs),
 }
 
 /// Task subcommands
@@ -606,8 +713,14 @@ enum TaskAction {
         /// Filter by status
         #[arg(long, value_enum)]
         status: Option<TaskStatus>,
+        /// Filter by task level
+        #[arg(long, value_enum)]
+        level: Option<commands::task::TaskLevelKind>,
     },
     
+    /// Show tasks grouped by dependencies, color-coded by level
+    Tree,
+    
     /// Show task details
     Show {
         /// Task ID
@@ -650,6 +763,18 @@ enum TaskAction {
     
     /// Execute tasks using DAG scheduler
     Execute,
+
+    /// Import tasks from a JSON, CSV or GitHub issues file
+    Import {
+        /// File to import
+        path: std::path::PathBuf,
+        /// Input format
+        #[arg(long, short, value_enum, default_value = "json")]
+        format: TaskImportFormat,
+        /// Preview the tasks without creating them
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 /// Output format enum for search results
@@ -752,6 +877,25 @@ impl From<TaskPriority> for cis_core::types::TaskPriority {
     }
 }
 
+/// Task import format enum
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum TaskImportFormat {
+    Json,
+    Csv,
+    /// `gh issue list --json title,body,labels,milestone` output
+    GithubIssues,
+}
+
+impl From<TaskImportFormat> for commands::task::ImportFormat {
+    fn from(format: TaskImportFormat) -> Self {
+        match format {
+            TaskImportFormat::Json => commands::task::ImportFormat::Json,
+            TaskImportFormat::Csv => commands::task::ImportFormat::Csv,
+            TaskImportFormat::GithubIssues => commands::task::ImportFormat::GithubIssues,
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() {
     // Initialize tracing
@@ -808,6 +952,10 @@ async fn check_first_run(command: &Commands) -> anyhow::Result<()> {
         )
     };
     
+    if needs_init && Paths::config_file().exists() {
+        load_node_key()?;
+    }
+    
     if needs_init && !Paths::config_file().exists() {
         // Release 模式下自动初始化
         if Paths::run_mode() == RunMode::Release {
@@ -864,6 +1012,21 @@ async fn check_first_run(command: &Commands) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// 节点已认领时解封节点密钥，并清除配置文件中残留的明文 `[node] key`
+///
+/// 解封失败（换了机器或引导链变化）时拒绝启动。
+fn load_node_key() -> anyhow::Result<()> {
+    use cis_core::identity::NodeClaimService;
+    
+    let service = NodeClaimService::open_default();
+    if service.attestation_path().exists() {
+        service
+            .load_node_key()
+            .map_err(|e| anyhow::anyhow!("Failed to unseal the hardware-bound node key: {}", e))?;
+    }
+    Ok(())
+}
+
 /// 创建默认配置（用于 Release 模式自动初始化）
 async fn create_default_config() -> anyhow::Result<String> {
     use cis_core::wizard::ConfigGenerator;
@@ -886,16 +1049,10 @@ async fn create_default_config() -> anyhow::Result<String> {
         std::fs::set_permissions(&key_path, permissions)?;
     }
     
+    // 节点密钥只保存在 node.key（认领后封存到硬件），不写入配置文件
     let generator = ConfigGenerator::new();
     let mut config = generator.generate_global_config(None)?;
     
-    // 添加节点密钥到配置
-    let key_hex = hex::encode(&node_key);
-    config.push_str(&format!(r#"
-[node]
-key = "{}"
-"#, key_hex));
-    
     // 添加 P2P 默认配置
     config.push_str(r#"
 
@@ -933,11 +1090,16 @@ async fn run_command(command: Commands, json_output: bool) -> anyhow::Result<()>
                 ImSubcommand::Create(args) => commands::im::ImAction::Create(args),
                 ImSubcommand::Read(args) => commands::im::ImAction::Read(args),
                 ImSubcommand::Info(args) => commands::im::ImAction::Info(args),
+                ImSubcommand::Summarize(args) => commands::im::ImAction::Summarize(args),
+                ImSubcommand::Stats(args) => commands::im::ImAction::Stats(args),
+                ImSubcommand::Label(args) => commands::im::ImAction::Label(args),
+                ImSubcommand::IndexRebuild(args) => commands::im::ImAction::IndexRebuild(args),
+                ImSubcommand::IndexCheck(args) => commands::im::ImAction::IndexCheck(args),
             }};
             commands::im::handle_im(args).await
         }
         
-        Commands::Init { project, force, non_interactive, skip_checks, provider } => {
+        Commands::Init { project, force, non_interactive, skip_checks, provider, from_env } => {
             let options = commands::init::InitOptions {
                 project_mode: project,
                 project_dir: None,
@@ -945,13 +1107,14 @@ async fn run_command(command: Commands, json_output: bool) -> anyhow::Result<()>
                 force,
                 preferred_provider: provider,
                 non_interactive,
+                from_env,
             };
             
             commands::init::init_with_options(options).await
         }
         
         Commands::Skill { action } => match action {
-            SkillAction::List => commands::skill::list_skills(),
+            SkillAction::List { project } => commands::skill::list_skills(project),
             SkillAction::Load { name, activate } => commands::skill::load_skill(&name, activate).await,
             SkillAction::Unload { name } => commands::skill::unload_skill(&name).await,
             SkillAction::Activate { name } => commands::skill::activate_skill(&name).await,
@@ -983,18 +1146,20 @@ async fn run_command(command: Commands, json_output: bool) -> anyhow::Result<()>
         
         Commands::Memory { action } => match action {
             MemoryAction::Get { key } => commands::memory::get_memory(&key),
-            MemoryAction::Set { key, value, domain, category } => {
+            MemoryAction::Set { key, value, domain, category, tags } => {
                 commands::memory::set_memory(
                     &key,
                     &value,
                     domain.into(),
                     category.into(),
+                    &tags,
                 )
             }
             MemoryAction::Delete { key } => commands::memory::delete_memory(&key),
-            MemoryAction::Search { query, limit } => {
-                commands::memory::search_memory(&query, limit).await
+            MemoryAction::Search { query, tag, limit } => {
+                commands::memory::search_memory(query.as_deref(), tag.as_deref(), limit).await
             }
+            MemoryAction::Tags => commands::memory::list_tags().await,
             MemoryAction::VectorSearch { query, limit, threshold, category, format } => {
                 let args = commands::memory::MemorySearchArgs {
                     query,
@@ -1023,6 +1188,13 @@ async fn run_command(command: Commands, json_output: bool) -> anyhow::Result<()>
             MemoryAction::Stats { domain } => {
                 commands::memory::handle_memory_action(commands::memory::MemoryAction::Stats { domain }).await
             }
+            MemoryAction::Reindex { model, batch_size, restart } => {
+                commands::memory::handle_memory_action(commands::memory::MemoryAction::Reindex { model, batch_size, restart }).await
+            }
+            MemoryAction::Sync { peer } => commands::memory::sync_memory(&peer).await,
+            MemoryAction::Gc { orphans, dry_run, yes } => {
+                commands::memory::gc_memory(orphans, dry_run, yes).await
+            }
             MemoryAction::Conflicts { action } => {
                 commands::memory_conflicts::handle_conflicts(action).await
             }
@@ -1037,9 +1209,10 @@ async fn run_command(command: Commands, json_output: bool) -> anyhow::Result<()>
         }
 
         Commands::Task { action } => match action {
-            TaskAction::List { status } => {
-                commands::task::list_tasks(status.map(Into::into))
+            TaskAction::List { status, level } => {
+                commands::task::list_tasks(status.map(Into::into), level, json_output)
             }
+            TaskAction::Tree => commands::task::task_tree(),
             TaskAction::Show { id } => commands::task::task_details(&id),
             TaskAction::Create { title, description, group, priority, criteria } => {
                 commands::task::create_task(
@@ -1055,9 +1228,17 @@ async fn run_command(command: Commands, json_output: bool) -> anyhow::Result<()>
             }
             TaskAction::Delete { id } => commands::task::delete_task(&id),
             TaskAction::Execute => commands::task::execute_tasks().await,
+            TaskAction::Import { path, format, dry_run } => {
+                commands::task::import_tasks(&path, format.into(), dry_run)
+                    .map(|report| commands::task::print_import_report(&report, dry_run))
+            }
         }
         
-        Commands::Agent { action, prompt, chat, list, session, project } => {
+        Commands::Agent { action, prompt, chat, list, session, project, system_prompt, system_prompt_file } => {
+            let system_prompt = commands::agent::SystemPromptArgs {
+                text: system_prompt,
+                file: system_prompt_file,
+            };
             // 如果指定了子命令，使用子命令
             if let Some(action) = action {
                 match action {
@@ -1066,10 +1247,10 @@ async fn run_command(command: Commands, json_output: bool) -> anyhow::Result<()>
                         if prompt.is_empty() {
                             return Err(anyhow::anyhow!("Prompt is required"));
                         }
-                        commands::agent::execute_prompt(&prompt).await
+                        commands::agent::execute_prompt(&prompt, &system_prompt).await
                     }
                     AgentSubcommand::Chat => {
-                        commands::agent::interactive_chat().await
+                        commands::agent::interactive_chat(&system_prompt).await
                     }
                     AgentSubcommand::List => {
                         commands::agent::list_agents().await
@@ -1079,16 +1260,29 @@ async fn run_command(command: Commands, json_output: bool) -> anyhow::Result<()>
                             prompt,
                             session,
                             project,
+                            system_prompt,
                         };
                         commands::agent::handle_agent_context(args).await
                     }
+                    AgentSubcommand::Cost { session } => {
+                        commands::agent::show_cost(session.as_deref()).await
+                    }
+                    AgentSubcommand::Prompts { action } => match action {
+                        AgentPromptsAction::List => commands::agent::list_prompts(),
+                        AgentPromptsAction::Save { name, file } => {
+                            commands::agent::save_prompt(&name, &file)
+                        }
+                        AgentPromptsAction::Delete { name } => {
+                            commands::agent::delete_prompt(&name)
+                        }
+                    },
                 }
             } else {
                 // 向后兼容：使用 flags
                 if list {
                     commands::agent::list_agents().await
                 } else if chat {
-                    commands::agent::interactive_chat().await
+                    commands::agent::interactive_chat(&system_prompt).await
                 } else if session.is_some() || project.is_some() {
                     let prompt = if prompt.is_empty() {
                         return Err(anyhow::anyhow!("Prompt is required. Use --chat for interactive mode or --list to see available agents."));
@@ -1099,6 +1293,7 @@ async fn run_command(command: Commands, json_output: bool) -> anyhow::Result<()>
                         prompt,
                         session,
                         project,
+                        system_prompt,
                     };
                     commands::agent::handle_agent_context(args).await
                 } else {
@@ -1107,7 +1302,7 @@ async fn run_command(command: Commands, json_output: bool) -> anyhow::Result<()>
                     } else {
                         prompt.join(" ")
                     };
-                    commands::agent::execute_prompt(&prompt).await
+                    commands::agent::execute_prompt(&prompt, &system_prompt).await
                 }
             }
         }
@@ -1116,7 +1311,7 @@ async fn run_command(command: Commands, json_output: bool) -> anyhow::Result<()>
             if fix {
                 commands::doctor::quick_fix()
             } else {
-                commands::doctor::doctor()
+                commands::doctor::doctor().await
             }
         }
         
@@ -1171,6 +1366,10 @@ async fn run_command(command: Commands, json_output: bool) -> anyhow::Result<()>
             commands::debt::handle(action).await
         }
 
+        Commands::Push { action } => {
+            commands::push::handle(action, json_output)
+        }
+
         Commands::Decision { action } => {
             commands::decision::handle(action).await
         }
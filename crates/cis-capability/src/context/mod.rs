@@ -1,14 +1,25 @@
 //! Project context extraction service
 
-use crate::types::{GitStatus, ProjectContext, Result};
+use crate::types::{DockerContext, GitBlameContext, GitStatus, ProjectContext, Result};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
-pub struct ContextExtractor;
+/// How long a `git blame` result stays cached
+const BLAME_CACHE_TTL: Duration = Duration::from_secs(60);
+
+type BlameCache = HashMap<(PathBuf, u32), (Instant, Option<GitBlameContext>)>;
+
+pub struct ContextExtractor {
+    blame_cache: Mutex<BlameCache>,
+}
 
 impl ContextExtractor {
     pub fn new() -> Self {
-        Self
+        Self {
+            blame_cache: Mutex::new(HashMap::new()),
+        }
     }
 
     /// Extract project context from given path
@@ -30,6 +41,7 @@ impl ContextExtractor {
             git_status,
             detected_files,
             environment,
+            authored_by: None,
         })
     }
 
@@ -39,6 +51,64 @@ impl ContextExtractor {
         self.extract(current).await
     }
 
+    /// Attribute a line of code to its last author via `git blame`
+    ///
+    /// Returns `None` when the file is not tracked by git or the line is not
+    /// committed yet. Results are cached for 60 seconds.
+    pub async fn detect_git_blame(&self, file: &Path, line: u32) -> Result<Option<GitBlameContext>> {
+        let cache_key = (file.to_path_buf(), line);
+        if let Some((at, blame)) = self.blame_cache.lock().unwrap().get(&cache_key) {
+            if at.elapsed() < BLAME_CACHE_TTL {
+                return Ok(blame.clone());
+            }
+        }
+
+        let dir = file.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        let range = format!("{},{}", line, line);
+        let output = tokio::process::Command::new("git")
+            .args(["blame", "-L", &range, "--porcelain", "--"])
+            .arg(file.file_name().unwrap_or(file.as_os_str()))
+            .current_dir(dir)
+            .output()
+            .await?;
+
+        let blame = if output.status.success() {
+            parse_blame_porcelain(&String::from_utf8_lossy(&output.stdout))
+        } else {
+            None
+        };
+
+        let mut cache = self.blame_cache.lock().unwrap();
+        cache.retain(|_, (at, _)| at.elapsed() < BLAME_CACHE_TTL);
+        cache.insert(cache_key, (Instant::now(), blame.clone()));
+
+        Ok(blame)
+    }
+
+    /// Fill `authored_by` in a context from `git blame`
+    pub async fn annotate_author(&self, context: &mut ProjectContext, file: &Path, line: u32) -> Result<()> {
+        context.authored_by = self.detect_git_blame(file, line).await?.map(|b| b.author);
+        Ok(())
+    }
+
+    /// Detect whether the process runs inside a Docker container
+    ///
+    /// Docker is recognized by `/.dockerenv` or a `docker` entry in
+    /// `/proc/1/cgroup` (the latter is empty on cgroup v2 hosts, so either
+    /// marker is enough). Returns `None` on the host.
+    pub async fn detect_docker_context(&self) -> Result<Option<DockerContext>> {
+        detect_docker_context_in(Path::new("/")).await
+    }
+
+    /// Whether skills started from this process run inside a container
+    ///
+    /// Callers building a cis-core `ExecutionContext` map `true` to
+    /// `Environment::Container`, so that executors can skip GUI-dependent
+    /// skills.
+    pub async fn in_container(&self) -> Result<bool> {
+        Ok(self.detect_docker_context().await?.is_some())
+    }
+
     /// Find project root by looking for marker files
     async fn find_project_root(&self, start: &Path) -> Result<PathBuf> {
         let markers = [
@@ -190,6 +260,93 @@ impl ContextExtractor {
     }
 }
 
+/// Docker detection relative to `root` (the filesystem root outside tests)
+async fn detect_docker_context_in(root: &Path) -> Result<Option<DockerContext>> {
+    let dockerenv = root.join(".dockerenv").exists();
+    let cgroup = tokio::fs::read_to_string(root.join("proc/1/cgroup"))
+        .await
+        .map(|c| c.contains("docker"))
+        .unwrap_or(false);
+    if !dockerenv && !cgroup {
+        return Ok(None);
+    }
+
+    let container_id = match tokio::fs::read_to_string(root.join("etc/hostname")).await {
+        Ok(hostname) => hostname.trim().to_string(),
+        Err(_) => std::env::var("HOSTNAME").unwrap_or_default(),
+    };
+
+    let mut context = DockerContext {
+        container_id,
+        ..DockerContext::default()
+    };
+    if let Ok(metadata) = tokio::fs::read_to_string(root.join(".docker/image_metadata")).await {
+        parse_image_metadata(&metadata, &mut context);
+    }
+
+    Ok(Some(context))
+}
+
+/// Read image name and labels from `/.docker/image_metadata`
+///
+/// Accepts a plain image name, `{"image": ..., "labels": {...}}`, or the
+/// `Config` section of `docker inspect` output.
+fn parse_image_metadata(metadata: &str, context: &mut DockerContext) {
+    let metadata = metadata.trim();
+    if metadata.is_empty() {
+        return;
+    }
+
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(metadata) else {
+        context.image = Some(metadata.to_string());
+        return;
+    };
+
+    context.image = value
+        .get("image")
+        .or_else(|| value.pointer("/Config/Image"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let labels = value.get("labels").or_else(|| value.pointer("/Config/Labels"));
+    if let Some(labels) = labels.and_then(|v| v.as_object()) {
+        context.labels = labels
+            .iter()
+            .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+            .collect();
+    }
+}
+
+/// Parse the output of `git blame --porcelain` for a single line
+fn parse_blame_porcelain(output: &str) -> Option<GitBlameContext> {
+    let mut lines = output.lines();
+    let commit = lines.next()?.split_whitespace().next()?.to_string();
+
+    // Uncommitted lines are attributed to the all-zero commit
+    if commit.chars().all(|c| c == '0') {
+        return None;
+    }
+
+    let mut author = None;
+    let mut time = None;
+    let mut message = String::new();
+    for line in lines {
+        if let Some(v) = line.strip_prefix("author ") {
+            author = Some(v.to_string());
+        } else if let Some(v) = line.strip_prefix("author-time ") {
+            time = v.trim().parse::<i64>().ok();
+        } else if let Some(v) = line.strip_prefix("summary ") {
+            message = v.to_string();
+        }
+    }
+
+    Some(GitBlameContext {
+        commit,
+        author: author?,
+        date: chrono::DateTime::from_timestamp(time?, 0)?,
+        message,
+    })
+}
+
 impl Default for ContextExtractor {
     fn default() -> Self {
         Self::new()
@@ -209,4 +366,85 @@ mod tests {
         assert_eq!(ctx.project_type, Some("rust".to_string()));
         assert_eq!(ctx.package_manager, Some("cargo".to_string()));
     }
+
+    #[test]
+    fn test_parse_blame_porcelain() {
+        let output = "\
+3f2a1b0c9d8e7f6a5b4c3d2e1f0a9b8c7d6e5f4a 12 12 1
+author Alice
+author-mail <alice@example.com>
+author-time 1700000000
+author-tz +0800
+committer Alice
+summary Add retry support
+filename src/lib.rs
+\tlet retries = 3;
+";
+        let blame = parse_blame_porcelain(output).unwrap();
+        assert_eq!(blame.commit, "3f2a1b0c9d8e7f6a5b4c3d2e1f0a9b8c7d6e5f4a");
+        assert_eq!(blame.author, "Alice");
+        assert_eq!(blame.date.timestamp(), 1700000000);
+        assert_eq!(blame.message, "Add retry support");
+
+        let uncommitted = "0000000000000000000000000000000000000000 1 1 1\nauthor Not Committed Yet\n";
+        assert!(parse_blame_porcelain(uncommitted).is_none());
+        assert!(parse_blame_porcelain("").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_detect_git_blame() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(["-c", "user.name=Bob", "-c", "user.email=bob@example.com"])
+                .args(args)
+                .current_dir(temp.path())
+                .output()
+                .unwrap()
+        };
+        git(&["init", "-q"]);
+        std::fs::write(temp.path().join("main.rs"), "fn main() {}\n").unwrap();
+        git(&["add", "main.rs"]);
+        git(&["commit", "-q", "-m", "Initial commit"]);
+
+        let extractor = ContextExtractor::new();
+        let file = temp.path().join("main.rs");
+        let blame = extractor.detect_git_blame(&file, 1).await.unwrap().unwrap();
+        assert_eq!(blame.author, "Bob");
+        assert_eq!(blame.message, "Initial commit");
+
+        let mut ctx = ProjectContext::default();
+        extractor.annotate_author(&mut ctx, &file, 1).await.unwrap();
+        assert_eq!(ctx.authored_by, Some("Bob".to_string()));
+
+        // Out-of-range line
+        assert!(extractor.detect_git_blame(&file, 99).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_detect_docker_context() {
+        let root = tempfile::TempDir::new().unwrap();
+        assert!(detect_docker_context_in(root.path()).await.unwrap().is_none());
+
+        std::fs::create_dir_all(root.path().join("proc/1")).unwrap();
+        std::fs::write(root.path().join("proc/1/cgroup"), "12:cpu:/docker/3f2a1b0c9d8e\n").unwrap();
+        std::fs::create_dir_all(root.path().join("etc")).unwrap();
+        std::fs::write(root.path().join("etc/hostname"), "3f2a1b0c9d8e\n").unwrap();
+        std::fs::create_dir_all(root.path().join(".docker")).unwrap();
+        std::fs::write(
+            root.path().join(".docker/image_metadata"),
+            r#"{"image": "cis/node:latest", "labels": {"maintainer": "cis"}}"#,
+        )
+        .unwrap();
+
+        let docker = detect_docker_context_in(root.path()).await.unwrap().unwrap();
+        assert_eq!(docker.container_id, "3f2a1b0c9d8e");
+        assert_eq!(docker.image.as_deref(), Some("cis/node:latest"));
+        assert_eq!(docker.labels.get("maintainer").map(String::as_str), Some("cis"));
+
+        let mut plain = DockerContext::default();
+        parse_image_metadata("alpine:3.19\n", &mut plain);
+        assert_eq!(plain.image.as_deref(), Some("alpine:3.19"));
+        assert!(plain.labels.is_empty());
+    }
 }
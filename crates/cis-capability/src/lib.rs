@@ -21,6 +21,8 @@ pub struct CapabilityLayer {
     pub skill: Arc<RwLock<SkillEngine>>,
     pub memory: Arc<RwLock<MemoryService>>,
     pub context: Arc<RwLock<ContextExtractor>>,
+    /// Receives capability events (e.g. memory writes for watermark checks)
+    events: Option<Arc<dyn EventSink>>,
 }
 
 impl CapabilityLayer {
@@ -30,7 +32,7 @@ impl CapabilityLayer {
         let memory = Arc::new(RwLock::new(MemoryService::open_default()?));
         let context = Arc::new(RwLock::new(ContextExtractor::new()));
 
-        Ok(Self { skill, memory, context })
+        Ok(Self { skill, memory, context, events: None })
     }
 
     /// Initialize with custom paths
@@ -43,6 +45,19 @@ impl CapabilityLayer {
             skill: Arc::new(RwLock::new(skill_engine)),
             memory: Arc::new(RwLock::new(memory_service)),
             context: Arc::new(RwLock::new(context_extractor)),
+            events: None,
+        }
+    }
+
+    /// Send capability events to `sink`
+    pub fn with_event_sink(mut self, sink: Arc<dyn EventSink>) -> Self {
+        self.events = Some(sink);
+        self
+    }
+
+    fn publish(&self, event: CapabilityEvent) {
+        if let Some(sink) = &self.events {
+            sink.publish(event);
         }
     }
 
@@ -83,7 +98,24 @@ impl CapabilityLayer {
         let project_path = context.detect_current().await?.project_root;
         
         let memory = self.memory.read().await;
-        memory.store(key, value, scope, project_path.as_deref())
+        let entry = memory.store(key, value, scope, project_path.as_deref())?;
+        self.publish(CapabilityEvent::MemoryWritten { db_path: memory.db_path(), count: 1 });
+        Ok(entry)
+    }
+
+    /// Convenience: store multiple memories atomically
+    pub async fn batch_remember(
+        &self,
+        entries: Vec<MemoryBatchEntry>,
+        scope: MemoryScope,
+    ) -> types::Result<Vec<MemoryEntry>> {
+        let context = self.context.read().await;
+        let project_path = context.detect_current().await?.project_root;
+        
+        let memory = self.memory.read().await;
+        let stored = memory.store_batch(entries, scope, project_path.as_deref())?;
+        self.publish(CapabilityEvent::MemoryWritten { db_path: memory.db_path(), count: stored.len() });
+        Ok(stored)
     }
 
     /// Convenience: recall memory
@@ -97,6 +129,41 @@ impl CapabilityLayer {
         let memory = self.memory.read().await;
         memory.recall(key, project_path.as_deref())
     }
+
+    /// Convenience: delete memory
+    ///
+    /// Deletes the key in the current project, or in every scope when `all` is set.
+    pub async fn forget(&self, key: &str, all: bool) -> types::Result<bool> {
+        let project_path = if all {
+            None
+        } else {
+            let context = self.context.read().await;
+            context.detect_current().await?.project_root
+        };
+        
+        let memory = self.memory.read().await;
+        memory.forget(key, project_path.as_deref())
+    }
+
+    /// Convenience: search memories by keyword and vector similarity
+    pub async fn search_memories(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> types::Result<Vec<HybridSearchResult>> {
+        let memory = self.memory.read().await;
+        memory.search_hybrid(query, limit, 0.5, 0.5)
+    }
+
+    /// Convenience: list memories, optionally filtered by key prefix
+    pub async fn list_memories(
+        &self,
+        prefix: Option<&str>,
+        limit: usize,
+    ) -> types::Result<Vec<MemoryEntry>> {
+        let memory = self.memory.read().await;
+        memory.search(prefix.unwrap_or(""), limit)
+    }
 }
 
 #[cfg(test)]
@@ -108,4 +175,32 @@ mod tests {
         let capability = CapabilityLayer::new().await;
         assert!(capability.is_ok());
     }
+
+    #[derive(Default)]
+    struct RecordingSink(std::sync::Mutex<Vec<CapabilityEvent>>);
+
+    impl EventSink for RecordingSink {
+        fn publish(&self, event: CapabilityEvent) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_writes_reach_event_sink() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db_path = dir.path().join("memory.db");
+        let sink = Arc::new(RecordingSink::default());
+        let capability = CapabilityLayer::with_paths(
+            SkillEngine::new(),
+            MemoryService::open(&db_path).unwrap(),
+            ContextExtractor::new(),
+        )
+        .await
+        .with_event_sink(sink.clone());
+
+        capability.remember("k", "v", MemoryScope::Global).await.unwrap();
+
+        let events = sink.0.lock().unwrap();
+        assert_eq!(*events, vec![CapabilityEvent::MemoryWritten { db_path: Some(db_path), count: 1 }]);
+    }
 }
@@ -1,31 +1,53 @@
 //! Memory service for persisting project preferences and execution history
 
-use crate::types::{MemoryEntry, MemoryScope, Result};
+use crate::types::{HybridSearchResult, MemoryBatchEntry, MemoryEntry, MemoryScope, Result};
 use chrono::Utc;
 use rusqlite::{Connection, OptionalExtension};
+use std::collections::HashSet;
 use std::path::Path;
 use uuid::Uuid;
 
+/// Text embedder used for the vector part of hybrid search
+pub trait Embedder: Send + Sync {
+    fn embed(&self, text: &str) -> Result<Vec<f32>>;
+}
+
 pub struct MemoryService {
     conn: Connection,
+    embedder: Option<Box<dyn Embedder>>,
 }
 
 impl MemoryService {
     /// Open memory database
     pub fn open(path: impl AsRef<Path>) -> Result<Self> {
         let conn = Connection::open(path)?;
-        let service = Self { conn };
+        let service = Self { conn, embedder: None };
         service.init_schema()?;
         Ok(service)
     }
 
-    /// Open with default path
-    pub fn open_default() -> Result<Self> {
-        let path = dirs::data_dir()
+    /// Attach an embedder to enable the vector part of `search_hybrid`
+    pub fn with_embedder(mut self, embedder: Box<dyn Embedder>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    /// Path of the open database, `None` when in memory
+    pub fn db_path(&self) -> Option<std::path::PathBuf> {
+        self.conn.path().filter(|p| !p.is_empty()).map(std::path::PathBuf::from)
+    }
+
+    /// Default database path
+    pub fn default_path() -> std::path::PathBuf {
+        dirs::data_dir()
             .unwrap_or_else(|| std::path::PathBuf::from("."))
             .join("cis")
-            .join("memory.db");
-        
+            .join("memory.db")
+    }
+
+    /// Open with default path
+    pub fn open_default() -> Result<Self> {
+        let path = Self::default_path();
         std::fs::create_dir_all(path.parent().unwrap())?;
         Self::open(path)
     }
@@ -62,6 +84,47 @@ impl MemoryService {
             created_at: Utc::now(),
         };
 
+        self.insert_entry(&entry)?;
+
+        Ok(entry)
+    }
+
+    /// Store multiple memories atomically
+    ///
+    /// Runs in a single `BEGIN IMMEDIATE` transaction; if any write fails the
+    /// whole batch is rolled back and the first error is returned.
+    pub fn store_batch(&self, entries: Vec<MemoryBatchEntry>, scope: MemoryScope, project_path: Option<&Path>) -> Result<Vec<MemoryEntry>> {
+        let now = Utc::now();
+        let stored: Vec<MemoryEntry> = entries
+            .into_iter()
+            .map(|e| MemoryEntry {
+                id: Uuid::new_v4().to_string(),
+                key: e.key,
+                value: e.value,
+                scope,
+                project_path: project_path.map(|p| p.to_path_buf()),
+                created_at: now,
+            })
+            .collect();
+
+        self.conn.execute_batch("BEGIN IMMEDIATE")?;
+
+        for entry in &stored {
+            if let Err(e) = self.insert_entry(entry) {
+                let _ = self.conn.execute_batch("ROLLBACK");
+                return Err(e);
+            }
+        }
+
+        if let Err(e) = self.conn.execute_batch("COMMIT") {
+            let _ = self.conn.execute_batch("ROLLBACK");
+            return Err(e.into());
+        }
+
+        Ok(stored)
+    }
+
+    fn insert_entry(&self, entry: &MemoryEntry) -> Result<()> {
         self.conn.execute(
             "INSERT OR REPLACE INTO memories 
              (id, key, value, scope, project_path, created_at, updated_at)
@@ -76,7 +139,7 @@ impl MemoryService {
             ],
         )?;
 
-        Ok(entry)
+        Ok(())
     }
 
     /// Recall a memory by key
@@ -172,6 +235,88 @@ impl MemoryService {
         Ok(entries)
     }
 
+    /// Hybrid search combining keyword and vector similarity
+    ///
+    /// Each component is normalized to [0, 1] across the candidate set, then
+    /// combined as `keyword_weight * keyword + vector_weight * vector`.
+    /// Without an embedder the vector component is always 0.
+    pub fn search_hybrid(
+        &self,
+        query: &str,
+        limit: usize,
+        keyword_weight: f32,
+        vector_weight: f32,
+    ) -> Result<Vec<HybridSearchResult>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, key, value, scope, project_path, created_at 
+             FROM memories 
+             ORDER BY updated_at DESC"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(MemoryEntry {
+                id: row.get(0)?,
+                key: row.get(1)?,
+                value: row.get(2)?,
+                scope: parse_scope(&row.get::<_, String>(3)?),
+                project_path: row.get::<_, Option<String>>(4)?.map(|p| p.into()),
+                created_at: chrono::DateTime::from_timestamp(row.get(5)?, 0)
+                    .unwrap_or_else(Utc::now),
+            })
+        })?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+
+        let query_tokens: HashSet<String> = tokenize(query).collect();
+        let query_vec = match &self.embedder {
+            Some(embedder) if vector_weight > 0.0 => Some(embedder.embed(query)?),
+            _ => None,
+        };
+
+        let mut keyword_scores = Vec::with_capacity(entries.len());
+        let mut vector_scores = Vec::with_capacity(entries.len());
+        for entry in &entries {
+            let text = format!("{} {}", entry.key, entry.value);
+
+            let keyword = if query_tokens.is_empty() {
+                0.0
+            } else {
+                let tokens: HashSet<String> = tokenize(&text).collect();
+                query_tokens.intersection(&tokens).count() as f32 / query_tokens.len() as f32
+            };
+            keyword_scores.push(keyword);
+
+            let vector = match (&self.embedder, &query_vec) {
+                (Some(embedder), Some(q)) => cosine_similarity(q, &embedder.embed(&text)?).max(0.0),
+                _ => 0.0,
+            };
+            vector_scores.push(vector);
+        }
+
+        normalize(&mut keyword_scores);
+        normalize(&mut vector_scores);
+
+        let mut results: Vec<HybridSearchResult> = entries
+            .into_iter()
+            .zip(keyword_scores.into_iter().zip(vector_scores))
+            .map(|(entry, (keyword_score, vector_score))| HybridSearchResult {
+                entry,
+                score: keyword_weight * keyword_score + vector_weight * vector_score,
+                keyword_score,
+                vector_score,
+            })
+            .filter(|r| r.score > 0.0)
+            .collect();
+
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(limit);
+
+        Ok(results)
+    }
+
     /// Delete a memory
     pub fn forget(&self, key: &str, project_path: Option<&Path>) -> Result<bool> {
         let affected = if let Some(project) = project_path {
@@ -198,6 +343,37 @@ fn parse_scope(s: &str) -> MemoryScope {
     }
 }
 
+/// Split text into lowercase alphanumeric tokens
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+}
+
+/// Scale scores so the maximum becomes 1.0
+fn normalize(scores: &mut [f32]) {
+    let max = scores.iter().cloned().fold(0.0_f32, f32::max);
+    if max > 0.0 {
+        for score in scores.iter_mut() {
+            *score /= max;
+        }
+    }
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,4 +410,103 @@ mod tests {
         let value = service.recall("cmd", Some(&other)).unwrap();
         assert_eq!(value, Some("global_cmd".to_string()));
     }
+
+    /// Bag-of-concepts embedder that maps synonyms to the same dimension
+    struct ConceptEmbedder;
+
+    impl Embedder for ConceptEmbedder {
+        fn embed(&self, text: &str) -> Result<Vec<f32>> {
+            let mut vec = vec![0.0; 4];
+            for token in tokenize(text) {
+                let dim = match token.as_str() {
+                    "car" | "automobile" | "vehicle" => 0,
+                    "repair" | "maintenance" | "fix" => 1,
+                    "wash" | "clean" => 2,
+                    "coupon" | "discount" => 3,
+                    _ => continue,
+                };
+                vec[dim] += 1.0;
+            }
+            Ok(vec)
+        }
+    }
+
+    #[test]
+    fn test_search_hybrid_semantic_outranks_keyword() {
+        let temp = TempDir::new().unwrap();
+        let service = MemoryService::open(temp.path().join("test.db"))
+            .unwrap()
+            .with_embedder(Box::new(ConceptEmbedder));
+
+        service.store("vehicle_note", "automobile maintenance schedule", MemoryScope::Global, None).unwrap();
+        service.store("coupon_1", "car wash coupon", MemoryScope::Global, None).unwrap();
+
+        // Pure keyword: only the lexical match is found
+        let keyword = service.search_hybrid("car repair", 10, 1.0, 0.0).unwrap();
+        assert_eq!(keyword.len(), 1);
+        assert_eq!(keyword[0].entry.key, "coupon_1");
+
+        // Hybrid: the semantically similar entry ranks first
+        let hybrid = service.search_hybrid("car repair", 10, 0.3, 0.7).unwrap();
+        assert_eq!(hybrid.len(), 2);
+        assert_eq!(hybrid[0].entry.key, "vehicle_note");
+        assert_eq!(hybrid[0].keyword_score, 0.0);
+        assert!((hybrid[0].vector_score - 1.0).abs() < 1e-6);
+        assert!(hybrid[0].score > hybrid[1].score);
+    }
+
+    #[test]
+    fn test_search_hybrid_without_embedder() {
+        let temp = TempDir::new().unwrap();
+        let service = MemoryService::open(temp.path().join("test.db")).unwrap();
+
+        service.store("build_cmd", "cargo build", MemoryScope::Global, None).unwrap();
+        service.store("test_cmd", "cargo test", MemoryScope::Global, None).unwrap();
+
+        let results = service.search_hybrid("cargo test", 1, 0.5, 0.5).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entry.key, "test_cmd");
+        assert_eq!(results[0].vector_score, 0.0);
+    }
+
+    fn batch(pairs: &[(&str, &str)]) -> Vec<MemoryBatchEntry> {
+        pairs
+            .iter()
+            .map(|(k, v)| MemoryBatchEntry { key: k.to_string(), value: v.to_string() })
+            .collect()
+    }
+
+    #[test]
+    fn test_store_batch() {
+        let temp = TempDir::new().unwrap();
+        let service = MemoryService::open(temp.path().join("test.db")).unwrap();
+
+        let stored = service
+            .store_batch(batch(&[("summary", "talked about DAGs"), ("keywords", "dag,worker")]), MemoryScope::Global, None)
+            .unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(service.recall("summary", None).unwrap(), Some("talked about DAGs".to_string()));
+        assert_eq!(service.recall("keywords", None).unwrap(), Some("dag,worker".to_string()));
+    }
+
+    #[test]
+    fn test_store_batch_rolls_back_on_error() {
+        let temp = TempDir::new().unwrap();
+        let service = MemoryService::open(temp.path().join("test.db")).unwrap();
+
+        // Reject the second write to force a failure mid-batch
+        service.conn.execute_batch(
+            "CREATE TRIGGER reject_bad BEFORE INSERT ON memories
+             WHEN NEW.key = 'bad'
+             BEGIN SELECT RAISE(ABORT, 'rejected'); END;"
+        ).unwrap();
+
+        let result = service.store_batch(batch(&[("good", "1"), ("bad", "2")]), MemoryScope::Global, None);
+        assert!(result.is_err());
+        assert_eq!(service.recall("good", None).unwrap(), None);
+
+        // Connection is usable again after rollback
+        service.store("after", "ok", MemoryScope::Global, None).unwrap();
+        assert_eq!(service.recall("after", None).unwrap(), Some("ok".to_string()));
+    }
 }
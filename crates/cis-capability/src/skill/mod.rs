@@ -1,6 +1,6 @@
 //! Skill execution engine
 
-use crate::types::{ExecutionRequest, ExecutionResult, SkillMatch, SkillMetadata};
+use crate::types::{ExecutionRequest, ExecutionResult, MethodDescriptor, OutputSchema, SkillMatch, SkillMetadata};
 use crate::types::{CapabilityError, Result};
 use std::collections::HashMap;
 use std::process::Stdio;
@@ -20,6 +20,10 @@ impl SkillEngine {
     }
 
     /// Execute a skill by name
+    ///
+    /// When the skill declares an output schema, `data` is validated against it.
+    /// A mismatch is reported through `validation_errors` / `schema_valid`
+    /// rather than as an error, so callers decide how strict to be.
     pub async fn execute(&self, request: ExecutionRequest) -> Result<ExecutionResult> {
         let start = Instant::now();
         
@@ -45,14 +49,15 @@ impl SkillEngine {
 
         let duration_ms = start.elapsed().as_millis() as u64;
 
-        Ok(ExecutionResult {
-            success: result.success,
-            output: result.output,
-            exit_code: result.exit_code,
+        let mut result = ExecutionResult {
             work_dir: work_dir.clone(),
             duration_ms,
-            metadata: result.metadata,
-        })
+            ..result
+        };
+        if let Some(schema) = &skill.output_schema {
+            result.validate_output(schema);
+        }
+        Ok(result)
     }
 
     /// Find matching skills for a description
@@ -85,6 +90,13 @@ impl SkillEngine {
         self.registry.skills.values().map(|s| &s.metadata).collect()
     }
 
+    /// List callable methods of a skill
+    pub fn list_methods(&self, skill_name: &str) -> Result<Vec<MethodDescriptor>> {
+        let skill = self.registry.get(skill_name)
+            .ok_or_else(|| CapabilityError::SkillNotFound(skill_name.to_string()))?;
+        Ok(skill.describe_methods())
+    }
+
     /// Execute shell command
     async fn execute_shell(&self, skill: &SkillDef, params: &serde_json::Value, work_dir: &std::path::Path) -> Result<ExecutionResult> {
         // Build command
@@ -123,6 +135,9 @@ impl SkillEngine {
             format!("Error: {}\n{}", stderr, stdout)
         };
 
+        // JSON stdout doubles as structured data
+        let data = if success { serde_json::from_str(stdout.trim()).ok() } else { None };
+
         Ok(ExecutionResult {
             success,
             output: result_output,
@@ -130,6 +145,9 @@ impl SkillEngine {
             work_dir: work_dir.to_path_buf(),
             duration_ms: 0,
             metadata: HashMap::new(),
+            data,
+            validation_errors: Vec::new(),
+            schema_valid: true,
         })
     }
 
@@ -139,7 +157,7 @@ impl SkillEngine {
             "context-extract" => {
                 // Return context as JSON
                 let context_json = serde_json::to_string_pretty(&request.context)?;
-                Ok(ExecutionResult::success(context_json))
+                Ok(ExecutionResult::success(context_json).with_data(serde_json::to_value(&request.context)?))
             }
             "memory-store" => {
                 // This would need memory service injected
@@ -195,6 +213,7 @@ impl SkillRegistry {
             },
             skill_type: SkillType::Shell,
             command: Some("git commit -m '{{message}}'".to_string()),
+            output_schema: None,
         });
 
         // Register git-status
@@ -209,6 +228,7 @@ impl SkillRegistry {
             },
             skill_type: SkillType::Shell,
             command: Some("git status".to_string()),
+            output_schema: None,
         });
 
         // Register context-extract
@@ -223,6 +243,15 @@ impl SkillRegistry {
             },
             skill_type: SkillType::Builtin,
             command: None,
+            output_schema: Some(OutputSchema(serde_json::json!({
+                "type": "object",
+                "required": ["detected_files", "environment"],
+                "properties": {
+                    "project_root": { "type": ["string", "null"] },
+                    "detected_files": { "type": "array", "items": { "type": "string" } },
+                    "environment": { "type": "object" }
+                }
+            }))),
         });
     }
 }
@@ -233,6 +262,53 @@ struct SkillDef {
     metadata: SkillMetadata,
     skill_type: SkillType,
     command: Option<String>,
+    output_schema: Option<OutputSchema>,
+}
+
+impl SkillDef {
+    /// Registry skills expose a single `execute` method built from their parameters
+    fn describe_methods(&self) -> Vec<MethodDescriptor> {
+        vec![MethodDescriptor {
+            name: "execute".to_string(),
+            description: self.metadata.description.clone(),
+            params_schema: params_schema(&self.metadata.parameters),
+            return_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "success": { "type": "boolean" },
+                    "output": { "type": "string" },
+                    "exit_code": { "type": ["integer", "null"] }
+                }
+            }),
+            output_schema: self.output_schema.clone(),
+        }]
+    }
+}
+
+/// Build a JSON schema from skill parameters
+fn params_schema(params: &[crate::types::SkillParameter]) -> serde_json::Value {
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+
+    for param in params {
+        let mut prop = serde_json::json!({
+            "type": param.param_type,
+            "description": param.description,
+        });
+        if let Some(default) = &param.default_value {
+            prop["default"] = default.clone();
+        }
+        properties.insert(param.name.clone(), prop);
+        if param.required {
+            required.push(serde_json::Value::String(param.name.clone()));
+        }
+    }
+
+    serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
 }
 
 #[derive(Clone, Copy)]
@@ -287,3 +363,58 @@ fn render_template(template: &str, params: &serde_json::Value) -> Result<String>
     
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_methods() {
+        let engine = SkillEngine::new();
+
+        let methods = engine.list_methods("git-commit").unwrap();
+        assert_eq!(methods.len(), 1);
+        assert_eq!(methods[0].name, "execute");
+        assert_eq!(methods[0].params_schema["properties"]["message"]["type"], "string");
+        assert_eq!(methods[0].params_schema["required"][0], "message");
+
+        assert!(matches!(
+            engine.list_methods("no-such-skill"),
+            Err(CapabilityError::SkillNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_output() {
+        let schema = OutputSchema(serde_json::json!({
+            "type": "object",
+            "required": ["count"],
+            "properties": { "count": { "type": "integer" } }
+        }));
+
+        let mut result = ExecutionResult::success("{}").with_data(serde_json::json!({ "count": 3 }));
+        result.validate_output(&schema);
+        assert!(result.schema_valid);
+
+        // Mismatch keeps the raw data and records the error
+        let mut result = ExecutionResult::success("{}").with_data(serde_json::json!({ "count": "three" }));
+        result.validate_output(&schema);
+        assert!(!result.schema_valid);
+        assert_eq!(result.data.unwrap()["count"], "three");
+        assert_eq!(result.validation_errors.len(), 1);
+
+        // Every mismatch is reported, not just the first
+        let schema = OutputSchema(serde_json::json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": { "tags": { "type": "array", "items": { "type": "string" } } }
+        }));
+        let mut result = ExecutionResult::success("{}").with_data(serde_json::json!({ "tags": ["a", 1, 2] }));
+        result.validate_output(&schema);
+        assert_eq!(result.validation_errors, vec![
+            "$: missing required property 'name'".to_string(),
+            "$.tags[1]: expected type \"string\"".to_string(),
+            "$.tags[2]: expected type \"string\"".to_string(),
+        ]);
+    }
+}
@@ -52,6 +52,19 @@ pub struct ExecutionResult {
     pub work_dir: PathBuf,
     pub duration_ms: u64,
     pub metadata: HashMap<String, String>,
+    /// Structured output (JSON stdout or builtin result), returned as-is even if invalid
+    #[serde(default)]
+    pub data: Option<serde_json::Value>,
+    /// Mismatches against the method's output schema
+    #[serde(default)]
+    pub validation_errors: Vec<String>,
+    /// False when `data` does not match the output schema
+    #[serde(default = "default_schema_valid")]
+    pub schema_valid: bool,
+}
+
+fn default_schema_valid() -> bool {
+    true
 }
 
 impl ExecutionResult {
@@ -63,6 +76,9 @@ impl ExecutionResult {
             work_dir: std::env::current_dir().unwrap_or_default(),
             duration_ms: 0,
             metadata: HashMap::new(),
+            data: None,
+            validation_errors: Vec::new(),
+            schema_valid: true,
         }
     }
 
@@ -74,8 +90,25 @@ impl ExecutionResult {
             work_dir: std::env::current_dir().unwrap_or_default(),
             duration_ms: 0,
             metadata: HashMap::new(),
+            data: None,
+            validation_errors: Vec::new(),
+            schema_valid: true,
         }
     }
+
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Check `data` against `schema`, recording mismatches instead of failing
+    pub fn validate_output(&mut self, schema: &OutputSchema) {
+        let Some(data) = &self.data else {
+            return;
+        };
+        self.validation_errors = schema.validate(data);
+        self.schema_valid = self.validation_errors.is_empty();
+    }
 }
 
 /// Project context information
@@ -88,6 +121,9 @@ pub struct ProjectContext {
     pub git_status: Option<GitStatus>,
     pub detected_files: Vec<String>,
     pub environment: HashMap<String, String>,
+    /// Author of the code the caller is working on (from `git blame`)
+    #[serde(default)]
+    pub authored_by: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -99,6 +135,25 @@ pub struct GitStatus {
     pub untracked: Vec<String>,
 }
 
+/// `git blame` attribution for a single line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitBlameContext {
+    pub commit: String,
+    pub author: String,
+    pub date: chrono::DateTime<chrono::Utc>,
+    pub message: String,
+}
+
+/// Docker container the process is running in
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DockerContext {
+    /// Container ID (Docker uses it as the hostname)
+    pub container_id: String,
+    /// Image name, from `/.docker/image_metadata` when present
+    pub image: Option<String>,
+    pub labels: HashMap<String, String>,
+}
+
 /// Memory entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryEntry {
@@ -110,6 +165,25 @@ pub struct MemoryEntry {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Single entry of a batch memory write
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryBatchEntry {
+    pub key: String,
+    pub value: String,
+}
+
+/// Hybrid (keyword + vector) search result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HybridSearchResult {
+    pub entry: MemoryEntry,
+    /// Weighted combination of the two normalized scores
+    pub score: f32,
+    /// Keyword score normalized to [0, 1]
+    pub keyword_score: f32,
+    /// Vector score normalized to [0, 1]
+    pub vector_score: f32,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MemoryScope {
     Global,
@@ -136,6 +210,102 @@ pub struct SkillParameter {
     pub default_value: Option<serde_json::Value>,
 }
 
+/// Event emitted by the capability layer
+#[derive(Debug, Clone, PartialEq)]
+pub enum CapabilityEvent {
+    /// `count` memories were written to the database at `db_path`
+    MemoryWritten { db_path: Option<PathBuf>, count: usize },
+}
+
+/// Receiver for capability events, e.g. an adapter onto the cis-core event bus
+pub trait EventSink: Send + Sync {
+    fn publish(&self, event: CapabilityEvent);
+}
+
+/// Callable method of a skill (for introspection)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MethodDescriptor {
+    pub name: String,
+    pub description: String,
+    pub params_schema: serde_json::Value,
+    pub return_schema: serde_json::Value,
+    /// Schema of `ExecutionResult.data`; unvalidated when absent
+    #[serde(default)]
+    pub output_schema: Option<OutputSchema>,
+}
+
+/// JSON Schema for the structured data a method returns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OutputSchema(pub serde_json::Value);
+
+impl OutputSchema {
+    /// Mismatches between `data` and the schema, empty when valid
+    ///
+    /// Supports the subset skills declare: `type`, `enum`, `required`,
+    /// `properties` and `items`.
+    pub fn validate(&self, data: &serde_json::Value) -> Vec<String> {
+        let mut errors = Vec::new();
+        validate_value(data, &self.0, "$", &mut errors);
+        errors
+    }
+}
+
+fn validate_value(value: &serde_json::Value, schema: &serde_json::Value, path: &str, errors: &mut Vec<String>) {
+    use serde_json::Value;
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            errors.push(format!("{}: value not in enum", path));
+        }
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let matches = |ty: &str| match ty {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "boolean" => value.is_boolean(),
+            "null" => value.is_null(),
+            _ => true,
+        };
+        let ok = match expected {
+            Value::String(ty) => matches(ty),
+            Value::Array(types) => types.iter().filter_map(Value::as_str).any(matches),
+            _ => true,
+        };
+        if !ok {
+            errors.push(format!("{}: expected type {}", path, expected));
+            return;
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(key) {
+                    errors.push(format!("{}: missing required property '{}'", path, key));
+                }
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (key, property_schema) in properties {
+                if let Some(property) = object.get(key) {
+                    validate_value(property, property_schema, &format!("{}.{}", path, key), errors);
+                }
+            }
+        }
+    }
+
+    if let (Some(items), Some(item_schema)) = (value.as_array(), schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            validate_value(item, item_schema, &format!("{}[{}]", path, i), errors);
+        }
+    }
+}
+
 /// Skill match result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkillMatch {
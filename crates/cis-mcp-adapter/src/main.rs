@@ -3,7 +3,11 @@
 //! Exposes CIS capabilities via Model Context Protocol
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use cis_capability::{CapabilityEvent, EventSink};
+use cis_core::config::Config;
+use cis_core::event_bus::MemoryEventBus;
+use cis_core::memory::MemoryWatermarkMonitor;
 use std::sync::Arc;
 use tracing::info;
 
@@ -21,12 +25,25 @@ struct Cli {
     /// Enable verbose logging
     #[arg(short, long)]
     verbose: bool,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// List available prompt templates without starting the server
+    ListPrompts,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    if let Some(Commands::ListPrompts) = cli.command {
+        return list_prompts();
+    }
+
     // Setup logging
     let level = if cli.verbose {
         tracing::Level::DEBUG
@@ -40,8 +57,11 @@ async fn main() -> Result<()> {
 
     info!("Starting CIS MCP Server...");
 
-    // Initialize capability layer
-    let capability = cis_capability::CapabilityLayer::new().await?;
+    // Initialize capability layer; memory writes trigger a watermark check
+    let watermark = start_memory_watermark();
+    let capability = cis_capability::CapabilityLayer::new()
+        .await?
+        .with_event_sink(Arc::new(WatermarkSink(watermark)));
     
     // Create MCP server
     #[allow(clippy::arc_with_non_send_sync)]
@@ -52,3 +72,56 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Start the memory watermark monitor on the capability memory database
+///
+/// Thresholds come from the global config (`[memory_watermark]`); alerts
+/// go to the cis-core event bus.
+fn start_memory_watermark() -> Arc<MemoryWatermarkMonitor> {
+    let config = Config::load().unwrap_or_else(|e| {
+        tracing::warn!("Failed to load config, using default watermark thresholds: {}", e);
+        Config::default()
+    });
+    let monitor = Arc::new(MemoryWatermarkMonitor::new(
+        cis_capability::MemoryService::default_path(),
+        config.memory_watermark,
+        Arc::new(MemoryEventBus::new()),
+    ));
+    monitor.clone().spawn();
+    monitor
+}
+
+/// Checks the watermark right after memories are written
+struct WatermarkSink(Arc<MemoryWatermarkMonitor>);
+
+impl EventSink for WatermarkSink {
+    fn publish(&self, event: CapabilityEvent) {
+        let CapabilityEvent::MemoryWritten { .. } = event;
+        let monitor = self.0.clone();
+        tokio::spawn(async move {
+            if let Err(e) = monitor.check().await {
+                tracing::warn!("Memory watermark check failed: {}", e);
+            }
+        });
+    }
+}
+
+/// Print available prompts and their arguments
+fn list_prompts() -> Result<()> {
+    let store = prompts::PromptStore::new();
+    let mut prompts = store.list_prompts();
+    prompts.sort_by(|a, b| a.name.cmp(&b.name));
+
+    println!("{:<28} {:<30} Description", "Name", "Arguments");
+    println!("{}", "-".repeat(100));
+    for prompt in prompts {
+        let args: Vec<String> = prompt
+            .arguments
+            .iter()
+            .map(|a| if a.required { a.name.clone() } else { format!("[{}]", a.name) })
+            .collect();
+        println!("{:<28} {:<30} {}", prompt.name, args.join(", "), prompt.description);
+    }
+
+    Ok(())
+}
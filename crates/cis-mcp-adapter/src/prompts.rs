@@ -7,8 +7,16 @@ use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::path::Path;
 use tracing::{debug, info};
 
+/// Built-in prompt files from the `prompts/` directory
+const BUILTIN_PROMPT_FILES: &[(&str, &str)] = &[
+    ("summarize_memory.toml", include_str!("../prompts/summarize_memory.toml")),
+    ("plan_dag_from_description.toml", include_str!("../prompts/plan_dag_from_description.toml")),
+    ("review_todo_list.toml", include_str!("../prompts/review_todo_list.toml")),
+];
+
 /// Prompt definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Prompt {
@@ -56,6 +64,23 @@ pub struct PromptTemplate {
     pub template: String,
 }
 
+/// Prompt defined in a TOML file
+#[derive(Debug, Clone, Deserialize)]
+pub struct PromptFile {
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub arguments: Vec<PromptArgument>,
+    pub messages: Vec<PromptFileMessage>,
+}
+
+/// Message template in a prompt file
+#[derive(Debug, Clone, Deserialize)]
+pub struct PromptFileMessage {
+    pub role: String,
+    pub content: String,
+}
+
 /// Rendered prompt result
 #[derive(Debug, Clone, Serialize)]
 pub struct RenderedPrompt {
@@ -90,6 +115,8 @@ pub enum Content {
 pub struct PromptStore {
     prompts: HashMap<String, Prompt>,
     templates: HashMap<String, PromptTemplate>,
+    /// Multi-message templates loaded from prompt files
+    message_templates: HashMap<String, Vec<PromptFileMessage>>,
 }
 
 impl Default for PromptStore {
@@ -103,6 +130,7 @@ impl PromptStore {
         let mut store = Self {
             prompts: HashMap::new(),
             templates: HashMap::new(),
+            message_templates: HashMap::new(),
         };
 
         // Register built-in prompts
@@ -110,6 +138,12 @@ impl PromptStore {
             tracing::error!("Failed to register built-in prompts: {}", e);
         }
 
+        for (file, content) in BUILTIN_PROMPT_FILES {
+            if let Err(e) = store.load_prompt_toml(content) {
+                tracing::error!("Failed to load built-in prompt {}: {}", file, e);
+            }
+        }
+
         store
     }
 
@@ -253,13 +287,52 @@ Create a detailed execution plan that includes:
         Ok(())
     }
 
+    /// Load a prompt from TOML (`name`, `description`, `arguments[]`, `messages[]`)
+    pub fn load_prompt_toml(&mut self, content: &str) -> Result<()> {
+        let file: PromptFile = toml::from_str(content)?;
+
+        for message in &file.messages {
+            if !matches!(message.role.as_str(), "user" | "assistant" | "system") {
+                return Err(anyhow!(
+                    "Invalid role '{}' in prompt: {}",
+                    message.role,
+                    file.name
+                ));
+            }
+        }
+
+        self.register_prompt(Prompt {
+            name: file.name.clone(),
+            description: file.description,
+            arguments: file.arguments,
+            metadata: None,
+        })?;
+        self.message_templates.insert(file.name, file.messages);
+        Ok(())
+    }
+
+    /// Load all `*.toml` prompt files in a directory
+    pub fn load_dir(&mut self, dir: &Path) -> Result<usize> {
+        let mut loaded = 0;
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "toml") {
+                let content = std::fs::read_to_string(&path)?;
+                self.load_prompt_toml(&content)
+                    .map_err(|e| anyhow!("{}: {}", path.display(), e))?;
+                loaded += 1;
+            }
+        }
+        Ok(loaded)
+    }
+
     /// Register a new prompt
     pub fn register_prompt(&mut self, prompt: Prompt) -> Result<()> {
         if self.prompts.contains_key(&prompt.name) {
             return Err(anyhow!("Prompt already exists: {}", prompt.name));
         }
-        self.prompts.insert(prompt.name.clone(), prompt);
         info!("Registered prompt: {}", prompt.name);
+        self.prompts.insert(prompt.name.clone(), prompt);
         Ok(())
     }
 
@@ -271,8 +344,8 @@ Create a detailed execution plan that includes:
                 template.name
             ));
         }
-        self.templates.insert(template.name.clone(), template);
         debug!("Registered template for: {}", template.name);
+        self.templates.insert(template.name.clone(), template);
         Ok(())
     }
 
@@ -304,26 +377,32 @@ Create a detailed execution plan that includes:
             }
         }
 
+        if let Some(messages) = self.message_templates.get(name) {
+            return Ok(RenderedPrompt {
+                name: prompt.name.clone(),
+                description: prompt.description.clone(),
+                messages: messages
+                    .iter()
+                    .map(|m| {
+                        let content = Content::Text {
+                            text: Self::render_template(&m.content, arguments),
+                        };
+                        match m.role.as_str() {
+                            "assistant" => PromptMessage::Assistant { content },
+                            "system" => PromptMessage::System { content },
+                            _ => PromptMessage::User { content },
+                        }
+                    })
+                    .collect(),
+            });
+        }
+
         let template = self
             .templates
             .get(name)
             .ok_or_else(|| anyhow!("Template not found for prompt: {}", name))?;
 
-        // Simple template rendering (replace {{key}} with values)
-        let mut rendered = template.template.clone();
-        for (key, value) in arguments {
-            let placeholder = format!("{{{{{}}}}}", key);
-            let value_str = match value {
-                Value::String(s) => s.clone(),
-                Value::Number(n) => n.to_string(),
-                Value::Bool(b) => b.to_string(),
-                _ => serde_json::to_string(value).unwrap_or_default(),
-            };
-            rendered = rendered.replace(&placeholder, &value_str);
-        }
-
-        // Remove any unreplaced placeholders (optional arguments)
-        rendered = Self::remove_conditionals(&rendered);
+        let rendered = Self::render_template(&template.template, arguments);
 
         Ok(RenderedPrompt {
             name: prompt.name.clone(),
@@ -334,15 +413,32 @@ Create a detailed execution plan that includes:
         })
     }
 
-    /// Remove conditional blocks that weren't rendered
-    fn remove_conditionals(template: &str) -> String {
-        let mut result = template.to_string();
+    /// Render `{{#if key}}...{{/if}}` blocks and `{{key}}` placeholders
+    fn render_template(template: &str, arguments: &HashMap<String, Value>) -> String {
+        let value_str = |value: &Value| match value {
+            Value::String(s) => s.clone(),
+            Value::Number(n) => n.to_string(),
+            Value::Bool(b) => b.to_string(),
+            _ => serde_json::to_string(value).unwrap_or_default(),
+        };
+
+        // Keep conditional blocks only when the argument is set
+        let re = regex::Regex::new(r"(?s)\{\{#if\s+(\w+)\}\}(.*?)\{\{/if\}\}").unwrap();
+        let mut rendered = re
+            .replace_all(template, |caps: &regex::Captures| {
+                match arguments.get(&caps[1]) {
+                    Some(v) if !v.is_null() && !value_str(v).is_empty() => caps[2].to_string(),
+                    _ => String::new(),
+                }
+            })
+            .to_string();
 
-        // Remove {{#if key}}...{{/if}} blocks where key is not set
-        let re = regex::Regex::new(r"\{\{#if\s+(\w+)\}\}.*?\{\{/if\}\}").unwrap();
-        result = re.replace_all(&result, "").to_string();
+        for (key, value) in arguments {
+            let placeholder = format!("{{{{{}}}}}", key);
+            rendered = rendered.replace(&placeholder, &value_str(value));
+        }
 
-        result
+        rendered
     }
 
     /// Search prompts by metadata
@@ -409,4 +505,56 @@ mod tests {
         let results = store.search_prompts(Some("code-analysis"), None);
         assert!(!results.is_empty());
     }
+
+    #[test]
+    fn test_builtin_prompt_files() {
+        let store = PromptStore::new();
+        for name in ["summarize_memory", "plan_dag_from_description", "review_todo_list"] {
+            assert!(store.get_prompt(name).is_some(), "missing prompt {}", name);
+        }
+
+        let prompt = store.get_prompt("summarize_memory").unwrap();
+        assert_eq!(prompt.arguments[0].name, "key");
+        assert!(prompt.arguments[0].required);
+    }
+
+    #[test]
+    fn test_render_prompt_file() {
+        let store = PromptStore::new();
+        let mut args = HashMap::new();
+        args.insert("description".to_string(), Value::String("build and test".to_string()));
+
+        let rendered = store.render_prompt("plan_dag_from_description", &args).unwrap();
+        assert_eq!(rendered.messages.len(), 2);
+        assert!(matches!(rendered.messages[0], PromptMessage::System { .. }));
+
+        let text = match &rendered.messages[1] {
+            PromptMessage::User { content: Content::Text { text } } => text.clone(),
+            _ => panic!("expected user text message"),
+        };
+        assert!(text.contains("build and test"));
+        assert!(!text.contains("Constraints"));
+        assert!(!text.contains("{{"));
+
+        args.insert("constraints".to_string(), Value::String("linux only".to_string()));
+        let rendered = store.render_prompt("plan_dag_from_description", &args).unwrap();
+        let text = match &rendered.messages[1] {
+            PromptMessage::User { content: Content::Text { text } } => text.clone(),
+            _ => panic!("expected user text message"),
+        };
+        assert!(text.contains("Constraints: linux only"));
+    }
+
+    #[test]
+    fn test_load_prompt_toml_rejects_bad_role() {
+        let mut store = PromptStore::new();
+        let toml = r#"
+            name = "bad"
+            description = "bad role"
+            [[messages]]
+            role = "tool"
+            content = "hi"
+        "#;
+        assert!(store.load_prompt_toml(toml).is_err());
+    }
 }
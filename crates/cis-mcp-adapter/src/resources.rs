@@ -5,7 +5,7 @@
 
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
@@ -92,6 +92,36 @@ pub enum ResourceChangeType {
     Deleted,
 }
 
+/// Paginated `resources/list` result
+#[derive(Debug, Clone, Serialize)]
+pub struct ListResourcesResult {
+    pub resources: Vec<Resource>,
+    #[serde(rename = "nextCursor", skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl ListResourcesResult {
+    /// Take one page of `items` starting at `cursor`
+    ///
+    /// The cursor is the offset of the first item on the page; an invalid
+    /// cursor is rejected rather than silently restarting from the beginning.
+    pub fn paginate(items: Vec<Resource>, cursor: Option<&str>, page_size: usize) -> Result<Self> {
+        let start = match cursor {
+            Some(c) => c
+                .parse::<usize>()
+                .map_err(|_| anyhow!("Invalid cursor: {}", c))?,
+            None => 0,
+        };
+
+        let total = items.len();
+        let resources: Vec<Resource> = items.into_iter().skip(start).take(page_size).collect();
+        let end = start + resources.len();
+        let next_cursor = if end < total { Some(end.to_string()) } else { None };
+
+        Ok(Self { resources, next_cursor })
+    }
+}
+
 /// Resource manager with full CRUD and subscription support
 pub struct ResourceManager {
     resources: Arc<RwLock<HashMap<String, Resource>>>,
@@ -233,13 +263,14 @@ impl ResourceManager {
             return Err(anyhow!("Resource already exists: {}", resource.uri));
         }
 
-        resources.insert(resource.uri.clone(), resource);
-        info!("Resource created: {}", resource.uri);
+        let uri = resource.uri.clone();
+        resources.insert(uri.clone(), resource);
+        info!("Resource created: {}", uri);
 
         // Notify subscribers
         drop(resources);
         self.notify_subscribers(ResourceChangeEvent {
-            uri: resource.uri.clone(),
+            uri,
             change_type: ResourceChangeType::Created,
             timestamp: chrono::Utc::now(),
             new_content: None,
@@ -485,7 +516,7 @@ impl ResourceManager {
             "context://current" => {
                 // In a real implementation, this would query actual context
                 let context = json!({
-                    "project_root": std::env::current_dir().unwrap_or_default().display(),
+                    "project_root": std::env::current_dir().unwrap_or_default().display().to_string(),
                     "project_type": "rust",
                     "package_manager": "cargo",
                     "git_branch": "feature/1.1.5"
@@ -564,4 +595,30 @@ mod tests {
         // Unsubscribe
         assert!(manager.unsubscribe(&sub_id).await.is_ok());
     }
+
+    fn named(uri: &str) -> Resource {
+        Resource {
+            uri: uri.to_string(),
+            name: uri.to_string(),
+            description: None,
+            mime_type: "text/plain".to_string(),
+            metadata: None,
+            annotations: None,
+        }
+    }
+
+    #[test]
+    fn test_paginate() {
+        let items: Vec<Resource> = (0..5).map(|i| named(&format!("test://{}", i))).collect();
+
+        let page = ListResourcesResult::paginate(items.clone(), None, 2).unwrap();
+        assert_eq!(page.resources.len(), 2);
+        assert_eq!(page.next_cursor.as_deref(), Some("2"));
+
+        let page = ListResourcesResult::paginate(items.clone(), Some("4"), 2).unwrap();
+        assert_eq!(page.resources[0].uri, "test://4");
+        assert!(page.next_cursor.is_none());
+
+        assert!(ListResourcesResult::paginate(items, Some("abc"), 2).is_err());
+    }
 }
@@ -2,16 +2,28 @@
 
 use crate::mcp_protocol::*;
 use crate::prompts::PromptStore;
-use crate::resources::ResourceManager;
-use cis_capability::{CapabilityLayer, CallerType};
+use crate::resources::{ListResourcesResult, ResourceContent, ResourceManager};
+use cis_capability::{CapabilityError, CapabilityLayer, CallerType};
 use serde_json::json;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::RwLock;
 use tracing::{debug, error, info};
 
+/// URI scheme for memory entries exposed as resources
+const MEMORY_URI_PREFIX: &str = "cis-memory://";
+
+/// Page size for `resources/list`
+const RESOURCES_PAGE_SIZE: usize = 100;
+
 pub struct CisMcpServer {
     capability: Arc<CapabilityLayer>,
     prompts: Arc<PromptStore>,
     resources: Arc<ResourceManager>,
+    /// subscription_id -> memory resource URI
+    memory_subscriptions: RwLock<HashMap<String, String>>,
+    /// Notifications waiting to be written after the current response
+    pending_notifications: Mutex<Vec<serde_json::Value>>,
 }
 
 impl CisMcpServer {
@@ -20,9 +32,96 @@ impl CisMcpServer {
             capability,
             prompts: Arc::new(PromptStore::new()),
             resources: Arc::new(ResourceManager::new()),
+            memory_subscriptions: RwLock::new(HashMap::new()),
+            pending_notifications: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// List all resources: built-in resources plus one per memory key
+    pub async fn list_resources(&self) -> anyhow::Result<Vec<crate::resources::Resource>> {
+        let mut resources = self.resources.list_resources().await?;
+        resources.sort_by(|a, b| a.uri.cmp(&b.uri));
+
+        // usize::MAX maps to SQLite's "no limit"
+        let entries = self
+            .capability
+            .list_memories(None, usize::MAX)
+            .await
+            .map_err(memory_error)?;
+
+        let mut seen = std::collections::HashSet::new();
+        let mut keys: Vec<String> = entries
+            .into_iter()
+            .map(|e| e.key)
+            .filter(|k| seen.insert(k.clone()))
+            .collect();
+        keys.sort();
+
+        resources.extend(keys.into_iter().map(|key| crate::resources::Resource {
+            uri: format!("{}{}", MEMORY_URI_PREFIX, key),
+            name: key,
+            description: None,
+            mime_type: "text/plain".to_string(),
+            metadata: None,
+            annotations: None,
+        }));
+
+        Ok(resources)
+    }
+
+    /// Read a resource, resolving `cis-memory://<key>` against the memory store
+    pub async fn read_resource(&self, uri: &str) -> anyhow::Result<ResourceContent> {
+        let key = match uri.strip_prefix(MEMORY_URI_PREFIX) {
+            Some(key) => key,
+            None => return self.resources.read_resource(uri).await,
+        };
+
+        let value = self
+            .capability
+            .recall(key)
+            .await
+            .map_err(memory_error)?
+            .ok_or_else(|| anyhow::anyhow!("Memory not found: {}", key))?;
+
+        Ok(ResourceContent {
+            uri: uri.to_string(),
+            mime_type: "text/plain".to_string(),
+            text: Some(value),
+            blob: None,
+        })
+    }
+
+    /// Queue a `resources/updated` notification if the client subscribed to this key
+    async fn notify_memory_changed(&self, key: &str) {
+        let uri = format!("{}{}", MEMORY_URI_PREFIX, key);
+        let subscribed = self
+            .memory_subscriptions
+            .read()
+            .await
+            .values()
+            .any(|u| u == &uri);
+
+        if subscribed {
+            self.pending_notifications.lock().unwrap().push(json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/resources/updated",
+                "params": { "uri": uri }
+            }));
         }
     }
 
+    /// List available prompt templates, sorted by name
+    pub fn list_prompts(&self) -> Vec<crate::prompts::Prompt> {
+        let mut prompts = self.prompts.list_prompts();
+        prompts.sort_by(|a, b| a.name.cmp(&b.name));
+        prompts
+    }
+
+    /// Drain queued notifications
+    fn take_notifications(&self) -> Vec<serde_json::Value> {
+        std::mem::take(&mut *self.pending_notifications.lock().unwrap())
+    }
+
     pub async fn run_stdio(&self) -> anyhow::Result<()> {
         use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 
@@ -42,6 +141,12 @@ impl CisMcpServer {
                     stdout.write_all(b"\n").await?;
                     stdout.flush().await?;
                     debug!("Sent: {}", response_json);
+
+                    for notification in self.take_notifications() {
+                        stdout.write_all(notification.to_string().as_bytes()).await?;
+                        stdout.write_all(b"\n").await?;
+                    }
+                    stdout.flush().await?;
                 }
                 Err(e) => {
                     error!("Error handling request: {}", e);
@@ -78,7 +183,7 @@ impl CisMcpServer {
             "initialize" => self.handle_initialize(id, &request).await,
             "tools/list" => self.handle_tools_list(id).await,
             "tools/call" => self.handle_tool_call(id, &request).await,
-            "resources/list" => self.handle_resources_list(id).await,
+            "resources/list" => self.handle_resources_list(id, &request).await,
             "resources/read" => self.handle_resources_read(id, &request).await,
             "resources/subscribe" => self.handle_resources_subscribe(id, &request).await,
             "resources/unsubscribe" => self.handle_resources_unsubscribe(id, &request).await,
@@ -104,7 +209,7 @@ impl CisMcpServer {
             capabilities: ServerCapabilities {
                 tools: Some(ToolsCapability { list_changed: false }),
                 resources: Some(ResourcesCapability {
-                    subscribe: false,
+                    subscribe: true,
                     list_changed: false,
                 }),
             },
@@ -118,7 +223,7 @@ impl CisMcpServer {
     }
 
     async fn handle_tools_list(&self, id: Option<serde_json::Value>) -> anyhow::Result<McpResponse> {
-        let tools = vec![
+        let mut tools = vec![
             // DAG Tools
             Tool {
                 name: "dag_create_run".to_string(),
@@ -336,6 +441,7 @@ impl CisMcpServer {
                 }),
             },
         ];
+        tools.extend(memory_tools());
 
         Ok(McpResponse::success(id, json!({ "tools": tools })))
     }
@@ -358,6 +464,18 @@ impl CisMcpServer {
 
         info!("Tool call: {} with {:?}", name, arguments);
 
+        if let Some(tool) = memory_tools().into_iter().find(|t| t.name == name) {
+            if let Err(msg) = validate_arguments(&tool.input_schema, &arguments) {
+                let tool_result = ToolCallResult {
+                    content: vec![Content::Text {
+                        text: format!("Invalid arguments for {}: {}", name, msg),
+                    }],
+                    is_error: Some(true),
+                };
+                return Ok(McpResponse::success(id, serde_json::to_value(tool_result)?));
+            }
+        }
+
         let result = match name {
             // DAG tools
             "dag_create_run" => self.dag_create_run(arguments).await,
@@ -371,6 +489,11 @@ impl CisMcpServer {
             // Memory tools
             "memory_store" => self.memory_store(arguments).await,
             "memory_recall" => self.memory_recall(arguments).await,
+            "memory_set" => self.memory_set(arguments).await,
+            "memory_get" => self.memory_get(arguments).await,
+            "memory_delete" => self.memory_delete(arguments).await,
+            "memory_search" => self.memory_search(arguments).await,
+            "memory_list" => self.memory_list(arguments).await,
             "context_extract" => self.context_extract().await,
             _ => Err(anyhow::anyhow!("Unknown tool: {}", name)),
         };
@@ -398,9 +521,16 @@ impl CisMcpServer {
     async fn handle_resources_list(
         &self,
         id: Option<serde_json::Value>,
+        request: &serde_json::Value,
     ) -> anyhow::Result<McpResponse> {
-        let resources = self.resources.list_resources().await?;
-        Ok(McpResponse::success(id, json!({ "resources": resources })))
+        let cursor = request
+            .get("params")
+            .and_then(|p| p.get("cursor"))
+            .and_then(|c| c.as_str());
+
+        let resources = self.list_resources().await?;
+        let page = ListResourcesResult::paginate(resources, cursor, RESOURCES_PAGE_SIZE)?;
+        Ok(McpResponse::success(id, serde_json::to_value(page)?))
     }
 
     async fn handle_resources_read(
@@ -417,7 +547,7 @@ impl CisMcpServer {
             .and_then(|u| u.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing uri"))?;
 
-        let content = self.resources.read_resource(uri).await?;
+        let content = self.read_resource(uri).await?;
 
         Ok(McpResponse::success(id, serde_json::to_value(content)?))
     }
@@ -442,7 +572,16 @@ impl CisMcpServer {
             .and_then(|s| s.as_str())
             .unwrap_or("stdio_client");
 
-        let subscription_id = self.resources.subscribe(uri, subscriber_id).await?;
+        let subscription_id = if uri.starts_with(MEMORY_URI_PREFIX) {
+            let subscription_id = format!("sub_{}_{}", uri, subscriber_id);
+            self.memory_subscriptions
+                .write()
+                .await
+                .insert(subscription_id.clone(), uri.to_string());
+            subscription_id
+        } else {
+            self.resources.subscribe(uri, subscriber_id).await?
+        };
 
         Ok(McpResponse::success(id, json!({
             "subscriptionId": subscription_id
@@ -463,7 +602,15 @@ impl CisMcpServer {
             .and_then(|s| s.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing subscriptionId"))?;
 
-        self.resources.unsubscribe(subscription_id).await?;
+        let removed = self
+            .memory_subscriptions
+            .write()
+            .await
+            .remove(subscription_id)
+            .is_some();
+        if !removed {
+            self.resources.unsubscribe(subscription_id).await?;
+        }
 
         Ok(McpResponse::success(id, json!({ "success": true })))
     }
@@ -472,7 +619,7 @@ impl CisMcpServer {
         &self,
         id: Option<serde_json::Value>,
     ) -> anyhow::Result<McpResponse> {
-        let prompts = self.prompts.list_prompts();
+        let prompts = self.list_prompts();
         Ok(McpResponse::success(id, json!({ "prompts": prompts })))
     }
 
@@ -490,6 +637,21 @@ impl CisMcpServer {
             .and_then(|n| n.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing name"))?;
 
+        // With arguments, return the rendered GetPromptResult
+        if let Some(args) = params.get("arguments").and_then(|a| a.as_object()) {
+            let args_map: std::collections::HashMap<_, _> =
+                args.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+            let rendered = self.prompts.render_prompt(name, &args_map)?;
+
+            return Ok(McpResponse::success(
+                id,
+                json!({
+                    "description": rendered.description,
+                    "messages": rendered.messages
+                }),
+            ));
+        }
+
         let prompt = self
             .prompts
             .get_prompt(name)
@@ -862,6 +1024,7 @@ impl CisMcpServer {
         };
 
         let entry = self.capability.remember(key, value, scope).await?;
+        self.notify_memory_changed(&entry.key).await;
 
         Ok(format!("Memory stored: {} = {}", entry.key, entry.value))
     }
@@ -878,6 +1041,89 @@ impl CisMcpServer {
         }
     }
 
+    async fn memory_set(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        let key = args["key"].as_str().unwrap_or_default();
+        let value = args["value"].as_str().unwrap_or_default();
+        let scope = parse_memory_scope(args.get("scope").and_then(|s| s.as_str()));
+
+        let entry = self
+            .capability
+            .remember(key, value, scope)
+            .await
+            .map_err(memory_error)?;
+        self.notify_memory_changed(&entry.key).await;
+
+        Ok(format!(
+            "Memory set: {} = {} (scope: {:?})",
+            entry.key, entry.value, entry.scope
+        ))
+    }
+
+    async fn memory_get(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        let key = args["key"].as_str().unwrap_or_default();
+
+        match self.capability.recall(key).await.map_err(memory_error)? {
+            Some(value) => Ok(value),
+            None => Err(anyhow::anyhow!("Memory not found: {}", key)),
+        }
+    }
+
+    async fn memory_delete(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        let key = args["key"].as_str().unwrap_or_default();
+        let all = args.get("all").and_then(|a| a.as_bool()).unwrap_or(false);
+
+        if self.capability.forget(key, all).await.map_err(memory_error)? {
+            self.notify_memory_changed(key).await;
+            Ok(format!("Memory deleted: {}", key))
+        } else {
+            Err(anyhow::anyhow!("Memory not found: {}", key))
+        }
+    }
+
+    async fn memory_search(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        let query = args["query"].as_str().unwrap_or_default();
+        let limit = args.get("limit").and_then(|l| l.as_u64()).unwrap_or(10) as usize;
+
+        let results = self
+            .capability
+            .search_memories(query, limit)
+            .await
+            .map_err(memory_error)?;
+
+        if results.is_empty() {
+            return Ok(format!("No memories match: {}", query));
+        }
+
+        let mut output = format!("Memories matching '{}' ({} found):\n", query, results.len());
+        for r in results {
+            output.push_str(&format!(
+                "\n  [{:.2}] {} = {}",
+                r.score, r.entry.key, r.entry.value
+            ));
+        }
+        Ok(output)
+    }
+
+    async fn memory_list(&self, args: serde_json::Value) -> anyhow::Result<String> {
+        let prefix = args.get("prefix").and_then(|p| p.as_str());
+        let limit = args.get("limit").and_then(|l| l.as_u64()).unwrap_or(50) as usize;
+
+        let entries = self
+            .capability
+            .list_memories(prefix, limit)
+            .await
+            .map_err(memory_error)?;
+
+        let mut output = format!("Memories ({} found):\n", entries.len());
+        for entry in entries {
+            output.push_str(&format!(
+                "\n  {} = {} ({:?})",
+                entry.key, entry.value, entry.scope
+            ));
+        }
+        Ok(output)
+    }
+
     async fn context_extract(&self) -> anyhow::Result<String> {
         let context = self.capability.context.read().await;
         let ctx = context.detect_current().await?;
@@ -889,6 +1135,152 @@ impl CisMcpServer {
     }
 }
 
+/// Memory tool definitions (also used to validate `tools/call` arguments)
+fn memory_tools() -> Vec<Tool> {
+    vec![
+        Tool {
+            name: "memory_set".to_string(),
+            description: "Store a value under a memory key".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "key": { "type": "string", "description": "Memory key" },
+                    "value": { "type": "string", "description": "Memory value" },
+                    "scope": {
+                        "type": "string",
+                        "enum": ["global", "project", "session"],
+                        "default": "project"
+                    }
+                },
+                "required": ["key", "value"]
+            }),
+        },
+        Tool {
+            name: "memory_get".to_string(),
+            description: "Get the value of a memory key".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "key": { "type": "string", "description": "Memory key" }
+                },
+                "required": ["key"]
+            }),
+        },
+        Tool {
+            name: "memory_delete".to_string(),
+            description: "Delete a memory key".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "key": { "type": "string", "description": "Memory key" },
+                    "all": {
+                        "type": "boolean",
+                        "description": "Delete the key in every scope, not just the current project",
+                        "default": false
+                    }
+                },
+                "required": ["key"]
+            }),
+        },
+        Tool {
+            name: "memory_search".to_string(),
+            description: "Search memories by keyword and semantic similarity".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Search query" },
+                    "limit": { "type": "integer", "default": 10 }
+                },
+                "required": ["query"]
+            }),
+        },
+        Tool {
+            name: "memory_list".to_string(),
+            description: "List stored memories".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "prefix": { "type": "string", "description": "Only list keys with this prefix" },
+                    "limit": { "type": "integer", "default": 50 }
+                }
+            }),
+        },
+    ]
+}
+
+/// Validate tool arguments against a (flat) JSON schema
+///
+/// Checks required properties, primitive types and enums.
+fn validate_arguments(schema: &serde_json::Value, args: &serde_json::Value) -> Result<(), String> {
+    let args = args.as_object().ok_or("arguments must be an object")?;
+
+    if let Some(required) = schema["required"].as_array() {
+        for name in required.iter().filter_map(|r| r.as_str()) {
+            if !args.contains_key(name) {
+                return Err(format!("missing required field '{}'", name));
+            }
+        }
+    }
+
+    let properties = match schema["properties"].as_object() {
+        Some(p) => p,
+        None => return Ok(()),
+    };
+
+    for (name, value) in args {
+        let prop = match properties.get(name) {
+            Some(p) => p,
+            None => return Err(format!("unknown field '{}'", name)),
+        };
+
+        let type_ok = match prop["type"].as_str() {
+            Some("string") => value.is_string(),
+            Some("integer") => value.is_u64() || value.is_i64(),
+            Some("number") => value.is_number(),
+            Some("boolean") => value.is_boolean(),
+            Some("object") => value.is_object(),
+            Some("array") => value.is_array(),
+            _ => true,
+        };
+        if !type_ok {
+            return Err(format!(
+                "field '{}' must be of type {}",
+                name,
+                prop["type"].as_str().unwrap_or("unknown")
+            ));
+        }
+
+        if let Some(allowed) = prop["enum"].as_array() {
+            if !allowed.contains(value) {
+                return Err(format!("field '{}' must be one of {}", name, prop["enum"]));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse memory scope, defaulting to project
+fn parse_memory_scope(s: Option<&str>) -> cis_capability::MemoryScope {
+    match s {
+        Some("global") => cis_capability::MemoryScope::Global,
+        Some("session") => cis_capability::MemoryScope::Session,
+        _ => cis_capability::MemoryScope::Project,
+    }
+}
+
+/// Translate capability errors into messages suitable for `isError` results
+fn memory_error(e: CapabilityError) -> anyhow::Error {
+    match e {
+        CapabilityError::MemoryError(msg) => anyhow::anyhow!("Memory operation failed: {}", msg),
+        CapabilityError::Database(err) => anyhow::anyhow!("Memory storage error: {}", err),
+        CapabilityError::ContextError(msg) => {
+            anyhow::anyhow!("Could not detect project context for memory scope: {}", msg)
+        }
+        other => anyhow::anyhow!("{}", other),
+    }
+}
+
 /// Load DAG from file
 async fn load_dag_from_file(path: &str) -> anyhow::Result<cis_core::scheduler::TaskDag> {
     use std::path::Path;
@@ -928,3 +1320,165 @@ fn parse_status(s: &str) -> Option<cis_core::scheduler::TodoItemStatus> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cis_capability::{ContextExtractor, MemoryService, SkillEngine};
+    use tempfile::TempDir;
+
+    /// Minimal MCP client driving the server through JSON-RPC lines
+    struct MockClient {
+        server: CisMcpServer,
+        next_id: u64,
+        _temp: TempDir,
+    }
+
+    impl MockClient {
+        async fn new() -> Self {
+            let temp = TempDir::new().unwrap();
+            let memory = MemoryService::open(temp.path().join("memory.db")).unwrap();
+            let capability =
+                CapabilityLayer::with_paths(SkillEngine::new(), memory, ContextExtractor::new()).await;
+            #[allow(clippy::arc_with_non_send_sync)]
+            let server = CisMcpServer::new(Arc::new(capability));
+            Self { server, next_id: 1, _temp: temp }
+        }
+
+        async fn call_tool(&mut self, name: &str, arguments: serde_json::Value) -> serde_json::Value {
+            let request = json!({
+                "jsonrpc": "2.0",
+                "id": self.next_id,
+                "method": "tools/call",
+                "params": { "name": name, "arguments": arguments }
+            });
+            self.next_id += 1;
+
+            let response = self.server.handle_request(&request.to_string()).await.unwrap();
+            let response = serde_json::to_value(response).unwrap();
+            assert!(response.get("error").is_none(), "unexpected RPC error: {}", response);
+            response["result"].clone()
+        }
+    }
+
+    fn text(result: &serde_json::Value) -> &str {
+        result["content"][0]["text"].as_str().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_memory_tools_roundtrip() {
+        let mut client = MockClient::new().await;
+
+        let result = client
+            .call_tool("memory_set", json!({ "key": "build_cmd", "value": "cargo build", "scope": "global" }))
+            .await;
+        assert_eq!(result["is_error"], false);
+
+        let result = client.call_tool("memory_get", json!({ "key": "build_cmd" })).await;
+        assert_eq!(text(&result), "cargo build");
+
+        let result = client.call_tool("memory_list", json!({ "prefix": "build" })).await;
+        assert!(text(&result).contains("build_cmd = cargo build"));
+
+        let result = client.call_tool("memory_search", json!({ "query": "cargo" })).await;
+        assert!(text(&result).contains("build_cmd"));
+
+        let result = client.call_tool("memory_delete", json!({ "key": "build_cmd", "all": true })).await;
+        assert_eq!(result["is_error"], false);
+
+        let result = client.call_tool("memory_get", json!({ "key": "build_cmd" })).await;
+        assert_eq!(result["is_error"], true);
+        assert!(text(&result).contains("Memory not found"));
+    }
+
+    #[tokio::test]
+    async fn test_memory_tools_validate_arguments() {
+        let mut client = MockClient::new().await;
+
+        let result = client.call_tool("memory_set", json!({ "key": "k" })).await;
+        assert_eq!(result["is_error"], true);
+        assert!(text(&result).contains("missing required field 'value'"));
+
+        let result = client.call_tool("memory_get", json!({ "key": 42 })).await;
+        assert_eq!(result["is_error"], true);
+        assert!(text(&result).contains("must be of type string"));
+
+        let result = client
+            .call_tool("memory_set", json!({ "key": "k", "value": "v", "scope": "team" }))
+            .await;
+        assert_eq!(result["is_error"], true);
+        assert!(text(&result).contains("must be one of"));
+    }
+
+    #[tokio::test]
+    async fn test_memory_resources() {
+        let mut client = MockClient::new().await;
+        client.call_tool("memory_set", json!({ "key": "style", "value": "tabs", "scope": "global" })).await;
+
+        let resources = client.server.list_resources().await.unwrap();
+        let memory: Vec<_> = resources.iter().filter(|r| r.uri.starts_with(MEMORY_URI_PREFIX)).collect();
+        assert_eq!(memory.len(), 1);
+        assert_eq!(memory[0].uri, "cis-memory://style");
+        assert_eq!(memory[0].mime_type, "text/plain");
+
+        let content = client.server.read_resource("cis-memory://style").await.unwrap();
+        assert_eq!(content.text.as_deref(), Some("tabs"));
+        assert!(client.server.read_resource("cis-memory://missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_memory_resource_updated_notification() {
+        let mut client = MockClient::new().await;
+        client.call_tool("memory_set", json!({ "key": "style", "value": "tabs", "scope": "global" })).await;
+
+        // No subscription yet: no notification
+        assert!(client.server.take_notifications().is_empty());
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 99,
+            "method": "resources/subscribe",
+            "params": { "uri": "cis-memory://style" }
+        });
+        client.server.handle_request(&request.to_string()).await.unwrap();
+
+        client.call_tool("memory_set", json!({ "key": "style", "value": "spaces", "scope": "global" })).await;
+        client.call_tool("memory_set", json!({ "key": "other", "value": "x", "scope": "global" })).await;
+
+        let notifications = client.server.take_notifications();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0]["method"], "notifications/resources/updated");
+        assert_eq!(notifications[0]["params"]["uri"], "cis-memory://style");
+    }
+
+    #[tokio::test]
+    async fn test_prompts_get_with_arguments() {
+        let client = MockClient::new().await;
+
+        let names: Vec<_> = client.server.list_prompts().into_iter().map(|p| p.name).collect();
+        assert!(names.contains(&"summarize_memory".to_string()));
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "prompts/get",
+            "params": { "name": "summarize_memory", "arguments": { "key": "style" } }
+        });
+        let response = client.server.handle_request(&request.to_string()).await.unwrap();
+        let result = serde_json::to_value(response).unwrap()["result"].clone();
+        assert_eq!(result["messages"][0]["role"], "user");
+        assert!(result["messages"][0]["content"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("cis-memory://style"));
+    }
+
+    #[test]
+    fn test_memory_error_formatting() {
+        let err = memory_error(CapabilityError::MemoryError("disk full".to_string()));
+        assert_eq!(err.to_string(), "Memory operation failed: disk full");
+
+        let err = memory_error(CapabilityError::ContextError("no cwd".to_string()));
+        assert!(err.to_string().contains("no cwd"));
+    }
+}
@@ -16,6 +16,9 @@ pub enum DagExecutorError {
     #[error("Worker died: {0}")]
     WorkerDied(String),
 
+    #[error("Task timed out: {0}")]
+    TaskTimeout(String),
+
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
@@ -24,6 +27,18 @@ pub enum DagExecutorError {
 
     #[error("Matrix room error: {0}")]
     MatrixRoom(String),
+
+    #[error("Run not found: {0}")]
+    RunNotFound(String),
+
+    #[error("Invalid run state: {0}")]
+    InvalidRunState(String),
+
+    #[error("No pending confirmation: {0}")]
+    ConfirmationNotFound(String),
+
+    #[error("Approver not authorized: {0}")]
+    ApproverNotAuthorized(String),
 }
 
 pub type Result<T> = std::result::Result<T, DagExecutorError>;
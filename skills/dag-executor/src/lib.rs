@@ -12,15 +12,27 @@
 //! - Project: 每 project 独立 worker-project-{id}
 //! - User: 每 user 独立 worker-user-{id}
 //! - Type: 每 type 独立 worker-type-{type}
+//! - Ephemeral: 每次新建 worker-ephemeral-{uuid}，Run 结束或空闲超时后自动销毁
+//!
+//! 确认流程：
+//! - `TaskLevel::Confirmed` / `TaskLevel::Arbitrated` 任务不会直接分发，
+//!   Run 进入 AwaitingConfirmation，确认请求发送到配置的确认 Room
+//! - `confirm_task` 记录审批人后分发该任务及后续任务
+//! - 配置超时后由后台定时器按 `default_action` 处理（与 Recommended 倒计时一致）：
+//!   `Execute` 自动批准，`Skip` 跳过该任务，`Abort` 终止 Run
+//! - 用户暂停（Paused）与等待确认相互独立：暂停期间完成的确认在恢复后才分发
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
 use tracing::{debug, info, warn};
 
-use cis_core::scheduler::DagSpec;
+use cis_core::scheduler::{DagSpec, DagTaskSpec};
+use cis_core::types::{Action, TaskLevel};
 use cis_core::skill::{Event, Skill, SkillConfig, SkillContext};
 use cis_core::matrix::nucleus::{MatrixNucleus, RoomOptions, RoomId};
 use ruma::events::room::message::RoomMessageEventContent;
@@ -30,28 +42,12 @@ pub mod process_lock;
 pub mod worker;
 
 use error::DagExecutorError;
-use worker::WorkerManager;
+use worker::{RunStatus as WorkerRunStatus, WorkerManager};
 
-/// Task 重试配置
-#[derive(Debug, Clone)]
-pub struct RetryConfig {
-    /// 最大重试次数
-    pub max_retries: u32,
-    /// 重试间隔（秒）
-    pub retry_delay_secs: u64,
-    /// 是否启用指数退避
-    pub exponential_backoff: bool,
-}
+pub use worker::TaskResult;
 
-impl Default for RetryConfig {
-    fn default() -> Self {
-        Self {
-            max_retries: 3,
-            retry_delay_secs: 5,
-            exponential_backoff: true,
-        }
-    }
-}
+/// Task 重试配置（DAG 级默认值，可被 `DagTaskSpec::per_task_retry` 覆盖）
+pub use cis_core::scheduler::RetryConfig;
 
 /// DAG 执行器 Skill
 pub struct DagExecutorSkill {
@@ -60,25 +56,46 @@ pub struct DagExecutorSkill {
     /// Worker 管理器
     worker_manager: WorkerManager,
     /// Matrix Nucleus（用于发送 Room 事件）
-    nucleus: Mutex<Option<Arc<MatrixNucleus>>>,
+    nucleus: Arc<Mutex<Option<Arc<MatrixNucleus>>>>,
     /// 节点 ID
     node_id: String,
     /// Worker 二进制路径
     worker_binary: String,
     /// 重试配置
     retry_config: RetryConfig,
+    /// 临时 Worker 清理任务
+    ephemeral_reaper: Mutex<Option<tokio::task::JoinHandle<()>>>,
+    /// 任务确认服务
+    confirmation: Arc<ConfirmationService>,
+    /// 确认超时定时器
+    confirmation_timer: Mutex<Option<tokio::task::JoinHandle<()>>>,
+    /// 用户暂停期间已结算确认的 Run: run_id -> 待分发任务
+    deferred: Arc<Mutex<HashMap<String, ReleasedRun>>>,
+    /// 重复提交去重
+    deduplicator: DagDeduplicator,
 }
 
+/// 临时 Worker 清理检查间隔（秒）
+const EPHEMERAL_REAP_INTERVAL_SECS: u64 = 30;
+
+/// 确认超时检查间隔（秒）
+const CONFIRMATION_CHECK_INTERVAL_SECS: u64 = 1;
+
 impl DagExecutorSkill {
     /// 创建新的 DAG 执行器 Skill
     pub fn new(node_id: String, worker_binary: String) -> Self {
         Self {
             name: "dag-executor".to_string(),
             worker_manager: WorkerManager::new(),
-            nucleus: Mutex::new(None),
+            nucleus: Arc::new(Mutex::new(None)),
             node_id,
             worker_binary,
             retry_config: RetryConfig::default(),
+            ephemeral_reaper: Mutex::new(None),
+            confirmation: Arc::new(ConfirmationService::default()),
+            confirmation_timer: Mutex::new(None),
+            deferred: Arc::new(Mutex::new(HashMap::new())),
+            deduplicator: DagDeduplicator::default(),
         }
     }
     
@@ -91,30 +108,148 @@ impl DagExecutorSkill {
         Self {
             name: "dag-executor".to_string(),
             worker_manager: WorkerManager::new(),
-            nucleus: Mutex::new(None),
+            nucleus: Arc::new(Mutex::new(None)),
             node_id,
             worker_binary,
             retry_config,
+            ephemeral_reaper: Mutex::new(None),
+            confirmation: Arc::new(ConfirmationService::default()),
+            confirmation_timer: Mutex::new(None),
+            deferred: Arc::new(Mutex::new(HashMap::new())),
+            deduplicator: DagDeduplicator::default(),
+        }
+    }
+
+    /// 设置任务确认服务
+    pub fn with_confirmation(mut self, confirmation: ConfirmationService) -> Self {
+        self.confirmation = Arc::new(confirmation);
+        self
+    }
+
+    /// 设置去重窗口
+    pub fn with_dedup_window(mut self, dedup_window: Duration) -> Self {
+        self.deduplicator = DagDeduplicator::new(dedup_window);
+        self
+    }
+
+    /// 提交 DAG（按内容去重）
+    ///
+    /// 窗口内已有相同内容（`DagSpec::content_hash`）的 DAG 在运行时，
+    /// 返回已有的 run_id 而不重复执行。
+    pub async fn submit_dag(&self, spec: DagSpec) -> Result<(String, DedupeStatus), DagExecutorError> {
+        let hash = spec.content_hash();
+        let run_id = format!("dag-run-{}-{}", spec.dag_id, uuid::Uuid::new_v4());
+
+        // 持锁只做检查与预留，执行期间不阻塞其他 DAG 的提交
+        {
+            let mut entries = self.deduplicator.entries.lock().await;
+            self.deduplicator.prune(&mut entries);
+
+            if let Some(entry) = entries.get(&hash) {
+                let still_running = !entry.dispatched
+                    || self
+                        .get_run_status(&entry.run_id)
+                        .await
+                        .is_some_and(|run| {
+                            matches!(run.status.as_str(), "running" | "paused" | "awaiting_confirmation")
+                        });
+                if still_running {
+                    info!("DAG {} already running as {}, skipping duplicate", spec.dag_id, entry.run_id);
+                    return Ok((entry.run_id.clone(), DedupeStatus::AlreadyRunning));
+                }
+            }
+
+            entries.insert(hash.clone(), RunningDagEntry {
+                run_id: run_id.clone(),
+                submitted_at: Instant::now(),
+                dispatched: false,
+            });
+        }
+
+        let result = self.execute_dag(spec, &run_id).await;
+
+        // 只处理本次的预留（可能已过期被清理或被替换）
+        let mut entries = self.deduplicator.entries.lock().await;
+        let reserved = entries.get_mut(&hash).filter(|entry| entry.run_id == run_id);
+        match result {
+            Ok(()) => {
+                if let Some(entry) = reserved {
+                    entry.dispatched = true;
+                }
+                Ok((run_id, DedupeStatus::Started))
+            }
+            Err(e) => {
+                if reserved.is_some() {
+                    entries.remove(&hash);
+                }
+                Err(e)
+            }
         }
     }
 
     /// 执行 DAG
-    async fn execute_dag(&self, spec: DagSpec) -> Result<String, DagExecutorError> {
+    async fn execute_dag(&self, spec: DagSpec, run_id: &str) -> Result<(), DagExecutorError> {
         info!("Executing DAG {} with scope {:?}", spec.dag_id, spec.scope);
 
         let worker_id = spec.worker_id();
-        let run_id = format!("dag-run-{}-{}", spec.dag_id, uuid::Uuid::new_v4());
+        let run_id = run_id.to_string();
 
         // 1. 确保 Worker 存在
         let room_id = self.ensure_worker(&worker_id, &spec.scope).await?;
 
-        // 2. 分发每个 Task 到 Worker
-        for task in &spec.tasks {
-            self.dispatch_task(&worker_id, &room_id, &run_id, task).await?;
-        }
+        // 2. 登记 Run（用于 pause/resume 定位 Worker）
+        self.worker_manager
+            .add_run(run_id.clone(), worker_id.clone(), spec.tasks.len())
+            .await;
+
+        // 3. 分发 Task 到 Worker（遇到需确认的任务时暂停）
+        self.dispatch_tasks(&worker_id, &room_id, &run_id, spec.tasks).await?;
 
         info!("DAG {} dispatched to worker {} (run_id: {})", spec.dag_id, worker_id, run_id);
-        Ok(run_id)
+        Ok(())
+    }
+
+    /// 共享分发状态的句柄（供后台定时器使用）
+    fn dispatcher(&self) -> RunDispatcher {
+        RunDispatcher {
+            worker_manager: self.worker_manager.clone(),
+            nucleus: Arc::clone(&self.nucleus),
+            retry_config: self.retry_config.clone(),
+            confirmation: Arc::clone(&self.confirmation),
+            deferred: Arc::clone(&self.deferred),
+        }
+    }
+
+    /// 依次分发任务，遇到需确认的任务时挂起剩余任务
+    async fn dispatch_tasks(
+        &self,
+        worker_id: &str,
+        room_id: &str,
+        run_id: &str,
+        tasks: Vec<DagTaskSpec>,
+    ) -> Result<(), DagExecutorError> {
+        self.dispatcher().dispatch_tasks(worker_id, room_id, run_id, tasks).await
+    }
+
+    /// 确认任务并恢复执行
+    ///
+    /// `Arbitrated` 任务要求 `approver_id` 在 `stakeholders` 中。
+    /// Run 已被用户暂停时，任务在 `resume_run` 后分发。
+    pub async fn confirm_task(
+        &self,
+        run_id: &str,
+        task_id: &str,
+        approver_id: &str,
+    ) -> Result<(), DagExecutorError> {
+        let pending = self.confirmation.approve(run_id, task_id, approver_id).await?;
+        self.dispatcher().release(pending.into_released(true)).await
+    }
+
+    /// 结算已超时的确认请求，返回被处理的 (run_id, task_id)
+    ///
+    /// `init` 启动的后台定时器会周期调用。
+    pub async fn process_confirmation_timeouts(&self) -> Vec<(String, String)> {
+        self.dispatcher().process_confirmation_timeouts().await
     }
 
     /// 确保 Worker 存在
@@ -123,10 +258,12 @@ impl DagExecutorSkill {
         worker_id: &str,
         scope: &cis_core::scheduler::DagScope,
     ) -> Result<String, DagExecutorError> {
-        // 检查现有 Worker
-        if let Some(room_id) = self.worker_manager.check_and_get_room(worker_id).await {
-            debug!("Reusing existing worker {}", worker_id);
-            return Ok(room_id);
+        // 检查现有 Worker（Ephemeral 等 force_new 作用域不复用）
+        if !scope.force_new_worker() {
+            if let Some(room_id) = self.worker_manager.check_and_get_room(worker_id).await {
+                debug!("Reusing existing worker {}", worker_id);
+                return Ok(room_id);
+            }
         }
 
         // 创建新 Worker
@@ -205,6 +342,268 @@ impl DagExecutorSkill {
         }
     }
 
+    /// 暂停 DAG 运行
+    ///
+    /// 将运行中或等待确认的 Run 切换为 Paused，并向 Worker 发送 `dag.pause` 事件，
+    /// Worker 收到后停止领取新的就绪任务（已在执行的任务会继续完成）。
+    pub async fn pause_run(&self, run_id: &str) -> Result<(), DagExecutorError> {
+        let run = match self
+            .worker_manager
+            .transition_run(run_id, WorkerRunStatus::Running, WorkerRunStatus::Paused)
+            .await
+        {
+            Err(DagExecutorError::InvalidRunState(_)) => {
+                self.worker_manager
+                    .transition_run(run_id, WorkerRunStatus::AwaitingConfirmation, WorkerRunStatus::Paused)
+                    .await?
+            }
+            result => result?,
+        };
+
+        if let Err(e) = self.send_run_control(&run.worker_id, run_id, "dag.pause").await {
+            // 事件未送达，回滚状态
+            self.worker_manager.update_run_status(run_id, run.status).await;
+            return Err(e);
+        }
+
+        info!("DAG run {} paused", run_id);
+        Ok(())
+    }
+
+    /// 恢复用户暂停的 DAG 运行
+    ///
+    /// 向 Worker 发送 `dag.resume` 事件，重新开启任务分发，并分发暂停期间
+    /// 已结算确认的任务。仍有待确认任务时 Run 回到 AwaitingConfirmation；
+    /// 等待确认的 Run 需通过 `confirm_task` 恢复。
+    pub async fn resume_run(&self, run_id: &str) -> Result<(), DagExecutorError> {
+        let run = self
+            .worker_manager
+            .transition_run(run_id, WorkerRunStatus::Paused, WorkerRunStatus::Running)
+            .await?;
+
+        if let Err(e) = self.send_run_control(&run.worker_id, run_id, "dag.resume").await {
+            self.worker_manager
+                .update_run_status(run_id, WorkerRunStatus::Paused)
+                .await;
+            return Err(e);
+        }
+
+        let deferred = self.deferred.lock().await.remove(run_id);
+        if let Some(released) = deferred {
+            self.dispatcher().dispatch_released(released).await?;
+        } else if self.confirmation.is_pending(run_id).await {
+            self.worker_manager
+                .transition_run(run_id, WorkerRunStatus::Running, WorkerRunStatus::AwaitingConfirmation)
+                .await?;
+            info!("DAG run {} resumed, still awaiting confirmation", run_id);
+            return Ok(());
+        }
+
+        info!("DAG run {} resumed", run_id);
+        Ok(())
+    }
+
+    /// 向 Run 所在 Worker 的 Room 发送控制事件
+    async fn send_run_control(
+        &self,
+        worker_id: &str,
+        run_id: &str,
+        event_type: &str,
+    ) -> Result<(), DagExecutorError> {
+        let room_id = self
+            .worker_manager
+            .check_and_get_room(worker_id)
+            .await
+            .unwrap_or_else(|| format!("!worker-{}:{}", worker_id, self.node_id));
+
+        let control_event = serde_json::json!({
+            "type": event_type,
+            "run_id": run_id,
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+        });
+
+        self.dispatcher().send_room_event(&room_id, &control_event).await
+    }
+
+    /// 获取 DAG 运行状态
+    pub async fn get_run_status(&self, run_id: &str) -> Option<RunStatus> {
+        self.worker_manager.get_run_status(run_id).await
+    }
+
+    /// 记录 Worker 上报的任务结果
+    pub async fn record_task_result(
+        &self,
+        run_id: &str,
+        task_id: &str,
+        result: TaskResult,
+    ) -> Result<(), DagExecutorError> {
+        self.worker_manager.record_task_result(run_id, task_id, result).await?;
+        Ok(())
+    }
+
+    /// 获取 Run 中已上报的任务结果
+    pub async fn task_results(&self, run_id: &str) -> HashMap<String, TaskResult> {
+        self.worker_manager
+            .get_run(run_id)
+            .await
+            .map(|info| info.task_results)
+            .unwrap_or_default()
+    }
+}
+
+/// Run 分发状态
+///
+/// 与 `DagExecutorSkill` 共享内部状态，可克隆到后台定时器中使用。
+#[derive(Clone)]
+struct RunDispatcher {
+    worker_manager: WorkerManager,
+    nucleus: Arc<Mutex<Option<Arc<MatrixNucleus>>>>,
+    retry_config: RetryConfig,
+    confirmation: Arc<ConfirmationService>,
+    deferred: Arc<Mutex<HashMap<String, ReleasedRun>>>,
+}
+
+impl RunDispatcher {
+    /// 依次分发任务，遇到需确认的任务时挂起剩余任务
+    async fn dispatch_tasks(
+        &self,
+        worker_id: &str,
+        room_id: &str,
+        run_id: &str,
+        tasks: Vec<DagTaskSpec>,
+    ) -> Result<(), DagExecutorError> {
+        let mut tasks = tasks.into_iter();
+        while let Some(task) = tasks.next() {
+            if ConfirmationService::requires_confirmation(&task) {
+                return self
+                    .hold_for_confirmation(PendingConfirmation {
+                        run_id: run_id.to_string(),
+                        worker_id: worker_id.to_string(),
+                        room_id: room_id.to_string(),
+                        task,
+                        remaining: tasks.collect(),
+                        requested_at: Instant::now(),
+                    })
+                    .await;
+            }
+            self.dispatch_task(worker_id, room_id, run_id, &task).await?;
+        }
+        Ok(())
+    }
+
+    /// 挂起待确认任务并发送确认请求
+    ///
+    /// 运行中的 Run 进入 AwaitingConfirmation；已被用户暂停的 Run 保持 Paused，
+    /// 恢复后再进入等待确认。
+    async fn hold_for_confirmation(&self, pending: PendingConfirmation) -> Result<(), DagExecutorError> {
+        let run_id = pending.run_id.clone();
+        let request = self.confirmation.request_event(&pending);
+
+        let awaiting = self
+            .worker_manager
+            .transition_run(&run_id, WorkerRunStatus::Running, WorkerRunStatus::AwaitingConfirmation)
+            .await
+            .is_ok();
+        if awaiting {
+            info!("DAG run {} awaiting confirmation of task {}", run_id, pending.task.id);
+        } else {
+            info!("Task {} of run {} held for confirmation", pending.task.id, run_id);
+        }
+        self.confirmation.hold(pending).await;
+
+        match self.confirmation.room_id() {
+            Some(room_id) => self.send_room_event(room_id, &request).await,
+            None => {
+                warn!("No confirmation room configured, request logged only: {}", request);
+                Ok(())
+            }
+        }
+    }
+
+    /// 结算已超时的确认请求，返回被处理的 (run_id, task_id)
+    ///
+    /// 按配置的 `default_action` 处理：`Execute` 自动批准；`Skip` 跳过该任务
+    /// （记为成功结果）并继续分发后续任务；`Abort` 将 Run 标记为失败。
+    async fn process_confirmation_timeouts(&self) -> Vec<(String, String)> {
+        let mut resolved = Vec::new();
+
+        for (pending, action) in self.confirmation.take_expired().await {
+            let (run_id, task_id) = (pending.run_id.clone(), pending.task.id.clone());
+            match action {
+                Action::Execute => {
+                    if let Err(e) = self.release(pending.into_released(true)).await {
+                        warn!("Failed to resume run {} after confirmation timeout: {}", run_id, e);
+                    }
+                }
+                Action::Skip => {
+                    warn!("Task {} in run {} skipped by confirmation timeout", task_id, run_id);
+                    if let Err(e) = self.release(pending.into_released(false)).await {
+                        warn!("Failed to resume run {} after confirmation timeout: {}", run_id, e);
+                    }
+                    let skipped = TaskResult {
+                        success: true,
+                        output: "skipped: confirmation timeout".to_string(),
+                    };
+                    if let Err(e) = self.worker_manager.record_task_result(&run_id, &task_id, skipped).await {
+                        warn!("Failed to record skipped task {}/{}: {}", run_id, task_id, e);
+                    }
+                }
+                Action::Abort => {
+                    warn!("Task {} in run {} rejected by confirmation timeout", task_id, run_id);
+                    self.worker_manager
+                        .update_run_status(&run_id, WorkerRunStatus::Failed)
+                        .await;
+                }
+            }
+            resolved.push((run_id, task_id));
+        }
+
+        resolved
+    }
+
+    /// 启动确认超时定时器
+    fn spawn_confirmation_timer(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let dispatcher = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                for (run_id, task_id) in dispatcher.process_confirmation_timeouts().await {
+                    info!("Confirmation timeout resolved for {}/{}", run_id, task_id);
+                }
+            }
+        })
+    }
+
+    /// 确认结算后恢复 Run
+    ///
+    /// Run 已被用户暂停时只登记，待 `resume_run` 时分发。
+    async fn release(&self, released: ReleasedRun) -> Result<(), DagExecutorError> {
+        let run_id = released.run_id.clone();
+        let status = self.worker_manager.get_run(&run_id).await.map(|run| run.status);
+        if status == Some(WorkerRunStatus::Paused) {
+            info!("DAG run {} is paused, dispatch deferred until resume", run_id);
+            self.deferred.lock().await.insert(run_id, released);
+            return Ok(());
+        }
+
+        self.worker_manager
+            .transition_run(&run_id, WorkerRunStatus::AwaitingConfirmation, WorkerRunStatus::Running)
+            .await?;
+        info!("DAG run {} resumed after confirmation", run_id);
+        self.dispatch_released(released).await
+    }
+
+    /// 分发确认结算后的任务：已批准的任务及其后的任务
+    async fn dispatch_released(&self, released: ReleasedRun) -> Result<(), DagExecutorError> {
+        let ReleasedRun { run_id, worker_id, room_id, approved, remaining } = released;
+
+        if let Some(task) = approved {
+            self.dispatch_task(&worker_id, &room_id, &run_id, &task).await?;
+        }
+        self.dispatch_tasks(&worker_id, &room_id, &run_id, remaining).await
+    }
+
     /// 分发 Task 到 Worker（带重试）
     async fn dispatch_task(
         &self,
@@ -214,7 +613,9 @@ impl DagExecutorSkill {
         task: &cis_core::scheduler::DagTaskSpec,
     ) -> Result<(), DagExecutorError> {
         let mut last_error = None;
-        let max_retries = self.retry_config.max_retries;
+        // Task 级配置优先，否则使用 DAG 级默认值
+        let retry_config = task.retry_config(&self.retry_config);
+        let max_retries = retry_config.max_retries;
         
         for attempt in 0..=max_retries {
             match self.try_dispatch_task(worker_id, room_id, run_id, task).await {
@@ -227,11 +628,7 @@ impl DagExecutorSkill {
                 Err(e) => {
                     last_error = Some(e);
                     if attempt < max_retries {
-                        let delay = if self.retry_config.exponential_backoff {
-                            self.retry_config.retry_delay_secs * (2_u64.pow(attempt))
-                        } else {
-                            self.retry_config.retry_delay_secs
-                        };
+                        let delay = retry_config.delay_for_attempt(attempt);
                         warn!(
                             "Task {} dispatch failed (attempt {}/{}), retrying in {}s...",
                             task.id,
@@ -268,6 +665,10 @@ impl DagExecutorSkill {
                 "command": task.command,
                 "depends_on": task.depends_on,
                 "env": task.env,
+                "retry": task.per_task_retry,
+                "timeout_secs": task.timeout_secs,
+                "level": task.level,
+                "fail_fast": task.fail_fast,
             },
             "timestamp": chrono::Utc::now().to_rfc3339(),
         });
@@ -278,6 +679,22 @@ impl DagExecutorSkill {
         );
 
         // 通过 Matrix Room 发送事件
+        self.send_room_event(room_id, &task_event).await?;
+
+        // 更新 Worker 活跃任务计数
+        self.worker_manager.increment_tasks(worker_id).await;
+
+        Ok(())
+    }
+
+    /// 通过 Matrix Room 向 Worker 发送事件
+    async fn send_room_event(
+        &self,
+        room_id: &str,
+        event: &serde_json::Value,
+    ) -> Result<(), DagExecutorError> {
+        let event_type = event["type"].as_str().unwrap_or("unknown");
+
         let nucleus_guard = self.nucleus.lock().await;
         if let Some(nucleus) = nucleus_guard.as_ref() {
             // 解析 Room ID
@@ -285,15 +702,15 @@ impl DagExecutorSkill {
                 .map_err(|e| DagExecutorError::MatrixRoom(format!("Invalid room ID: {}", e)))?;
             
             // 创建 RoomMessageEventContent
-            let content = RoomMessageEventContent::text_plain(task_event.to_string());
+            let content = RoomMessageEventContent::text_plain(event.to_string());
             
             // 发送事件
             match nucleus.send_event(&room_id_parsed, content).await {
                 Ok(event_id) => {
-                    info!("Task {} -> room {} (event_id: {})", task.id, room_id, event_id);
+                    info!("{} -> room {} (event_id: {})", event_type, room_id, event_id);
                 }
                 Err(e) => {
-                    warn!("Failed to send task {} to room {}: {}", task.id, room_id, e);
+                    warn!("Failed to send {} to room {}: {}", event_type, room_id, e);
                     return Err(DagExecutorError::MatrixRoom(format!(
                         "Failed to send event: {}", e
                     )));
@@ -301,20 +718,12 @@ impl DagExecutorSkill {
             }
         } else {
             // Nucleus 未初始化，仅记录日志（用于测试场景）
-            info!("Task {} -> room {} (content: {}) - Nucleus not available, logged only", 
-                task.id, room_id, task_event);
+            info!("{} -> room {} (content: {}) - Nucleus not available, logged only", 
+                event_type, room_id, event);
         }
 
-        // 更新 Worker 活跃任务计数
-        self.worker_manager.increment_tasks(worker_id).await;
-
         Ok(())
     }
-
-    /// 获取 DAG 运行状态
-    async fn get_run_status(&self, run_id: &str) -> Option<RunStatus> {
-        self.worker_manager.get_run_status(run_id).await
-    }
 }
 
 #[async_trait]
@@ -336,6 +745,18 @@ impl Skill for DagExecutorSkill {
     }
 
     async fn init(&mut self, _config: SkillConfig) -> cis_core::error::Result<()> {
+        let reaper = self.worker_manager.spawn_ephemeral_reaper(
+            std::time::Duration::from_secs(EPHEMERAL_REAP_INTERVAL_SECS),
+        );
+        *self.ephemeral_reaper.get_mut() = Some(reaper);
+
+        if self.confirmation.timeout().is_some() {
+            let timer = self.dispatcher().spawn_confirmation_timer(
+                std::time::Duration::from_secs(CONFIRMATION_CHECK_INTERVAL_SECS),
+            );
+            *self.confirmation_timer.get_mut() = Some(timer);
+        }
+
         info!("DAG Executor Skill initialized");
         Ok(())
     }
@@ -360,10 +781,13 @@ impl Skill for DagExecutorSkill {
                             .map_err(|e| cis_core::error::CisError::skill(format!("Invalid DAG spec: {}", e)))?;
 
                         // 执行 DAG
-                        match self.execute_dag(spec).await {
-                            Ok(run_id) => {
+                        match self.submit_dag(spec).await {
+                            Ok((run_id, DedupeStatus::Started)) => {
                                 ctx.log_info(&format!("DAG executed, run_id: {}", run_id));
                             }
+                            Ok((run_id, DedupeStatus::AlreadyRunning)) => {
+                                ctx.log_info(&format!("DAG already running, run_id: {}", run_id));
+                            }
                             Err(e) => {
                                 ctx.log_error(&format!("DAG execution failed: {}", e));
                                 return Err(cis_core::error::CisError::skill(e.to_string()));
@@ -377,10 +801,13 @@ impl Skill for DagExecutorSkill {
                             let spec: DagSpec = serde_json::from_value(dag_spec.clone())
                                 .map_err(|e| cis_core::error::CisError::skill(format!("Invalid DAG spec: {}", e)))?;
 
-                            match self.execute_dag(spec).await {
-                                Ok(run_id) => {
+                            match self.submit_dag(spec).await {
+                                Ok((run_id, DedupeStatus::Started)) => {
                                     ctx.log_info(&format!("HTTP triggered DAG executed, run_id: {}", run_id));
                                 }
+                                Ok((run_id, DedupeStatus::AlreadyRunning)) => {
+                                    ctx.log_info(&format!("HTTP triggered DAG already running, run_id: {}", run_id));
+                                }
                                 Err(e) => {
                                     ctx.log_error(&format!("HTTP triggered DAG execution failed: {}", e));
                                     return Err(cis_core::error::CisError::skill(e.to_string()));
@@ -388,6 +815,52 @@ impl Skill for DagExecutorSkill {
                             }
                         }
                     }
+                    "dag:pause" | "dag:resume" => {
+                        let run_id = data.get("run_id").and_then(|v| v.as_str())
+                            .ok_or_else(|| cis_core::error::CisError::skill("Missing run_id"))?;
+
+                        let result = if name == "dag:pause" {
+                            self.pause_run(run_id).await
+                        } else {
+                            self.resume_run(run_id).await
+                        };
+
+                        if let Err(e) = result {
+                            ctx.log_error(&format!("{} failed for {}: {}", name, run_id, e));
+                            return Err(cis_core::error::CisError::skill(e.to_string()));
+                        }
+                        ctx.log_info(&format!("{} applied to {}", name, run_id));
+                    }
+                    "dag:confirm" => {
+                        let field = |key: &str| {
+                            data.get(key).and_then(|v| v.as_str()).ok_or_else(|| {
+                                cis_core::error::CisError::skill(format!("Missing {}", key))
+                            })
+                        };
+                        let (run_id, task_id, approver) = (field("run_id")?, field("task_id")?, field("approver_id")?);
+
+                        if let Err(e) = self.confirm_task(run_id, task_id, approver).await {
+                            ctx.log_error(&format!("Confirmation of {}/{} failed: {}", run_id, task_id, e));
+                            return Err(cis_core::error::CisError::skill(e.to_string()));
+                        }
+                        ctx.log_info(&format!("Task {}/{} confirmed by {}", run_id, task_id, approver));
+                    }
+                    "dag:task_result" => {
+                        // data 格式: { "run_id", "task_id", "success", "output" }
+                        let field = |key: &str| {
+                            data.get(key).and_then(|v| v.as_str()).ok_or_else(|| {
+                                cis_core::error::CisError::skill(format!("Missing {}", key))
+                            })
+                        };
+                        let (run_id, task_id) = (field("run_id")?, field("task_id")?);
+                        let result: TaskResult = serde_json::from_value(data.clone())
+                            .map_err(|e| cis_core::error::CisError::skill(format!("Invalid task result: {}", e)))?;
+
+                        if let Err(e) = self.record_task_result(run_id, task_id, result).await {
+                            ctx.log_error(&format!("Recording result of {}/{} failed: {}", run_id, task_id, e));
+                            return Err(cis_core::error::CisError::skill(e.to_string()));
+                        }
+                    }
                     "dag:status" => {
                         // 查询 DAG 状态
                         if let Some(run_id) = data.get("run_id").and_then(|v| v.as_str()) {
@@ -405,11 +878,293 @@ impl Skill for DagExecutorSkill {
 
     async fn shutdown(&self) -> cis_core::error::Result<()> {
         info!("Shutting down DAG Executor Skill");
+        if let Some(reaper) = self.ephemeral_reaper.lock().await.take() {
+            reaper.abort();
+        }
+        if let Some(timer) = self.confirmation_timer.lock().await.take() {
+            timer.abort();
+        }
         self.worker_manager.stop_all().await;
         Ok(())
     }
 }
 
+/// 默认去重窗口（秒）
+const DEFAULT_DEDUP_WINDOW_SECS: u64 = 300;
+
+/// DAG 提交结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupeStatus {
+    /// 新启动的 Run
+    Started,
+    /// 相同内容的 DAG 已在运行，返回已有 run_id
+    AlreadyRunning,
+}
+
+/// 去重表中的运行记录
+#[derive(Debug, Clone)]
+pub struct RunningDagEntry {
+    pub run_id: String,
+    pub submitted_at: Instant,
+    /// 为 false 时表示 Run 已预留、仍在启动中
+    pub dispatched: bool,
+}
+
+/// DAG 内容去重器
+///
+/// 以 `DagSpec::content_hash` 为键记录运行中的 DAG，超过 `dedup_window`
+/// 的记录失效，相同 DAG 可再次执行。
+pub struct DagDeduplicator {
+    entries: Mutex<HashMap<String, RunningDagEntry>>,
+    dedup_window: Duration,
+}
+
+impl DagDeduplicator {
+    pub fn new(dedup_window: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            dedup_window,
+        }
+    }
+
+    /// 去重窗口
+    pub fn dedup_window(&self) -> Duration {
+        self.dedup_window
+    }
+
+    /// 查询窗口内的记录
+    pub async fn get(&self, content_hash: &str) -> Option<RunningDagEntry> {
+        let mut entries = self.entries.lock().await;
+        self.prune(&mut entries);
+        entries.get(content_hash).cloned()
+    }
+
+    /// 移除过期记录
+    fn prune(&self, entries: &mut HashMap<String, RunningDagEntry>) {
+        entries.retain(|_, entry| entry.submitted_at.elapsed() < self.dedup_window);
+    }
+}
+
+impl Default for DagDeduplicator {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_DEDUP_WINDOW_SECS))
+    }
+}
+
+/// 确认超时配置
+///
+/// 与 `TaskLevel::Recommended` 倒计时一致：到期后 `Execute` 视为批准，
+/// `Skip` 跳过该任务，`Abort` 终止 Run。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmationTimeout {
+    pub timeout_secs: u16,
+    pub default_action: Action,
+}
+
+/// 等待确认的任务（及其后尚未分发的任务）
+#[derive(Debug, Clone)]
+pub struct PendingConfirmation {
+    pub run_id: String,
+    pub worker_id: String,
+    /// Worker Room
+    pub room_id: String,
+    pub task: DagTaskSpec,
+    pub remaining: Vec<DagTaskSpec>,
+    pub requested_at: Instant,
+}
+
+impl PendingConfirmation {
+    /// 结算确认，`approved` 为 false 时跳过该任务
+    fn into_released(self, approved: bool) -> ReleasedRun {
+        ReleasedRun {
+            run_id: self.run_id,
+            worker_id: self.worker_id,
+            room_id: self.room_id,
+            approved: approved.then_some(self.task),
+            remaining: self.remaining,
+        }
+    }
+}
+
+/// 确认结算后待分发的任务
+#[derive(Debug, Clone)]
+struct ReleasedRun {
+    run_id: String,
+    worker_id: String,
+    room_id: String,
+    /// 已批准的任务（跳过时为空）
+    approved: Option<DagTaskSpec>,
+    remaining: Vec<DagTaskSpec>,
+}
+
+/// 确认记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfirmationRecord {
+    pub run_id: String,
+    pub task_id: String,
+    /// 审批人 ID（超时自动处理时为 `timeout`）
+    pub approver_id: String,
+    pub approved: bool,
+    pub decided_at: String,
+}
+
+/// 任务确认服务
+///
+/// 每个 Run 同一时间最多有一个待确认任务：分发在该任务处停止，
+/// 确认后继续分发剩余任务。
+#[derive(Default)]
+pub struct ConfirmationService {
+    /// 确认请求发送到的 Room
+    room_id: Option<String>,
+    /// 超时自动处理
+    timeout: Option<ConfirmationTimeout>,
+    /// run_id -> 待确认任务
+    pending: Mutex<HashMap<String, PendingConfirmation>>,
+    /// 审批记录
+    records: Mutex<Vec<ConfirmationRecord>>,
+}
+
+impl ConfirmationService {
+    /// 创建确认服务，请求发送到 `room_id`
+    pub fn new(room_id: impl Into<String>) -> Self {
+        Self {
+            room_id: Some(room_id.into()),
+            ..Default::default()
+        }
+    }
+
+    /// 设置超时自动处理
+    pub fn with_timeout(mut self, timeout_secs: u16, default_action: Action) -> Self {
+        self.timeout = Some(ConfirmationTimeout { timeout_secs, default_action });
+        self
+    }
+
+    /// 确认 Room
+    pub fn room_id(&self) -> Option<&str> {
+        self.room_id.as_deref()
+    }
+
+    /// 超时自动处理配置
+    pub fn timeout(&self) -> Option<ConfirmationTimeout> {
+        self.timeout
+    }
+
+    /// 任务是否需要人工确认
+    pub fn requires_confirmation(task: &DagTaskSpec) -> bool {
+        matches!(
+            task.level,
+            Some(TaskLevel::Confirmed) | Some(TaskLevel::Arbitrated { .. })
+        )
+    }
+
+    /// 构建确认请求事件
+    pub fn request_event(&self, pending: &PendingConfirmation) -> serde_json::Value {
+        let stakeholders = match &pending.task.level {
+            Some(TaskLevel::Arbitrated { stakeholders }) => stakeholders.clone(),
+            _ => Vec::new(),
+        };
+
+        serde_json::json!({
+            "type": "dag.confirmation_request",
+            "run_id": pending.run_id,
+            "task_id": pending.task.id,
+            "command": pending.task.command,
+            "stakeholders": stakeholders,
+            "timeout_secs": self.timeout.map(|t| t.timeout_secs),
+            "default_action": self.timeout.map(|t| format!("{:?}", t.default_action)),
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// 登记待确认任务
+    pub async fn hold(&self, pending: PendingConfirmation) {
+        self.pending.lock().await.insert(pending.run_id.clone(), pending);
+    }
+
+    /// 批准待确认任务，返回需恢复的分发状态
+    pub async fn approve(
+        &self,
+        run_id: &str,
+        task_id: &str,
+        approver_id: &str,
+    ) -> Result<PendingConfirmation, DagExecutorError> {
+        let mut pending = self.pending.lock().await;
+
+        let entry = pending
+            .get(run_id)
+            .filter(|p| p.task.id == task_id)
+            .ok_or_else(|| DagExecutorError::ConfirmationNotFound(format!("{}/{}", run_id, task_id)))?;
+
+        if let Some(TaskLevel::Arbitrated { stakeholders }) = &entry.task.level {
+            if !stakeholders.iter().any(|s| s == approver_id) {
+                warn!("{} is not a stakeholder of task {}/{}", approver_id, run_id, task_id);
+                return Err(DagExecutorError::ApproverNotAuthorized(format!(
+                    "{} is not a stakeholder of task {}",
+                    approver_id, task_id
+                )));
+            }
+        }
+
+        let entry = pending.remove(run_id).expect("checked above");
+        drop(pending);
+
+        self.record(run_id, task_id, approver_id, true).await;
+        Ok(entry)
+    }
+
+    /// 取出已超时的待确认任务，附带超时处理动作
+    pub async fn take_expired(&self) -> Vec<(PendingConfirmation, Action)> {
+        let Some(timeout) = self.timeout else {
+            return Vec::new();
+        };
+        let limit = Duration::from_secs(timeout.timeout_secs as u64);
+        let approved = timeout.default_action == Action::Execute;
+
+        let expired: Vec<PendingConfirmation> = {
+            let mut pending = self.pending.lock().await;
+            let run_ids: Vec<String> = pending
+                .values()
+                .filter(|p| p.requested_at.elapsed() >= limit)
+                .map(|p| p.run_id.clone())
+                .collect();
+            run_ids.iter().filter_map(|id| pending.remove(id)).collect()
+        };
+
+        let mut result = Vec::with_capacity(expired.len());
+        for pending in expired {
+            self.record(&pending.run_id, &pending.task.id, "timeout", approved).await;
+            result.push((pending, timeout.default_action));
+        }
+        result
+    }
+
+    /// 待确认任务数量
+    pub async fn pending_count(&self) -> usize {
+        self.pending.lock().await.len()
+    }
+
+    /// Run 是否有待确认任务
+    pub async fn is_pending(&self, run_id: &str) -> bool {
+        self.pending.lock().await.contains_key(run_id)
+    }
+
+    /// 审批记录
+    pub async fn records(&self) -> Vec<ConfirmationRecord> {
+        self.records.lock().await.clone()
+    }
+
+    async fn record(&self, run_id: &str, task_id: &str, approver_id: &str, approved: bool) {
+        self.records.lock().await.push(ConfirmationRecord {
+            run_id: run_id.to_string(),
+            task_id: task_id.to_string(),
+            approver_id: approver_id.to_string(),
+            approved,
+            decided_at: chrono::Utc::now().to_rfc3339(),
+        });
+    }
+}
+
 /// DAG 执行事件（用于 Matrix Room 接收）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DagExecuteEvent {
@@ -447,4 +1202,297 @@ mod tests {
         assert_eq!(skill.name(), "dag-executor");
         assert_eq!(skill.version(), "0.1.0");
     }
+
+    #[test]
+    fn test_task_retry_override() {
+        let dag_default = RetryConfig::default();
+        let mut task: cis_core::scheduler::DagTaskSpec = serde_json::from_value(serde_json::json!({
+            "id": "t1",
+            "type": "shell",
+            "command": "echo hi",
+        }))
+        .unwrap();
+        assert_eq!(task.retry_config(&dag_default), &dag_default);
+        assert_eq!(task.timeout_secs, None);
+
+        task.per_task_retry = Some(RetryConfig {
+            max_retries: 0,
+            retry_delay_secs: 1,
+            exponential_backoff: false,
+        });
+        assert_eq!(task.retry_config(&dag_default).max_retries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume_run() {
+        let skill = DagExecutorSkill::new(
+            "test-node".to_string(),
+            "/usr/local/bin/cis-node".to_string(),
+        );
+        skill
+            .worker_manager
+            .add_run("run-1".to_string(), "worker-global".to_string(), 3)
+            .await;
+
+        skill.pause_run("run-1").await.unwrap();
+        assert_eq!(skill.get_run_status("run-1").await.unwrap().status, "paused");
+
+        // 重复暂停应失败
+        assert!(matches!(
+            skill.pause_run("run-1").await,
+            Err(DagExecutorError::InvalidRunState(_))
+        ));
+
+        skill.resume_run("run-1").await.unwrap();
+        assert_eq!(skill.get_run_status("run-1").await.unwrap().status, "running");
+
+        assert!(matches!(
+            skill.resume_run("run-1").await,
+            Err(DagExecutorError::InvalidRunState(_))
+        ));
+    }
+
+    fn leveled_task(id: &str, level: Option<TaskLevel>) -> DagTaskSpec {
+        let mut task: DagTaskSpec = serde_json::from_value(serde_json::json!({
+            "id": id,
+            "type": "shell",
+            "command": "echo hi",
+        }))
+        .unwrap();
+        task.level = level;
+        task
+    }
+
+    async fn run_with_tasks(skill: &DagExecutorSkill, tasks: Vec<DagTaskSpec>) {
+        skill
+            .worker_manager
+            .add_run("run-1".to_string(), "worker-global".to_string(), tasks.len())
+            .await;
+        skill
+            .dispatch_tasks("worker-global", "!worker-global:test-node", "run-1", tasks)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_confirmed_task_pauses_run() {
+        let skill = DagExecutorSkill::new("test-node".to_string(), "cis-node".to_string())
+            .with_confirmation(ConfirmationService::new("!confirm:test-node"));
+
+        run_with_tasks(&skill, vec![
+            leveled_task("t1", None),
+            leveled_task("t2", Some(TaskLevel::Confirmed)),
+            leveled_task("t3", None),
+        ])
+        .await;
+        assert_eq!(skill.get_run_status("run-1").await.unwrap().status, "awaiting_confirmation");
+        assert_eq!(skill.confirmation.pending_count().await, 1);
+
+        // 等待确认的 Run 不能通过 resume 恢复
+        assert!(matches!(
+            skill.resume_run("run-1").await,
+            Err(DagExecutorError::InvalidRunState(_))
+        ));
+
+        // 任务 ID 不匹配
+        assert!(matches!(
+            skill.confirm_task("run-1", "t3", "alice").await,
+            Err(DagExecutorError::ConfirmationNotFound(_))
+        ));
+
+        skill.confirm_task("run-1", "t2", "alice").await.unwrap();
+        assert_eq!(skill.get_run_status("run-1").await.unwrap().status, "running");
+        assert_eq!(skill.confirmation.pending_count().await, 0);
+
+        let records = skill.confirmation.records().await;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].approver_id, "alice");
+        assert!(records[0].approved);
+    }
+
+    #[tokio::test]
+    async fn test_arbitrated_requires_stakeholder() {
+        let skill = DagExecutorSkill::new("test-node".to_string(), "cis-node".to_string());
+
+        run_with_tasks(&skill, vec![leveled_task(
+            "t1",
+            Some(TaskLevel::Arbitrated { stakeholders: vec!["did:cis:admin:01".to_string()] }),
+        )])
+        .await;
+
+        assert!(matches!(
+            skill.confirm_task("run-1", "t1", "did:cis:other:02").await,
+            Err(DagExecutorError::ApproverNotAuthorized(_))
+        ));
+        skill.confirm_task("run-1", "t1", "did:cis:admin:01").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_timeout() {
+        let skill = DagExecutorSkill::new("test-node".to_string(), "cis-node".to_string())
+            .with_confirmation(ConfirmationService::default().with_timeout(0, Action::Abort));
+
+        run_with_tasks(&skill, vec![leveled_task("t1", Some(TaskLevel::Confirmed))]).await;
+
+        let resolved = skill.process_confirmation_timeouts().await;
+        assert_eq!(resolved, vec![("run-1".to_string(), "t1".to_string())]);
+        assert_eq!(skill.get_run_status("run-1").await.unwrap().status, "failed");
+        assert!(!skill.confirmation.records().await[0].approved);
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_timeout_skip_skips_only_that_task() {
+        let skill = DagExecutorSkill::new("test-node".to_string(), "cis-node".to_string())
+            .with_confirmation(ConfirmationService::default().with_timeout(0, Action::Skip));
+
+        run_with_tasks(&skill, vec![
+            leveled_task("t1", Some(TaskLevel::Confirmed)),
+            leveled_task("t2", None),
+        ])
+        .await;
+
+        skill.process_confirmation_timeouts().await;
+        let status = skill.get_run_status("run-1").await.unwrap();
+        assert_eq!(status.status, "running");
+        assert_eq!(status.completed_count, 1);
+        assert_eq!(status.failed_count, 0);
+        assert!(skill.task_results("run-1").await["t1"].success);
+
+        // 后续任务正常上报后 Run 完成
+        let ok = TaskResult { success: true, output: String::new() };
+        skill.record_task_result("run-1", "t2", ok).await.unwrap();
+        assert_eq!(skill.get_run_status("run-1").await.unwrap().status, "completed");
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_timer_resolves_timeouts() {
+        let skill = DagExecutorSkill::new("test-node".to_string(), "cis-node".to_string())
+            .with_confirmation(ConfirmationService::default().with_timeout(0, Action::Abort));
+        run_with_tasks(&skill, vec![leveled_task("t1", Some(TaskLevel::Confirmed))]).await;
+
+        let timer = skill.dispatcher().spawn_confirmation_timer(Duration::from_millis(10));
+        for _ in 0..100 {
+            if skill.get_run_status("run-1").await.unwrap().status == "failed" {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        timer.abort();
+        assert_eq!(skill.get_run_status("run-1").await.unwrap().status, "failed");
+    }
+
+    #[tokio::test]
+    async fn test_user_pause_is_kept_across_confirmation() {
+        let skill = DagExecutorSkill::new("test-node".to_string(), "cis-node".to_string());
+        run_with_tasks(&skill, vec![
+            leveled_task("t1", Some(TaskLevel::Confirmed)),
+            leveled_task("t2", None),
+        ])
+        .await;
+
+        skill.pause_run("run-1").await.unwrap();
+        assert_eq!(skill.get_run_status("run-1").await.unwrap().status, "paused");
+
+        // 暂停期间确认不会恢复 Run
+        skill.confirm_task("run-1", "t1", "alice").await.unwrap();
+        assert_eq!(skill.get_run_status("run-1").await.unwrap().status, "paused");
+        assert!(skill.deferred.lock().await.contains_key("run-1"));
+
+        // 恢复时分发已确认的任务
+        skill.resume_run("run-1").await.unwrap();
+        assert_eq!(skill.get_run_status("run-1").await.unwrap().status, "running");
+        assert!(skill.deferred.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resume_returns_to_awaiting_confirmation() {
+        let skill = DagExecutorSkill::new("test-node".to_string(), "cis-node".to_string());
+        run_with_tasks(&skill, vec![leveled_task("t1", Some(TaskLevel::Confirmed))]).await;
+
+        skill.pause_run("run-1").await.unwrap();
+        skill.resume_run("run-1").await.unwrap();
+        assert_eq!(skill.get_run_status("run-1").await.unwrap().status, "awaiting_confirmation");
+
+        skill.confirm_task("run-1", "t1", "alice").await.unwrap();
+        assert_eq!(skill.get_run_status("run-1").await.unwrap().status, "running");
+    }
+
+    #[tokio::test]
+    async fn test_submit_dag_deduplicates() {
+        let skill = DagExecutorSkill::new("test-node".to_string(), "cis-node".to_string());
+        let spec = DagSpec::new("dedup".to_string(), vec![leveled_task("t1", None)]);
+        let hash = spec.content_hash();
+
+        // 预置一个运行中的 Run，避免启动真实 Worker
+        skill
+            .worker_manager
+            .add_run("run-1".to_string(), "worker-global".to_string(), 1)
+            .await;
+        skill.deduplicator.entries.lock().await.insert(hash.clone(), RunningDagEntry {
+            run_id: "run-1".to_string(),
+            submitted_at: Instant::now(),
+            dispatched: true,
+        });
+
+        let (run_id, status) = skill.submit_dag(spec).await.unwrap();
+        assert_eq!(run_id, "run-1");
+        assert_eq!(status, DedupeStatus::AlreadyRunning);
+        assert!(skill.deduplicator.get(&hash).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_submit_dag_reserved_run_is_deduplicated() {
+        let skill = DagExecutorSkill::new("test-node".to_string(), "cis-node".to_string());
+        let spec = DagSpec::new("dedup".to_string(), vec![leveled_task("t1", None)]);
+        let hash = spec.content_hash();
+
+        // 另一个提交已预留但尚未分发完成
+        skill.deduplicator.entries.lock().await.insert(hash.clone(), RunningDagEntry {
+            run_id: "run-pending".to_string(),
+            submitted_at: Instant::now(),
+            dispatched: false,
+        });
+
+        let (run_id, status) = skill.submit_dag(spec).await.unwrap();
+        assert_eq!(run_id, "run-pending");
+        assert_eq!(status, DedupeStatus::AlreadyRunning);
+    }
+
+    #[tokio::test]
+    async fn test_submit_dag_failure_releases_reservation() {
+        let skill = DagExecutorSkill::new(
+            "test-node".to_string(),
+            "/nonexistent/cis-node".to_string(),
+        );
+        let spec = DagSpec::new("dedup".to_string(), vec![leveled_task("t1", None)]);
+        let hash = spec.content_hash();
+
+        assert!(skill.submit_dag(spec).await.is_err());
+        assert!(skill.deduplicator.get(&hash).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_dedup_window_expires() {
+        let deduplicator = DagDeduplicator::new(Duration::from_millis(0));
+        deduplicator.entries.lock().await.insert("hash".to_string(), RunningDagEntry {
+            run_id: "run-1".to_string(),
+            submitted_at: Instant::now(),
+            dispatched: true,
+        });
+
+        assert!(deduplicator.get("hash").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pause_unknown_run() {
+        let skill = DagExecutorSkill::new(
+            "test-node".to_string(),
+            "/usr/local/bin/cis-node".to_string(),
+        );
+
+        assert!(matches!(
+            skill.pause_run("missing").await,
+            Err(DagExecutorError::RunNotFound(_))
+        ));
+    }
 }
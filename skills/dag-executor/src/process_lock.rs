@@ -112,6 +112,26 @@ impl ProcessLock {
         }
     }
 
+    /// 删除 Worker 的锁文件（用于清理被强制终止的 Worker）
+    ///
+    /// 返回是否删除了锁文件
+    pub fn remove_lock(worker_id: &str) -> bool {
+        let lock_path = Self::lock_path(worker_id);
+        if !lock_path.exists() {
+            return false;
+        }
+        match std::fs::remove_file(&lock_path) {
+            Ok(()) => {
+                info!("Removed lock file for worker {}", worker_id);
+                true
+            }
+            Err(e) => {
+                warn!("Failed to remove lock file for {}: {}", worker_id, e);
+                false
+            }
+        }
+    }
+
     /// 获取锁文件路径
     fn lock_path(worker_id: &str) -> PathBuf {
         let lock_dir = std::env::temp_dir().join("cis").join("worker_locks");
@@ -158,6 +178,21 @@ impl ProcessLock {
     }
 }
 
+/// 检查 PID 对应的进程是否存在
+///
+/// Linux 上检查 `/proc/<pid>`，其他平台退回到信号探测
+pub fn pid_exists(pid: u32) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        std::path::Path::new("/proc").join(pid.to_string()).exists()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        ProcessLock::is_process_alive(pid)
+    }
+}
+
 impl Drop for ProcessLock {
     fn drop(&mut self) {
         // 尝试删除锁文件
@@ -2,15 +2,18 @@
 //!
 //! 管理 Worker 进程的生命周期
 
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 
 use std::sync::Arc;
 
 use tokio::process::Child;
 use tokio::sync::Mutex;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
-use cis_core::scheduler::DagScope;
+use cis_core::event_bus::EventBusRef;
+use cis_core::events::{EventWrapper, SystemEvent};
+use cis_core::scheduler::{DagPriority, DagRun, DagScope};
 use crate::error::DagExecutorError;
 
 /// Worker 信息
@@ -42,6 +45,24 @@ impl WorkerInfo {
         }
     }
 
+    /// 进程 PID（进程已被回收时为 None）
+    pub fn pid(&self) -> Option<u32> {
+        self.process.id()
+    }
+
+    /// 健康状态：PID 不存在或进程已退出时视为孤儿
+    pub async fn health(&mut self) -> WorkerHealth {
+        let pid_gone = match self.pid() {
+            Some(pid) => !crate::process_lock::pid_exists(pid),
+            None => true,
+        };
+        if pid_gone || !self.is_alive().await {
+            WorkerHealth::Orphaned
+        } else {
+            WorkerHealth::Healthy
+        }
+    }
+
     /// 检查进程是否仍在运行
     pub async fn is_alive(&mut self) -> bool {
         match self.process.try_wait() {
@@ -82,12 +103,26 @@ pub struct RunInfo {
     pub completed_count: usize,
     pub failed_count: usize,
     pub started_at: chrono::DateTime<chrono::Utc>,
+    /// 已上报的任务结果（task_id -> 结果）
+    pub task_results: HashMap<String, TaskResult>,
+}
+
+/// 任务执行结果（由 Worker 上报）
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TaskResult {
+    pub success: bool,
+    #[serde(default)]
+    pub output: String,
 }
 
 /// Run 状态
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RunStatus {
     Running,
+    /// 用户暂停
+    Paused,
+    /// 等待任务确认（与用户暂停相互独立）
+    AwaitingConfirmation,
     Completed,
     Failed,
 }
@@ -96,6 +131,8 @@ impl std::fmt::Display for RunStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Running => write!(f, "running"),
+            Self::Paused => write!(f, "paused"),
+            Self::AwaitingConfirmation => write!(f, "awaiting_confirmation"),
             Self::Completed => write!(f, "completed"),
             Self::Failed => write!(f, "failed"),
         }
@@ -111,6 +148,8 @@ pub struct WorkerPoolConfig {
     pub idle_timeout_secs: u64,
     /// 是否启用 LRU 淘汰
     pub enable_lru: bool,
+    /// 排队 Run 每等待该时长（秒）提升一级优先级，防止饥饿
+    pub max_wait_secs: u64,
 }
 
 impl Default for WorkerPoolConfig {
@@ -119,11 +158,119 @@ impl Default for WorkerPoolConfig {
             max_workers: 10,
             idle_timeout_secs: 300, // 5分钟
             enable_lru: true,
+            max_wait_secs: 300,
+        }
+    }
+}
+
+/// 按排队深度自动伸缩 Worker 数量
+#[derive(Debug, Clone)]
+pub struct AutoScaler {
+    /// 缩容下限
+    pub min_workers: usize,
+    /// 扩容上限
+    pub max_workers: usize,
+    /// 排队 Run 数超过该值时扩容
+    pub scale_up_threshold: usize,
+    /// 排队 Run 数低于该值时缩容
+    pub scale_down_threshold: usize,
+    /// 两次伸缩之间的最短间隔（秒），防止抖动
+    pub cooldown_secs: u64,
+}
+
+impl Default for AutoScaler {
+    fn default() -> Self {
+        Self {
+            min_workers: 1,
+            max_workers: 10,
+            scale_up_threshold: 5,
+            scale_down_threshold: 1,
+            cooldown_secs: 60,
         }
     }
 }
 
+/// 一次自动伸缩的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScaleDecision {
+    /// 新启动了一个 Worker
+    ScaledUp { worker_id: String },
+    /// Worker 已标记为待退出，当前 Run 结束后停止
+    ScaledDown { worker_id: String },
+    /// 保持不变（未达阈值、已达上下限或处于冷却期）
+    Hold,
+}
+
+/// 临时 Worker 跟踪信息（DagScope::Ephemeral）
+#[derive(Debug, Clone)]
+struct EphemeralWorker {
+    /// 最后一次任务活动后的清理时间
+    cleanup_after: std::time::Duration,
+    /// 最后一次任务活动
+    last_activity: std::time::Instant,
+}
+
+/// 等待 Worker 的 Run
+///
+/// 按 `(priority, 提交时间倒序)` 排序：优先级高的先出队，同优先级先到先出。
+#[derive(Debug, Clone)]
+pub struct PrioritizedRun {
+    pub run: DagRun,
+    /// 当前优先级（含饥饿提升）
+    pub priority: DagPriority,
+    pub submitted_at: chrono::DateTime<chrono::Utc>,
+    /// 入队或最近一次提升的时间
+    promoted_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl PrioritizedRun {
+    pub fn new(run: DagRun) -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            priority: run.priority,
+            run,
+            submitted_at: now,
+            promoted_at: now,
+        }
+    }
+
+    /// 每等待 `max_wait` 提升一级，返回是否有提升
+    fn promote_if_starved(&mut self, now: chrono::DateTime<chrono::Utc>, max_wait: chrono::Duration) -> bool {
+        let mut promoted = false;
+        while self.priority != DagPriority::Critical && now - self.promoted_at >= max_wait {
+            self.priority = self.priority.promoted();
+            self.promoted_at += max_wait;
+            promoted = true;
+        }
+        promoted
+    }
+}
+
+impl Ord for PrioritizedRun {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.priority.value(), std::cmp::Reverse(self.submitted_at))
+            .cmp(&(other.priority.value(), std::cmp::Reverse(other.submitted_at)))
+    }
+}
+
+impl PartialOrd for PrioritizedRun {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for PrioritizedRun {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PrioritizedRun {}
+
 /// Worker 管理器
+///
+/// 内部状态均为 `Arc` 共享，clone 得到的是同一个管理器的句柄
+#[derive(Clone)]
 pub struct WorkerManager {
     /// Worker 映射: worker_id -> WorkerInfo
     workers: Arc<Mutex<HashMap<String, WorkerInfo>>>,
@@ -131,8 +278,20 @@ pub struct WorkerManager {
     runs: Arc<Mutex<HashMap<String, RunInfo>>>,
     /// 访问顺序（用于 LRU）
     access_order: Arc<Mutex<Vec<String>>>,
+    /// 临时 Worker: worker_id -> EphemeralWorker（单独跟踪，不参与复用）
+    ephemeral: Arc<Mutex<HashMap<String, EphemeralWorker>>>,
+    /// 等待 Worker 的 Run: worker_id -> 优先级队列
+    queue: Arc<Mutex<HashMap<String, BinaryHeap<PrioritizedRun>>>>,
+    /// 缩容中的 Worker：当前 Run 结束后停止
+    draining: Arc<Mutex<HashSet<String>>>,
+    /// 最近一次伸缩时间（冷却期判断）
+    last_scaled: Arc<Mutex<Option<std::time::Instant>>>,
     /// 配置
     config: WorkerPoolConfig,
+    /// 自动伸缩配置，`None` 时 Worker 数量固定
+    autoscaler: Option<AutoScaler>,
+    /// 伸缩事件发布
+    event_bus: Option<EventBusRef>,
 }
 
 impl WorkerManager {
@@ -145,10 +304,28 @@ impl WorkerManager {
             workers: Arc::new(Mutex::new(HashMap::new())),
             runs: Arc::new(Mutex::new(HashMap::new())),
             access_order: Arc::new(Mutex::new(Vec::new())),
+            ephemeral: Arc::new(Mutex::new(HashMap::new())),
+            queue: Arc::new(Mutex::new(HashMap::new())),
+            draining: Arc::new(Mutex::new(HashSet::new())),
+            last_scaled: Arc::new(Mutex::new(None)),
             config,
+            autoscaler: None,
+            event_bus: None,
         }
     }
 
+    /// 启用自动伸缩
+    pub fn with_autoscaler(mut self, autoscaler: AutoScaler) -> Self {
+        self.autoscaler = Some(autoscaler);
+        self
+    }
+
+    /// 伸缩决策以 `SystemEvent::Info` 发布到事件总线
+    pub fn with_event_bus(mut self, event_bus: EventBusRef) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
     /// 获取或创建 Worker（Task 3.2）
     /// 
     /// 逻辑：
@@ -230,10 +407,65 @@ impl WorkerManager {
         // 从访问顺序中移除
         let mut order = self.access_order.lock().await;
         order.retain(|id| id != worker_id);
+        drop(order);
+
+        self.ephemeral.lock().await.remove(worker_id);
+        self.draining.lock().await.remove(worker_id);
 
         Ok(())
     }
 
+    /// 清理空闲超时的临时 Worker
+    ///
+    /// 返回被停止的 worker_id 列表
+    pub async fn cleanup_ephemeral_workers(&self) -> Vec<String> {
+        let expired: Vec<String> = {
+            let ephemeral = self.ephemeral.lock().await;
+            ephemeral
+                .iter()
+                .filter(|(_, w)| w.last_activity.elapsed() >= w.cleanup_after)
+                .map(|(id, _)| id.clone())
+                .collect()
+        };
+
+        for worker_id in &expired {
+            info!("Ephemeral worker {} idle timeout, stopping", worker_id);
+            if let Err(e) = self.stop_worker(worker_id).await {
+                error!("Failed to stop ephemeral worker {}: {}", worker_id, e);
+            }
+        }
+
+        expired
+    }
+
+    /// 启动后台任务，定期清理临时 Worker
+    pub fn spawn_ephemeral_reaper(
+        &self,
+        interval: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let stopped = manager.cleanup_ephemeral_workers().await;
+                if !stopped.is_empty() {
+                    debug!("Reaped {} ephemeral workers", stopped.len());
+                }
+            }
+        })
+    }
+
+    /// 是否为临时 Worker
+    pub async fn is_ephemeral(&self, worker_id: &str) -> bool {
+        self.ephemeral.lock().await.contains_key(worker_id)
+    }
+
+    /// 临时 Worker 数量
+    pub async fn ephemeral_count(&self) -> usize {
+        self.ephemeral.lock().await.len()
+    }
+
     /// 清理已死亡的 Worker（Task 3.2）
     pub async fn cleanup_dead_workers(&self) -> Vec<String> {
         let mut workers = self.workers.lock().await;
@@ -289,18 +521,9 @@ impl WorkerManager {
     pub async fn get_worker_info(&self, worker_id: &str) -> Option<WorkerSummary> {
         let mut workers = self.workers.lock().await;
         
-        if let Some(info) = workers.get_mut(worker_id) {
-            Some(WorkerSummary {
-                worker_id: worker_id.to_string(),
-                scope: format!("{:?}", info.scope),
-                is_alive: info.is_alive().await,
-                started_at: info.started_at,
-                active_tasks: info.active_tasks,
-                room_id: info.room_id.clone(),
-            })
-        } else {
-            None
-        }
+        let info = workers.get_mut(worker_id)?;
+        let run_count = self.run_count(worker_id).await;
+        Some(WorkerSummary::from_info(info, run_count).await)
     }
 
     /// 添加 Worker
@@ -311,6 +534,16 @@ impl WorkerManager {
         process: Child,
         room_id: String,
     ) {
+        if let DagScope::Ephemeral { cleanup_after_secs } = scope {
+            self.ephemeral.lock().await.insert(
+                worker_id.clone(),
+                EphemeralWorker {
+                    cleanup_after: std::time::Duration::from_secs(cleanup_after_secs),
+                    last_activity: std::time::Instant::now(),
+                },
+            );
+        }
+
         let mut workers = self.workers.lock().await;
         let info = WorkerInfo::new(worker_id.clone(), scope, process, room_id);
         workers.insert(worker_id, info);
@@ -328,6 +561,12 @@ impl WorkerManager {
         if let Some(info) = workers.get_mut(worker_id) {
             info.active_tasks += 1;
         }
+        drop(workers);
+
+        // 刷新临时 Worker 的活动时间
+        if let Some(w) = self.ephemeral.lock().await.get_mut(worker_id) {
+            w.last_activity = std::time::Instant::now();
+        }
     }
 
     /// 减少任务计数
@@ -351,28 +590,61 @@ impl WorkerManager {
         }
         
         workers.clear();
+        self.ephemeral.lock().await.clear();
         info!("All workers stopped");
     }
 
     /// 列出所有 Worker
     pub async fn list_workers(&self) -> Vec<WorkerSummary> {
+        let mut run_counts: HashMap<String, usize> = HashMap::new();
+        for run in self.runs.lock().await.values() {
+            *run_counts.entry(run.worker_id.clone()).or_insert(0) += 1;
+        }
+
         let mut workers = self.workers.lock().await;
         let mut summaries = Vec::new();
-        
+
         for (id, info) in workers.iter_mut() {
-            summaries.push(WorkerSummary {
-                worker_id: id.clone(),
-                scope: format!("{:?}", info.scope),
-                is_alive: info.is_alive().await,
-                started_at: info.started_at,
-                active_tasks: info.active_tasks,
-                room_id: info.room_id.clone(),
-            });
+            let run_count = run_counts.get(id).copied().unwrap_or(0);
+            summaries.push(WorkerSummary::from_info(info, run_count).await);
         }
-        
+        summaries.sort_by(|a, b| a.worker_id.cmp(&b.worker_id));
+
         summaries
     }
 
+    /// 终止 Worker：发送 SIGTERM，删除锁文件并移除 Worker（及其 Room 映射）
+    pub async fn kill_worker(&self, worker_id: &str) -> Result<(), DagExecutorError> {
+        let mut info = self
+            .workers
+            .lock()
+            .await
+            .remove(worker_id)
+            .ok_or_else(|| DagExecutorError::WorkerNotFound(worker_id.to_string()))?;
+
+        if info.is_alive().await {
+            terminate(&mut info).await;
+        }
+        crate::process_lock::ProcessLock::remove_lock(worker_id);
+
+        self.access_order.lock().await.retain(|id| id != worker_id);
+        self.ephemeral.lock().await.remove(worker_id);
+        self.draining.lock().await.remove(worker_id);
+
+        info!("Worker {} killed (room: {})", worker_id, info.room_id);
+        Ok(())
+    }
+
+    /// 指定 Worker 上的 Run 数量
+    async fn run_count(&self, worker_id: &str) -> usize {
+        self.runs
+            .lock()
+            .await
+            .values()
+            .filter(|r| r.worker_id == worker_id)
+            .count()
+    }
+
     /// 获取统计信息
     pub async fn stats(&self) -> WorkerStats {
         let workers = self.workers.lock().await;
@@ -393,6 +665,7 @@ impl WorkerManager {
             completed_count: 0,
             failed_count: 0,
             started_at: chrono::Utc::now(),
+            task_results: HashMap::new(),
         };
         runs.insert(run_id, info);
     }
@@ -412,10 +685,346 @@ impl WorkerManager {
     }
 
     /// 更新 Run 状态
+    ///
+    /// Run 结束时，如果所在 Worker 是临时 Worker 则立即停止
     pub async fn update_run_status(&self, run_id: &str, status: RunStatus) {
+        let worker_id = {
+            let mut runs = self.runs.lock().await;
+            match runs.get_mut(run_id) {
+                Some(info) => {
+                    info.status = status;
+                    info.worker_id.clone()
+                }
+                None => return,
+            }
+        };
+
+        if matches!(status, RunStatus::Completed | RunStatus::Failed)
+            && self.is_ephemeral(&worker_id).await
+        {
+            info!("Run {} finished, stopping ephemeral worker {}", run_id, worker_id);
+            if let Err(e) = self.stop_worker(&worker_id).await {
+                error!("Failed to stop ephemeral worker {}: {}", worker_id, e);
+            }
+        }
+
+        if matches!(status, RunStatus::Completed | RunStatus::Failed)
+            && self.is_draining(&worker_id).await
+            && !self.has_unfinished_runs(&worker_id).await
+        {
+            info!("Run {} finished, stopping draining worker {}", run_id, worker_id);
+            if let Err(e) = self.stop_worker(&worker_id).await {
+                error!("Failed to stop draining worker {}: {}", worker_id, e);
+            }
+        }
+    }
+
+    /// Worker 是否还有未结束的 Run
+    async fn has_unfinished_runs(&self, worker_id: &str) -> bool {
+        self.runs.lock().await.values().any(|r| {
+            r.worker_id == worker_id
+                && matches!(
+                    r.status,
+                    RunStatus::Running | RunStatus::Paused | RunStatus::AwaitingConfirmation
+                )
+        })
+    }
+
+    /// 记录任务结果
+    ///
+    /// 重复上报同一任务时忽略。全部任务上报后 Run 结束：
+    /// 有失败任务为 `Failed`，否则为 `Completed`。返回 Run 当前状态。
+    pub async fn record_task_result(
+        &self,
+        run_id: &str,
+        task_id: &str,
+        result: TaskResult,
+    ) -> Result<RunStatus, DagExecutorError> {
+        let finished = {
+            let mut runs = self.runs.lock().await;
+            let info = runs
+                .get_mut(run_id)
+                .ok_or_else(|| DagExecutorError::RunNotFound(run_id.to_string()))?;
+
+            if info.task_results.contains_key(task_id) {
+                debug!("Duplicate result for task {} in run {} ignored", task_id, run_id);
+                return Ok(info.status);
+            }
+
+            if result.success {
+                info.completed_count += 1;
+            } else {
+                info.failed_count += 1;
+            }
+            info.task_results.insert(task_id.to_string(), result);
+
+            if info.completed_count + info.failed_count < info.task_count {
+                return Ok(info.status);
+            }
+            if info.failed_count > 0 {
+                RunStatus::Failed
+            } else {
+                RunStatus::Completed
+            }
+        };
+
+        info!("Run {} finished: {}", run_id, finished);
+        self.update_run_status(run_id, finished).await;
+        Ok(finished)
+    }
+
+    /// 获取 Run 信息
+    pub async fn get_run(&self, run_id: &str) -> Option<RunInfo> {
+        self.runs.lock().await.get(run_id).cloned()
+    }
+
+    /// 条件切换 Run 状态
+    ///
+    /// 仅当当前状态为 `from` 时切换到 `to`，返回切换前的 RunInfo
+    pub async fn transition_run(
+        &self,
+        run_id: &str,
+        from: RunStatus,
+        to: RunStatus,
+    ) -> Result<RunInfo, DagExecutorError> {
         let mut runs = self.runs.lock().await;
-        if let Some(info) = runs.get_mut(run_id) {
-            info.status = status;
+        let info = runs
+            .get_mut(run_id)
+            .ok_or_else(|| DagExecutorError::RunNotFound(run_id.to_string()))?;
+
+        if info.status != from {
+            return Err(DagExecutorError::InvalidRunState(format!(
+                "run {} is {}, expected {}",
+                run_id, info.status, from
+            )));
+        }
+
+        let previous = info.clone();
+        info.status = to;
+        Ok(previous)
+    }
+
+    /// Run 排队等待 Worker
+    pub async fn enqueue_run(&self, worker_id: &str, run: DagRun) {
+        debug!("Run {} queued for worker {} ({:?})", run.run_id, worker_id, run.priority);
+        self.queue
+            .lock()
+            .await
+            .entry(worker_id.to_string())
+            .or_default()
+            .push(PrioritizedRun::new(run));
+    }
+
+    /// 取出 Worker 的下一个 Run
+    ///
+    /// 高优先级先出队（Critical 抢占 Normal / Low）。等待超过
+    /// `max_wait_secs` 的 Run 先提升一级优先级再参与排序。
+    pub async fn next_run_for_worker(&self, worker_id: &str) -> Option<DagRun> {
+        let mut queue = self.queue.lock().await;
+        let heap = queue.get_mut(worker_id)?;
+
+        let now = chrono::Utc::now();
+        let max_wait = chrono::Duration::seconds(self.config.max_wait_secs as i64);
+        let mut runs = std::mem::take(heap).into_vec();
+        for run in &mut runs {
+            if run.promote_if_starved(now, max_wait) {
+                debug!("Run {} promoted to {:?} after waiting", run.run.run_id, run.priority);
+            }
+        }
+        *heap = BinaryHeap::from(runs);
+
+        let next = heap.pop().map(|p| p.run);
+        if heap.is_empty() {
+            queue.remove(worker_id);
+        }
+        next
+    }
+
+    /// Worker 排队中的 Run 数量
+    pub async fn queued_runs(&self, worker_id: &str) -> usize {
+        self.queue.lock().await.get(worker_id).map_or(0, |heap| heap.len())
+    }
+
+    /// 所有 Worker 排队中的 Run 总数
+    pub async fn queue_depth(&self) -> usize {
+        self.queue.lock().await.values().map(|heap| heap.len()).sum()
+    }
+
+    /// Worker 是否已标记为缩容
+    pub async fn is_draining(&self, worker_id: &str) -> bool {
+        self.draining.lock().await.contains(worker_id)
+    }
+
+    /// 按排队深度扩容或缩容一次
+    ///
+    /// - 排队深度超过 `scale_up_threshold` 且未达 `max_workers` 时，以新的
+    ///   worker_id 调用 `spawn_fn` 启动一个全局 Worker；
+    /// - 排队深度低于 `scale_down_threshold` 且高于 `min_workers` 时，把最空闲的
+    ///   Worker 标记为缩容，其当前 Run 结束后停止（没有 Run 时立即停止）。
+    ///
+    /// 临时 Worker 和缩容中的 Worker 不计入数量。两次伸缩间隔不足
+    /// `cooldown_secs` 时不做调整。未配置 [`AutoScaler`] 时总是返回 `Hold`。
+    pub async fn auto_scale<F, Fut>(&self, spawn_fn: F) -> Result<ScaleDecision, DagExecutorError>
+    where
+        F: FnOnce(String) -> Fut,
+        Fut: std::future::Future<Output = Result<(Child, String), DagExecutorError>>,
+    {
+        let Some(scaler) = self.autoscaler.clone() else {
+            return Ok(ScaleDecision::Hold);
+        };
+
+        if let Some(at) = *self.last_scaled.lock().await {
+            if at.elapsed() < std::time::Duration::from_secs(scaler.cooldown_secs) {
+                return Ok(ScaleDecision::Hold);
+            }
+        }
+
+        let depth = self.queue_depth().await;
+        let candidates = self.scalable_workers().await;
+        let workers = candidates.len();
+
+        let decision = if depth > scaler.scale_up_threshold && workers < scaler.max_workers {
+            let worker_id = format!("worker-auto-{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+            let (process, room_id) = spawn_fn(worker_id.clone()).await?;
+            self.add_worker(worker_id.clone(), DagScope::Global, process, room_id).await;
+            self.update_access_time(&worker_id).await;
+            ScaleDecision::ScaledUp { worker_id }
+        } else if depth < scaler.scale_down_threshold && workers > scaler.min_workers {
+            match self.most_idle_worker(&candidates).await {
+                Some(worker_id) => {
+                    self.draining.lock().await.insert(worker_id.clone());
+                    if !self.has_unfinished_runs(&worker_id).await {
+                        self.stop_worker(&worker_id).await?;
+                    }
+                    ScaleDecision::ScaledDown { worker_id }
+                }
+                None => ScaleDecision::Hold,
+            }
+        } else {
+            ScaleDecision::Hold
+        };
+
+        if decision != ScaleDecision::Hold {
+            *self.last_scaled.lock().await = Some(std::time::Instant::now());
+            self.publish_scale_event(&decision, depth, workers).await;
+        }
+        Ok(decision)
+    }
+
+    /// 启动后台任务，定期调用 [`auto_scale`](Self::auto_scale)
+    pub fn spawn_autoscaler<F, Fut>(
+        &self,
+        interval: std::time::Duration,
+        spawn_fn: F,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<(Child, String), DagExecutorError>> + Send,
+    {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = manager.auto_scale(&spawn_fn).await {
+                    error!("Worker auto-scaling failed: {}", e);
+                }
+            }
+        })
+    }
+
+    /// 参与伸缩的 Worker：非临时、未缩容
+    async fn scalable_workers(&self) -> Vec<String> {
+        let ephemeral = self.ephemeral.lock().await;
+        let draining = self.draining.lock().await;
+        self.workers
+            .lock()
+            .await
+            .keys()
+            .filter(|id| !ephemeral.contains_key(*id) && !draining.contains(*id))
+            .cloned()
+            .collect()
+    }
+
+    /// 活跃任务最少的 Worker，相同时取最久未使用的
+    async fn most_idle_worker(&self, candidates: &[String]) -> Option<String> {
+        let workers = self.workers.lock().await;
+        let order = self.access_order.lock().await;
+        candidates
+            .iter()
+            .filter_map(|id| workers.get(id).map(|w| (id, w.active_tasks)))
+            .min_by_key(|(id, active)| (*active, order.iter().position(|o| o == *id).unwrap_or(0)))
+            .map(|(id, _)| id.clone())
+    }
+
+    async fn publish_scale_event(&self, decision: &ScaleDecision, depth: usize, workers: usize) {
+        let (action, worker_id, message) = match decision {
+            ScaleDecision::ScaledUp { worker_id } => (
+                "scale_up",
+                worker_id,
+                format!("Queue depth {} exceeds threshold, started worker {}", depth, worker_id),
+            ),
+            ScaleDecision::ScaledDown { worker_id } => (
+                "scale_down",
+                worker_id,
+                format!("Queue depth {} below threshold, draining worker {}", depth, worker_id),
+            ),
+            ScaleDecision::Hold => return,
+        };
+        info!("{}", message);
+
+        let Some(bus) = &self.event_bus else {
+            return;
+        };
+        let event = SystemEvent::info("worker_autoscale", message, "dag-executor").with_details(
+            serde_json::json!({
+                "action": action,
+                "worker_id": worker_id,
+                "queue_depth": depth,
+                "workers_before": workers,
+            }),
+        );
+        if let Err(e) = bus.publish(EventWrapper::System(event)).await {
+            warn!("Failed to publish auto-scaling event: {}", e);
+        }
+    }
+}
+
+/// 发送 SIGTERM；非 Unix 平台直接终止进程
+async fn terminate(info: &mut WorkerInfo) {
+    #[cfg(unix)]
+    {
+        if let Some(pid) = info.pid() {
+            // SAFETY: PID 来自本进程启动并持有的子进程句柄，SIGTERM 为标准信号
+            let result = unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) };
+            if result != 0 {
+                warn!(
+                    "Failed to send SIGTERM to worker {}: {}",
+                    info.worker_id,
+                    std::io::Error::last_os_error()
+                );
+            }
+            return;
+        }
+    }
+
+    let _ = info.kill().await;
+}
+
+/// Worker 健康状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerHealth {
+    /// 进程存活
+    Healthy,
+    /// 进程已不存在，但 Worker 仍登记在管理器中
+    Orphaned,
+}
+
+impl std::fmt::Display for WorkerHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkerHealth::Healthy => write!(f, "healthy"),
+            WorkerHealth::Orphaned => write!(f, "orphaned"),
         }
     }
 }
@@ -425,10 +1034,32 @@ impl WorkerManager {
 pub struct WorkerSummary {
     pub worker_id: String,
     pub scope: String,
-    pub is_alive: bool,
+    pub pid: Option<u32>,
+    pub room_id: String,
     pub started_at: chrono::DateTime<chrono::Utc>,
     pub active_tasks: usize,
-    pub room_id: String,
+    pub health: WorkerHealth,
+    /// 分配到该 Worker 的 Run 数量
+    pub run_count: usize,
+}
+
+impl WorkerSummary {
+    async fn from_info(info: &mut WorkerInfo, run_count: usize) -> Self {
+        Self {
+            worker_id: info.worker_id.clone(),
+            scope: format!("{:?}", info.scope),
+            pid: info.pid(),
+            room_id: info.room_id.clone(),
+            started_at: info.started_at,
+            active_tasks: info.active_tasks,
+            health: info.health().await,
+            run_count,
+        }
+    }
+
+    pub fn is_orphaned(&self) -> bool {
+        self.health == WorkerHealth::Orphaned
+    }
 }
 
 /// Worker 统计
@@ -465,6 +1096,7 @@ pub async fn spawn_worker(
         cis_core::scheduler::DagScope::Project { project_id, .. } => format!("project:{}", project_id),
         cis_core::scheduler::DagScope::User { user_id, .. } => format!("user:{}", user_id),
         cis_core::scheduler::DagScope::Type { dag_type, .. } => format!("type:{}", dag_type),
+        cis_core::scheduler::DagScope::Ephemeral { .. } => "ephemeral".to_string(),
     };
 
     let worker_args = vec![
@@ -539,4 +1171,257 @@ mod tests {
         assert_eq!(stats.total, 0);
         assert_eq!(stats.active, 0);
     }
+
+    fn queued_run(run_id: &str, priority: DagPriority) -> DagRun {
+        let mut run = DagRun::with_run_id(cis_core::scheduler::TaskDag::new(), run_id.to_string());
+        run.priority = priority;
+        run
+    }
+
+    #[tokio::test]
+    async fn test_next_run_for_worker_priority() {
+        let manager = WorkerManager::new();
+        manager.enqueue_run("w", queued_run("low", DagPriority::Low)).await;
+        manager.enqueue_run("w", queued_run("normal-1", DagPriority::Normal)).await;
+        manager.enqueue_run("w", queued_run("critical", DagPriority::Critical)).await;
+        manager.enqueue_run("w", queued_run("normal-2", DagPriority::Normal)).await;
+        assert_eq!(manager.queued_runs("w").await, 4);
+
+        let order: Vec<String> = [
+            manager.next_run_for_worker("w").await,
+            manager.next_run_for_worker("w").await,
+            manager.next_run_for_worker("w").await,
+            manager.next_run_for_worker("w").await,
+        ]
+        .into_iter()
+        .map(|r| r.unwrap().run_id)
+        .collect();
+        assert_eq!(order, vec!["critical", "normal-1", "normal-2", "low"]);
+
+        assert!(manager.next_run_for_worker("w").await.is_none());
+        assert!(manager.next_run_for_worker("other").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_starved_run_promoted() {
+        let manager = WorkerManager::with_config(WorkerPoolConfig {
+            max_wait_secs: 60,
+            ..WorkerPoolConfig::default()
+        });
+        manager.enqueue_run("w", queued_run("old-low", DagPriority::Low)).await;
+        manager.enqueue_run("w", queued_run("normal", DagPriority::Normal)).await;
+
+        // 模拟 old-low 已等待 90 秒
+        {
+            let mut queue = manager.queue.lock().await;
+            let heap = queue.get_mut("w").unwrap();
+            let mut runs = std::mem::take(heap).into_vec();
+            for run in &mut runs {
+                if run.run.run_id == "old-low" {
+                    run.submitted_at -= chrono::Duration::seconds(90);
+                    run.promoted_at -= chrono::Duration::seconds(90);
+                }
+            }
+            *heap = BinaryHeap::from(runs);
+        }
+
+        // 提升为 Normal 后，同优先级中提交更早者先出队
+        assert_eq!(manager.next_run_for_worker("w").await.unwrap().run_id, "old-low");
+        assert_eq!(manager.next_run_for_worker("w").await.unwrap().run_id, "normal");
+    }
+
+    fn spawn_sleeper() -> Child {
+        tokio::process::Command::new("sleep")
+            .arg("30")
+            .kill_on_drop(true)
+            .spawn()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_ephemeral_worker_idle_cleanup() {
+        let manager = WorkerManager::new();
+        manager
+            .add_worker(
+                "worker-ephemeral-a".to_string(),
+                DagScope::Ephemeral { cleanup_after_secs: 0 },
+                spawn_sleeper(),
+                "!a:node".to_string(),
+            )
+            .await;
+        manager
+            .add_worker(
+                "worker-global".to_string(),
+                DagScope::Global,
+                spawn_sleeper(),
+                "!g:node".to_string(),
+            )
+            .await;
+
+        assert!(manager.is_ephemeral("worker-ephemeral-a").await);
+        assert!(!manager.is_ephemeral("worker-global").await);
+
+        let stopped = manager.cleanup_ephemeral_workers().await;
+        assert_eq!(stopped, vec!["worker-ephemeral-a".to_string()]);
+        assert_eq!(manager.worker_count().await, 1);
+        assert_eq!(manager.ephemeral_count().await, 0);
+
+        manager.stop_all().await;
+    }
+
+    #[tokio::test]
+    async fn test_ephemeral_worker_stopped_on_run_completion() {
+        let manager = WorkerManager::new();
+        manager
+            .add_worker(
+                "worker-ephemeral-b".to_string(),
+                DagScope::Ephemeral { cleanup_after_secs: 3600 },
+                spawn_sleeper(),
+                "!b:node".to_string(),
+            )
+            .await;
+        manager.add_run("run-1".to_string(), "worker-ephemeral-b".to_string(), 1).await;
+
+        // 未超时，不清理
+        assert!(manager.cleanup_ephemeral_workers().await.is_empty());
+
+        manager.update_run_status("run-1", RunStatus::Completed).await;
+        assert_eq!(manager.worker_count().await, 0);
+        assert!(!manager.is_ephemeral("worker-ephemeral-b").await);
+    }
+
+    #[tokio::test]
+    async fn test_transition_run() {
+        let manager = WorkerManager::new();
+        manager.add_run("run-1".to_string(), "worker-global".to_string(), 2).await;
+
+        manager
+            .transition_run("run-1", RunStatus::Running, RunStatus::Paused)
+            .await
+            .unwrap();
+        assert_eq!(manager.get_run("run-1").await.unwrap().status, RunStatus::Paused);
+
+        // 已暂停的 Run 不能再次暂停
+        let err = manager
+            .transition_run("run-1", RunStatus::Running, RunStatus::Paused)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DagExecutorError::InvalidRunState(_)));
+
+        let err = manager
+            .transition_run("missing", RunStatus::Running, RunStatus::Paused)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DagExecutorError::RunNotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_record_task_result() {
+        let manager = WorkerManager::new();
+        manager.add_run("run-1".to_string(), "worker-global".to_string(), 2).await;
+
+        let ok = TaskResult { success: true, output: "done".to_string() };
+        let failed = TaskResult { success: false, output: String::new() };
+
+        assert_eq!(manager.record_task_result("run-1", "a", ok.clone()).await.unwrap(), RunStatus::Running);
+        // 重复上报不计数
+        assert_eq!(manager.record_task_result("run-1", "a", failed.clone()).await.unwrap(), RunStatus::Running);
+        assert_eq!(manager.record_task_result("run-1", "b", failed).await.unwrap(), RunStatus::Failed);
+
+        let info = manager.get_run("run-1").await.unwrap();
+        assert_eq!((info.completed_count, info.failed_count), (1, 1));
+        assert_eq!(info.task_results["a"], ok);
+
+        assert!(matches!(
+            manager.record_task_result("missing", "a", ok).await,
+            Err(DagExecutorError::RunNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_auto_scale() {
+        use cis_core::event_bus::MemoryEventBus;
+
+        let bus = Arc::new(MemoryEventBus::new());
+        let manager = WorkerManager::new()
+            .with_autoscaler(AutoScaler {
+                min_workers: 1,
+                max_workers: 2,
+                scale_up_threshold: 1,
+                scale_down_threshold: 1,
+                cooldown_secs: 0,
+            })
+            .with_event_bus(bus.clone());
+        let spawn = |_id: String| async { Ok((spawn_sleeper(), "!auto:node".to_string())) };
+
+        for i in 0..3 {
+            manager.enqueue_run("w", queued_run(&format!("run-{}", i), DagPriority::Normal)).await;
+        }
+        assert!(matches!(manager.auto_scale(spawn).await.unwrap(), ScaleDecision::ScaledUp { .. }));
+        assert!(matches!(manager.auto_scale(spawn).await.unwrap(), ScaleDecision::ScaledUp { .. }));
+        // 已达 max_workers
+        assert_eq!(manager.auto_scale(spawn).await.unwrap(), ScaleDecision::Hold);
+        assert_eq!(manager.worker_count().await, 2);
+
+        // 队列清空后缩容：有 Run 的 Worker 等 Run 结束后才停止
+        while manager.next_run_for_worker("w").await.is_some() {}
+        let busy = manager.scalable_workers().await;
+        for worker_id in &busy {
+            manager.add_run(format!("run-{}", worker_id), worker_id.clone(), 1).await;
+        }
+        let ScaleDecision::ScaledDown { worker_id } = manager.auto_scale(spawn).await.unwrap() else {
+            panic!("expected scale down");
+        };
+        assert!(manager.is_draining(&worker_id).await);
+        assert_eq!(manager.worker_count().await, 2);
+        // 已达 min_workers
+        assert_eq!(manager.auto_scale(spawn).await.unwrap(), ScaleDecision::Hold);
+
+        manager.update_run_status(&format!("run-{}", worker_id), RunStatus::Completed).await;
+        assert_eq!(manager.worker_count().await, 1);
+        assert!(!manager.is_draining(&worker_id).await);
+
+        let events = bus.get_all_history().await;
+        assert_eq!(
+            events
+                .iter()
+                .filter(|(_, e)| matches!(e, EventWrapper::System(s) if s.category == "worker_autoscale"))
+                .count(),
+            3
+        );
+
+        manager.stop_all().await;
+    }
+
+    #[tokio::test]
+    async fn test_list_and_kill_workers() {
+        let manager = WorkerManager::new();
+        manager
+            .add_worker("worker-a".to_string(), DagScope::Global, spawn_sleeper(), "!a:node".to_string())
+            .await;
+        let exited = tokio::process::Command::new("true").spawn().unwrap();
+        manager
+            .add_worker("worker-b".to_string(), DagScope::Global, exited, "!b:node".to_string())
+            .await;
+        manager.add_run("run-1".to_string(), "worker-a".to_string(), 2).await;
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+        let workers = manager.list_workers().await;
+        assert_eq!(workers.len(), 2);
+        assert_eq!(workers[0].worker_id, "worker-a");
+        assert_eq!(workers[0].health, WorkerHealth::Healthy);
+        assert!(workers[0].pid.is_some());
+        assert_eq!(workers[0].run_count, 1);
+        // 进程已退出的 Worker 标记为孤儿
+        assert!(workers[1].is_orphaned());
+
+        manager.kill_worker("worker-a").await.unwrap();
+        assert!(manager.get_worker_info("worker-a").await.is_none());
+        assert!(matches!(
+            manager.kill_worker("worker-a").await,
+            Err(DagExecutorError::WorkerNotFound(_))
+        ));
+
+        manager.stop_all().await;
+    }
 }
@@ -0,0 +1,82 @@
+//! IM 批量保存性能基准
+//!
+//! 对比 1000 条消息逐条 `save_message` 与一次 `batch_save_messages` 的吞吐量。
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use im_skill::{Conversation, ConversationType, ImDatabase, Message, MessageContent};
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::runtime::Runtime;
+
+const BATCH_SIZE: usize = 1000;
+
+/// 创建带一个会话的临时数据库
+fn setup(rt: &Runtime) -> (ImDatabase, TempDir) {
+    let dir = TempDir::new().unwrap();
+    let db = ImDatabase::open(dir.path()).unwrap();
+    let now = chrono::Utc::now();
+    let conversation = Conversation {
+        id: "bench".to_string(),
+        conversation_type: ConversationType::Group,
+        name: None,
+        participants: vec!["user1".to_string()],
+        admins: Vec::new(),
+        created_at: now,
+        updated_at: now,
+        last_message_at: None,
+        avatar_url: None,
+        metadata: serde_json::json!({}),
+    };
+    rt.block_on(db.create_session(&conversation)).unwrap();
+    (db, dir)
+}
+
+fn generate_messages(count: usize) -> Vec<Message> {
+    (0..count)
+        .map(|i| {
+            Message::new(
+                "bench".to_string(),
+                "user1".to_string(),
+                MessageContent::Text { text: format!("message {}", i) },
+            )
+        })
+        .collect()
+}
+
+fn bench_save_messages(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("im_save_messages");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+    group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+
+    group.bench_function(BenchmarkId::new("sequential", BATCH_SIZE), |b| {
+        b.iter_batched(
+            || (setup(&rt), generate_messages(BATCH_SIZE)),
+            |((db, _dir), messages)| {
+                rt.block_on(async {
+                    for message in &messages {
+                        db.save_message(message).await.unwrap();
+                    }
+                })
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.bench_function(BenchmarkId::new("batch", BATCH_SIZE), |b| {
+        b.iter_batched(
+            || (setup(&rt), generate_messages(BATCH_SIZE)),
+            |((db, _dir), messages)| {
+                let result = rt.block_on(db.batch_save_messages(&messages)).unwrap();
+                assert_eq!(result.inserted, BATCH_SIZE);
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_save_messages);
+criterion_main!(benches);
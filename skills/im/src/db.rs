@@ -1,13 +1,139 @@
 //! IM 数据库完整实现
 
 use rusqlite::{Connection, OptionalExtension};
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, RwLock};
 
 use crate::types::*;
 use crate::error::{ImError, Result};
+use crate::webhook::{Webhook, WebhookId};
+
+/// 批量保存中失败的消息
+#[derive(Debug, Clone)]
+pub struct BatchSaveFailure {
+    pub message_id: MessageId,
+    pub error: String,
+}
+
+/// 批量保存结果
+#[derive(Debug, Clone, Default)]
+pub struct BatchSaveResult {
+    /// 新插入的消息数
+    pub inserted: usize,
+    /// ID 已存在而跳过的消息数
+    pub skipped_duplicates: usize,
+    /// 失败的消息数
+    pub failed: usize,
+    /// 失败详情
+    pub failures: Vec<BatchSaveFailure>,
+}
+
+impl BatchSaveResult {
+    fn record_failure(&mut self, message: &Message, error: ImError) {
+        self.failed += 1;
+        self.failures.push(BatchSaveFailure {
+            message_id: message.id.clone(),
+            error: error.to_string(),
+        });
+    }
+    
+    /// 合并另一批结果
+    pub fn merge(&mut self, other: BatchSaveResult) {
+        self.inserted += other.inserted;
+        self.skipped_duplicates += other.skipped_duplicates;
+        self.failed += other.failed;
+        self.failures.extend(other.failures);
+    }
+}
+
+/// 统计时间窗口
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeWindow {
+    Last7Days,
+    Last30Days,
+    AllTime,
+}
+
+impl TimeWindow {
+    /// 窗口天数，`AllTime` 为 None
+    pub fn days(&self) -> Option<i64> {
+        match self {
+            TimeWindow::Last7Days => Some(7),
+            TimeWindow::Last30Days => Some(30),
+            TimeWindow::AllTime => None,
+        }
+    }
+}
+
+/// 会话统计
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConversationStats {
+    pub message_count: u64,
+    /// 发过消息的用户数
+    pub active_users: u32,
+    pub messages_per_day: f64,
+    /// 消息最多的用户，没有消息时为空
+    pub most_active_user: String,
+    /// 不同发送方相邻两条消息的平均间隔（秒）
+    pub avg_response_time_secs: f64,
+    /// 消息最多的小时（UTC，0-23）
+    pub peak_hour: u8,
+}
+
+/// 会话标签（按用户隔离，每个用户只能看到自己添加的标签）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationLabel {
+    pub label: String,
+    /// 添加标签的用户
+    pub added_by: UserId,
+    pub added_at: DateTime<Utc>,
+    /// 显示颜色（如 `#ff8800`）
+    pub color: Option<String>,
+}
+
+/// 全文索引重建报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexRebuildReport {
+    /// 重建后索引中的文档数
+    pub documents_indexed: u64,
+    /// 重建前删除的孤立或过期文档数
+    pub removed_documents: u64,
+    /// 重建前补齐的文档数（含过期文档的新版本）
+    pub added_documents: u64,
+    pub duration_ms: u64,
+}
+
+/// 全文索引一致性检查报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FtsIntegrityReport {
+    /// 索引是否已创建（未启用全文检索时为 false）
+    pub index_exists: bool,
+    pub indexed_documents: u64,
+    /// 有文本但不在索引中的消息数
+    pub missing_documents: u64,
+    /// 索引中已不存在对应消息的文档数
+    pub orphaned_documents: u64,
+    /// 索引文本与消息内容不一致的文档数
+    pub stale_documents: u64,
+    /// FTS5 `integrity-check` 报告的错误
+    pub errors: Vec<String>,
+}
+
+impl FtsIntegrityReport {
+    /// 索引存在且与 messages 表一致
+    pub fn is_healthy(&self) -> bool {
+        self.index_exists
+            && self.errors.is_empty()
+            && self.missing_documents == 0
+            && self.orphaned_documents == 0
+            && self.stale_documents == 0
+    }
+}
 
 /// IM 数据库
 pub struct ImDatabase {
@@ -17,10 +143,14 @@ pub struct ImDatabase {
 impl ImDatabase {
     /// 打开数据库（同步版本，用于非异步上下文）
     pub fn open(data_dir: &Path) -> Result<Self> {
-        let db_path = data_dir.join("im.db");
         std::fs::create_dir_all(data_dir)
             .map_err(|e| ImError::Database(format!("Failed to create data dir: {}", e)))?;
-        let conn = Connection::open(&db_path)
+        Self::open_file(&data_dir.join("im.db"))
+    }
+    
+    /// 打开指定路径的数据库文件（同步版本）
+    pub fn open_file(db_path: &Path) -> Result<Self> {
+        let conn = Connection::open(db_path)
             .map_err(|e| ImError::Database(format!("Failed to open database: {}", e)))?;
         
         let db = Self { 
@@ -33,6 +163,11 @@ impl ImDatabase {
         Ok(db)
     }
     
+    /// 按会话分片打开数据库，见 [`ShardedImDatabase`]
+    pub fn shard_by_conversation(data_dir: &Path, shard_count: usize) -> Result<ShardedImDatabase> {
+        ShardedImDatabase::open(data_dir, shard_count)
+    }
+    
     /// 异步打开数据库（用于异步上下文）
     pub async fn open_async(data_dir: &Path) -> Result<Self> {
         let db_path = data_dir.join("im.db");
@@ -64,11 +199,17 @@ impl ImDatabase {
                 updated_at TEXT NOT NULL,
                 last_message_at TEXT,
                 avatar_url TEXT,
-                metadata TEXT
+                metadata TEXT,
+                channel_topic TEXT,
+                channel_public INTEGER NOT NULL DEFAULT 0
             )",
             [],
         ).map_err(|e| ImError::Database(e.to_string()))?;
         
+        // 旧库迁移：补充频道列（列已存在时忽略错误）
+        let _ = conn.execute("ALTER TABLE sessions ADD COLUMN channel_topic TEXT", []);
+        let _ = conn.execute("ALTER TABLE sessions ADD COLUMN channel_public INTEGER NOT NULL DEFAULT 0", []);
+        
         // 参与者表
         conn.execute(
             "CREATE TABLE IF NOT EXISTS participants (
@@ -96,12 +237,41 @@ impl ImDatabase {
                 reply_to TEXT,
                 read_by TEXT,
                 metadata TEXT,
+                signature TEXT,
+                detected_language TEXT,
                 FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE,
                 FOREIGN KEY (reply_to) REFERENCES messages(id)
             )",
             [],
         ).map_err(|e| ImError::Database(e.to_string()))?;
         
+        // 旧库迁移：补充签名列（列已存在时忽略错误）
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN signature TEXT", []);
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN detected_language TEXT", []);
+        
+        // Matrix 事件映射表（联邦去重）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS matrix_events (
+                event_id TEXT PRIMARY KEY,
+                message_id TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        
+        // 出站 Webhook（scope 固定为全局会话，分片时与用户资料放在同一分片）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS webhooks (
+                id TEXT PRIMARY KEY,
+                scope TEXT NOT NULL DEFAULT '__global__',
+                url TEXT NOT NULL,
+                secret TEXT NOT NULL,
+                events TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        
         // 已读状态表
         conn.execute(
             "CREATE TABLE IF NOT EXISTS read_status (
@@ -115,6 +285,20 @@ impl ImDatabase {
             [],
         ).map_err(|e| ImError::Database(e.to_string()))?;
         
+        // 会话标签表（按用户隔离）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS conversation_labels (
+                session_id TEXT NOT NULL,
+                user_id TEXT NOT NULL,
+                label TEXT NOT NULL,
+                color TEXT,
+                added_at TEXT NOT NULL,
+                PRIMARY KEY (session_id, user_id, label),
+                FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
+            )",
+            [],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        
         // 索引
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_messages_session_time 
@@ -132,6 +316,11 @@ impl ImDatabase {
             [],
         ).map_err(|e| ImError::Database(e.to_string()))?;
         
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_conversation_labels_user ON conversation_labels(user_id, label)",
+            [],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        
         Ok(())
     }
     
@@ -151,11 +340,17 @@ impl ImDatabase {
                 updated_at TEXT NOT NULL,
                 last_message_at TEXT,
                 avatar_url TEXT,
-                metadata TEXT
+                metadata TEXT,
+                channel_topic TEXT,
+                channel_public INTEGER NOT NULL DEFAULT 0
             )",
             [],
         ).map_err(|e| ImError::Database(e.to_string()))?;
         
+        // 旧库迁移：补充频道列（列已存在时忽略错误）
+        let _ = conn.execute("ALTER TABLE sessions ADD COLUMN channel_topic TEXT", []);
+        let _ = conn.execute("ALTER TABLE sessions ADD COLUMN channel_public INTEGER NOT NULL DEFAULT 0", []);
+        
         // 参与者表
         conn.execute(
             "CREATE TABLE IF NOT EXISTS participants (
@@ -183,12 +378,41 @@ impl ImDatabase {
                 reply_to TEXT,
                 read_by TEXT,
                 metadata TEXT,
+                signature TEXT,
+                detected_language TEXT,
                 FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE,
                 FOREIGN KEY (reply_to) REFERENCES messages(id)
             )",
             [],
         ).map_err(|e| ImError::Database(e.to_string()))?;
         
+        // 旧库迁移：补充签名列（列已存在时忽略错误）
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN signature TEXT", []);
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN detected_language TEXT", []);
+        
+        // Matrix 事件映射表（联邦去重）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS matrix_events (
+                event_id TEXT PRIMARY KEY,
+                message_id TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        
+        // 出站 Webhook（scope 固定为全局会话，分片时与用户资料放在同一分片）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS webhooks (
+                id TEXT PRIMARY KEY,
+                scope TEXT NOT NULL DEFAULT '__global__',
+                url TEXT NOT NULL,
+                secret TEXT NOT NULL,
+                events TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        
         // 已读状态表
         conn.execute(
             "CREATE TABLE IF NOT EXISTS read_status (
@@ -202,6 +426,20 @@ impl ImDatabase {
             [],
         ).map_err(|e| ImError::Database(e.to_string()))?;
         
+        // 会话标签表（按用户隔离）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS conversation_labels (
+                session_id TEXT NOT NULL,
+                user_id TEXT NOT NULL,
+                label TEXT NOT NULL,
+                color TEXT,
+                added_at TEXT NOT NULL,
+                PRIMARY KEY (session_id, user_id, label),
+                FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
+            )",
+            [],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        
         // 索引
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_messages_session_time 
@@ -219,6 +457,11 @@ impl ImDatabase {
             [],
         ).map_err(|e| ImError::Database(e.to_string()))?;
         
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_conversation_labels_user ON conversation_labels(user_id, label)",
+            [],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        
         Ok(())
     }
     
@@ -227,27 +470,32 @@ impl ImDatabase {
     /// 创建或更新会话
     pub async fn create_session(&self, session: &Conversation) -> Result<()> {
         let conn = self.conn.lock().await;
+        let (channel_topic, channel_public) = Self::channel_columns(&session.conversation_type);
         
         // 插入会话
         conn.execute(
             "INSERT INTO sessions (id, session_type, title, created_at, updated_at, 
-                                  last_message_at, avatar_url, metadata)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                                  last_message_at, avatar_url, metadata, channel_topic, channel_public)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
              ON CONFLICT(id) DO UPDATE SET
              title = excluded.title,
              updated_at = excluded.updated_at,
              last_message_at = excluded.last_message_at,
              avatar_url = excluded.avatar_url,
-             metadata = excluded.metadata",
+             metadata = excluded.metadata,
+             channel_topic = excluded.channel_topic,
+             channel_public = excluded.channel_public",
             rusqlite::params![
                 session.id,
-                format!("{:?}", session.conversation_type).to_lowercase(),
+                session.conversation_type.as_str(),
                 session.name,
                 session.created_at.to_rfc3339(),
                 session.updated_at.to_rfc3339(),
                 session.last_message_at.map(|t| t.to_rfc3339()),
                 session.avatar_url,
                 serde_json::to_string(&session.metadata).unwrap_or_default(),
+                channel_topic,
+                channel_public,
             ],
         ).map_err(|e| ImError::Database(e.to_string()))?;
         
@@ -259,6 +507,7 @@ impl ImDatabase {
         
         // 插入参与者
         for user_id in &session.participants {
+            let role = if session.is_admin(user_id) { "admin" } else { "member" };
             conn.execute(
                 "INSERT INTO participants (session_id, user_id, role, joined_at)
                  VALUES (?1, ?2, ?3, ?4)
@@ -267,7 +516,7 @@ impl ImDatabase {
                 rusqlite::params![
                     session.id,
                     user_id,
-                    "member",
+                    role,
                     session.created_at.to_rfc3339(),
                 ],
             ).map_err(|e| ImError::Database(e.to_string()))?;
@@ -282,7 +531,7 @@ impl ImDatabase {
         
         let session = conn.query_row(
             "SELECT id, session_type, title, created_at, updated_at, 
-                    last_message_at, avatar_url, metadata
+                    last_message_at, avatar_url, metadata, channel_topic, channel_public
              FROM sessions WHERE id = ?1",
             [session_id],
             Self::row_to_conversation,
@@ -290,7 +539,7 @@ impl ImDatabase {
         
         if let Some(mut session) = session {
             // 加载参与者
-            session.participants = Self::get_participants_internal_sync(&conn, &session.id)?;
+            Self::load_members_sync(&conn, &mut session)?;
             Ok(Some(session))
         } else {
             Ok(None)
@@ -305,7 +554,7 @@ impl ImDatabase {
         
         let mut stmt = conn.prepare(
             "SELECT s.id, s.session_type, s.title, s.created_at, s.updated_at,
-                    s.last_message_at, s.avatar_url, s.metadata
+                    s.last_message_at, s.avatar_url, s.metadata, s.channel_topic, s.channel_public
              FROM sessions s
              JOIN participants p ON s.id = p.session_id
              WHERE p.user_id = ?1
@@ -326,15 +575,46 @@ impl ImDatabase {
         
         // 加载每个会话的参与者
         for session in &mut sessions {
-            session.participants = Self::get_participants_internal_sync(&conn, &session.id)?;
+            Self::load_members_sync(&conn, session)?;
         }
         
         Ok(sessions)
     }
     
+    /// 按名称或主题搜索公开频道，不要求是参与者
+    pub async fn search_public_channels(&self, query: &str, limit: usize) -> Result<Vec<Conversation>> {
+        let conn = self.conn.lock().await;
+        
+        let escaped = query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        let pattern = format!("%{}%", escaped);
+        let mut stmt = conn.prepare(
+            "SELECT id, session_type, title, created_at, updated_at,
+                    last_message_at, avatar_url, metadata, channel_topic, channel_public
+             FROM sessions
+             WHERE session_type = 'channel' AND channel_public = 1
+             AND (title LIKE ?1 ESCAPE '\\' OR channel_topic LIKE ?1 ESCAPE '\\')
+             ORDER BY updated_at DESC
+             LIMIT ?2"
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        
+        let channels: Result<Vec<_>> = stmt
+            .query_map(rusqlite::params![pattern, limit as i64], Self::row_to_conversation)
+            .map_err(|e| ImError::Database(e.to_string()))?
+            .map(|r| r.map_err(|e| ImError::Database(e.to_string())))
+            .collect();
+        
+        let mut channels = channels?;
+        for channel in &mut channels {
+            Self::load_members_sync(&conn, channel)?;
+        }
+        
+        Ok(channels)
+    }
+    
     /// 更新会话
     pub async fn update_session(&self, session: &Conversation) -> Result<()> {
         let conn = self.conn.lock().await;
+        let (channel_topic, channel_public) = Self::channel_columns(&session.conversation_type);
         
         conn.execute(
             "UPDATE sessions SET
@@ -342,7 +622,9 @@ impl ImDatabase {
              updated_at = ?3,
              last_message_at = ?4,
              avatar_url = ?5,
-             metadata = ?6
+             metadata = ?6,
+             channel_topic = ?7,
+             channel_public = ?8
              WHERE id = ?1",
             rusqlite::params![
                 session.id,
@@ -351,6 +633,8 @@ impl ImDatabase {
                 session.last_message_at.map(|t| t.to_rfc3339()),
                 session.avatar_url,
                 serde_json::to_string(&session.metadata).unwrap_or_default(),
+                channel_topic,
+                channel_public,
             ],
         ).map_err(|e| ImError::Database(e.to_string()))?;
         
@@ -386,12 +670,13 @@ impl ImDatabase {
         
         conn.execute(
             "INSERT INTO messages (id, session_id, sender_id, content_type, content, 
-                                  timestamp, status, reply_to, read_by, metadata)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                                  timestamp, status, reply_to, read_by, metadata, signature, detected_language)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
              ON CONFLICT(id) DO UPDATE SET
              status = excluded.status,
              content = excluded.content,
-             read_by = excluded.read_by",
+             read_by = excluded.read_by,
+             detected_language = excluded.detected_language",
             rusqlite::params![
                 message.id,
                 message.conversation_id,
@@ -403,6 +688,8 @@ impl ImDatabase {
                 reply_to,
                 serde_json::to_string(&message.read_by).unwrap_or_default(),
                 serde_json::to_string(&message.metadata).unwrap_or_default(),
+                message.signature,
+                message.detected_language,
             ],
         ).map_err(|e| ImError::Database(e.to_string()))?;
         
@@ -415,13 +702,84 @@ impl ImDatabase {
         Ok(())
     }
     
+    /// 批量保存消息（用于导入历史）
+    ///
+    /// 所有插入在同一事务内复用一条预编译语句执行。已存在的消息 ID 跳过而不覆盖，
+    /// 单条失败记录在结果中，不影响其余消息。
+    pub async fn batch_save_messages(&self, messages: &[Message]) -> Result<BatchSaveResult> {
+        let mut result = BatchSaveResult::default();
+        let mut latest: HashMap<&str, DateTime<Utc>> = HashMap::new();
+        
+        let mut conn = self.conn.lock().await;
+        let tx = conn.transaction().map_err(|e| ImError::Database(e.to_string()))?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR IGNORE INTO messages (id, session_id, sender_id, content_type, content, 
+                                                timestamp, status, reply_to, read_by, metadata, signature, detected_language)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)"
+            ).map_err(|e| ImError::Database(e.to_string()))?;
+            
+            for message in messages {
+                let content_json = match serde_json::to_string(&message.content) {
+                    Ok(json) => json,
+                    Err(e) => {
+                        result.record_failure(message, ImError::Serialization(e.to_string()));
+                        continue;
+                    }
+                };
+                let reply_to = match &message.content {
+                    MessageContent::Reply { reply_to, .. } => Some(reply_to.as_str()),
+                    _ => None,
+                };
+                
+                let inserted = stmt.execute(rusqlite::params![
+                    message.id,
+                    message.conversation_id,
+                    message.sender_id,
+                    message.content.content_type(),
+                    content_json,
+                    message.created_at.to_rfc3339(),
+                    "sent",
+                    reply_to,
+                    serde_json::to_string(&message.read_by).unwrap_or_default(),
+                    serde_json::to_string(&message.metadata).unwrap_or_default(),
+                    message.signature,
+                    message.detected_language,
+                ]);
+                match inserted {
+                    Ok(0) => result.skipped_duplicates += 1,
+                    Ok(_) => {
+                        result.inserted += 1;
+                        let time = latest.entry(message.conversation_id.as_str())
+                            .or_insert(message.created_at);
+                        *time = (*time).max(message.created_at);
+                    }
+                    Err(e) => result.record_failure(message, ImError::Database(e.to_string())),
+                }
+            }
+            
+            // 会话时间只向后推进，导入较早的历史不会覆盖最新消息时间
+            let mut update = tx.prepare(
+                "UPDATE sessions SET updated_at = ?1, last_message_at = ?1
+                 WHERE id = ?2 AND (last_message_at IS NULL OR last_message_at < ?1)"
+            ).map_err(|e| ImError::Database(e.to_string()))?;
+            for (conversation_id, time) in &latest {
+                update.execute(rusqlite::params![time.to_rfc3339(), conversation_id])
+                    .map_err(|e| ImError::Database(e.to_string()))?;
+            }
+        }
+        tx.commit().map_err(|e| ImError::Database(e.to_string()))?;
+        
+        Ok(result)
+    }
+    
     /// 获取单条消息
     pub async fn get_message(&self, message_id: &str) -> Result<Option<Message>> {
         let conn = self.conn.lock().await;
         
         let message = conn.query_row(
             "SELECT id, session_id, sender_id, content_type, content, timestamp, 
-                    status, reply_to, read_by, metadata
+                    status, reply_to, read_by, metadata, signature, detected_language
              FROM messages WHERE id = ?1",
             [message_id],
             Self::row_to_message,
@@ -439,7 +797,7 @@ impl ImDatabase {
         let messages: Result<Vec<Message>> = if let Some(before_time) = before {
             let mut stmt = conn.prepare(
                 "SELECT id, session_id, sender_id, content_type, content, timestamp,
-                        status, reply_to, read_by, metadata
+                        status, reply_to, read_by, metadata, signature, detected_language
                  FROM messages 
                  WHERE session_id = ?1 AND timestamp < ?2
                  ORDER BY timestamp DESC
@@ -455,7 +813,7 @@ impl ImDatabase {
         } else {
             let mut stmt = conn.prepare(
                 "SELECT id, session_id, sender_id, content_type, content, timestamp,
-                        status, reply_to, read_by, metadata
+                        status, reply_to, read_by, metadata, signature, detected_language
                  FROM messages 
                  WHERE session_id = ?1
                  ORDER BY timestamp DESC
@@ -487,7 +845,7 @@ impl ImDatabase {
         let messages: Result<Vec<Message>> = if let Some(sid) = session_id {
             let mut stmt = conn.prepare(
                 "SELECT id, session_id, sender_id, content_type, content, timestamp,
-                        status, reply_to, read_by, metadata
+                        status, reply_to, read_by, metadata, signature, detected_language
                  FROM messages 
                  WHERE session_id = ?1 AND content LIKE ?2
                  ORDER BY timestamp DESC
@@ -503,7 +861,7 @@ impl ImDatabase {
         } else {
             let mut stmt = conn.prepare(
                 "SELECT id, session_id, sender_id, content_type, content, timestamp,
-                        status, reply_to, read_by, metadata
+                        status, reply_to, read_by, metadata, signature, detected_language
                  FROM messages 
                  WHERE content LIKE ?1
                  ORDER BY timestamp DESC
@@ -521,6 +879,209 @@ impl ImDatabase {
         messages
     }
     
+    // ===== 全文索引 (FTS5) =====
+    
+    /// 创建（或按新分词参数重建）消息全文索引
+    ///
+    /// `fts_options` 为 FTS5 表选项，如 `tokenize = 'trigram'`。索引通过触发器与
+    /// messages 表保持同步；参数变化时删除旧表并从已有消息回填。返回是否（重）建了索引。
+    pub async fn ensure_message_fts(&self, fts_options: &str) -> Result<bool> {
+        let conn = self.conn.lock().await;
+        let columns = format!("fts5(body, {})", fts_options);
+        
+        let existing: Option<String> = conn.query_row(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'messages_fts'",
+            [],
+            |row| row.get(0),
+        ).optional().map_err(|e| ImError::Database(e.to_string()))?;
+        
+        if existing.as_deref().is_some_and(|sql| sql.contains(&columns)) {
+            return Ok(false);
+        }
+        
+        conn.execute_batch(&format!(
+            "DROP TABLE IF EXISTS messages_fts;
+             DROP TRIGGER IF EXISTS messages_fts_ai;
+             DROP TRIGGER IF EXISTS messages_fts_au;
+             DROP TRIGGER IF EXISTS messages_fts_ad;
+             CREATE VIRTUAL TABLE messages_fts USING {columns};
+             CREATE TRIGGER messages_fts_ai AFTER INSERT ON messages
+             WHEN {new_text} IS NOT NULL BEGIN
+                 INSERT INTO messages_fts (rowid, body) VALUES (new.rowid, {new_text});
+             END;
+             CREATE TRIGGER messages_fts_au AFTER UPDATE OF content ON messages BEGIN
+                 DELETE FROM messages_fts WHERE rowid = old.rowid;
+                 INSERT INTO messages_fts (rowid, body)
+                 SELECT new.rowid, {new_text} WHERE {new_text} IS NOT NULL;
+             END;
+             CREATE TRIGGER messages_fts_ad AFTER DELETE ON messages BEGIN
+                 DELETE FROM messages_fts WHERE rowid = old.rowid;
+             END;
+             INSERT INTO messages_fts (rowid, body)
+             SELECT rowid, {text} FROM messages WHERE {text} IS NOT NULL;",
+            columns = columns,
+            text = message_text_sql("content"),
+            new_text = message_text_sql("new.content"),
+        )).map_err(|e| ImError::Database(e.to_string()))?;
+        
+        Ok(true)
+    }
+    
+    /// 重建消息全文索引
+    ///
+    /// 先按 messages 表补齐缺失、删除孤立和过期的索引文档（进程在更新中途崩溃时可能出现），
+    /// 再执行 FTS5 `rebuild` 重建整个倒排索引。索引不存在时返回错误。
+    pub async fn rebuild_fts_index(&self) -> Result<IndexRebuildReport> {
+        let started = std::time::Instant::now();
+        let mut conn = self.conn.lock().await;
+        if !Self::fts_exists(&conn)? {
+            return Err(ImError::Database("Message FTS index has not been created".to_string()));
+        }
+        
+        let text = message_text_sql("m.content");
+        let tx = conn.transaction().map_err(|e| ImError::Database(e.to_string()))?;
+        let removed = tx.execute(
+            &format!(
+                "DELETE FROM messages_fts WHERE rowid IN (
+                     SELECT f.rowid FROM messages_fts f LEFT JOIN messages m ON m.rowid = f.rowid
+                     WHERE m.rowid IS NULL OR f.body IS NOT {text}
+                 )",
+                text = text
+            ),
+            [],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        let added = tx.execute(
+            &format!(
+                "INSERT INTO messages_fts (rowid, body)
+                 SELECT m.rowid, {text} FROM messages m
+                 WHERE {text} IS NOT NULL AND m.rowid NOT IN (SELECT rowid FROM messages_fts)",
+                text = text
+            ),
+            [],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        tx.execute("INSERT INTO messages_fts (messages_fts) VALUES ('rebuild')", [])
+            .map_err(|e| ImError::Database(e.to_string()))?;
+        let documents_indexed: i64 = tx
+            .query_row("SELECT COUNT(*) FROM messages_fts", [], |row| row.get(0))
+            .map_err(|e| ImError::Database(e.to_string()))?;
+        tx.commit().map_err(|e| ImError::Database(e.to_string()))?;
+        
+        Ok(IndexRebuildReport {
+            documents_indexed: documents_indexed as u64,
+            removed_documents: removed as u64,
+            added_documents: added as u64,
+            duration_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+    
+    /// 检查消息全文索引
+    ///
+    /// 运行 FTS5 `integrity-check`，并与 messages 表比对缺失、孤立和过期的文档。
+    pub async fn check_fts_integrity(&self) -> Result<FtsIntegrityReport> {
+        let conn = self.conn.lock().await;
+        if !Self::fts_exists(&conn)? {
+            return Ok(FtsIntegrityReport::default());
+        }
+        
+        let mut report = FtsIntegrityReport {
+            index_exists: true,
+            ..Default::default()
+        };
+        if let Err(e) = conn.execute("INSERT INTO messages_fts (messages_fts) VALUES ('integrity-check')", []) {
+            report.errors.push(e.to_string());
+        }
+        
+        let text = message_text_sql("m.content");
+        let count = |sql: &str| -> Result<u64> {
+            conn.query_row(sql, [], |row| row.get::<_, i64>(0))
+                .map(|n| n as u64)
+                .map_err(|e| ImError::Database(e.to_string()))
+        };
+        report.indexed_documents = count("SELECT COUNT(*) FROM messages_fts")?;
+        report.missing_documents = count(&format!(
+            "SELECT COUNT(*) FROM messages m
+             WHERE {text} IS NOT NULL AND m.rowid NOT IN (SELECT rowid FROM messages_fts)",
+            text = text
+        ))?;
+        report.orphaned_documents = count(
+            "SELECT COUNT(*) FROM messages_fts WHERE rowid NOT IN (SELECT rowid FROM messages)",
+        )?;
+        report.stale_documents = count(&format!(
+            "SELECT COUNT(*) FROM messages_fts f JOIN messages m ON m.rowid = f.rowid
+             WHERE f.body IS NOT {text}",
+            text = text
+        ))?;
+        
+        Ok(report)
+    }
+    
+    fn fts_exists(conn: &Connection) -> Result<bool> {
+        conn.query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'messages_fts'",
+            [],
+            |_| Ok(()),
+        )
+        .optional()
+        .map(|found| found.is_some())
+        .map_err(|e| ImError::Database(e.to_string()))
+    }
+    
+    /// 全文检索消息（`match_expr` 为 FTS5 MATCH 表达式），按相关度排序
+    pub async fn match_messages_fts(&self, match_expr: &str, session_id: Option<&str>, limit: usize)
+        -> Result<Vec<Message>>
+    {
+        self.query_messages_fts("messages_fts MATCH ?1", "f.rank", match_expr, session_id, limit).await
+    }
+    
+    /// 在全文索引的正文中做子串匹配（用于短于 n-gram 长度的查询）
+    pub async fn substring_messages_fts(&self, needle: &str, session_id: Option<&str>, limit: usize)
+        -> Result<Vec<Message>>
+    {
+        let escaped = needle.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        let pattern = format!("%{}%", escaped);
+        self.query_messages_fts("f.body LIKE ?1 ESCAPE '\\'", "m.timestamp DESC", &pattern, session_id, limit).await
+    }
+    
+    async fn query_messages_fts(&self, condition: &str, order: &str, arg: &str, session_id: Option<&str>, limit: usize)
+        -> Result<Vec<Message>>
+    {
+        let conn = self.conn.lock().await;
+        
+        let sql = format!(
+            "SELECT m.id, m.session_id, m.sender_id, m.content_type, m.content, m.timestamp,
+                    m.status, m.reply_to, m.read_by, m.metadata, m.signature, m.detected_language
+             FROM messages_fts f
+             JOIN messages m ON m.rowid = f.rowid
+             WHERE {} AND (?2 IS NULL OR m.session_id = ?2)
+             ORDER BY {}
+             LIMIT ?3",
+            condition, order
+        );
+        let mut stmt = conn.prepare(&sql).map_err(|e| ImError::Database(e.to_string()))?;
+        
+        let rows = stmt.query_map(
+            rusqlite::params![arg, session_id, limit as i64],
+            Self::row_to_message,
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        
+        rows.map(|r| r.map_err(|e| ImError::Database(e.to_string()))).collect()
+    }
+    
+    /// 取最近消息的文本（用于自动判断语言）
+    pub async fn recent_message_texts(&self, limit: usize) -> Result<Vec<String>> {
+        let conn = self.conn.lock().await;
+        
+        let sql = format!(
+            "SELECT {text} FROM messages WHERE {text} IS NOT NULL ORDER BY timestamp DESC LIMIT ?1",
+            text = message_text_sql("content"),
+        );
+        let mut stmt = conn.prepare(&sql).map_err(|e| ImError::Database(e.to_string()))?;
+        let rows = stmt.query_map([limit as i64], |row| row.get::<_, String>(0))
+            .map_err(|e| ImError::Database(e.to_string()))?;
+        
+        rows.map(|r| r.map_err(|e| ImError::Database(e.to_string()))).collect()
+    }
+    
     /// 删除消息
     pub async fn delete_message(&self, message_id: &str) -> Result<()> {
         let conn = self.conn.lock().await;
@@ -533,6 +1094,32 @@ impl ImDatabase {
         Ok(())
     }
     
+    // ===== Matrix 事件映射 =====
+    
+    /// 记录 Matrix 事件对应的消息，事件已记录时返回 false
+    pub async fn record_matrix_event(&self, event_id: &str, message_id: &str) -> Result<bool> {
+        let conn = self.conn.lock().await;
+        
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO matrix_events (event_id, message_id, created_at)
+             VALUES (?1, ?2, ?3)",
+            rusqlite::params![event_id, message_id, Utc::now().to_rfc3339()],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        
+        Ok(inserted > 0)
+    }
+    
+    /// 查询 Matrix 事件对应的消息 ID
+    pub async fn get_matrix_event_message(&self, event_id: &str) -> Result<Option<String>> {
+        let conn = self.conn.lock().await;
+        
+        conn.query_row(
+            "SELECT message_id FROM matrix_events WHERE event_id = ?1",
+            [event_id],
+            |row| row.get(0),
+        ).optional().map_err(|e| ImError::Database(e.to_string()))
+    }
+    
     // ===== 已读状态 =====
     
     /// 标记消息已读
@@ -624,8 +1211,16 @@ impl ImDatabase {
     }
     
     /// 列出会话（旧接口兼容）
-    pub async fn list_conversations(&self, user_id: &str) -> Result<Vec<Conversation>> {
-        self.list_sessions(user_id, 100, 0).await
+    ///
+    /// `label_filter` 非空时只返回带有其中任一标签（该用户自己的标签）的会话。
+    pub async fn list_conversations(&self, user_id: &str, label_filter: Vec<String>) -> Result<Vec<Conversation>> {
+        let mut conversations = self.list_sessions(user_id, 100, 0).await?;
+        if !label_filter.is_empty() {
+            let conn = self.conn.lock().await;
+            let labeled = Self::labeled_session_ids(&conn, user_id, &label_filter)?;
+            conversations.retain(|c| labeled.contains(&c.id));
+        }
+        Ok(conversations)
     }
     
     /// 更新会话（旧接口兼容）
@@ -658,80 +1253,360 @@ impl ImDatabase {
         Ok(())
     }
     
-    /// 获取用户资料
-    pub async fn get_user_profile(&self, user_id: &str) -> Result<Option<UserProfile>> {
+    /// 保存 Webhook
+    pub async fn save_webhook(&self, webhook: &Webhook) -> Result<()> {
         let conn = self.conn.lock().await;
         
-        let profile = conn.query_row(
-            "SELECT user_id, display_name FROM participants 
-             WHERE session_id = '__global__' AND user_id = ?1",
-            [user_id],
-            |row| {
-                Ok(UserProfile {
-                    user_id: row.get(0)?,
-                    display_name: row.get(1)?,
-                    avatar_url: None,
-                    status: UserStatus::Offline,
-                    last_seen_at: None,
-                })
-            },
-        ).optional().map_err(|e| ImError::Database(e.to_string()))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO webhooks (id, scope, url, secret, events, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                webhook.id,
+                GLOBAL_SESSION,
+                webhook.config.url,
+                webhook.config.secret,
+                serde_json::to_string(&webhook.config.events)?,
+                webhook.created_at.to_rfc3339(),
+            ],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
         
-        Ok(profile)
+        Ok(())
     }
     
-    // ===== 辅助方法 =====
-    
-    fn get_participants_internal_sync(
-        conn: &Connection, 
-        session_id: &str
-    ) -> Result<Vec<String>> {
+    /// 列出 Webhook（按创建时间）
+    pub async fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        let conn = self.conn.lock().await;
+        
         let mut stmt = conn.prepare(
-            "SELECT user_id FROM participants WHERE session_id = ?1"
+            "SELECT id, url, secret, events, created_at FROM webhooks ORDER BY created_at"
         ).map_err(|e| ImError::Database(e.to_string()))?;
-        
-        let participants: Result<Vec<String>> = stmt
-            .query_map([session_id], |row| row.get(0))
+        let rows: Vec<(String, String, String, String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)))
             .map_err(|e| ImError::Database(e.to_string()))?
-            .map(|r| r.map_err(|e| ImError::Database(e.to_string())))
-            .collect();
+            .collect::<rusqlite::Result<_>>()
+            .map_err(|e| ImError::Database(e.to_string()))?;
         
-        participants
+        rows.into_iter()
+            .map(|(id, url, secret, events, created_at)| {
+                Ok(Webhook {
+                    id,
+                    config: crate::webhook::ImWebhookConfig {
+                        url,
+                        secret,
+                        events: serde_json::from_str(&events)?,
+                    },
+                    created_at: DateTime::parse_from_rfc3339(&created_at)
+                        .map_err(|e| ImError::Serialization(e.to_string()))?
+                        .with_timezone(&Utc),
+                })
+            })
+            .collect()
     }
     
-    fn row_to_conversation(row: &rusqlite::Row) -> std::result::Result<Conversation, rusqlite::Error> {
-        let session_type_str: String = row.get(1)?;
-        let conversation_type = match session_type_str.as_str() {
-            "direct" => ConversationType::Direct,
-            "group" => ConversationType::Group,
-            "channel" => ConversationType::Channel,
-            _ => ConversationType::Direct,
-        };
+    /// 删除 Webhook，返回是否存在
+    pub async fn delete_webhook(&self, id: &WebhookId) -> Result<bool> {
+        let conn = self.conn.lock().await;
+        let removed = conn.execute("DELETE FROM webhooks WHERE id = ?1", [id])
+            .map_err(|e| ImError::Database(e.to_string()))?;
+        Ok(removed > 0)
+    }
+    
+    // ===== 会话标签 =====
+    
+    /// 为用户添加会话标签，已存在时保留原有颜色和添加时间
+    pub async fn add_label(&self, conversation_id: &str, label: &str, user_id: &str) -> Result<()> {
+        let label = normalize_label(label)?;
+        let conn = self.conn.lock().await;
         
-        let created_at_str: String = row.get(3)?;
-        let created_at = DateTime::parse_from_rfc3339(&created_at_str)
-            .map_err(|_| rusqlite::Error::InvalidColumnType(3, "created_at".to_string(), rusqlite::types::Type::Text))?
-            .with_timezone(&Utc);
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM sessions WHERE id = ?1)",
+            [conversation_id],
+            |row| row.get(0),
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        if !exists {
+            return Err(ImError::ConversationNotFound(conversation_id.to_string()));
+        }
         
-        let updated_at_str: String = row.get(4)?;
-        let updated_at = DateTime::parse_from_rfc3339(&updated_at_str)
-            .map_err(|_| rusqlite::Error::InvalidColumnType(4, "updated_at".to_string(), rusqlite::types::Type::Text))?
-            .with_timezone(&Utc);
+        conn.execute(
+            "INSERT OR IGNORE INTO conversation_labels (session_id, user_id, label, color, added_at)
+             VALUES (?1, ?2, ?3, NULL, ?4)",
+            rusqlite::params![conversation_id, user_id, label, Utc::now().to_rfc3339()],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
         
-        let last_message_at = row.get::<_, Option<String>>(5)?.map(|s| {
-            DateTime::parse_from_rfc3339(&s)
-                .map(|dt| dt.with_timezone(&Utc))
-                .map_err(|_| rusqlite::Error::InvalidColumnType(5, "last_message_at".to_string(), rusqlite::types::Type::Text))
-        }).transpose()?;
+        Ok(())
+    }
+    
+    /// 设置标签颜色，返回标签是否存在
+    pub async fn set_label_color(&self, conversation_id: &str, label: &str, user_id: &str, color: Option<&str>)
+        -> Result<bool>
+    {
+        let conn = self.conn.lock().await;
+        let updated = conn.execute(
+            "UPDATE conversation_labels SET color = ?4
+             WHERE session_id = ?1 AND user_id = ?2 AND label = ?3",
+            rusqlite::params![conversation_id, user_id, label.trim(), color],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        Ok(updated > 0)
+    }
+    
+    /// 移除用户的会话标签，返回标签是否存在
+    pub async fn remove_label(&self, conversation_id: &str, label: &str, user_id: &str) -> Result<bool> {
+        let conn = self.conn.lock().await;
+        let removed = conn.execute(
+            "DELETE FROM conversation_labels WHERE session_id = ?1 AND user_id = ?2 AND label = ?3",
+            rusqlite::params![conversation_id, user_id, label.trim()],
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        Ok(removed > 0)
+    }
+    
+    /// 列出会话上的所有标签（包含所有用户，按添加者和添加时间排序）
+    pub async fn list_labels(&self, conversation_id: &str) -> Result<Vec<ConversationLabel>> {
+        let conn = self.conn.lock().await;
         
-        let metadata_json: Option<String> = row.get(7)?;
-        let metadata = metadata_json.and_then(|s| serde_json::from_str(&s).ok()).unwrap_or_default();
+        let mut stmt = conn.prepare(
+            "SELECT label, user_id, added_at, color FROM conversation_labels
+             WHERE session_id = ?1
+             ORDER BY user_id, added_at, label"
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        let rows: Vec<(String, String, String, Option<String>)> = stmt
+            .query_map([conversation_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+            .map_err(|e| ImError::Database(e.to_string()))?
+            .collect::<rusqlite::Result<_>>()
+            .map_err(|e| ImError::Database(e.to_string()))?;
+        
+        rows.into_iter()
+            .map(|(label, added_by, added_at, color)| {
+                Ok(ConversationLabel {
+                    label,
+                    added_by,
+                    added_at: DateTime::parse_from_rfc3339(&added_at)
+                        .map_err(|e| ImError::Serialization(e.to_string()))?
+                        .with_timezone(&Utc),
+                    color,
+                })
+            })
+            .collect()
+    }
+    
+    /// 按标签查找用户的会话
+    pub async fn search_by_label(&self, label: &str, user_id: &str) -> Result<Vec<Conversation>> {
+        let conn = self.conn.lock().await;
+        
+        let mut stmt = conn.prepare(
+            "SELECT s.id, s.session_type, s.title, s.created_at, s.updated_at,
+                    s.last_message_at, s.avatar_url, s.metadata, s.channel_topic, s.channel_public
+             FROM sessions s
+             JOIN conversation_labels l ON s.id = l.session_id
+             WHERE l.user_id = ?1 AND l.label = ?2
+             ORDER BY s.updated_at DESC"
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        
+        let mut sessions = stmt
+            .query_map(rusqlite::params![user_id, label.trim()], Self::row_to_conversation)
+            .map_err(|e| ImError::Database(e.to_string()))?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(|e| ImError::Database(e.to_string()))?;
+        
+        for session in &mut sessions {
+            Self::load_members_sync(&conn, session)?;
+        }
+        
+        Ok(sessions)
+    }
+    
+    /// 带有任一给定标签的会话 ID
+    fn labeled_session_ids(conn: &Connection, user_id: &str, labels: &[String]) -> Result<HashSet<String>> {
+        let mut stmt = conn.prepare(
+            "SELECT session_id FROM conversation_labels WHERE user_id = ?1 AND label = ?2"
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        
+        let mut ids = HashSet::new();
+        for label in labels {
+            let rows = stmt
+                .query_map(rusqlite::params![user_id, label.trim()], |row| row.get::<_, String>(0))
+                .map_err(|e| ImError::Database(e.to_string()))?;
+            for id in rows {
+                ids.insert(id.map_err(|e| ImError::Database(e.to_string()))?);
+            }
+        }
+        Ok(ids)
+    }
+    
+    /// 会话统计
+    ///
+    /// 只统计用户消息（不含 AI 摘要）。`messages_per_day` 在固定窗口下按窗口天数计算，
+    /// `AllTime` 按首条消息至今的天数（至少 1 天）计算。
+    pub async fn get_conversation_statistics(&self, conversation_id: &str, window: TimeWindow)
+        -> Result<ConversationStats>
+    {
+        let now = Utc::now();
+        let since = window.days().map(|days| (now - chrono::Duration::days(days)).to_rfc3339());
+        let conn = self.conn.lock().await;
+        let db_err = |e: rusqlite::Error| ImError::Database(e.to_string());
+        
+        const FILTER: &str = "session_id = ?1 AND content_type != 'summary'
+             AND (?2 IS NULL OR julianday(timestamp) >= julianday(?2))";
+        let params = rusqlite::params![conversation_id, since];
+        
+        let (message_count, active_users, first_day): (i64, i64, Option<f64>) = conn.query_row(
+            &format!(
+                "SELECT COUNT(*), COUNT(DISTINCT sender_id), MIN(julianday(timestamp))
+                 FROM messages WHERE {}",
+                FILTER
+            ),
+            params,
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        ).map_err(db_err)?;
+        if message_count == 0 {
+            return Ok(ConversationStats::default());
+        }
+        
+        let most_active_user: String = conn.query_row(
+            &format!(
+                "SELECT sender_id FROM messages WHERE {}
+                 GROUP BY sender_id ORDER BY COUNT(*) DESC, sender_id LIMIT 1",
+                FILTER
+            ),
+            params,
+            |row| row.get(0),
+        ).map_err(db_err)?;
+        
+        // 相邻消息按时间排序，只统计发送方发生变化的间隔
+        let avg_response_time_secs: Option<f64> = conn.query_row(
+            &format!(
+                "SELECT AVG(gap) FROM (
+                     SELECT (julianday(timestamp) - julianday(LAG(timestamp) OVER w)) * 86400.0 AS gap,
+                            sender_id,
+                            LAG(sender_id) OVER w AS prev_sender
+                     FROM messages WHERE {}
+                     WINDOW w AS (ORDER BY julianday(timestamp))
+                 ) WHERE prev_sender IS NOT NULL AND prev_sender != sender_id",
+                FILTER
+            ),
+            params,
+            |row| row.get(0),
+        ).map_err(db_err)?;
+        
+        let peak_hour: i64 = conn.query_row(
+            &format!(
+                "SELECT CAST(strftime('%H', timestamp) AS INTEGER) AS hour FROM messages WHERE {}
+                 GROUP BY hour ORDER BY COUNT(*) DESC, hour LIMIT 1",
+                FILTER
+            ),
+            params,
+            |row| row.get(0),
+        ).map_err(db_err)?;
+        
+        let days = match window.days() {
+            Some(days) => days as f64,
+            None => {
+                let now_day = now.timestamp() as f64 / 86400.0 + 2440587.5;
+                (now_day - first_day.unwrap_or(now_day)).max(1.0)
+            }
+        };
+        
+        Ok(ConversationStats {
+            message_count: message_count as u64,
+            active_users: active_users as u32,
+            messages_per_day: message_count as f64 / days,
+            most_active_user,
+            avg_response_time_secs: avg_response_time_secs.unwrap_or(0.0),
+            peak_hour: peak_hour as u8,
+        })
+    }
+    
+    /// 获取用户资料
+    pub async fn get_user_profile(&self, user_id: &str) -> Result<Option<UserProfile>> {
+        let conn = self.conn.lock().await;
+        
+        let profile = conn.query_row(
+            "SELECT user_id, display_name FROM participants 
+             WHERE session_id = '__global__' AND user_id = ?1",
+            [user_id],
+            |row| {
+                Ok(UserProfile {
+                    user_id: row.get(0)?,
+                    display_name: row.get(1)?,
+                    avatar_url: None,
+                    status: UserStatus::Offline,
+                    last_seen_at: None,
+                })
+            },
+        ).optional().map_err(|e| ImError::Database(e.to_string()))?;
+        
+        Ok(profile)
+    }
+    
+    // ===== 辅助方法 =====
+    
+    /// 加载参与者及管理员
+    fn load_members_sync(conn: &Connection, session: &mut Conversation) -> Result<()> {
+        let mut stmt = conn.prepare(
+            "SELECT user_id, role FROM participants WHERE session_id = ?1"
+        ).map_err(|e| ImError::Database(e.to_string()))?;
+        
+        let members: Result<Vec<(String, Option<String>)>> = stmt
+            .query_map([&session.id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| ImError::Database(e.to_string()))?
+            .map(|r| r.map_err(|e| ImError::Database(e.to_string())))
+            .collect();
+        
+        session.participants.clear();
+        session.admins.clear();
+        for (user_id, role) in members? {
+            if role.as_deref() == Some("admin") {
+                session.admins.push(user_id.clone());
+            }
+            session.participants.push(user_id);
+        }
+        Ok(())
+    }
+    
+    /// 频道主题和公开标记对应的列值，非频道会话为 (NULL, 0)
+    fn channel_columns(conversation_type: &ConversationType) -> (Option<&str>, bool) {
+        match conversation_type {
+            ConversationType::Channel { topic, is_public } => (Some(topic.as_str()), *is_public),
+            _ => (None, false),
+        }
+    }
+    
+    fn row_to_conversation(row: &rusqlite::Row) -> std::result::Result<Conversation, rusqlite::Error> {
+        let session_type_str: String = row.get(1)?;
+        let conversation_type = match session_type_str.as_str() {
+            "direct" => ConversationType::Direct,
+            "group" => ConversationType::Group,
+            "channel" => ConversationType::Channel {
+                topic: row.get::<_, Option<String>>(8)?.unwrap_or_default(),
+                is_public: row.get(9)?,
+            },
+            _ => ConversationType::Direct,
+        };
+        
+        let created_at_str: String = row.get(3)?;
+        let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+            .map_err(|_| rusqlite::Error::InvalidColumnType(3, "created_at".to_string(), rusqlite::types::Type::Text))?
+            .with_timezone(&Utc);
+        
+        let updated_at_str: String = row.get(4)?;
+        let updated_at = DateTime::parse_from_rfc3339(&updated_at_str)
+            .map_err(|_| rusqlite::Error::InvalidColumnType(4, "updated_at".to_string(), rusqlite::types::Type::Text))?
+            .with_timezone(&Utc);
+        
+        let last_message_at = row.get::<_, Option<String>>(5)?.map(|s| {
+            DateTime::parse_from_rfc3339(&s)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|_| rusqlite::Error::InvalidColumnType(5, "last_message_at".to_string(), rusqlite::types::Type::Text))
+        }).transpose()?;
+        
+        let metadata_json: Option<String> = row.get(7)?;
+        let metadata = metadata_json.and_then(|s| serde_json::from_str(&s).ok()).unwrap_or_default();
         
         Ok(Conversation {
             id: row.get(0)?,
             conversation_type,
             name: row.get(2)?,
             participants: vec![], // 单独加载
+            admins: vec![],
             created_at,
             updated_at,
             last_message_at,
@@ -765,8 +1640,728 @@ impl ImDatabase {
             updated_at: None,
             read_by,
             metadata,
+            signature: row.get(10)?,
+            detected_language: row.get(11)?,
+        })
+    }
+}
+
+/// 从消息 content JSON 中提取可检索文本的 SQL 表达式
+///
+/// 文本消息取 `$.content.text`，引用回复取被包装的文本。
+fn message_text_sql(column: &str) -> String {
+    format!(
+        "coalesce(json_extract({c}, '$.content.text'), json_extract({c}, '$.content.content.content.text'))",
+        c = column
+    )
+}
+
+/// 标签最大长度（字符）
+const MAX_LABEL_LEN: usize = 64;
+
+/// 去除首尾空白并检查标签
+fn normalize_label(label: &str) -> Result<&str> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Err(ImError::InvalidMessage("Label must not be empty".to_string()));
+    }
+    if label.chars().count() > MAX_LABEL_LEN {
+        return Err(ImError::InvalidMessage(format!(
+            "Label is longer than {} characters: {}",
+            MAX_LABEL_LEN, label
+        )));
+    }
+    Ok(label)
+}
+
+// ===== 分片数据库 =====
+
+/// 分片根目录（位于 IM 数据目录下）
+const SHARD_DIR: &str = "im-shards";
+
+/// 分片布局文件
+const SHARD_LAYOUT_FILE: &str = "layout.json";
+
+/// 用户资料等全局数据所在的逻辑会话
+const GLOBAL_SESSION: &str = "__global__";
+
+/// 按会话分片的表及其会话列
+const SESSION_TABLES: &[(&str, &str)] = &[
+    ("sessions", "id"),
+    ("participants", "session_id"),
+    ("messages", "session_id"),
+    ("read_status", "session_id"),
+    ("conversation_labels", "session_id"),
+    ("webhooks", "scope"),
+];
+
+/// FNV-1a 哈希：跨进程、跨编译器版本稳定，分片位置不会漂移
+fn shard_hash(key: &str) -> u64 {
+    key.bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, b| (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+fn shard_index(key: &str, shard_count: usize) -> usize {
+    (shard_hash(key) % shard_count as u64) as usize
+}
+
+/// 分片布局（持久化到 `im-shards/layout.json`）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct ShardLayout {
+    /// 每次重新分片递增，对应 `im-shards/<generation>/` 目录
+    generation: u64,
+    shard_count: usize,
+}
+
+/// 单个分片
+struct Shard {
+    path: PathBuf,
+    db: ImDatabase,
+}
+
+/// 当前生效的分片集合
+struct ShardSet {
+    generation: u64,
+    shards: Vec<Shard>,
+}
+
+impl ShardSet {
+    fn route(&self, key: &str) -> &ImDatabase {
+        &self.shards[shard_index(key, self.shards.len())].db
+    }
+
+    fn get(&self, index: usize) -> Result<&Shard> {
+        self.shards
+            .get(index)
+            .ok_or_else(|| ImError::Other(format!("Shard {} does not exist", index)))
+    }
+}
+
+/// 重新分片期间被写入的键，切换布局前需要重新复制
+#[derive(Debug, Default)]
+struct DirtyKeys {
+    conversations: HashSet<String>,
+    matrix_events: HashSet<String>,
+}
+
+/// 重新分片报告
+#[derive(Debug, Clone, Default)]
+pub struct RebalanceReport {
+    pub old_shard_count: usize,
+    pub new_shard_count: usize,
+    /// 迁移的会话数
+    pub conversations: usize,
+    /// 迁移的消息数
+    pub messages: usize,
+    /// 迁移的 Matrix 事件映射数
+    pub matrix_events: usize,
+    /// 迁移期间有写入、切换前重新复制的会话数
+    pub recopied_conversations: usize,
+}
+
+/// 按会话分片的 IM 数据库
+///
+/// 会话及其参与者、消息、已读状态按 `hash(conversation_id) % shard_count`
+/// 存放在同一个 SQLite 文件中，Matrix 事件映射按事件 ID 分片。
+/// 接口与 [`ImDatabase`] 一致；不带会话 ID 的查询会遍历所有分片。
+///
+/// 分片以 WAL 模式打开，可以单独备份和 VACUUM。
+pub struct ShardedImDatabase {
+    root: PathBuf,
+    state: RwLock<ShardSet>,
+    /// 重新分片期间为 `Some`
+    dirty: std::sync::Mutex<Option<DirtyKeys>>,
+    rebalancing: Mutex<()>,
+}
+
+impl ShardedImDatabase {
+    /// 打开分片数据库
+    ///
+    /// 已有分片布局时沿用原布局（改变分片数请使用 [`Self::rebalance`]）。
+    pub fn open(data_dir: &Path, shard_count: usize) -> Result<Self> {
+        if shard_count == 0 {
+            return Err(ImError::Other("Shard count must be at least 1".to_string()));
+        }
+
+        let root = data_dir.join(SHARD_DIR);
+        std::fs::create_dir_all(&root)
+            .map_err(|e| ImError::Database(format!("Failed to create shard dir: {}", e)))?;
+
+        let layout = match std::fs::read(root.join(SHARD_LAYOUT_FILE)) {
+            Ok(bytes) => {
+                let layout: ShardLayout = serde_json::from_slice(&bytes)?;
+                if layout.shard_count != shard_count {
+                    tracing::warn!(
+                        "IM database has {} shards, ignoring requested {} (use rebalance to change)",
+                        layout.shard_count,
+                        shard_count
+                    );
+                }
+                layout
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let layout = ShardLayout { generation: 0, shard_count };
+                Self::write_layout(&root, layout)?;
+                layout
+            }
+            Err(e) => {
+                return Err(ImError::Database(format!("Failed to read shard layout: {}", e)))
+            }
+        };
+
+        let shards = Self::open_generation(&root, layout)?;
+        Ok(Self {
+            root,
+            state: RwLock::new(ShardSet { generation: layout.generation, shards }),
+            dirty: std::sync::Mutex::new(None),
+            rebalancing: Mutex::new(()),
+        })
+    }
+
+    fn generation_dir(root: &Path, generation: u64) -> PathBuf {
+        root.join(generation.to_string())
+    }
+
+    fn open_generation(root: &Path, layout: ShardLayout) -> Result<Vec<Shard>> {
+        let dir = Self::generation_dir(root, layout.generation);
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| ImError::Database(format!("Failed to create shard dir: {}", e)))?;
+
+        (0..layout.shard_count)
+            .map(|i| {
+                let path = dir.join(format!("shard-{}.db", i));
+                let db = ImDatabase::open_file(&path)?;
+                {
+                    let conn = db.conn.try_lock()
+                        .map_err(|_| ImError::Database("Failed to acquire lock".to_string()))?;
+                    conn.query_row("PRAGMA journal_mode=WAL", [], |row| row.get::<_, String>(0))?;
+                }
+                Ok(Shard { path, db })
+            })
+            .collect()
+    }
+
+    /// 先写临时文件再重命名，保证布局文件不会半写
+    fn write_layout(root: &Path, layout: ShardLayout) -> Result<()> {
+        let tmp = root.join(format!("{}.tmp", SHARD_LAYOUT_FILE));
+        std::fs::write(&tmp, serde_json::to_vec(&layout)?)
+            .and_then(|_| std::fs::rename(&tmp, root.join(SHARD_LAYOUT_FILE)))
+            .map_err(|e| ImError::Database(format!("Failed to write shard layout: {}", e)))
+    }
+
+    /// 当前分片数
+    pub async fn shard_count(&self) -> usize {
+        self.state.read().await.shards.len()
+    }
+
+    /// 会话所在的分片
+    pub async fn shard_for(&self, conversation_id: &str) -> usize {
+        shard_index(conversation_id, self.shard_count().await)
+    }
+
+    /// 各分片的数据库文件
+    pub async fn shard_paths(&self) -> Vec<PathBuf> {
+        self.state.read().await.shards.iter().map(|s| s.path.clone()).collect()
+    }
+
+    /// 备份单个分片（`VACUUM INTO`，得到一致的快照）
+    pub async fn backup_shard(&self, index: usize, dest: &Path) -> Result<()> {
+        let set = self.state.read().await;
+        let conn = set.get(index)?.db.conn.lock().await;
+        conn.execute("VACUUM INTO ?1", [dest.to_string_lossy().into_owned()])?;
+        Ok(())
+    }
+
+    /// 整理单个分片
+    pub async fn vacuum_shard(&self, index: usize) -> Result<()> {
+        let set = self.state.read().await;
+        let conn = set.get(index)?.db.conn.lock().await;
+        conn.execute_batch("VACUUM")?;
+        Ok(())
+    }
+
+    /// 记录重新分片期间写入的会话（调用时需持有分片读锁）
+    fn mark_conversation(&self, conversation_id: &str) {
+        if let Some(dirty) = self.dirty.lock().unwrap().as_mut() {
+            dirty.conversations.insert(conversation_id.to_string());
+        }
+    }
+
+    fn mark_matrix_event(&self, event_id: &str) {
+        if let Some(dirty) = self.dirty.lock().unwrap().as_mut() {
+            dirty.matrix_events.insert(event_id.to_string());
+        }
+    }
+
+    // ===== 会话操作 =====
+
+    /// 创建或更新会话
+    pub async fn create_session(&self, session: &Conversation) -> Result<()> {
+        let set = self.state.read().await;
+        self.mark_conversation(&session.id);
+        set.route(&session.id).create_session(session).await
+    }
+
+    /// 获取会话
+    pub async fn get_session(&self, session_id: &str) -> Result<Option<Conversation>> {
+        let set = self.state.read().await;
+        set.route(session_id).get_session(session_id).await
+    }
+
+    /// 列出用户的会话（合并所有分片后分页）
+    pub async fn list_sessions(&self, user_id: &str, limit: usize, offset: usize)
+        -> Result<Vec<Conversation>>
+    {
+        let set = self.state.read().await;
+        let mut sessions = vec![];
+        for shard in &set.shards {
+            sessions.extend(shard.db.list_sessions(user_id, limit + offset, 0).await?);
+        }
+        sessions.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(sessions.into_iter().skip(offset).take(limit).collect())
+    }
+
+    /// 按名称或主题搜索公开频道（遍历所有分片）
+    pub async fn search_public_channels(&self, query: &str, limit: usize) -> Result<Vec<Conversation>> {
+        let set = self.state.read().await;
+        let mut channels = vec![];
+        for shard in &set.shards {
+            channels.extend(shard.db.search_public_channels(query, limit).await?);
+        }
+        channels.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        channels.truncate(limit);
+        Ok(channels)
+    }
+
+    /// 更新会话
+    pub async fn update_session(&self, session: &Conversation) -> Result<()> {
+        let set = self.state.read().await;
+        self.mark_conversation(&session.id);
+        set.route(&session.id).update_session(session).await
+    }
+
+    /// 删除会话
+    pub async fn delete_session(&self, session_id: &str) -> Result<()> {
+        let set = self.state.read().await;
+        self.mark_conversation(session_id);
+        set.route(session_id).delete_session(session_id).await
+    }
+
+    // ===== 消息操作 =====
+
+    /// 保存消息
+    pub async fn save_message(&self, message: &Message) -> Result<()> {
+        let set = self.state.read().await;
+        self.mark_conversation(&message.conversation_id);
+        set.route(&message.conversation_id).save_message(message).await
+    }
+
+    /// 批量保存消息，按分片分组后各自在一个事务内写入
+    pub async fn batch_save_messages(&self, messages: &[Message]) -> Result<BatchSaveResult> {
+        let set = self.state.read().await;
+        let mut groups: Vec<Vec<Message>> = vec![vec![]; set.shards.len()];
+        for message in messages {
+            self.mark_conversation(&message.conversation_id);
+            groups[shard_index(&message.conversation_id, set.shards.len())].push(message.clone());
+        }
+        
+        let mut result = BatchSaveResult::default();
+        for (shard, group) in set.shards.iter().zip(&groups) {
+            if !group.is_empty() {
+                result.merge(shard.db.batch_save_messages(group).await?);
+            }
+        }
+        Ok(result)
+    }
+    
+    /// 获取单条消息（遍历所有分片）
+    pub async fn get_message(&self, message_id: &str) -> Result<Option<Message>> {
+        let set = self.state.read().await;
+        for shard in &set.shards {
+            if let Some(message) = shard.db.get_message(message_id).await? {
+                return Ok(Some(message));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 获取会话消息历史
+    pub async fn get_messages(&self, session_id: &str, before: Option<DateTime<Utc>>, limit: usize)
+        -> Result<Vec<Message>>
+    {
+        let set = self.state.read().await;
+        set.route(session_id).get_messages(session_id, before, limit).await
+    }
+
+    /// 搜索消息；未指定会话时合并所有分片的结果
+    pub async fn search_messages(&self, query: &str, session_id: Option<&str>, limit: usize)
+        -> Result<Vec<Message>>
+    {
+        let set = self.state.read().await;
+        if let Some(sid) = session_id {
+            return set.route(sid).search_messages(query, Some(sid), limit).await;
+        }
+
+        let mut messages = vec![];
+        for shard in &set.shards {
+            messages.extend(shard.db.search_messages(query, None, limit).await?);
+        }
+        messages.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        messages.truncate(limit);
+        Ok(messages)
+    }
+
+    /// 删除消息
+    pub async fn delete_message(&self, message_id: &str) -> Result<()> {
+        let set = self.state.read().await;
+        for shard in &set.shards {
+            if let Some(message) = shard.db.get_message(message_id).await? {
+                self.mark_conversation(&message.conversation_id);
+                return shard.db.delete_message(message_id).await;
+            }
+        }
+        Ok(())
+    }
+
+    // ===== Matrix 事件映射 =====
+
+    /// 记录 Matrix 事件对应的消息，事件已记录时返回 false
+    pub async fn record_matrix_event(&self, event_id: &str, message_id: &str) -> Result<bool> {
+        let set = self.state.read().await;
+        self.mark_matrix_event(event_id);
+        set.route(event_id).record_matrix_event(event_id, message_id).await
+    }
+
+    /// 查询 Matrix 事件对应的消息 ID
+    pub async fn get_matrix_event_message(&self, event_id: &str) -> Result<Option<String>> {
+        let set = self.state.read().await;
+        set.route(event_id).get_matrix_event_message(event_id).await
+    }
+
+    // ===== 已读状态 =====
+
+    /// 标记消息已读
+    pub async fn mark_as_read(&self, session_id: &str, user_id: &str, message_id: &str)
+        -> Result<()>
+    {
+        let set = self.state.read().await;
+        self.mark_conversation(session_id);
+        set.route(session_id).mark_as_read(session_id, user_id, message_id).await
+    }
+
+    /// 获取未读数
+    pub async fn get_unread_count(&self, session_id: &str, user_id: &str) -> Result<u64> {
+        let set = self.state.read().await;
+        set.route(session_id).get_unread_count(session_id, user_id).await
+    }
+
+    /// 标记消息已读（旧接口兼容）
+    pub async fn mark_message_read(&self, message_id: &str, user_id: &str) -> Result<()> {
+        let message = self.get_message(message_id).await?
+            .ok_or_else(|| ImError::Database(format!("Message not found: {}", message_id)))?;
+        self.mark_as_read(&message.conversation_id, user_id, message_id).await
+    }
+
+    /// 创建或更新会话（旧接口兼容）
+    pub async fn create_conversation(&self, conversation: &Conversation) -> Result<()> {
+        self.create_session(conversation).await
+    }
+
+    /// 获取会话（旧接口兼容）
+    pub async fn get_conversation(&self, id: &str) -> Result<Option<Conversation>> {
+        self.get_session(id).await
+    }
+
+    /// 列出会话（旧接口兼容，遍历所有分片）
+    pub async fn list_conversations(&self, user_id: &str, label_filter: Vec<String>) -> Result<Vec<Conversation>> {
+        let set = self.state.read().await;
+        let mut conversations = vec![];
+        for shard in &set.shards {
+            conversations.extend(shard.db.list_conversations(user_id, label_filter.clone()).await?);
+        }
+        conversations.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        conversations.truncate(100);
+        Ok(conversations)
+    }
+
+    /// 更新会话（旧接口兼容）
+    pub async fn update_conversation(&self, conversation: &Conversation) -> Result<()> {
+        self.update_session(conversation).await
+    }
+
+    /// 删除会话（旧接口兼容）
+    pub async fn delete_conversation(&self, id: &str) -> Result<()> {
+        self.delete_session(id).await
+    }
+
+    /// 保存用户资料
+    pub async fn save_user_profile(&self, profile: &UserProfile) -> Result<()> {
+        let set = self.state.read().await;
+        self.mark_conversation(GLOBAL_SESSION);
+        set.route(GLOBAL_SESSION).save_user_profile(profile).await
+    }
+
+    /// 会话统计
+    pub async fn get_conversation_statistics(&self, conversation_id: &str, window: TimeWindow)
+        -> Result<ConversationStats>
+    {
+        let set = self.state.read().await;
+        set.route(conversation_id).get_conversation_statistics(conversation_id, window).await
+    }
+
+    /// 获取用户资料
+    pub async fn get_user_profile(&self, user_id: &str) -> Result<Option<UserProfile>> {
+        let set = self.state.read().await;
+        set.route(GLOBAL_SESSION).get_user_profile(user_id).await
+    }
+
+    /// 保存 Webhook
+    pub async fn save_webhook(&self, webhook: &Webhook) -> Result<()> {
+        let set = self.state.read().await;
+        self.mark_conversation(GLOBAL_SESSION);
+        set.route(GLOBAL_SESSION).save_webhook(webhook).await
+    }
+
+    /// 列出 Webhook
+    pub async fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        let set = self.state.read().await;
+        set.route(GLOBAL_SESSION).list_webhooks().await
+    }
+
+    /// 删除 Webhook
+    pub async fn delete_webhook(&self, id: &WebhookId) -> Result<bool> {
+        let set = self.state.read().await;
+        self.mark_conversation(GLOBAL_SESSION);
+        set.route(GLOBAL_SESSION).delete_webhook(id).await
+    }
+
+    /// 添加会话标签
+    pub async fn add_label(&self, conversation_id: &str, label: &str, user_id: &str) -> Result<()> {
+        let set = self.state.read().await;
+        self.mark_conversation(conversation_id);
+        set.route(conversation_id).add_label(conversation_id, label, user_id).await
+    }
+
+    /// 设置标签颜色
+    pub async fn set_label_color(&self, conversation_id: &str, label: &str, user_id: &str, color: Option<&str>)
+        -> Result<bool>
+    {
+        let set = self.state.read().await;
+        self.mark_conversation(conversation_id);
+        set.route(conversation_id).set_label_color(conversation_id, label, user_id, color).await
+    }
+
+    /// 移除会话标签
+    pub async fn remove_label(&self, conversation_id: &str, label: &str, user_id: &str) -> Result<bool> {
+        let set = self.state.read().await;
+        self.mark_conversation(conversation_id);
+        set.route(conversation_id).remove_label(conversation_id, label, user_id).await
+    }
+
+    /// 列出会话标签
+    pub async fn list_labels(&self, conversation_id: &str) -> Result<Vec<ConversationLabel>> {
+        let set = self.state.read().await;
+        set.route(conversation_id).list_labels(conversation_id).await
+    }
+
+    /// 按标签查找用户的会话（遍历所有分片）
+    pub async fn search_by_label(&self, label: &str, user_id: &str) -> Result<Vec<Conversation>> {
+        let set = self.state.read().await;
+        let mut conversations = vec![];
+        for shard in &set.shards {
+            conversations.extend(shard.db.search_by_label(label, user_id).await?);
+        }
+        conversations.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(conversations)
+    }
+
+    // ===== 重新分片 =====
+
+    /// 迁移到 `new_shard_count` 个分片
+    ///
+    /// 1. 新布局写入新的 generation 目录，旧分片继续服务读写；
+    ///    借助 WAL，批量复制使用独立的只读连接，不阻塞在线操作
+    /// 2. 复制期间写入的会话被记录下来，短暂持有写锁重新复制这些会话后切换布局
+    /// 3. 删除旧 generation 目录
+    ///
+    /// 失败时丢弃新目录，旧布局保持不变。
+    pub async fn rebalance(&self, new_shard_count: usize) -> Result<RebalanceReport> {
+        if new_shard_count == 0 {
+            return Err(ImError::Other("Shard count must be at least 1".to_string()));
+        }
+        let _rebalancing = self.rebalancing.lock().await;
+
+        // 持有写锁开启写入记录，确保没有进行中的未记录写操作
+        let (old_generation, old_paths) = {
+            let set = self.state.write().await;
+            *self.dirty.lock().unwrap() = Some(DirtyKeys::default());
+            (set.generation, set.shards.iter().map(|s| s.path.clone()).collect::<Vec<_>>())
+        };
+        let layout = ShardLayout { generation: old_generation + 1, shard_count: new_shard_count };
+
+        let result = self.rebalance_into(layout, old_paths).await;
+        if result.is_err() {
+            *self.dirty.lock().unwrap() = None;
+            let _ = std::fs::remove_dir_all(Self::generation_dir(&self.root, layout.generation));
+            return result;
+        }
+
+        if let Err(e) = std::fs::remove_dir_all(Self::generation_dir(&self.root, old_generation)) {
+            tracing::warn!("Failed to remove old IM shard generation {}: {}", old_generation, e);
+        }
+        result
+    }
+
+    async fn rebalance_into(&self, layout: ShardLayout, old_paths: Vec<PathBuf>)
+        -> Result<RebalanceReport>
+    {
+        let new_shards = Self::open_generation(&self.root, layout)?;
+        let new_paths: Vec<PathBuf> = new_shards.iter().map(|s| s.path.clone()).collect();
+
+        // 第一遍：全量复制，不持有分片锁
+        let mut report = RebalanceReport {
+            old_shard_count: old_paths.len(),
+            new_shard_count: layout.shard_count,
+            ..Default::default()
+        };
+        let (paths, targets) = (old_paths.clone(), new_paths.clone());
+        report = tokio::task::spawn_blocking(move || {
+            copy_shards(&paths, &targets, None, &mut report)?;
+            Ok::<_, ImError>(report)
+        })
+        .await
+        .map_err(|e| ImError::Other(format!("Rebalance task failed: {}", e)))??;
+
+        // 第二遍：阻塞读写，只补复制迁移期间被写入的键，然后切换
+        let mut set = self.state.write().await;
+        let dirty = self.dirty.lock().unwrap().take().unwrap_or_default();
+        report = tokio::task::spawn_blocking(move || {
+            copy_shards(&old_paths, &new_paths, Some(&dirty), &mut report)?;
+            Ok::<_, ImError>(report)
         })
+        .await
+        .map_err(|e| ImError::Other(format!("Rebalance task failed: {}", e)))??;
+
+        Self::write_layout(&self.root, layout)?;
+        let old = std::mem::replace(
+            &mut *set,
+            ShardSet { generation: layout.generation, shards: new_shards },
+        );
+        drop(set);
+        drop(old);
+
+        tracing::info!(
+            "IM database rebalanced from {} to {} shards ({} conversations, {} messages)",
+            report.old_shard_count,
+            report.new_shard_count,
+            report.conversations,
+            report.messages
+        );
+        Ok(report)
+    }
+}
+
+/// 把旧分片中的数据按新布局复制到 `targets`
+///
+/// `only` 为 `Some` 时只复制其中的键，并先清除目标分片中这些会话的旧数据。
+fn copy_shards(
+    sources: &[PathBuf],
+    targets: &[PathBuf],
+    only: Option<&DirtyKeys>,
+    report: &mut RebalanceReport,
+) -> Result<()> {
+    let targets: Vec<Connection> = targets.iter().map(Connection::open).collect::<rusqlite::Result<_>>()?;
+    let transactions: Vec<_> = targets
+        .iter()
+        .map(|c| c.unchecked_transaction())
+        .collect::<rusqlite::Result<_>>()?;
+
+    for (index, source) in sources.iter().enumerate() {
+        let src = Connection::open_with_flags(source, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let owned_here = |key: &&String| shard_index(key, sources.len()) == index;
+
+        let conversations: Vec<String> = match only {
+            Some(dirty) => dirty.conversations.iter().filter(owned_here).cloned().collect(),
+            None => {
+                let mut stmt = src.prepare(
+                    "SELECT id FROM sessions
+                     UNION SELECT session_id FROM participants
+                     UNION SELECT session_id FROM messages
+                     UNION SELECT session_id FROM read_status
+                     UNION SELECT session_id FROM conversation_labels
+                     UNION SELECT scope FROM webhooks",
+                )?;
+                let ids = stmt.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<_>>()?;
+                ids
+            }
+        };
+
+        for conversation_id in &conversations {
+            let dst = &transactions[shard_index(conversation_id, transactions.len())];
+            for (table, column) in SESSION_TABLES {
+                if only.is_some() {
+                    dst.execute(&format!("DELETE FROM {} WHERE {} = ?1", table, column), [conversation_id])?;
+                }
+                let copied = copy_rows(&src, dst, table, column, conversation_id)?;
+                if only.is_none() && *table == "messages" {
+                    report.messages += copied;
+                }
+            }
+        }
+
+        match only {
+            Some(dirty) => {
+                report.recopied_conversations += conversations.len();
+                for event_id in dirty.matrix_events.iter().filter(owned_here) {
+                    let dst = &transactions[shard_index(event_id, transactions.len())];
+                    copy_rows(&src, dst, "matrix_events", "event_id", event_id)?;
+                }
+            }
+            None => {
+                report.conversations += conversations.len();
+                let mut stmt = src.prepare("SELECT event_id, message_id, created_at FROM matrix_events")?;
+                let mut rows = stmt.query([])?;
+                while let Some(row) = rows.next()? {
+                    let event_id: String = row.get(0)?;
+                    let dst = &transactions[shard_index(&event_id, transactions.len())];
+                    dst.execute(
+                        "INSERT OR IGNORE INTO matrix_events (event_id, message_id, created_at)
+                         VALUES (?1, ?2, ?3)",
+                        rusqlite::params![event_id, row.get::<_, String>(1)?, row.get::<_, String>(2)?],
+                    )?;
+                    report.matrix_events += 1;
+                }
+            }
+        }
+    }
+
+    for tx in transactions {
+        tx.commit()?;
+    }
+    Ok(())
+}
+
+/// 复制 `table` 中 `column = key` 的行，返回行数
+fn copy_rows(src: &Connection, dst: &Connection, table: &str, column: &str, key: &str) -> Result<usize> {
+    let mut select = src.prepare_cached(&format!("SELECT * FROM {} WHERE {} = ?1", table, column))?;
+    let columns: Vec<String> = select.column_names().into_iter().map(String::from).collect();
+    let mut insert = dst.prepare_cached(&format!(
+        "INSERT OR REPLACE INTO {} ({}) VALUES ({})",
+        table,
+        columns.join(", "),
+        vec!["?"; columns.len()].join(", ")
+    ))?;
+
+    let mut rows = select.query([key])?;
+    let mut copied = 0;
+    while let Some(row) = rows.next()? {
+        let values = (0..columns.len())
+            .map(|i| row.get::<_, rusqlite::types::Value>(i))
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        insert.execute(rusqlite::params_from_iter(values))?;
+        copied += 1;
     }
+    Ok(copied)
 }
 
 #[cfg(test)]
@@ -785,6 +2380,7 @@ mod tests {
             conversation_type: ConversationType::Group,
             name: Some("Test Session".to_string()),
             participants: vec!["user1".to_string(), "user2".to_string()],
+            admins: Vec::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
             last_message_at: None,
@@ -806,6 +2402,51 @@ mod tests {
         assert_eq!(sessions.len(), 1);
     }
     
+    #[tokio::test]
+    async fn test_conversation_labels() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = ImDatabase::open(temp_dir.path()).unwrap();
+        for id in ["conv-a", "conv-b"] {
+            db.create_session(&Conversation {
+                id: id.to_string(),
+                conversation_type: ConversationType::Group,
+                name: None,
+                participants: vec!["alice".to_string(), "bob".to_string()],
+                admins: Vec::new(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                last_message_at: None,
+                avatar_url: None,
+                metadata: serde_json::json!({}),
+            }).await.unwrap();
+        }
+        
+        db.add_label("conv-a", " work ", "alice").await.unwrap();
+        db.add_label("conv-a", "work", "alice").await.unwrap();
+        db.add_label("conv-b", "family", "alice").await.unwrap();
+        db.add_label("conv-b", "work", "bob").await.unwrap();
+        assert!(db.set_label_color("conv-a", "work", "alice", Some("#ff8800")).await.unwrap());
+        assert!(matches!(db.add_label("missing", "work", "alice").await, Err(ImError::ConversationNotFound(_))));
+        assert!(db.add_label("conv-a", "  ", "alice").await.is_err());
+        
+        let labels = db.list_labels("conv-a").await.unwrap();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].label, "work");
+        assert_eq!(labels[0].added_by, "alice");
+        assert_eq!(labels[0].color.as_deref(), Some("#ff8800"));
+        
+        // 标签按用户隔离
+        let found = db.search_by_label("work", "alice").await.unwrap();
+        assert_eq!(found.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(), vec!["conv-a"]);
+        let filtered = db.list_conversations("bob", vec!["work".to_string()]).await.unwrap();
+        assert_eq!(filtered.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(), vec!["conv-b"]);
+        assert_eq!(db.list_conversations("alice", vec![]).await.unwrap().len(), 2);
+        
+        assert!(db.remove_label("conv-a", "work", "alice").await.unwrap());
+        assert!(!db.remove_label("conv-a", "work", "alice").await.unwrap());
+        assert!(db.search_by_label("work", "alice").await.unwrap().is_empty());
+    }
+    
     #[tokio::test]
     async fn test_message_operations() {
         let temp_dir = TempDir::new().unwrap();
@@ -817,6 +2458,7 @@ mod tests {
             conversation_type: ConversationType::Direct,
             name: None,
             participants: vec!["user1".to_string(), "user2".to_string()],
+            admins: Vec::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
             last_message_at: None,
@@ -847,6 +2489,46 @@ mod tests {
         assert_eq!(results.len(), 1);
     }
     
+    #[tokio::test]
+    async fn test_fts_integrity_and_rebuild() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = ImDatabase::open(temp_dir.path()).unwrap();
+        assert!(!db.check_fts_integrity().await.unwrap().index_exists);
+        assert!(db.rebuild_fts_index().await.is_err());
+        
+        db.create_session(&test_conversation("c1")).await.unwrap();
+        db.ensure_message_fts("tokenize = 'trigram'").await.unwrap();
+        let mut ids = Vec::new();
+        for text in ["deploy on friday", "rollback plan", "audit notes"] {
+            let message = Message::new("c1".to_string(), "alice".to_string(), MessageContent::Text {
+                text: text.to_string(),
+            });
+            db.save_message(&message).await.unwrap();
+            ids.push(message.id);
+        }
+        assert!(db.check_fts_integrity().await.unwrap().is_healthy());
+        
+        // 模拟崩溃留下的不一致：缺失、孤立、过期各一条
+        {
+            let conn = db.conn.lock().await;
+            conn.execute_batch(&format!(
+                "DELETE FROM messages_fts WHERE rowid = (SELECT rowid FROM messages WHERE id = '{}');
+                 UPDATE messages_fts SET body = 'garbage' WHERE rowid = (SELECT rowid FROM messages WHERE id = '{}');
+                 INSERT INTO messages_fts (rowid, body) VALUES (999, 'orphan');",
+                ids[0], ids[1]
+            )).unwrap();
+        }
+        let report = db.check_fts_integrity().await.unwrap();
+        assert!(!report.is_healthy());
+        assert_eq!((report.missing_documents, report.orphaned_documents, report.stale_documents), (1, 1, 1));
+        
+        let rebuilt = db.rebuild_fts_index().await.unwrap();
+        assert_eq!(rebuilt.documents_indexed, 3);
+        assert_eq!((rebuilt.removed_documents, rebuilt.added_documents), (2, 2));
+        assert!(db.check_fts_integrity().await.unwrap().is_healthy());
+        assert_eq!(db.match_messages_fts("\"friday\"", None, 10).await.unwrap().len(), 1);
+    }
+    
     #[tokio::test]
     async fn test_read_status() {
         let temp_dir = TempDir::new().unwrap();
@@ -858,6 +2540,7 @@ mod tests {
             conversation_type: ConversationType::Direct,
             name: None,
             participants: vec!["user1".to_string(), "user2".to_string()],
+            admins: Vec::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
             last_message_at: None,
@@ -884,4 +2567,161 @@ mod tests {
         let count = db.get_unread_count("session-1", "user2").await.unwrap();
         assert_eq!(count, 0);
     }
+    
+    fn test_conversation(id: &str) -> Conversation {
+        Conversation {
+            id: id.to_string(),
+            conversation_type: ConversationType::Direct,
+            name: None,
+            participants: vec!["user1".to_string(), "user2".to_string()],
+            admins: Vec::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_message_at: None,
+            avatar_url: None,
+            metadata: serde_json::json!({}),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_sharded_routing() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = ImDatabase::shard_by_conversation(temp_dir.path(), 4).unwrap();
+        assert_eq!(db.shard_count().await, 4);
+        
+        let mut message_ids = vec![];
+        for i in 0..12 {
+            let session_id = format!("session-{}", i);
+            db.create_session(&test_conversation(&session_id)).await.unwrap();
+            let message = Message::new(
+                session_id.clone(),
+                "user1".to_string(),
+                MessageContent::Text { text: format!("Hello {}", i) },
+            );
+            db.save_message(&message).await.unwrap();
+            message_ids.push(message.id);
+        }
+        
+        // 会话分布在多个分片上
+        let mut used = HashSet::new();
+        for i in 0..12 {
+            used.insert(db.shard_for(&format!("session-{}", i)).await);
+        }
+        assert!(used.len() > 1);
+        
+        // 跨分片查询
+        assert_eq!(db.list_sessions("user1", 100, 0).await.unwrap().len(), 12);
+        assert_eq!(db.list_sessions("user1", 5, 10).await.unwrap().len(), 2);
+        assert_eq!(db.search_messages("Hello", None, 100).await.unwrap().len(), 12);
+        assert!(db.get_message(&message_ids[7]).await.unwrap().is_some());
+        
+        db.mark_message_read(&message_ids[3], "user2").await.unwrap();
+        assert_eq!(db.get_unread_count("session-3", "user2").await.unwrap(), 0);
+        
+        // 分片可以单独备份
+        let backup = temp_dir.path().join("shard-0.bak");
+        db.backup_shard(0, &backup).await.unwrap();
+        db.vacuum_shard(0).await.unwrap();
+        assert!(backup.exists());
+        assert!(db.backup_shard(4, &temp_dir.path().join("x.bak")).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_sharded_rebalance() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = ShardedImDatabase::open(temp_dir.path(), 2).unwrap();
+        let old_paths = db.shard_paths().await;
+        
+        for i in 0..10 {
+            let session_id = format!("session-{}", i);
+            db.create_session(&test_conversation(&session_id)).await.unwrap();
+            for j in 0..3 {
+                let message = Message::new(
+                    session_id.clone(),
+                    "user1".to_string(),
+                    MessageContent::Text { text: format!("msg {}-{}", i, j) },
+                );
+                db.save_message(&message).await.unwrap();
+            }
+        }
+        assert!(db.record_matrix_event("$event", "m1").await.unwrap());
+        
+        let report = db.rebalance(5).await.unwrap();
+        assert_eq!(report.old_shard_count, 2);
+        assert_eq!(report.new_shard_count, 5);
+        assert_eq!(report.conversations, 10);
+        assert_eq!(report.messages, 30);
+        assert_eq!(report.matrix_events, 1);
+        
+        assert_eq!(db.shard_count().await, 5);
+        assert!(old_paths.iter().all(|p| !p.exists()));
+        for i in 0..10 {
+            let session_id = format!("session-{}", i);
+            assert!(db.get_session(&session_id).await.unwrap().is_some());
+            assert_eq!(db.get_messages(&session_id, None, 10).await.unwrap().len(), 3);
+        }
+        assert_eq!(db.get_matrix_event_message("$event").await.unwrap(), Some("m1".to_string()));
+        assert!(!db.record_matrix_event("$event", "m1").await.unwrap());
+        
+        // 重新打开时沿用持久化的布局
+        drop(db);
+        let reopened = ShardedImDatabase::open(temp_dir.path(), 2).unwrap();
+        assert_eq!(reopened.shard_count().await, 5);
+        assert_eq!(reopened.list_sessions("user1", 100, 0).await.unwrap().len(), 10);
+    }
+    
+    #[tokio::test]
+    async fn test_conversation_statistics() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = ImDatabase::open(temp_dir.path()).unwrap();
+        db.create_session(&test_conversation("stats")).await.unwrap();
+        
+        let base = (Utc::now() - chrono::Duration::days(1))
+            .date_naive()
+            .and_hms_opt(10, 0, 0)
+            .unwrap()
+            .and_utc();
+        let at = |sender: &str, secs: i64| {
+            let mut message = Message::new(
+                "stats".to_string(),
+                sender.to_string(),
+                MessageContent::Text { text: "hi".to_string() },
+            );
+            message.created_at = base + chrono::Duration::seconds(secs);
+            message
+        };
+        for message in [
+            at("carol", -40 * 86400),
+            at("alice", 0),
+            at("bob", 60),
+            at("alice", 90),
+            at("alice", 100),
+        ] {
+            db.save_message(&message).await.unwrap();
+        }
+        // AI 摘要不计入统计
+        let mut summary = at("cis:summary", 200);
+        summary.content = MessageContent::Summary {
+            text: "summary".to_string(),
+            message_range: ("a".to_string(), "b".to_string()),
+            generated_at: Utc::now(),
+        };
+        db.save_message(&summary).await.unwrap();
+        
+        let week = db.get_conversation_statistics("stats", TimeWindow::Last7Days).await.unwrap();
+        assert_eq!(week.message_count, 4);
+        assert_eq!(week.active_users, 2);
+        assert_eq!(week.most_active_user, "alice");
+        // alice→bob 60s、bob→alice 30s，alice→alice 不计
+        assert!((week.avg_response_time_secs - 45.0).abs() < 0.01);
+        assert_eq!(week.peak_hour, 10);
+        assert!((week.messages_per_day - 4.0 / 7.0).abs() < 1e-9);
+        
+        let all = db.get_conversation_statistics("stats", TimeWindow::AllTime).await.unwrap();
+        assert_eq!(all.message_count, 5);
+        assert_eq!(all.active_users, 3);
+        
+        let empty = db.get_conversation_statistics("missing", TimeWindow::Last30Days).await.unwrap();
+        assert_eq!(empty, ConversationStats::default());
+    }
 }
@@ -0,0 +1,88 @@
+//! 消息实体抽取
+//!
+//! 调用 AI Provider 的结构化输出，从聊天消息中抽取人物、组织、时间、话题和待办事项，
+//! 结果可直接序列化后写入记忆存储。
+
+use serde::{Deserialize, Serialize};
+
+use cis_core::ai::AiProvider;
+
+use crate::error::{ImError, Result};
+use crate::types::Message;
+
+/// 从消息中抽取的实体
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractedEntities {
+    /// 人物
+    #[serde(default)]
+    pub people: Vec<String>,
+    /// 组织/团队
+    #[serde(default)]
+    pub organizations: Vec<String>,
+    /// 日期/时间表达
+    #[serde(default)]
+    pub dates: Vec<String>,
+    /// 话题
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// 待办事项
+    #[serde(default)]
+    pub action_items: Vec<String>,
+}
+
+impl ExtractedEntities {
+    /// 是否没有抽取到任何实体
+    pub fn is_empty(&self) -> bool {
+        self.people.is_empty()
+            && self.organizations.is_empty()
+            && self.dates.is_empty()
+            && self.topics.is_empty()
+            && self.action_items.is_empty()
+    }
+
+    /// 实体抽取使用的 JSON Schema
+    pub fn schema() -> serde_json::Value {
+        let list = serde_json::json!({ "type": "array", "items": { "type": "string" } });
+        serde_json::json!({
+            "type": "object",
+            "required": ["people", "organizations", "dates", "topics", "action_items"],
+            "properties": {
+                "people": list,
+                "organizations": list,
+                "dates": list,
+                "topics": list,
+                "action_items": list,
+            }
+        })
+    }
+}
+
+/// 构建抽取 prompt，只包含有文本内容的消息
+pub(crate) fn extraction_prompt(messages: &[Message]) -> Option<String> {
+    let lines: Vec<String> = messages
+        .iter()
+        .filter_map(|m| m.content.text_content().map(|text| format!("{}: {}", m.sender_id, text)))
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "Extract the people, organizations, dates, topics and action items mentioned in \
+         the following chat messages. Use empty lists for categories with no entries.\n\n{}",
+        lines.join("\n")
+    ))
+}
+
+/// 调用结构化输出抽取实体
+pub(crate) async fn extract(provider: &dyn AiProvider, messages: &[Message]) -> Result<ExtractedEntities> {
+    let Some(prompt) = extraction_prompt(messages) else {
+        return Ok(ExtractedEntities::default());
+    };
+
+    let value = provider
+        .structured_chat(&prompt, &ExtractedEntities::schema())
+        .await
+        .map_err(|e| ImError::Other(format!("Entity extraction failed: {}", e)))?;
+    serde_json::from_value(value).map_err(|e| ImError::Serialization(e.to_string()))
+}
@@ -2,6 +2,8 @@
 
 use thiserror::Error;
 
+use crate::validation::ValidationError;
+
 pub type Result<T> = std::result::Result<T, ImError>;
 
 #[derive(Error, Debug)]
@@ -24,9 +26,21 @@ pub enum ImError {
     #[error("Unauthorized")]
     Unauthorized,
     
+    #[error("Access denied: {0}")]
+    AccessDenied(String),
+    
+    #[error("Channel {0} is read-only for non-admin members")]
+    ChannelReadOnly(String),
+    
+    #[error("Conversation {0} is not a channel")]
+    NotAChannel(String),
+    
     #[error("Message too large: {size} > {max}")]
     MessageTooLarge { size: usize, max: usize },
     
+    #[error("Content validation failed: {}", .0.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; "))]
+    ContentValidationFailed(Vec<ValidationError>),
+    
     #[error("Other: {0}")]
     Other(String),
 }
@@ -42,3 +56,9 @@ impl From<serde_json::Error> for ImError {
         ImError::Serialization(e.to_string())
     }
 }
+
+impl From<cis_core::error::CisError> for ImError {
+    fn from(e: cis_core::error::CisError) -> Self {
+        ImError::Other(e.to_string())
+    }
+}
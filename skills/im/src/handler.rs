@@ -23,6 +23,12 @@ pub struct CreateSessionRequest {
     pub session_type: String,
     pub title: Option<String>,
     pub participants: Vec<String>,
+    /// 频道主题（仅 channel）
+    #[serde(default)]
+    pub topic: String,
+    /// 是否公开频道（仅 channel）
+    #[serde(default)]
+    pub is_public: bool,
 }
 
 /// 获取消息请求
@@ -68,6 +74,9 @@ pub struct ListSessionsRequest {
     pub user_id: String,
     #[serde(default = "default_list_limit")]
     pub limit: usize,
+    /// 只返回带有其中任一标签的会话
+    #[serde(default)]
+    pub label_filter: Vec<String>,
 }
 
 fn default_list_limit() -> usize {
@@ -131,7 +140,7 @@ pub async fn handle_create_session(
         }
         "channel" => {
             skill.create_conversation(
-                ConversationType::Channel,
+                ConversationType::Channel { topic: req.topic, is_public: req.is_public },
                 req.title,
                 req.participants,
             ).await?
@@ -161,15 +170,19 @@ pub async fn handle_get_messages(
 
     let messages = skill.get_history(&req.session_id, req.before, req.limit).await?;
 
-    let messages_json: Vec<Value> = messages.iter().map(|msg| {
-        serde_json::json!({
+    let mut messages_json: Vec<Value> = Vec::with_capacity(messages.len());
+    for msg in &messages {
+        let verification = skill.verify_message(msg).await?;
+        messages_json.push(serde_json::json!({
             "id": msg.id,
             "sender_id": msg.sender_id,
             "content": msg.content,
             "created_at": msg.created_at,
             "read_by": msg.read_by,
-        })
-    }).collect();
+            "verification": verification,
+            "badge": verification.badge(),
+        }));
+    }
 
     Ok(serde_json::json!({
         "success": true,
@@ -187,7 +200,7 @@ pub async fn handle_list_sessions(
     let req: ListSessionsRequest = serde_json::from_value(data)
         .map_err(|e| crate::error::ImError::Serialization(e.to_string()))?;
 
-    let conversations = skill.list_conversations(&req.user_id).await?;
+    let conversations = skill.list_conversations(&req.user_id, req.label_filter).await?;
 
     // 限制返回数量
     let conversations: Vec<_> = conversations.into_iter().take(req.limit).collect();
@@ -446,6 +459,7 @@ mod tests {
         let get_result = handle_get_messages(&skill, get_data).await.unwrap();
         assert_eq!(get_result["success"], true);
         assert_eq!(get_result["count"], 1);
+        assert_eq!(get_result["messages"][0]["verification"]["status"], "no_signature");
     }
 
     #[tokio::test]
@@ -0,0 +1,114 @@
+//! 消息语言识别
+//!
+//! 基于 `whatlang` 在本地识别文本消息的语言，结果为 ISO 639-1 代码（如 `en`、`zh`）。
+//! 识别结果不可靠（文本过短、混排）时返回 None，不强行打标签。
+
+use whatlang::Lang;
+
+/// ISO 639-1 语言代码
+pub type LanguageCode = String;
+
+/// 识别文本语言
+pub fn detect(text: &str) -> Option<LanguageCode> {
+    let info = whatlang::detect(text.trim())?;
+    if !info.is_reliable() {
+        return None;
+    }
+    iso_639_1(info.lang()).map(str::to_string)
+}
+
+/// whatlang 语言到 ISO 639-1 代码（没有两字母代码的语言返回 None）
+fn iso_639_1(lang: Lang) -> Option<&'static str> {
+    let code = match lang.code() {
+        "afr" => "af",
+        "aka" => "ak",
+        "amh" => "am",
+        "ara" => "ar",
+        "aze" => "az",
+        "bel" => "be",
+        "ben" => "bn",
+        "bul" => "bg",
+        "cat" => "ca",
+        "ces" => "cs",
+        "cmn" => "zh",
+        "dan" => "da",
+        "deu" => "de",
+        "ell" => "el",
+        "eng" => "en",
+        "epo" => "eo",
+        "est" => "et",
+        "fin" => "fi",
+        "fra" => "fr",
+        "guj" => "gu",
+        "heb" => "he",
+        "hin" => "hi",
+        "hrv" => "hr",
+        "hun" => "hu",
+        "hye" => "hy",
+        "ind" => "id",
+        "ita" => "it",
+        "jav" => "jv",
+        "jpn" => "ja",
+        "kan" => "kn",
+        "kat" => "ka",
+        "khm" => "km",
+        "kor" => "ko",
+        "lat" => "la",
+        "lav" => "lv",
+        "lit" => "lt",
+        "mal" => "ml",
+        "mar" => "mr",
+        "mkd" => "mk",
+        "mya" => "my",
+        "nep" => "ne",
+        "nld" => "nl",
+        "nob" => "nb",
+        "ori" => "or",
+        "pan" => "pa",
+        "pes" => "fa",
+        "pol" => "pl",
+        "por" => "pt",
+        "ron" => "ro",
+        "rus" => "ru",
+        "sin" => "si",
+        "slk" => "sk",
+        "slv" => "sl",
+        "sna" => "sn",
+        "spa" => "es",
+        "srp" => "sr",
+        "swe" => "sv",
+        "tam" => "ta",
+        "tel" => "te",
+        "tgl" => "tl",
+        "tha" => "th",
+        "tuk" => "tk",
+        "tur" => "tr",
+        "ukr" => "uk",
+        "urd" => "ur",
+        "uzb" => "uz",
+        "vie" => "vi",
+        "yid" => "yi",
+        "zul" => "zu",
+        _ => return None,
+    };
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            detect("The release is planned for Friday after the security audit is finished").as_deref(),
+            Some("en")
+        );
+        assert_eq!(detect("我们计划在周五安全审计完成之后发布新版本").as_deref(), Some("zh"));
+        assert_eq!(
+            detect("La versión se publicará el viernes después de la auditoría de seguridad").as_deref(),
+            Some("es")
+        );
+        assert_eq!(detect("   "), None);
+    }
+}
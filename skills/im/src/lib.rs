@@ -7,29 +7,64 @@
 //! - 联邦同步
 
 pub mod db;
+pub mod entities;
 pub mod error;
 pub mod handler;
+pub mod language;
 pub mod message;
 pub mod search;
 pub mod session;
+pub mod signature;
+pub mod smart_reply;
+pub mod summary;
 pub mod types;
+pub mod validation;
+pub mod webhook;
 pub mod matrix_adapter;
 
-pub use db::ImDatabase;
+pub use db::{
+    BatchSaveFailure, BatchSaveResult, ConversationLabel, ConversationStats, FtsIntegrityReport, ImDatabase,
+    IndexRebuildReport, RebalanceReport, ShardedImDatabase, TimeWindow,
+};
+pub use entities::ExtractedEntities;
 pub use error::{ImError, Result};
 pub use handler::*;
+pub use language::LanguageCode;
+pub use matrix_adapter::ImFederation;
 pub use message::MessageManager;
-pub use search::ImMessageSearch;
+pub use search::{FtsTokenizerConfig, ImMessageSearch, Language};
 pub use session::SessionManager;
+pub use signature::VerificationResult;
+pub use smart_reply::{ReplyType, SmartReply};
+pub use summary::ConversationSummary;
 pub use types::*;
+pub use validation::{
+    AllowedContentTypesRule, BlockedKeywordsRule, MaxLengthRule, ValidationError, ValidationRule,
+};
+pub use webhook::{ImWebhookConfig, Webhook, WebhookEvent, WebhookId};
 
+use std::collections::HashMap;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
+
+use cis_core::ai::AiProvider;
+use cis_core::conversation::{ConsensusResult, ConversationContext, ProviderResponse};
+use cis_core::identity::{DIDDocumentStore, DIDManager};
+use cis_core::matrix::nucleus::MatrixNucleus;
+use cis_core::network::{ConversationAcl, NetworkAcl};
+use ed25519_dalek::SigningKey as Ed25519SigningKey;
+use smart_reply::SmartReplyCache;
+use webhook::WebhookDispatcher;
 
 /// IM Skill 主结构
 pub struct ImSkill {
     db: Arc<ImDatabase>,
     config: ImConfig,
+    did_store: DIDDocumentStore,
+    acl: Option<NetworkAcl>,
+    federation: OnceLock<Arc<ImFederation>>,
+    smart_replies: SmartReplyCache,
+    webhooks: WebhookDispatcher,
 }
 
 impl ImSkill {
@@ -39,6 +74,11 @@ impl ImSkill {
         Ok(Self {
             db: Arc::new(db),
             config: ImConfig::default(),
+            did_store: DIDDocumentStore::open_default(),
+            acl: None,
+            federation: OnceLock::new(),
+            smart_replies: SmartReplyCache::default(),
+            webhooks: WebhookDispatcher::default(),
         })
     }
     
@@ -48,17 +88,114 @@ impl ImSkill {
         self
     }
     
+    /// 使用自定义 DID 文档存储（用于解析发送方公钥）
+    pub fn with_did_store(mut self, did_store: DIDDocumentStore) -> Self {
+        self.did_store = did_store;
+        self
+    }
+    
+    /// 启用发送方访问控制
+    ///
+    /// 会话级规则取自 `conversation.metadata.acl`（`{"allow": [...], "deny": [...]}`），
+    /// 未命中规则的发送方按 `acl.default_policy` 处理。
+    pub fn with_acl(mut self, acl: NetworkAcl) -> Self {
+        self.acl = Some(acl);
+        self
+    }
+    
     /// 获取数据库引用
     pub fn db(&self) -> &Arc<ImDatabase> {
         &self.db
     }
     
+    /// 启用 Matrix 联邦转发
+    ///
+    /// `room_mapping` 为会话 ID 到 Matrix Room ID 的映射。映射会话中发送的消息
+    /// 同时发送到对应 Room；Room 中收到的消息（包括 `MatrixNucleus::start_sync`
+    /// 拉取的事件）写入数据库。只能启用一次。
+    pub fn enable_federation(
+        self: &Arc<Self>,
+        nucleus: Arc<MatrixNucleus>,
+        room_mapping: HashMap<String, String>,
+    ) -> Result<()> {
+        let federation = Arc::new(ImFederation::new(nucleus, room_mapping)?);
+        self.federation
+            .set(federation.clone())
+            .map_err(|_| ImError::Other("Federation already enabled".to_string()))?;
+        
+        federation.spawn_ingest(Arc::downgrade(self));
+        Ok(())
+    }
+    
+    /// 获取联邦转发（未启用时为 None）
+    pub fn federation(&self) -> Option<&Arc<ImFederation>> {
+        self.federation.get()
+    }
+    
     /// 发送消息
     pub async fn send_message(
         &self,
         conversation_id: &str,
         sender_id: &str,
         content: MessageContent,
+    ) -> Result<Message> {
+        let message = self.send_message_inner(conversation_id, sender_id, content, None).await?;
+        if cfg!(debug_assertions) {
+            Self::check_message_schema(&message);
+        }
+        Ok(message)
+    }
+    
+    /// 调试构建下校验返回的消息符合 `Message::json_schema()`，不符时记录错误
+    fn check_message_schema(message: &Message) {
+        let value = serde_json::to_value(message).unwrap_or_default();
+        if let Err(reason) = cis_core::ai::structured::validate_schema(&value, &Message::json_schema(), "$") {
+            tracing::error!("Message {} does not match schema: {}", message.id, reason);
+        }
+    }
+    
+    /// 发送 DID 签名消息
+    ///
+    /// 签名覆盖 `conversation_id + message_id + content_hash`，`sender_did` 作为发送方 ID。
+    pub async fn send_signed_message(
+        &self,
+        conversation_id: &str,
+        sender_did: &str,
+        content: MessageContent,
+        key: &Ed25519SigningKey,
+    ) -> Result<Message> {
+        if !DIDManager::is_valid_did(sender_did) {
+            return Err(ImError::InvalidMessage(format!("Invalid sender DID: {}", sender_did)));
+        }
+        self.send_message_inner(conversation_id, sender_did, content, Some(key)).await
+    }
+    
+    /// 验证消息签名
+    ///
+    /// 通过发送方 DID 解析 DID 文档，用文档中的公钥验证签名。
+    pub async fn verify_message(&self, message: &Message) -> Result<VerificationResult> {
+        if message.signature.is_none() {
+            return Ok(VerificationResult::NoSignature);
+        }
+        
+        let document = match DIDManager::resolve_from(&self.did_store, &message.sender_id) {
+            Ok(document) => document,
+            Err(e) => return Ok(VerificationResult::Unverified(format!("Cannot resolve sender DID: {}", e))),
+        };
+        let key = match document.verifying_key() {
+            Ok(key) => key,
+            Err(e) => return Ok(VerificationResult::Unverified(e.to_string())),
+        };
+        
+        signature::verify_with_key(message, &key)
+    }
+    
+    async fn send_message_inner(
+        &self,
+        conversation_id: &str,
+        sender_id: &str,
+        content: MessageContent,
+        key: Option<&Ed25519SigningKey>,
     ) -> Result<Message> {
         // 验证消息长度
         let content_size = serde_json::to_string(&content).unwrap_or_default().len();
@@ -69,22 +206,95 @@ impl ImSkill {
             });
         }
         
+        // 内容校验（收集全部失败）
+        let violations = validation::validate_all(&self.config.validation_rules, &content);
+        if !violations.is_empty() {
+            return Err(ImError::ContentValidationFailed(violations));
+        }
+        
         // 验证会话存在
-        if self.db.get_conversation(conversation_id).await?.is_none() {
-            return Err(ImError::ConversationNotFound(conversation_id.to_string()));
+        let conversation = self.db.get_conversation(conversation_id).await?
+            .ok_or_else(|| ImError::ConversationNotFound(conversation_id.to_string()))?;
+        
+        // 频道只允许管理员发送
+        if conversation.conversation_type.is_channel() && !conversation.is_admin(sender_id) {
+            return Err(ImError::ChannelReadOnly(conversation_id.to_string()));
+        }
+        
+        // 访问控制（写入前）
+        if let Some(acl) = &self.acl {
+            self.check_send_access(acl, &conversation, sender_id)?;
         }
         
-        let message = Message::new(
+        let mut message = Message::new(
             conversation_id.to_string(),
             sender_id.to_string(),
             content,
         );
+        message.detected_language = message.content.text_content().and_then(language::detect);
+        
+        if let Some(key) = key {
+            signature::sign_message(&mut message, key)?;
+        }
         
         self.db.save_message(&message).await?;
+        self.emit_webhook_event(WebhookEvent::NewMessage, conversation_id, serde_json::json!(message)).await;
+        
+        // 转发失败不影响本地发送
+        if let Some(federation) = self.federation.get() {
+            if let Err(e) = federation.forward(self, &message).await {
+                tracing::warn!("Failed to forward message {} to Matrix: {}", message.id, e);
+            }
+        }
         
         Ok(message)
     }
     
+    /// 写入从 Matrix 收到的消息（不再转发）
+    pub(crate) async fn receive_message(
+        &self,
+        conversation_id: &str,
+        sender_id: &str,
+        content: MessageContent,
+        timestamp_ms: i64,
+    ) -> Result<Message> {
+        let conversation = self.db.get_conversation(conversation_id).await?
+            .ok_or_else(|| ImError::ConversationNotFound(conversation_id.to_string()))?;
+        
+        if let Some(acl) = &self.acl {
+            self.check_send_access(acl, &conversation, sender_id)?;
+        }
+        
+        let mut message = Message::new(
+            conversation_id.to_string(),
+            sender_id.to_string(),
+            content,
+        );
+        if let Some(created_at) = chrono::DateTime::from_timestamp_millis(timestamp_ms) {
+            message.created_at = created_at;
+        }
+        message.detected_language = message.content.text_content().and_then(language::detect);
+        
+        self.db.save_message(&message).await?;
+        self.emit_webhook_event(WebhookEvent::NewMessage, &message.conversation_id, serde_json::json!(message))
+            .await;
+        Ok(message)
+    }
+    
+    fn check_send_access(&self, acl: &NetworkAcl, conversation: &Conversation, sender_id: &str) -> Result<()> {
+        // 会话元数据中的规则优先于 ACL 文件中保存的规则
+        let rules = ConversationAcl::from_metadata(&conversation.metadata).map_err(|e| {
+            tracing::warn!("Invalid ACL metadata on conversation {}: {}", conversation.id, e);
+            ImError::AccessDenied(format!("invalid conversation ACL: {}", e))
+        })?;
+        let rules = rules.as_ref().or_else(|| acl.conversations.get(&conversation.id));
+        
+        acl.check_message_send_with(sender_id, &conversation.id, rules).map_err(|e| {
+            tracing::warn!("IM send rejected for {} in {}: {}", sender_id, conversation.id, e);
+            ImError::AccessDenied(e.to_string())
+        })
+    }
+    
     /// 获取消息历史
     pub async fn get_history(
         &self,
@@ -95,7 +305,37 @@ impl ImSkill {
         self.db.get_messages(conversation_id, before, limit).await
     }
     
+    /// 导入会话历史消息
+    ///
+    /// 在一个事务内批量写入，已存在的消息跳过；不属于该会话的消息计为失败。
+    pub async fn import_history(
+        &self,
+        conversation_id: &str,
+        messages: &[Message],
+    ) -> Result<BatchSaveResult> {
+        if self.db.get_conversation(conversation_id).await?.is_none() {
+            return Err(ImError::ConversationNotFound(conversation_id.to_string()));
+        }
+        
+        let (matching, foreign): (Vec<Message>, Vec<Message>) = messages
+            .iter()
+            .cloned()
+            .partition(|m| m.conversation_id == conversation_id);
+        
+        let mut result = self.db.batch_save_messages(&matching).await?;
+        for message in &foreign {
+            result.failed += 1;
+            result.failures.push(BatchSaveFailure {
+                message_id: message.id.clone(),
+                error: format!("message belongs to conversation {}", message.conversation_id),
+            });
+        }
+        Ok(result)
+    }
+    
     /// 创建会话
+    ///
+    /// 频道的第一个参与者作为管理员（频道所有者）。
     pub async fn create_conversation(
         &self,
         conversation_type: ConversationType,
@@ -103,11 +343,17 @@ impl ImSkill {
         participants: Vec<String>,
     ) -> Result<Conversation> {
         let now = chrono::Utc::now();
+        let admins = if conversation_type.is_channel() {
+            participants.first().cloned().into_iter().collect()
+        } else {
+            Vec::new()
+        };
         let conversation = Conversation {
             id: uuid::Uuid::new_v4().to_string(),
             conversation_type,
             name,
             participants,
+            admins,
             created_at: now,
             updated_at: now,
             last_message_at: None,
@@ -125,9 +371,173 @@ impl ImSkill {
         self.db.get_conversation(conversation_id).await
     }
     
-    /// 列出用户的会话
-    pub async fn list_conversations(&self, user_id: &str) -> Result<Vec<Conversation>> {
-        self.db.list_conversations(user_id).await
+    /// 列出用户的会话，`label_filter` 非空时只返回带有其中任一标签的会话
+    pub async fn list_conversations(&self, user_id: &str, label_filter: Vec<String>) -> Result<Vec<Conversation>> {
+        self.db.list_conversations(user_id, label_filter).await
+    }
+    
+    /// 为会话添加标签（仅参与者可添加，标签只对添加者可见）
+    pub async fn add_label(&self, conversation_id: &str, label: &str, user_id: &str) -> Result<()> {
+        let conversation = self.get_conversation(conversation_id).await?
+            .ok_or_else(|| ImError::ConversationNotFound(conversation_id.to_string()))?;
+        if !conversation.participants.iter().any(|p| p == user_id) {
+            return Err(ImError::AccessDenied(format!(
+                "{} is not a participant of {}",
+                user_id, conversation_id
+            )));
+        }
+        self.db.add_label(conversation_id, label, user_id).await
+    }
+    
+    /// 设置标签颜色，返回标签是否存在
+    pub async fn set_label_color(&self, conversation_id: &str, label: &str, user_id: &str, color: Option<&str>)
+        -> Result<bool>
+    {
+        self.db.set_label_color(conversation_id, label, user_id, color).await
+    }
+    
+    /// 移除会话标签，返回标签是否存在
+    pub async fn remove_label(&self, conversation_id: &str, label: &str, user_id: &str) -> Result<bool> {
+        self.db.remove_label(conversation_id, label, user_id).await
+    }
+    
+    /// 用户在会话上的标签
+    pub async fn list_labels(&self, conversation_id: &str, user_id: &str) -> Result<Vec<ConversationLabel>> {
+        let mut labels = self.db.list_labels(conversation_id).await?;
+        labels.retain(|l| l.added_by == user_id);
+        Ok(labels)
+    }
+    
+    /// 按标签查找用户的会话
+    pub async fn search_by_label(&self, label: &str, user_id: &str) -> Result<Vec<Conversation>> {
+        self.db.search_by_label(label, user_id).await
+    }
+    
+    /// 订阅频道（以只读成员身份加入）
+    ///
+    /// 非公开频道只能由管理员添加成员，不能自行订阅。
+    pub async fn subscribe_to_channel(&self, channel_id: &str, user_id: &str) -> Result<()> {
+        let mut channel = self.get_channel(channel_id).await?;
+        if channel.participants.iter().any(|p| p == user_id) {
+            return Ok(());
+        }
+        if !matches!(channel.conversation_type, ConversationType::Channel { is_public: true, .. }) {
+            return Err(ImError::AccessDenied(format!("Channel {} is not public", channel_id)));
+        }
+        
+        channel.participants.push(user_id.to_string());
+        channel.updated_at = chrono::Utc::now();
+        self.db.create_conversation(&channel).await?;
+        self.emit_webhook_event(WebhookEvent::UserJoined, channel_id, serde_json::json!({ "user_id": user_id }))
+            .await;
+        Ok(())
+    }
+    
+    /// 取消订阅频道；管理员不能通过取消订阅离开频道
+    pub async fn unsubscribe_from_channel(&self, channel_id: &str, user_id: &str) -> Result<()> {
+        let mut channel = self.get_channel(channel_id).await?;
+        if channel.is_admin(user_id) {
+            return Err(ImError::InvalidMessage(format!(
+                "Admin {} cannot unsubscribe from channel {}",
+                user_id, channel_id
+            )));
+        }
+        let before = channel.participants.len();
+        channel.participants.retain(|p| p != user_id);
+        if channel.participants.len() == before {
+            return Ok(());
+        }
+        
+        channel.updated_at = chrono::Utc::now();
+        self.db.create_conversation(&channel).await?;
+        self.emit_webhook_event(WebhookEvent::UserLeft, channel_id, serde_json::json!({ "user_id": user_id }))
+            .await;
+        Ok(())
+    }
+    
+    /// 编辑自己发送的文本消息（`ImConfig::enable_editing` 关闭时拒绝）
+    pub async fn edit_message(&self, message_id: &str, sender_id: &str, text: &str) -> Result<Message> {
+        if !self.config.enable_editing {
+            return Err(ImError::InvalidMessage("Message editing is disabled".to_string()));
+        }
+        let mut message = self.db.get_message(message_id).await?
+            .ok_or_else(|| ImError::InvalidMessage(format!("Message not found: {}", message_id)))?;
+        if message.sender_id != sender_id {
+            return Err(ImError::Unauthorized);
+        }
+        
+        let content = MessageContent::Text { text: text.to_string() };
+        let violations = validation::validate_all(&self.config.validation_rules, &content);
+        if !violations.is_empty() {
+            return Err(ImError::ContentValidationFailed(violations));
+        }
+        
+        message.detected_language = content.text_content().and_then(language::detect);
+        message.content = content;
+        message.updated_at = Some(chrono::Utc::now());
+        self.db.save_message(&message).await?;
+        self.emit_webhook_event(WebhookEvent::MessageEdited, &message.conversation_id, serde_json::json!(message))
+            .await;
+        Ok(message)
+    }
+    
+    /// 识别消息语言并写回 `Message::detected_language`
+    ///
+    /// 非文本消息或识别结果不可靠时返回 None（同时清除已有标签）。
+    pub async fn detect_language(&self, message_id: &str) -> Result<Option<LanguageCode>> {
+        let mut message = self.db.get_message(message_id).await?
+            .ok_or_else(|| ImError::InvalidMessage(format!("Message not found: {}", message_id)))?;
+        
+        let detected = message.content.text_content().and_then(language::detect);
+        if detected != message.detected_language {
+            message.detected_language = detected.clone();
+            self.db.save_message(&message).await?;
+        }
+        Ok(detected)
+    }
+    
+    /// 注册出站 Webhook
+    pub async fn register_webhook(&self, config: ImWebhookConfig) -> Result<WebhookId> {
+        config.validate()?;
+        let webhook = Webhook {
+            id: uuid::Uuid::new_v4().to_string(),
+            config,
+            created_at: chrono::Utc::now(),
+        };
+        self.db.save_webhook(&webhook).await?;
+        Ok(webhook.id)
+    }
+    
+    /// 已注册的 Webhook
+    pub async fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        self.db.list_webhooks().await
+    }
+    
+    /// 删除 Webhook，返回是否存在
+    pub async fn delete_webhook(&self, id: WebhookId) -> Result<bool> {
+        self.db.delete_webhook(&id).await
+    }
+    
+    /// 在后台投递事件到订阅的 Webhook；读取配置失败只记录日志
+    async fn emit_webhook_event(&self, event: WebhookEvent, conversation_id: &str, data: serde_json::Value) {
+        match self.db.list_webhooks().await {
+            Ok(webhooks) => self.webhooks.dispatch(webhooks, event, conversation_id, data),
+            Err(e) => tracing::warn!("Failed to load IM webhooks: {}", e),
+        }
+    }
+    
+    /// 按名称或主题搜索公开频道，不要求已订阅
+    pub async fn search_channels(&self, query: &str) -> Result<Vec<Conversation>> {
+        self.db.search_public_channels(query, 50).await
+    }
+    
+    async fn get_channel(&self, channel_id: &str) -> Result<Conversation> {
+        let channel = self.db.get_conversation(channel_id).await?
+            .ok_or_else(|| ImError::ConversationNotFound(channel_id.to_string()))?;
+        if !channel.conversation_type.is_channel() {
+            return Err(ImError::NotAChannel(channel_id.to_string()));
+        }
+        Ok(channel)
     }
     
     /// 标记已读
@@ -144,6 +554,106 @@ impl ImSkill {
     pub async fn get_user_profile(&self, user_id: &str) -> Result<Option<UserProfile>> {
         self.db.get_user_profile(user_id).await
     }
+    
+    /// 从消息中抽取结构化实体（用于写入记忆）
+    ///
+    /// 通过 `AiProvider::structured_chat` 按固定 Schema 抽取；没有文本消息时返回空结果。
+    pub async fn extract_entities(
+        &self,
+        provider: &dyn AiProvider,
+        messages: &[Message],
+    ) -> Result<ExtractedEntities> {
+        entities::extract(provider, messages).await
+    }
+    
+    /// 为会话最近 `max_messages` 条消息生成摘要
+    ///
+    /// 摘要作为 [`MessageContent::Summary`] 消息保存到会话中（不经过内容校验），
+    /// 同时返回识别出的话题和各参与者的发言数。
+    pub async fn summarize_conversation(
+        &self,
+        conversation_id: &str,
+        provider: &dyn AiProvider,
+        max_messages: usize,
+    ) -> Result<ConversationSummary> {
+        if self.db.get_conversation(conversation_id).await?.is_none() {
+            return Err(ImError::ConversationNotFound(conversation_id.to_string()));
+        }
+        
+        // get_messages 按时间倒序返回
+        let mut messages = self.db.get_messages(conversation_id, None, max_messages).await?;
+        messages.reverse();
+        
+        let summary = summary::summarize(provider, conversation_id, &messages).await?;
+        self.db.save_message(&summary.to_message()).await?;
+        Ok(summary)
+    }
+    
+    /// 为 `user_id` 生成 `count` 条候选回复
+    ///
+    /// 以会话最近 10 条消息为上下文；`ImConfig::smart_replies_enabled` 关闭时返回空列表。
+    /// 结果按 `(最后一条消息 ID, user_id)` 缓存 30 秒。
+    pub async fn suggest_replies(
+        &self,
+        conversation_id: &str,
+        user_id: &str,
+        provider: &dyn AiProvider,
+        count: usize,
+    ) -> Result<Vec<SmartReply>> {
+        if !self.config.smart_replies_enabled || count == 0 {
+            return Ok(Vec::new());
+        }
+        if self.db.get_conversation(conversation_id).await?.is_none() {
+            return Err(ImError::ConversationNotFound(conversation_id.to_string()));
+        }
+        
+        // get_messages 按时间倒序返回，第一条即最新消息
+        let mut messages = self
+            .db
+            .get_messages(conversation_id, None, smart_reply::SMART_REPLY_CONTEXT_MESSAGES)
+            .await?;
+        let Some(last_message_id) = messages.first().map(|m| m.id.clone()) else {
+            return Ok(Vec::new());
+        };
+        if let Some(cached) = self.smart_replies.get(&last_message_id, user_id, count) {
+            return Ok(cached);
+        }
+        
+        messages.reverse();
+        let replies = smart_reply::suggest(provider, &messages, user_id, count).await?;
+        self.smart_replies.insert(&last_message_id, user_id, replies.clone());
+        Ok(replies)
+    }
+    
+    /// 生成要写入记忆的 AI 回复
+    ///
+    /// `ImConfig::use_consensus_for_memory` 开启时通过 [`ConversationContext::consensus_chat`]
+    /// 要求至少 `quorum` 个 Provider 的回复语义一致；关闭时只调用第一个 Provider，
+    /// 结果视为 [`ConsensusResult::Agree`]。
+    pub async fn memory_reply(
+        &self,
+        ctx: &ConversationContext,
+        providers: &[Arc<dyn AiProvider>],
+        messages: &[cis_core::ai::Message],
+        quorum: usize,
+    ) -> Result<ConsensusResult> {
+        if self.config.use_consensus_for_memory {
+            return Ok(ctx.consensus_chat(providers, messages, quorum).await?);
+        }
+        
+        let provider = providers
+            .first()
+            .ok_or_else(|| ImError::Other("No AI provider configured".to_string()))?;
+        let system = ctx.project_context_prompt().unwrap_or_default();
+        let content = provider
+            .chat_with_context(&system, messages)
+            .await
+            .map_err(|e| ImError::Other(format!("Memory reply failed: {}", e)))?;
+        Ok(ConsensusResult::Agree(ProviderResponse {
+            provider: provider.name().to_string(),
+            content,
+        }))
+    }
 }
 
 impl Default for ImSkill {
@@ -153,6 +663,11 @@ impl Default for ImSkill {
         Self {
             db: Arc::new(db),
             config: ImConfig::default(),
+            did_store: DIDDocumentStore::open_default(),
+            acl: None,
+            federation: OnceLock::new(),
+            smart_replies: SmartReplyCache::default(),
+            webhooks: WebhookDispatcher::default(),
         }
     }
 }
@@ -167,8 +682,245 @@ pub const SKILL_FEDERATE: bool = true;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use cis_core::{MockAiProvider, MockResponse};
     use tempfile::TempDir;
     
+    #[test]
+    fn test_message_json_schema() {
+        let schema = Message::json_schema();
+        let mut message = Message::new("c".to_string(), "bob".to_string(), MessageContent::Voice {
+            url: "mxc://voice".to_string(),
+            duration_secs: 3,
+        });
+        message.mark_read("alice".to_string());
+        let value = serde_json::to_value(&message).unwrap();
+        assert!(cis_core::ai::structured::validate_schema(&value, &schema, "$").is_ok());
+        
+        let mut broken = value.clone();
+        broken["read_by"] = serde_json::json!("alice");
+        assert!(cis_core::ai::structured::validate_schema(&broken, &schema, "$").is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_extract_entities() {
+        let skill = ImSkill::default();
+        let provider = MockAiProvider::with_responses(vec![
+            MockResponse::Return(r#"```json
+{"people": ["Alice"], "organizations": ["CIS"], "dates": ["Friday"], "topics": ["release"], "action_items": ["tag v1.2"]}
+```"#.to_string()),
+            // 不符合 Schema 的回复
+            MockResponse::Return(r#"{"people": "Alice"}"#.to_string()),
+        ]);
+        let messages = vec![
+            Message::new("c".to_string(), "bob".to_string(), MessageContent::Text {
+                text: "Alice will tag v1.2 of CIS on Friday".to_string(),
+            }),
+            Message::new("c".to_string(), "bob".to_string(), MessageContent::Voice {
+                url: "mxc://voice".to_string(),
+                duration_secs: 3,
+            }),
+        ];
+        
+        let entities = skill.extract_entities(&provider, &messages).await.unwrap();
+        assert_eq!(entities.people, vec!["Alice"]);
+        assert_eq!(entities.action_items, vec!["tag v1.2"]);
+        assert!(provider.last_prompt().unwrap().contains("bob: Alice will tag v1.2"));
+        
+        // 没有文本消息时不调用 AI
+        let empty = skill.extract_entities(&provider, &messages[1..]).await.unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(provider.call_count(), 1);
+        
+        assert!(matches!(
+            skill.extract_entities(&provider, &messages).await,
+            Err(ImError::Other(_))
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_summarize_conversation() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill = ImSkill::new(&temp_dir.path().join("im.db")).unwrap();
+        let conv = skill.create_conversation(
+            ConversationType::Group,
+            None,
+            vec!["alice".to_string(), "bob".to_string()],
+        ).await.unwrap();
+        
+        let mut sent = Vec::new();
+        for (sender, text) in [("alice", "Release on Friday?"), ("bob", "Yes, after the audit"), ("alice", "OK")] {
+            let msg = skill.send_message(&conv.id, sender, MessageContent::Text { text: text.to_string() })
+                .await
+                .unwrap();
+            sent.push(msg.id);
+        }
+        
+        let provider = MockAiProvider::with_responses(vec![MockResponse::Return(
+            r#"{"summary": "Release planned for Friday after the audit.", "topics": ["release", "audit"]}"#.to_string(),
+        )]);
+        let summary = skill.summarize_conversation(&conv.id, &provider, 10).await.unwrap();
+        
+        assert_eq!(summary.topics, vec!["release", "audit"]);
+        assert_eq!(summary.participant_activity["alice"], 2);
+        assert_eq!(summary.participant_activity["bob"], 1);
+        assert_eq!(summary.message_range, (sent[0].clone(), sent[2].clone()));
+        assert!(provider.last_prompt().unwrap().contains("[bob]: Yes, after the audit"));
+        
+        // 摘要消息写回会话
+        let history = skill.get_history(&conv.id, None, 10).await.unwrap();
+        assert_eq!(history.len(), 4);
+        assert!(history.iter().any(|m| matches!(
+            &m.content,
+            MessageContent::Summary { text, .. } if text == "Release planned for Friday after the audit."
+        )));
+        
+        assert!(matches!(
+            skill.summarize_conversation("missing", &provider, 10).await,
+            Err(ImError::ConversationNotFound(_))
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_suggest_replies() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill = ImSkill::new(&temp_dir.path().join("im.db")).unwrap()
+            .with_config(ImConfig {
+                smart_replies_enabled: true,
+                ..Default::default()
+            });
+        let conv = skill.create_conversation(
+            ConversationType::Direct,
+            None,
+            vec!["alice".to_string(), "bob".to_string()],
+        ).await.unwrap();
+        skill.send_message(&conv.id, "alice", MessageContent::Text { text: "Lunch at noon?".to_string() })
+            .await
+            .unwrap();
+        
+        let provider = MockAiProvider::with_responses(vec![MockResponse::Return(
+            r#"{"replies": [{"text": "Sounds good", "reply_type": "agree"}, {"text": "Where?", "reply_type": "question"}]}"#
+                .to_string(),
+        )]);
+        let replies = skill.suggest_replies(&conv.id, "bob", &provider, 2).await.unwrap();
+        assert_eq!(replies.len(), 2);
+        assert_eq!(replies[1].reply_type, ReplyType::Question);
+        assert!(provider.last_prompt().unwrap().contains("[alice]: Lunch at noon?"));
+        
+        // 命中缓存，不再调用 AI
+        let cached = skill.suggest_replies(&conv.id, "bob", &provider, 1).await.unwrap();
+        assert_eq!(cached[0].text, "Sounds good");
+        assert_eq!(provider.call_count(), 1);
+        
+        let disabled = ImSkill::new(&temp_dir.path().join("im2.db")).unwrap();
+        assert!(disabled.suggest_replies(&conv.id, "bob", &provider, 2).await.unwrap().is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_memory_reply() {
+        let temp_dir = TempDir::new().unwrap();
+        let ctx = ConversationContext::new("conv".to_string(), "session".to_string());
+        let providers: Vec<Arc<dyn AiProvider>> = vec![
+            Arc::new(MockAiProvider::with_responses(vec![MockResponse::Return("Friday".to_string())])),
+            Arc::new(MockAiProvider::with_responses(vec![MockResponse::Return("Monday".to_string())])),
+        ];
+        let messages = vec![cis_core::ai::Message::user("When is the release?")];
+        
+        // 未开启共识时只使用第一个 Provider
+        let skill = ImSkill::new(&temp_dir.path().join("im.db")).unwrap();
+        match skill.memory_reply(&ctx, &providers, &messages, 2).await.unwrap() {
+            ConsensusResult::Agree(response) => assert_eq!(response.content, "Friday"),
+            other => panic!("expected single-provider reply, got {:?}", other),
+        }
+        
+        // 开启共识后需要向量存储计算嵌入
+        let consensus = ImSkill::new(&temp_dir.path().join("im2.db")).unwrap()
+            .with_config(ImConfig {
+                use_consensus_for_memory: true,
+                ..Default::default()
+            });
+        assert!(consensus.memory_reply(&ctx, &providers, &messages, 2).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_channel() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill = ImSkill::new(&temp_dir.path().join("im.db")).unwrap();
+        let channel = skill.create_conversation(
+            ConversationType::Channel { topic: "Release announcements".to_string(), is_public: true },
+            Some("releases".to_string()),
+            vec!["owner".to_string()],
+        ).await.unwrap();
+        assert_eq!(channel.admins, vec!["owner"]);
+        let private = skill.create_conversation(
+            ConversationType::Channel { topic: "Release internals".to_string(), is_public: false },
+            Some("release-team".to_string()),
+            vec!["owner".to_string()],
+        ).await.unwrap();
+        
+        // 公开频道无需参与即可搜索到，私有频道不出现
+        let found = skill.search_channels("announce").await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, channel.id);
+        assert_eq!(
+            found[0].conversation_type,
+            ConversationType::Channel { topic: "Release announcements".to_string(), is_public: true }
+        );
+        assert_eq!(skill.search_channels("release").await.unwrap().len(), 1);
+        
+        skill.subscribe_to_channel(&channel.id, "reader").await.unwrap();
+        assert!(matches!(
+            skill.subscribe_to_channel(&private.id, "reader").await,
+            Err(ImError::AccessDenied(_))
+        ));
+        
+        let text = || MessageContent::Text { text: "v1.2 is out".to_string() };
+        skill.send_message(&channel.id, "owner", text()).await.unwrap();
+        assert!(matches!(
+            skill.send_message(&channel.id, "reader", text()).await,
+            Err(ImError::ChannelReadOnly(_))
+        ));
+        
+        // 订阅后管理员身份保持不变
+        let reloaded = skill.get_conversation(&channel.id).await.unwrap().unwrap();
+        assert_eq!(reloaded.admins, vec!["owner"]);
+        assert_eq!(reloaded.participants.len(), 2);
+        
+        skill.unsubscribe_from_channel(&channel.id, "reader").await.unwrap();
+        let reloaded = skill.get_conversation(&channel.id).await.unwrap().unwrap();
+        assert_eq!(reloaded.participants, vec!["owner"]);
+        assert!(skill.unsubscribe_from_channel(&channel.id, "owner").await.is_err());
+        
+        let group = skill.create_conversation(ConversationType::Group, None, vec!["a".to_string()]).await.unwrap();
+        assert!(matches!(
+            skill.subscribe_to_channel(&group.id, "reader").await,
+            Err(ImError::NotAChannel(_))
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_webhook_registration() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill = ImSkill::new(&temp_dir.path().join("im.db")).unwrap();
+        
+        let config = ImWebhookConfig {
+            url: "https://example.com/hook".to_string(),
+            secret: "s3cret".to_string(),
+            events: vec![WebhookEvent::NewMessage, WebhookEvent::UserJoined],
+        };
+        let id = skill.register_webhook(config.clone()).await.unwrap();
+        assert!(skill.register_webhook(ImWebhookConfig { url: "ftp://x".to_string(), ..config.clone() }).await.is_err());
+        assert!(skill.register_webhook(ImWebhookConfig { events: vec![], ..config.clone() }).await.is_err());
+        
+        let webhooks = skill.list_webhooks().await.unwrap();
+        assert_eq!(webhooks.len(), 1);
+        assert_eq!(webhooks[0].id, id);
+        assert_eq!(webhooks[0].config, config);
+        
+        assert!(skill.delete_webhook(id.clone()).await.unwrap());
+        assert!(!skill.delete_webhook(id).await.unwrap());
+        assert!(skill.list_webhooks().await.unwrap().is_empty());
+    }
+    
     #[tokio::test]
     async fn test_create_conversation() {
         let temp_dir = TempDir::new().unwrap();
@@ -208,6 +960,34 @@ mod tests {
         assert!(matches!(msg.content, MessageContent::Text { .. }));
     }
     
+    #[tokio::test]
+    async fn test_send_message_acl() {
+        use cis_core::network::DefaultPolicy;
+        
+        let temp_dir = TempDir::new().unwrap();
+        let mut acl = NetworkAcl::new("did:cis:local:abc123");
+        acl.set_default_policy(DefaultPolicy::Deny);
+        let skill = ImSkill::new(&temp_dir.path().join("im.db")).unwrap().with_acl(acl);
+        
+        let mut conv = skill.create_conversation(
+            ConversationType::Group,
+            None,
+            vec!["did:cis:a:01".to_string(), "did:cis:b:02".to_string()],
+        ).await.unwrap();
+        conv.metadata = serde_json::json!({ "acl": { "allow": ["did:cis:a:01"] } });
+        skill.db().update_conversation(&conv).await.unwrap();
+        
+        let text = || MessageContent::Text { text: "hi".to_string() };
+        assert!(skill.send_message(&conv.id, "did:cis:a:01", text()).await.is_ok());
+        
+        let result = skill.send_message(&conv.id, "did:cis:b:02", text()).await;
+        assert!(matches!(result, Err(ImError::AccessDenied(_))));
+        
+        // 被拒绝的消息不写入
+        let history = skill.get_history(&conv.id, None, 10).await.unwrap();
+        assert_eq!(history.len(), 1);
+    }
+    
     #[tokio::test]
     async fn test_message_too_large() {
         let temp_dir = TempDir::new().unwrap();
@@ -233,6 +1013,153 @@ mod tests {
         assert!(matches!(result, Err(ImError::MessageTooLarge { .. })));
     }
     
+    #[tokio::test]
+    async fn test_validation_rules_report_all_violations() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill = ImSkill::new(&temp_dir.path().join("im.db")).unwrap()
+            .with_config(ImConfig {
+                validation_rules: vec![
+                    Arc::new(MaxLengthRule { max_chars: 10 }),
+                    Arc::new(BlockedKeywordsRule { keywords: vec!["secret".to_string()] }),
+                    Arc::new(AllowedContentTypesRule { types: vec![MessageContentType::Text] }),
+                ],
+                ..Default::default()
+            });
+        
+        let conv = skill.create_conversation(
+            ConversationType::Direct,
+            None,
+            vec!["user1".to_string()],
+        ).await.unwrap();
+        
+        let result = skill.send_message(
+            &conv.id,
+            "user1",
+            MessageContent::Text { text: "the secret password".to_string() },
+        ).await;
+        
+        let errors = match result {
+            Err(ImError::ContentValidationFailed(errors)) => errors,
+            other => panic!("expected validation failure, got {:?}", other),
+        };
+        let rules: Vec<&str> = errors.iter().map(|e| e.rule.as_str()).collect();
+        assert_eq!(rules, vec!["max_length", "blocked_keywords"]);
+        
+        // 未写入
+        assert!(skill.get_history(&conv.id, None, 10).await.unwrap().is_empty());
+        assert!(skill.send_message(
+            &conv.id,
+            "user1",
+            MessageContent::Text { text: "hello".to_string() },
+        ).await.is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_send_and_verify_signed_message() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = DIDDocumentStore::new(temp_dir.path().join("documents"));
+        let skill = ImSkill::new(&temp_dir.path().join("im.db")).unwrap()
+            .with_did_store(store.clone());
+        let sender = DIDManager::generate("alice").unwrap();
+        
+        let conv = skill.create_conversation(
+            ConversationType::Direct,
+            None,
+            vec![sender.did().to_string()],
+        ).await.unwrap();
+        
+        let msg = skill.send_signed_message(
+            &conv.id,
+            sender.did(),
+            MessageContent::Text { text: "Signed hello".to_string() },
+            sender.signing_key(),
+        ).await.unwrap();
+        assert!(msg.signature.is_some());
+        
+        // 发送方 DID 文档尚未解析到
+        let result = skill.verify_message(&msg).await.unwrap();
+        assert!(matches!(result, VerificationResult::Unverified(_)));
+        
+        store.put(&sender.document()).unwrap();
+        let history = skill.get_history(&conv.id, None, 10).await.unwrap();
+        assert_eq!(history[0].signature, msg.signature);
+        assert_eq!(skill.verify_message(&history[0]).await.unwrap(), VerificationResult::Verified);
+        
+        // 未签名消息
+        let plain = skill.send_message(
+            &conv.id,
+            sender.did(),
+            MessageContent::Text { text: "Plain".to_string() },
+        ).await.unwrap();
+        assert_eq!(skill.verify_message(&plain).await.unwrap(), VerificationResult::NoSignature);
+    }
+    
+    #[tokio::test]
+    async fn test_federation_forward_and_ingest() {
+        use cis_core::matrix::nucleus::{EventId, MatrixEvent, RoomId, UserId};
+        use cis_core::matrix::MatrixStore;
+        
+        let temp_dir = TempDir::new().unwrap();
+        let skill = Arc::new(ImSkill::new(&temp_dir.path().join("im.db")).unwrap());
+        let nucleus = Arc::new(MatrixNucleus::new_simple(
+            Arc::new(MatrixStore::open_in_memory().unwrap()),
+            Arc::new(DIDManager::generate("test-node").unwrap()),
+        ));
+        
+        let conv = skill.create_conversation(
+            ConversationType::Group,
+            None,
+            vec!["user1".to_string()],
+        ).await.unwrap();
+        let room = "!chat:cis.local";
+        skill.enable_federation(
+            nucleus.clone(),
+            HashMap::from([(conv.id.clone(), room.to_string())]),
+        ).unwrap();
+        assert!(skill.enable_federation(nucleus.clone(), HashMap::new()).is_err());
+        
+        // 本地消息发送到 Room，回流事件不重复写入
+        let mut room_events = nucleus.subscribe_events();
+        let sent = skill.send_message(
+            &conv.id,
+            "user1",
+            MessageContent::Text { text: "local".to_string() },
+        ).await.unwrap();
+        let echoed = room_events.recv().await.unwrap();
+        assert_eq!(echoed.room_id_str(), room);
+        assert_eq!(echoed.content["body"], "local");
+        assert_eq!(
+            skill.db().get_matrix_event_message(echoed.event_id_str()).await.unwrap(),
+            Some(sent.id.clone())
+        );
+        
+        // 远端事件写入一次
+        let remote = MatrixEvent::new(
+            RoomId::new(room),
+            EventId::new("$remote"),
+            UserId::new("@bob:remote"),
+            "m.room.message",
+            serde_json::json!({ "msgtype": "m.text", "body": "remote" }),
+        );
+        nucleus.event_bus().send(remote.clone()).unwrap();
+        nucleus.event_bus().send(remote).unwrap();
+        
+        let mut history = Vec::new();
+        for _ in 0..50 {
+            history = skill.get_history(&conv.id, None, 10).await.unwrap();
+            if history.len() >= 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        history = skill.get_history(&conv.id, None, 10).await.unwrap();
+        
+        assert_eq!(history.len(), 2);
+        assert!(history.iter().any(|m| m.sender_id == "@bob:remote"
+            && m.content.text_content() == Some("remote")));
+    }
+    
     #[tokio::test]
     async fn test_list_conversations() {
         let temp_dir = TempDir::new().unwrap();
@@ -252,7 +1179,49 @@ mod tests {
         ).await.unwrap();
         
         // 列出 user1 的会话
-        let conversations = skill.list_conversations("user1").await.unwrap();
+        let conversations = skill.list_conversations("user1", vec![]).await.unwrap();
         assert_eq!(conversations.len(), 2);
     }
+    
+    #[tokio::test]
+    async fn test_import_history() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill = ImSkill::new(&temp_dir.path().join("im.db")).unwrap();
+        let conv = skill.create_conversation(
+            ConversationType::Direct,
+            None,
+            vec!["user1".to_string(), "user2".to_string()],
+        ).await.unwrap();
+        
+        let mut messages: Vec<Message> = (0..5)
+            .map(|i| Message::new(
+                conv.id.clone(),
+                "user1".to_string(),
+                MessageContent::Text { text: format!("old {}", i) },
+            ))
+            .collect();
+        let existing = messages[0].clone();
+        skill.db().save_message(&existing).await.unwrap();
+        messages.push(Message::new(
+            "other".to_string(),
+            "user1".to_string(),
+            MessageContent::Text { text: "elsewhere".to_string() },
+        ));
+        
+        let result = skill.import_history(&conv.id, &messages).await.unwrap();
+        assert_eq!(result.inserted, 4);
+        assert_eq!(result.skipped_duplicates, 1);
+        assert_eq!(result.failed, 1);
+        assert_eq!(result.failures[0].message_id, messages[5].id);
+        
+        let history = skill.get_history(&conv.id, None, 10).await.unwrap();
+        assert_eq!(history.len(), 5);
+        let conv = skill.get_conversation(&conv.id).await.unwrap().unwrap();
+        assert!(conv.last_message_at.is_some());
+        
+        assert!(matches!(
+            skill.import_history("missing", &messages).await,
+            Err(ImError::ConversationNotFound(_))
+        ));
+    }
 }
@@ -1,12 +1,19 @@
 //! IM Skill Matrix 适配器
 //!
-//! 将 IM Skill 与 Matrix Room 集成
+//! 将 IM Skill 与 Matrix Room 集成：
+//! - [`ImMatrixAdapter`]：实现 CIS Core Skill trait
+//! - [`ImFederation`]：会话与 Matrix Room 之间的双向消息转发
 
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::path::Path;
-use std::sync::Arc;
-use tracing::{debug, error, info};
+use std::sync::{Arc, Weak};
+use tracing::{debug, error, info, warn};
 
+use cis_core::matrix::nucleus::{MatrixEvent, MatrixNucleus, RoomId};
+use ruma::events::room::message::RoomMessageEventContent;
+
+use crate::error::{ImError, Result};
 use crate::{ImSkill, ImConfig, types::*};
 
 /// Matrix 消息结构
@@ -22,12 +29,13 @@ pub struct MatrixMessage {
 
 impl MatrixMessage {
     /// 从 MatrixEvent 解析
-    pub fn from_cis_core_event(event: &cis_core::matrix::nucleus::MatrixEvent) -> Option<Self> {
+    pub fn from_cis_core_event(event: &MatrixEvent) -> Option<Self> {
         if event.event_type != "m.room.message" {
             return None;
         }
         
-        let content = event.content.get("content")?;
+        // 兼容嵌套的 `{"content": {...}}` 与 `/sync`、`send_event` 产生的扁平内容
+        let content = event.content.get("content").unwrap_or(&event.content);
         let msgtype = content.get("msgtype")?.as_str()?.to_string();
         let body = content.get("body")?.as_str()?.to_string();
         
@@ -40,6 +48,169 @@ impl MatrixMessage {
             timestamp: event.timestamp,
         })
     }
+    
+    /// 转换为 IM 消息内容
+    pub fn to_content(&self) -> MessageContent {
+        match self.msgtype.as_str() {
+            "m.text" => {
+                MessageContent::Text { text: self.body.clone() }
+            }
+            "m.image" => {
+                // 简化处理，实际应该解析 mxc URL
+                MessageContent::Image { 
+                    url: format!("mxc://{}/image", self.room_id),
+                    width: None,
+                    height: None,
+                    alt_text: Some(self.body.clone()),
+                }
+            }
+            "m.file" => {
+                MessageContent::File {
+                    name: self.body.clone(),
+                    url: format!("mxc://{}/file", self.room_id),
+                    size: 0,
+                    mime_type: None,
+                }
+            }
+            "m.audio" | "m.voice" => {
+                MessageContent::Voice {
+                    url: format!("mxc://{}/voice", self.room_id),
+                    duration_secs: 0,
+                }
+            }
+            _ => {
+                // 不支持的消息类型，转为文本
+                MessageContent::Text { 
+                    text: format!("[Unsupported message type: {}] {}", self.msgtype, self.body) 
+                }
+            }
+        }
+    }
+}
+
+/// IM 联邦转发
+///
+/// 按 `room_mapping`（会话 ID -> Matrix Room ID）把本地消息发送到 Room，
+/// 并把 Room 中的消息写入 IM 数据库。已处理的 Matrix 事件 ID 记录在
+/// `matrix_events` 表中，本地发出的消息经事件总线回流时不会重复写入。
+pub struct ImFederation {
+    nucleus: Arc<MatrixNucleus>,
+    /// 会话 ID -> Room ID
+    rooms: HashMap<String, RoomId>,
+    /// Room ID -> 会话 ID
+    conversations: HashMap<String, String>,
+    /// 串行化本地发送与事件接收，保证回流事件到达时映射已记录
+    outgoing: tokio::sync::Mutex<()>,
+}
+
+impl ImFederation {
+    /// 创建联邦转发
+    pub fn new(nucleus: Arc<MatrixNucleus>, room_mapping: HashMap<String, String>) -> Result<Self> {
+        let mut rooms = HashMap::new();
+        let mut conversations = HashMap::new();
+        for (conversation_id, room_id) in room_mapping {
+            let room = RoomId::parse(&room_id)
+                .map_err(|e| ImError::Other(format!("Invalid room ID '{}': {}", room_id, e)))?;
+            if let Some(other) = conversations.insert(room_id.clone(), conversation_id.clone()) {
+                return Err(ImError::Other(format!(
+                    "Room {} mapped to both {} and {}",
+                    room_id, other, conversation_id
+                )));
+            }
+            rooms.insert(conversation_id, room);
+        }
+        
+        Ok(Self {
+            nucleus,
+            rooms,
+            conversations,
+            outgoing: tokio::sync::Mutex::new(()),
+        })
+    }
+    
+    /// 会话对应的 Room
+    pub fn room_for(&self, conversation_id: &str) -> Option<&RoomId> {
+        self.rooms.get(conversation_id)
+    }
+    
+    /// Room 对应的会话
+    pub fn conversation_for(&self, room_id: &str) -> Option<&str> {
+        self.conversations.get(room_id).map(String::as_str)
+    }
+    
+    /// 把本地消息发送到会话对应的 Room，未映射的会话忽略
+    pub async fn forward(&self, skill: &ImSkill, message: &Message) -> Result<()> {
+        let Some(room_id) = self.room_for(&message.conversation_id) else {
+            return Ok(());
+        };
+        
+        let body = match message.content.text_content() {
+            Some(text) => text.to_string(),
+            None => format!("[{}]", message.content.content_type()),
+        };
+        
+        let _guard = self.outgoing.lock().await;
+        let event_id = self.nucleus
+            .send_event(room_id, RoomMessageEventContent::text_plain(body))
+            .await
+            .map_err(|e| ImError::Other(format!("Failed to send to room {}: {}", room_id, e)))?;
+        skill.db().record_matrix_event(event_id.as_str(), &message.id).await?;
+        
+        debug!("Forwarded message {} to room {} as {}", message.id, room_id, event_id);
+        Ok(())
+    }
+    
+    /// 写入 Room 中的消息，返回新写入的消息
+    ///
+    /// 非消息事件、未映射的 Room 和已处理过的事件返回 `None`。
+    pub async fn ingest(&self, skill: &ImSkill, event: &MatrixEvent) -> Result<Option<Message>> {
+        let Some(conversation_id) = self.conversation_for(event.room_id_str()) else {
+            return Ok(None);
+        };
+        let Some(msg) = MatrixMessage::from_cis_core_event(event) else {
+            return Ok(None);
+        };
+        
+        let _guard = self.outgoing.lock().await;
+        if skill.db().get_matrix_event_message(&msg.event_id).await?.is_some() {
+            debug!("Skipping already stored Matrix event {}", msg.event_id);
+            return Ok(None);
+        }
+        
+        let message = skill
+            .receive_message(conversation_id, &msg.sender, msg.to_content(), msg.timestamp)
+            .await?;
+        skill.db().record_matrix_event(&msg.event_id, &message.id).await?;
+        
+        Ok(Some(message))
+    }
+    
+    /// 启动事件接收任务（包括 `MatrixNucleus::start_sync` 拉取的事件）
+    ///
+    /// Skill 被释放或事件总线关闭时任务退出。
+    pub(crate) fn spawn_ingest(self: &Arc<Self>, skill: Weak<ImSkill>) -> tokio::task::JoinHandle<()> {
+        let federation = Arc::clone(self);
+        let mut events = self.nucleus.subscribe_events();
+        
+        tokio::spawn(async move {
+            loop {
+                let event = match events.recv().await {
+                    Ok(event) => event,
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("IM federation lagged, skipped {} Matrix events", n);
+                        continue;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                };
+                let Some(skill) = skill.upgrade() else {
+                    break;
+                };
+                if let Err(e) = federation.ingest(&skill, &event).await {
+                    warn!("Failed to ingest Matrix event {}: {}", event.event_id, e);
+                }
+            }
+        })
+    }
 }
 
 /// IM Skill Matrix 适配器
@@ -88,41 +259,7 @@ impl ImMatrixAdapter {
             }
         };
         
-        // 根据消息类型处理
-        let content = match msg.msgtype.as_str() {
-            "m.text" => {
-                MessageContent::Text { text: msg.body }
-            }
-            "m.image" => {
-                // 简化处理，实际应该解析 mxc URL
-                MessageContent::Image { 
-                    url: format!("mxc://{}/image", msg.room_id),
-                    width: None,
-                    height: None,
-                    alt_text: Some(msg.body),
-                }
-            }
-            "m.file" => {
-                MessageContent::File {
-                    name: msg.body,
-                    url: format!("mxc://{}/file", msg.room_id),
-                    size: 0,
-                    mime_type: None,
-                }
-            }
-            "m.audio" | "m.voice" => {
-                MessageContent::Voice {
-                    url: format!("mxc://{}/voice", msg.room_id),
-                    duration_secs: 0,
-                }
-            }
-            _ => {
-                // 不支持的消息类型，转为文本
-                MessageContent::Text { 
-                    text: format!("[Unsupported message type: {}] {}", msg.msgtype, msg.body) 
-                }
-            }
-        };
+        let content = msg.to_content();
         
         // 发送消息
         self.inner.send_message(&conversation.id, &msg.sender, content).await?;
@@ -267,6 +404,16 @@ impl cis_core::skill::Skill for ImMatrixAdapter {
     ) -> cis_core::error::Result<()> {
         debug!("Received Matrix event: {} in room {}", event.event_type, event.room_id);
         
+        // 联邦映射的 Room 由 ImFederation 处理（按事件 ID 去重）
+        if let Some(federation) = self.inner.federation() {
+            if federation.conversation_for(event.room_id_str()).is_some() {
+                federation.ingest(&self.inner, &event).await.map_err(|e| {
+                    cis_core::error::CisError::skill(format!("IM error: {}", e))
+                })?;
+                return Ok(());
+            }
+        }
+        
         if let Some(msg) = MatrixMessage::from_cis_core_event(&event) {
             if let Err(e) = self.handle_matrix_message(msg).await {
                 error!("Failed to handle Matrix message: {}", e);
@@ -67,6 +67,16 @@ pub struct MessageFilter {
     pub after: Option<chrono::DateTime<Utc>>,
     /// 时间范围结束
     pub before: Option<chrono::DateTime<Utc>>,
+    /// 语言过滤（ISO 639-1，匹配 `Message::detected_language`）
+    pub language: Option<String>,
+}
+
+impl MessageFilter {
+    /// 只保留识别为指定语言的消息
+    pub fn filter_by_language(mut self, lang: &str) -> Self {
+        self.language = Some(lang.to_ascii_lowercase());
+        self
+    }
 }
 
 /// 接收消息结果
@@ -208,12 +218,18 @@ impl MessageManager {
             return Err(ImError::Unauthorized);
         }
         
+        // 频道只允许管理员发送
+        if session.conversation_type.is_channel() && !session.is_admin(sender_id) {
+            return Err(ImError::ChannelReadOnly(session_id.to_string()));
+        }
+        
         // 创建消息
-        let message = Message::new(
+        let mut message = Message::new(
             session_id.to_string(),
             sender_id.to_string(),
             content.clone(),
         );
+        message.detected_language = content.text_content().and_then(crate::language::detect);
         
         // 保存到数据库
         if options.persist {
@@ -398,6 +414,13 @@ impl MessageManager {
                     }
                 }
                 
+                // 语言过滤
+                if let Some(ref language) = filter.language {
+                    if msg.detected_language.as_deref() != Some(language.as_str()) {
+                        return false;
+                    }
+                }
+                
                 true
             })
             .take(limit)
@@ -599,6 +622,7 @@ mod tests {
             conversation_type: ConversationType::Group,
             name: Some("Test Session".to_string()),
             participants: vec!["user1".to_string(), "user2".to_string()],
+            admins: Vec::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
             last_message_at: None,
@@ -803,6 +827,21 @@ mod tests {
         let results = manager.filter_messages(&session_id, filter, 10).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].sender_id, "user1");
+        
+        // 按语言过滤
+        manager
+            .send_text(
+                &session_id,
+                "user2",
+                "我们计划在周五安全审计完成之后发布新版本",
+                SendOptions { persist: true, ..Default::default() },
+            )
+            .await
+            .unwrap();
+        let filter = MessageFilter::default().filter_by_language("zh");
+        let results = manager.filter_messages(&session_id, filter, 10).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].detected_language.as_deref(), Some("zh"));
     }
     
     #[tokio::test]
@@ -1,6 +1,10 @@
 //! IM 消息语义搜索
 //!
-//! 集成 VectorStorage 实现消息语义搜索。
+//! 集成 VectorStorage 实现消息语义搜索，并基于 SQLite FTS5 提供关键词全文检索。
+//!
+//! FTS5 默认按空白/标点分词，无法切分中日韩文本。[`FtsTokenizerConfig`] 为
+//! `Cjk` / `Mixed` 选用 `trigram` 分词器（无需词典），`Latin` 使用 `unicode61`
+//! 并按 `min_gram..=max_gram` 建立前缀索引。
 
 use std::sync::Arc;
 use serde::{Deserialize, Serialize};
@@ -8,7 +12,105 @@ use serde::{Deserialize, Serialize};
 use crate::db::ImDatabase;
 use crate::message::MessageManager;
 use crate::types::MessageContent;
-use crate::error::Result;
+use crate::error::{ImError, Result};
+
+/// trigram 分词器的 n-gram 长度（SQLite 固定为 3）
+const TRIGRAM_LEN: usize = 3;
+
+/// `Language::Auto` 判断语言时采样的消息数
+const AUTO_DETECT_SAMPLE: usize = 200;
+
+/// 全文索引语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    /// 根据已有消息自动选择（含 CJK 字符时按 `Mixed`，否则按 `Latin`）
+    #[default]
+    Auto,
+    /// 中日韩文本
+    Cjk,
+    /// 空格分词的拉丁语系文本
+    Latin,
+    /// CJK 与拉丁文混排
+    Mixed,
+}
+
+/// FTS5 分词配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FtsTokenizerConfig {
+    /// 索引语言
+    pub language: Language,
+    /// 最小 n-gram 长度：`Latin` 的最短前缀索引；trigram 下短于 3 的查询改为子串匹配
+    pub min_gram: u8,
+    /// 最大 n-gram 长度：`Latin` 的最长前缀索引
+    pub max_gram: u8,
+}
+
+impl Default for FtsTokenizerConfig {
+    fn default() -> Self {
+        Self {
+            language: Language::Auto,
+            min_gram: 2,
+            max_gram: 4,
+        }
+    }
+}
+
+impl FtsTokenizerConfig {
+    /// 指定语言，其余取默认值
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            ..Default::default()
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.min_gram == 0 || self.min_gram > self.max_gram {
+            return Err(ImError::Other(format!(
+                "Invalid FTS n-gram range: {}..={}",
+                self.min_gram, self.max_gram
+            )));
+        }
+        Ok(())
+    }
+
+    /// 已确定语言对应的 FTS5 表选项
+    fn fts_options(&self, language: Language) -> String {
+        match language {
+            Language::Cjk => "tokenize = 'trigram'".to_string(),
+            Language::Mixed => "tokenize = 'trigram remove_diacritics 1'".to_string(),
+            Language::Latin | Language::Auto => {
+                let prefixes: Vec<String> = (self.min_gram..=self.max_gram)
+                    .map(|n| n.to_string())
+                    .collect();
+                format!(
+                    "tokenize = 'unicode61 remove_diacritics 2', prefix = '{}'",
+                    prefixes.join(" ")
+                )
+            }
+        }
+    }
+}
+
+/// 是否为中日韩字符（汉字、假名、谚文及 CJK 标点）
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3000..=0x303F     // CJK 标点
+        | 0x3040..=0x30FF   // 平假名、片假名
+        | 0x3400..=0x4DBF   // 扩展 A
+        | 0x4E00..=0x9FFF   // 基本汉字
+        | 0xAC00..=0xD7AF   // 谚文音节
+        | 0xF900..=0xFAFF   // 兼容汉字
+        | 0xFF00..=0xFFEF   // 全角字符
+        | 0x20000..=0x2FFFF // 扩展 B 及以后
+    )
+}
+
+/// 把用户输入转为 FTS5 短语（双引号转义）
+fn fts_phrase(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
 
 /// 搜索结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,16 +125,105 @@ pub struct MessageSearchResult {
 
 /// IM 消息语义搜索器
 pub struct ImMessageSearch {
-    #[allow(dead_code)]
     db: Arc<ImDatabase>,
-    #[allow(dead_code)]
     message_manager: Arc<MessageManager>,
+    tokenizer: FtsTokenizerConfig,
+    /// 实际使用的语言（`Auto` 已解析）
+    language: Language,
 }
 
 impl ImMessageSearch {
     /// 创建新的搜索器
-    pub fn new(db: Arc<ImDatabase>, message_manager: Arc<MessageManager>) -> Self {
-        Self { db, message_manager }
+    ///
+    /// 按分词配置创建消息全文索引；配置与已有索引不同时会重建并回填。
+    pub async fn new(
+        db: Arc<ImDatabase>,
+        message_manager: Arc<MessageManager>,
+        tokenizer: FtsTokenizerConfig,
+    ) -> Result<Self> {
+        tokenizer.validate()?;
+
+        let language = match tokenizer.language {
+            Language::Auto => {
+                let texts = db.recent_message_texts(AUTO_DETECT_SAMPLE).await?;
+                if texts.iter().any(|t| t.chars().any(is_cjk)) {
+                    Language::Mixed
+                } else {
+                    Language::Latin
+                }
+            }
+            language => language,
+        };
+
+        if db.ensure_message_fts(&tokenizer.fts_options(language)).await? {
+            tracing::info!("Built message FTS index ({:?} tokenizer)", language);
+        }
+
+        Ok(Self {
+            db,
+            message_manager,
+            tokenizer,
+            language,
+        })
+    }
+
+    /// 实际使用的索引语言
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// 关键词全文检索
+    ///
+    /// trigram 索引下按短语匹配（短于 3 个字符时退化为子串匹配）；
+    /// `Latin` 索引下各词均需出现，长度不小于 `min_gram` 的词按前缀匹配。
+    pub async fn keyword_search(
+        &self,
+        query: &str,
+        session_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<MessageSearchResult>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let messages = match self.language {
+            Language::Cjk | Language::Mixed => {
+                if query.chars().count() < TRIGRAM_LEN {
+                    self.db.substring_messages_fts(query, session_id, limit).await?
+                } else {
+                    self.db.match_messages_fts(&fts_phrase(query), session_id, limit).await?
+                }
+            }
+            Language::Latin | Language::Auto => {
+                let expr = query
+                    .split_whitespace()
+                    .map(|term| {
+                        if term.chars().count() >= self.tokenizer.min_gram as usize {
+                            format!("{}*", fts_phrase(term))
+                        } else {
+                            fts_phrase(term)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                self.db.match_messages_fts(&expr, session_id, limit).await?
+            }
+        };
+
+        Ok(messages
+            .into_iter()
+            .map(|msg| MessageSearchResult {
+                message_id: msg.id.clone(),
+                session_id: msg.conversation_id.clone(),
+                sender_id: msg.sender_id.clone(),
+                content_preview: msg.content.text_content()
+                    .map(|s| s.chars().take(100).collect())
+                    .unwrap_or_default(),
+                similarity: 1.0, // 关键词匹配给最高相似度
+                timestamp: msg.created_at,
+            })
+            .collect())
     }
 
     /// 索引消息
@@ -101,41 +292,16 @@ impl ImMessageSearch {
         // 同时进行语义搜索和关键词搜索
         let semantic_results = self.semantic_search(query, session_id, limit).await?;
         
-        // 关键词搜索通过 MessageManager 实现
-        let keyword_results = if let Some(sid) = session_id {
-            // 使用 filter_messages 进行关键词过滤
-            let filter = crate::message::MessageFilter {
-                ..Default::default()
-            };
-            let messages = self.message_manager.filter_messages(sid, filter, limit).await?;
-            
-            messages
-                .into_iter()
-                .filter(|msg| {
-                    if let Some(text) = msg.content.text_content() {
-                        text.to_lowercase().contains(&query.to_lowercase())
-                    } else {
-                        false
-                    }
-                })
-                .map(|msg| MessageSearchResult {
-                    message_id: msg.id.clone(),
-                    session_id: msg.conversation_id.clone(),
-                    sender_id: msg.sender_id.clone(),
-                    content_preview: msg.content.text_content()
-                        .map(|s| s.chars().take(100).collect())
-                        .unwrap_or_default(),
-                    similarity: 1.0, // 关键词匹配给最高相似度
-                    timestamp: msg.created_at,
-                })
-                .collect::<Vec<_>>()
-        } else {
-            vec![]
-        };
+        // 关键词搜索走 FTS5 全文索引
+        let keyword_results = self.keyword_search(query, session_id, limit).await?;
 
         // 合并结果并去重
         let mut results = semantic_results;
-        results.extend(keyword_results);
+        for result in keyword_results {
+            if !results.iter().any(|r| r.message_id == result.message_id) {
+                results.push(result);
+            }
+        }
         
         // 按相似度排序
         results.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap());
@@ -234,19 +400,41 @@ mod tests {
     use crate::session::SessionManager;
 
     async fn setup_search() -> (ImMessageSearch, Arc<ImDatabase>, tempfile::TempDir) {
+        setup_search_with(FtsTokenizerConfig::default()).await
+    }
+
+    async fn setup_search_with(
+        tokenizer: FtsTokenizerConfig,
+    ) -> (ImMessageSearch, Arc<ImDatabase>, tempfile::TempDir) {
         let temp_dir = TempDir::new().unwrap();
         let db = Arc::new(ImDatabase::open(&temp_dir.path().join("test.db")).unwrap());
         let msg_manager = Arc::new(MessageManager::new(Arc::clone(&db)));
-        let search = ImMessageSearch::new(Arc::clone(&db), msg_manager);
+        let search = ImMessageSearch::new(Arc::clone(&db), msg_manager, tokenizer)
+            .await
+            .unwrap();
         (search, db, temp_dir)
     }
 
+    async fn save_text(db: &ImDatabase, conv_id: &str, text: &str) {
+        let msg = Message::new(
+            conv_id.to_string(),
+            "user1".to_string(),
+            MessageContent::Text { text: text.to_string() },
+        );
+        db.save_message(&msg).await.unwrap();
+    }
+
+    async fn hits(search: &ImMessageSearch, query: &str) -> usize {
+        search.keyword_search(query, None, 10).await.unwrap().len()
+    }
+
     async fn create_test_conversation(db: &ImDatabase) -> String {
         let conv = Conversation {
             id: "test-conv".to_string(),
             conversation_type: ConversationType::Group,
             name: Some("Test".to_string()),
             participants: vec!["user1".to_string(), "user2".to_string()],
+            admins: Vec::new(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             last_message_at: None,
@@ -313,4 +501,62 @@ mod tests {
         // 至少应该有关键词匹配结果
         assert!(!results.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_cjk_search_recall() {
+        let (search, db, _temp) = setup_search_with(FtsTokenizerConfig::new(Language::Cjk)).await;
+        let conv_id = create_test_conversation(&db).await;
+
+        save_text(&db, &conv_id, "今天我们讨论搜索功能的实现").await;
+        save_text(&db, &conv_id, "明天下午开会讨论预算").await;
+        save_text(&db, &conv_id, "東京の天気は晴れです").await;
+        save_text(&db, &conv_id, "検索機能をテストしています").await;
+        save_text(&db, &conv_id, "Hello World").await;
+
+        // 中文
+        assert_eq!(hits(&search, "搜索功能").await, 1);
+        assert_eq!(hits(&search, "讨论").await, 2); // 短于 trigram，走子串匹配
+        assert_eq!(hits(&search, "开会讨论预算").await, 1);
+        // 日文
+        assert_eq!(hits(&search, "検索機能").await, 1);
+        assert_eq!(hits(&search, "晴れです").await, 1);
+        assert_eq!(hits(&search, "天気").await, 1);
+        // 不存在的词
+        assert_eq!(hits(&search, "数据库").await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_auto_language_rebuilds_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Arc::new(ImDatabase::open(&temp_dir.path().join("test.db")).unwrap());
+        let msg_manager = Arc::new(MessageManager::new(Arc::clone(&db)));
+        let conv_id = create_test_conversation(&db).await;
+
+        // 先以拉丁语系建索引
+        let latin = ImMessageSearch::new(Arc::clone(&db), Arc::clone(&msg_manager), FtsTokenizerConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(latin.language(), Language::Latin);
+        save_text(&db, &conv_id, "Let's discuss the implementation").await;
+        assert_eq!(hits(&latin, "impl").await, 1); // 前缀匹配
+        save_text(&db, &conv_id, "我们来讨论一下实现方案").await;
+
+        // 出现中文后重新打开，自动切换为混合分词并回填已有消息
+        let auto = ImMessageSearch::new(Arc::clone(&db), msg_manager, FtsTokenizerConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(auto.language(), Language::Mixed);
+        assert_eq!(hits(&auto, "实现方案").await, 1);
+        assert_eq!(hits(&auto, "implementation").await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_gram_range() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Arc::new(ImDatabase::open(&temp_dir.path().join("test.db")).unwrap());
+        let msg_manager = Arc::new(MessageManager::new(Arc::clone(&db)));
+        let config = FtsTokenizerConfig { language: Language::Latin, min_gram: 4, max_gram: 2 };
+
+        assert!(ImMessageSearch::new(db, msg_manager, config).await.is_err());
+    }
 }
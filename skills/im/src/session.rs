@@ -37,6 +37,7 @@ impl SessionManager {
             conversation_type: ConversationType::Direct,
             name: None,
             participants: vec![user1, user2],
+            admins: Vec::new(),
             created_at: now,
             updated_at: now,
             last_message_at: None,
@@ -66,6 +67,7 @@ impl SessionManager {
             conversation_type: ConversationType::Group,
             name: Some(name),
             participants,
+            admins: Vec::new(),
             created_at: now,
             updated_at: now,
             last_message_at: None,
@@ -91,19 +93,18 @@ impl SessionManager {
         let now = Utc::now();
         let session = Conversation {
             id: uuid::Uuid::new_v4().to_string(),
-            conversation_type: ConversationType::Channel,
+            conversation_type: ConversationType::Channel {
+                topic: String::new(),
+                is_public: true,
+            },
             name: Some(name),
-            participants: vec![owner],
+            participants: vec![owner.clone()],
+            admins: vec![owner],
             created_at: now,
             updated_at: now,
             last_message_at: None,
             avatar_url: None,
-            metadata: serde_json::json!({
-                "channel_settings": {
-                    "public": true,
-                    "readonly": false,
-                }
-            }),
+            metadata: serde_json::Value::Null,
         };
 
         self.db.create_conversation(&session).await?;
@@ -138,7 +139,7 @@ impl SessionManager {
 
     /// 列出用户的所有会话
     pub async fn list_user_sessions(&self, user_id: &str) -> Result<Vec<Conversation>> {
-        self.db.list_conversations(user_id).await
+        self.db.list_conversations(user_id, Vec::new()).await
     }
 
     /// 添加参与者
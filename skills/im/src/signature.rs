@@ -0,0 +1,119 @@
+//! 消息签名
+//!
+//! 签名内容为 `conversation_id + message_id + content_hash`，
+//! 其中 `content_hash` 为消息内容 JSON 的 SHA-256（十六进制）。
+//! 验证时通过发送方 DID 解析 DID 文档获取公钥。
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::Result;
+use crate::types::{Message, MessageContent};
+
+/// 消息验证结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "reason", rename_all = "snake_case")]
+pub enum VerificationResult {
+    /// 签名有效
+    Verified,
+    /// 签名无效或无法验证
+    Unverified(String),
+    /// 消息未签名
+    NoSignature,
+}
+
+impl VerificationResult {
+    pub fn is_verified(&self) -> bool {
+        matches!(self, VerificationResult::Verified)
+    }
+
+    /// 历史记录中显示的标记
+    pub fn badge(&self) -> &'static str {
+        match self {
+            VerificationResult::Verified => "✅ 已验证",
+            VerificationResult::Unverified(_) => "⚠️ 未验证",
+            VerificationResult::NoSignature => "⬜ 未签名",
+        }
+    }
+}
+
+/// 消息内容哈希
+pub fn content_hash(content: &MessageContent) -> Result<String> {
+    let json = serde_json::to_vec(content)?;
+    Ok(hex::encode(Sha256::digest(&json)))
+}
+
+/// 签名覆盖的字节
+pub fn signing_payload(message: &Message) -> Result<Vec<u8>> {
+    let hash = content_hash(&message.content)?;
+    Ok(format!("{}{}{}", message.conversation_id, message.id, hash).into_bytes())
+}
+
+/// 签名消息，写入 `message.signature`
+pub fn sign_message(message: &mut Message, key: &SigningKey) -> Result<()> {
+    let payload = signing_payload(message)?;
+    message.signature = Some(hex::encode(key.sign(&payload).to_bytes()));
+    Ok(())
+}
+
+/// 用给定公钥验证消息签名
+pub fn verify_with_key(message: &Message, key: &VerifyingKey) -> Result<VerificationResult> {
+    let Some(signature_hex) = &message.signature else {
+        return Ok(VerificationResult::NoSignature);
+    };
+
+    let signature = match hex::decode(signature_hex)
+        .ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+    {
+        Some(signature) => signature,
+        None => return Ok(VerificationResult::Unverified("malformed signature".to_string())),
+    };
+
+    let payload = signing_payload(message)?;
+    match key.verify(&payload, &signature) {
+        Ok(()) => Ok(VerificationResult::Verified),
+        Err(_) => Ok(VerificationResult::Unverified("signature mismatch".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> MessageContent {
+        MessageContent::Text { text: s.to_string() }
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = SigningKey::from_bytes(&[1u8; 32]);
+        let mut message = Message::new("conv-1".to_string(), "did:cis:a:0011".to_string(), text("hi"));
+
+        assert_eq!(
+            verify_with_key(&message, &key.verifying_key()).unwrap(),
+            VerificationResult::NoSignature
+        );
+
+        sign_message(&mut message, &key).unwrap();
+        assert!(verify_with_key(&message, &key.verifying_key()).unwrap().is_verified());
+
+        // 内容被篡改
+        message.content = text("bye");
+        assert!(matches!(
+            verify_with_key(&message, &key.verifying_key()).unwrap(),
+            VerificationResult::Unverified(_)
+        ));
+    }
+
+    #[test]
+    fn test_verify_with_wrong_key() {
+        let key = SigningKey::from_bytes(&[1u8; 32]);
+        let other = SigningKey::from_bytes(&[2u8; 32]);
+        let mut message = Message::new("conv-1".to_string(), "did:cis:a:0011".to_string(), text("hi"));
+        sign_message(&mut message, &key).unwrap();
+
+        assert!(!verify_with_key(&message, &other.verifying_key()).unwrap().is_verified());
+    }
+}
@@ -0,0 +1,237 @@
+//! 智能回复
+//!
+//! 以会话最近的消息为上下文，通过 AI Provider 的结构化输出生成若干条候选回复，
+//! 每条附带回复类型。结果按 `(最后一条消息 ID, 用户 ID)` 缓存 30 秒，
+//! 避免界面反复刷新时重复调用模型。
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use cis_core::ai::AiProvider;
+
+use crate::error::{ImError, Result};
+use crate::types::{Message, MessageId, UserId};
+
+/// 作为上下文的最近消息数
+pub const SMART_REPLY_CONTEXT_MESSAGES: usize = 10;
+
+/// 缓存有效期
+pub const SMART_REPLY_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// 回复类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplyType {
+    /// 同意
+    Agree,
+    /// 反对
+    Disagree,
+    /// 追问
+    Question,
+    /// 确认收到
+    Acknowledge,
+    /// 其他
+    Custom,
+}
+
+/// 候选回复
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmartReply {
+    pub text: String,
+    pub reply_type: ReplyType,
+}
+
+#[derive(Debug, Deserialize)]
+struct SmartReplyOutput {
+    replies: Vec<SmartReply>,
+}
+
+fn smart_reply_schema(count: usize) -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "required": ["replies"],
+        "properties": {
+            "replies": {
+                "type": "array",
+                "minItems": count,
+                "maxItems": count,
+                "items": {
+                    "type": "object",
+                    "required": ["text", "reply_type"],
+                    "properties": {
+                        "text": { "type": "string" },
+                        "reply_type": {
+                            "type": "string",
+                            "enum": ["agree", "disagree", "question", "acknowledge", "custom"],
+                        },
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// 各语言的智能回复指令模板（`{count}`、`{user}` 为占位符），未收录的语言使用英文
+const SMART_REPLY_PROMPTS: &[(&str, &str)] = &[
+    (
+        "en",
+        "Suggest {count} short replies that {user} could send next in the following conversation. \
+         Keep each reply under 10 words, make them distinct, and classify each one as \
+         agree, disagree, question, acknowledge or custom.",
+    ),
+    (
+        "zh",
+        "请为 {user} 建议 {count} 条可以在以下对话中接着发送的简短中文回复。\
+         每条不超过 20 个字，彼此不同，并将每条归类为 \
+         agree、disagree、question、acknowledge 或 custom。",
+    ),
+    (
+        "ja",
+        "次の会話で {user} が次に送れる短い日本語の返信を {count} 件提案してください。\
+         各返信は 20 文字以内で互いに異なるものにし、それぞれを \
+         agree、disagree、question、acknowledge、custom のいずれかに分類してください。",
+    ),
+    (
+        "es",
+        "Sugiere {count} respuestas cortas en español que {user} podría enviar a continuación en la \
+         siguiente conversación. Cada respuesta debe tener menos de 10 palabras y ser distinta; \
+         clasifica cada una como agree, disagree, question, acknowledge o custom.",
+    ),
+    (
+        "fr",
+        "Propose {count} réponses courtes en français que {user} pourrait envoyer ensuite dans la \
+         conversation suivante. Chaque réponse doit faire moins de 10 mots et être distincte ; \
+         classe chacune comme agree, disagree, question, acknowledge ou custom.",
+    ),
+    (
+        "de",
+        "Schlage {count} kurze Antworten auf Deutsch vor, die {user} als Nächstes im folgenden \
+         Gespräch senden könnte. Jede Antwort soll unter 10 Wörtern bleiben und sich von den \
+         anderen unterscheiden; ordne jede als agree, disagree, question, acknowledge oder custom ein.",
+    ),
+];
+
+/// 按语言选择指令模板
+fn smart_reply_template(language: Option<&str>) -> &'static str {
+    language
+        .and_then(|lang| SMART_REPLY_PROMPTS.iter().find(|(code, _)| *code == lang))
+        .unwrap_or(&SMART_REPLY_PROMPTS[0])
+        .1
+}
+
+/// 构建智能回复 prompt，只包含有文本内容的消息
+///
+/// 指令语言取最近一条已识别语言的消息，使回复与对话语言一致。
+pub(crate) fn smart_reply_prompt(messages: &[Message], user_id: &str, count: usize) -> Option<String> {
+    let lines: Vec<String> = messages
+        .iter()
+        .filter_map(|m| m.content.text_content().map(|text| format!("[{}]: {}", m.sender_id, text)))
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+
+    let language = messages.iter().rev().find_map(|m| m.detected_language.as_deref());
+    let instruction = smart_reply_template(language)
+        .replace("{count}", &count.to_string())
+        .replace("{user}", user_id);
+    Some(format!("{}\n\n{}", instruction, lines.join("\n")))
+}
+
+/// 调用结构化输出生成候选回复，`messages` 按时间升序
+pub(crate) async fn suggest(
+    provider: &dyn AiProvider,
+    messages: &[Message],
+    user_id: &str,
+    count: usize,
+) -> Result<Vec<SmartReply>> {
+    let Some(prompt) = smart_reply_prompt(messages, user_id, count) else {
+        return Ok(Vec::new());
+    };
+
+    let value = provider
+        .structured_chat(&prompt, &smart_reply_schema(count))
+        .await
+        .map_err(|e| ImError::Other(format!("Smart reply generation failed: {}", e)))?;
+    let output: SmartReplyOutput = serde_json::from_value(value).map_err(|e| ImError::Serialization(e.to_string()))?;
+
+    let mut replies = output.replies;
+    replies.retain(|r| !r.text.trim().is_empty());
+    replies.truncate(count);
+    Ok(replies)
+}
+
+/// 按 `(最后一条消息 ID, 用户 ID)` 缓存的候选回复
+pub(crate) struct SmartReplyCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<(MessageId, UserId), (Instant, Vec<SmartReply>)>>,
+}
+
+impl SmartReplyCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 未过期且数量足够时返回缓存
+    pub(crate) fn get(&self, last_message_id: &str, user_id: &str, count: usize) -> Option<Vec<SmartReply>> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, (at, _)| at.elapsed() < self.ttl);
+
+        let (_, replies) = entries.get(&(last_message_id.to_string(), user_id.to_string()))?;
+        (replies.len() >= count).then(|| replies[..count].to_vec())
+    }
+
+    pub(crate) fn insert(&self, last_message_id: &str, user_id: &str, replies: Vec<SmartReply>) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert((last_message_id.to_string(), user_id.to_string()), (Instant::now(), replies));
+    }
+}
+
+impl Default for SmartReplyCache {
+    fn default() -> Self {
+        Self::new(SMART_REPLY_CACHE_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reply(text: &str, reply_type: ReplyType) -> SmartReply {
+        SmartReply { text: text.to_string(), reply_type }
+    }
+
+    #[test]
+    fn test_localized_prompt() {
+        let mut message = Message::new("c".to_string(), "bob".to_string(), crate::types::MessageContent::Text {
+            text: "周五发布吗？".to_string(),
+        });
+        let english = smart_reply_prompt(std::slice::from_ref(&message), "alice", 3).unwrap();
+        assert!(english.starts_with("Suggest 3 short replies that alice"));
+
+        message.detected_language = Some("zh".to_string());
+        let chinese = smart_reply_prompt(std::slice::from_ref(&message), "alice", 3).unwrap();
+        assert!(chinese.starts_with("请为 alice 建议 3 条"));
+        assert!(chinese.ends_with("[bob]: 周五发布吗？"));
+    }
+
+    #[test]
+    fn test_cache_expiry() {
+        let cache = SmartReplyCache::new(Duration::from_millis(50));
+        cache.insert("m1", "alice", vec![reply("Sure", ReplyType::Agree), reply("Why?", ReplyType::Question)]);
+
+        assert_eq!(cache.get("m1", "alice", 1).unwrap(), vec![reply("Sure", ReplyType::Agree)]);
+        // 缓存条数不足时视为未命中
+        assert!(cache.get("m1", "alice", 3).is_none());
+        assert!(cache.get("m1", "bob", 1).is_none());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(cache.get("m1", "alice", 1).is_none());
+    }
+}
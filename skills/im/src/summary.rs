@@ -0,0 +1,127 @@
+//! 会话摘要
+//!
+//! 取会话最近的若干条消息，按 `[sender]: text` 格式交给 AI Provider 生成摘要和话题，
+//! 并统计各参与者的发言数。摘要本身作为 [`MessageContent::Summary`] 消息写回会话。
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use cis_core::ai::AiProvider;
+
+use crate::error::{ImError, Result};
+use crate::types::{ConversationId, Message, MessageContent, MessageId, UserId};
+
+/// 摘要消息的发送方 ID
+pub const SUMMARY_SENDER_ID: &str = "cis:summary";
+
+/// 会话摘要
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationSummary {
+    pub conversation_id: ConversationId,
+    /// 摘要正文
+    pub text: String,
+    /// 识别出的话题
+    pub topics: Vec<String>,
+    /// 各参与者在摘要范围内的消息数
+    pub participant_activity: BTreeMap<UserId, usize>,
+    /// 摘要覆盖的消息范围（首条、末条消息 ID）
+    pub message_range: (MessageId, MessageId),
+    pub generated_at: DateTime<Utc>,
+}
+
+impl ConversationSummary {
+    /// 转换为可写入会话的摘要消息
+    pub fn to_message(&self) -> Message {
+        Message::new(
+            self.conversation_id.clone(),
+            SUMMARY_SENDER_ID.to_string(),
+            MessageContent::Summary {
+                text: self.text.clone(),
+                message_range: self.message_range.clone(),
+                generated_at: self.generated_at,
+            },
+        )
+    }
+}
+
+/// AI 返回的摘要结构
+#[derive(Debug, Deserialize)]
+struct SummaryOutput {
+    summary: String,
+    #[serde(default)]
+    topics: Vec<String>,
+}
+
+fn summary_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "required": ["summary", "topics"],
+        "properties": {
+            "summary": { "type": "string" },
+            "topics": { "type": "array", "items": { "type": "string" } },
+        }
+    })
+}
+
+/// 构建摘要 prompt，只包含有文本内容的消息
+pub(crate) fn summary_prompt(messages: &[Message]) -> Option<String> {
+    let lines: Vec<String> = messages
+        .iter()
+        .filter_map(|m| m.content.text_content().map(|text| format!("[{}]: {}", m.sender_id, text)))
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "Summarize the following conversation in a few sentences, focusing on decisions, \
+         open questions and follow-ups. Also list the main topics discussed.\n\n{}",
+        lines.join("\n")
+    ))
+}
+
+/// 生成摘要
+///
+/// `messages` 按时间升序；已有的摘要消息不参与摘要和发言统计。
+pub(crate) async fn summarize(
+    provider: &dyn AiProvider,
+    conversation_id: &str,
+    messages: &[Message],
+) -> Result<ConversationSummary> {
+    let messages: Vec<Message> = messages
+        .iter()
+        .filter(|m| !matches!(m.content, MessageContent::Summary { .. }))
+        .cloned()
+        .collect();
+    let (Some(first), Some(last)) = (messages.first(), messages.last()) else {
+        return Err(ImError::InvalidMessage(format!(
+            "Conversation {} has no messages to summarize",
+            conversation_id
+        )));
+    };
+    let prompt = summary_prompt(&messages).ok_or_else(|| {
+        ImError::InvalidMessage(format!("Conversation {} has no text messages to summarize", conversation_id))
+    })?;
+
+    let value = provider
+        .structured_chat(&prompt, &summary_schema())
+        .await
+        .map_err(|e| ImError::Other(format!("Conversation summary failed: {}", e)))?;
+    let output: SummaryOutput = serde_json::from_value(value).map_err(|e| ImError::Serialization(e.to_string()))?;
+
+    let mut participant_activity = BTreeMap::new();
+    for message in &messages {
+        *participant_activity.entry(message.sender_id.clone()).or_insert(0) += 1;
+    }
+
+    Ok(ConversationSummary {
+        conversation_id: conversation_id.to_string(),
+        text: output.summary,
+        topics: output.topics,
+        participant_activity,
+        message_range: (first.id.clone(), last.id.clone()),
+        generated_at: Utc::now(),
+    })
+}
@@ -2,8 +2,11 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::validation::ValidationRule;
+
 /// 消息 ID
 pub type MessageId = String;
 
@@ -52,6 +55,27 @@ pub enum MessageContent {
         reply_to: MessageId,
         content: Box<MessageContent>,
     },
+    
+    /// AI 生成的会话摘要
+    #[serde(rename = "summary")]
+    Summary {
+        text: String,
+        /// 摘要覆盖的消息范围（首条、末条消息 ID）
+        message_range: (MessageId, MessageId),
+        generated_at: DateTime<Utc>,
+    },
+}
+
+/// 消息内容类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageContentType {
+    Text,
+    Image,
+    File,
+    Voice,
+    Reply,
+    Summary,
 }
 
 /// 消息结构
@@ -65,6 +89,12 @@ pub struct Message {
     pub updated_at: Option<DateTime<Utc>>,
     pub read_by: Vec<UserId>,
     pub metadata: serde_json::Value,
+    /// 发送方 DID 签名（十六进制），未签名消息为 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// 识别出的语言（ISO 639-1），未识别或非文本消息为 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detected_language: Option<String>,
 }
 
 impl Message {
@@ -82,6 +112,8 @@ impl Message {
             updated_at: None,
             read_by: Vec::new(),
             metadata: serde_json::Value::Null,
+            signature: None,
+            detected_language: None,
         }
     }
     
@@ -90,18 +122,63 @@ impl Message {
             self.read_by.push(user_id);
         }
     }
+
+    /// 序列化后 `Message` 的 JSON Schema
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "required": ["id", "conversation_id", "sender_id", "content", "created_at", "read_by", "metadata"],
+            "properties": {
+                "id": { "type": "string" },
+                "conversation_id": { "type": "string" },
+                "sender_id": { "type": "string" },
+                "content": {
+                    "type": "object",
+                    "required": ["type", "content"],
+                    "properties": {
+                        "type": { "enum": ["text", "image", "file", "voice", "reply", "summary"] },
+                        "content": { "type": "object" }
+                    }
+                },
+                "created_at": { "type": "string" },
+                "updated_at": { "type": ["string", "null"] },
+                "read_by": { "type": "array", "items": { "type": "string" } },
+                "signature": { "type": "string" },
+                "detected_language": { "type": "string" }
+            }
+        })
+    }
 }
 
 /// 会话类型
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ConversationType {
     /// 一对一聊天
     Direct,
     /// 群组聊天
     Group,
-    /// 频道（广播）
-    Channel,
+    /// 频道（广播）：只有管理员可以发送，订阅者只读
+    Channel {
+        topic: String,
+        /// 公开频道可被搜索和自由订阅
+        is_public: bool,
+    },
+}
+
+impl ConversationType {
+    /// 存储用的类型名
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConversationType::Direct => "direct",
+            ConversationType::Group => "group",
+            ConversationType::Channel { .. } => "channel",
+        }
+    }
+    
+    pub fn is_channel(&self) -> bool {
+        matches!(self, ConversationType::Channel { .. })
+    }
 }
 
 /// 会话结构
@@ -111,6 +188,9 @@ pub struct Conversation {
     pub conversation_type: ConversationType,
     pub name: Option<String>,
     pub participants: Vec<UserId>,
+    /// 管理员（同时也是参与者）；频道中只有管理员可以发送消息
+    #[serde(default)]
+    pub admins: Vec<UserId>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub last_message_at: Option<DateTime<Utc>>,
@@ -118,6 +198,12 @@ pub struct Conversation {
     pub metadata: serde_json::Value,
 }
 
+impl Conversation {
+    pub fn is_admin(&self, user_id: &str) -> bool {
+        self.admins.iter().any(|a| a == user_id)
+    }
+}
+
 /// 用户资料
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserProfile {
@@ -149,6 +235,15 @@ pub struct ImConfig {
     pub enable_reactions: bool,
     pub enable_editing: bool,
     pub enable_deletion: bool,
+    /// 是否启用 AI 智能回复（会调用 AI Provider，默认关闭）
+    #[serde(default)]
+    pub smart_replies_enabled: bool,
+    /// 写入记忆的 AI 回复是否要求多个 Provider 达成共识（默认关闭）
+    #[serde(default)]
+    pub use_consensus_for_memory: bool,
+    /// 发送前执行的内容校验规则（不参与序列化）
+    #[serde(skip)]
+    pub validation_rules: Vec<Arc<dyn ValidationRule>>,
 }
 
 impl Default for ImConfig {
@@ -160,6 +255,9 @@ impl Default for ImConfig {
             enable_reactions: true,
             enable_editing: true,
             enable_deletion: true,
+            smart_replies_enabled: false,
+            use_consensus_for_memory: false,
+            validation_rules: Vec::new(),
         }
     }
 }
@@ -173,6 +271,19 @@ impl MessageContent {
             MessageContent::File { .. } => "file",
             MessageContent::Voice { .. } => "voice",
             MessageContent::Reply { .. } => "reply",
+            MessageContent::Summary { .. } => "summary",
+        }
+    }
+    
+    /// 获取内容类型
+    pub fn message_type(&self) -> MessageContentType {
+        match self {
+            MessageContent::Text { .. } => MessageContentType::Text,
+            MessageContent::Image { .. } => MessageContentType::Image,
+            MessageContent::File { .. } => MessageContentType::File,
+            MessageContent::Voice { .. } => MessageContentType::Voice,
+            MessageContent::Reply { .. } => MessageContentType::Reply,
+            MessageContent::Summary { .. } => MessageContentType::Summary,
         }
     }
     
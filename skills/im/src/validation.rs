@@ -0,0 +1,167 @@
+//! 消息内容校验规则
+//!
+//! 通过 [`ImConfig::validation_rules`](crate::ImConfig) 配置，发送消息前依次执行
+//! 全部规则，所有失败汇总为 [`ImError::ContentValidationFailed`](crate::ImError)。
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::types::{MessageContent, MessageContentType};
+
+/// 单条规则的校验失败
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{rule}: {reason}")]
+pub struct ValidationError {
+    /// 规则名称
+    pub rule: String,
+    /// 失败原因
+    pub reason: String,
+}
+
+impl ValidationError {
+    pub fn new(rule: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            rule: rule.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// 消息内容校验规则
+pub trait ValidationRule: std::fmt::Debug + Send + Sync {
+    /// 规则名称（用于错误信息）
+    fn name(&self) -> &str;
+
+    /// 校验消息内容
+    fn validate(&self, content: &MessageContent) -> Result<(), ValidationError>;
+}
+
+/// 执行全部规则，返回所有失败
+pub fn validate_all<'a, I>(rules: I, content: &MessageContent) -> Vec<ValidationError>
+where
+    I: IntoIterator<Item = &'a std::sync::Arc<dyn ValidationRule>>,
+{
+    rules
+        .into_iter()
+        .filter_map(|rule| rule.validate(content).err())
+        .collect()
+}
+
+/// 消息中用户可见的文本（文本、图片说明、文件名，含引用回复）
+fn visible_text(content: &MessageContent) -> Vec<&str> {
+    match content {
+        MessageContent::Text { text } => vec![text.as_str()],
+        MessageContent::Image { alt_text, .. } => alt_text.as_deref().into_iter().collect(),
+        MessageContent::File { name, .. } => vec![name.as_str()],
+        MessageContent::Voice { .. } => vec![],
+        MessageContent::Reply { content, .. } => visible_text(content),
+        MessageContent::Summary { text, .. } => vec![text.as_str()],
+    }
+}
+
+/// 文本最大字符数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaxLengthRule {
+    pub max_chars: usize,
+}
+
+impl ValidationRule for MaxLengthRule {
+    fn name(&self) -> &str {
+        "max_length"
+    }
+
+    fn validate(&self, content: &MessageContent) -> Result<(), ValidationError> {
+        let len = content.text_content().map_or(0, |text| text.chars().count());
+        if len > self.max_chars {
+            return Err(ValidationError::new(
+                self.name(),
+                format!("text has {} characters, limit is {}", len, self.max_chars),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 禁用关键词（不区分大小写）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockedKeywordsRule {
+    pub keywords: Vec<String>,
+}
+
+impl ValidationRule for BlockedKeywordsRule {
+    fn name(&self) -> &str {
+        "blocked_keywords"
+    }
+
+    fn validate(&self, content: &MessageContent) -> Result<(), ValidationError> {
+        let text = visible_text(content).join("\n").to_lowercase();
+        let found: Vec<&str> = self
+            .keywords
+            .iter()
+            .filter(|keyword| !keyword.is_empty() && text.contains(&keyword.to_lowercase()))
+            .map(String::as_str)
+            .collect();
+
+        if !found.is_empty() {
+            return Err(ValidationError::new(
+                self.name(),
+                format!("contains blocked keywords: {}", found.join(", ")),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 允许的消息类型
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AllowedContentTypesRule {
+    pub types: Vec<MessageContentType>,
+}
+
+impl ValidationRule for AllowedContentTypesRule {
+    fn name(&self) -> &str {
+        "allowed_content_types"
+    }
+
+    fn validate(&self, content: &MessageContent) -> Result<(), ValidationError> {
+        let content_type = content.message_type();
+        if !self.types.contains(&content_type) {
+            return Err(ValidationError::new(
+                self.name(),
+                format!("content type '{}' is not allowed", content.content_type()),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_builtin_rules() {
+        let text = MessageContent::Text { text: "Buy SPAM now".to_string() };
+        let file = MessageContent::File {
+            name: "spam.zip".to_string(),
+            url: "mxc://x/file".to_string(),
+            size: 1,
+            mime_type: None,
+        };
+
+        assert!(MaxLengthRule { max_chars: 12 }.validate(&text).is_ok());
+        assert!(MaxLengthRule { max_chars: 5 }.validate(&text).is_err());
+
+        let blocked = BlockedKeywordsRule { keywords: vec!["spam".to_string()] };
+        assert!(blocked.validate(&text).is_err());
+        assert!(blocked.validate(&file).is_err());
+
+        let text_only = AllowedContentTypesRule { types: vec![MessageContentType::Text] };
+        assert!(text_only.validate(&text).is_ok());
+        assert!(text_only.validate(&file).is_err());
+
+        let rules: Vec<Arc<dyn ValidationRule>> = vec![Arc::new(blocked), Arc::new(text_only)];
+        assert_eq!(validate_all(&rules, &file).len(), 2);
+    }
+}
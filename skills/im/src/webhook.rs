@@ -0,0 +1,279 @@
+//! 出站 Webhook
+//!
+//! 第三方应用注册 URL 与密钥后，IM 事件（新消息、消息编辑、用户加入等）以 JSON 形式
+//! POST 到该 URL。签名方式与飞书自定义机器人一致：
+//! `sign = base64(HmacSHA256(key = "{timestamp}\n{secret}", data = ""))`，
+//! `timestamp` 与 `sign` 随请求体一起发送，接收方按同样方式计算后比对。
+//!
+//! 非 2xx 响应最多重试 3 次，每次间隔 1 秒。投递在后台任务中进行，不阻塞消息发送。
+
+use std::time::Duration;
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tracing::warn;
+
+use crate::error::{ImError, Result};
+use crate::types::ConversationId;
+
+/// Webhook ID
+pub type WebhookId = String;
+
+/// 最大投递次数
+pub const WEBHOOK_MAX_ATTEMPTS: u32 = 3;
+
+/// 重试间隔
+pub const WEBHOOK_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// 可订阅的 IM 事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    NewMessage,
+    MessageEdited,
+    UserJoined,
+    UserLeft,
+}
+
+/// Webhook 配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImWebhookConfig {
+    pub url: String,
+    /// 签名密钥
+    pub secret: String,
+    /// 订阅的事件
+    pub events: Vec<WebhookEvent>,
+}
+
+impl ImWebhookConfig {
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !(self.url.starts_with("http://") || self.url.starts_with("https://")) {
+            return Err(ImError::InvalidMessage(format!("Invalid webhook URL: {}", self.url)));
+        }
+        if self.secret.is_empty() {
+            return Err(ImError::InvalidMessage("Webhook secret must not be empty".to_string()));
+        }
+        if self.events.is_empty() {
+            return Err(ImError::InvalidMessage("Webhook must subscribe to at least one event".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// 已注册的 Webhook
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: WebhookId,
+    pub config: ImWebhookConfig,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    pub fn subscribes_to(&self, event: WebhookEvent) -> bool {
+        self.config.events.contains(&event)
+    }
+}
+
+/// 投递的请求体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    /// Unix 秒
+    pub timestamp: i64,
+    /// 签名，见模块文档
+    pub sign: String,
+    pub event: WebhookEvent,
+    pub conversation_id: ConversationId,
+    pub data: serde_json::Value,
+}
+
+/// 飞书风格签名
+pub fn sign(secret: &str, timestamp: i64) -> String {
+    let key = format!("{}\n{}", timestamp, secret);
+    let mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length");
+    base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+}
+
+/// Webhook 投递器
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    client: reqwest::Client,
+    max_attempts: u32,
+    retry_delay: Duration,
+}
+
+impl WebhookDispatcher {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            max_attempts: WEBHOOK_MAX_ATTEMPTS,
+            retry_delay: WEBHOOK_RETRY_DELAY,
+        }
+    }
+
+    /// 自定义重试间隔（测试用）
+    pub fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// 投递一次事件，非 2xx 或请求失败时重试，返回实际尝试次数
+    pub async fn deliver(
+        &self,
+        webhook: &Webhook,
+        event: WebhookEvent,
+        conversation_id: &str,
+        data: &serde_json::Value,
+    ) -> Result<u32> {
+        let timestamp = Utc::now().timestamp();
+        let payload = WebhookPayload {
+            timestamp,
+            sign: sign(&webhook.config.secret, timestamp),
+            event,
+            conversation_id: conversation_id.to_string(),
+            data: data.clone(),
+        };
+
+        let mut last_error = String::new();
+        for attempt in 1..=self.max_attempts {
+            match self.client.post(&webhook.config.url).json(&payload).send().await {
+                Ok(response) if response.status().is_success() => return Ok(attempt),
+                Ok(response) => last_error = format!("HTTP {}", response.status()),
+                Err(e) => last_error = e.to_string(),
+            }
+            if attempt < self.max_attempts {
+                tokio::time::sleep(self.retry_delay).await;
+            }
+        }
+
+        Err(ImError::Other(format!(
+            "Webhook {} delivery failed after {} attempts: {}",
+            webhook.id, self.max_attempts, last_error
+        )))
+    }
+
+    /// 在后台任务中投递到所有订阅了该事件的 Webhook
+    pub fn dispatch(
+        &self,
+        webhooks: Vec<Webhook>,
+        event: WebhookEvent,
+        conversation_id: &str,
+        data: serde_json::Value,
+    ) {
+        for webhook in webhooks.into_iter().filter(|w| w.subscribes_to(event)) {
+            let dispatcher = self.clone();
+            let conversation_id = conversation_id.to_string();
+            let data = data.clone();
+            tokio::spawn(async move {
+                if let Err(e) = dispatcher.deliver(&webhook, event, &conversation_id, &data).await {
+                    warn!("{}", e);
+                }
+            });
+        }
+    }
+}
+
+impl Default for WebhookDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// 依次返回给定状态码的最小 HTTP 服务器，记录收到的请求体
+    async fn serve(statuses: Vec<u16>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let bodies = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&bodies);
+
+        tokio::spawn(async move {
+            for status in statuses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().to_string()))
+                            .and_then(|v| v.parse::<usize>().ok())
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            recorded.lock().unwrap().push(body.to_string());
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+                let response = format!("HTTP/1.1 {} X\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", status);
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        (url, bodies)
+    }
+
+    fn webhook(url: String) -> Webhook {
+        Webhook {
+            id: "wh-1".to_string(),
+            config: ImWebhookConfig {
+                url,
+                secret: "s3cret".to_string(),
+                events: vec![WebhookEvent::NewMessage],
+            },
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_sign() {
+        // 与飞书文档的算法一致：密钥为 "timestamp\nsecret"，对空串做 HMAC
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"1700000000\ns3cret").unwrap();
+        mac.update(b"");
+        let expected = base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes());
+        assert_eq!(sign("s3cret", 1_700_000_000), expected);
+        assert_ne!(sign("other", 1_700_000_000), expected);
+    }
+
+    #[tokio::test]
+    async fn test_deliver_retries() {
+        let (url, bodies) = serve(vec![500, 502, 200]).await;
+        let dispatcher = WebhookDispatcher::new().with_retry_delay(Duration::from_millis(10));
+        let data = serde_json::json!({ "text": "hi" });
+
+        let attempts = dispatcher
+            .deliver(&webhook(url), WebhookEvent::NewMessage, "conv-1", &data)
+            .await
+            .unwrap();
+        assert_eq!(attempts, 3);
+
+        let bodies = bodies.lock().unwrap();
+        let payload: WebhookPayload = serde_json::from_str(&bodies[0]).unwrap();
+        assert_eq!(payload.event, WebhookEvent::NewMessage);
+        assert_eq!(payload.sign, sign("s3cret", payload.timestamp));
+        assert_eq!(payload.data["text"], "hi");
+
+        let (url, _) = serve(vec![500, 500, 500]).await;
+        assert!(dispatcher
+            .deliver(&webhook(url), WebhookEvent::NewMessage, "conv-1", &data)
+            .await
+            .is_err());
+    }
+}
@@ -6,7 +6,7 @@ use std::sync::Arc;
 use tempfile::TempDir;
 
 use im_skill::{
-    ImSkill, ImDatabase, SessionManager, MessageManager, ImMessageSearch,
+    ImSkill, ImDatabase, SessionManager, MessageManager, ImMessageSearch, FtsTokenizerConfig,
     types::*,
     message::SendOptions,
 };
@@ -29,7 +29,7 @@ async fn create_test_session(
     let name = match session_type {
         ConversationType::Direct => None,
         ConversationType::Group => Some("Test Group".to_string()),
-        ConversationType::Channel => Some("Test Channel".to_string()),
+        ConversationType::Channel { .. } => Some("Test Channel".to_string()),
     };
 
     skill.create_conversation(session_type, name, participants).await.unwrap()
@@ -191,10 +191,11 @@ async fn test_session_types() {
 
     let channel = create_test_session(
         &skill,
-        ConversationType::Channel,
+        ConversationType::Channel { topic: "News".to_string(), is_public: true },
         vec!["owner".to_string()],
     ).await;
-    assert_eq!(channel.conversation_type, ConversationType::Channel);
+    assert!(channel.conversation_type.is_channel());
+    assert_eq!(channel.admins, vec!["owner"]);
 }
 
 #[tokio::test]
@@ -293,7 +294,9 @@ async fn test_concurrent_messaging() {
 async fn test_semantic_search_placeholder() {
     let (skill, db, _temp) = setup_test_env().await;
     let msg_manager = Arc::new(MessageManager::new(db.clone()));
-    let search = ImMessageSearch::new(db, msg_manager);
+    let search = ImMessageSearch::new(db, msg_manager, FtsTokenizerConfig::default())
+        .await
+        .unwrap();
 
     // 创建会话和消息
     let session = create_test_session(
@@ -374,7 +377,7 @@ async fn test_session_persistence() {
     // 创建第二个实例，验证数据持久化
     {
         let skill = ImSkill::new(&db_path).unwrap();
-        let sessions = skill.list_conversations("user1").await.unwrap();
+        let sessions = skill.list_conversations("user1", vec![]).await.unwrap();
         
         assert_eq!(sessions.len(), 1);
         
@@ -4,8 +4,18 @@
 //! 注意: CIS 核心禁止云端同步，此 skill 用于:
 //! 1. 向外部系统发送通知（webhook）
 //! 2. 与其他 CIS 节点 P2P 通信（元数据同步）
+//!
+//! 投递失败的消息可写入 [`retry_queue::RetryQueue`]，按指数退避重试。
+//! Skill 导出函数共用一个带重试队列的客户端，队列位于
+//! [`retry_queue::default_path`]，`cis push status` 读取同一数据库。
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use retry_queue::{unix_now, QueueStats, RetryConfig, RetryQueue};
 
 /// 推送目标
 #[derive(Debug, Clone)]
@@ -39,21 +49,111 @@ pub struct PushResult {
 }
 
 pub struct PushClient {
-    targets: Vec<PushTarget>,
+    targets: RwLock<Vec<PushTarget>>,
+    retry_queue: Option<RetryQueue>,
 }
 
 impl PushClient {
     pub fn new() -> Self {
-        Self { targets: vec![] }
+        Self { targets: RwLock::new(vec![]), retry_queue: None }
     }
     
-    pub fn add_target(&mut self, target: PushTarget) {
-        self.targets.push(target);
+    /// 投递失败的消息写入重试队列
+    pub fn with_retry_queue(mut self, queue: RetryQueue) -> Self {
+        self.retry_queue = Some(queue);
+        self
+    }
+    
+    /// 注册推送目标（重试线程运行期间也可添加）
+    pub fn add_target(&self, target: PushTarget) {
+        self.targets
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(target);
     }
     
     /// 推送消息（通过 host 执行实际网络操作）
+    ///
+    /// 失败且配置了重试队列时，消息进入队列等待后台重试。
     pub fn push(&self, msg: &PushMessage) -> PushResult {
-        let target = match self.targets.iter().find(|t| t.id == msg.target_id) {
+        let result = self.deliver(msg);
+        if !result.success {
+            if let Some(queue) = &self.retry_queue {
+                if let Err(e) = queue.enqueue(msg, unix_now()) {
+                    self.host_log(&format!("[Push to {}] failed to queue retry: {}", msg.target_id, e));
+                }
+            }
+        }
+        result
+    }
+    
+    /// 处理到期的重试消息，返回本次成功投递的条数
+    pub fn process_retry_queue(&self) -> usize {
+        self.process_retry_queue_at(unix_now())
+    }
+    
+    fn process_retry_queue_at(&self, now: u64) -> usize {
+        let Some(queue) = &self.retry_queue else {
+            return 0;
+        };
+        let due = match queue.due(now) {
+            Ok(due) => due,
+            Err(e) => {
+                self.host_log(&format!("[Push] failed to read retry queue: {}", e));
+                return 0;
+            }
+        };
+        
+        let mut delivered = 0;
+        for entry in due {
+            let outcome = if queue.is_expired(&entry, now) {
+                queue.mark_failed(entry.id)
+            } else if self.deliver(&entry.message).success {
+                delivered += 1;
+                queue.mark_delivered(entry.id)
+            } else {
+                queue.reschedule(entry.id, entry.attempt_count + 1, now)
+            };
+            if let Err(e) = outcome {
+                self.host_log(&format!("[Push] failed to update retry queue: {}", e));
+            }
+        }
+        delivered
+    }
+    
+    /// 启动后台线程，每隔 `interval` 处理一次重试队列
+    pub fn spawn_retry_worker(self: Arc<Self>, interval: Duration) -> RetryWorker {
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&stop);
+        let handle = std::thread::spawn(move || {
+            while !flag.load(Ordering::Acquire) {
+                self.process_retry_queue();
+                std::thread::park_timeout(interval);
+            }
+        });
+        RetryWorker { stop, handle: Some(handle) }
+    }
+    
+    /// 重试队列统计；未配置队列时全为 0
+    pub fn queue_stats(&self) -> QueueStats {
+        let Some(queue) = &self.retry_queue else {
+            return QueueStats::default();
+        };
+        queue.stats().unwrap_or_else(|e| {
+            self.host_log(&format!("[Push] failed to read retry queue: {}", e));
+            QueueStats::default()
+        })
+    }
+    
+    fn deliver(&self, msg: &PushMessage) -> PushResult {
+        let target = self
+            .targets
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|t| t.id == msg.target_id)
+            .cloned();
+        let target = match target {
             Some(t) => t,
             None => {
                 return PushResult {
@@ -66,9 +166,9 @@ impl PushClient {
         
         // 调用 host 执行推送
         match target.target_type {
-            TargetType::Webhook => self.push_webhook(target, msg),
-            TargetType::P2PNode => self.push_p2p(target, msg),
-            TargetType::Log => self.push_log(target, msg),
+            TargetType::Webhook => self.push_webhook(&target, msg),
+            TargetType::P2PNode => self.push_p2p(&target, msg),
+            TargetType::Log => self.push_log(&target, msg),
         }
     }
     
@@ -118,6 +218,280 @@ impl Default for PushClient {
     fn default() -> Self { Self::new() }
 }
 
+/// 重试后台线程句柄，`stop` 或 drop 时结束线程
+pub struct RetryWorker {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl RetryWorker {
+    /// 通知线程退出并等待结束
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+    
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for RetryWorker {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// 投递失败消息的持久化重试队列
+///
+/// 第 n 次失败后等待 `min(2^(n-1), max_delay_secs)` 秒再重试（1s, 2s, 4s, ...），
+/// 超过 `max_age_secs` 仍未送达的消息标记为 failed 并不再重试。
+pub mod retry_queue {
+    use std::path::{Path, PathBuf};
+    use std::sync::{Mutex, MutexGuard};
+    use std::time::{SystemTime, UNIX_EPOCH};
+    
+    use rusqlite::{params, Connection};
+    
+    use super::PushMessage;
+    
+    pub use rusqlite::Error;
+    pub type Result<T> = std::result::Result<T, Error>;
+    
+    const STATUS_PENDING: &str = "pending";
+    const STATUS_DELIVERED: &str = "delivered";
+    const STATUS_FAILED: &str = "failed";
+    
+    /// 重试队列数据库文件名
+    pub const RETRY_QUEUE_DB: &str = "push_retry.db";
+    
+    /// 默认队列路径：`$CIS_DATA_DIR/push_retry.db`，未设置时为 `~/.cis/push_retry.db`
+    pub fn default_path() -> PathBuf {
+        let data_dir = std::env::var_os("CIS_DATA_DIR")
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME")
+                    .or_else(|| std::env::var_os("USERPROFILE"))
+                    .map(|home| PathBuf::from(home).join(".cis"))
+            })
+            .unwrap_or_else(|| PathBuf::from(".cis"));
+        data_dir.join(RETRY_QUEUE_DB)
+    }
+    
+    /// SQLite INTEGER 为 i64，超出范围的时间戳截断到 `i64::MAX`
+    fn sql_ts(secs: u64) -> i64 {
+        i64::try_from(secs).unwrap_or(i64::MAX)
+    }
+    
+    /// 重试配置
+    #[derive(Debug, Clone, Copy)]
+    pub struct RetryConfig {
+        /// 退避上限（秒）
+        pub max_delay_secs: u64,
+        /// 消息最长保留时间（秒），从首次入队算起
+        pub max_age_secs: u64,
+    }
+    
+    impl Default for RetryConfig {
+        fn default() -> Self {
+            Self {
+                max_delay_secs: 300,
+                max_age_secs: 24 * 3600,
+            }
+        }
+    }
+    
+    impl RetryConfig {
+        /// 第 `attempt` 次失败后的等待时间（秒）
+        pub fn backoff_secs(&self, attempt: u32) -> u64 {
+            let exp = attempt.saturating_sub(1).min(63);
+            (1u64 << exp).min(self.max_delay_secs)
+        }
+    }
+    
+    /// 队列统计
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct QueueStats {
+        /// 等待重试
+        pub pending: u64,
+        /// 超过最长保留时间而放弃
+        pub failed: u64,
+        /// 重试成功
+        pub delivered: u64,
+    }
+    
+    /// 队列中的一条消息
+    #[derive(Debug, Clone)]
+    pub struct QueuedMessage {
+        pub id: i64,
+        pub message: PushMessage,
+        /// 已失败的投递次数
+        pub attempt_count: u32,
+        pub next_retry_at: u64,
+        pub original_created_at: u64,
+    }
+    
+    /// SQLite 重试队列
+    pub struct RetryQueue {
+        conn: Mutex<Connection>,
+        config: RetryConfig,
+    }
+    
+    impl RetryQueue {
+        /// 打开（必要时创建）数据库
+        pub fn open<P: AsRef<Path>>(path: P, config: RetryConfig) -> Result<Self> {
+            Self::init(Connection::open(path)?, config)
+        }
+        
+        /// 内存数据库（测试用）
+        pub fn open_in_memory(config: RetryConfig) -> Result<Self> {
+            Self::init(Connection::open_in_memory()?, config)
+        }
+        
+        fn init(conn: Connection, config: RetryConfig) -> Result<Self> {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS push_retry_queue (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    target_id TEXT NOT NULL,
+                    payload BLOB NOT NULL,
+                    headers TEXT NOT NULL,
+                    attempt_count INTEGER NOT NULL,
+                    next_retry_at INTEGER NOT NULL,
+                    original_created_at INTEGER NOT NULL,
+                    status TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_push_retry_due
+                    ON push_retry_queue(status, next_retry_at);",
+            )?;
+            Ok(Self {
+                conn: Mutex::new(conn),
+                config,
+            })
+        }
+        
+        pub fn config(&self) -> &RetryConfig {
+            &self.config
+        }
+        
+        fn conn(&self) -> MutexGuard<'_, Connection> {
+            // 连接本身不会因 panic 处于不一致状态，直接沿用
+            self.conn.lock().unwrap_or_else(|e| e.into_inner())
+        }
+        
+        /// 记录一次失败的投递，返回队列 ID
+        pub fn enqueue(&self, msg: &PushMessage, now: u64) -> Result<i64> {
+            let headers = serde_json::to_string(&msg.headers)
+                .map_err(|e| Error::ToSqlConversionFailure(Box::new(e)))?;
+            let conn = self.conn();
+            conn.execute(
+                "INSERT INTO push_retry_queue
+                    (target_id, payload, headers, attempt_count, next_retry_at, original_created_at, status)
+                 VALUES (?1, ?2, ?3, 1, ?4, ?5, ?6)",
+                params![
+                    msg.target_id,
+                    msg.payload,
+                    headers,
+                    sql_ts(now.saturating_add(self.config.backoff_secs(1))),
+                    sql_ts(now),
+                    STATUS_PENDING
+                ],
+            )?;
+            Ok(conn.last_insert_rowid())
+        }
+        
+        /// 到期（`next_retry_at <= now`）的待重试消息
+        pub fn due(&self, now: u64) -> Result<Vec<QueuedMessage>> {
+            let conn = self.conn();
+            let mut stmt = conn.prepare(
+                "SELECT id, target_id, payload, headers, attempt_count, next_retry_at, original_created_at
+                 FROM push_retry_queue
+                 WHERE status = ?1 AND next_retry_at <= ?2
+                 ORDER BY next_retry_at",
+            )?;
+            let rows = stmt.query_map(params![STATUS_PENDING, sql_ts(now)], |row| {
+                let headers: String = row.get(3)?;
+                Ok(QueuedMessage {
+                    id: row.get(0)?,
+                    message: PushMessage {
+                        target_id: row.get(1)?,
+                        payload: row.get(2)?,
+                        headers: serde_json::from_str(&headers).map_err(|e| {
+                            Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e))
+                        })?,
+                    },
+                    attempt_count: row.get(4)?,
+                    next_retry_at: row.get::<_, i64>(5)? as u64,
+                    original_created_at: row.get::<_, i64>(6)? as u64,
+                })
+            })?;
+            rows.collect()
+        }
+        
+        /// 是否超过最长保留时间
+        pub fn is_expired(&self, entry: &QueuedMessage, now: u64) -> bool {
+            now.saturating_sub(entry.original_created_at) > self.config.max_age_secs
+        }
+        
+        /// 再次失败，按 `attempt_count` 计算下次重试时间
+        pub fn reschedule(&self, id: i64, attempt_count: u32, now: u64) -> Result<()> {
+            self.conn().execute(
+                "UPDATE push_retry_queue SET attempt_count = ?1, next_retry_at = ?2 WHERE id = ?3",
+                params![
+                    attempt_count,
+                    sql_ts(now.saturating_add(self.config.backoff_secs(attempt_count))),
+                    id
+                ],
+            )?;
+            Ok(())
+        }
+        
+        pub fn mark_delivered(&self, id: i64) -> Result<()> {
+            self.set_status(id, STATUS_DELIVERED)
+        }
+        
+        pub fn mark_failed(&self, id: i64) -> Result<()> {
+            self.set_status(id, STATUS_FAILED)
+        }
+        
+        fn set_status(&self, id: i64, status: &str) -> Result<()> {
+            self.conn().execute(
+                "UPDATE push_retry_queue SET status = ?1 WHERE id = ?2",
+                params![status, id],
+            )?;
+            Ok(())
+        }
+        
+        /// 按状态统计
+        pub fn stats(&self) -> Result<QueueStats> {
+            let conn = self.conn();
+            let mut stmt = conn.prepare("SELECT status, COUNT(*) FROM push_retry_queue GROUP BY status")?;
+            let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+            
+            let mut stats = QueueStats::default();
+            for row in rows {
+                let (status, count) = row?;
+                match status.as_str() {
+                    STATUS_PENDING => stats.pending = count as u64,
+                    STATUS_DELIVERED => stats.delivered = count as u64,
+                    STATUS_FAILED => stats.failed = count as u64,
+                    _ => {}
+                }
+            }
+            Ok(stats)
+        }
+    }
+    
+    pub(crate) fn unix_now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+}
+
 // ==================== AgentFlow 迁移的代码 ====================
 
 /// 从 AgentFlow push/client.rs 迁移的收据系统
@@ -172,20 +546,212 @@ pub mod types {
     }
 }
 
-// WASM 导出
+// ==================== Skill 导出 ====================
+
+/// 后台重试间隔，与最小退避（1s）一致
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Skill 导出函数共用的客户端及其重试线程
+struct SkillState {
+    client: Arc<PushClient>,
+    _worker: Option<RetryWorker>,
+}
+
+static SKILL: OnceLock<SkillState> = OnceLock::new();
+
+/// 打开默认重试队列并启动后台重试；队列不可用时退化为不重试的客户端
+fn skill_state() -> &'static SkillState {
+    SKILL.get_or_init(|| {
+        let path = retry_queue::default_path();
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        match RetryQueue::open(&path, RetryConfig::default()) {
+            Ok(queue) => {
+                let client = Arc::new(PushClient::new().with_retry_queue(queue));
+                let worker = Arc::clone(&client).spawn_retry_worker(RETRY_INTERVAL);
+                SkillState { client, _worker: Some(worker) }
+            }
+            Err(e) => {
+                eprintln!("[Push] failed to open retry queue {}: {}", path.display(), e);
+                SkillState { client: Arc::new(PushClient::new()), _worker: None }
+            }
+        }
+    })
+}
+
+/// `skill_add_target` 输入
+#[derive(serde::Deserialize)]
+struct TargetSpec {
+    id: String,
+    endpoint: String,
+    /// webhook | p2p | log
+    target_type: String,
+}
+
+fn parse_target(input: &[u8]) -> Option<PushTarget> {
+    let spec: TargetSpec = serde_json::from_slice(input).ok()?;
+    let target_type = match spec.target_type.as_str() {
+        "webhook" => TargetType::Webhook,
+        "p2p" => TargetType::P2PNode,
+        "log" => TargetType::Log,
+        _ => return None,
+    };
+    Some(PushTarget { id: spec.id, endpoint: spec.endpoint, target_type })
+}
+
+/// `skill_push` 输入
+#[derive(serde::Deserialize)]
+struct MessageSpec {
+    target_id: String,
+    payload: String,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+}
+
+fn parse_message(input: &[u8]) -> Option<PushMessage> {
+    let spec: MessageSpec = serde_json::from_slice(input).ok()?;
+    Some(PushMessage {
+        target_id: spec.target_id,
+        payload: spec.payload.into_bytes(),
+        headers: spec.headers,
+    })
+}
+
+/// 读取 host 传入的输入缓冲区
+///
+/// # Safety
+///
+/// 非空的 `ptr` 必须指向 `len` 个有效字节
+unsafe fn input_slice<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    if ptr.is_null() || len == 0 {
+        return None;
+    }
+    Some(std::slice::from_raw_parts(ptr, len))
+}
+
+/// 初始化共享客户端：打开重试队列并启动后台重试线程
+///
+/// 返回 0 成功，-1 表示重试队列不可用（推送仍可用，但失败消息不会重试）
 #[no_mangle]
 pub extern "C" fn skill_init() -> i32 {
-    0
+    if skill_state().client.retry_queue.is_some() {
+        0
+    } else {
+        -1
+    }
 }
 
 /// 添加推送目标
+/// 输入: JSON { "id": "...", "endpoint": "...", "target_type": "webhook|p2p|log" }
+///
+/// # Safety
+///
+/// The caller must ensure that `json_ptr` is valid and points to `len` bytes.
 #[no_mangle]
-pub extern "C" fn skill_add_target(_json_ptr: *const u8, _len: usize) -> i32 {
+pub unsafe extern "C" fn skill_add_target(json_ptr: *const u8, len: usize) -> i32 {
+    let Some(target) = input_slice(json_ptr, len).and_then(parse_target) else {
+        return -1;
+    };
+    skill_state().client.add_target(target);
     0
 }
 
-/// 执行推送
+/// 执行推送，失败的消息进入重试队列
+/// 输入: JSON { "target_id": "...", "payload": "...", "headers": { ... } }
+///
+/// 返回 0 送达，1 投递失败（已入重试队列），-1 输入无效
+///
+/// # Safety
+///
+/// The caller must ensure that `json_ptr` is valid and points to `len` bytes.
 #[no_mangle]
-pub extern "C" fn skill_push(_json_ptr: *const u8, _len: usize) -> i32 {
-    0
+pub unsafe extern "C" fn skill_push(json_ptr: *const u8, len: usize) -> i32 {
+    let Some(msg) = input_slice(json_ptr, len).and_then(parse_message) else {
+        return -1;
+    };
+    if skill_state().client.push(&msg).success {
+        0
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::retry_queue::RetryConfig;
+    use super::*;
+    
+    fn message(target_id: &str) -> PushMessage {
+        PushMessage {
+            target_id: target_id.to_string(),
+            payload: b"hello".to_vec(),
+            headers: HashMap::from([("x-trace".to_string(), "1".to_string())]),
+        }
+    }
+    
+    #[test]
+    fn test_backoff() {
+        let config = RetryConfig { max_delay_secs: 10, max_age_secs: 60 };
+        let delays: Vec<u64> = (1..=6).map(|n| config.backoff_secs(n)).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 10, 10]);
+    }
+    
+    #[test]
+    fn test_retry_queue() {
+        let config = RetryConfig { max_delay_secs: 10, max_age_secs: 60 };
+        let client = PushClient::new().with_retry_queue(RetryQueue::open_in_memory(config).unwrap());
+        
+        // 目标尚未注册，投递失败后入队
+        assert!(!client.push(&message("node-b")).success);
+        assert_eq!(client.queue_stats().pending, 1);
+        
+        let queue = client.retry_queue.as_ref().unwrap();
+        let created = queue.due(u64::MAX).unwrap()[0].original_created_at;
+        assert!(queue.due(created).unwrap().is_empty());
+        
+        // 第一次重试仍失败，退避到 2 秒
+        assert_eq!(client.process_retry_queue_at(created + 1), 0);
+        let entry = client.retry_queue.as_ref().unwrap().due(u64::MAX).unwrap().remove(0);
+        assert_eq!(entry.attempt_count, 2);
+        assert_eq!(entry.next_retry_at, created + 3);
+        assert_eq!(entry.message.headers["x-trace"], "1");
+        
+        client.add_target(PushTarget {
+            id: "node-b".to_string(),
+            endpoint: "log".to_string(),
+            target_type: TargetType::Log,
+        });
+        assert_eq!(client.process_retry_queue_at(created + 3), 1);
+        
+        // 超过 max_age 的消息直接放弃
+        assert!(!client.push(&message("node-c")).success);
+        assert_eq!(client.process_retry_queue_at(created + 120), 0);
+        
+        assert_eq!(client.queue_stats(), QueueStats { pending: 0, failed: 1, delivered: 1 });
+    }
+    
+    #[test]
+    fn test_retry_queue_far_future_timestamps() {
+        let queue = RetryQueue::open_in_memory(RetryConfig::default()).unwrap();
+        queue.enqueue(&message("node-b"), u64::MAX).unwrap();
+        
+        // 超出 i64 的时间戳截断而不是回绕成负数
+        let entry = queue.due(u64::MAX).unwrap().remove(0);
+        assert_eq!(entry.original_created_at, i64::MAX as u64);
+        assert!(queue.due(0).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_parse_skill_input() {
+        let target = parse_target(br#"{"id":"node-b","endpoint":"log","target_type":"p2p"}"#).unwrap();
+        assert_eq!(target.id, "node-b");
+        assert!(matches!(target.target_type, TargetType::P2PNode));
+        assert!(parse_target(br#"{"id":"x","endpoint":"y","target_type":"fax"}"#).is_none());
+        
+        let msg = parse_message(br#"{"target_id":"node-b","payload":"hi","headers":{"x-trace":"1"}}"#).unwrap();
+        assert_eq!(msg.payload, b"hi");
+        assert_eq!(msg.headers["x-trace"], "1");
+        assert!(parse_message(b"not json").is_none());
+    }
 }